@@ -0,0 +1,270 @@
+//! Benchmarks of core algorithms and storages over generated graphs.
+//!
+//! For how to run them and compare against a baseline checkout the [`harness`] module.
+mod harness;
+
+use harness::Harness;
+use prepona::algo::{
+    Bfs, BfsListener, ConnectedComponents, Dfs, DfsListener, Dijkstra, IsomorphismType, TarjanSCC,
+    VF2Isomorphism,
+};
+use prepona::gen::random::{random_connected, random_tree};
+use prepona::graph::{DefaultEdge, ListGraph, SimpleGraph, UndirectedEdge};
+use prepona::io::synthetic::{grid, sbm, torus};
+use prepona::misc::rng::Rng;
+use prepona::prelude::*;
+use prepona::provide::IdMap;
+use prepona::storage::{DiList, GraphStorage, List, Map, Mat};
+
+// Listens to nothing, so traversals are measured on their own.
+struct Quiet;
+
+impl BfsListener for Quiet {}
+
+impl DfsListener for Quiet {}
+
+fn main() {
+    let mut harness = Harness::from_args();
+
+    traversal(&mut harness);
+    shortest_paths(&mut harness);
+    components(&mut harness);
+    isomorphism(&mut harness);
+    id_map(&mut harness);
+    storage_mutation(&mut harness);
+
+    harness.finish();
+}
+
+// # Returns
+// A random connected graph with `vertex_count` vertices and about four times as many edges.
+fn sparse_random<S>(
+    mut storage: S,
+    vertex_count: usize,
+) -> SimpleGraph<usize, DefaultEdge<usize>, UndirectedEdge, S>
+where
+    S: GraphStorage<usize, DefaultEdge<usize>, UndirectedEdge>,
+{
+    random_connected(
+        &mut storage,
+        vertex_count,
+        3 * vertex_count,
+        &mut Rng::seed_from(1),
+    );
+
+    SimpleGraph::init(storage)
+}
+
+fn traversal(harness: &mut Harness) {
+    let mut storage = List::<usize>::init();
+    torus(&mut storage, 150, 150);
+    let list_torus = ListGraph::init(storage);
+    let list_random = sparse_random(List::<usize>::init(), 20_000);
+    let map_random = sparse_random(Map::<usize>::init(), 20_000);
+    let mat_random = sparse_random(Mat::<usize>::init(), 2_000);
+
+    harness.bench("traversal/bfs/list_torus_150x150", || {
+        Bfs::init(&list_torus, &mut Quiet).execute(&list_torus)
+    });
+    harness.bench("traversal/bfs/list_random_20000", || {
+        Bfs::init(&list_random, &mut Quiet).execute(&list_random)
+    });
+    harness.bench("traversal/bfs/map_random_20000", || {
+        Bfs::init(&map_random, &mut Quiet).execute(&map_random)
+    });
+    harness.bench("traversal/bfs/mat_random_2000", || {
+        Bfs::init(&mat_random, &mut Quiet).execute(&mat_random)
+    });
+
+    harness.bench("traversal/dfs/list_torus_150x150", || {
+        Dfs::init(&list_torus, &mut Quiet).execute(&list_torus)
+    });
+    harness.bench("traversal/dfs/list_random_20000", || {
+        Dfs::init(&list_random, &mut Quiet).execute(&list_random)
+    });
+    harness.bench("traversal/dfs/map_random_20000", || {
+        Dfs::init(&map_random, &mut Quiet).execute(&map_random)
+    });
+}
+
+fn shortest_paths(harness: &mut Harness) {
+    let mut storage = List::<usize>::init();
+    let ids = torus(&mut storage, 150, 150);
+    let list_torus = ListGraph::init(storage);
+    let list_random = sparse_random(List::<usize>::init(), 20_000);
+    let map_random = sparse_random(Map::<usize>::init(), 20_000);
+
+    harness.bench("shortest_paths/dijkstra/list_torus_150x150", || {
+        Dijkstra::init(&list_torus).execute(&list_torus, ids[0][0])
+    });
+    harness.bench("shortest_paths/dijkstra/list_random_20000", || {
+        Dijkstra::init(&list_random).execute(&list_random, 0)
+    });
+    harness.bench("shortest_paths/dijkstra/map_random_20000", || {
+        Dijkstra::init(&map_random).execute(&map_random, 0)
+    });
+}
+
+fn components(harness: &mut Harness) {
+    // Communities are strongly connected with overwhelming probability and only a few edges go between them.
+    let mut storage = DiList::<usize>::init();
+    sbm(
+        &mut storage,
+        &[200; 20],
+        0.02,
+        0.0002,
+        &mut Rng::seed_from(2),
+    );
+    let directed_sbm = ListGraph::init(storage);
+
+    let mut storage = List::<usize>::init();
+    sbm(
+        &mut storage,
+        &[1000; 10],
+        0.005,
+        0.0,
+        &mut Rng::seed_from(3),
+    );
+    let undirected_sbm = ListGraph::init(storage);
+
+    harness.bench("components/tarjan/list_sbm_20x200", || {
+        TarjanSCC::init(&directed_sbm).execute(&directed_sbm)
+    });
+    harness.bench("components/connected/list_sbm_10x1000", || {
+        ConnectedComponents::init(&undirected_sbm).execute(&undirected_sbm)
+    });
+}
+
+fn isomorphism(harness: &mut Harness) {
+    // Host is a grid and pattern is a square, so every cell of the grid matches in 8 ways.
+    let mut storage = List::<usize>::init();
+    grid(&mut storage, 30, 30);
+    let host = ListGraph::init(storage);
+    let mut storage = List::<usize>::init();
+    grid(&mut storage, 2, 2);
+    let square = ListGraph::init(storage);
+
+    // Same random tree with shuffled vertex ids.
+    let mut rng = Rng::seed_from(4);
+    let mut storage = List::<usize>::init();
+    random_tree(&mut storage, 300, &mut rng);
+    let tree = ListGraph::init(storage);
+    let mut permutation: Vec<usize> = (0..300).collect();
+    rng.shuffle(&mut permutation);
+    let mut storage = List::<usize>::init();
+    storage.extend_with_vertices(300);
+    for (src_id, dst_id, _) in tree.edges() {
+        storage.add_edge_unchecked(permutation[src_id], permutation[dst_id], 1.into());
+    }
+    let shuffled_tree = ListGraph::init(storage);
+
+    harness.bench("isomorphism/vf2_monomorphism/square_in_grid_30x30", || {
+        VF2Isomorphism::init(&host, &square, IsomorphismType::Monomorphism)
+            .mappings()
+            .count()
+    });
+    harness.bench("isomorphism/vf2_graph/random_tree_300", || {
+        VF2Isomorphism::init(&tree, &shuffled_tree, IsomorphismType::Graph).execute()
+    });
+}
+
+fn id_map(harness: &mut Harness) {
+    // Removing every third vertex leaves scattered ids, which can not use the identity map.
+    let mut storage = List::<usize>::init();
+    storage.extend_with_vertices(100_000);
+    storage.retain_vertices(|vertex_id| vertex_id % 3 != 0);
+    let scattered = ListGraph::init(storage);
+    let vertices = scattered.vertices();
+
+    harness.bench("id_map/new/scattered_100000", || IdMap::new(&scattered));
+
+    let id_map = IdMap::new(&scattered);
+    harness.bench("id_map/virt_id_of/scattered_100000", || {
+        vertices
+            .iter()
+            .map(|vertex_id| id_map.virt_id_of(*vertex_id))
+            .sum::<usize>()
+    });
+}
+
+// # Returns
+// `edge_count` random pairs of distinct vertices in range 0..`vertex_count`.
+fn random_pairs(vertex_count: usize, edge_count: usize) -> Vec<(usize, usize)> {
+    let mut rng = Rng::seed_from(5);
+
+    (0..edge_count)
+        .map(|_| {
+            let src_id = rng.next_below(vertex_count);
+            let dst_id = (src_id + 1 + rng.next_below(vertex_count - 1)) % vertex_count;
+
+            (src_id, dst_id)
+        })
+        .collect()
+}
+
+fn mutate_storage<S, F>(harness: &mut Harness, name: &str, init: F, vertex_count: usize)
+where
+    S: GraphStorage<usize, DefaultEdge<usize>, UndirectedEdge>,
+    F: Fn() -> S,
+{
+    let pairs = random_pairs(vertex_count, 4 * vertex_count);
+
+    let filled = || {
+        let mut storage = init();
+        storage.extend_with_vertices(vertex_count);
+        let edge_ids: Vec<usize> = pairs
+            .iter()
+            .map(|(src_id, dst_id)| storage.add_edge_unchecked(*src_id, *dst_id, 1.into()))
+            .collect();
+
+        (storage, edge_ids)
+    };
+
+    harness.bench(&format!("storage/add_vertices/{}", name), || {
+        let mut storage = init();
+        for _ in 0..vertex_count {
+            storage.add_vertex();
+        }
+        storage
+    });
+    harness.bench_with_setup(
+        &format!("storage/add_edges/{}", name),
+        || {
+            let mut storage = init();
+            storage.extend_with_vertices(vertex_count);
+            storage
+        },
+        |mut storage| {
+            for (src_id, dst_id) in &pairs {
+                storage.add_edge_unchecked(*src_id, *dst_id, 1.into());
+            }
+            storage
+        },
+    );
+    harness.bench_with_setup(
+        &format!("storage/remove_edges/{}", name),
+        filled,
+        |(mut storage, edge_ids)| {
+            for ((src_id, dst_id), edge_id) in pairs.iter().zip(edge_ids) {
+                storage.remove_edge_unchecked(*src_id, *dst_id, edge_id);
+            }
+            storage
+        },
+    );
+    harness.bench_with_setup(
+        &format!("storage/remove_vertices/{}", name),
+        filled,
+        |(mut storage, _)| {
+            for vertex_id in (0..vertex_count).step_by(10) {
+                storage.remove_vertex_unchecked(vertex_id);
+            }
+            storage
+        },
+    );
+}
+
+fn storage_mutation(harness: &mut Harness) {
+    mutate_storage(harness, "list_10000", List::<usize>::init, 10_000);
+    mutate_storage(harness, "map_10000", Map::<usize>::init, 10_000);
+    mutate_storage(harness, "mat_1000", Mat::<usize>::init, 1_000);
+}
@@ -0,0 +1,276 @@
+//! A small benchmark harness in the spirit of [criterion](https://github.com/bheisler/criterion.rs), built only on `std`.
+//!
+//! Each benchmark is warmed up first, then timed over a fixed number of samples.
+//! Iterations per sample are chosen so that each sample takes roughly [`SAMPLE_TIME`].
+//! The median time of one iteration is reported along with the fastest and slowest samples.
+//!
+//! # Usage
+//! ```text
+//! # Run every benchmark.
+//! cargo bench --bench core
+//!
+//! # Run benchmarks whose name contains "dijkstra".
+//! cargo bench --bench core -- dijkstra
+//!
+//! # Store medians as a baseline named "main" in target/bench-baselines/main.txt.
+//! cargo bench --bench core -- --save-baseline main
+//!
+//! # Compare against the "main" baseline and fail if any benchmark got more than 10% slower.
+//! cargo bench --bench core -- --baseline main --threshold 0.1
+//! ```
+//!
+//! To catch regressions before a release, save a baseline on the last released commit, then run the comparison on the release candidate.
+//! Benchmarks that are missing from the baseline are reported but never fail the run.
+//!
+//! Without the `--bench` flag that `cargo bench` passes, each benchmark runs once as a smoke test.
+//! This keeps `cargo test --benches` quick while still checking that every benchmark works.
+use std::collections::HashMap;
+use std::fs;
+use std::hint::black_box;
+use std::path::PathBuf;
+use std::process;
+use std::time::{Duration, Instant};
+
+/// Time spent running a benchmark before measuring it.
+pub const WARM_UP_TIME: Duration = Duration::from_millis(300);
+
+/// Approximate time of each sample.
+pub const SAMPLE_TIME: Duration = Duration::from_millis(50);
+
+/// Number of samples taken from each benchmark.
+pub const SAMPLE_COUNT: usize = 20;
+
+/// Relative slowdown of the median that counts as a regression, unless `--threshold` is passed.
+pub const DEFAULT_THRESHOLD: f64 = 0.1;
+
+/// Runs benchmarks, reports their timings and compares them against a baseline.
+pub struct Harness {
+    filter: Option<String>,
+    measure: bool,
+    save_baseline: Option<String>,
+    baseline: Option<String>,
+    threshold: f64,
+    medians: Vec<(String, Duration)>,
+}
+
+impl Harness {
+    /// Initializes the harness from command line arguments.
+    ///
+    /// # Panics
+    /// If a flag is missing its value or `--threshold` is not a number.
+    pub fn from_args() -> Self {
+        let mut harness = Harness {
+            filter: None,
+            measure: false,
+            save_baseline: None,
+            baseline: None,
+            threshold: DEFAULT_THRESHOLD,
+            medians: vec![],
+        };
+
+        let mut args = std::env::args().skip(1);
+        while let Some(arg) = args.next() {
+            let mut value_of = |flag: &str| {
+                args.next()
+                    .unwrap_or_else(|| panic!("{} must be followed by a value", flag))
+            };
+
+            match arg.as_str() {
+                "--bench" => harness.measure = true,
+                "--save-baseline" => harness.save_baseline = Some(value_of(&arg)),
+                "--baseline" => harness.baseline = Some(value_of(&arg)),
+                "--threshold" => {
+                    harness.threshold = value_of(&arg)
+                        .parse()
+                        .expect("--threshold must be a number")
+                }
+                // Flags of the default test harness that cargo may pass are ignored.
+                _ if arg.starts_with("--") => {}
+                _ => harness.filter = Some(arg),
+            }
+        }
+
+        harness
+    }
+
+    /// # Arguments
+    /// `name`: Name of a benchmark.
+    ///
+    /// # Returns
+    /// `true` if the benchmark passes the filter given on the command line.
+    pub fn is_selected(&self, name: &str) -> bool {
+        self.filter
+            .as_ref()
+            .is_none_or(|filter| name.contains(filter.as_str()))
+    }
+
+    /// Times `routine`.
+    ///
+    /// # Arguments
+    /// * `name`: Unique name of the benchmark, in the form of `group/algorithm/input`.
+    /// * `routine`: Code to measure. Its output is passed through [`black_box`] so it's not optimized away.
+    pub fn bench<R, F>(&mut self, name: &str, mut routine: F)
+    where
+        F: FnMut() -> R,
+    {
+        self.bench_with_setup(name, || (), |_| routine())
+    }
+
+    /// Times `routine` on inputs made by `setup`. Only `routine` is measured.
+    ///
+    /// Use it for benchmarks that consume or mutate their input, like adding edges to a storage.
+    ///
+    /// # Arguments
+    /// * `name`: Unique name of the benchmark, in the form of `group/algorithm/input`.
+    /// * `setup`: Makes a fresh input for each iteration.
+    /// * `routine`: Code to measure.
+    pub fn bench_with_setup<T, R, S, F>(&mut self, name: &str, mut setup: S, mut routine: F)
+    where
+        S: FnMut() -> T,
+        F: FnMut(T) -> R,
+    {
+        if !self.is_selected(name) {
+            return;
+        }
+
+        if !self.measure {
+            black_box(routine(setup()));
+            println!("{:<50} ok", name);
+            return;
+        }
+
+        // Times `iterations` runs of the routine, leaving out the setup.
+        let mut sample = |iterations: usize| {
+            let inputs: Vec<T> = (0..iterations).map(|_| setup()).collect();
+
+            let start = Instant::now();
+            for input in inputs {
+                black_box(routine(input));
+            }
+            start.elapsed()
+        };
+
+        let mut warm_up_iterations = 0;
+        let mut warm_up_elapsed = Duration::ZERO;
+        while warm_up_elapsed < WARM_UP_TIME {
+            warm_up_elapsed += sample(1);
+            warm_up_iterations += 1;
+        }
+
+        let per_iteration = warm_up_elapsed / warm_up_iterations;
+        let iterations = (SAMPLE_TIME.as_nanos() / per_iteration.as_nanos().max(1)).max(1) as usize;
+
+        let mut samples: Vec<Duration> = (0..SAMPLE_COUNT)
+            .map(|_| sample(iterations) / iterations as u32)
+            .collect();
+        samples.sort_unstable();
+
+        let median = samples[SAMPLE_COUNT / 2];
+        println!(
+            "{:<50} median: {:>10}   [{} .. {}]   {} x {} iterations",
+            name,
+            format_duration(median),
+            format_duration(samples[0]),
+            format_duration(samples[SAMPLE_COUNT - 1]),
+            SAMPLE_COUNT,
+            iterations
+        );
+
+        self.medians.push((name.to_string(), median));
+    }
+
+    /// Compares medians against the baseline and stores them as a new baseline, as requested on the command line.
+    ///
+    /// Exits the process with a non-zero code if any benchmark regressed beyond the threshold.
+    pub fn finish(self) {
+        let mut regressions = vec![];
+
+        if let Some(baseline) = &self.baseline {
+            let old_medians = read_baseline(baseline);
+
+            println!("\nComparison with baseline {}:", baseline);
+            for (name, median) in &self.medians {
+                match old_medians.get(name) {
+                    Some(old_median) => {
+                        let change = median.as_secs_f64() / old_median.as_secs_f64() - 1.0;
+                        let verdict = if change > self.threshold {
+                            regressions.push(name.clone());
+                            "REGRESSED"
+                        } else if change < -self.threshold {
+                            "improved"
+                        } else {
+                            "unchanged"
+                        };
+
+                        println!("{:<50} {:>+8.2}%   {}", name, change * 100.0, verdict);
+                    }
+                    None => println!("{:<50} {:>9}   not in baseline", name, "-"),
+                }
+            }
+        }
+
+        if let Some(baseline) = &self.save_baseline {
+            write_baseline(baseline, &self.medians);
+        }
+
+        if !regressions.is_empty() {
+            eprintln!(
+                "\n{} benchmark(s) got more than {:.0}% slower: {}",
+                regressions.len(),
+                self.threshold * 100.0,
+                regressions.join(", ")
+            );
+            process::exit(1);
+        }
+    }
+}
+
+fn baseline_path(baseline: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("target")
+        .join("bench-baselines")
+        .join(format!("{}.txt", baseline))
+}
+
+// Each line of a baseline file is in the form of: (name) (median in nanoseconds)
+fn read_baseline(baseline: &str) -> HashMap<String, Duration> {
+    let path = baseline_path(baseline);
+    let text = fs::read_to_string(&path)
+        .unwrap_or_else(|err| panic!("Can not read baseline {}: {}", path.display(), err));
+
+    text.lines()
+        .filter_map(|line| {
+            let (name, nanos) = line.rsplit_once(' ')?;
+
+            Some((name.to_string(), Duration::from_nanos(nanos.parse().ok()?)))
+        })
+        .collect()
+}
+
+fn write_baseline(baseline: &str, medians: &[(String, Duration)]) {
+    let path = baseline_path(baseline);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+
+    let text: String = medians
+        .iter()
+        .map(|(name, median)| format!("{} {}\n", name, median.as_nanos()))
+        .collect();
+    fs::write(&path, text)
+        .unwrap_or_else(|err| panic!("Can not write baseline {}: {}", path.display(), err));
+
+    println!("\nSaved baseline to {}", path.display());
+}
+
+fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos() as f64;
+
+    if nanos < 1e3 {
+        format!("{:.0} ns", nanos)
+    } else if nanos < 1e6 {
+        format!("{:.2} µs", nanos / 1e3)
+    } else if nanos < 1e9 {
+        format!("{:.2} ms", nanos / 1e6)
+    } else {
+        format!("{:.2} s", nanos / 1e9)
+    }
+}
@@ -0,0 +1,279 @@
+/// An algorithm that can be run on graphs of type `G`.
+///
+/// Algorithms are executed in different ways: some are structures that are initialized and then executed,
+/// some are free functions and some take extra arguments like a source vertex.
+/// This trait gives them all the same interface, so code that runs, times, logs or caches algorithms can be written once and used with any of them.
+///
+/// Structures that are initialized from a graph only keep their configuration(like the orientation of [`ConnectedComponents`](crate::algo::ConnectedComponents))
+/// when used as an `Algorithm`. Everything else is initialized again from the graph each algorithm is run on, so the same value can be run on many graphs.
+///
+/// Any closure that takes a reference to the graph is also an `Algorithm`.
+/// Use one to adapt free functions and algorithms that need more than the graph, like the source vertex of [`Dijkstra`](crate::algo::Dijkstra).
+///
+/// ## Generic Parameters
+/// * `G`: **G**raph type.
+/// * `M`: **M**arker that distinguishes implementations of the same algorithm for different graphs.
+///   Algorithms that work with any weight, edge or direction use a tuple of them, like `(W, E, Dir)`, because they can not be deduced from `G` alone.
+///   Closures use `()`. Code that is generic over algorithms should be generic over `M` too.
+///
+/// # Examples
+/// ```
+/// use std::time::{Duration, Instant};
+///
+/// use prepona::prelude::*;
+/// use prepona::storage::DiList;
+/// use prepona::graph::ListGraph;
+/// use prepona::algo::{Algorithm, Dijkstra, MapOutput, TarjanSCC, TopologicalSort};
+///
+/// // A wrapper that works with every algorithm.
+/// fn timed<'a, G, M, A: Algorithm<G, M>>(algorithm: &A, graph: &'a G) -> (A::Output<'a>, Duration) {
+///     let start = Instant::now();
+///     let output = algorithm.run(graph);
+///
+///     (output, start.elapsed())
+/// }
+///
+/// // Given: Graph
+/// //
+/// //      a  -->  b  -->  c
+/// //
+/// let mut graph = ListGraph::init(DiList::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+///
+/// // When: Running a structure, a closure and a pipeline through the same wrapper.
+/// let (sorted_ids, _) = timed(&TopologicalSort::init(), &graph);
+/// let (distance, _) = timed(
+///     &|graph: &ListGraph<usize, DirectedEdge>| Dijkstra::init(graph).execute(graph, a).distance_to(c),
+///     &graph,
+/// );
+/// let scc_count = MapOutput::init(TarjanSCC::init(&graph), |sccs: Vec<Vec<usize>>| sccs.len());
+/// let (scc_count, _) = timed(&scc_count, &graph);
+///
+/// // Then:
+/// assert_eq!(sorted_ids, vec![a, b, c]);
+/// assert_eq!(distance, Some(2.into()));
+/// assert_eq!(scc_count, 3);
+/// ```
+pub trait Algorithm<G, M = ()> {
+    /// Result of running the algorithm. It may borrow from the graph.
+    type Output<'a>
+    where
+        G: 'a,
+        M: 'a;
+
+    /// # Arguments
+    /// `graph`: Graph to run the algorithm on.
+    ///
+    /// # Returns
+    /// Result of the algorithm on `graph`.
+    fn run<'a>(&self, graph: &'a G) -> Self::Output<'a>;
+}
+
+impl<G, O, F> Algorithm<G> for F
+where
+    F: Fn(&G) -> O,
+{
+    type Output<'a>
+        = O
+    where
+        G: 'a;
+
+    fn run(&self, graph: &G) -> O {
+        self(graph)
+    }
+}
+
+/// An algorithm whose output is piped into a function, so algorithms can be composed into pipelines.
+pub struct MapOutput<A, F> {
+    algorithm: A,
+    f: F,
+}
+
+impl<A, F> MapOutput<A, F> {
+    /// # Arguments
+    /// * `algorithm`: Algorithm to run first.
+    /// * `f`: Function that computes the new output from the output of `algorithm`.
+    pub fn init(algorithm: A, f: F) -> Self {
+        MapOutput { algorithm, f }
+    }
+}
+
+impl<G, M, O, A, F> Algorithm<G, M> for MapOutput<A, F>
+where
+    A: Algorithm<G, M>,
+    F: for<'a> Fn(A::Output<'a>) -> O,
+{
+    type Output<'a>
+        = O
+    where
+        G: 'a,
+        M: 'a;
+
+    fn run(&self, graph: &G) -> O {
+        (self.f)(self.algorithm.run(graph))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::{
+        is_dag, ConnectedComponents, FloydWarshall, HasCycle, Kruskal, Orientation, TarjanSCC,
+        TopologicalSort, VertexEdgeCut,
+    };
+    use crate::graph::{DirectedEdge, Edge, ListGraph, UndirectedEdge};
+    use crate::provide::{Edges, Graph, Vertices};
+    use crate::storage::{DiList, List};
+
+    // Runs `algorithm` twice, so reusing it on another graph can be checked too.
+    fn run_twice<'a, G, M, A: Algorithm<G, M>>(
+        algorithm: &A,
+        first: &'a G,
+        second: &'a G,
+    ) -> (A::Output<'a>, A::Output<'a>) {
+        (algorithm.run(first), algorithm.run(second))
+    }
+
+    fn sorted(mut components: Vec<Vec<usize>>) -> Vec<Vec<usize>> {
+        components
+            .iter_mut()
+            .for_each(|component| component.sort_unstable());
+        components.sort_unstable();
+
+        components
+    }
+
+    #[test]
+    fn directed_algorithms_run_like_execute() {
+        // Given: Graphs
+        //
+        //      a  -->  b  -->  c           a  -->  b  <--> c
+        //
+        let mut path = ListGraph::init(DiList::<usize>::init());
+        let mut cyclic = ListGraph::init(DiList::<usize>::init());
+        for graph in [&mut path, &mut cyclic] {
+            let a = graph.add_vertex();
+            let b = graph.add_vertex();
+            let c = graph.add_vertex();
+            graph.add_edge_unchecked(a, b, 1.into());
+            graph.add_edge_unchecked(b, c, 2.into());
+        }
+        cyclic.add_edge_unchecked(2, 1, 3.into());
+
+        // When:
+        let (path_sort, cyclic_sort) = run_twice(&TopologicalSort::init(), &path, &cyclic);
+        let (path_sccs, cyclic_sccs) = run_twice(&TarjanSCC::init(&path), &path, &cyclic);
+        let (path_cycle, cyclic_cycle) = run_twice(&HasCycle::init(&path), &path, &cyclic);
+        let (path_distances, cyclic_distances) = run_twice(&FloydWarshall::init(), &path, &cyclic);
+        let (path_is_dag, cyclic_is_dag) = run_twice(
+            &|graph: &ListGraph<usize, DirectedEdge>| is_dag(graph),
+            &path,
+            &cyclic,
+        );
+
+        // Then:
+        assert_eq!(path_sort, TopologicalSort::init().execute(&path));
+        assert_eq!(cyclic_sort, TopologicalSort::init().execute(&cyclic));
+        assert_eq!(sorted(path_sccs), vec![vec![0], vec![1], vec![2]]);
+        assert_eq!(sorted(cyclic_sccs), vec![vec![0], vec![1, 2]]);
+        assert!(path_cycle.is_none());
+        let cycle_vertices = cyclic_cycle.unwrap().vertices();
+        assert!([1, 2]
+            .iter()
+            .all(|vertex_id| cycle_vertices.contains(vertex_id)));
+        assert_eq!(path_distances.unwrap()[&(0, 2)], 3.into());
+        assert_eq!(cyclic_distances.unwrap()[&(2, 1)], 3.into());
+        assert!(path_is_dag);
+        assert!(!cyclic_is_dag);
+    }
+
+    #[test]
+    fn undirected_algorithms_run_like_execute() {
+        // Given: Graphs
+        //
+        //      a  ---  b  ---  c           a  ---  b  ---  c
+        //                                  |               |
+        //                                  '---------------'
+        //
+        let mut path = ListGraph::init(List::<usize>::init());
+        let mut triangle = ListGraph::init(List::<usize>::init());
+        for graph in [&mut path, &mut triangle] {
+            let a = graph.add_vertex();
+            let b = graph.add_vertex();
+            let c = graph.add_vertex();
+            graph.add_edge_unchecked(a, b, 1.into());
+            graph.add_edge_unchecked(b, c, 2.into());
+        }
+        triangle.add_edge_unchecked(2, 0, 3.into());
+
+        // When:
+        let (path_ccs, triangle_ccs) =
+            run_twice(&ConnectedComponents::init(&path), &path, &triangle);
+        let (path_mst, triangle_mst) = run_twice(&Kruskal::init(&path), &path, &triangle);
+        let ((path_cut_vertices, path_cut_edges), (triangle_cut_vertices, triangle_cut_edges)) =
+            run_twice(&VertexEdgeCut::init(&path), &path, &triangle);
+
+        // Then:
+        assert_eq!(sorted(path_ccs), vec![vec![0, 1, 2]]);
+        assert_eq!(sorted(triangle_ccs), vec![vec![0, 1, 2]]);
+        assert_eq!(path_mst.edges_count(), 2);
+        assert_eq!(triangle_mst.edges_count(), 2);
+        assert!(triangle_mst
+            .edges()
+            .iter()
+            .all(|(_, _, edge)| edge.get_weight().unwrap() < 3));
+        assert_eq!(path_cut_vertices, vec![1]);
+        assert_eq!(path_cut_edges.len(), 2);
+        assert!(triangle_cut_vertices.is_empty());
+        assert!(triangle_cut_edges.is_empty());
+    }
+
+    #[test]
+    fn configuration_is_kept() {
+        // Given: Graph
+        //
+        //      a  -->  b       c
+        //
+        let mut graph = ListGraph::init(DiList::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+
+        // When: Running connected components that ignore direction of edges.
+        let algorithm = ConnectedComponents::init_with_orientation(&graph, Orientation::Ignore);
+        let ccs = algorithm.run(&graph);
+
+        // Then:
+        assert_eq!(sorted(ccs), vec![vec![0, 1], vec![2]]);
+    }
+
+    #[test]
+    fn map_output_pipes_outputs() {
+        // Given: Graph
+        //
+        //      a  ---  b       c  ---  d       e
+        //
+        let mut graph = ListGraph::init(List::<usize>::init());
+        let ids: Vec<usize> = (0..5).map(|_| graph.add_vertex()).collect();
+        graph.add_edge_unchecked(ids[0], ids[1], 1.into());
+        graph.add_edge_unchecked(ids[2], ids[3], 1.into());
+
+        // When: Counting components, then checking whether there are more than two vertices.
+        let count = MapOutput::init(ConnectedComponents::init(&graph), |ccs: Vec<Vec<usize>>| {
+            ccs.len()
+        });
+        let has_many_vertices = MapOutput::init(
+            |graph: &ListGraph<usize, UndirectedEdge>| graph.vertex_count(),
+            |count: usize| count > 2,
+        );
+
+        // Then:
+        assert_eq!(count.run(&graph), 3);
+        assert!(has_many_vertices.run(&graph));
+    }
+}
@@ -0,0 +1,410 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::graph::{Edge, UndirectedEdge};
+use crate::provide::{Edges, Graph, Vertices};
+
+/// Solves the assignment problem using the Hungarian algorithm: Assigns rows of a cost matrix to distinct columns so the total cost is minimum.
+///
+/// If there are more columns than rows every row is assigned, otherwise every column is assigned.
+///
+/// # Arguments
+/// `cost_matrix`: Cost of assigning each row to each column. All rows must have the same length.
+///
+/// # Returns
+/// (assignment, total cost)
+/// * Assigned pairs in the format of (row, column), sorted by row.
+/// * Sum of the costs of the assigned pairs.
+///
+/// # Complexity
+/// O(n<sup>2</sup> * m), where n is the smaller and m is the larger dimension of the matrix.
+///
+/// # Panics
+/// * If rows of the matrix have different lengths.
+/// * If any of the costs is not finite.
+///
+/// # Examples
+/// ```
+/// use prepona::algo::hungarian;
+///
+/// // Given: Cost of each worker doing each job.
+/// let costs = vec![
+///     vec![4.0, 1.0, 3.0],
+///     vec![2.0, 0.0, 5.0],
+///     vec![3.0, 2.0, 2.0],
+/// ];
+///
+/// // When:
+/// let (assignment, cost) = hungarian(&costs);
+///
+/// // Then:
+/// assert_eq!(assignment, vec![(0, 1), (1, 0), (2, 2)]);
+/// assert_eq!(cost, 5.0);
+/// ```
+pub fn hungarian(cost_matrix: &[Vec<f64>]) -> (Vec<(usize, usize)>, f64) {
+    let row_count = cost_matrix.len();
+    let column_count = cost_matrix.first().map_or(0, |row| row.len());
+
+    for row in cost_matrix.iter() {
+        assert_eq!(
+            row.len(),
+            column_count,
+            "All rows of the cost matrix must have the same length"
+        );
+        assert!(
+            row.iter().all(|cost| cost.is_finite()),
+            "Costs must be finite"
+        );
+    }
+
+    if row_count == 0 || column_count == 0 {
+        return (vec![], 0.0);
+    }
+
+    // The algorithm assigns every row, so it needs at least as many columns as rows.
+    let mut assignment = if row_count <= column_count {
+        assign_rows(row_count, column_count, |row, column| {
+            cost_matrix[row][column]
+        })
+    } else {
+        assign_rows(column_count, row_count, |column, row| {
+            cost_matrix[row][column]
+        })
+        .into_iter()
+        .map(|(column, row)| (row, column))
+        .collect()
+    };
+    assignment.sort_unstable();
+
+    let cost = assignment
+        .iter()
+        .map(|(row, column)| cost_matrix[*row][*column])
+        .sum();
+
+    (assignment, cost)
+}
+
+/// Finds a minimum cost matching between two sides of a bipartite graph.
+///
+/// Among the matchings with the largest possible number of edges, the one with the minimum total weight is returned.
+/// Vertices that are not in `top_vertices` form the other side. Edges between vertices of the same side are ignored,
+/// and among parallel edges only the one with the minimum weight is used.
+///
+/// # Arguments
+/// * `graph`: Bipartite graph.
+/// * `weight_fn`: Maps each edge to the cost of matching its end points.
+/// * `top_vertices`: Ids of the vertices on one side of the graph. Ids that are not in the graph are ignored.
+///
+/// # Returns
+/// (matching, total cost)
+/// * Matched edges in the format of (`top_id`, `bottom_id`, `edge_id`), sorted.
+/// * Sum of the weights of the matched edges.
+///
+/// # Complexity
+/// O(n<sup>2</sup> * m + |E|), where n and m are the sizes of the smaller and the larger side.
+///
+/// # Panics
+/// If weight of any edge is not finite.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::List;
+/// use prepona::graph::ListGraph;
+/// use prepona::algo::min_cost_assignment;
+///
+/// // Given: Graph
+/// //
+/// //      a  --1--  x
+/// //        \
+/// //         --2--  y  --9--  b
+/// //
+/// let mut graph = ListGraph::init(List::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let x = graph.add_vertex();
+/// let y = graph.add_vertex();
+/// let ax = graph.add_edge_unchecked(a, x, 1.into());
+/// let ay = graph.add_edge_unchecked(a, y, 2.into());
+/// let by = graph.add_edge_unchecked(b, y, 9.into());
+///
+/// // When:
+/// let (matching, cost) = min_cost_assignment(&graph, |edge| edge.get_weight().unwrap() as f64, &[a, b]);
+///
+/// // Then: Matching both a and b is preferred over the cheaper a - x.
+/// assert_eq!(matching, vec![(a, x, ax), (b, y, by)]);
+/// assert_eq!(cost, 10.0);
+/// ```
+pub fn min_cost_assignment<W, E, G, F>(
+    graph: &G,
+    weight_fn: F,
+    top_vertices: &[usize],
+) -> (Vec<(usize, usize, usize)>, f64)
+where
+    E: Edge<W>,
+    G: Vertices + Edges<W, E> + Graph<W, E, UndirectedEdge>,
+    F: Fn(&E) -> f64,
+{
+    let top_set: HashSet<usize> = top_vertices
+        .iter()
+        .copied()
+        .filter(|vertex_id| graph.contains_vertex(*vertex_id))
+        .collect();
+    let mut tops: Vec<usize> = top_set.iter().copied().collect();
+    tops.sort_unstable();
+    let bottoms: Vec<usize> = graph
+        .vertices()
+        .into_iter()
+        .filter(|vertex_id| !top_set.contains(vertex_id))
+        .collect();
+    let bottom_indices: HashMap<usize, usize> = bottoms
+        .iter()
+        .enumerate()
+        .map(|(index, vertex_id)| (*vertex_id, index))
+        .collect();
+
+    // Cheapest edge between each pair of top and bottom vertices.
+    let mut cheapest: Vec<HashMap<usize, (f64, usize)>> = vec![HashMap::new(); tops.len()];
+    for (top_index, top_id) in tops.iter().enumerate() {
+        for (dst_id, edge) in graph.edges_from_unchecked(*top_id) {
+            if let Some(bottom_index) = bottom_indices.get(&dst_id) {
+                let weight = weight_fn(edge);
+                assert!(weight.is_finite(), "Weights must be finite");
+
+                let entry = cheapest[top_index]
+                    .entry(*bottom_index)
+                    .or_insert((weight, edge.get_id()));
+                if weight < entry.0 {
+                    *entry = (weight, edge.get_id());
+                }
+            }
+        }
+    }
+
+    // Missing edges cost more than any matching of existing edges, so the number of matched edges is maximized first.
+    let penalty = 1.0
+        + 2.0
+            * cheapest
+                .iter()
+                .flat_map(|edges| edges.values())
+                .map(|(weight, _)| weight.abs())
+                .sum::<f64>();
+
+    let cost_matrix: Vec<Vec<f64>> = (0..tops.len())
+        .map(|top_index| {
+            (0..bottoms.len())
+                .map(|bottom_index| {
+                    cheapest[top_index]
+                        .get(&bottom_index)
+                        .map_or(penalty, |(weight, _)| *weight)
+                })
+                .collect()
+        })
+        .collect();
+
+    let (assignment, _) = hungarian(&cost_matrix);
+
+    let mut matching = vec![];
+    let mut cost = 0.0;
+    for (top_index, bottom_index) in assignment {
+        if let Some((weight, edge_id)) = cheapest[top_index].get(&bottom_index) {
+            matching.push((tops[top_index], bottoms[bottom_index], *edge_id));
+            cost += weight;
+        }
+    }
+    matching.sort_unstable();
+
+    (matching, cost)
+}
+
+// Assigns each of the rows to a distinct column, using potentials on rows and columns.
+// Requires `row_count` <= `column_count`.
+fn assign_rows<F>(row_count: usize, column_count: usize, cost: F) -> Vec<(usize, usize)>
+where
+    F: Fn(usize, usize) -> f64,
+{
+    // Rows and columns are indexed from 1, and column 0 is a virtual column that holds the row being assigned.
+    let mut row_potentials = vec![0.0; row_count + 1];
+    let mut column_potentials = vec![0.0; column_count + 1];
+    let mut row_of_column = vec![0; column_count + 1];
+    let mut previous_column = vec![0; column_count + 1];
+
+    for row in 1..=row_count {
+        row_of_column[0] = row;
+        let mut column = 0;
+        let mut min_slacks = vec![f64::INFINITY; column_count + 1];
+        let mut is_used = vec![false; column_count + 1];
+
+        // Grow a tree of tight edges until it reaches a free column.
+        loop {
+            is_used[column] = true;
+            let current_row = row_of_column[column];
+            let mut delta = f64::INFINITY;
+            let mut next_column = 0;
+
+            for other_column in 1..=column_count {
+                if is_used[other_column] {
+                    continue;
+                }
+
+                let slack = cost(current_row - 1, other_column - 1)
+                    - row_potentials[current_row]
+                    - column_potentials[other_column];
+                if slack < min_slacks[other_column] {
+                    min_slacks[other_column] = slack;
+                    previous_column[other_column] = column;
+                }
+                if min_slacks[other_column] < delta {
+                    delta = min_slacks[other_column];
+                    next_column = other_column;
+                }
+            }
+
+            for other_column in 0..=column_count {
+                if is_used[other_column] {
+                    row_potentials[row_of_column[other_column]] += delta;
+                    column_potentials[other_column] -= delta;
+                } else {
+                    min_slacks[other_column] -= delta;
+                }
+            }
+
+            column = next_column;
+            if row_of_column[column] == 0 {
+                break;
+            }
+        }
+
+        // Flip the assignments along the path to the free column.
+        while column != 0 {
+            let previous = previous_column[column];
+            row_of_column[column] = row_of_column[previous];
+            column = previous;
+        }
+    }
+
+    (1..=column_count)
+        .filter(|column| row_of_column[*column] != 0)
+        .map(|column| (row_of_column[column] - 1, column - 1))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{ListGraph, MultiListGraph};
+    use crate::misc::rng::Rng;
+    use crate::storage::List;
+
+    // Minimum cost of assigning each row to a distinct column, by trying every choice.
+    fn brute_force(cost_matrix: &[Vec<f64>], row: usize, used: &mut Vec<bool>) -> f64 {
+        if row == cost_matrix.len() {
+            return 0.0;
+        }
+
+        let mut best = f64::INFINITY;
+        for column in 0..used.len() {
+            if !used[column] {
+                used[column] = true;
+                best = best.min(cost_matrix[row][column] + brute_force(cost_matrix, row + 1, used));
+                used[column] = false;
+            }
+        }
+        best
+    }
+
+    #[test]
+    fn empty_matrix() {
+        assert_eq!(hungarian(&[]), (vec![], 0.0));
+        assert_eq!(hungarian(&[vec![], vec![]]), (vec![], 0.0));
+    }
+
+    #[test]
+    fn rectangular_matrices() {
+        // Given: Matrix with more columns than rows, and its transpose.
+        let wide = vec![vec![5.0, 1.0, 7.0, 2.0], vec![6.0, 2.0, 3.0, 9.0]];
+        let tall: Vec<Vec<f64>> = (0..4)
+            .map(|column| wide.iter().map(|row| row[column]).collect())
+            .collect();
+
+        // When:
+        let (wide_assignment, wide_cost) = hungarian(&wide);
+        let (tall_assignment, tall_cost) = hungarian(&tall);
+
+        // Then:
+        assert_eq!(wide_assignment, vec![(0, 1), (1, 2)]);
+        assert_eq!(wide_cost, 4.0);
+        assert_eq!(tall_assignment, vec![(1, 0), (2, 1)]);
+        assert_eq!(tall_cost, 4.0);
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let mut rng = Rng::seed_from(9);
+        for _ in 0..100 {
+            // Given: Random matrix with negative costs.
+            let row_count = 1 + rng.next_below(5);
+            let column_count = row_count + rng.next_below(3);
+            let cost_matrix: Vec<Vec<f64>> = (0..row_count)
+                .map(|_| {
+                    (0..column_count)
+                        .map(|_| rng.next_below(20) as f64 - 5.0)
+                        .collect()
+                })
+                .collect();
+
+            // When:
+            let (assignment, cost) = hungarian(&cost_matrix);
+
+            // Then:
+            assert_eq!(assignment.len(), row_count);
+            let columns: HashSet<usize> = assignment.iter().map(|(_, column)| *column).collect();
+            assert_eq!(columns.len(), row_count);
+            assert_eq!(
+                cost,
+                brute_force(&cost_matrix, 0, &mut vec![false; column_count])
+            );
+        }
+    }
+
+    #[test]
+    fn assignment_in_graph_with_parallel_edges() {
+        // Given: Graph in which c has no neighbor, a - x has a cheaper parallel edge and x - y is ignored.
+        let mut graph = MultiListGraph::init(List::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let x = graph.add_vertex();
+        let y = graph.add_vertex();
+        graph.add_edge_unchecked(a, x, 5.into());
+        let cheap_ax = graph.add_edge_unchecked(a, x, 1.into());
+        graph.add_edge_unchecked(b, x, 1.into());
+        let by = graph.add_edge_unchecked(b, y, 3.into());
+        graph.add_edge_unchecked(x, y, 0.into());
+
+        // When:
+        let (matching, cost) = min_cost_assignment(
+            &graph,
+            |edge| edge.get_weight().unwrap() as f64,
+            &[c, b, a, 100],
+        );
+
+        // Then:
+        assert_eq!(matching, vec![(a, x, cheap_ax), (b, y, by)]);
+        assert_eq!(cost, 4.0);
+    }
+
+    #[test]
+    fn assignment_without_edges() {
+        // Given:
+        let mut graph = ListGraph::init(List::<usize>::init());
+        let a = graph.add_vertex();
+        graph.add_vertex();
+
+        // When:
+        let (matching, cost) = min_cost_assignment(&graph, |_| 1.0, &[a]);
+
+        // Then:
+        assert!(matching.is_empty());
+        assert_eq!(cost, 0.0);
+    }
+}
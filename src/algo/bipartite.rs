@@ -0,0 +1,198 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::graph::{ListGraph, UndirectedEdge};
+use crate::provide::{Graph, Neighbors, Vertices};
+use crate::storage::List;
+
+/// Decides the weight of each edge in a [`projection`](crate::algo::bipartite::project).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Weighting {
+    /// Every edge has weight 1.
+    Unweighted,
+
+    /// Weight of each edge is the number of bottom vertices that its end points share.
+    Count,
+
+    /// Newman's collaboration weight: Each shared bottom vertex with degree d adds 1 / (d - 1) to the weight.
+    /// So a bottom vertex connecting many top vertices counts less for each pair of them.
+    Newman,
+}
+
+/// Computes the one mode projection of a bipartite graph onto its top vertices:
+/// A graph over the top vertices, where two vertices are connected if they share a neighbor among the bottom vertices.
+///
+/// The graph is treated as undirected, so neighbors of a vertex must be the same as its incident vertices.
+/// Edges between two top vertices or two bottom vertices are ignored.
+///
+/// # Arguments
+/// * `graph`: Bipartite graph to project.
+/// * `top_vertices`: Vertices to project onto. Every other vertex of `graph` is a bottom vertex.
+/// * `weighting`: Decides the weight of each edge.
+///
+/// # Returns
+/// Graph with one edge between each pair of top vertices that share a bottom vertex.
+/// Vertices of the projection have the same ids as the top vertices that are present in `graph`.
+///
+/// # Complexity
+/// O(|V| + |E| + sum of squared degrees of the bottom vertices)
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::List;
+/// use prepona::graph::ListGraph;
+/// use prepona::algo::bipartite::{self, Weighting};
+///
+/// // Given: Authors a, b and c, a paper p written by all of them and a paper q written by a and b.
+/// let mut graph = ListGraph::init(List::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let p = graph.add_vertex();
+/// let q = graph.add_vertex();
+/// for author in [a, b, c].iter() {
+///     graph.add_edge_unchecked(*author, p, 1.into());
+/// }
+/// graph.add_edge_unchecked(a, q, 1.into());
+/// graph.add_edge_unchecked(b, q, 1.into());
+///
+/// // When:
+/// let coauthors = bipartite::project(&graph, &[a, b, c], Weighting::Newman);
+///
+/// // Then: p adds 1/2 to each pair of its authors and q adds 1 to a - b.
+/// assert_eq!(coauthors.vertices(), vec![a, b, c]);
+/// assert_eq!(coauthors.edges_between_unchecked(a, b)[0].get_weight(), &1.5.into());
+/// assert_eq!(coauthors.edges_between_unchecked(b, c)[0].get_weight(), &0.5.into());
+/// ```
+pub fn project<G>(
+    graph: &G,
+    top_vertices: &[usize],
+    weighting: Weighting,
+) -> ListGraph<f64, UndirectedEdge>
+where
+    G: Vertices + Neighbors,
+{
+    let top_vertices: HashSet<usize> = top_vertices
+        .iter()
+        .copied()
+        .filter(|vertex_id| graph.contains_vertex(*vertex_id))
+        .collect();
+
+    let mut weights: HashMap<(usize, usize), f64> = HashMap::new();
+    for bottom_id in graph.vertices() {
+        if top_vertices.contains(&bottom_id) {
+            continue;
+        }
+
+        let mut neighbors: Vec<usize> = graph.neighbors_unchecked(bottom_id);
+        let degree = neighbors.len();
+        neighbors.retain(|vertex_id| top_vertices.contains(vertex_id));
+        neighbors.sort_unstable();
+
+        let weight = match weighting {
+            Weighting::Unweighted | Weighting::Count => 1.0,
+            Weighting::Newman => 1.0 / (degree as f64 - 1.0),
+        };
+
+        for (index, src_id) in neighbors.iter().enumerate() {
+            for dst_id in neighbors[index + 1..].iter() {
+                *weights.entry((*src_id, *dst_id)).or_insert(0.0) += weight;
+            }
+        }
+    }
+
+    // Adding every id and removing the ones that are not on top keeps the ids of the vertices.
+    let mut projection = ListGraph::init(List::init());
+    let max_id = top_vertices.iter().max().map_or(0, |max_id| max_id + 1);
+    for _ in 0..max_id {
+        projection.add_vertex();
+    }
+    for vertex_id in 0..max_id {
+        if !top_vertices.contains(&vertex_id) {
+            projection.remove_vertex_unchecked(vertex_id);
+        }
+    }
+
+    for ((src_id, dst_id), weight) in weights {
+        let weight = if weighting == Weighting::Unweighted {
+            1.0
+        } else {
+            weight
+        };
+
+        projection.add_edge_unchecked(src_id, dst_id, weight.into());
+    }
+
+    projection
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{Edge, MultiListGraph};
+    use crate::provide::Edges;
+    use crate::storage::{Bipartite, GraphStorage};
+
+    fn weight_between(graph: &ListGraph<f64, UndirectedEdge>, src_id: usize, dst_id: usize) -> f64 {
+        graph.edges_between_unchecked(src_id, dst_id)[0]
+            .get_weight()
+            .unwrap()
+    }
+
+    #[test]
+    fn weightings() {
+        // Given: Top vertices t0..t3 (t3 is isolated), bottom vertices b0 connected to t0, t1, t2 and b1 connected to t0, t1.
+        let mut graph = MultiListGraph::init(List::<usize>::init());
+        let top: Vec<_> = (0..4).map(|_| graph.add_vertex()).collect();
+        let bottom: Vec<_> = (0..2).map(|_| graph.add_vertex()).collect();
+        for (t, b) in [(0, 0), (1, 0), (2, 0), (0, 1), (1, 1)].iter() {
+            graph.add_edge_unchecked(top[*t], bottom[*b], 1.into());
+        }
+
+        // When:
+        let unweighted = project(&graph, &top, Weighting::Unweighted);
+        let count = project(&graph, &top, Weighting::Count);
+        let newman = project(&graph, &top, Weighting::Newman);
+
+        // Then:
+        for projection in [&unweighted, &count, &newman].iter() {
+            assert_eq!(projection.vertices(), top);
+            assert_eq!(projection.edges_count(), 3);
+            assert!(projection.neighbors_unchecked(top[3]).is_empty());
+        }
+        assert_eq!(weight_between(&unweighted, top[0], top[1]), 1.0);
+        assert_eq!(weight_between(&count, top[0], top[1]), 2.0);
+        assert_eq!(weight_between(&count, top[1], top[2]), 1.0);
+        assert_eq!(weight_between(&newman, top[0], top[1]), 1.5);
+        assert_eq!(weight_between(&newman, top[0], top[2]), 0.5);
+    }
+
+    #[test]
+    fn matches_bipartite_storage() {
+        // Given: Same graph in a bipartite storage and a regular graph.
+        let mut storage = Bipartite::init(List::<usize>::init());
+        let mut graph = ListGraph::init(List::<usize>::init());
+        let top: Vec<_> = (0..3).map(|_| storage.add_top_vertex()).collect();
+        let bottom: Vec<_> = (0..3).map(|_| storage.add_bottom_vertex()).collect();
+        (0..6).for_each(|_| {
+            graph.add_vertex();
+        });
+        for (t, b) in [(0, 0), (1, 0), (1, 1), (2, 1), (2, 2), (0, 2)].iter() {
+            storage.add_edge_unchecked(top[*t], bottom[*b], 1.into());
+            graph.add_edge_unchecked(top[*t], bottom[*b], 1.into());
+        }
+
+        // When:
+        let expected = storage.project_top();
+        let projection = project(&graph, &top, Weighting::Count);
+
+        // Then:
+        assert_eq!(projection.vertices(), expected.vertices());
+        for (src_id, dst_id, edge) in expected.edges() {
+            assert_eq!(
+                weight_between(&projection, src_id, dst_id),
+                edge.get_weight().unwrap() as f64
+            );
+        }
+    }
+}
@@ -0,0 +1,459 @@
+use crate::provide::{IdMap, Neighbors, Vertices};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// Adjacency matrix of a graph in which neighborhood of each vertex is stored as a fixed width bitset.
+///
+/// Row of each vertex has one bit for every vertex of the graph, indexed by virtual ids of the [`IdMap`](crate::provide::IdMap) returned by [`id_map`](crate::algo::BitAdj::id_map).
+/// Intersecting or merging two neighborhoods is done 64 vertices at a time using bitwise AND/OR on words of the rows,
+/// which makes algorithms like triangle counting, clique enumeration and transitive closure much faster on dense graphs.
+///
+/// The matrix takes |V|<sup>2</sup> bits of memory, so it's not suitable for large sparse graphs.
+/// It's a snapshot of the graph and later changes to the graph are not reflected in it.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+/// use prepona::algo::BitAdj;
+///
+/// // Given: Graph
+/// //
+/// //      a  ---  b
+/// //      |  \    |
+/// //      |    \  |
+/// //      d  ---  c
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(c, d, 1.into());
+/// graph.add_edge_unchecked(d, a, 1.into());
+/// graph.add_edge_unchecked(a, c, 1.into());
+///
+/// // When:
+/// let bit_adj = BitAdj::init(&graph);
+///
+/// // Then:
+/// assert_eq!(bit_adj.triangle_count(), 2);
+/// assert_eq!(bit_adj.maximal_cliques().len(), 2);
+/// assert!(bit_adj.has_edge(a, c));
+/// assert!(!bit_adj.has_edge(b, d));
+/// ```
+pub struct BitAdj {
+    id_map: IdMap,
+    vertex_count: usize,
+    words_per_row: usize,
+
+    // Rows are stored back to back, each one `words_per_row` words long.
+    bits: Vec<u64>,
+}
+
+impl BitAdj {
+    /// # Arguments
+    /// `graph`: Graph to build the bitset rows of.
+    ///
+    /// # Returns
+    /// Bitset adjacency matrix of `graph`.
+    ///
+    /// # Complexity
+    /// O(|V|<sup>2</sup>/64 + |E|)
+    pub fn init<G>(graph: &G) -> Self
+    where
+        G: Vertices + Neighbors,
+    {
+        let id_map = graph.continuos_id_map();
+        let vertex_count = graph.vertex_count();
+        let words_per_row = vertex_count.div_ceil(WORD_BITS);
+
+        let mut bit_adj = BitAdj {
+            id_map,
+            vertex_count,
+            words_per_row,
+            bits: vec![0; vertex_count * words_per_row],
+        };
+
+        for src_id in graph.vertices() {
+            let src_virt_id = bit_adj.id_map.virt_id_of(src_id);
+
+            for dst_id in graph.neighbors_unchecked(src_id) {
+                let dst_virt_id = bit_adj.id_map.virt_id_of(dst_id);
+                bit_adj.set(src_virt_id, dst_virt_id);
+            }
+        }
+
+        bit_adj
+    }
+
+    /// # Returns
+    /// Mapping between ids of vertices and indices of rows and bits.
+    pub fn id_map(&self) -> &IdMap {
+        &self.id_map
+    }
+
+    /// # Returns
+    /// Number of vertices, which is also the width of each row in bits.
+    pub fn vertex_count(&self) -> usize {
+        self.vertex_count
+    }
+
+    /// # Arguments
+    /// `src_id`: Id of the vertex to get its row.
+    ///
+    /// # Returns
+    /// Words of the row of the vertex. Bit `i` of the row is set if there is an edge from the vertex to the vertex with virtual id `i`.
+    ///
+    /// # Panics
+    /// If there is no vertex with id: `src_id` in the graph.
+    pub fn row(&self, src_id: usize) -> &[u64] {
+        self.row_of(self.id_map.virt_id_of(src_id))
+    }
+
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    ///
+    /// # Returns
+    /// * `true`: If there is an edge from source to destination.
+    /// * `false`: Otherwise.
+    ///
+    /// # Complexity
+    /// O(1)
+    ///
+    /// # Panics
+    /// If there is no vertex with id: `src_id` or `dst_id` in the graph.
+    pub fn has_edge(&self, src_id: usize, dst_id: usize) -> bool {
+        self.get(
+            self.id_map.virt_id_of(src_id),
+            self.id_map.virt_id_of(dst_id),
+        )
+    }
+
+    /// # Arguments
+    /// `src_id`: Id of the source vertex.
+    ///
+    /// # Returns
+    /// Id of vertices accessible from the source vertex using one edge.
+    ///
+    /// # Complexity
+    /// O(|V|/64 + number of neighbors)
+    ///
+    /// # Panics
+    /// If there is no vertex with id: `src_id` in the graph.
+    pub fn neighbors(&self, src_id: usize) -> Vec<usize> {
+        ones(self.row(src_id))
+            .map(|virt_id| self.id_map.real_id_of(virt_id))
+            .collect()
+    }
+
+    /// Counts the triangles of an undirected graph.
+    ///
+    /// Each triangle is counted once and loops are ignored.
+    /// Rows are assumed to be symmetric, so for a directed graph the result is not meaningful.
+    ///
+    /// # Returns
+    /// Number of triangles in the graph.
+    ///
+    /// # Complexity
+    /// O(|E| * |V|/64)
+    pub fn triangle_count(&self) -> usize {
+        let mut count = 0;
+
+        for u in 0..self.vertex_count {
+            let u_row = self.row_of(u);
+
+            // Count each triangle u < v < w only once, from its edge (u, v).
+            for v in ones(u_row).filter(|v| *v > u) {
+                let v_row = self.row_of(v);
+                let first_word = (v + 1) / WORD_BITS;
+
+                for word_index in first_word..self.words_per_row {
+                    let mut common = u_row[word_index] & v_row[word_index];
+                    if word_index == first_word {
+                        common &= !0u64 << ((v + 1) % WORD_BITS);
+                    }
+
+                    count += common.count_ones() as usize;
+                }
+            }
+        }
+
+        count
+    }
+
+    /// Enumerates maximal cliques of an undirected graph using [Bron–Kerbosch](https://en.wikipedia.org/wiki/Bron%E2%80%93Kerbosch_algorithm) algorithm with pivoting.
+    ///
+    /// Candidate and excluded sets are kept as bitsets, so each step of the algorithm is a few word-parallel AND operations.
+    /// Loops are ignored and rows are assumed to be symmetric.
+    ///
+    /// # Returns
+    /// Maximal cliques of the graph. Each clique is a vector containing ids of its vertices.
+    ///
+    /// # Complexity
+    /// O(3<sup>|V|/3</sup>) in the worst case, which is the maximum number of maximal cliques a graph can have.
+    pub fn maximal_cliques(&self) -> Vec<Vec<usize>> {
+        let mut candidates = vec![0; self.words_per_row];
+        for virt_id in 0..self.vertex_count {
+            candidates[virt_id / WORD_BITS] |= 1 << (virt_id % WORD_BITS);
+        }
+
+        let mut cliques = vec![];
+        self.bron_kerbosch(
+            &mut vec![],
+            candidates,
+            vec![0; self.words_per_row],
+            &mut cliques,
+        );
+
+        cliques
+    }
+
+    fn bron_kerbosch(
+        &self,
+        clique: &mut Vec<usize>,
+        mut candidates: Vec<u64>,
+        mut excluded: Vec<u64>,
+        cliques: &mut Vec<Vec<usize>>,
+    ) {
+        if candidates
+            .iter()
+            .chain(excluded.iter())
+            .all(|word| *word == 0)
+        {
+            cliques.push(
+                clique
+                    .iter()
+                    .map(|virt_id| self.id_map.real_id_of(*virt_id))
+                    .collect(),
+            );
+            return;
+        }
+
+        // Pivot is the vertex with most neighbors among candidates, so fewer branches are explored.
+        let pivot = ones(&candidates)
+            .chain(ones(&excluded))
+            .max_by_key(|pivot| {
+                intersection(&candidates, self.row_of(*pivot))
+                    .map(|word| word.count_ones())
+                    .sum::<u32>()
+            })
+            .unwrap();
+
+        let mut branches: Vec<u64> = candidates
+            .iter()
+            .zip(self.row_of(pivot))
+            .map(|(candidate, pivot_neighbor)| candidate & !pivot_neighbor)
+            .collect();
+
+        // A loop puts the pivot in its own row, but the pivot itself must still be branched on.
+        branches[pivot / WORD_BITS] |= candidates[pivot / WORD_BITS] & (1 << (pivot % WORD_BITS));
+
+        for v in ones(&branches) {
+            let v_row = self.row_of(v);
+            let mut next_candidates: Vec<u64> = intersection(&candidates, v_row).collect();
+            let mut next_excluded: Vec<u64> = intersection(&excluded, v_row).collect();
+
+            // Loops must not make a vertex a candidate of its own clique.
+            next_candidates[v / WORD_BITS] &= !(1 << (v % WORD_BITS));
+            next_excluded[v / WORD_BITS] &= !(1 << (v % WORD_BITS));
+
+            clique.push(v);
+            self.bron_kerbosch(clique, next_candidates, next_excluded, cliques);
+            clique.pop();
+
+            candidates[v / WORD_BITS] &= !(1 << (v % WORD_BITS));
+            excluded[v / WORD_BITS] |= 1 << (v % WORD_BITS);
+        }
+    }
+
+    /// Computes the [transitive closure](https://en.wikipedia.org/wiki/Transitive_closure#In_graph_theory) of the graph using Warshall's algorithm.
+    ///
+    /// Whenever vertex `i` can reach vertex `k`, row of `k` is merged into row of `i` using word-parallel OR.
+    ///
+    /// # Returns
+    /// Bitset matrix in which there is an edge from `src` to `dst` if `dst` is reachable from `src` using one or more edges.
+    /// So a vertex has an edge to itself only if it's on a cycle.
+    ///
+    /// # Complexity
+    /// O(|V|<sup>3</sup>/64)
+    pub fn transitive_closure(mut self) -> Self {
+        for k in 0..self.vertex_count {
+            let k_row = self.row_of(k).to_vec();
+
+            for i in 0..self.vertex_count {
+                if self.get(i, k) {
+                    let start = i * self.words_per_row;
+                    self.bits[start..start + self.words_per_row]
+                        .iter_mut()
+                        .zip(k_row.iter())
+                        .for_each(|(word, k_word)| *word |= k_word);
+                }
+            }
+        }
+
+        self
+    }
+
+    fn row_of(&self, virt_id: usize) -> &[u64] {
+        let start = virt_id * self.words_per_row;
+
+        &self.bits[start..start + self.words_per_row]
+    }
+
+    fn get(&self, src_virt_id: usize, dst_virt_id: usize) -> bool {
+        self.row_of(src_virt_id)[dst_virt_id / WORD_BITS] & (1 << (dst_virt_id % WORD_BITS)) != 0
+    }
+
+    fn set(&mut self, src_virt_id: usize, dst_virt_id: usize) {
+        self.bits[src_virt_id * self.words_per_row + dst_virt_id / WORD_BITS] |=
+            1 << (dst_virt_id % WORD_BITS);
+    }
+}
+
+// Indices of the set bits of `words`, in increasing order.
+fn ones(words: &[u64]) -> impl Iterator<Item = usize> + '_ {
+    words.iter().enumerate().flat_map(|(word_index, word)| {
+        let mut word = *word;
+        std::iter::from_fn(move || {
+            if word == 0 {
+                None
+            } else {
+                let bit = word.trailing_zeros() as usize;
+                word &= word - 1;
+                Some(word_index * WORD_BITS + bit)
+            }
+        })
+    })
+}
+
+fn intersection<'a>(first: &'a [u64], second: &'a [u64]) -> impl Iterator<Item = u64> + 'a {
+    first.iter().zip(second).map(|(a, b)| a & b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{MatGraph, MultiMatGraph};
+    use crate::misc::rng::Rng;
+    use crate::provide::{Edges, Graph};
+    use crate::storage::{DiMat, Mat};
+    use std::collections::HashSet;
+
+    #[test]
+    fn triangles_and_cliques_of_complete_graph() {
+        // Given: Complete graph with 70 vertices, so rows span two words, and a loop on every vertex.
+        let mut graph = MultiMatGraph::init(Mat::<usize>::init());
+        let vertices: Vec<usize> = (0..70).map(|_| graph.add_vertex()).collect();
+        for i in 0..vertices.len() {
+            for j in i..vertices.len() {
+                graph.add_edge_unchecked(vertices[i], vertices[j], 1.into());
+            }
+        }
+
+        // When:
+        let bit_adj = BitAdj::init(&graph);
+
+        // Then:
+        assert_eq!(bit_adj.row(0).len(), 2);
+        assert_eq!(bit_adj.triangle_count(), 70 * 69 * 68 / 6);
+        let cliques = bit_adj.maximal_cliques();
+        assert_eq!(cliques.len(), 1);
+        assert_eq!(cliques[0].len(), 70);
+    }
+
+    #[test]
+    fn matches_naive_counting_on_random_graphs() {
+        let mut rng = Rng::seed_from(3094);
+
+        for _ in 0..10 {
+            // Given: Random graph with loops and a removed vertex.
+            let mut graph = MultiMatGraph::init(Mat::<usize>::init());
+            let vertices: Vec<usize> = (0..80).map(|_| graph.add_vertex()).collect();
+            for i in 0..vertices.len() {
+                for j in i..vertices.len() {
+                    if rng.next_bool(0.3) {
+                        graph.add_edge_unchecked(vertices[i], vertices[j], 1.into());
+                    }
+                }
+            }
+            graph.remove_vertex_unchecked(vertices[5]);
+            let vertices = graph.vertices();
+
+            // When:
+            let bit_adj = BitAdj::init(&graph);
+
+            // Then: Triangles match the cubic count.
+            let mut naive_count = 0;
+            for (i, u) in vertices.iter().enumerate() {
+                for (j, v) in vertices.iter().enumerate().skip(i + 1) {
+                    for w in vertices.iter().skip(j + 1) {
+                        if graph.has_any_edge_unchecked(*u, *v)
+                            && graph.has_any_edge_unchecked(*v, *w)
+                            && graph.has_any_edge_unchecked(*u, *w)
+                        {
+                            naive_count += 1;
+                        }
+                    }
+                }
+            }
+            assert_eq!(bit_adj.triangle_count(), naive_count);
+
+            // And: Every clique is complete, maximal and reported once.
+            let cliques = bit_adj.maximal_cliques();
+            let distinct: HashSet<Vec<usize>> = cliques
+                .iter()
+                .map(|clique| {
+                    let mut clique = clique.clone();
+                    clique.sort_unstable();
+                    clique
+                })
+                .collect();
+            assert_eq!(distinct.len(), cliques.len());
+            for clique in &cliques {
+                assert!(clique
+                    .iter()
+                    .all(|u| clique.iter().all(|v| u == v || bit_adj.has_edge(*u, *v))));
+                assert!(vertices.iter().all(
+                    |v| clique.contains(v) || clique.iter().any(|u| !bit_adj.has_edge(*u, *v))
+                ));
+            }
+        }
+    }
+
+    #[test]
+    fn transitive_closure_of_directed_graph() {
+        // Given: Graph
+        //
+        //      a  -->  b  -->  c  -->  d
+        //              ^       |
+        //              '-------'
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(c, b, 1.into());
+        graph.add_edge_unchecked(c, d, 1.into());
+
+        // When:
+        let closure = BitAdj::init(&graph).transitive_closure();
+
+        // Then:
+        let mut reachable_from_a = closure.neighbors(a);
+        reachable_from_a.sort_unstable();
+        assert_eq!(reachable_from_a, vec![b, c, d]);
+        assert!(closure.has_edge(b, b));
+        assert!(closure.has_edge(c, c));
+        assert!(!closure.has_edge(a, a));
+        assert!(!closure.has_edge(d, a));
+        assert!(closure.neighbors(d).is_empty());
+    }
+}
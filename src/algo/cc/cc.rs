@@ -1,5 +1,5 @@
-use crate::algo::{Dfs, DfsListener};
-use crate::graph::{Edge, UndirectedEdge};
+use crate::algo::{Algorithm, Dfs, DfsListener, Orientation};
+use crate::graph::{Edge, EdgeDir, UndirectedEdge};
 use crate::provide;
 
 /// Finds connected components of an undirected graph.
@@ -43,6 +43,7 @@ use crate::provide;
 pub struct ConnectedComponents {
     current_component: Vec<usize>,
     ccs: Vec<Vec<usize>>,
+    orientation: Orientation,
 }
 
 impl DfsListener for ConnectedComponents {
@@ -63,13 +64,39 @@ impl DfsListener for ConnectedComponents {
 
 impl ConnectedComponents {
     /// Initializes the structure.
-    pub fn init<G, W, E: Edge<W>>(_: &G) -> Self
+    pub fn init<G, W, E: Edge<W>>(graph: &G) -> Self
     where
         G: provide::Graph<W, E, UndirectedEdge> + provide::Vertices + provide::Neighbors,
     {
+        ConnectedComponents::init_with_orientation(graph, Orientation::default())
+    }
+
+    /// Initializes the structure to follow edges in the given orientation.
+    ///
+    /// Connected components of a directed graph are found by ignoring direction of its edges(weakly connected components),
+    /// so the graph does not need to be copied into an undirected one.
+    ///
+    /// # Arguments
+    /// * `graph`: Graph to search for its connected components.
+    /// * `orientation`: How to follow the edges.
+    ///
+    /// # Panics
+    /// If `graph` is directed and `orientation` is not [`Ignore`](crate::algo::Orientation::Ignore).
+    pub fn init_with_orientation<G, W, E: Edge<W>, Dir: EdgeDir>(
+        _: &G,
+        orientation: Orientation,
+    ) -> Self
+    where
+        G: provide::Graph<W, E, Dir> + provide::Vertices + provide::Neighbors,
+    {
+        if Dir::is_directed() && orientation != Orientation::Ignore {
+            panic!("Direction of edges must be ignored to find connected components of a directed graph");
+        }
+
         ConnectedComponents {
             ccs: vec![],
             current_component: vec![],
+            orientation,
         }
     }
 
@@ -81,11 +108,12 @@ impl ConnectedComponents {
     /// # Returns
     /// Connected components of the graph. \
     /// Returned value will be vector of vectors. Each vector contains ids of vertices that are in a component.
-    pub fn execute<G, W, E: Edge<W>>(mut self, graph: &G) -> Vec<Vec<usize>>
+    pub fn execute<G, W, E: Edge<W>, Dir: EdgeDir>(mut self, graph: &G) -> Vec<Vec<usize>>
     where
-        G: provide::Graph<W, E, UndirectedEdge> + provide::Vertices + provide::Neighbors,
+        G: provide::Graph<W, E, Dir> + provide::Vertices + provide::Neighbors,
     {
-        let mut dfs = Dfs::init(graph, &mut self);
+        let orientation = self.orientation;
+        let mut dfs = Dfs::init(graph, &mut self).with_orientation(orientation);
 
         dfs.execute(graph);
 
@@ -93,6 +121,23 @@ impl ConnectedComponents {
     }
 }
 
+impl<W, E: Edge<W>, Dir: EdgeDir, G> Algorithm<G, (W, E, Dir)> for ConnectedComponents
+where
+    G: provide::Graph<W, E, Dir> + provide::Vertices + provide::Neighbors,
+{
+    type Output<'a>
+        = Vec<Vec<usize>>
+    where
+        G: 'a,
+        (W, E, Dir): 'a;
+
+    /// # Panics
+    /// If `graph` is directed and orientation of the structure is not [`Ignore`](crate::algo::Orientation::Ignore).
+    fn run(&self, graph: &G) -> Vec<Vec<usize>> {
+        ConnectedComponents::init_with_orientation(graph, self.orientation).execute(graph)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
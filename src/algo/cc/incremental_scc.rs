@@ -0,0 +1,403 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::provide::{Neighbors, Vertices};
+
+/// Keeps strongly connected components of a directed graph up to date while edges get inserted.
+///
+/// Components are kept in a topological order. Inserting an edge that agrees with the order costs O(1). Otherwise only the components
+/// between the two end points in the order are searched, to either move them so the order stays valid, or to merge them into one component
+/// if the edge closes a cycle, in the style of Pearce-Kelly and Haeupler et al.
+/// So a dependency graph can reject or report a cycle as soon as the edge creating it is inserted, instead of running [`TarjanSCC`](crate::algo::TarjanSCC) again.
+///
+/// The structure only knows about the vertices and edges it's given, and it does not support removals.
+///
+/// # Examples
+/// ```
+/// use prepona::algo::IncrementalScc;
+///
+/// // Given: Dependencies a --> b --> c.
+/// let (a, b, c) = (0, 1, 2);
+/// let mut sccs = IncrementalScc::init();
+/// assert!(sccs.insert_edge(a, b).is_none());
+/// assert!(sccs.insert_edge(b, c).is_none());
+///
+/// // When: c depends on a.
+/// let cycle = sccs.insert_edge(c, a);
+///
+/// // Then:
+/// assert_eq!(cycle, Some(vec![a, b, c]));
+/// assert!(sccs.same_component(a, c));
+/// assert_eq!(sccs.component_count(), 1);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct IncrementalScc {
+    index_of: HashMap<usize, usize>,
+    vertex_ids: Vec<usize>,
+
+    // Union-find over indices of vertices. Fields below are only valid for the index of the representative of each component.
+    parents: Vec<usize>,
+    members: Vec<Vec<usize>>,
+    orders: Vec<usize>,
+    // Components at the other end of outgoing and incoming edges, possibly stale indices that must be resolved with `find`.
+    successors: Vec<HashSet<usize>>,
+    predecessors: Vec<HashSet<usize>>,
+
+    next_order: usize,
+    component_count: usize,
+}
+
+impl IncrementalScc {
+    /// # Returns
+    /// Structure without any vertex.
+    pub fn init() -> Self {
+        IncrementalScc::default()
+    }
+
+    /// # Arguments
+    /// `graph`: Directed graph to start with.
+    ///
+    /// # Returns
+    /// Structure that knows about the vertices and edges of `graph`.
+    pub fn from_graph<G>(graph: &G) -> Self
+    where
+        G: Vertices + Neighbors,
+    {
+        let mut sccs = IncrementalScc::init();
+
+        for vertex_id in graph.vertices() {
+            sccs.add_vertex(vertex_id);
+        }
+        for src_id in graph.vertices() {
+            for dst_id in graph.neighbors_unchecked(src_id) {
+                sccs.insert_edge(src_id, dst_id);
+            }
+        }
+
+        sccs
+    }
+
+    fn find(&mut self, index: usize) -> usize {
+        let mut root = index;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+
+        let mut index = index;
+        while self.parents[index] != root {
+            let parent = self.parents[index];
+            self.parents[index] = root;
+            index = parent;
+        }
+
+        root
+    }
+
+    /// Adds a vertex in a component of its own. Does nothing if the vertex is already known.
+    ///
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    pub fn add_vertex(&mut self, vertex_id: usize) {
+        if self.index_of.contains_key(&vertex_id) {
+            return;
+        }
+
+        let index = self.vertex_ids.len();
+        self.index_of.insert(vertex_id, index);
+        self.vertex_ids.push(vertex_id);
+
+        self.parents.push(index);
+        self.members.push(vec![vertex_id]);
+        self.orders.push(self.next_order);
+        self.successors.push(HashSet::new());
+        self.predecessors.push(HashSet::new());
+
+        self.next_order += 1;
+        self.component_count += 1;
+    }
+
+    // Components reachable from `start`, or reaching it if `forward` is false, without leaving the order range [`min_order`, `max_order`].
+    fn bounded_search(
+        &mut self,
+        start: usize,
+        min_order: usize,
+        max_order: usize,
+        forward: bool,
+    ) -> HashSet<usize> {
+        let mut visited = HashSet::new();
+        visited.insert(start);
+        let mut stack = vec![start];
+
+        while let Some(component) = stack.pop() {
+            let neighbors: Vec<usize> = if forward {
+                self.successors[component].iter().copied().collect()
+            } else {
+                self.predecessors[component].iter().copied().collect()
+            };
+
+            for neighbor in neighbors {
+                let neighbor = self.find(neighbor);
+                let order = self.orders[neighbor];
+                if min_order <= order && order <= max_order && visited.insert(neighbor) {
+                    stack.push(neighbor);
+                }
+            }
+        }
+
+        visited
+    }
+
+    /// Inserts an edge from `src_id` to `dst_id`. Unknown vertices are added first.
+    ///
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    ///
+    /// # Returns
+    /// * `Some`: Containing sorted ids of the vertices in the component of the end points, if the edge lies on a cycle.
+    ///   That's the case if the edge merges components, or if its end points were already in the same component, including self loops.
+    /// * `None`: If the edge does not lie on any cycle.
+    ///
+    /// # Complexity
+    /// O(1) if the edge agrees with the current order of the components,
+    /// otherwise proportional to the edges of the components between the two end points in the order.
+    pub fn insert_edge(&mut self, src_id: usize, dst_id: usize) -> Option<Vec<usize>> {
+        self.add_vertex(src_id);
+        self.add_vertex(dst_id);
+
+        let src_component = self.find(self.index_of[&src_id]);
+        let dst_component = self.find(self.index_of[&dst_id]);
+
+        if src_component == dst_component {
+            return Some(self.sorted_members(src_component));
+        }
+
+        self.successors[src_component].insert(dst_component);
+        self.predecessors[dst_component].insert(src_component);
+
+        let (src_order, dst_order) = (self.orders[src_component], self.orders[dst_component]);
+        if src_order < dst_order {
+            return None;
+        }
+
+        // Components that the new edge may have to be moved around, all placed in [dst_order, src_order].
+        let forward = self.bounded_search(dst_component, dst_order, src_order, true);
+        let backward = self.bounded_search(src_component, dst_order, src_order, false);
+
+        let mut slots: Vec<usize> = forward
+            .union(&backward)
+            .map(|component| self.orders[*component])
+            .collect();
+        slots.sort_unstable();
+
+        let by_order = |components: Vec<usize>, orders: &[usize]| {
+            let mut components = components;
+            components.sort_unstable_by_key(|component| orders[*component]);
+            components
+        };
+        let on_cycle: Vec<usize> = forward.intersection(&backward).copied().collect();
+        let only_backward = by_order(
+            backward.difference(&forward).copied().collect(),
+            &self.orders,
+        );
+        let only_forward = by_order(
+            forward.difference(&backward).copied().collect(),
+            &self.orders,
+        );
+
+        // Vertices that reach the source come first, then the merged cycle if any, then vertices reachable from the destination.
+        // Components that only reach the source keep the lowest slots and the ones only reachable from the destination keep the highest,
+        // so none of them moves past a component outside of the search. The merged component takes one of the slots freed in between.
+        let merged = if on_cycle.is_empty() {
+            None
+        } else {
+            let freed = only_backward.len() + 1..only_backward.len() + on_cycle.len();
+            slots.drain(freed);
+            Some(self.merge(on_cycle))
+        };
+
+        let new_order = only_backward.into_iter().chain(merged).chain(only_forward);
+        for (component, slot) in new_order.zip(slots) {
+            self.orders[component] = slot;
+        }
+
+        merged.map(|component| self.sorted_members(component))
+    }
+
+    // Merges `components` into one, and returns its representative.
+    fn merge(&mut self, components: Vec<usize>) -> usize {
+        let root = *components
+            .iter()
+            .max_by_key(|component| self.members[**component].len())
+            .unwrap();
+
+        for component in components {
+            if component == root {
+                continue;
+            }
+
+            self.parents[component] = root;
+            let members = std::mem::take(&mut self.members[component]);
+            self.members[root].extend(members);
+            let successors = std::mem::take(&mut self.successors[component]);
+            self.successors[root].extend(successors);
+            let predecessors = std::mem::take(&mut self.predecessors[component]);
+            self.predecessors[root].extend(predecessors);
+
+            self.component_count -= 1;
+        }
+
+        // Edges inside the merged component are not needed for searching anymore.
+        let successors: Vec<usize> = self.successors[root].iter().copied().collect();
+        let predecessors: Vec<usize> = self.predecessors[root].iter().copied().collect();
+        self.successors[root] = successors
+            .into_iter()
+            .map(|index| self.find(index))
+            .filter(|component| *component != root)
+            .collect();
+        self.predecessors[root] = predecessors
+            .into_iter()
+            .map(|index| self.find(index))
+            .filter(|component| *component != root)
+            .collect();
+
+        root
+    }
+
+    fn sorted_members(&self, component: usize) -> Vec<usize> {
+        let mut members = self.members[component].clone();
+        members.sort_unstable();
+
+        members
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// * `Some`: Containing sorted ids of the vertices in the same component as the vertex, including itself.
+    /// * `None`: If the vertex is not known.
+    pub fn component_of(&mut self, vertex_id: usize) -> Option<Vec<usize>> {
+        let index = *self.index_of.get(&vertex_id)?;
+        let component = self.find(index);
+
+        Some(self.sorted_members(component))
+    }
+
+    /// # Arguments
+    /// * `vertex_id1`: Id of the first vertex.
+    /// * `vertex_id2`: Id of the second vertex.
+    ///
+    /// # Returns
+    /// `true` if both vertices are known and are in the same component, `false` otherwise.
+    pub fn same_component(&mut self, vertex_id1: usize, vertex_id2: usize) -> bool {
+        match (
+            self.index_of.get(&vertex_id1).copied(),
+            self.index_of.get(&vertex_id2).copied(),
+        ) {
+            (Some(index1), Some(index2)) => self.find(index1) == self.find(index2),
+            _ => false,
+        }
+    }
+
+    /// # Returns
+    /// Number of components.
+    pub fn component_count(&self) -> usize {
+        self.component_count
+    }
+
+    /// # Returns
+    /// Components in topological order: Every edge between two components goes from an earlier one to a later one.
+    /// Ids of the vertices in each component are sorted.
+    pub fn components(&self) -> Vec<Vec<usize>> {
+        let mut roots: Vec<usize> = (0..self.parents.len())
+            .filter(|index| self.parents[*index] == *index)
+            .collect();
+        roots.sort_unstable_by_key(|root| self.orders[*root]);
+
+        roots
+            .into_iter()
+            .map(|root| self.sorted_members(root))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::TarjanSCC;
+    use crate::graph::MultiListGraph;
+    use crate::misc::rng::Rng;
+    use crate::provide::{Edges, Graph};
+    use crate::storage::DiList;
+
+    fn sorted_sccs(mut sccs: Vec<Vec<usize>>) -> Vec<Vec<usize>> {
+        for scc in sccs.iter_mut() {
+            scc.sort_unstable();
+        }
+        sccs.sort_unstable();
+
+        sccs
+    }
+
+    #[test]
+    fn matches_tarjan_after_each_insertion() {
+        let mut rng = Rng::seed_from(3160);
+
+        for _ in 0..5 {
+            // Given: Random edges inserted one by one.
+            let mut graph = MultiListGraph::init(DiList::<usize>::init());
+            for _ in 0..30 {
+                graph.add_vertex();
+            }
+            let mut sccs = IncrementalScc::from_graph(&graph);
+
+            for _ in 0..60 {
+                let src_id = rng.next_below(30);
+                let dst_id = rng.next_below(30);
+
+                // When:
+                let was_same = sccs.same_component(src_id, dst_id);
+                let cycle = sccs.insert_edge(src_id, dst_id);
+                graph.add_edge_unchecked(src_id, dst_id, 1.into());
+
+                // Then:
+                let expected = sorted_sccs(TarjanSCC::init(&graph).execute(&graph));
+                assert_eq!(sorted_sccs(sccs.components()), expected);
+                assert_eq!(sccs.component_count(), expected.len());
+                assert_eq!(cycle.is_some(), sccs.same_component(src_id, dst_id));
+                if was_same {
+                    assert!(cycle.is_some());
+                }
+
+                // And: Components are in topological order.
+                let position_of: HashMap<usize, usize> = sccs
+                    .components()
+                    .into_iter()
+                    .enumerate()
+                    .flat_map(|(position, scc)| scc.into_iter().map(move |id| (id, position)))
+                    .collect();
+                for (src_id, dst_id, _) in graph.edges() {
+                    assert!(position_of[&src_id] <= position_of[&dst_id]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn unknown_vertices() {
+        // Given:
+        let mut sccs = IncrementalScc::init();
+        sccs.add_vertex(7);
+        sccs.add_vertex(7);
+
+        // When:
+        let cycle = sccs.insert_edge(3, 3);
+
+        // Then:
+        assert_eq!(cycle, Some(vec![3]));
+        assert_eq!(sccs.component_count(), 2);
+        assert_eq!(sccs.component_of(7), Some(vec![7]));
+        assert_eq!(sccs.component_of(5), None);
+        assert!(!sccs.same_component(3, 5));
+    }
+}
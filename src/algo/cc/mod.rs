@@ -1,5 +1,7 @@
 mod cc;
+mod incremental_scc;
 mod tarjan;
 
 pub use cc::ConnectedComponents;
+pub use incremental_scc::IncrementalScc;
 pub use tarjan::TarjanSCC;
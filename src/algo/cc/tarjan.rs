@@ -1,5 +1,6 @@
 use magnitude::Magnitude;
 
+use crate::algo::Algorithm;
 use crate::graph::{DirectedEdge, Edge};
 use crate::provide;
 
@@ -156,6 +157,21 @@ impl TarjanSCC {
     }
 }
 
+impl<W, E: Edge<W>, G> Algorithm<G, (W, E)> for TarjanSCC
+where
+    G: provide::Graph<W, E, DirectedEdge> + provide::Vertices + provide::Neighbors,
+{
+    type Output<'a>
+        = Vec<Vec<usize>>
+    where
+        G: 'a,
+        (W, E): 'a;
+
+    fn run(&self, graph: &G) -> Vec<Vec<usize>> {
+        TarjanSCC::init(graph).execute(graph)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,270 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::graph::{Edge, UndirectedEdge};
+use crate::provide::{Graph, Neighbors, Vertices};
+
+/// Result of [`color_with_k`](crate::algo::color_with_k).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KColoring {
+    /// Every vertex got a color. Maps id of each vertex to its color, a number less than `k`.
+    Colored(HashMap<usize, usize>),
+
+    /// Sorted ids of vertices that could not be colored and must be spilled.
+    /// Removing them from the graph does not guarantee that the rest can be colored, so like register allocators the caller should rebuild the graph and try again.
+    Spilled(Vec<usize>),
+}
+
+impl KColoring {
+    /// # Returns
+    /// `true` if every vertex got a color.
+    pub fn is_colored(&self) -> bool {
+        matches!(self, KColoring::Colored(_))
+    }
+}
+
+/// Colors an interference graph with at most `k` colors, the way register allocators assign registers.
+///
+/// Uses Chaitin-Briggs simplification: Vertices with less than `k` neighbors are removed from the graph, because they can always be colored
+/// after their neighbors are. When every remaining vertex has at least `k` neighbors, the one with the lowest spill cost per neighbor is removed
+/// as a spill candidate. Vertices are then colored in reverse order of removal with the smallest color not used by their neighbors.
+/// Following Briggs, a spill candidate is only spilled if its neighbors actually used all `k` colors.
+///
+/// Loops and parallel edges are ignored.
+///
+/// # Arguments
+/// * `graph`: Interference graph to color.
+/// * `k`: Number of available colors.
+/// * `spill_cost_fn`: Receives id of a vertex and returns the cost of spilling it. Vertices with lower costs are more likely to be spilled.
+///
+/// # Returns
+/// * [`Colored`](crate::algo::KColoring::Colored): If all vertices got a color.
+/// * [`Spilled`](crate::algo::KColoring::Spilled): Containing vertices that could not be colored.
+///
+/// # Complexity
+/// O(|V|<sup>2</sup> + |E|): Each spill candidate is found by a pass over the remaining vertices.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::List;
+/// use prepona::graph::ListGraph;
+/// use prepona::algo::{color_with_k, KColoring};
+///
+/// // Given: Graph
+/// //
+/// //      a  ---  b
+/// //      |       |
+/// //      d  ---  c
+/// //
+/// let mut graph = ListGraph::init(List::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(c, d, 1.into());
+/// graph.add_edge_unchecked(d, a, 1.into());
+///
+/// // When: Only one color is available and c is the cheapest to spill.
+/// let coloring = color_with_k(&graph, 1, |vertex_id| if vertex_id == c { 1.0 } else { 10.0 });
+///
+/// // Then: b and d share the only color.
+/// assert_eq!(coloring, KColoring::Spilled(vec![a, c]));
+///
+/// // When: Two colors are available.
+/// let coloring = color_with_k(&graph, 2, |_| 1.0);
+///
+/// // Then:
+/// if let KColoring::Colored(colors) = coloring {
+///     assert_eq!(colors[&a], colors[&c]);
+///     assert_ne!(colors[&a], colors[&b]);
+/// } else {
+///     panic!("Graph is 2-colorable");
+/// }
+/// ```
+pub fn color_with_k<W, E, G, F>(graph: &G, k: usize, spill_cost_fn: F) -> KColoring
+where
+    E: Edge<W>,
+    G: Vertices + Neighbors + Graph<W, E, UndirectedEdge>,
+    F: Fn(usize) -> f64,
+{
+    let id_map = graph.continuos_id_map();
+    let vertex_count = id_map.len();
+
+    let neighbors_of: Vec<HashSet<usize>> = (0..vertex_count)
+        .map(|virt_id| {
+            graph
+                .neighbors_unchecked(id_map.real_id_of(virt_id))
+                .into_iter()
+                .map(|dst_real_id| id_map.virt_id_of(dst_real_id))
+                .filter(|dst_virt_id| *dst_virt_id != virt_id)
+                .collect()
+        })
+        .collect();
+    let spill_costs: Vec<f64> = (0..vertex_count)
+        .map(|virt_id| spill_cost_fn(id_map.real_id_of(virt_id)))
+        .collect();
+
+    // Simplify.
+    let mut degrees: Vec<usize> = neighbors_of.iter().map(HashSet::len).collect();
+    let mut is_removed = vec![false; vertex_count];
+    let mut stack = Vec::with_capacity(vertex_count);
+    let mut low_degrees: Vec<usize> = (0..vertex_count)
+        .filter(|virt_id| degrees[*virt_id] < k)
+        .collect();
+
+    while stack.len() < vertex_count {
+        let virt_id = match low_degrees.pop() {
+            Some(virt_id) => virt_id,
+            None => (0..vertex_count)
+                .filter(|virt_id| !is_removed[*virt_id])
+                .min_by(|v1, v2| {
+                    let cost1 = spill_costs[*v1] / degrees[*v1] as f64;
+                    let cost2 = spill_costs[*v2] / degrees[*v2] as f64;
+                    cost1.total_cmp(&cost2).then(v1.cmp(v2))
+                })
+                .unwrap(),
+        };
+
+        is_removed[virt_id] = true;
+        stack.push(virt_id);
+
+        for neighbor_id in &neighbors_of[virt_id] {
+            if !is_removed[*neighbor_id] {
+                degrees[*neighbor_id] -= 1;
+                if degrees[*neighbor_id] + 1 == k {
+                    low_degrees.push(*neighbor_id);
+                }
+            }
+        }
+    }
+
+    // Select.
+    let mut colors: Vec<Option<usize>> = vec![None; vertex_count];
+    let mut spilled = vec![];
+    while let Some(virt_id) = stack.pop() {
+        let used_colors: HashSet<usize> = neighbors_of[virt_id]
+            .iter()
+            .filter_map(|neighbor_id| colors[*neighbor_id])
+            .collect();
+
+        match (0..k).find(|color| !used_colors.contains(color)) {
+            Some(color) => colors[virt_id] = Some(color),
+            None => spilled.push(id_map.real_id_of(virt_id)),
+        }
+    }
+
+    if spilled.is_empty() {
+        KColoring::Colored(
+            colors
+                .into_iter()
+                .enumerate()
+                .map(|(virt_id, color)| (id_map.real_id_of(virt_id), color.unwrap()))
+                .collect(),
+        )
+    } else {
+        spilled.sort_unstable();
+        KColoring::Spilled(spilled)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::ListGraph;
+    use crate::misc::rng::Rng;
+    use crate::provide::Edges;
+    use crate::storage::List;
+
+    #[test]
+    fn empty_graph() {
+        // Given:
+        let graph = ListGraph::init(List::<usize>::init());
+
+        // When:
+        let coloring = color_with_k(&graph, 0, |_| 1.0);
+
+        // Then:
+        assert_eq!(coloring, KColoring::Colored(HashMap::new()));
+    }
+
+    #[test]
+    fn clique_spills_cheapest() {
+        // Given: K4 with a loop on a.
+        let mut graph = ListGraph::init(List::<usize>::init());
+        let ids: Vec<usize> = (0..4).map(|_| graph.add_vertex()).collect();
+        for i in 0..4 {
+            for j in i + 1..4 {
+                graph.add_edge_unchecked(ids[i], ids[j], 1.into());
+            }
+        }
+        graph.add_edge_unchecked(ids[0], ids[0], 1.into());
+
+        // When:
+        let with_three = color_with_k(&graph, 3, |vertex_id| vertex_id as f64 + 1.0);
+        let with_four = color_with_k(&graph, 4, |_| 1.0);
+
+        // Then:
+        assert_eq!(with_three, KColoring::Spilled(vec![ids[0]]));
+        assert!(with_four.is_colored());
+    }
+
+    #[test]
+    fn optimistic_coloring() {
+        // Given: Cycle of length 4 where every vertex has 2 neighbors.
+        let mut graph = ListGraph::init(List::<usize>::init());
+        let ids: Vec<usize> = (0..4).map(|_| graph.add_vertex()).collect();
+        for i in 0..4 {
+            graph.add_edge_unchecked(ids[i], ids[(i + 1) % 4], 1.into());
+        }
+
+        // When: Pessimistic simplification gets stuck with 2 colors.
+        let coloring = color_with_k(&graph, 2, |_| 1.0);
+
+        // Then: Spill candidate still gets a color.
+        assert!(coloring.is_colored());
+    }
+
+    #[test]
+    fn random_graphs() {
+        let mut rng = Rng::seed_from(3162);
+
+        for _ in 0..20 {
+            // Given:
+            let mut graph = ListGraph::init(List::<usize>::init());
+            for _ in 0..20 {
+                graph.add_vertex();
+            }
+            for _ in 0..40 {
+                let src_id = rng.next_below(20);
+                let dst_id = rng.next_below(20);
+                if src_id != dst_id && !graph.has_any_edge_unchecked(src_id, dst_id) {
+                    graph.add_edge_unchecked(src_id, dst_id, 1.into());
+                }
+            }
+            let k = 1 + rng.next_below(4);
+
+            // When:
+            let coloring = color_with_k(&graph, k, |vertex_id| (vertex_id % 5) as f64);
+
+            // Then:
+            match coloring {
+                KColoring::Colored(colors) => {
+                    assert_eq!(colors.len(), 20);
+                    assert!(colors.values().all(|color| *color < k));
+                    for (src_id, dst_id, _) in graph.edges() {
+                        assert_ne!(colors[&src_id], colors[&dst_id]);
+                    }
+                }
+                KColoring::Spilled(spilled) => {
+                    assert!(!spilled.is_empty());
+                    // A spilled vertex has at least k neighbors.
+                    for vertex_id in spilled {
+                        assert!(graph.neighbors_unchecked(vertex_id).len() >= k);
+                    }
+                }
+            }
+        }
+    }
+}
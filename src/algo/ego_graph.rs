@@ -0,0 +1,186 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::graph::{Edge, FilterView, KHopView, KeepAll};
+use crate::provide::{Edges, Neighbors, Vertices};
+
+/// Extracts the ego network of a vertex: The subgraph induced by the vertices within `radius` hops of it.
+///
+/// # Arguments
+/// * `graph`: Graph to extract the ego network from.
+/// * `center`: Id of the center vertex.
+/// * `radius`: Maximum number of hops between the center and other vertices of the ego network.
+/// * `undirected`: If `true`, direction of edges is ignored when counting hops in directed graphs, so vertices that reach the center are included too.
+///   It has no effect on undirected graphs.
+///
+/// # Returns
+/// A [`FilterView`](crate::graph::FilterView) of `graph` that only shows the vertices of the ego network and the edges between them.
+/// The view borrows `graph`, so neither vertices nor edges are copied.
+///
+/// # Complexity
+/// O(|V<sub>r</sub>| + |E<sub>r</sub>|) where V<sub>r</sub> and E<sub>r</sub> are the vertices and edges within `radius` hops of the center.
+/// If `undirected` is `true`, incoming edges of every vertex are indexed first, which takes O(|V| + |E|).
+///
+/// # Panics
+/// If there is no vertex with id: `center`.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::DiList;
+/// use prepona::graph::ListGraph;
+/// use prepona::algo::ego_graph;
+///
+/// // Given: Graph
+/// //
+/// //      a  -->  b  -->  c  -->  d
+/// //
+/// let mut graph = ListGraph::init(DiList::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(c, d, 1.into());
+///
+/// // When:
+/// let ego = ego_graph(&graph, b, 1, false);
+/// let undirected_ego = ego_graph(&graph, b, 1, true);
+///
+/// // Then:
+/// assert_eq!(ego.vertices(), vec![b, c]);
+/// assert_eq!(undirected_ego.vertices(), vec![a, b, c]);
+/// assert_eq!(undirected_ego.edges_count(), 2);
+/// ```
+pub fn ego_graph<'a, W, E, G>(
+    graph: &'a G,
+    center: usize,
+    radius: usize,
+    undirected: bool,
+) -> FilterView<'a, W, E, G, HashSet<usize>, KeepAll>
+where
+    E: Edge<W>,
+    G: Vertices + Neighbors + Edges<W, E>,
+{
+    assert!(
+        graph.contains_vertex(center),
+        "Vertex with id: {} not found",
+        center
+    );
+
+    let members = if undirected {
+        undirected_ball(graph, center, radius)
+    } else {
+        KHopView::init(graph, radius)
+            .hops_from(center)
+            .into_iter()
+            .map(|(vertex_id, _)| vertex_id)
+            .chain(std::iter::once(center))
+            .collect()
+    };
+
+    FilterView::vertices_only(graph, members)
+}
+
+// Vertices within `radius` hops of `center`, following edges in both directions.
+fn undirected_ball<W, E, G>(graph: &G, center: usize, radius: usize) -> HashSet<usize>
+where
+    E: Edge<W>,
+    G: Edges<W, E>,
+{
+    let mut adjacency: HashMap<usize, Vec<usize>> = HashMap::new();
+    for (src_id, dst_id, _) in graph.as_directed_edges() {
+        adjacency.entry(src_id).or_default().push(dst_id);
+        adjacency.entry(dst_id).or_default().push(src_id);
+    }
+
+    let mut members = HashSet::new();
+    members.insert(center);
+
+    let mut queue = VecDeque::new();
+    queue.push_back((center, 0));
+    while let Some((vertex_id, hops)) = queue.pop_front() {
+        if hops == radius {
+            continue;
+        }
+
+        for dst_id in adjacency.get(&vertex_id).into_iter().flatten() {
+            if members.insert(*dst_id) {
+                queue.push_back((*dst_id, hops + 1));
+            }
+        }
+    }
+
+    members
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::MatGraph;
+    use crate::provide::Graph;
+    use crate::storage::{DiMat, Mat};
+
+    #[test]
+    fn undirected_graph() {
+        // Given: Graph
+        //
+        //      a  --  b  --  c  --  d
+        //             |
+        //             e
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let vertices: Vec<_> = (0..5).map(|_| graph.add_vertex()).collect();
+        for (src, dst) in [(0, 1), (1, 2), (2, 3), (1, 4)].iter() {
+            graph.add_edge_unchecked(vertices[*src], vertices[*dst], 1.into());
+        }
+
+        // When:
+        let ego = ego_graph(&graph, vertices[1], 1, false);
+        let same_ego = ego_graph(&graph, vertices[1], 1, true);
+
+        // Then:
+        let mut ego_vertices = ego.vertices();
+        ego_vertices.sort_unstable();
+        assert_eq!(
+            ego_vertices,
+            vec![vertices[0], vertices[1], vertices[2], vertices[4]]
+        );
+        assert_eq!(ego.edges_count(), 3);
+        assert_eq!(same_ego.vertices(), ego.vertices());
+        assert_eq!(
+            ego_graph(&graph, vertices[3], 0, false).vertices(),
+            vec![vertices[3]]
+        );
+    }
+
+    #[test]
+    fn directed_graph_follows_direction() {
+        // Given: Graph
+        //
+        //      a  <--  b  -->  c  <--  d
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let vertices: Vec<_> = (0..4).map(|_| graph.add_vertex()).collect();
+        for (src, dst) in [(1, 0), (1, 2), (3, 2)].iter() {
+            graph.add_edge_unchecked(vertices[*src], vertices[*dst], 1.into());
+        }
+
+        // When:
+        let ego = ego_graph(&graph, vertices[2], 2, false);
+        let undirected_ego = ego_graph(&graph, vertices[2], 2, true);
+
+        // Then:
+        assert_eq!(ego.vertices(), vec![vertices[2]]);
+        assert_eq!(undirected_ego.vertex_count(), 4);
+        assert_eq!(undirected_ego.edges_count(), 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn missing_center_panics() {
+        let graph = MatGraph::init(Mat::<usize>::init());
+
+        ego_graph(&graph, 0, 1, false);
+    }
+}
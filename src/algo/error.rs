@@ -3,6 +3,10 @@ pub enum ErrorKind {
     EulerianTrailNotFound,
     EulerianCircuitNotFound,
     NegativeCycleDetected,
+    CycleDetected,
+    AnonymizationNotFound,
+    CountOverflow,
+    InfinitelyManyPaths,
 }
 
 /// Error type returns in [`algo`](crate::algo) module.
@@ -44,10 +48,70 @@ impl Error {
         }
     }
 
+    /// Creates a new [`NegativeCycleDetected`](crate::algo::ErrorKind::NegativeCycleDetected) kind of error.
+    ///
+    /// # Returns
+    /// `Error` with `NegativeCycleDetected` kind and predefined message.
     pub fn new_ncd() -> Self {
         Error {
             kind: ErrorKind::NegativeCycleDetected,
-            msg: format!("Graph contains cycle"),
+            msg: format!("Graph contains negative cycle"),
+        }
+    }
+
+    /// Creates a new [`CycleDetected`](crate::algo::ErrorKind::CycleDetected) kind of error.
+    ///
+    /// # Returns
+    /// `Error` with `CycleDetected` kind and predefined message.
+    pub fn new_cd() -> Self {
+        Error {
+            kind: ErrorKind::CycleDetected,
+            msg: "Graph is not acyclic".to_string(),
+        }
+    }
+
+    /// Creates a new [`AnonymizationNotFound`](crate::algo::ErrorKind::AnonymizationNotFound) kind of error.
+    ///
+    /// # Arguments
+    /// `k`: Number of times each degree was required to appear in the graph.
+    ///
+    /// # Returns
+    /// `Error` with `AnonymizationNotFound` kind and predefined message.
+    pub fn new_anf(k: usize) -> Self {
+        Error {
+            kind: ErrorKind::AnonymizationNotFound,
+            msg: format!("Could not make graph {}-degree anonymous", k),
+        }
+    }
+
+    /// Creates a new [`CountOverflow`](crate::algo::ErrorKind::CountOverflow) kind of error.
+    ///
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex whose count does not fit in the result type.
+    ///
+    /// # Returns
+    /// `Error` with `CountOverflow` kind and predefined message.
+    pub fn new_co(vertex_id: usize) -> Self {
+        Error {
+            kind: ErrorKind::CountOverflow,
+            msg: format!(
+                "Count of vertex: {} does not fit in the result type",
+                vertex_id
+            ),
+        }
+    }
+
+    /// Creates a new [`InfinitelyManyPaths`](crate::algo::ErrorKind::InfinitelyManyPaths) kind of error.
+    ///
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex with infinitely many paths.
+    ///
+    /// # Returns
+    /// `Error` with `InfinitelyManyPaths` kind and predefined message.
+    pub fn new_imp(vertex_id: usize) -> Self {
+        Error {
+            kind: ErrorKind::InfinitelyManyPaths,
+            msg: format!("Vertex: {} has infinitely many shortest paths", vertex_id),
         }
     }
 
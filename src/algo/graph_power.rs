@@ -0,0 +1,131 @@
+use crate::graph::{Edge, EdgeDir, KHopView, ListGraph};
+use crate::provide::{Graph, Neighbors, Vertices};
+use crate::storage::List;
+
+/// Computes the `k`-th power of a graph: The graph in which two vertices are connected if one can be reached from the other in at most `k` hops.
+///
+/// For a lazy alternative that computes neighbors on demand checkout [`KHopView`](crate::graph::KHopView).
+///
+/// # Arguments
+/// * `graph`: Graph to compute its power.
+/// * `k`: Maximum number of hops between connected vertices.
+///
+/// # Returns
+/// Graph with the same vertex ids as `graph`, and one edge from each vertex to each vertex within `k` hops of it.
+/// Weight of each edge is the minimum number of hops between its end points.
+/// Edges are directed if `graph` is directed, and loops are never added.
+///
+/// # Complexity
+/// O(|V| * (|V| + |E|)) in the worst case, since a bounded breadth-first search runs from each vertex.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::List;
+/// use prepona::graph::ListGraph;
+/// use prepona::algo::graph_power;
+///
+/// // Given: Graph
+/// //
+/// //      a  --  b  --  c  --  d
+/// //
+/// let mut graph = ListGraph::init(List::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(c, d, 1.into());
+///
+/// // When:
+/// let square = graph_power(&graph, 2);
+///
+/// // Then: a - c and b - d are added.
+/// assert_eq!(square.edges_count(), 5);
+/// assert_eq!(square.edges_between_unchecked(a, c)[0].get_weight(), &2.into());
+/// assert!(!square.has_any_edge_unchecked(a, d));
+/// ```
+pub fn graph_power<W, E, Dir, G>(graph: &G, k: usize) -> ListGraph<usize, Dir>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Vertices + Neighbors + Graph<W, E, Dir>,
+{
+    let vertices = graph.vertices();
+
+    // Adding every id and removing the missing ones keeps the ids of the vertices.
+    let mut power = ListGraph::init(List::init());
+    let max_id = vertices.iter().max().map_or(0, |max_id| max_id + 1);
+    for _ in 0..max_id {
+        power.add_vertex();
+    }
+    for vertex_id in 0..max_id {
+        if !graph.contains_vertex(vertex_id) {
+            power.remove_vertex_unchecked(vertex_id);
+        }
+    }
+
+    let view = KHopView::init(graph, k);
+    for src_id in vertices {
+        for (dst_id, hops) in view.hops_from(src_id) {
+            // Undirected edges are found from both of their end points.
+            if Dir::is_directed() || src_id < dst_id {
+                power.add_edge_unchecked(src_id, dst_id, hops.into());
+            }
+        }
+    }
+
+    power
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::MatGraph;
+    use crate::provide::Edges;
+    use crate::storage::{DiMat, Mat};
+
+    #[test]
+    fn directed_graph_power() {
+        // Given: Graph
+        //
+        //      a  -->  b  -->  c  -->  d
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let vertices: Vec<_> = (0..4).map(|_| graph.add_vertex()).collect();
+        for index in 0..3 {
+            graph.add_edge_unchecked(vertices[index], vertices[index + 1], 1.into());
+        }
+
+        // When:
+        let power = graph_power(&graph, 2);
+
+        // Then:
+        assert_eq!(power.edges_count(), 5);
+        assert!(power.has_any_edge_unchecked(vertices[0], vertices[2]));
+        assert!(!power.has_any_edge_unchecked(vertices[2], vertices[0]));
+        assert!(!power.has_any_edge_unchecked(vertices[0], vertices[3]));
+    }
+
+    #[test]
+    fn keeps_vertex_ids() {
+        // Given: Star graph with center c and a removed vertex.
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let vertices: Vec<_> = (0..5).map(|_| graph.add_vertex()).collect();
+        for leaf_id in vertices[1..].iter() {
+            graph.add_edge_unchecked(vertices[0], *leaf_id, 1.into());
+        }
+        graph.remove_vertex_unchecked(vertices[2]);
+
+        // When:
+        let square = graph_power(&graph, 2);
+        let first_power = graph_power(&graph, 1);
+
+        // Then: Square of a star is a complete graph.
+        assert_eq!(square.vertices(), graph.vertices());
+        assert_eq!(square.edges_count(), 6);
+        assert_eq!(first_power.edges_count(), graph.edges_count());
+        assert_eq!(graph_power(&graph, 0).edges_count(), 0);
+    }
+}
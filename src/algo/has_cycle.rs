@@ -2,7 +2,8 @@ use std::collections::HashSet;
 
 use provide::{Edges, Graph, Vertices};
 
-use crate::graph::{subgraph::Subgraph, Edge, EdgeDir};
+use crate::algo::Algorithm;
+use crate::graph::{subgraph::Subgraph, DirectedEdge, Edge, EdgeDir};
 use crate::provide;
 
 /// Detects cycle in a graph.
@@ -156,11 +157,153 @@ impl HasCycle {
     }
 }
 
+/// Finds a cycle in a graph, searching from every vertex so cycles in all components are found.
+///
+/// Search stops as soon as a cycle is found. In undirected graphs an edge is never walked back right after it is taken,
+/// so only parallel edges and loops form cycles shorter than three vertices.
+///
+/// # Arguments
+/// `graph`: Graph to search for a cycle in it.
+///
+/// # Returns
+/// * `Some`: Containing ids of vertices of the cycle in the order they appear on it. The last vertex is connected to the first one and is not repeated.
+/// * `None`: If graph does not have any cycle.
+///
+/// # Complexity
+/// O(|V| + |E|)
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::DiList;
+/// use prepona::graph::ListGraph;
+/// use prepona::algo::find_cycle;
+///
+/// // Given: Graph
+/// //
+/// //      a  -->  b  -->  c
+/// //              ^       |
+/// //              '-------'
+/// //
+/// let mut graph = ListGraph::init(DiList::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(c, b, 1.into());
+///
+/// // When:
+/// let cycle = find_cycle(&graph);
+///
+/// // Then:
+/// assert_eq!(cycle, Some(vec![b, c]));
+/// ```
+pub fn find_cycle<W, E, Dir, G>(graph: &G) -> Option<Vec<usize>>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Vertices + Edges<W, E> + Graph<W, E, Dir>,
+{
+    let id_map = graph.continuos_id_map();
+    let vertex_count = id_map.len();
+
+    let mut is_visited = vec![false; vertex_count];
+    // Position of each vertex on the current path, if it is on it.
+    let mut path_index: Vec<Option<usize>> = vec![None; vertex_count];
+
+    for root_virt_id in 0..vertex_count {
+        if is_visited[root_virt_id] {
+            continue;
+        }
+
+        let edges_of = |virt_id: usize| -> Vec<(usize, usize)> {
+            graph
+                .edges_from_unchecked(id_map.real_id_of(virt_id))
+                .into_iter()
+                .map(|(dst_id, edge)| (id_map.virt_id_of(dst_id), edge.get_id()))
+                .collect()
+        };
+
+        // Each frame holds the vertex, the id of the edge used to reach it, its outgoing edges and the index of the next edge to take.
+        // Vertices of the frames form the current path.
+        is_visited[root_virt_id] = true;
+        path_index[root_virt_id] = Some(0);
+        let mut path = vec![root_virt_id];
+        let mut stack = vec![(root_virt_id, None, edges_of(root_virt_id), 0)];
+
+        while let Some((virt_id, parent_edge_id, edges, next_index)) = stack.last_mut() {
+            if *next_index == edges.len() {
+                path_index[*virt_id] = None;
+                path.pop();
+                stack.pop();
+                continue;
+            }
+
+            let (dst_virt_id, edge_id) = edges[*next_index];
+            *next_index += 1;
+
+            if Dir::is_undirected() && Some(edge_id) == *parent_edge_id {
+                continue;
+            }
+
+            if let Some(index) = path_index[dst_virt_id] {
+                return Some(
+                    path[index..]
+                        .iter()
+                        .map(|virt_id| id_map.real_id_of(*virt_id))
+                        .collect(),
+                );
+            }
+
+            if !is_visited[dst_virt_id] {
+                is_visited[dst_virt_id] = true;
+                path_index[dst_virt_id] = Some(path.len());
+                path.push(dst_virt_id);
+                stack.push((dst_virt_id, Some(edge_id), edges_of(dst_virt_id), 0));
+            }
+        }
+    }
+
+    None
+}
+
+/// # Arguments
+/// `graph`: Directed graph to check.
+///
+/// # Returns
+/// `true` if graph does not have any cycle.
+///
+/// # Complexity
+/// O(|V| + |E|)
+pub fn is_dag<W, E, G>(graph: &G) -> bool
+where
+    E: Edge<W>,
+    G: Vertices + Edges<W, E> + Graph<W, E, DirectedEdge>,
+{
+    find_cycle(graph).is_none()
+}
+
+impl<W, E: Edge<W>, Dir: EdgeDir, G> Algorithm<G, (W, E, Dir)> for HasCycle
+where
+    G: provide::Neighbors + Vertices + Graph<W, E, Dir> + Edges<W, E>,
+{
+    type Output<'a>
+        = Option<Subgraph<'a, W, E, Dir, G>>
+    where
+        G: 'a,
+        (W, E, Dir): 'a;
+
+    fn run<'a>(&self, graph: &'a G) -> Option<Subgraph<'a, W, E, Dir, G>> {
+        HasCycle::init(graph).execute(graph)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::graph::MatGraph;
-    use crate::storage::{DiMat, Mat};
+    use crate::graph::{MatGraph, MultiListGraph};
+    use crate::storage::{DiList, DiMat, List, Mat};
     use provide::*;
 
     #[test]
@@ -379,4 +522,112 @@ mod tests {
             .iter()
             .all(|edge_id| cycle.edge(*edge_id).is_ok()));
     }
+
+    // Checks that consecutive vertices of the cycle are connected.
+    fn assert_is_cycle<G: Neighbors>(graph: &G, cycle: &[usize]) {
+        for (index, vertex_id) in cycle.iter().enumerate() {
+            let next_id = cycle[(index + 1) % cycle.len()];
+            assert!(graph.neighbors_unchecked(*vertex_id).contains(&next_id));
+        }
+    }
+
+    #[test]
+    fn find_cycle_in_later_component() {
+        // Given: Graph
+        //
+        //      a  -->  b       c  -->  d  -->  e
+        //                              ^       |
+        //                              '-------'
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(c, d, 1.into());
+        graph.add_edge_unchecked(d, e, 1.into());
+        graph.add_edge_unchecked(e, d, 1.into());
+
+        // When:
+        let cycle = find_cycle(&graph).unwrap();
+
+        // Then:
+        assert_eq!(cycle.len(), 2);
+        assert_is_cycle(&graph, &cycle);
+        assert!(!is_dag(&graph));
+    }
+
+    #[test]
+    fn find_cycle_in_dag() {
+        // Given: Graph
+        //
+        //      a  -->  b  -->  d
+        //      |               ^
+        //      '-----> c ------'
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(a, c, 1.into());
+        graph.add_edge_unchecked(b, d, 1.into());
+        graph.add_edge_unchecked(c, d, 1.into());
+
+        // Then:
+        assert!(find_cycle(&graph).is_none());
+        assert!(is_dag(&graph));
+    }
+
+    #[test]
+    fn find_cycle_in_undirected_graph() {
+        // Given: A tree, and then the tree with an edge that closes a cycle.
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let vertices: Vec<usize> = (0..6).map(|_| graph.add_vertex()).collect();
+        for index in 1..6 {
+            graph.add_edge_unchecked(vertices[(index - 1) / 2], vertices[index], 1.into());
+        }
+
+        // Then: Walking an edge back is not a cycle.
+        assert!(find_cycle(&graph).is_none());
+
+        // When:
+        graph.add_edge_unchecked(vertices[3], vertices[4], 1.into());
+        let cycle = find_cycle(&graph).unwrap();
+
+        // Then: 3 - 1 - 4 - 3
+        assert_eq!(cycle.len(), 3);
+        assert_is_cycle(&graph, &cycle);
+    }
+
+    #[test]
+    fn find_cycle_of_parallel_edges_and_loops() {
+        // Given: Undirected graph with two parallel edges.
+        let mut graph = MultiListGraph::init(List::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+
+        // Then:
+        let mut cycle = find_cycle(&graph).unwrap();
+        cycle.sort_unstable();
+        assert_eq!(cycle, vec![b, c]);
+
+        // Given: Directed graph with a loop.
+        let mut graph = MultiListGraph::init(DiList::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, b, 1.into());
+
+        // Then:
+        assert_eq!(find_cycle(&graph), Some(vec![b]));
+        assert!(!is_dag(&graph));
+    }
 }
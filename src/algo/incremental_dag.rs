@@ -0,0 +1,337 @@
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+use anyhow::Result;
+
+use crate::algo::{find_cycle, Error, TopologicalSort};
+use crate::graph::{DirectedEdge, Edge};
+use crate::provide::{Edges, Graph, Neighbors, Vertices};
+
+/// A directed acyclic graph that keeps a topological order of its vertices while edges get inserted.
+///
+/// Edges are inserted with [`try_add_edge_acyclic`](crate::algo::try_add_edge_acyclic).
+/// Insertions that agree with the current order cost O(1). Otherwise only vertices between the two end points in the order are searched
+/// to either find the path that the edge would close into a cycle, or to reorder them (Pearce-Kelly).
+///
+/// ## Generic Parameters
+/// * `W`: **W**eight type associated with edges.
+/// * `E`: **E**dge type that graph uses.
+/// * `G`: **G**raph type.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::DiList;
+/// use prepona::graph::ListGraph;
+/// use prepona::algo::{try_add_edge_acyclic, IncrementalDag};
+///
+/// // Given: Dependencies a --> b --> c.
+/// let mut graph = ListGraph::init(DiList::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let mut dag = IncrementalDag::init(graph).unwrap();
+/// try_add_edge_acyclic(&mut dag, a, b, 1.into()).unwrap();
+/// try_add_edge_acyclic(&mut dag, b, c, 1.into()).unwrap();
+///
+/// // When: c depends on a.
+/// let rejected = try_add_edge_acyclic(&mut dag, c, a, 1.into());
+///
+/// // Then: Path from a to c is reported and the graph is left unchanged.
+/// assert_eq!(rejected, Err(vec![a, b, c]));
+/// assert_eq!(dag.graph().edges_count(), 2);
+/// assert_eq!(dag.topological_order(), vec![a, b, c]);
+/// ```
+pub struct IncrementalDag<W, E: Edge<W>, G> {
+    graph: G,
+
+    // Position of each vertex in the topological order. Positions are unique but not necessarily continuous.
+    order_of: HashMap<usize, usize>,
+    predecessors: HashMap<usize, Vec<usize>>,
+    next_order: usize,
+
+    phantom_w: PhantomData<W>,
+    phantom_e: PhantomData<E>,
+}
+
+impl<W, E, G> IncrementalDag<W, E, G>
+where
+    E: Edge<W>,
+    G: Graph<W, E, DirectedEdge> + Vertices + Neighbors + Edges<W, E>,
+{
+    /// # Arguments
+    /// `graph`: Directed graph to start with.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing the structure that owns `graph`.
+    /// * `Err`: [`CycleDetected`](crate::algo::ErrorKind::CycleDetected) if `graph` is not acyclic.
+    ///
+    /// # Complexity
+    /// O(|V| + |E|)
+    pub fn init(graph: G) -> Result<Self> {
+        if find_cycle(&graph).is_some() {
+            Err(Error::new_cd())?
+        }
+
+        let sorted_vertex_ids = TopologicalSort::init().execute(&graph);
+        let order_of: HashMap<usize, usize> = sorted_vertex_ids
+            .iter()
+            .enumerate()
+            .map(|(order, vertex_id)| (*vertex_id, order))
+            .collect();
+
+        let mut predecessors: HashMap<usize, Vec<usize>> = graph
+            .vertices()
+            .into_iter()
+            .map(|vertex_id| (vertex_id, vec![]))
+            .collect();
+        for (src_id, dst_id, _) in graph.edges() {
+            predecessors.get_mut(&dst_id).unwrap().push(src_id);
+        }
+
+        Ok(IncrementalDag {
+            next_order: sorted_vertex_ids.len(),
+            graph,
+            order_of,
+            predecessors,
+            phantom_w: PhantomData,
+            phantom_e: PhantomData,
+        })
+    }
+
+    /// Adds a new vertex at the end of the topological order.
+    ///
+    /// # Returns
+    /// Id of the new vertex.
+    pub fn add_vertex(&mut self) -> usize {
+        let vertex_id = self.graph.add_vertex();
+
+        self.order_of.insert(vertex_id, self.next_order);
+        self.predecessors.insert(vertex_id, vec![]);
+        self.next_order += 1;
+
+        vertex_id
+    }
+
+    /// # Returns
+    /// Ids of the vertices in topological order: Every edge goes from an earlier vertex to a later one.
+    pub fn topological_order(&self) -> Vec<usize> {
+        let mut vertex_ids: Vec<usize> = self.order_of.keys().copied().collect();
+        vertex_ids.sort_unstable_by_key(|vertex_id| self.order_of[vertex_id]);
+
+        vertex_ids
+    }
+
+    /// # Returns
+    /// The underlying graph.
+    pub fn graph(&self) -> &G {
+        &self.graph
+    }
+
+    /// # Returns
+    /// The underlying graph, consuming the structure.
+    pub fn into_graph(self) -> G {
+        self.graph
+    }
+
+    // Vertices reachable from `start` (or reaching it if `forward` is false) without leaving the order range [`min_order`, `max_order`].
+    // Each visited vertex is mapped to the vertex it was discovered from.
+    fn bounded_search(
+        &self,
+        start: usize,
+        min_order: usize,
+        max_order: usize,
+        forward: bool,
+    ) -> HashMap<usize, usize> {
+        let mut parent_of = HashMap::new();
+        parent_of.insert(start, start);
+        let mut stack = vec![start];
+
+        while let Some(vertex_id) = stack.pop() {
+            let neighbors = if forward {
+                self.graph.neighbors_unchecked(vertex_id)
+            } else {
+                self.predecessors[&vertex_id].clone()
+            };
+
+            for neighbor_id in neighbors {
+                let order = self.order_of[&neighbor_id];
+                if min_order <= order && order <= max_order && !parent_of.contains_key(&neighbor_id)
+                {
+                    parent_of.insert(neighbor_id, vertex_id);
+                    stack.push(neighbor_id);
+                }
+            }
+        }
+
+        parent_of
+    }
+}
+
+/// Adds an edge to `dag` unless it would create a cycle.
+///
+/// # Arguments
+/// * `dag`: Graph to add the edge to.
+/// * `src_id`: Id of the source vertex.
+/// * `dst_id`: Id of the destination vertex.
+/// * `edge`: Edge to add.
+///
+/// # Returns
+/// * `Ok`: Containing id of the added edge.
+/// * `Err`: Containing ids of the vertices on an existing path from `dst_id` to `src_id`, starting with `dst_id` and ending with `src_id`,
+///   which together with the rejected edge would form a cycle. For a self loop it only contains `src_id`. `dag` is left unchanged.
+///
+/// # Complexity
+/// O(1) if `src_id` already comes before `dst_id` in the topological order,
+/// otherwise proportional to the edges of the vertices between `dst_id` and `src_id` in the order.
+///
+/// # Panics
+/// If `src_id` or `dst_id` is not in the graph.
+pub fn try_add_edge_acyclic<W, E, G>(
+    dag: &mut IncrementalDag<W, E, G>,
+    src_id: usize,
+    dst_id: usize,
+    edge: E,
+) -> std::result::Result<usize, Vec<usize>>
+where
+    E: Edge<W>,
+    G: Graph<W, E, DirectedEdge> + Vertices + Neighbors + Edges<W, E>,
+{
+    let src_order = dag.order_of[&src_id];
+    let dst_order = dag.order_of[&dst_id];
+
+    if src_id == dst_id {
+        return Err(vec![src_id]);
+    }
+
+    if src_order > dst_order {
+        let forward = dag.bounded_search(dst_id, dst_order, src_order, true);
+
+        if forward.contains_key(&src_id) {
+            let mut path = vec![src_id];
+            while *path.last().unwrap() != dst_id {
+                path.push(forward[path.last().unwrap()]);
+            }
+            path.reverse();
+
+            return Err(path);
+        }
+
+        let backward = dag.bounded_search(src_id, dst_order, src_order, false);
+
+        // Vertices reaching the source move before the ones reachable from the destination, reusing their positions.
+        let by_order = |vertex_ids: &HashMap<usize, usize>| {
+            let mut vertex_ids: Vec<usize> = vertex_ids.keys().copied().collect();
+            vertex_ids.sort_unstable_by_key(|vertex_id| dag.order_of[vertex_id]);
+            vertex_ids
+        };
+        let moved: Vec<usize> = by_order(&backward)
+            .into_iter()
+            .chain(by_order(&forward))
+            .collect();
+        let mut slots: Vec<usize> = moved
+            .iter()
+            .map(|vertex_id| dag.order_of[vertex_id])
+            .collect();
+        slots.sort_unstable();
+
+        for (vertex_id, slot) in moved.into_iter().zip(slots) {
+            dag.order_of.insert(vertex_id, slot);
+        }
+    }
+
+    let edge_id = dag.graph.add_edge_unchecked(src_id, dst_id, edge);
+    dag.predecessors.get_mut(&dst_id).unwrap().push(src_id);
+
+    Ok(edge_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::{is_dag, ErrorKind};
+    use crate::graph::{ListGraph, MultiListGraph};
+    use crate::misc::rng::Rng;
+    use crate::storage::DiList;
+
+    #[test]
+    fn rejects_cyclic_graph() {
+        // Given: a --> b --> a
+        let mut graph = ListGraph::init(DiList::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, a, 1.into());
+
+        // When:
+        let err = IncrementalDag::init(graph).err().unwrap();
+
+        // Then:
+        assert!(matches!(
+            err.downcast_ref::<Error>().unwrap().kind(),
+            ErrorKind::CycleDetected
+        ));
+    }
+
+    #[test]
+    fn self_loop() {
+        // Given:
+        let mut dag = IncrementalDag::init(ListGraph::init(DiList::<usize>::init())).unwrap();
+        let a = dag.add_vertex();
+
+        // When:
+        let rejected = try_add_edge_acyclic(&mut dag, a, a, 1.into());
+
+        // Then:
+        assert_eq!(rejected, Err(vec![a]));
+        assert_eq!(dag.graph().edges_count(), 0);
+    }
+
+    #[test]
+    fn random_insertions() {
+        let mut rng = Rng::seed_from(3161);
+
+        for _ in 0..5 {
+            // Given: Random edges offered one by one.
+            let mut dag =
+                IncrementalDag::init(MultiListGraph::init(DiList::<usize>::init())).unwrap();
+            for _ in 0..25 {
+                dag.add_vertex();
+            }
+
+            for _ in 0..80 {
+                let src_id = rng.next_below(25);
+                let dst_id = rng.next_below(25);
+
+                // When:
+                let result = try_add_edge_acyclic(&mut dag, src_id, dst_id, 1.into());
+
+                // Then: Either the edge is added, or the reported path is an existing path from dst to src.
+                match result {
+                    Ok(edge_id) => {
+                        assert!(dag.graph().edge(edge_id).is_ok());
+                    }
+                    Err(path) => {
+                        assert_eq!(path.first(), Some(&dst_id));
+                        assert_eq!(path.last(), Some(&src_id));
+                        for pair in path.windows(2) {
+                            assert!(dag.graph().has_any_edge_unchecked(pair[0], pair[1]));
+                        }
+                    }
+                }
+                assert!(is_dag(dag.graph()));
+
+                // And: Order stays topological.
+                let position_of: HashMap<usize, usize> = dag
+                    .topological_order()
+                    .into_iter()
+                    .enumerate()
+                    .map(|(position, vertex_id)| (vertex_id, position))
+                    .collect();
+                for (src_id, dst_id, _) in dag.graph().edges() {
+                    assert!(position_of[&src_id] < position_of[&dst_id]);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,5 @@
+mod vf2;
+mod wl;
+
+pub use vf2::{match_pattern, GraphRole, IsomorphismType, VF2Isomorphism, VF2Mappings};
+pub use wl::{graph_hash, wl_colors};
@@ -0,0 +1,909 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::graph::{Edge, EdgeDir};
+use crate::provide::{Edges, Graph, Vertices};
+
+/// Kind of isomorphism to search for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IsomorphismType {
+    /// Pattern and host must be isomorphic.
+    Graph,
+
+    /// Pattern must be isomorphic to an induced subgraph of the host.
+    /// So two matched host vertices are connected if and only if their pattern vertices are connected.
+    InducedSubgraph,
+
+    /// Pattern must be isomorphic to a subgraph of the host.
+    /// Edges of the pattern must exist in the host but the host can have extra edges between matched vertices.
+    Monomorphism,
+}
+
+/// Role of a graph in a search for a pattern.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphRole {
+    /// Graph to search in.
+    Host,
+
+    /// Graph to search for.
+    Pattern,
+}
+
+// Assigns a class to each distinct label, so labels of both graphs can be compared by their classes.
+struct LabelClasses<L> {
+    labels: Vec<L>,
+}
+
+impl<L: PartialEq> LabelClasses<L> {
+    fn init() -> Self {
+        LabelClasses { labels: vec![] }
+    }
+
+    fn class_of(&mut self, label: L) -> usize {
+        match self.labels.iter().position(|other| *other == label) {
+            Some(class) => class,
+            None => {
+                self.labels.push(label);
+                self.labels.len() - 1
+            }
+        }
+    }
+}
+
+// Adjacency of a graph in terms of continuos ids.
+struct Structure {
+    real_ids: Vec<usize>,
+    outgoing: Vec<Vec<usize>>,
+    incoming: Vec<Vec<usize>>,
+
+    // Class of the label of each vertex.
+    vertex_classes: Vec<usize>,
+
+    // Sorted classes of the labels of edges between each pair of vertices. Parallel edges with the same label are counted once.
+    edges: HashMap<(usize, usize), Vec<usize>>,
+}
+
+impl Structure {
+    fn of<W, E, Dir, G>(graph: &G) -> Self
+    where
+        E: Edge<W>,
+        Dir: EdgeDir,
+        G: Vertices + Edges<W, E> + Graph<W, E, Dir>,
+    {
+        Structure::with_classes(graph, |_| 0, |_| 0)
+    }
+
+    fn with_classes<W, E, Dir, G, VC, EC>(
+        graph: &G,
+        mut vertex_class_of: VC,
+        mut edge_class_of: EC,
+    ) -> Self
+    where
+        E: Edge<W>,
+        Dir: EdgeDir,
+        G: Vertices + Edges<W, E> + Graph<W, E, Dir>,
+        VC: FnMut(usize) -> usize,
+        EC: FnMut(&E) -> usize,
+    {
+        let id_map = graph.continuos_id_map();
+        let vertex_count = graph.vertex_count();
+
+        let real_ids: Vec<usize> = (0..vertex_count)
+            .map(|virt_id| id_map.real_id_of(virt_id))
+            .collect();
+
+        let mut structure = Structure {
+            vertex_classes: real_ids
+                .iter()
+                .map(|real_id| vertex_class_of(*real_id))
+                .collect(),
+            real_ids,
+            outgoing: vec![vec![]; vertex_count],
+            incoming: vec![vec![]; vertex_count],
+            edges: HashMap::new(),
+        };
+
+        // Undirected edges are returned in both directions, so for undirected graphs outgoing and incoming are the same.
+        for (src_id, dst_id, edge) in graph.as_directed_edges() {
+            let (src, dst) = (id_map.virt_id_of(src_id), id_map.virt_id_of(dst_id));
+            let class = edge_class_of(edge);
+
+            match structure.edges.get_mut(&(src, dst)) {
+                Some(classes) => {
+                    if let Err(index) = classes.binary_search(&class) {
+                        classes.insert(index, class);
+                    }
+                }
+                None => {
+                    structure.edges.insert((src, dst), vec![class]);
+                    structure.outgoing[src].push(dst);
+                    structure.incoming[dst].push(src);
+                }
+            }
+        }
+
+        structure
+    }
+
+    fn vertex_count(&self) -> usize {
+        self.real_ids.len()
+    }
+
+    fn has_edge(&self, src: usize, dst: usize) -> bool {
+        self.edges.contains_key(&(src, dst))
+    }
+}
+
+// Matches pattern vertices one by one in a fixed order and backtracks when a partial mapping can not be extended.
+// Search state is kept explicitly so search can be paused after each complete mapping and resumed later.
+struct Matcher {
+    ty: IsomorphismType,
+    host: Structure,
+    pattern: Structure,
+
+    // Order in which pattern vertices get matched.
+    order: Vec<usize>,
+
+    pattern_to_host: Vec<Option<usize>>,
+    host_to_pattern: Vec<Option<usize>>,
+
+    // One frame for each matched depth: (candidates, index of the next candidate to try).
+    frames: Vec<(Vec<usize>, usize)>,
+    is_started: bool,
+    is_exhausted: bool,
+}
+
+impl Matcher {
+    fn init(host: Structure, pattern: Structure, ty: IsomorphismType) -> Self {
+        let is_exhausted = match ty {
+            IsomorphismType::Graph => {
+                host.vertex_count() != pattern.vertex_count()
+                    || host.edges.len() != pattern.edges.len()
+            }
+            IsomorphismType::InducedSubgraph | IsomorphismType::Monomorphism => {
+                host.vertex_count() < pattern.vertex_count()
+                    || host.edges.len() < pattern.edges.len()
+            }
+        };
+
+        Matcher {
+            ty,
+            order: Matcher::order_of(&pattern),
+            pattern_to_host: vec![None; pattern.vertex_count()],
+            host_to_pattern: vec![None; host.vertex_count()],
+            frames: vec![],
+            is_started: false,
+            is_exhausted,
+            host,
+            pattern,
+        }
+    }
+
+    // Vertices are ordered in a breadth-first manner starting from the vertex with the highest degree in each component.
+    // So each vertex(except the first one in each component) has an already matched neighbor which limits its candidates.
+    fn order_of(pattern: &Structure) -> Vec<usize> {
+        let degree_of = |v: usize| pattern.outgoing[v].len() + pattern.incoming[v].len();
+
+        let mut by_degree: Vec<usize> = (0..pattern.vertex_count()).collect();
+        by_degree.sort_by_key(|v| std::cmp::Reverse(degree_of(*v)));
+
+        let mut is_ordered = vec![false; pattern.vertex_count()];
+        let mut order = Vec::with_capacity(pattern.vertex_count());
+
+        for root in by_degree {
+            if is_ordered[root] {
+                continue;
+            }
+
+            is_ordered[root] = true;
+            let mut queue = VecDeque::from(vec![root]);
+
+            while let Some(v) = queue.pop_front() {
+                order.push(v);
+
+                let mut neighbors: Vec<usize> = pattern.outgoing[v]
+                    .iter()
+                    .chain(pattern.incoming[v].iter())
+                    .copied()
+                    .filter(|n| !is_ordered[*n])
+                    .collect();
+                neighbors.sort_by_key(|n| std::cmp::Reverse(degree_of(*n)));
+                neighbors.dedup();
+
+                for n in neighbors {
+                    if !is_ordered[n] {
+                        is_ordered[n] = true;
+                        queue.push_back(n);
+                    }
+                }
+            }
+        }
+
+        order
+    }
+
+    fn candidates_of(&self, depth: usize) -> Vec<usize> {
+        let p = self.order[depth];
+
+        // Candidates are limited to the neighbors of the host vertex that a matched neighbor of p is mapped to.
+        let from_outgoing = self.pattern.incoming[p]
+            .iter()
+            .find_map(|n| self.pattern_to_host[*n])
+            .map(|h| &self.host.outgoing[h]);
+        let from_incoming = self.pattern.outgoing[p]
+            .iter()
+            .find_map(|n| self.pattern_to_host[*n])
+            .map(|h| &self.host.incoming[h]);
+
+        match from_outgoing.or(from_incoming) {
+            Some(neighbors) => neighbors
+                .iter()
+                .copied()
+                .filter(|h| self.host_to_pattern[*h].is_none())
+                .collect(),
+            None => (0..self.host.vertex_count())
+                .filter(|h| self.host_to_pattern[*h].is_none())
+                .collect(),
+        }
+    }
+
+    fn is_feasible(&self, p: usize, h: usize) -> bool {
+        let (p_out, p_in) = (
+            self.pattern.outgoing[p].len(),
+            self.pattern.incoming[p].len(),
+        );
+        let (h_out, h_in) = (self.host.outgoing[h].len(), self.host.incoming[h].len());
+
+        let degrees_match = match self.ty {
+            IsomorphismType::Graph => p_out == h_out && p_in == h_in,
+            IsomorphismType::InducedSubgraph | IsomorphismType::Monomorphism => {
+                p_out <= h_out && p_in <= h_in
+            }
+        };
+        if !degrees_match {
+            return false;
+        }
+
+        if self.pattern.vertex_classes[p] != self.host.vertex_classes[h] {
+            return false;
+        }
+
+        let (p_has_loop, h_has_loop) = (self.pattern.has_edge(p, p), self.host.has_edge(h, h));
+        let loops_match = match self.ty {
+            IsomorphismType::Monomorphism => !p_has_loop || self.edges_match((p, p), (h, h)),
+            _ => p_has_loop == h_has_loop && (!p_has_loop || self.edges_match((p, p), (h, h))),
+        };
+        if !loops_match {
+            return false;
+        }
+
+        // Every edge between p and a matched pattern vertex must exist between their images in the host, with matching labels.
+        let pattern_edges_exist = self.pattern.outgoing[p]
+            .iter()
+            .filter_map(|n| self.pattern_to_host[*n].map(|hn| (*n, hn)))
+            .all(|(n, hn)| self.edges_match((p, n), (h, hn)))
+            && self.pattern.incoming[p]
+                .iter()
+                .filter_map(|n| self.pattern_to_host[*n].map(|hn| (*n, hn)))
+                .all(|(n, hn)| self.edges_match((n, p), (hn, h)));
+
+        if self.ty == IsomorphismType::Monomorphism {
+            return pattern_edges_exist;
+        }
+
+        // And vice versa, since matched subgraphs must be induced.
+        let host_edges_exist = self.host.outgoing[h]
+            .iter()
+            .filter_map(|n| self.host_to_pattern[*n])
+            .all(|pn| self.pattern.has_edge(p, pn))
+            && self.host.incoming[h]
+                .iter()
+                .filter_map(|n| self.host_to_pattern[*n])
+                .all(|pn| self.pattern.has_edge(pn, p));
+
+        pattern_edges_exist && host_edges_exist
+    }
+
+    // Whether labels of the pattern edges between a pair of vertices match the labels of the host edges between their images.
+    // Host edges must have exactly the same labels, unless searching for monomorphisms which allows extra labels in the host.
+    fn edges_match(&self, pattern_edge: (usize, usize), host_edge: (usize, usize)) -> bool {
+        let pattern_classes = &self.pattern.edges[&pattern_edge];
+
+        match self.host.edges.get(&host_edge) {
+            None => false,
+            Some(host_classes) => match self.ty {
+                IsomorphismType::Monomorphism => pattern_classes
+                    .iter()
+                    .all(|class| host_classes.binary_search(class).is_ok()),
+                _ => pattern_classes == host_classes,
+            },
+        }
+    }
+
+    fn map(&mut self, p: usize, h: usize) {
+        self.pattern_to_host[p] = Some(h);
+        self.host_to_pattern[h] = Some(p);
+    }
+
+    fn unmap(&mut self, p: usize) {
+        if let Some(h) = self.pattern_to_host[p].take() {
+            self.host_to_pattern[h] = None;
+        }
+    }
+
+    fn current_mapping(&self) -> HashMap<usize, usize> {
+        self.pattern_to_host
+            .iter()
+            .enumerate()
+            .map(|(p, h)| (self.pattern.real_ids[p], self.host.real_ids[h.unwrap()]))
+            .collect()
+    }
+
+    fn next_mapping(&mut self) -> Option<HashMap<usize, usize>> {
+        if self.is_exhausted {
+            return None;
+        }
+
+        if !self.is_started {
+            self.is_started = true;
+
+            if self.order.is_empty() {
+                self.is_exhausted = true;
+                return Some(HashMap::new());
+            }
+
+            let candidates = self.candidates_of(0);
+            self.frames.push((candidates, 0));
+        }
+
+        while !self.frames.is_empty() {
+            let depth = self.frames.len() - 1;
+            let p = self.order[depth];
+
+            let (candidates, index) = &mut self.frames[depth];
+            if *index == candidates.len() {
+                // All candidates of this depth are tried, so backtrack.
+                self.frames.pop();
+                if depth > 0 {
+                    self.unmap(self.order[depth - 1]);
+                }
+                continue;
+            }
+
+            let h = candidates[*index];
+            *index += 1;
+
+            if !self.is_feasible(p, h) {
+                continue;
+            }
+
+            self.map(p, h);
+
+            if depth + 1 == self.order.len() {
+                let mapping = self.current_mapping();
+                self.unmap(p);
+
+                return Some(mapping);
+            }
+
+            let candidates = self.candidates_of(depth + 1);
+            self.frames.push((candidates, 0));
+        }
+
+        self.is_exhausted = true;
+        None
+    }
+}
+
+/// Finds [isomorphisms](https://en.wikipedia.org/wiki/Graph_isomorphism) between a pattern graph and a host graph.
+///
+/// Uses the state space search of the [VF2](https://doi.org/10.1109/TPAMI.2004.75) algorithm:
+/// Pattern vertices are matched one by one and a candidate host vertex is only accepted if the partial mapping stays consistent
+/// with the edges between already matched vertices. Pattern vertices are matched in breadth-first order, so candidates of each vertex are limited to neighbors of already matched vertices.
+///
+/// Mappings are from pattern vertex ids to host vertex ids. Check out [`IsomorphismType`](crate::algo::IsomorphismType) for the supported kinds of matching.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+/// use prepona::algo::{IsomorphismType, VF2Isomorphism};
+///
+/// // Given: Host graph
+/// //
+/// //      a  ---  b
+/// //      |       |
+/// //      d  ---  c  ---  e
+/// //
+/// let mut host = MatGraph::init(Mat::<usize>::init());
+/// let a = host.add_vertex();
+/// let b = host.add_vertex();
+/// let c = host.add_vertex();
+/// let d = host.add_vertex();
+/// let e = host.add_vertex();
+/// host.add_edge_unchecked(a, b, 1.into());
+/// host.add_edge_unchecked(b, c, 1.into());
+/// host.add_edge_unchecked(c, d, 1.into());
+/// host.add_edge_unchecked(d, a, 1.into());
+/// host.add_edge_unchecked(c, e, 1.into());
+///
+/// // And: Pattern graph which is a path of length 2: x --- y --- z
+/// let mut pattern = MatGraph::init(Mat::<usize>::init());
+/// let x = pattern.add_vertex();
+/// let y = pattern.add_vertex();
+/// let z = pattern.add_vertex();
+/// pattern.add_edge_unchecked(x, y, 1.into());
+/// pattern.add_edge_unchecked(y, z, 1.into());
+///
+/// // When: Searching for induced paths of length 2.
+/// let mapping = VF2Isomorphism::init(&host, &pattern, IsomorphismType::InducedSubgraph).find_mapping().unwrap();
+///
+/// // Then:
+/// assert!(host.has_any_edge_unchecked(mapping[&x], mapping[&y]));
+/// assert!(host.has_any_edge_unchecked(mapping[&y], mapping[&z]));
+///
+/// // Each path is found in both directions.
+/// let count = VF2Isomorphism::init(&host, &pattern, IsomorphismType::InducedSubgraph).mappings().count();
+/// assert_eq!(count, 2 * 6);
+/// ```
+pub struct VF2Isomorphism {
+    matcher: Matcher,
+}
+
+impl VF2Isomorphism {
+    /// Initializes the structure.
+    ///
+    /// # Arguments
+    /// * `host`: Graph to search in.
+    /// * `pattern`: Graph to search for.
+    /// * `ty`: Kind of isomorphism to search for.
+    pub fn init<W1, E1, G1, W2, E2, G2, Dir>(host: &G1, pattern: &G2, ty: IsomorphismType) -> Self
+    where
+        E1: Edge<W1>,
+        E2: Edge<W2>,
+        Dir: EdgeDir,
+        G1: Vertices + Edges<W1, E1> + Graph<W1, E1, Dir>,
+        G2: Vertices + Edges<W2, E2> + Graph<W2, E2, Dir>,
+    {
+        VF2Isomorphism {
+            matcher: Matcher::init(Structure::of(host), Structure::of(pattern), ty),
+        }
+    }
+
+    /// # Returns
+    /// `true` if there is at least one isomorphism.
+    pub fn execute(self) -> bool {
+        self.find_mapping().is_some()
+    }
+
+    /// # Returns
+    /// * `Some`: Containing the first isomorphism that is found as a map from pattern vertex ids to host vertex ids.
+    /// * `None`: If there is no isomorphism.
+    pub fn find_mapping(mut self) -> Option<HashMap<usize, usize>> {
+        self.matcher.next_mapping()
+    }
+
+    /// # Returns
+    /// An iterator over all isomorphisms. Isomorphisms are found lazily, so the search can be stopped at any point.
+    pub fn mappings(self) -> VF2Mappings {
+        VF2Mappings {
+            matcher: self.matcher,
+        }
+    }
+}
+
+/// Iterator over isomorphisms found by [`VF2Isomorphism`](crate::algo::VF2Isomorphism).
+///
+/// Each item is a map from pattern vertex ids to host vertex ids.
+pub struct VF2Mappings {
+    matcher: Matcher,
+}
+
+impl Iterator for VF2Mappings {
+    type Item = HashMap<usize, usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.matcher.next_mapping()
+    }
+}
+
+/// Finds induced subgraphs of the host that match the pattern, respecting labels of vertices and edges.
+///
+/// A pattern vertex can only be mapped to a host vertex with an equal label, and labels of the edges between two pattern vertices
+/// must be equal to labels of the edges between their images in the host. Parallel edges with the same label are considered as one.
+/// Matching is the same as [`VF2Isomorphism`](crate::algo::VF2Isomorphism) with [`InducedSubgraph`](crate::algo::IsomorphismType::InducedSubgraph), which is used when all labels are the same.
+///
+/// ## Generic Parameters
+/// * **L**: Type of vertex labels.
+/// * **M**: Type of edge labels.
+///
+/// # Arguments
+/// * `host`: Graph to search in.
+/// * `pattern`: Graph to search for.
+/// * `node_label_fn`: Maps a vertex id in the graph with the given role to the label of the vertex.
+/// * `edge_label_fn`: Maps an edge of either graph to its label.
+///
+/// # Returns
+/// An iterator over all matches, each one a map from pattern vertex ids to host vertex ids. Matches are found lazily.
+///
+/// # Complexity
+/// Labels are compared by equality, so assigning labels takes O(|V| + |E|) times the number of distinct labels.
+/// Search takes exponential time in the worst case.
+///
+/// # Examples
+/// ```
+/// use std::collections::HashMap;
+/// use prepona::prelude::*;
+/// use prepona::storage::DiList;
+/// use prepona::graph::ListGraph;
+/// use prepona::algo::{match_pattern, GraphRole};
+///
+/// // Given: Host graph with the kind of each vertex.
+/// //
+/// //      alice  --follows-->  bob  --owns-->  repo
+/// //        |                                   ^
+/// //        |______________owns_________________|
+/// //
+/// let mut host = ListGraph::init(DiList::<&str>::init());
+/// let alice = host.add_vertex();
+/// let bob = host.add_vertex();
+/// let repo = host.add_vertex();
+/// host.add_edge_unchecked(alice, bob, "follows".into());
+/// host.add_edge_unchecked(bob, repo, "owns".into());
+/// host.add_edge_unchecked(alice, repo, "owns".into());
+/// let host_kinds: HashMap<usize, &str> = vec![(alice, "user"), (bob, "user"), (repo, "repo")].into_iter().collect();
+///
+/// // And: Pattern of a user that owns a repo.
+/// let mut pattern = ListGraph::init(DiList::<&str>::init());
+/// let user = pattern.add_vertex();
+/// let project = pattern.add_vertex();
+/// pattern.add_edge_unchecked(user, project, "owns".into());
+/// let pattern_kinds: HashMap<usize, &str> = vec![(user, "user"), (project, "repo")].into_iter().collect();
+///
+/// // When:
+/// let mut owners: Vec<usize> = match_pattern(
+///     &host,
+///     &pattern,
+///     |role, vertex_id| match role {
+///         GraphRole::Host => host_kinds[&vertex_id],
+///         GraphRole::Pattern => pattern_kinds[&vertex_id],
+///     },
+///     |edge| *edge.get_weight(),
+/// )
+/// .map(|mapping| mapping[&user])
+/// .collect();
+///
+/// // Then: The follows edge is never matched with owns.
+/// owners.sort_unstable();
+/// assert_eq!(owners, vec![alice, bob]);
+/// ```
+pub fn match_pattern<W, E, Dir, G1, G2, L, M, NF, EF>(
+    host: &G1,
+    pattern: &G2,
+    node_label_fn: NF,
+    edge_label_fn: EF,
+) -> VF2Mappings
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G1: Vertices + Edges<W, E> + Graph<W, E, Dir>,
+    G2: Vertices + Edges<W, E> + Graph<W, E, Dir>,
+    L: PartialEq,
+    M: PartialEq,
+    NF: Fn(GraphRole, usize) -> L,
+    EF: Fn(&E) -> M,
+{
+    let mut vertex_classes = LabelClasses::init();
+    let mut edge_classes = LabelClasses::init();
+
+    let host = Structure::with_classes(
+        host,
+        |vertex_id| vertex_classes.class_of(node_label_fn(GraphRole::Host, vertex_id)),
+        |edge| edge_classes.class_of(edge_label_fn(edge)),
+    );
+    let pattern = Structure::with_classes(
+        pattern,
+        |vertex_id| vertex_classes.class_of(node_label_fn(GraphRole::Pattern, vertex_id)),
+        |edge| edge_classes.class_of(edge_label_fn(edge)),
+    );
+
+    VF2Mappings {
+        matcher: Matcher::init(host, pattern, IsomorphismType::InducedSubgraph),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{MatGraph, MultiListGraph};
+    use crate::storage::{DiList, DiMat, Mat};
+
+    fn cycle(vertex_count: usize) -> MatGraph<usize, crate::graph::UndirectedEdge> {
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let vertices: Vec<usize> = (0..vertex_count).map(|_| graph.add_vertex()).collect();
+        for i in 0..vertex_count {
+            graph.add_edge_unchecked(vertices[i], vertices[(i + 1) % vertex_count], 1.into());
+        }
+
+        graph
+    }
+
+    fn path(vertex_count: usize) -> MatGraph<usize, crate::graph::UndirectedEdge> {
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let vertices: Vec<usize> = (0..vertex_count).map(|_| graph.add_vertex()).collect();
+        for i in 1..vertex_count {
+            graph.add_edge_unchecked(vertices[i - 1], vertices[i], 1.into());
+        }
+
+        graph
+    }
+
+    #[test]
+    fn empty_graphs() {
+        // Given: Two empty graphs.
+        let host = MatGraph::init(Mat::<usize>::init());
+        let pattern = MatGraph::init(Mat::<usize>::init());
+
+        // When: Searching for isomorphisms.
+        let mappings: Vec<_> = VF2Isomorphism::init(&host, &pattern, IsomorphismType::Graph)
+            .mappings()
+            .collect();
+
+        // Then: There is exactly one empty mapping.
+        assert_eq!(mappings, vec![HashMap::new()]);
+    }
+
+    #[test]
+    fn automorphisms_of_cycle() {
+        // Given: Cycle of length 5.
+        let host = cycle(5);
+        let pattern = cycle(5);
+
+        // When: Enumerating isomorphisms.
+        let mappings: Vec<_> = VF2Isomorphism::init(&host, &pattern, IsomorphismType::Graph)
+            .mappings()
+            .collect();
+
+        // Then: There are 5 rotations in 2 directions.
+        assert_eq!(mappings.len(), 10);
+        for mapping in &mappings {
+            for (src_id, dst_id, _) in pattern.edges() {
+                assert!(host.has_any_edge_unchecked(mapping[&src_id], mapping[&dst_id]));
+            }
+        }
+    }
+
+    #[test]
+    fn not_isomorphic() {
+        // Given: Cycle and path of the same length.
+        let host = cycle(4);
+        let pattern = path(4);
+
+        // Then:
+        assert!(!VF2Isomorphism::init(&host, &pattern, IsomorphismType::Graph).execute());
+        // Path of length 4 is not an induced subgraph of cycle of length 4.
+        assert!(!VF2Isomorphism::init(&host, &pattern, IsomorphismType::InducedSubgraph).execute());
+        // But path of length 3 is.
+        assert!(VF2Isomorphism::init(&host, &path(3), IsomorphismType::InducedSubgraph).execute());
+    }
+
+    #[test]
+    fn induced_subgraph() {
+        // Given: Triangle as host and path of length 3 as pattern.
+        let host = cycle(3);
+        let pattern = path(3);
+
+        // Then: Path is not induced because the triangle has the extra edge.
+        assert!(
+            VF2Isomorphism::init(&host, &pattern, IsomorphismType::InducedSubgraph)
+                .find_mapping()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn monomorphism() {
+        // Given: Triangle as host and path of length 3 as pattern.
+        let host = cycle(3);
+        let pattern = path(3);
+
+        // When: Enumerating monomorphisms.
+        let mappings: Vec<_> = VF2Isomorphism::init(&host, &pattern, IsomorphismType::Monomorphism)
+            .mappings()
+            .collect();
+
+        // Then: Extra edge of the triangle is ignored, so every permutation of the triangle is a match.
+        assert_eq!(mappings.len(), 6);
+        for mapping in &mappings {
+            for (src_id, dst_id, _) in pattern.edges() {
+                assert!(host.has_any_edge_unchecked(mapping[&src_id], mapping[&dst_id]));
+            }
+        }
+    }
+
+    #[test]
+    fn monomorphism_of_cycle() {
+        // Given: Complete graph with 4 vertices.
+        let mut host = MatGraph::init(Mat::<usize>::init());
+        let vertices: Vec<usize> = (0..4).map(|_| host.add_vertex()).collect();
+        for i in 0..4 {
+            for j in i + 1..4 {
+                host.add_edge_unchecked(vertices[i], vertices[j], 1.into());
+            }
+        }
+
+        // Then: Cycle of length 4 is a subgraph of it but not an induced one.
+        assert!(VF2Isomorphism::init(&host, &cycle(4), IsomorphismType::Monomorphism).execute());
+        assert!(
+            !VF2Isomorphism::init(&host, &cycle(4), IsomorphismType::InducedSubgraph).execute()
+        );
+        // And: Every ordering of the vertices is a hamiltonian cycle, 4! in total.
+        assert_eq!(
+            VF2Isomorphism::init(&host, &cycle(4), IsomorphismType::Monomorphism)
+                .mappings()
+                .count(),
+            24
+        );
+    }
+
+    #[test]
+    fn directed_graphs() {
+        // Given: Directed host
+        //
+        //      a  -->  b  -->  c
+        //      ^               |
+        //      |_______________|
+        //
+        let mut host = MatGraph::init(DiMat::<usize>::init());
+        let a = host.add_vertex();
+        let b = host.add_vertex();
+        let c = host.add_vertex();
+        host.add_edge_unchecked(a, b, 1.into());
+        host.add_edge_unchecked(b, c, 1.into());
+        host.add_edge_unchecked(c, a, 1.into());
+
+        // And: Pattern x --> y.
+        let mut pattern = MatGraph::init(DiMat::<usize>::init());
+        let x = pattern.add_vertex();
+        let y = pattern.add_vertex();
+        pattern.add_edge_unchecked(x, y, 1.into());
+
+        // When: Enumerating induced subgraphs.
+        let mut mappings: Vec<(usize, usize)> =
+            VF2Isomorphism::init(&host, &pattern, IsomorphismType::InducedSubgraph)
+                .mappings()
+                .map(|mapping| (mapping[&x], mapping[&y]))
+                .collect();
+
+        // Then: Each edge is matched once in its own direction.
+        mappings.sort_unstable();
+        assert_eq!(mappings, vec![(a, b), (b, c), (c, a)]);
+    }
+
+    #[test]
+    fn disconnected_pattern() {
+        // Given: Path of length 4 as host and two isolated vertices as pattern.
+        let host = path(4);
+        let mut pattern = MatGraph::init(Mat::<usize>::init());
+        pattern.add_vertex();
+        pattern.add_vertex();
+
+        // When: Enumerating induced subgraphs.
+        let count = VF2Isomorphism::init(&host, &pattern, IsomorphismType::InducedSubgraph)
+            .mappings()
+            .count();
+
+        // Then: Each non adjacent pair in both orders.
+        assert_eq!(count, 2 * 3);
+    }
+
+    #[test]
+    fn match_pattern_with_vertex_labels() {
+        // Given: Cycle of length 4 with alternating colors, and a path of length 2 whose middle vertex has the odd color.
+        let host = cycle(4);
+        let pattern = path(3);
+
+        // When:
+        let mappings: Vec<_> = match_pattern(
+            &host,
+            &pattern,
+            |role, vertex_id| match role {
+                GraphRole::Host => vertex_id % 2,
+                GraphRole::Pattern => usize::from(vertex_id == 1),
+            },
+            |_| (),
+        )
+        .collect();
+
+        // Then: Middle vertex is mapped to one of the two odd vertices, in both directions.
+        assert_eq!(mappings.len(), 4);
+        for mapping in &mappings {
+            assert_eq!(mapping[&1] % 2, 1);
+            assert_eq!(mapping[&0] % 2, 0);
+        }
+    }
+
+    #[test]
+    fn match_pattern_with_edge_labels() {
+        // Given: Host with a loop and parallel edges
+        //
+        //      a  ==1,2==>  b  --1-->  c  --2-->  c
+        //
+        let mut host = MultiListGraph::init(DiList::<usize>::init());
+        let a = host.add_vertex();
+        let b = host.add_vertex();
+        let c = host.add_vertex();
+        host.add_edge_unchecked(a, b, 1.into());
+        host.add_edge_unchecked(a, b, 2.into());
+        host.add_edge_unchecked(a, b, 1.into());
+        host.add_edge_unchecked(b, c, 1.into());
+        host.add_edge_unchecked(c, c, 2.into());
+
+        // And: Pattern x --1--> y.
+        let mut pattern = MultiListGraph::init(DiList::<usize>::init());
+        let x = pattern.add_vertex();
+        let y = pattern.add_vertex();
+        pattern.add_edge_unchecked(x, y, 1.into());
+
+        // When:
+        let mappings: Vec<_> = match_pattern(
+            &host,
+            &pattern,
+            |_, _| (),
+            |edge| edge.get_weight().unwrap(),
+        )
+        .map(|mapping| (mapping[&x], mapping[&y]))
+        .collect();
+
+        // Then: a --> b has an extra label and c has a loop, so neither is matched.
+        assert!(mappings.is_empty());
+
+        // When: Adding the labels of a --> b to the pattern.
+        pattern.add_edge_unchecked(x, y, 2.into());
+        let mappings: Vec<_> = match_pattern(
+            &host,
+            &pattern,
+            |_, _| (),
+            |edge| edge.get_weight().unwrap(),
+        )
+        .map(|mapping| (mapping[&x], mapping[&y]))
+        .collect();
+
+        // Then:
+        assert_eq!(mappings, vec![(a, b)]);
+
+        // When: Pattern is a single vertex with a loop labeled 2.
+        let mut pattern = MultiListGraph::init(DiList::<usize>::init());
+        let z = pattern.add_vertex();
+        pattern.add_edge_unchecked(z, z, 2.into());
+        let mappings: Vec<_> = match_pattern(
+            &host,
+            &pattern,
+            |_, _| (),
+            |edge| edge.get_weight().unwrap(),
+        )
+        .map(|mapping| mapping[&z])
+        .collect();
+
+        // Then:
+        assert_eq!(mappings, vec![c]);
+    }
+
+    #[test]
+    fn match_pattern_without_labels_is_induced_subgraph_search() {
+        // Given:
+        let host = cycle(5);
+        let pattern = path(3);
+
+        // When:
+        let count = match_pattern(&host, &pattern, |_, _| 0, |_| 0).count();
+
+        // Then:
+        assert_eq!(
+            count,
+            VF2Isomorphism::init(&host, &pattern, IsomorphismType::InducedSubgraph)
+                .mappings()
+                .count()
+        );
+    }
+}
@@ -0,0 +1,244 @@
+use std::collections::HashMap;
+
+use crate::graph::{Edge, EdgeDir};
+use crate::misc::rng::mix;
+use crate::provide::{Edges, Graph, Vertices};
+
+/// Computes the color of each vertex after `iterations` rounds of [Weisfeiler-Lehman](https://en.wikipedia.org/wiki/Weisfeiler_Leman_graph_isomorphism_test) refinement.
+///
+/// Initially each vertex is colored by its out and in degree. In each round, color of a vertex is replaced by
+/// a hash of its current color together with the sorted colors of its out and in neighbors.
+/// Isomorphic graphs produce the same colors for vertices that are mapped to each other. Edge weights are ignored.
+///
+/// # Arguments
+/// * `graph`: Graph to compute the colors of.
+/// * `iterations`: Number of refinement rounds.
+///
+/// # Returns
+/// Map from id of each vertex to its color.
+pub fn wl_colors<W, E, Dir, G>(graph: &G, iterations: usize) -> HashMap<usize, u64>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Vertices + Edges<W, E> + Graph<W, E, Dir>,
+{
+    let (real_ids, rounds) = refine(graph, iterations);
+
+    let last_round = rounds.last().unwrap();
+
+    real_ids
+        .into_iter()
+        .zip(last_round.iter().copied())
+        .collect()
+}
+
+/// Computes a hash of the graph based on [Weisfeiler-Lehman](https://en.wikipedia.org/wiki/Weisfeiler_Leman_graph_isomorphism_test) refinement.
+///
+/// The hash is computed from the histogram of vertex colors in every round of [`wl_colors`](crate::algo::wl_colors), so it does not depend on the ids of the vertices.
+/// Isomorphic graphs always have the same hash, so different hashes prove that graphs are not isomorphic.
+/// The reverse does not hold: Some non isomorphic graphs(like regular graphs with the same degree and number of vertices) can not be distinguished by this refinement.
+/// So graphs with equal hashes must still be checked using [`VF2Isomorphism`](crate::algo::VF2Isomorphism) if an exact answer is needed.
+///
+/// Hashes are deterministic and do not depend on the platform, so they can be stored and compared later.
+///
+/// # Arguments
+/// * `graph`: Graph to compute the hash of.
+/// * `iterations`: Number of refinement rounds.
+///
+/// # Returns
+/// Hash of the graph.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+/// use prepona::algo::graph_hash;
+///
+/// // Given: Path a --- b --- c and path y --- x --- z which only differ in ids.
+/// let mut graph1 = MatGraph::init(Mat::<usize>::init());
+/// let a = graph1.add_vertex();
+/// let b = graph1.add_vertex();
+/// let c = graph1.add_vertex();
+/// graph1.add_edge_unchecked(a, b, 1.into());
+/// graph1.add_edge_unchecked(b, c, 1.into());
+///
+/// let mut graph2 = MatGraph::init(Mat::<usize>::init());
+/// let x = graph2.add_vertex();
+/// let y = graph2.add_vertex();
+/// let z = graph2.add_vertex();
+/// graph2.add_edge_unchecked(y, x, 1.into());
+/// graph2.add_edge_unchecked(x, z, 1.into());
+///
+/// // Then:
+/// assert_eq!(graph_hash(&graph1, 3), graph_hash(&graph2, 3));
+///
+/// // When: Closing the first path into a triangle.
+/// graph1.add_edge_unchecked(c, a, 1.into());
+///
+/// // Then:
+/// assert_ne!(graph_hash(&graph1, 3), graph_hash(&graph2, 3));
+/// ```
+pub fn graph_hash<W, E, Dir, G>(graph: &G, iterations: usize) -> u64
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Vertices + Edges<W, E> + Graph<W, E, Dir>,
+{
+    let (real_ids, rounds) = refine(graph, iterations);
+
+    let mut hash = combine(mix(Dir::is_directed() as u64), real_ids.len() as u64);
+
+    for mut colors in rounds {
+        colors.sort_unstable();
+
+        hash = colors.into_iter().fold(hash, combine);
+    }
+
+    hash
+}
+
+// Returns real id of each vertex and colors of vertices in each round, including the initial coloring.
+fn refine<W, E, Dir, G>(graph: &G, iterations: usize) -> (Vec<usize>, Vec<Vec<u64>>)
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Vertices + Edges<W, E> + Graph<W, E, Dir>,
+{
+    let id_map = graph.continuos_id_map();
+    let vertex_count = graph.vertex_count();
+
+    let mut outgoing = vec![vec![]; vertex_count];
+    let mut incoming = vec![vec![]; vertex_count];
+    for (src_id, dst_id, _) in graph.as_directed_edges() {
+        let (src, dst) = (id_map.virt_id_of(src_id), id_map.virt_id_of(dst_id));
+
+        outgoing[src].push(dst);
+        incoming[dst].push(src);
+    }
+
+    let initial_colors = (0..vertex_count)
+        .map(|v| combine(mix(outgoing[v].len() as u64), incoming[v].len() as u64))
+        .collect();
+    let mut rounds: Vec<Vec<u64>> = vec![initial_colors];
+
+    for _ in 0..iterations {
+        let colors = rounds.last().unwrap();
+
+        let next_colors = (0..vertex_count)
+            .map(|v| {
+                let out_hash = neighborhood_hash(&outgoing[v], colors);
+                let in_hash = neighborhood_hash(&incoming[v], colors);
+
+                combine(combine(colors[v], out_hash), in_hash)
+            })
+            .collect();
+
+        rounds.push(next_colors);
+    }
+
+    let real_ids = (0..vertex_count)
+        .map(|virt_id| id_map.real_id_of(virt_id))
+        .collect();
+
+    (real_ids, rounds)
+}
+
+// Hash of the multiset of colors of the given neighbors.
+fn neighborhood_hash(neighbors: &[usize], colors: &[u64]) -> u64 {
+    let mut neighbor_colors: Vec<u64> = neighbors.iter().map(|n| colors[*n]).collect();
+    neighbor_colors.sort_unstable();
+
+    neighbor_colors
+        .into_iter()
+        .fold(mix(neighbors.len() as u64), combine)
+}
+
+fn combine(hash: u64, value: u64) -> u64 {
+    mix(hash.rotate_left(17) ^ mix(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{DirectedEdge, MatGraph, UndirectedEdge};
+    use crate::storage::{DiMat, Mat};
+
+    fn undirected(
+        vertex_count: usize,
+        edges: &[(usize, usize)],
+    ) -> MatGraph<usize, UndirectedEdge> {
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let vertices: Vec<usize> = (0..vertex_count).map(|_| graph.add_vertex()).collect();
+        for (src, dst) in edges {
+            graph.add_edge_unchecked(vertices[*src], vertices[*dst], 1.into());
+        }
+
+        graph
+    }
+
+    #[test]
+    fn relabeled_graphs_have_same_hash() {
+        // Given: Two labelings of the same graph, a square with a tail.
+        let graph1 = undirected(5, &[(0, 1), (1, 2), (2, 3), (3, 0), (3, 4)]);
+        let graph2 = undirected(5, &[(4, 2), (2, 0), (0, 1), (1, 4), (1, 3)]);
+
+        // Then:
+        assert_eq!(graph_hash(&graph1, 4), graph_hash(&graph2, 4));
+    }
+
+    #[test]
+    fn different_graphs_have_different_hashes() {
+        // Given: Cycle of length 4 and path of length 4.
+        let cycle = undirected(4, &[(0, 1), (1, 2), (2, 3), (3, 0)]);
+        let path = undirected(4, &[(0, 1), (1, 2), (2, 3)]);
+
+        // Then:
+        assert_ne!(graph_hash(&cycle, 3), graph_hash(&path, 3));
+    }
+
+    #[test]
+    fn regular_graphs_are_not_distinguished() {
+        // Given: Cycle of length 6 and two disjoint triangles.
+        let cycle = undirected(6, &[(0, 1), (1, 2), (2, 3), (3, 4), (4, 5), (5, 0)]);
+        let triangles = undirected(6, &[(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3)]);
+
+        // Then: Refinement can not tell 2-regular graphs apart.
+        assert_eq!(graph_hash(&cycle, 5), graph_hash(&triangles, 5));
+    }
+
+    #[test]
+    fn colors_of_path() {
+        // Given: Path of length 5.
+        let path = undirected(5, &[(0, 1), (1, 2), (2, 3), (3, 4)]);
+
+        // When: Refining twice.
+        let colors = wl_colors(&path, 2);
+
+        // Then: Vertices symmetric around the center share colors and others differ.
+        assert_eq!(colors[&0], colors[&4]);
+        assert_eq!(colors[&1], colors[&3]);
+        assert_ne!(colors[&0], colors[&1]);
+        assert_ne!(colors[&1], colors[&2]);
+        assert_ne!(colors[&0], colors[&2]);
+    }
+
+    #[test]
+    fn direction_matters() {
+        // Given: Directed paths a --> b --> c and a --> b <-- c.
+        let mut chain = MatGraph::<usize, DirectedEdge>::init(DiMat::<usize>::init());
+        let mut sink = MatGraph::<usize, DirectedEdge>::init(DiMat::<usize>::init());
+        for graph in [&mut chain, &mut sink].iter_mut() {
+            graph.add_vertex();
+            graph.add_vertex();
+            graph.add_vertex();
+        }
+        chain.add_edge_unchecked(0, 1, 1.into());
+        chain.add_edge_unchecked(1, 2, 1.into());
+        sink.add_edge_unchecked(0, 1, 1.into());
+        sink.add_edge_unchecked(2, 1, 1.into());
+
+        // Then:
+        assert_ne!(graph_hash(&chain, 2), graph_hash(&sink, 2));
+    }
+}
@@ -0,0 +1,193 @@
+use crate::algo::layout::Layout;
+use crate::graph::Edge;
+use crate::misc::rng::Rng;
+use crate::provide::{Edges, Vertices};
+
+/// Computes positions of vertices using the force-directed algorithm of Fruchterman and Reingold.
+///
+/// Vertices repel each other with a force of k<sup>2</sup> / d, and end points of each edge attract each other with a force of d<sup>2</sup> / k,
+/// where d is the distance between the two vertices and k is the ideal distance: The side of the square that each vertex would get if vertices were evenly spread.
+/// In each iteration, vertices move along the sum of the forces applied on them, but no further than the current temperature,
+/// which starts at 0.1 and cools down linearly to zero.
+///
+/// Direction of edges is ignored, parallel edges attract more and loops have no effect.
+///
+/// # Arguments
+/// * `graph`: Graph to compute its layout.
+/// * `iterations`: Number of iterations.
+/// * `rng`: Random number generator used to pick the initial positions.
+///
+/// # Returns
+/// Position of each vertex. Positions are in the unit square: Both coordinates are between 0 and 1.
+///
+/// # Complexity
+/// O(`iterations` * (|V|<sup>2</sup> + |E|))
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::misc::rng::Rng;
+/// use prepona::storage::List;
+/// use prepona::graph::ListGraph;
+/// use prepona::algo::layout;
+///
+/// // Given: Graph
+/// //
+/// //      a  --  b  --  c
+/// //
+/// let mut graph = ListGraph::init(List::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+///
+/// // When:
+/// let positions = layout::fruchterman_reingold(&graph, 100, &mut Rng::seed_from(7));
+///
+/// // Then: a and c end up further from each other than from b.
+/// let distance = |(x1, y1): (f64, f64), (x2, y2): (f64, f64)| ((x1 - x2).powi(2) + (y1 - y2).powi(2)).sqrt();
+/// assert!(distance(positions[&a], positions[&c]) > distance(positions[&a], positions[&b]));
+/// assert!(distance(positions[&a], positions[&c]) > distance(positions[&b], positions[&c]));
+/// ```
+pub fn fruchterman_reingold<W, E, G>(graph: &G, iterations: usize, rng: &mut Rng) -> Layout
+where
+    E: Edge<W>,
+    G: Vertices + Edges<W, E>,
+{
+    let id_map = graph.continuos_id_map();
+    let vertex_count = id_map.len();
+
+    let edges: Vec<(usize, usize)> = graph
+        .edges()
+        .into_iter()
+        .map(|(src_id, dst_id, _)| (id_map.virt_id_of(src_id), id_map.virt_id_of(dst_id)))
+        .filter(|(src_virt_id, dst_virt_id)| src_virt_id != dst_virt_id)
+        .collect();
+
+    let mut positions: Vec<(f64, f64)> = (0..vertex_count)
+        .map(|_| (rng.next_f64(), rng.next_f64()))
+        .collect();
+
+    let ideal_distance = (1.0 / vertex_count.max(1) as f64).sqrt();
+    let initial_temperature = 0.1;
+
+    for iteration in 0..iterations {
+        let mut displacements = vec![(0.0, 0.0); vertex_count];
+
+        for virt_id in 0..vertex_count {
+            for other_virt_id in virt_id + 1..vertex_count {
+                let (dx, dy, distance) = direction(positions[virt_id], positions[other_virt_id]);
+                let force = ideal_distance * ideal_distance / distance;
+
+                displacements[virt_id].0 += dx * force;
+                displacements[virt_id].1 += dy * force;
+                displacements[other_virt_id].0 -= dx * force;
+                displacements[other_virt_id].1 -= dy * force;
+            }
+        }
+
+        for (src_virt_id, dst_virt_id) in edges.iter().copied() {
+            let (dx, dy, distance) = direction(positions[src_virt_id], positions[dst_virt_id]);
+            let force = distance * distance / ideal_distance;
+
+            displacements[src_virt_id].0 -= dx * force;
+            displacements[src_virt_id].1 -= dy * force;
+            displacements[dst_virt_id].0 += dx * force;
+            displacements[dst_virt_id].1 += dy * force;
+        }
+
+        let temperature = initial_temperature * (1.0 - iteration as f64 / iterations as f64);
+        for (position, (dx, dy)) in positions.iter_mut().zip(displacements) {
+            let length = (dx * dx + dy * dy).sqrt();
+            if length > 0.0 {
+                let step = length.min(temperature);
+                position.0 = (position.0 + dx / length * step).clamp(0.0, 1.0);
+                position.1 = (position.1 + dy / length * step).clamp(0.0, 1.0);
+            }
+        }
+    }
+
+    positions
+        .into_iter()
+        .enumerate()
+        .map(|(virt_id, position)| (id_map.real_id_of(virt_id), position))
+        .collect()
+}
+
+// Returns unit vector pointing from `to` to `from`, and the distance between them.
+// Vertices at the same position are pushed apart along a fixed direction.
+fn direction(from: (f64, f64), to: (f64, f64)) -> (f64, f64, f64) {
+    let (dx, dy) = (from.0 - to.0, from.1 - to.1);
+    let distance = (dx * dx + dy * dy).sqrt();
+
+    if distance < 1e-9 {
+        (1.0, 0.0, 1e-9)
+    } else {
+        (dx / distance, dy / distance, distance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::ListGraph;
+    use crate::provide::Graph;
+    use crate::storage::List;
+
+    #[test]
+    fn positions_are_reproducible_and_bounded() {
+        // Given: Cycle of 12 vertices with a removed vertex.
+        let mut graph = ListGraph::init(List::<usize>::init());
+        for _ in 0..13 {
+            graph.add_vertex();
+        }
+        graph.remove_vertex_unchecked(0);
+        for vertex_id in 1..=12 {
+            graph.add_edge_unchecked(vertex_id, vertex_id % 12 + 1, 1.into());
+        }
+
+        // When:
+        let positions = fruchterman_reingold(&graph, 200, &mut Rng::seed_from(1));
+
+        // Then:
+        assert_eq!(positions.len(), 12);
+        assert_eq!(
+            positions,
+            fruchterman_reingold(&graph, 200, &mut Rng::seed_from(1))
+        );
+        assert!(positions
+            .values()
+            .all(|(x, y)| (0.0..=1.0).contains(x) && (0.0..=1.0).contains(y)));
+
+        // Then: Adjacent vertices are closer than opposite vertices of the cycle.
+        let distance = |src_id: usize, dst_id: usize| {
+            let ((x1, y1), (x2, y2)) = (positions[&src_id], positions[&dst_id]);
+            ((x1 - x2).powi(2) + (y1 - y2).powi(2)).sqrt()
+        };
+        let adjacent = (1..=12)
+            .map(|vertex_id| distance(vertex_id, vertex_id % 12 + 1))
+            .fold(0.0, f64::max);
+        let opposite = (1..=6)
+            .map(|vertex_id| distance(vertex_id, vertex_id + 6))
+            .fold(f64::MAX, f64::min);
+        assert!(adjacent < opposite);
+    }
+
+    #[test]
+    fn empty_and_single_vertex_graphs() {
+        // Given:
+        let mut graph = ListGraph::init(List::<usize>::init());
+
+        // Then:
+        assert!(fruchterman_reingold(&graph, 10, &mut Rng::seed_from(1)).is_empty());
+
+        // When:
+        let vertex_id = graph.add_vertex();
+        let positions = fruchterman_reingold(&graph, 10, &mut Rng::seed_from(1));
+
+        // Then:
+        assert_eq!(positions.len(), 1);
+        assert!(positions.contains_key(&vertex_id));
+    }
+}
@@ -0,0 +1,317 @@
+use anyhow::Result;
+use std::collections::{HashMap, VecDeque};
+
+use crate::algo::layout::Layout;
+use crate::algo::Error;
+use crate::graph::{DirectedEdge, Edge};
+use crate::provide::{Edges, Graph, Vertices};
+
+// Number of down and up sweeps of the barycenter heuristic.
+const SWEEPS: usize = 8;
+
+/// Computes positions of vertices of a directed acyclic graph in layers, in the style of Sugiyama.
+///
+/// * Each vertex is placed in the layer after the deepest layer of its predecessors, so sources are in the first layer and every edge points to a later layer.
+/// * Edges that span more than one layer are split by dummy vertices, one in each layer they pass through.
+/// * Vertices of each layer are reordered by the average position of their neighbors in the adjacent layer, sweeping down and up the layers,
+///   and the order with the fewest edge crossings is kept.
+///
+/// # Arguments
+/// `graph`: Directed acyclic graph to compute its layout.
+///
+/// # Returns
+/// * `Ok`: Containing position of each vertex. y is the index of the layer of the vertex, starting from zero, and x is the index of the vertex in its layer,
+///   shifted so each layer is centered around zero. Adjacent vertices in a layer are 1 apart.
+/// * `Err`: If graph contains a cycle. Loops are cycles too.
+///
+/// # Complexity
+/// O(|V| + |E| * L) for assigning layers and adding dummy vertices, where L is the number of layers.
+/// Counting the crossings after each sweep takes O(E'<sup>2</sup>), where E' is the number of edges after splitting.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::DiList;
+/// use prepona::graph::ListGraph;
+/// use prepona::algo::layout;
+///
+/// // Given: Graph
+/// //
+/// //      a  -->  b  -->  c
+/// //      |               ^
+/// //      '---------------'
+/// //
+/// let mut graph = ListGraph::init(DiList::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(a, c, 1.into());
+///
+/// // When:
+/// let positions = layout::layered(&graph).unwrap();
+///
+/// // Then: One vertex in each layer, next to the dummy vertex of a -> c in the second layer.
+/// assert_eq!(positions[&a], (0.0, 0.0));
+/// assert_eq!(positions[&b].1, 1.0);
+/// assert_eq!(positions[&b].0.abs(), 0.5);
+/// assert_eq!(positions[&c], (0.0, 2.0));
+/// ```
+pub fn layered<W, E, G>(graph: &G) -> Result<Layout>
+where
+    E: Edge<W>,
+    G: Vertices + Edges<W, E> + Graph<W, E, DirectedEdge>,
+{
+    let id_map = graph.continuos_id_map();
+    let vertex_count = id_map.len();
+
+    let edges: Vec<(usize, usize)> = graph
+        .edges()
+        .into_iter()
+        .map(|(src_id, dst_id, _)| (id_map.virt_id_of(src_id), id_map.virt_id_of(dst_id)))
+        .collect();
+
+    let vertex_layers = assign_layers(vertex_count, &edges)?;
+
+    // Split long edges so every edge connects two adjacent layers.
+    let mut node_layers = vertex_layers.clone();
+    let mut short_edges = vec![];
+    for (src_virt_id, dst_virt_id) in edges {
+        let mut previous = src_virt_id;
+        for layer in vertex_layers[src_virt_id] + 1..vertex_layers[dst_virt_id] {
+            let dummy = node_layers.len();
+            node_layers.push(layer);
+            short_edges.push((previous, dummy));
+            previous = dummy;
+        }
+        short_edges.push((previous, dst_virt_id));
+    }
+
+    let layer_count = node_layers.iter().max().map_or(0, |layer| layer + 1);
+    let mut layers = vec![vec![]; layer_count];
+    for (node, layer) in node_layers.iter().enumerate() {
+        layers[*layer].push(node);
+    }
+
+    let mut predecessors = vec![vec![]; node_layers.len()];
+    let mut successors = vec![vec![]; node_layers.len()];
+    for (src, dst) in short_edges.iter().copied() {
+        predecessors[dst].push(src);
+        successors[src].push(dst);
+    }
+
+    let mut best_crossings = crossings(&layers, &short_edges, &node_layers);
+    let mut best_layers = layers.clone();
+    for _ in 0..SWEEPS {
+        for layer in 1..layer_count {
+            reorder(&mut layers, layer, layer - 1, &predecessors);
+        }
+        for layer in (0..layer_count.saturating_sub(1)).rev() {
+            reorder(&mut layers, layer, layer + 1, &successors);
+        }
+
+        let current_crossings = crossings(&layers, &short_edges, &node_layers);
+        if current_crossings < best_crossings {
+            best_crossings = current_crossings;
+            best_layers = layers.clone();
+        }
+    }
+
+    let mut layout = Layout::new();
+    for (layer_index, layer) in best_layers.iter().enumerate() {
+        let center = (layer.len() as f64 - 1.0) / 2.0;
+        for (index, node) in layer.iter().enumerate() {
+            if *node < vertex_count {
+                layout.insert(
+                    id_map.real_id_of(*node),
+                    (index as f64 - center, layer_index as f64),
+                );
+            }
+        }
+    }
+
+    Ok(layout)
+}
+
+// Assigns each vertex to the layer after the deepest layer of its predecessors.
+fn assign_layers(vertex_count: usize, edges: &[(usize, usize)]) -> Result<Vec<usize>> {
+    let mut in_degrees = vec![0; vertex_count];
+    let mut successors = vec![vec![]; vertex_count];
+    for (src_virt_id, dst_virt_id) in edges.iter().copied() {
+        in_degrees[dst_virt_id] += 1;
+        successors[src_virt_id].push(dst_virt_id);
+    }
+
+    let mut layers = vec![0; vertex_count];
+    let mut queue: VecDeque<usize> = (0..vertex_count)
+        .filter(|virt_id| in_degrees[*virt_id] == 0)
+        .collect();
+    let mut visited_count = 0;
+
+    while let Some(virt_id) = queue.pop_front() {
+        visited_count += 1;
+
+        for s_virt_id in successors[virt_id].iter().copied() {
+            layers[s_virt_id] = layers[s_virt_id].max(layers[virt_id] + 1);
+
+            in_degrees[s_virt_id] -= 1;
+            if in_degrees[s_virt_id] == 0 {
+                queue.push_back(s_virt_id);
+            }
+        }
+    }
+
+    if visited_count < vertex_count {
+        Err(Error::new_cd())?
+    }
+
+    Ok(layers)
+}
+
+// Sorts nodes of `layer` by the average position of their neighbors in the `fixed` layer.
+// Nodes without any neighbor keep their current position.
+fn reorder(layers: &mut [Vec<usize>], layer: usize, fixed: usize, neighbors: &[Vec<usize>]) {
+    let mut fixed_positions = HashMap::new();
+    for (index, node) in layers[fixed].iter().enumerate() {
+        fixed_positions.insert(*node, index as f64);
+    }
+
+    let mut barycenters: Vec<(f64, usize)> = layers[layer]
+        .iter()
+        .enumerate()
+        .map(|(index, node)| {
+            let positions: Vec<f64> = neighbors[*node]
+                .iter()
+                .map(|neighbor| fixed_positions[neighbor])
+                .collect();
+
+            if positions.is_empty() {
+                (index as f64, *node)
+            } else {
+                (
+                    positions.iter().sum::<f64>() / positions.len() as f64,
+                    *node,
+                )
+            }
+        })
+        .collect();
+
+    barycenters.sort_by(|(barycenter1, _), (barycenter2, _)| barycenter1.total_cmp(barycenter2));
+    layers[layer] = barycenters.into_iter().map(|(_, node)| node).collect();
+}
+
+// Counts pairs of edges between the same two layers that cross each other.
+fn crossings(layers: &[Vec<usize>], edges: &[(usize, usize)], node_layers: &[usize]) -> usize {
+    let mut positions = vec![0; node_layers.len()];
+    for layer in layers.iter() {
+        for (index, node) in layer.iter().enumerate() {
+            positions[*node] = index;
+        }
+    }
+
+    let mut edges_by_layer = vec![vec![]; layers.len()];
+    for (src, dst) in edges.iter().copied() {
+        let layer = layers
+            .iter()
+            .position(|layer| layer.contains(&src))
+            .unwrap();
+        edges_by_layer[layer].push((positions[src], positions[dst]));
+    }
+
+    let mut count = 0;
+    for layer_edges in edges_by_layer.iter() {
+        for (index, (src1, dst1)) in layer_edges.iter().enumerate() {
+            for (src2, dst2) in layer_edges[index + 1..].iter() {
+                if (src1 < src2 && dst1 > dst2) || (src1 > src2 && dst1 < dst2) {
+                    count += 1;
+                }
+            }
+        }
+    }
+
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::ErrorKind;
+    use crate::graph::ListGraph;
+    use crate::storage::DiList;
+
+    fn graph_of(vertex_count: usize, edges: &[(usize, usize)]) -> ListGraph<usize, DirectedEdge> {
+        let mut graph = ListGraph::init(DiList::<usize>::init());
+        for _ in 0..vertex_count {
+            graph.add_vertex();
+        }
+        for (src_id, dst_id) in edges.iter() {
+            graph.add_edge_unchecked(*src_id, *dst_id, 1.into());
+        }
+
+        graph
+    }
+
+    #[test]
+    fn cycle_is_rejected() {
+        // Given: Graph
+        //
+        //      a  -->  b  -->  c
+        //      ^               |
+        //      '---------------'
+        //
+        let graph = graph_of(3, &[(0, 1), (1, 2), (2, 0)]);
+
+        // When:
+        let result = layered(&graph);
+
+        // Then:
+        let error = result.unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<Error>().unwrap().kind(),
+            ErrorKind::CycleDetected
+        ));
+    }
+
+    #[test]
+    fn edges_point_down_without_crossings() {
+        // Given: Graph in which the initial order of the second layer makes edges cross.
+        //
+        //      a   b
+        //       \ /
+        //        X
+        //       / \
+        //      c   d  -->  e
+        //
+        let graph = graph_of(5, &[(0, 3), (1, 2), (3, 4), (2, 4)]);
+
+        // When:
+        let positions = layered(&graph).unwrap();
+
+        // Then:
+        assert_eq!(positions.len(), 5);
+        for (src_id, dst_id, _) in graph.edges() {
+            assert_eq!(positions[&src_id].1 + 1.0, positions[&dst_id].1);
+        }
+        assert_eq!(
+            positions[&0].0 < positions[&1].0,
+            positions[&3].0 < positions[&2].0
+        );
+        assert_eq!(positions[&4], (0.0, 2.0));
+    }
+
+    #[test]
+    fn isolated_vertices_are_in_first_layer() {
+        // Given: Graph with no edges.
+        let graph = graph_of(3, &[]);
+
+        // When:
+        let positions = layered(&graph).unwrap();
+
+        // Then:
+        let mut xs: Vec<f64> = positions.values().map(|(x, _)| *x).collect();
+        xs.sort_by(f64::total_cmp);
+        assert_eq!(xs, vec![-1.0, 0.0, 1.0]);
+        assert!(positions.values().all(|(_, y)| *y == 0.0));
+    }
+}
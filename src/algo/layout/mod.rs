@@ -0,0 +1,12 @@
+mod force_directed;
+mod layered;
+mod svg;
+
+pub use force_directed::fruchterman_reingold;
+pub use layered::layered;
+pub use svg::{render_svg, SvgStyle};
+
+use std::collections::HashMap;
+
+/// Position of each vertex in the plane, mapped from its id, in the format of (x, y).
+pub type Layout = HashMap<usize, (f64, f64)>;
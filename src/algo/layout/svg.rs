@@ -0,0 +1,393 @@
+use std::collections::HashSet;
+
+use crate::algo::layout::Layout;
+use crate::graph::{Edge, EdgeDir};
+use crate::provide::{Edges, Graph, Vertices};
+
+/// Appearance of a graph rendered by [`render_svg`](crate::algo::layout::render_svg).
+///
+/// By default the image is 400 by 400, each vertex is a white circle with radius of 10 labeled by its id, and edges are black.
+/// Labels and colors can be computed by closures, which makes it easy to highlight the output of an algorithm, like the vertices of a component or the edges of a path.
+/// Colors are used as SVG paints, so any value like `red` or `#ff0000` is accepted.
+///
+/// ## Generic Parameters
+/// * `L`: **L**abel function that computes the label of each vertex.
+/// * `VC`: **V**ertex **C**olor function that computes the fill color of each vertex.
+/// * `EC`: **E**dge **C**olor function that computes the color of each edge.
+pub struct SvgStyle<
+    L = fn(usize) -> String,
+    VC = fn(usize) -> String,
+    EC = fn(usize, usize, usize) -> String,
+> {
+    width: f64,
+    height: f64,
+    vertex_radius: f64,
+
+    vertex_label: L,
+    vertex_color: VC,
+    edge_color: EC,
+}
+
+impl SvgStyle {
+    /// Initializes the default style.
+    pub fn init() -> Self {
+        SvgStyle {
+            width: 400.0,
+            height: 400.0,
+            vertex_radius: 10.0,
+
+            vertex_label: |vertex_id| vertex_id.to_string(),
+            vertex_color: |_| "white".to_string(),
+            edge_color: |_, _, _| "black".to_string(),
+        }
+    }
+}
+
+impl<L, VC, EC> SvgStyle<L, VC, EC>
+where
+    L: Fn(usize) -> String,
+    VC: Fn(usize) -> String,
+    EC: Fn(usize, usize, usize) -> String,
+{
+    /// # Arguments
+    /// * `width`: Width of the image.
+    /// * `height`: Height of the image.
+    pub fn with_size(mut self, width: f64, height: f64) -> Self {
+        self.width = width;
+        self.height = height;
+
+        self
+    }
+
+    /// # Arguments
+    /// `vertex_radius`: Radius of the circle drawn for each vertex.
+    pub fn with_vertex_radius(mut self, vertex_radius: f64) -> Self {
+        self.vertex_radius = vertex_radius;
+
+        self
+    }
+
+    /// # Arguments
+    /// `vertex_label`: Receives id of a vertex and returns its label. Empty labels are not drawn.
+    pub fn with_vertex_label<L2>(self, vertex_label: L2) -> SvgStyle<L2, VC, EC>
+    where
+        L2: Fn(usize) -> String,
+    {
+        SvgStyle {
+            width: self.width,
+            height: self.height,
+            vertex_radius: self.vertex_radius,
+
+            vertex_label,
+            vertex_color: self.vertex_color,
+            edge_color: self.edge_color,
+        }
+    }
+
+    /// # Arguments
+    /// `vertex_color`: Receives id of a vertex and returns its fill color.
+    pub fn with_vertex_color<VC2>(self, vertex_color: VC2) -> SvgStyle<L, VC2, EC>
+    where
+        VC2: Fn(usize) -> String,
+    {
+        SvgStyle {
+            width: self.width,
+            height: self.height,
+            vertex_radius: self.vertex_radius,
+
+            vertex_label: self.vertex_label,
+            vertex_color,
+            edge_color: self.edge_color,
+        }
+    }
+
+    /// # Arguments
+    /// `edge_color`: Receives (`src_id`, `dst_id`, `edge_id`) of an edge and returns its color.
+    pub fn with_edge_color<EC2>(self, edge_color: EC2) -> SvgStyle<L, VC, EC2>
+    where
+        EC2: Fn(usize, usize, usize) -> String,
+    {
+        SvgStyle {
+            width: self.width,
+            height: self.height,
+            vertex_radius: self.vertex_radius,
+
+            vertex_label: self.vertex_label,
+            vertex_color: self.vertex_color,
+            edge_color,
+        }
+    }
+}
+
+/// Renders a graph as an SVG image.
+///
+/// Positions of the layout are scaled to fit the image, so any layout like the ones computed by
+/// [`fruchterman_reingold`](crate::algo::layout::fruchterman_reingold) or [`layered`](crate::algo::layout::layered) can be used.
+/// Edges of directed graphs end with an arrow head and loops are drawn as small circles above their vertex.
+///
+/// # Arguments
+/// * `graph`: Graph to render.
+/// * `layout`: Position of each vertex. Vertices that are missing from the layout are not drawn, and neither are their edges.
+/// * `style`: Appearance of the image.
+///
+/// # Returns
+/// Content of an SVG file.
+///
+/// # Complexity
+/// O(|V| + |E|)
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::misc::rng::Rng;
+/// use prepona::storage::List;
+/// use prepona::graph::ListGraph;
+/// use prepona::algo::layout::{self, SvgStyle};
+///
+/// // Given: Graph
+/// //
+/// //      a  --  b  --  c
+/// //
+/// let mut graph = ListGraph::init(List::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let ab = graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+///
+/// // When: Highlighting a and its edge.
+/// let positions = layout::fruchterman_reingold(&graph, 50, &mut Rng::seed_from(7));
+/// let style = SvgStyle::init()
+///     .with_vertex_label(|vertex_id| format!("v{}", vertex_id))
+///     .with_vertex_color(move |vertex_id| if vertex_id == a { "red".to_string() } else { "white".to_string() })
+///     .with_edge_color(move |_, _, edge_id| if edge_id == ab { "red".to_string() } else { "gray".to_string() });
+/// let svg = layout::render_svg(&graph, &positions, &style);
+///
+/// // Then:
+/// assert!(svg.starts_with("<svg"));
+/// assert_eq!(svg.matches("<circle").count(), 3);
+/// assert_eq!(svg.matches("<line").count(), 2);
+/// assert_eq!(svg.matches("fill=\"red\"").count(), 1);
+/// assert!(svg.contains(">v1</text>"));
+/// ```
+pub fn render_svg<W, E, Dir, G, L, VC, EC>(
+    graph: &G,
+    layout: &Layout,
+    style: &SvgStyle<L, VC, EC>,
+) -> String
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Vertices + Edges<W, E> + Graph<W, E, Dir>,
+    L: Fn(usize) -> String,
+    VC: Fn(usize) -> String,
+    EC: Fn(usize, usize, usize) -> String,
+{
+    let radius = style.vertex_radius;
+    let margin = 2.0 * radius;
+
+    let (mut min_x, mut min_y) = (f64::MAX, f64::MAX);
+    let (mut max_x, mut max_y) = (f64::MIN, f64::MIN);
+    for (x, y) in graph
+        .vertices()
+        .iter()
+        .filter_map(|vertex_id| layout.get(vertex_id))
+    {
+        min_x = min_x.min(*x);
+        min_y = min_y.min(*y);
+        max_x = max_x.max(*x);
+        max_y = max_y.max(*y);
+    }
+
+    // Maps a coordinate into the drawable area. Layouts with no extent along an axis are centered along it.
+    let scale = |value: f64, min: f64, max: f64, size: f64| {
+        if max - min > f64::EPSILON {
+            margin + (value - min) / (max - min) * (size - 2.0 * margin)
+        } else {
+            size / 2.0
+        }
+    };
+    let position_of = |vertex_id: usize| {
+        layout.get(&vertex_id).map(|(x, y)| {
+            (
+                scale(*x, min_x, max_x, style.width),
+                scale(*y, min_y, max_y, style.height),
+            )
+        })
+    };
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\">\n",
+        style.width, style.height
+    );
+
+    if Dir::is_directed() {
+        svg.push_str(
+            "<defs><marker id=\"arrow\" viewBox=\"0 0 10 10\" refX=\"10\" refY=\"5\" markerWidth=\"6\" markerHeight=\"6\" orient=\"auto-start-reverse\">\
+             <path d=\"M 0 0 L 10 5 L 0 10 z\" fill=\"context-stroke\"/></marker></defs>\n",
+        );
+    }
+
+    // Loops of undirected graphs are visible twice.
+    let mut drawn_edge_ids = HashSet::new();
+    for (src_id, dst_id, edge) in graph.edges() {
+        if !drawn_edge_ids.insert(edge.get_id()) {
+            continue;
+        }
+
+        let (src_position, dst_position) = match (position_of(src_id), position_of(dst_id)) {
+            (Some(src_position), Some(dst_position)) => (src_position, dst_position),
+            _ => continue,
+        };
+        let color = escape(&(style.edge_color)(src_id, dst_id, edge.get_id()));
+
+        if src_id == dst_id {
+            svg.push_str(&format!(
+                "<circle cx=\"{:.2}\" cy=\"{:.2}\" r=\"{:.2}\" fill=\"none\" stroke=\"{}\"/>\n",
+                src_position.0,
+                src_position.1 - 1.5 * radius,
+                0.75 * radius,
+                color
+            ));
+            continue;
+        }
+
+        // Lines end at the border of the circles so arrow heads stay visible.
+        let (dx, dy) = (
+            dst_position.0 - src_position.0,
+            dst_position.1 - src_position.1,
+        );
+        let length = (dx * dx + dy * dy).sqrt().max(f64::EPSILON);
+        let (offset_x, offset_y) = (dx / length * radius, dy / length * radius);
+
+        svg.push_str(&format!(
+            "<line x1=\"{:.2}\" y1=\"{:.2}\" x2=\"{:.2}\" y2=\"{:.2}\" stroke=\"{}\"{}/>\n",
+            src_position.0 + offset_x,
+            src_position.1 + offset_y,
+            dst_position.0 - offset_x,
+            dst_position.1 - offset_y,
+            color,
+            if Dir::is_directed() {
+                " marker-end=\"url(#arrow)\""
+            } else {
+                ""
+            }
+        ));
+    }
+
+    for vertex_id in graph.vertices() {
+        let (x, y) = match position_of(vertex_id) {
+            Some(position) => position,
+            None => continue,
+        };
+
+        svg.push_str(&format!(
+            "<circle cx=\"{:.2}\" cy=\"{:.2}\" r=\"{:.2}\" fill=\"{}\" stroke=\"black\"/>\n",
+            x,
+            y,
+            radius,
+            escape(&(style.vertex_color)(vertex_id))
+        ));
+
+        let label = (style.vertex_label)(vertex_id);
+        if !label.is_empty() {
+            svg.push_str(&format!(
+                "<text x=\"{:.2}\" y=\"{:.2}\" font-size=\"{:.2}\" text-anchor=\"middle\" dominant-baseline=\"central\">{}</text>\n",
+                x,
+                y,
+                radius,
+                escape(&label)
+            ));
+        }
+    }
+
+    svg.push_str("</svg>\n");
+
+    svg
+}
+
+// Escapes characters that have special meaning in XML.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::layout::layered;
+    use crate::graph::{ListGraph, MultiListGraph};
+    use crate::storage::{DiList, List};
+
+    #[test]
+    fn directed_graph_has_arrows() {
+        // Given: Graph
+        //
+        //      a  -->  b  -->  c
+        //
+        let mut graph = ListGraph::init(DiList::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+
+        // When:
+        let positions = layered(&graph).unwrap();
+        let svg = render_svg(
+            &graph,
+            &positions,
+            &SvgStyle::init().with_size(100.0, 300.0),
+        );
+
+        // Then: Vertices are stacked vertically in the middle of the image.
+        assert!(svg.contains("width=\"100\" height=\"300\""));
+        assert_eq!(svg.matches("marker-end=\"url(#arrow)\"").count(), 2);
+        assert!(svg.contains("<circle cx=\"50.00\" cy=\"20.00\""));
+        assert!(svg.contains("<circle cx=\"50.00\" cy=\"280.00\""));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn missing_vertices_loops_and_escaping() {
+        // Given: Graph with a loop, and a layout that misses one of the vertices.
+        let mut graph = MultiListGraph::init(List::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(a, a, 1.into());
+        let positions: Layout = vec![(a, (0.0, 0.0)), (b, (1.0, 1.0))].into_iter().collect();
+
+        // When:
+        let style = SvgStyle::init()
+            .with_vertex_radius(5.0)
+            .with_vertex_label(|vertex_id| {
+                if vertex_id == a {
+                    "<a & b>".to_string()
+                } else {
+                    String::new()
+                }
+            });
+        let svg = render_svg(&graph, &positions, &style);
+
+        // Then:
+        assert_eq!(svg.matches("<line").count(), 1);
+        assert_eq!(svg.matches("<circle").count(), 3);
+        assert_eq!(svg.matches("<text").count(), 1);
+        assert!(svg.contains(">&lt;a &amp; b&gt;</text>"));
+        assert!(!svg.contains("marker"));
+    }
+}
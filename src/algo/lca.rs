@@ -0,0 +1,494 @@
+use std::collections::VecDeque;
+
+use crate::provide::{IdMap, Neighbors, Vertices};
+
+/// Answers lowest common ancestor queries on a rooted forest using binary lifting.
+///
+/// The forest is derived from the graph by a breadth-first search from each root, following the neighbors of each vertex.
+/// So any graph works: For a tree the forest is the tree itself, and for a directed acyclic graph with vertices reachable from multiple parents,
+/// parent of each vertex is the one that discovered it first.
+/// Vertices that are not reachable from any root are not in the forest.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::List;
+/// use prepona::graph::ListGraph;
+/// use prepona::algo::Lca;
+///
+/// // Given: Tree
+/// //
+/// //            a
+/// //          /   \
+/// //         b     c
+/// //        / \     \
+/// //       d   e     f
+/// //
+/// let mut graph = ListGraph::init(List::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// let e = graph.add_vertex();
+/// let f = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(a, c, 1.into());
+/// graph.add_edge_unchecked(b, d, 1.into());
+/// graph.add_edge_unchecked(b, e, 1.into());
+/// graph.add_edge_unchecked(c, f, 1.into());
+///
+/// // When:
+/// let lca = Lca::init(&graph, &[a]);
+///
+/// // Then:
+/// assert_eq!(lca.lca(d, e), Some(b));
+/// assert_eq!(lca.lca(d, f), Some(a));
+/// assert_eq!(lca.distance(d, f), Some(4));
+/// assert_eq!(Lca::offline(&graph, &[a], &[(d, e), (e, c)]), vec![Some(b), Some(a)]);
+/// ```
+pub struct Lca {
+    id_map: IdMap,
+
+    // Depth of each vertex, None if it's not in the forest.
+    depths: Vec<Option<usize>>,
+
+    // Root of the tree that contains each vertex.
+    roots: Vec<usize>,
+
+    // Virtual id of the 2^i-th ancestor of each vertex in ancestors[i]. Ancestor of a root is the root itself.
+    ancestors: Vec<Vec<usize>>,
+}
+
+impl Lca {
+    /// Builds the forest and preprocesses it for queries.
+    ///
+    /// # Arguments
+    /// * `graph`: Graph to derive the forest from.
+    /// * `roots`: Id of the root of each tree. A root that is reachable from a previous root is not a root.
+    ///
+    /// # Returns
+    /// Initialized structure.
+    ///
+    /// # Complexity
+    /// O(|V| log |V| + |E|)
+    ///
+    /// # Panics
+    /// If there is no vertex with id of one of the `roots`.
+    pub fn init<G>(graph: &G, roots: &[usize]) -> Self
+    where
+        G: Vertices + Neighbors,
+    {
+        let forest = Forest::init(graph, roots);
+        let vertex_count = forest.parents.len();
+
+        let mut ancestors = vec![(0..vertex_count)
+            .map(|virt_id| forest.parents[virt_id].unwrap_or(virt_id))
+            .collect::<Vec<usize>>()];
+        let mut span = 1;
+        while span < vertex_count {
+            let previous = ancestors.last().unwrap();
+            let next = previous
+                .iter()
+                .map(|ancestor| previous[*ancestor])
+                .collect();
+            ancestors.push(next);
+            span *= 2;
+        }
+
+        Lca {
+            id_map: forest.id_map,
+            depths: forest.depths,
+            roots: forest.roots,
+            ancestors,
+        }
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// * `Some`: Containing number of edges between the vertex and root of its tree.
+    /// * `None`: If vertex is not in the forest.
+    pub fn depth(&self, vertex_id: usize) -> Option<usize> {
+        self.id_map
+            .try_virt_id_of(vertex_id)
+            .and_then(|virt_id| self.depths[virt_id])
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// * `Some`: Containing id of the parent of the vertex.
+    /// * `None`: If vertex is a root or it's not in the forest.
+    pub fn parent(&self, vertex_id: usize) -> Option<usize> {
+        self.depth(vertex_id).filter(|depth| *depth > 0)?;
+
+        let virt_id = self.id_map.virt_id_of(vertex_id);
+        Some(self.id_map.real_id_of(self.ancestors[0][virt_id]))
+    }
+
+    /// # Arguments
+    /// * `src_id`: Id of the first vertex.
+    /// * `dst_id`: Id of the second vertex.
+    ///
+    /// # Returns
+    /// * `Some`: Containing id of the deepest vertex that is an ancestor of both vertices. A vertex is an ancestor of itself.
+    /// * `None`: If vertices are in different trees or any of them is not in the forest.
+    ///
+    /// # Complexity
+    /// O(log |V|)
+    pub fn lca(&self, src_id: usize, dst_id: usize) -> Option<usize> {
+        self.lca_virt(src_id, dst_id)
+            .map(|virt_id| self.id_map.real_id_of(virt_id))
+    }
+
+    /// # Arguments
+    /// * `src_id`: Id of the first vertex.
+    /// * `dst_id`: Id of the second vertex.
+    ///
+    /// # Returns
+    /// * `Some`: Containing number of edges on the path between the vertices in the forest.
+    /// * `None`: If vertices are in different trees or any of them is not in the forest.
+    ///
+    /// # Complexity
+    /// O(log |V|)
+    pub fn distance(&self, src_id: usize, dst_id: usize) -> Option<usize> {
+        let lca_virt_id = self.lca_virt(src_id, dst_id)?;
+        let lca_depth = self.depths[lca_virt_id].unwrap();
+
+        Some(self.depth(src_id)? + self.depth(dst_id)? - 2 * lca_depth)
+    }
+
+    /// Answers a batch of lowest common ancestor queries using Tarjan's offline algorithm,
+    /// which only needs a single depth-first traversal of the forest and no preprocessing that outlives the batch.
+    ///
+    /// # Arguments
+    /// * `graph`: Graph to derive the forest from. For more info checkout [`Lca`](crate::algo::Lca).
+    /// * `roots`: Id of the root of each tree.
+    /// * `queries`: Pairs of vertices to find their lowest common ancestor.
+    ///
+    /// # Returns
+    /// Answer of each query, same as [`lca`](crate::algo::Lca::lca), in the same order as `queries`.
+    ///
+    /// # Complexity
+    /// O((|V| + |E| + |Q|) α(|V|)) where Q is the set of queries.
+    ///
+    /// # Panics
+    /// If there is no vertex with id of one of the `roots`.
+    pub fn offline<G>(graph: &G, roots: &[usize], queries: &[(usize, usize)]) -> Vec<Option<usize>>
+    where
+        G: Vertices + Neighbors,
+    {
+        let forest = Forest::init(graph, roots);
+        let vertex_count = forest.parents.len();
+
+        let mut answers = vec![None; queries.len()];
+        let mut queries_of = vec![vec![]; vertex_count];
+        for (index, (src_id, dst_id)) in queries.iter().enumerate() {
+            let src_virt_id = forest.id_map.try_virt_id_of(*src_id);
+            let dst_virt_id = forest.id_map.try_virt_id_of(*dst_id);
+
+            if let (Some(src_virt_id), Some(dst_virt_id)) = (src_virt_id, dst_virt_id) {
+                if forest.depths[src_virt_id].is_some()
+                    && forest.depths[dst_virt_id].is_some()
+                    && forest.roots[src_virt_id] == forest.roots[dst_virt_id]
+                {
+                    queries_of[src_virt_id].push((dst_virt_id, index));
+                    queries_of[dst_virt_id].push((src_virt_id, index));
+                }
+            }
+        }
+
+        let mut sets = DisjointSets::init(vertex_count);
+        let mut ancestor: Vec<usize> = (0..vertex_count).collect();
+        let mut finished = vec![false; vertex_count];
+
+        // Each entry is (virtual id of a vertex, index of its next child to visit).
+        let mut stack: Vec<(usize, usize)> =
+            forest.tree_roots.iter().map(|root| (*root, 0)).collect();
+        while let Some((virt_id, child_index)) = stack.pop() {
+            if let Some(child_virt_id) = forest.children[virt_id].get(child_index) {
+                stack.push((virt_id, child_index + 1));
+                stack.push((*child_virt_id, 0));
+                continue;
+            }
+
+            // Every child is finished, so answer the queries and merge the vertex into its parent.
+            finished[virt_id] = true;
+            for (other_virt_id, index) in queries_of[virt_id].iter() {
+                if finished[*other_virt_id] {
+                    let representative = sets.find(*other_virt_id);
+                    answers[*index] = Some(forest.id_map.real_id_of(ancestor[representative]));
+                }
+            }
+
+            if let Some(parent_virt_id) = forest.parents[virt_id] {
+                sets.union(parent_virt_id, virt_id);
+                let representative = sets.find(parent_virt_id);
+                ancestor[representative] = parent_virt_id;
+            }
+        }
+
+        answers
+    }
+
+    fn lca_virt(&self, src_id: usize, dst_id: usize) -> Option<usize> {
+        let mut src_virt_id = self.id_map.try_virt_id_of(src_id)?;
+        let mut dst_virt_id = self.id_map.try_virt_id_of(dst_id)?;
+        let mut src_depth = self.depths[src_virt_id]?;
+        let mut dst_depth = self.depths[dst_virt_id]?;
+        if self.roots[src_virt_id] != self.roots[dst_virt_id] {
+            return None;
+        }
+
+        if src_depth < dst_depth {
+            std::mem::swap(&mut src_virt_id, &mut dst_virt_id);
+            std::mem::swap(&mut src_depth, &mut dst_depth);
+        }
+
+        // Lift the deeper vertex to the depth of the other one.
+        let mut difference = src_depth - dst_depth;
+        let mut level = 0;
+        while difference > 0 {
+            if difference & 1 == 1 {
+                src_virt_id = self.ancestors[level][src_virt_id];
+            }
+            difference >>= 1;
+            level += 1;
+        }
+
+        if src_virt_id == dst_virt_id {
+            return Some(src_virt_id);
+        }
+
+        // Lift both vertices as long as they stay below their lowest common ancestor.
+        for level in (0..self.ancestors.len()).rev() {
+            if self.ancestors[level][src_virt_id] != self.ancestors[level][dst_virt_id] {
+                src_virt_id = self.ancestors[level][src_virt_id];
+                dst_virt_id = self.ancestors[level][dst_virt_id];
+            }
+        }
+
+        Some(self.ancestors[0][src_virt_id])
+    }
+}
+
+// Breadth-first forest of a graph, indexed by virtual id.
+struct Forest {
+    id_map: IdMap,
+    parents: Vec<Option<usize>>,
+    children: Vec<Vec<usize>>,
+    depths: Vec<Option<usize>>,
+    roots: Vec<usize>,
+    tree_roots: Vec<usize>,
+}
+
+impl Forest {
+    fn init<G>(graph: &G, roots: &[usize]) -> Self
+    where
+        G: Vertices + Neighbors,
+    {
+        let id_map = graph.continuos_id_map();
+        let vertex_count = id_map.len();
+
+        let mut parents = vec![None; vertex_count];
+        let mut children = vec![vec![]; vertex_count];
+        let mut depths = vec![None; vertex_count];
+        let mut tree_of = vec![0; vertex_count];
+        let mut tree_roots = vec![];
+
+        for root_id in roots.iter().copied() {
+            assert!(
+                graph.contains_vertex(root_id),
+                "Vertex with id: {} not found",
+                root_id
+            );
+
+            let root_virt_id = id_map.virt_id_of(root_id);
+            if depths[root_virt_id].is_some() {
+                continue;
+            }
+            depths[root_virt_id] = Some(0);
+            tree_of[root_virt_id] = root_virt_id;
+            tree_roots.push(root_virt_id);
+
+            let mut queue = VecDeque::new();
+            queue.push_back(root_virt_id);
+            while let Some(virt_id) = queue.pop_front() {
+                let depth = depths[virt_id].unwrap() + 1;
+
+                for n_id in graph.neighbors_unchecked(id_map.real_id_of(virt_id)) {
+                    let n_virt_id = id_map.virt_id_of(n_id);
+                    if depths[n_virt_id].is_none() {
+                        depths[n_virt_id] = Some(depth);
+                        parents[n_virt_id] = Some(virt_id);
+                        children[virt_id].push(n_virt_id);
+                        tree_of[n_virt_id] = root_virt_id;
+                        queue.push_back(n_virt_id);
+                    }
+                }
+            }
+        }
+
+        Forest {
+            id_map,
+            parents,
+            children,
+            depths,
+            roots: tree_of,
+            tree_roots,
+        }
+    }
+}
+
+// Union-find with path halving and union by size.
+struct DisjointSets {
+    parents: Vec<usize>,
+    sizes: Vec<usize>,
+}
+
+impl DisjointSets {
+    fn init(count: usize) -> Self {
+        DisjointSets {
+            parents: (0..count).collect(),
+            sizes: vec![1; count],
+        }
+    }
+
+    fn find(&mut self, mut element: usize) -> usize {
+        while self.parents[element] != element {
+            self.parents[element] = self.parents[self.parents[element]];
+            element = self.parents[element];
+        }
+
+        element
+    }
+
+    fn union(&mut self, first: usize, second: usize) {
+        let (mut first, mut second) = (self.find(first), self.find(second));
+        if first == second {
+            return;
+        }
+
+        if self.sizes[first] < self.sizes[second] {
+            std::mem::swap(&mut first, &mut second);
+        }
+        self.parents[second] = first;
+        self.sizes[first] += self.sizes[second];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::ListGraph;
+    use crate::misc::rng::Rng;
+    use crate::provide::Graph;
+    use crate::storage::{DiList, List};
+
+    // Lowest common ancestor by walking up the parents.
+    fn naive_lca(lca: &Lca, mut src_id: usize, mut dst_id: usize) -> Option<usize> {
+        let mut src_depth = lca.depth(src_id)?;
+        let mut dst_depth = lca.depth(dst_id)?;
+        while src_depth > dst_depth {
+            src_id = lca.parent(src_id).unwrap();
+            src_depth -= 1;
+        }
+        while dst_depth > src_depth {
+            dst_id = lca.parent(dst_id).unwrap();
+            dst_depth -= 1;
+        }
+        while src_id != dst_id {
+            src_id = lca.parent(src_id)?;
+            dst_id = lca.parent(dst_id)?;
+        }
+
+        Some(src_id)
+    }
+
+    #[test]
+    fn random_forest_matches_naive_lca() {
+        // Given: Two random trees, a vertex outside of both and a removed vertex.
+        let mut rng = Rng::seed_from(13);
+        let mut graph = ListGraph::init(List::<usize>::init());
+        let removed = graph.add_vertex();
+        let vertices: Vec<_> = (0..61).map(|_| graph.add_vertex()).collect();
+        graph.remove_vertex_unchecked(removed);
+        for index in 1..60 {
+            // First 30 vertices form a tree and the next 30 form another one.
+            let parent = if index < 30 {
+                rng.next_below(index)
+            } else if index == 30 {
+                continue;
+            } else {
+                30 + rng.next_below(index - 30)
+            };
+            graph.add_edge_unchecked(vertices[parent], vertices[index], 1.into());
+        }
+
+        // When:
+        let roots = [vertices[0], vertices[30], vertices[5]];
+        let lca = Lca::init(&graph, &roots);
+        let queries: Vec<(usize, usize)> = (0..200)
+            .map(|_| (vertices[rng.next_below(61)], vertices[rng.next_below(61)]))
+            .chain(vec![(vertices[3], vertices[3]), (removed, vertices[0])])
+            .collect();
+        let offline = Lca::offline(&graph, &roots, &queries);
+
+        // Then:
+        assert_eq!(
+            lca.depth(vertices[5]),
+            lca.parent(vertices[5])
+                .and_then(|parent| lca.depth(parent))
+                .map(|depth| depth + 1)
+        );
+        assert_eq!(lca.depth(vertices[60]), None);
+        for ((src_id, dst_id), answer) in queries.iter().zip(offline) {
+            let expected = naive_lca(&lca, *src_id, *dst_id);
+            assert_eq!(lca.lca(*src_id, *dst_id), expected);
+            assert_eq!(answer, expected);
+            assert_eq!(
+                lca.distance(*src_id, *dst_id),
+                expected.map(
+                    |lca_id| lca.depth(*src_id).unwrap() + lca.depth(*dst_id).unwrap()
+                        - 2 * lca.depth(lca_id).unwrap()
+                )
+            );
+        }
+        assert_eq!(lca.lca(vertices[3], vertices[3]), Some(vertices[3]));
+        assert_eq!(lca.lca(vertices[1], vertices[31]), None);
+    }
+
+    #[test]
+    fn directed_acyclic_graph() {
+        // Given: Graph
+        //
+        //      a  -->  b  -->  d
+        //      |               ^
+        //      '---->  c  -----'
+        //
+        let mut graph = ListGraph::init(DiList::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(a, c, 1.into());
+        graph.add_edge_unchecked(b, d, 1.into());
+        graph.add_edge_unchecked(c, d, 1.into());
+
+        // When:
+        let lca = Lca::init(&graph, &[a]);
+
+        // Then: d is discovered by b first.
+        assert_eq!(lca.parent(d), Some(b));
+        assert_eq!(lca.lca(d, c), Some(a));
+        assert_eq!(lca.lca(d, b), Some(b));
+        assert_eq!(
+            Lca::offline(&graph, &[a], &[(d, c), (b, d)]),
+            vec![Some(a), Some(b)]
+        );
+        assert_eq!(Lca::init(&graph, &[b]).lca(b, a), None);
+    }
+}
@@ -0,0 +1,303 @@
+use num_traits::Zero;
+use std::ops::{AddAssign, Mul};
+
+use crate::graph::Edge;
+use crate::provide::{Edges, IdMap, Vertices};
+
+/// Computes `A * x`.
+///
+/// # Arguments
+/// * `graph`: Graph to use its adjacency matrix.
+/// * `id_map`: Maps ids of vertices to indices of `vector`.
+/// * `vector`: Dense vector `x` with one entry for each vertex.
+/// * `weight_fn`: Maps each edge to its entry in the adjacency matrix.
+///
+/// # Returns
+/// Dense vector `A * x` indexed the same as `vector`.
+///
+/// # Complexity
+/// O(|V| + |E|)
+///
+/// # Panics
+/// If length of `vector` is not equal to the number of vertices.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::DiList;
+/// use prepona::graph::ListGraph;
+/// use prepona::algo::linalg;
+///
+/// // Given: Graph
+/// //
+/// //      a  --2-->  b  --3-->  c
+/// //
+/// let mut graph = ListGraph::init(DiList::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 2.into());
+/// graph.add_edge_unchecked(b, c, 3.into());
+///
+/// let id_map = graph.continuos_id_map();
+///
+/// let mut x = vec![0.0; 3];
+/// x[id_map.virt_id_of(b)] = 1.0;
+/// x[id_map.virt_id_of(c)] = 10.0;
+///
+/// // When: Multiplying the adjacency matrix by x.
+/// let y = linalg::spmv(&graph, &id_map, &x, |edge| edge.get_weight().unwrap() as f64);
+///
+/// // Then:
+/// assert_eq!(y[id_map.virt_id_of(a)], 2.0);
+/// assert_eq!(y[id_map.virt_id_of(b)], 30.0);
+/// assert_eq!(y[id_map.virt_id_of(c)], 0.0);
+/// ```
+pub fn spmv<W, E, G, T, F>(graph: &G, id_map: &IdMap, vector: &[T], weight_fn: F) -> Vec<T>
+where
+    E: Edge<W>,
+    G: Vertices + Edges<W, E>,
+    T: Copy + Zero + Mul<Output = T> + AddAssign,
+    F: Fn(&E) -> T,
+{
+    assert_eq!(
+        vector.len(),
+        graph.vertex_count(),
+        "Length of the vector must be equal to the number of vertices"
+    );
+
+    let mut result = vec![T::zero(); vector.len()];
+
+    for src_id in graph.vertices() {
+        let src_virt_id = id_map.virt_id_of(src_id);
+
+        for (dst_id, edge) in graph.edges_from_unchecked(src_id) {
+            result[src_virt_id] += weight_fn(edge) * vector[id_map.virt_id_of(dst_id)];
+        }
+    }
+
+    result
+}
+
+/// Parallel version of [`spmv`](crate::algo::linalg::spmv).
+///
+/// Rows of the result are independent, so they are computed by multiple threads.
+/// Result is exactly the same as [`spmv`](crate::algo::linalg::spmv), even for floating point values, since each row is summed in the same order.
+///
+/// # Panics
+/// If length of `vector` is not equal to the number of vertices.
+#[cfg(feature = "parallel")]
+pub fn par_spmv<W, E, G, T, F>(graph: &G, id_map: &IdMap, vector: &[T], weight_fn: F) -> Vec<T>
+where
+    E: Edge<W>,
+    G: Vertices + Edges<W, E> + Sync,
+    T: Copy + Zero + Mul<Output = T> + AddAssign + Send + Sync,
+    F: Fn(&E) -> T + Sync,
+{
+    assert_eq!(
+        vector.len(),
+        graph.vertex_count(),
+        "Length of the vector must be equal to the number of vertices"
+    );
+
+    let rows = crate::misc::par::map(&graph.vertices(), |src_id| {
+        let mut entry = T::zero();
+        for (dst_id, edge) in graph.edges_from_unchecked(*src_id) {
+            entry += weight_fn(edge) * vector[id_map.virt_id_of(dst_id)];
+        }
+
+        (id_map.virt_id_of(*src_id), entry)
+    });
+
+    let mut result = vec![T::zero(); vector.len()];
+    for (virt_id, entry) in rows {
+        result[virt_id] = entry;
+    }
+
+    result
+}
+
+/// Computes `A * X`.
+///
+/// Multiplying by a matrix with `k` columns is equivalent to `k` calls to [`spmv`](crate::algo::linalg::spmv) but iterates the edges only once.
+///
+/// # Arguments
+/// * `graph`: Graph to use its adjacency matrix.
+/// * `id_map`: Maps ids of vertices to rows of `matrix`.
+/// * `matrix`: Dense matrix `X` with one row for each vertex. All rows must have the same length.
+/// * `weight_fn`: Maps each edge to its entry in the adjacency matrix.
+///
+/// # Returns
+/// Dense matrix `A * X` with rows indexed the same as `matrix`.
+///
+/// # Complexity
+/// O(k * (|V| + |E|)) where k is the number of columns of `matrix`.
+///
+/// # Panics
+/// * If number of rows of `matrix` is not equal to the number of vertices.
+/// * If rows of `matrix` have different lengths.
+pub fn spmm<W, E, G, T, F>(
+    graph: &G,
+    id_map: &IdMap,
+    matrix: &[Vec<T>],
+    weight_fn: F,
+) -> Vec<Vec<T>>
+where
+    E: Edge<W>,
+    G: Vertices + Edges<W, E>,
+    T: Copy + Zero + Mul<Output = T> + AddAssign,
+    F: Fn(&E) -> T,
+{
+    assert_eq!(
+        matrix.len(),
+        graph.vertex_count(),
+        "Number of rows of the matrix must be equal to the number of vertices"
+    );
+
+    let column_count = matrix.first().map_or(0, |row| row.len());
+    assert!(
+        matrix.iter().all(|row| row.len() == column_count),
+        "All rows of the matrix must have the same length"
+    );
+
+    let mut result = vec![vec![T::zero(); column_count]; matrix.len()];
+
+    for src_id in graph.vertices() {
+        let src_virt_id = id_map.virt_id_of(src_id);
+
+        for (dst_id, edge) in graph.edges_from_unchecked(src_id) {
+            let weight = weight_fn(edge);
+            let row = &matrix[id_map.virt_id_of(dst_id)];
+
+            for (entry, value) in result[src_virt_id].iter_mut().zip(row) {
+                *entry += weight * *value;
+            }
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::MatGraph;
+    use crate::provide::Graph;
+    use crate::storage::Mat;
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn par_spmv_matches_spmv() {
+        // Given: Graph
+        //
+        //      a  --1--  b  --2--  c  --3--  d
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let vertices: Vec<usize> = (0..4).map(|_| graph.add_vertex()).collect();
+        for i in 1..4 {
+            graph.add_edge_unchecked(vertices[i - 1], vertices[i], i.into());
+        }
+        let id_map = graph.continuos_id_map();
+        let vector = [1.5, -2.0, 0.25, 4.0];
+
+        // When:
+        let weight_fn = |edge: &crate::graph::DefaultEdge<usize>| edge.get_weight().unwrap() as f64;
+        let sequential = spmv(&graph, &id_map, &vector, weight_fn);
+        let parallel = par_spmv(&graph, &id_map, &vector, weight_fn);
+
+        // Then:
+        assert_eq!(sequential, parallel);
+    }
+
+    #[test]
+    fn spmv_on_empty_graph() {
+        // Given: Empty graph.
+        let graph = MatGraph::init(Mat::<usize>::init());
+        let id_map = graph.continuos_id_map();
+
+        // When: Multiplying by an empty vector.
+        let result = spmv(&graph, &id_map, &Vec::<f64>::new(), |_| 1.0);
+
+        // Then:
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn spmv_undirected_is_symmetric() {
+        // Given: Graph
+        //
+        //      a  --2--  b  --3--  c
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 2.into());
+        graph.add_edge_unchecked(b, c, 3.into());
+        let id_map = graph.continuos_id_map();
+
+        // When: Multiplying by the all ones vector.
+        let result = spmv(&graph, &id_map, &[1_usize; 3], |edge| {
+            edge.get_weight().unwrap()
+        });
+
+        // Then: Result is the weighted degree of each vertex.
+        assert_eq!(result[id_map.virt_id_of(a)], 2);
+        assert_eq!(result[id_map.virt_id_of(b)], 5);
+        assert_eq!(result[id_map.virt_id_of(c)], 3);
+    }
+
+    #[test]
+    fn spmm_matches_spmv_per_column() {
+        // Given: Graph
+        //
+        //      a  --1--  b
+        //      |         |
+        //      4         2
+        //      |         |
+        //      d  --3--  c
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 2.into());
+        graph.add_edge_unchecked(c, d, 3.into());
+        graph.add_edge_unchecked(d, a, 4.into());
+        let id_map = graph.continuos_id_map();
+        let weight_fn = |edge: &crate::graph::DefaultEdge<usize>| edge.get_weight().unwrap() as f64;
+
+        // When: Multiplying by a two column matrix.
+        let matrix = vec![
+            vec![1.0, 0.5],
+            vec![2.0, 0.0],
+            vec![3.0, -1.0],
+            vec![4.0, 2.0],
+        ];
+        let result = spmm(&graph, &id_map, &matrix, weight_fn);
+
+        // Then: Each column equals the result of spmv.
+        for column in 0..2 {
+            let vector: Vec<f64> = matrix.iter().map(|row| row[column]).collect();
+            let expected = spmv(&graph, &id_map, &vector, weight_fn);
+
+            for virt_id in 0..4 {
+                assert_eq!(result[virt_id][column], expected[virt_id]);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn spmv_with_wrong_length() {
+        // Given: Graph with one vertex.
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        graph.add_vertex();
+        let id_map = graph.continuos_id_map();
+
+        // When: Multiplying by a vector of wrong length.
+        spmv(&graph, &id_map, &[1.0, 2.0], |_| 1.0);
+    }
+}
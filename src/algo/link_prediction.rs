@@ -0,0 +1,313 @@
+use std::collections::HashSet;
+
+use crate::graph::{Edge, UndirectedEdge};
+use crate::provide::{Graph, Neighbors};
+
+#[derive(Debug, Clone, Copy)]
+enum Index {
+    Jaccard,
+    AdamicAdar,
+    PreferentialAttachment,
+    ResourceAllocation,
+}
+
+/// Iterator over candidate pairs of vertices and their link prediction scores.
+///
+/// Each item is in the format of (`u_id`, `v_id`, score). Scores are computed lazily from the neighbors of each pair when it's reached,
+/// so nothing is precomputed and large graphs can be scored in chunks or only partially. Neighbors of a vertex are considered as a set,
+/// so parallel edges are counted once.
+///
+/// Created by [`jaccard_coefficient`](crate::algo::link_prediction::jaccard_coefficient), [`adamic_adar_index`](crate::algo::link_prediction::adamic_adar_index),
+/// [`preferential_attachment`](crate::algo::link_prediction::preferential_attachment) and [`resource_allocation_index`](crate::algo::link_prediction::resource_allocation_index).
+pub struct LinkPredictions<'a, G, I> {
+    graph: &'a G,
+    pairs: I,
+    index: Index,
+}
+
+impl<'a, G, I> LinkPredictions<'a, G, I>
+where
+    G: Neighbors,
+    I: Iterator<Item = (usize, usize)>,
+{
+    fn neighbors_of(&self, vertex_id: usize) -> HashSet<usize> {
+        self.graph
+            .neighbors_unchecked(vertex_id)
+            .into_iter()
+            .collect()
+    }
+
+    fn score_of(&self, u_id: usize, v_id: usize) -> f64 {
+        let (u_neighbors, v_neighbors) = (self.neighbors_of(u_id), self.neighbors_of(v_id));
+        let common_neighbors = || u_neighbors.intersection(&v_neighbors);
+
+        match self.index {
+            Index::Jaccard => {
+                let union_size = u_neighbors.union(&v_neighbors).count();
+                if union_size == 0 {
+                    0.0
+                } else {
+                    common_neighbors().count() as f64 / union_size as f64
+                }
+            }
+            Index::AdamicAdar => common_neighbors()
+                .map(|w_id| self.neighbors_of(*w_id).len())
+                // A common neighbor with one neighbor only happens when u and v are the same.
+                .filter(|degree| *degree > 1)
+                .map(|degree| 1.0 / (degree as f64).ln())
+                .sum(),
+            Index::PreferentialAttachment => (u_neighbors.len() * v_neighbors.len()) as f64,
+            Index::ResourceAllocation => common_neighbors()
+                .map(|w_id| 1.0 / self.neighbors_of(*w_id).len() as f64)
+                .sum(),
+        }
+    }
+}
+
+impl<'a, G, I> Iterator for LinkPredictions<'a, G, I>
+where
+    G: Neighbors,
+    I: Iterator<Item = (usize, usize)>,
+{
+    type Item = (usize, usize, f64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (u_id, v_id) = self.pairs.next()?;
+
+        Some((u_id, v_id, self.score_of(u_id, v_id)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.pairs.size_hint()
+    }
+}
+
+fn predictions_of<W, E, G, P>(
+    graph: &G,
+    pairs: P,
+    index: Index,
+) -> LinkPredictions<'_, G, P::IntoIter>
+where
+    E: Edge<W>,
+    G: Neighbors + Graph<W, E, UndirectedEdge>,
+    P: IntoIterator<Item = (usize, usize)>,
+{
+    LinkPredictions {
+        graph,
+        pairs: pairs.into_iter(),
+        index,
+    }
+}
+
+/// Scores each pair by the [Jaccard coefficient](https://en.wikipedia.org/wiki/Jaccard_index) of their neighbors: |Γ(u) ∩ Γ(v)| / |Γ(u) ∪ Γ(v)|.
+///
+/// # Arguments
+/// * `graph`: Graph to predict its missing links.
+/// * `pairs`: Candidate pairs of vertices in the format of (`u_id`, `v_id`). Both ids must be in the graph.
+///
+/// # Returns
+/// Lazy iterator over pairs and their scores, in the same order as `pairs`. Score of a pair of vertices without any neighbor is zero.
+///
+/// # Complexity
+/// O(deg(u) + deg(v)) for each pair.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::List;
+/// use prepona::graph::ListGraph;
+/// use prepona::algo::link_prediction::jaccard_coefficient;
+///
+/// // Given: Graph
+/// //
+/// //      a  ---  b  ---  c
+/// //       \              |
+/// //        ----  d  -----'
+/// //
+/// let mut graph = ListGraph::init(List::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(c, d, 1.into());
+/// graph.add_edge_unchecked(d, a, 1.into());
+///
+/// // When:
+/// let scores: Vec<(usize, usize, f64)> = jaccard_coefficient(&graph, vec![(a, c), (a, b)]).collect();
+///
+/// // Then: a and c share all of their neighbors.
+/// assert_eq!(scores, vec![(a, c, 1.0), (a, b, 0.0)]);
+/// ```
+pub fn jaccard_coefficient<W, E, G, P>(graph: &G, pairs: P) -> LinkPredictions<'_, G, P::IntoIter>
+where
+    E: Edge<W>,
+    G: Neighbors + Graph<W, E, UndirectedEdge>,
+    P: IntoIterator<Item = (usize, usize)>,
+{
+    predictions_of(graph, pairs, Index::Jaccard)
+}
+
+/// Scores each pair by the [Adamic-Adar index](https://en.wikipedia.org/wiki/Adamic%E2%80%93Adar_index): Σ 1 / ln(|Γ(w)|) for each common neighbor w.
+///
+/// Common neighbors with few neighbors of their own contribute more to the score.
+///
+/// # Arguments
+/// * `graph`: Graph to predict its missing links.
+/// * `pairs`: Candidate pairs of vertices in the format of (`u_id`, `v_id`). Both ids must be in the graph.
+///
+/// # Returns
+/// Lazy iterator over pairs and their scores, in the same order as `pairs`.
+///
+/// # Complexity
+/// O(deg(u) + deg(v) + Σ deg(w)) for each pair, where w is a common neighbor of u and v.
+pub fn adamic_adar_index<W, E, G, P>(graph: &G, pairs: P) -> LinkPredictions<'_, G, P::IntoIter>
+where
+    E: Edge<W>,
+    G: Neighbors + Graph<W, E, UndirectedEdge>,
+    P: IntoIterator<Item = (usize, usize)>,
+{
+    predictions_of(graph, pairs, Index::AdamicAdar)
+}
+
+/// Scores each pair by preferential attachment: |Γ(u)| * |Γ(v)|.
+///
+/// # Arguments
+/// * `graph`: Graph to predict its missing links.
+/// * `pairs`: Candidate pairs of vertices in the format of (`u_id`, `v_id`). Both ids must be in the graph.
+///
+/// # Returns
+/// Lazy iterator over pairs and their scores, in the same order as `pairs`.
+///
+/// # Complexity
+/// O(deg(u) + deg(v)) for each pair.
+pub fn preferential_attachment<W, E, G, P>(
+    graph: &G,
+    pairs: P,
+) -> LinkPredictions<'_, G, P::IntoIter>
+where
+    E: Edge<W>,
+    G: Neighbors + Graph<W, E, UndirectedEdge>,
+    P: IntoIterator<Item = (usize, usize)>,
+{
+    predictions_of(graph, pairs, Index::PreferentialAttachment)
+}
+
+/// Scores each pair by the resource allocation index: Σ 1 / |Γ(w)| for each common neighbor w.
+///
+/// Like [`adamic_adar_index`](crate::algo::link_prediction::adamic_adar_index) but punishes common neighbors with many neighbors more heavily.
+///
+/// # Arguments
+/// * `graph`: Graph to predict its missing links.
+/// * `pairs`: Candidate pairs of vertices in the format of (`u_id`, `v_id`). Both ids must be in the graph.
+///
+/// # Returns
+/// Lazy iterator over pairs and their scores, in the same order as `pairs`.
+///
+/// # Complexity
+/// O(deg(u) + deg(v) + Σ deg(w)) for each pair, where w is a common neighbor of u and v.
+pub fn resource_allocation_index<W, E, G, P>(
+    graph: &G,
+    pairs: P,
+) -> LinkPredictions<'_, G, P::IntoIter>
+where
+    E: Edge<W>,
+    G: Neighbors + Graph<W, E, UndirectedEdge>,
+    P: IntoIterator<Item = (usize, usize)>,
+{
+    predictions_of(graph, pairs, Index::ResourceAllocation)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{ListGraph, MultiListGraph};
+    use crate::storage::List;
+
+    // Graph
+    //
+    //      a  ---  b  ---  c
+    //      |       |
+    //      d  ---  e       f
+    //
+    fn graph() -> (ListGraph<usize, UndirectedEdge>, Vec<usize>) {
+        let mut graph = ListGraph::init(List::<usize>::init());
+        let vertices: Vec<usize> = (0..6).map(|_| graph.add_vertex()).collect();
+        for (src, dst) in [(0, 1), (1, 2), (0, 3), (3, 4), (1, 4)].iter() {
+            graph.add_edge_unchecked(vertices[*src], vertices[*dst], 1.into());
+        }
+
+        (graph, vertices)
+    }
+
+    #[test]
+    fn scores_of_pairs() {
+        // Given:
+        let (graph, v) = graph();
+        let (a, c, e, f) = (v[0], v[2], v[4], v[5]);
+        let pairs = vec![(a, e), (a, c), (c, f)];
+
+        // When:
+        let jaccard: Vec<f64> = jaccard_coefficient(&graph, pairs.clone())
+            .map(|(_, _, score)| score)
+            .collect();
+        let adamic_adar: Vec<f64> = adamic_adar_index(&graph, pairs.clone())
+            .map(|(_, _, score)| score)
+            .collect();
+        let attachment: Vec<f64> = preferential_attachment(&graph, pairs.clone())
+            .map(|(_, _, score)| score)
+            .collect();
+        let allocation: Vec<f64> = resource_allocation_index(&graph, pairs)
+            .map(|(_, _, score)| score)
+            .collect();
+
+        // Then: a and e share b(with 3 neighbors) and d(with 2 neighbors).
+        assert_eq!(jaccard, vec![1.0, 1.0 / 2.0, 0.0]);
+        assert!((adamic_adar[0] - (1.0 / 3f64.ln() + 1.0 / 2f64.ln())).abs() < 1e-12);
+        assert!((adamic_adar[1] - 1.0 / 3f64.ln()).abs() < 1e-12);
+        assert_eq!(adamic_adar[2], 0.0);
+        assert_eq!(attachment, vec![4.0, 2.0, 0.0]);
+        assert!((allocation[0] - (1.0 / 3.0 + 1.0 / 2.0)).abs() < 1e-12);
+        assert!((allocation[1] - 1.0 / 3.0).abs() < 1e-12);
+        assert_eq!(allocation[2], 0.0);
+    }
+
+    #[test]
+    fn scores_are_lazy() {
+        // Given:
+        let (graph, v) = graph();
+
+        // When: Pairs are generated endlessly.
+        let scores: Vec<(usize, usize, f64)> =
+            preferential_attachment(&graph, (0..).map(|i| (v[i % 6], v[(i + 1) % 6])))
+                .take(3)
+                .collect();
+
+        // Then:
+        assert_eq!(
+            scores,
+            vec![(v[0], v[1], 6.0), (v[1], v[2], 3.0), (v[2], v[3], 2.0)]
+        );
+    }
+
+    #[test]
+    fn parallel_edges_are_counted_once() {
+        // Given: a and c are both connected to b, a with two parallel edges.
+        let mut graph = MultiListGraph::init(List::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+
+        // When:
+        let (_, _, score) = resource_allocation_index(&graph, vec![(a, c)])
+            .next()
+            .unwrap();
+
+        // Then:
+        assert_eq!(score, 1.0 / 2.0);
+    }
+}
@@ -0,0 +1,597 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::graph::{DirectedEdge, Edge, EdgeDir, ListGraph};
+use crate::provide::{Direction, Edges, Graph, IdMap, Vertices};
+use crate::storage::DiList;
+
+// Arc of the residual network. Each edge of a directed graph becomes an arc with its capacity and a reverse arc with no capacity.
+// Each edge of an undirected graph becomes two arcs, each with the capacity of the edge, that are the reverse of each other.
+struct Arc {
+    src_virt_id: usize,
+    dst_virt_id: usize,
+    capacity: usize,
+    flow: isize,
+}
+
+impl Arc {
+    fn residual(&self) -> usize {
+        (self.capacity as isize - self.flow) as usize
+    }
+}
+
+/// Flow of every edge in a maximum flow, returned by [`max_flow`](crate::algo::max_flow).
+pub struct FlowAssignment {
+    id_map: IdMap,
+    // Arc `2 * i` is the forward arc of the `i`-th edge and `2 * i + 1` is its reverse.
+    arcs: Vec<Arc>,
+    edge_indices: HashMap<usize, usize>,
+    src_virt_id: usize,
+    value: usize,
+}
+
+impl FlowAssignment {
+    /// # Returns
+    /// Value of the maximum flow: Net flow going out of the source.
+    pub fn value(&self) -> usize {
+        self.value
+    }
+
+    /// # Arguments
+    /// `edge_id`: Id of the edge.
+    ///
+    /// # Returns
+    /// * `Some`: Containing (`src_id`, `dst_id`, flow) of the edge. Flow goes from `src_id` to `dst_id`, which is the direction of the edge in directed graphs.
+    /// * `None`: If there is no edge with id: `edge_id`.
+    pub fn edge_flow(&self, edge_id: usize) -> Option<(usize, usize, usize)> {
+        self.edge_indices.get(&edge_id).map(|index| {
+            let arc = &self.arcs[2 * index];
+            let (src_virt_id, dst_virt_id) = if arc.flow >= 0 {
+                (arc.src_virt_id, arc.dst_virt_id)
+            } else {
+                (arc.dst_virt_id, arc.src_virt_id)
+            };
+
+            (
+                self.id_map.real_id_of(src_virt_id),
+                self.id_map.real_id_of(dst_virt_id),
+                arc.flow.unsigned_abs(),
+            )
+        })
+    }
+
+    /// # Arguments
+    /// * `src_id`: Id of the source of the flow.
+    /// * `dst_id`: Id of the destination of the flow.
+    ///
+    /// # Returns
+    /// Total flow going directly from `src_id` to `dst_id` over the edges between them.
+    pub fn flow(&self, src_id: usize, dst_id: usize) -> usize {
+        (0..self.edge_indices.len())
+            .filter_map(|index| {
+                let arc = &self.arcs[2 * index];
+                let src_real_id = self.id_map.real_id_of(arc.src_virt_id);
+                let dst_real_id = self.id_map.real_id_of(arc.dst_virt_id);
+
+                if (src_real_id, dst_real_id) == (src_id, dst_id) && arc.flow > 0 {
+                    Some(arc.flow as usize)
+                } else if (dst_real_id, src_real_id) == (src_id, dst_id) && arc.flow < 0 {
+                    Some(arc.flow.unsigned_abs())
+                } else {
+                    None
+                }
+            })
+            .sum()
+    }
+
+    /// # Returns
+    /// Edges with positive capacity whose flow is equal to their capacity, in the format of (`src_id`, `dst_id`, `edge_id`).
+    /// Edges of undirected graphs are reported in the direction of their flow.
+    pub fn saturated_edges(&self) -> Vec<(usize, usize, usize)> {
+        self.edge_indices
+            .iter()
+            .filter(|(_, index)| {
+                let arc = &self.arcs[2 * **index];
+                arc.capacity > 0 && arc.flow.unsigned_abs() == arc.capacity
+            })
+            .map(|(edge_id, _)| {
+                let (src_id, dst_id, _) = self.edge_flow(*edge_id).unwrap();
+                (src_id, dst_id, *edge_id)
+            })
+            .collect()
+    }
+
+    /// # Returns
+    /// (source side, sink side) of a minimum cut. Source side contains the vertices that can be reached from the source in the residual graph.
+    /// Total capacity of the edges from the source side to the sink side is equal to the value of the flow. Both sides are sorted.
+    pub fn min_cut(&self) -> (Vec<usize>, Vec<usize>) {
+        let levels = levels(
+            &self.arcs,
+            &out_arcs(&self.arcs, self.id_map.len()),
+            self.src_virt_id,
+        );
+
+        let mut source_side = vec![];
+        let mut sink_side = vec![];
+        for (virt_id, level) in levels.into_iter().enumerate() {
+            let real_id = self.id_map.real_id_of(virt_id);
+            if level.is_some() {
+                source_side.push(real_id);
+            } else {
+                sink_side.push(real_id);
+            }
+        }
+        source_side.sort_unstable();
+        sink_side.sort_unstable();
+
+        (source_side, sink_side)
+    }
+
+    /// # Returns
+    /// Residual graph: A directed graph with the same vertex ids, that has an edge from `u` to `v` if more flow can be pushed from `u` to `v`.
+    /// Weight of the edge is the amount of flow that can be pushed, including the flow that can be cancelled on edges from `v` to `u`.
+    pub fn residual_graph(&self) -> ListGraph<usize, DirectedEdge> {
+        let mut residuals: HashMap<(usize, usize), usize> = HashMap::new();
+        for arc in self.arcs.iter().filter(|arc| arc.residual() > 0) {
+            *residuals
+                .entry((arc.src_virt_id, arc.dst_virt_id))
+                .or_insert(0) += arc.residual();
+        }
+
+        // Adding every id and removing the missing ones keeps the ids of the vertices.
+        let mut graph = ListGraph::init(DiList::init());
+        let real_ids: Vec<usize> = (0..self.id_map.len())
+            .map(|virt_id| self.id_map.real_id_of(virt_id))
+            .collect();
+        let max_id = real_ids.iter().max().map_or(0, |max_id| max_id + 1);
+        for _ in 0..max_id {
+            graph.add_vertex();
+        }
+        for vertex_id in 0..max_id {
+            if self.id_map.try_virt_id_of(vertex_id).is_none() {
+                graph.remove_vertex_unchecked(vertex_id);
+            }
+        }
+
+        let mut residuals: Vec<((usize, usize), usize)> = residuals.into_iter().collect();
+        residuals.sort_unstable();
+        for ((src_virt_id, dst_virt_id), residual) in residuals {
+            graph.add_edge_unchecked(
+                real_ids[src_virt_id],
+                real_ids[dst_virt_id],
+                residual.into(),
+            );
+        }
+
+        graph
+    }
+}
+
+/// Finds a maximum flow from a source to a sink using Dinic's algorithm.
+///
+/// Edges of undirected graphs can carry flow in either direction, up to their capacity.
+///
+/// # Arguments
+/// * `graph`: Graph to find the flow in.
+/// * `src_id`: Id of the source.
+/// * `dst_id`: Id of the sink.
+/// * `capacity_fn`: Maps each edge to its capacity. For [`FlowEdge`](crate::graph::FlowEdge)s it can be `|edge| edge.get_capacity()`.
+///
+/// # Returns
+/// Flow of every edge in the maximum flow.
+///
+/// # Complexity
+/// O(|V|<sup>2</sup> * |E|)
+///
+/// # Panics
+/// * If `src_id` or `dst_id` is not in the graph.
+/// * If `src_id` is equal to `dst_id`.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::DiFlowList;
+/// use prepona::graph::{FlowEdge, SimpleGraph};
+/// use prepona::algo::max_flow;
+///
+/// // Given: Graph with capacities
+/// //
+/// //      s  --3-->  a  --2-->  t
+/// //      |                     ^
+/// //      '---1-->   b   --5----'
+/// //
+/// let mut graph = SimpleGraph::init(DiFlowList::<usize>::init());
+/// let s = graph.add_vertex();
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let t = graph.add_vertex();
+/// let sa = graph.add_edge_unchecked(s, a, FlowEdge::init_with(1.into(), 3, 0));
+/// let at = graph.add_edge_unchecked(a, t, FlowEdge::init_with(1.into(), 2, 0));
+/// let sb = graph.add_edge_unchecked(s, b, FlowEdge::init_with(1.into(), 1, 0));
+/// graph.add_edge_unchecked(b, t, FlowEdge::init_with(1.into(), 5, 0));
+///
+/// // When:
+/// let flow = max_flow(&graph, s, t, |edge| edge.get_capacity());
+///
+/// // Then:
+/// assert_eq!(flow.value(), 3);
+/// assert_eq!(flow.flow(s, a), 2);
+/// assert_eq!(flow.edge_flow(sb), Some((s, b, 1)));
+///
+/// let mut saturated: Vec<usize> = flow.saturated_edges().into_iter().map(|(_, _, edge_id)| edge_id).collect();
+/// saturated.sort();
+/// assert_eq!(saturated, vec![at, sb]);
+/// assert_eq!(flow.min_cut(), (vec![s, a], vec![b, t]));
+/// ```
+pub fn max_flow<W, E, Dir, G, F>(
+    graph: &G,
+    src_id: usize,
+    dst_id: usize,
+    capacity_fn: F,
+) -> FlowAssignment
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Vertices + Edges<W, E> + Direction<Dir>,
+    F: Fn(&E) -> usize,
+{
+    assert_ne!(
+        src_id, dst_id,
+        "Source and sink of the flow must be different"
+    );
+
+    let id_map = graph.continuos_id_map();
+    let src_virt_id = id_map.virt_id_of(src_id);
+    let dst_virt_id = id_map.virt_id_of(dst_id);
+
+    let mut arcs = vec![];
+    let mut edge_indices = HashMap::new();
+    for (src_id, dst_id, edge) in graph.edges() {
+        // Loops of undirected graphs are visible twice and never carry flow.
+        if edge_indices.contains_key(&edge.get_id()) {
+            continue;
+        }
+        edge_indices.insert(edge.get_id(), arcs.len() / 2);
+
+        let capacity = capacity_fn(edge);
+        let (src_virt_id, dst_virt_id) = (id_map.virt_id_of(src_id), id_map.virt_id_of(dst_id));
+        arcs.push(Arc {
+            src_virt_id,
+            dst_virt_id,
+            capacity,
+            flow: 0,
+        });
+        arcs.push(Arc {
+            src_virt_id: dst_virt_id,
+            dst_virt_id: src_virt_id,
+            capacity: if Dir::is_directed() { 0 } else { capacity },
+            flow: 0,
+        });
+    }
+
+    let out_arcs = out_arcs(&arcs, id_map.len());
+
+    let mut value = 0;
+    loop {
+        let levels = levels(&arcs, &out_arcs, src_virt_id);
+        if levels[dst_virt_id].is_none() {
+            break;
+        }
+
+        let mut next_arc = vec![0; id_map.len()];
+        loop {
+            let pushed = push(
+                &mut arcs,
+                &out_arcs,
+                &levels,
+                &mut next_arc,
+                src_virt_id,
+                dst_virt_id,
+                usize::MAX,
+            );
+            if pushed == 0 {
+                break;
+            }
+            value += pushed;
+        }
+    }
+
+    FlowAssignment {
+        id_map,
+        arcs,
+        edge_indices,
+        src_virt_id,
+        value,
+    }
+}
+
+fn out_arcs(arcs: &[Arc], vertex_count: usize) -> Vec<Vec<usize>> {
+    let mut out_arcs = vec![vec![]; vertex_count];
+    for (index, arc) in arcs.iter().enumerate() {
+        out_arcs[arc.src_virt_id].push(index);
+    }
+
+    out_arcs
+}
+
+// Distance of each vertex from the source in the residual graph, if it can be reached.
+fn levels(arcs: &[Arc], out_arcs: &[Vec<usize>], src_virt_id: usize) -> Vec<Option<usize>> {
+    let mut levels = vec![None; out_arcs.len()];
+    levels[src_virt_id] = Some(0);
+
+    let mut queue = VecDeque::from(vec![src_virt_id]);
+    while let Some(virt_id) = queue.pop_front() {
+        for arc in out_arcs[virt_id].iter().map(|index| &arcs[*index]) {
+            if arc.residual() > 0 && levels[arc.dst_virt_id].is_none() {
+                levels[arc.dst_virt_id] = Some(levels[virt_id].unwrap() + 1);
+                queue.push_back(arc.dst_virt_id);
+            }
+        }
+    }
+
+    levels
+}
+
+// Pushes flow along a path of increasing levels and returns the amount pushed.
+// Arcs that can not lead to the sink are skipped for the rest of the phase.
+fn push(
+    arcs: &mut [Arc],
+    out_arcs: &[Vec<usize>],
+    levels: &[Option<usize>],
+    next_arc: &mut [usize],
+    virt_id: usize,
+    dst_virt_id: usize,
+    limit: usize,
+) -> usize {
+    if virt_id == dst_virt_id {
+        return limit;
+    }
+
+    while next_arc[virt_id] < out_arcs[virt_id].len() {
+        let index = out_arcs[virt_id][next_arc[virt_id]];
+        let (next_virt_id, residual) = (arcs[index].dst_virt_id, arcs[index].residual());
+
+        if residual > 0 && levels[next_virt_id] == levels[virt_id].map(|level| level + 1) {
+            let pushed = push(
+                arcs,
+                out_arcs,
+                levels,
+                next_arc,
+                next_virt_id,
+                dst_virt_id,
+                limit.min(residual),
+            );
+
+            if pushed > 0 {
+                arcs[index].flow += pushed as isize;
+                arcs[index ^ 1].flow -= pushed as isize;
+                return pushed;
+            }
+        }
+
+        next_arc[virt_id] += 1;
+    }
+
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::has_path;
+    use crate::graph::{DefaultEdge, MultiListGraph, WeightedView};
+    use crate::misc::rng::Rng;
+    use crate::provide::Neighbors;
+    use crate::storage::List;
+
+    // Checks capacity constraints, conservation of flow and that the minimum cut has the value of the flow.
+    fn assert_valid<Dir: EdgeDir>(
+        graph: &MultiListGraph<usize, Dir>,
+        flow: &FlowAssignment,
+        src_id: usize,
+        dst_id: usize,
+    ) {
+        let mut net_flows: HashMap<usize, isize> = HashMap::new();
+        let mut seen = std::collections::HashSet::new();
+        for (_, _, edge) in graph.edges() {
+            if !seen.insert(edge.get_id()) {
+                continue;
+            }
+            let (from_id, to_id, edge_flow) = flow.edge_flow(edge.get_id()).unwrap();
+            assert!(edge_flow <= edge.get_weight().unwrap());
+            if from_id != to_id {
+                *net_flows.entry(from_id).or_insert(0) -= edge_flow as isize;
+                *net_flows.entry(to_id).or_insert(0) += edge_flow as isize;
+            }
+        }
+        for vertex_id in graph.vertices() {
+            let net_flow = net_flows.get(&vertex_id).copied().unwrap_or(0);
+            if vertex_id == src_id {
+                assert_eq!(net_flow, -(flow.value() as isize));
+            } else if vertex_id == dst_id {
+                assert_eq!(net_flow, flow.value() as isize);
+            } else {
+                assert_eq!(net_flow, 0);
+            }
+        }
+
+        let (source_side, sink_side) = flow.min_cut();
+        assert!(source_side.contains(&src_id));
+        assert!(sink_side.contains(&dst_id));
+        let mut seen = std::collections::HashSet::new();
+        let cut_capacity: usize = graph
+            .edges()
+            .into_iter()
+            .filter(|(_, _, edge)| seen.insert(edge.get_id()))
+            .filter(|(edge_src_id, edge_dst_id, _)| {
+                let crosses = source_side.contains(edge_src_id) && sink_side.contains(edge_dst_id);
+                let crosses_back =
+                    source_side.contains(edge_dst_id) && sink_side.contains(edge_src_id);
+                crosses || (Dir::is_undirected() && crosses_back)
+            })
+            .map(|(_, _, edge)| edge.get_weight().unwrap())
+            .sum();
+        assert_eq!(cut_capacity, flow.value());
+
+        assert!(!has_path(&flow.residual_graph(), src_id, dst_id));
+    }
+
+    #[test]
+    fn classic_network() {
+        // Given: Network of CLRS with maximum flow of 23.
+        let mut graph = MultiListGraph::init(DiList::<usize>::init());
+        let vertices: Vec<usize> = (0..6).map(|_| graph.add_vertex()).collect();
+        for (src, dst, capacity) in [
+            (0, 1, 16),
+            (0, 2, 13),
+            (2, 1, 4),
+            (1, 3, 12),
+            (3, 2, 9),
+            (2, 4, 14),
+            (4, 3, 7),
+            (3, 5, 20),
+            (4, 5, 4),
+        ] {
+            graph.add_edge_unchecked(vertices[src], vertices[dst], capacity.into());
+        }
+
+        // When:
+        let flow = max_flow(
+            &graph,
+            vertices[0],
+            vertices[5],
+            |edge: &DefaultEdge<usize>| edge.get_weight().unwrap(),
+        );
+
+        // Then:
+        assert_eq!(flow.value(), 23);
+        assert_valid(&graph, &flow, vertices[0], vertices[5]);
+        assert_eq!(flow.flow(vertices[5], vertices[3]), 0);
+    }
+
+    #[test]
+    fn residual_graph_of_a_path() {
+        // Given: Graph
+        //
+        //      a  --2-->  b  --1-->  c
+        //
+        let mut graph = MultiListGraph::init(DiList::<usize>::init());
+        let removed = graph.add_vertex();
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.remove_vertex_unchecked(removed);
+        graph.add_edge_unchecked(a, b, 2.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+
+        // When:
+        let flow = max_flow(&graph, a, c, |edge: &DefaultEdge<usize>| {
+            edge.get_weight().unwrap()
+        });
+        let residual = flow.residual_graph();
+
+        // Then: One unit can still go from a to b, and the pushed unit can be cancelled on both edges.
+        assert_eq!(residual.vertices(), vec![a, b, c]);
+        assert_eq!(
+            residual.edges_between_unchecked(a, b)[0].get_weight(),
+            &1.into()
+        );
+        assert_eq!(
+            residual.edges_between_unchecked(b, a)[0].get_weight(),
+            &1.into()
+        );
+        assert_eq!(
+            residual.edges_between_unchecked(c, b)[0].get_weight(),
+            &1.into()
+        );
+        assert!(!residual.has_any_edge_unchecked(b, c));
+        assert_eq!(flow.min_cut(), (vec![a, b], vec![c]));
+    }
+
+    #[test]
+    fn capacities_from_weighted_view() {
+        // Given: Unweighted graph
+        //
+        //      a  ---  b  ---  c
+        //      |               |
+        //      '---------------'
+        //
+        let mut graph = MultiListGraph::init(List::<()>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, ().into());
+        graph.add_edge_unchecked(b, c, ().into());
+        graph.add_edge_unchecked(a, c, ().into());
+
+        // And: Capacities kept outside of the graph.
+        let capacities: HashMap<(usize, usize), usize> =
+            HashMap::from([((a, b), 4), ((b, c), 2), ((a, c), 3)]);
+        let view = WeightedView::init(&graph, |src_id, dst_id| {
+            capacities[&(src_id.min(dst_id), src_id.max(dst_id))]
+        });
+
+        // When:
+        let flow = max_flow(&view, a, c, |edge: &DefaultEdge<usize>| {
+            edge.get_weight().unwrap()
+        });
+
+        // Then: Two units go through b and three go directly to c.
+        assert_eq!(flow.value(), 5);
+        assert_eq!(flow.flow(a, b), 2);
+        assert_eq!(flow.flow(a, c), 3);
+        assert_eq!(flow.min_cut(), (vec![a, b], vec![c]));
+    }
+
+    #[test]
+    fn random_networks() {
+        let mut rng = Rng::seed_from(5);
+        for round in 0..40 {
+            // Given: Random directed or undirected network with parallel edges and loops.
+            let vertex_count = 2 + rng.next_below(8);
+            let edges: Vec<(usize, usize, usize)> = (0..3 * vertex_count)
+                .map(|_| {
+                    (
+                        rng.next_below(vertex_count),
+                        rng.next_below(vertex_count),
+                        rng.next_below(6),
+                    )
+                })
+                .collect();
+
+            if round % 2 == 0 {
+                let mut graph = MultiListGraph::init(DiList::<usize>::init());
+                for _ in 0..vertex_count {
+                    graph.add_vertex();
+                }
+                for (src_id, dst_id, capacity) in edges.iter() {
+                    graph.add_edge_unchecked(*src_id, *dst_id, (*capacity).into());
+                }
+
+                // When:
+                let flow = max_flow(&graph, 0, vertex_count - 1, |edge: &DefaultEdge<usize>| {
+                    edge.get_weight().unwrap()
+                });
+
+                // Then:
+                assert_valid(&graph, &flow, 0, vertex_count - 1);
+            } else {
+                let mut graph = MultiListGraph::init(List::<usize>::init());
+                for _ in 0..vertex_count {
+                    graph.add_vertex();
+                }
+                for (src_id, dst_id, capacity) in edges.iter() {
+                    graph.add_edge_unchecked(*src_id, *dst_id, (*capacity).into());
+                }
+
+                // When:
+                let flow = max_flow(&graph, 0, vertex_count - 1, |edge: &DefaultEdge<usize>| {
+                    edge.get_weight().unwrap()
+                });
+
+                // Then:
+                assert_valid(&graph, &flow, 0, vertex_count - 1);
+                assert!(flow.value() <= graph.neighbors_unchecked(0).len() * 5);
+            }
+        }
+    }
+}
@@ -0,0 +1,367 @@
+use magnitude::Magnitude;
+use num_traits::{ToPrimitive, Zero};
+use std::any::Any;
+
+use crate::graph::{Edge, UndirectedEdge};
+use crate::provide::{Edges, Graph, IdMap, Vertices};
+
+/// Finds an approximate maximum cut of an undirected graph.
+///
+/// Finding the [maximum cut](https://en.wikipedia.org/wiki/Maximum_cut) is NP-hard, so this structure provides two heuristics:
+/// * `execute_local_search`: Greedily places each vertex on the side that cuts more weight, then keeps flipping vertices
+///   while flipping one of them increases the weight of the cut. The result is a local optimum which cuts at least half of the total weight.
+/// * `execute_spectral`: Inspired by Goemans-Williamson rounding. Computes the eigenvector of the largest eigenvalue of the laplacian
+///   using power iteration and partitions the vertices by the sign of their entry in that vector. The partition is then refined by the local search.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+/// use prepona::algo::MaxCut;
+///
+/// // Given: Graph
+/// //
+/// //      a  ---  b
+/// //      |       |
+/// //      d  ---  c
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+///
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(c, d, 1.into());
+/// graph.add_edge_unchecked(d, a, 1.into());
+///
+/// // When: Searching for the maximum cut.
+/// let (side1, side2, cut_weight) = MaxCut::init(&graph).execute_local_search(&graph);
+///
+/// // Then: All edges are cut.
+/// assert_eq!(cut_weight, 4.into());
+/// assert_eq!(side1.len(), 2);
+/// assert_eq!(side2.len(), 2);
+/// assert_eq!(side1.contains(&a), side1.contains(&c));
+/// ```
+pub struct MaxCut {
+    id_map: IdMap,
+    in_first_side: Vec<bool>,
+}
+
+impl MaxCut {
+    /// Initializes the structure.
+    pub fn init<W, E, G>(graph: &G) -> Self
+    where
+        E: Edge<W>,
+        G: Vertices + Edges<W, E> + Graph<W, E, UndirectedEdge>,
+    {
+        MaxCut {
+            id_map: graph.continuos_id_map(),
+            in_first_side: vec![true; graph.vertex_count()],
+        }
+    }
+
+    /// Finds an approximate maximum cut using greedy placement followed by local search.
+    ///
+    /// # Arguments
+    /// `graph`: Graph to find its maximum cut.
+    ///
+    /// # Returns
+    /// (first side, second side, weight of the cut) \
+    /// Each side is a vector containing ids of vertices on that side of the cut.
+    ///
+    /// # Complexity
+    /// Each pass over the vertices is O(|V| + |E|) and each pass strictly increases the weight of the cut.
+    pub fn execute_local_search<W, E, G>(
+        mut self,
+        graph: &G,
+    ) -> (Vec<usize>, Vec<usize>, Magnitude<W>)
+    where
+        W: Any + Copy + Zero + Ord,
+        E: Edge<W>,
+        G: Vertices + Edges<W, E> + Graph<W, E, UndirectedEdge>,
+    {
+        let vertex_count = self.in_first_side.len();
+
+        // Place each vertex on the side opposite to the heavier group of its already placed neighbors.
+        let mut is_placed = vec![false; vertex_count];
+        for virt_id in 0..vertex_count {
+            let (same_side, other_side) = self.weight_to_sides(graph, virt_id, &is_placed);
+
+            self.in_first_side[virt_id] = same_side <= other_side;
+            is_placed[virt_id] = true;
+        }
+
+        self.local_search(graph);
+
+        self.dissolve(graph)
+    }
+
+    /// Finds an approximate maximum cut using spectral rounding followed by local search.
+    ///
+    /// # Arguments
+    /// * `graph`: Graph to find its maximum cut.
+    /// * `iterations`: Number of power iterations used to approximate the eigenvector.
+    ///
+    /// # Returns
+    /// (first side, second side, weight of the cut) \
+    /// Each side is a vector containing ids of vertices on that side of the cut.
+    ///
+    /// # Complexity
+    /// O(`iterations` * (|V| + |E|)) for computing the eigenvector plus the cost of the local search.
+    pub fn execute_spectral<W, E, G>(
+        mut self,
+        graph: &G,
+        iterations: usize,
+    ) -> (Vec<usize>, Vec<usize>, Magnitude<W>)
+    where
+        W: Any + Copy + Zero + Ord + ToPrimitive,
+        E: Edge<W>,
+        G: Vertices + Edges<W, E> + Graph<W, E, UndirectedEdge>,
+    {
+        let vertex_count = self.in_first_side.len();
+
+        let weighted_neighbors: Vec<Vec<(usize, f64)>> = (0..vertex_count)
+            .map(|virt_id| {
+                graph
+                    .edges_from_unchecked(self.id_map.real_id_of(virt_id))
+                    .into_iter()
+                    .filter_map(|(dst_id, edge)| {
+                        edge.get_weight()
+                            .as_ref()
+                            .and_then(|weight| weight.to_f64())
+                            .map(|weight| (self.id_map.virt_id_of(dst_id), weight))
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let degrees: Vec<f64> = weighted_neighbors
+            .iter()
+            .map(|neighbors| neighbors.iter().map(|(_, weight)| weight).sum())
+            .collect();
+
+        // Start from a vector that is not likely to be orthogonal to the dominant eigenvector.
+        let mut vector: Vec<f64> = (0..vertex_count)
+            .map(|virt_id| if virt_id % 2 == 0 { 1.0 } else { -1.0 } * (1.0 + virt_id as f64))
+            .collect();
+
+        // Laplacian is positive semi-definite, so power iteration converges to the eigenvector of its largest eigenvalue.
+        for _ in 0..iterations {
+            let mut next: Vec<f64> = (0..vertex_count)
+                .map(|virt_id| {
+                    degrees[virt_id] * vector[virt_id]
+                        - weighted_neighbors[virt_id]
+                            .iter()
+                            .map(|(n_virt_id, weight)| weight * vector[*n_virt_id])
+                            .sum::<f64>()
+                })
+                .collect();
+
+            let norm = next.iter().map(|value| value * value).sum::<f64>().sqrt();
+            if norm == 0.0 {
+                break;
+            }
+            next.iter_mut().for_each(|value| *value /= norm);
+
+            vector = next;
+        }
+
+        self.in_first_side = vector.iter().map(|value| *value >= 0.0).collect();
+
+        self.local_search(graph);
+
+        self.dissolve(graph)
+    }
+
+    // Flips vertices while flipping any of them increases the weight of the cut.
+    fn local_search<W, E, G>(&mut self, graph: &G)
+    where
+        W: Any + Copy + Zero + Ord,
+        E: Edge<W>,
+        G: Vertices + Edges<W, E>,
+    {
+        let is_placed = vec![true; self.in_first_side.len()];
+
+        let mut improved = true;
+        while improved {
+            improved = false;
+
+            for virt_id in 0..self.in_first_side.len() {
+                let (same_side, other_side) = self.weight_to_sides(graph, virt_id, &is_placed);
+
+                // Flipping the vertex cuts its edges to the same side and uncuts its edges to the other side.
+                if same_side > other_side {
+                    self.in_first_side[virt_id] = !self.in_first_side[virt_id];
+                    improved = true;
+                }
+            }
+        }
+    }
+
+    // # Returns
+    // (Weight of edges from the vertex to placed vertices on its side, Weight of edges from the vertex to placed vertices on the other side)
+    fn weight_to_sides<W, E, G>(
+        &self,
+        graph: &G,
+        virt_id: usize,
+        is_placed: &[bool],
+    ) -> (Magnitude<W>, Magnitude<W>)
+    where
+        W: Any + Copy + Zero + Ord,
+        E: Edge<W>,
+        G: Vertices + Edges<W, E>,
+    {
+        let mut same_side = W::zero().into();
+        let mut other_side = W::zero().into();
+
+        let real_id = self.id_map.real_id_of(virt_id);
+        for (dst_real_id, edge) in graph.edges_from_unchecked(real_id) {
+            let dst_virt_id = self.id_map.virt_id_of(dst_real_id);
+
+            // Loops are never cut, so they don't affect the decision.
+            if dst_virt_id == virt_id || !is_placed[dst_virt_id] {
+                continue;
+            }
+
+            if self.in_first_side[dst_virt_id] == self.in_first_side[virt_id] {
+                same_side += *edge.get_weight();
+            } else {
+                other_side += *edge.get_weight();
+            }
+        }
+
+        (same_side, other_side)
+    }
+
+    fn dissolve<W, E, G>(self, graph: &G) -> (Vec<usize>, Vec<usize>, Magnitude<W>)
+    where
+        W: Any + Copy + Zero + Ord,
+        E: Edge<W>,
+        G: Vertices + Edges<W, E>,
+    {
+        let mut cut_weight = W::zero().into();
+        for (src_id, dst_id, edge) in graph.edges() {
+            let src_virt_id = self.id_map.virt_id_of(src_id);
+            let dst_virt_id = self.id_map.virt_id_of(dst_id);
+
+            if self.in_first_side[src_virt_id] != self.in_first_side[dst_virt_id] {
+                cut_weight += *edge.get_weight();
+            }
+        }
+
+        let (first_side, second_side): (Vec<usize>, Vec<usize>) = (0..self.in_first_side.len())
+            .map(|virt_id| self.id_map.real_id_of(virt_id))
+            .partition(|real_id| self.in_first_side[self.id_map.virt_id_of(*real_id)]);
+
+        (first_side, second_side, cut_weight)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::MatGraph;
+    use crate::storage::Mat;
+
+    #[test]
+    fn empty_graph() {
+        // Given: An empty graph.
+        let graph = MatGraph::init(Mat::<usize>::init());
+
+        // When: Searching for the maximum cut.
+        let (side1, side2, cut_weight) = MaxCut::init(&graph).execute_local_search(&graph);
+
+        // Then:
+        assert!(side1.is_empty());
+        assert!(side2.is_empty());
+        assert_eq!(cut_weight, 0.into());
+    }
+
+    #[test]
+    fn triangle() {
+        // Given: Graph
+        //
+        //      a  ---  b
+        //       \     /
+        //        \   /
+        //          c
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(c, a, 1.into());
+
+        // When: Searching for the maximum cut.
+        let (side1, side2, cut_weight) = MaxCut::init(&graph).execute_local_search(&graph);
+
+        // Then: Only two of the three edges can be cut.
+        assert_eq!(cut_weight, 2.into());
+        assert_eq!(side1.len() + side2.len(), 3);
+    }
+
+    #[test]
+    fn weighted_graph_local_search() {
+        // Given: Graph
+        //          5
+        //      a  ---  b
+        //    1 |       | 1
+        //      d  ---  c
+        //          5
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 5.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(c, d, 5.into());
+        graph.add_edge_unchecked(d, a, 1.into());
+
+        // When: Searching for the maximum cut.
+        let (side1, _, cut_weight) = MaxCut::init(&graph).execute_local_search(&graph);
+
+        // Then: The bipartition {a, c} | {b, d} cuts every edge.
+        assert_eq!(cut_weight, 12.into());
+        assert_eq!(side1.contains(&a), side1.contains(&c));
+        assert_eq!(side1.contains(&b), side1.contains(&d));
+    }
+
+    #[test]
+    fn bipartite_graph_spectral() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  c
+        //      |       |       |
+        //      d  ---  e  ---  f
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        let f = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(a, d, 1.into());
+        graph.add_edge_unchecked(b, e, 1.into());
+        graph.add_edge_unchecked(c, f, 1.into());
+        graph.add_edge_unchecked(d, e, 1.into());
+        graph.add_edge_unchecked(e, f, 1.into());
+
+        // When: Searching for the maximum cut.
+        let (side1, side2, cut_weight) = MaxCut::init(&graph).execute_spectral(&graph, 100);
+
+        // Then: Graph is bipartite so all edges are cut.
+        assert_eq!(cut_weight, 7.into());
+        assert_eq!(side1.len(), 3);
+        assert_eq!(side2.len(), 3);
+    }
+}
@@ -1,21 +1,107 @@
+/// Projections of bipartite graphs.
+///
+/// Algorithms over bipartite graphs, where vertices are split into a top and a bottom set and edges only connect the two sets.
+///
+/// Unlike [`Bipartite`](crate::storage::Bipartite) storage, these algorithms work on any graph and only need to know which vertices are on top.
+pub mod bipartite;
+
+mod algorithm;
+mod assignment;
+mod bit_adj;
 mod cc;
+mod coloring;
+mod ego_graph;
 mod error;
 mod eulerian;
+mod graph_power;
 mod has_cycle;
+mod incremental_dag;
+mod isomorphism;
+
+/// Computing positions of vertices for drawing graphs.
+pub mod layout;
+
+mod lca;
+
+/// Scoring candidate pairs of vertices by how likely they are to be linked.
+pub mod link_prediction;
+
+/// Sparse matrix kernels over the adjacency matrix of a graph.
+///
+/// Kernels that treat a graph as its weighted adjacency matrix `A`, where `A[u][v]` is the sum of weights of edges from `u` to `v`.
+///
+/// Vectors and matrices are dense and indexed by virtual ids of an [`IdMap`](crate::provide::IdMap),
+/// usually the one returned by [`continuos_id_map`](crate::provide::Vertices::continuos_id_map).
+/// Since undirected edges are visible from both of their end points, adjacency matrix of an undirected graph is symmetric.
+///
+/// These kernels are the building blocks of iterative algorithms like power iteration, PageRank and label propagation.
+pub mod linalg;
+
+mod max_flow;
+mod maxcut;
 mod mst;
+mod partition;
+mod per_component;
+mod planarity;
+mod privacy;
+mod random_walk;
+mod reachability;
 mod shortest_path;
+mod simple_cycles;
+mod simrank;
+mod temporal_paths;
 mod topological_sort;
 mod traversal;
+mod two_hop;
 mod vertex_edge_cut;
 
-pub use cc::{ConnectedComponents, TarjanSCC};
+pub use algorithm::{Algorithm, MapOutput};
+pub use assignment::{hungarian, min_cost_assignment};
+pub use bit_adj::BitAdj;
+pub use cc::{ConnectedComponents, IncrementalScc, TarjanSCC};
+pub use coloring::{color_with_k, KColoring};
+pub use ego_graph::ego_graph;
 pub use error::{Error, ErrorKind};
 pub use eulerian::Eulerian;
-pub use has_cycle::HasCycle;
+pub use graph_power::graph_power;
+pub use has_cycle::{find_cycle, is_dag, HasCycle};
+pub use incremental_dag::{try_add_edge_acyclic, IncrementalDag};
+pub use isomorphism::{
+    graph_hash, match_pattern, wl_colors, GraphRole, IsomorphismType, VF2Isomorphism, VF2Mappings,
+};
+pub use lca::Lca;
+pub use max_flow::{max_flow, FlowAssignment};
+pub use maxcut::MaxCut;
 pub use mst::Kruskal;
+pub use partition::{fiedler_vector, kernighan_lin, spectral_bisection};
+pub use per_component::{per_component, run_per_component};
+pub use planarity::{dual_graph, is_planar, planarity, DualGraph, Embedding, Planarity};
+pub use privacy::KDegreeAnonymity;
+pub use random_walk::{node2vec_walks, random_walks, Node2VecWalk, RandomWalk};
+pub use reachability::{has_path, is_reachable};
+pub use shortest_path::AStar;
 pub use shortest_path::BellmanFord;
+pub use shortest_path::BfsShortestPath;
+pub use shortest_path::ContractionHierarchy;
+pub use shortest_path::DeltaStepping;
 pub use shortest_path::Dijkstra;
-pub use shortest_path::FloydWarshall;
+pub use shortest_path::DynamicSSSP;
+pub use shortest_path::Landmarks;
+pub use shortest_path::{bfs_layers, shortest_path_dag, ShortestPathDag};
+pub use shortest_path::{bfs_within, dijkstra_filtered, dijkstra_within};
+pub use shortest_path::{count_all_shortest_paths, count_shortest_paths};
+pub use shortest_path::{dag_longest_path, dag_shortest_path};
+pub use shortest_path::{voronoi_partition, VoronoiPartition};
+pub use shortest_path::{AllPairsShortestPaths, FloydWarshall};
+pub use simple_cycles::{simple_cycles, SimpleCycles};
+pub use simrank::{approximate_simrank, simrank};
+pub use temporal_paths::{
+    earliest_arrival_path, fastest_path, latest_departure_path, TemporalHop, TemporalPath,
+};
 pub use topological_sort::TopologicalSort;
-pub use traversal::{Bfs, BfsListener, Color, Dfs, DfsListener};
+pub use traversal::{
+    BestFirst, BestFirstListener, Bfs, BfsIter, BfsListener, Color, Dfs, DfsIter, DfsListener,
+    Orientation, QueueKind, Walker, WalkerIter,
+};
+pub use two_hop::TwoHopIndex;
 pub use vertex_edge_cut::VertexEdgeCut;
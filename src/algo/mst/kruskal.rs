@@ -2,6 +2,7 @@ use std::cell::RefCell;
 use std::collections::HashSet;
 use std::rc::Rc;
 
+use crate::algo::Algorithm;
 use crate::graph::{subgraph::Subgraph, Edge, UndirectedEdge};
 use crate::provide;
 
@@ -66,7 +67,7 @@ impl Kruskal {
     /// Initializes the structure.
     pub fn init<G, W: Ord, E: Edge<W>>(graph: &G) -> Self
     where
-        G: provide::Vertices + provide::Edges<W, E> + provide::Graph<W, E, UndirectedEdge>,
+        G: provide::Vertices + provide::Edges<W, E> + provide::Direction<UndirectedEdge>,
     {
         let vertex_count = graph.vertex_count();
 
@@ -95,7 +96,7 @@ impl Kruskal {
         G: provide::Edges<W, E>
             + provide::Neighbors
             + provide::Vertices
-            + provide::Graph<W, E, UndirectedEdge>,
+            + provide::Direction<UndirectedEdge>,
     {
         let mut mst = Vec::<(usize, usize, usize)>::new();
 
@@ -138,6 +139,24 @@ impl Kruskal {
     }
 }
 
+impl<W: Ord, E: Edge<W>, G> Algorithm<G, (W, E)> for Kruskal
+where
+    G: provide::Edges<W, E>
+        + provide::Neighbors
+        + provide::Vertices
+        + provide::Direction<UndirectedEdge>,
+{
+    type Output<'a>
+        = Subgraph<'a, W, E, UndirectedEdge, G>
+    where
+        G: 'a,
+        (W, E): 'a;
+
+    fn run<'a>(&self, graph: &'a G) -> Subgraph<'a, W, E, UndirectedEdge, G> {
+        Kruskal::init(graph).execute(graph)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
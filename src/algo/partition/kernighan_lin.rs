@@ -0,0 +1,303 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::graph::{Edge, UndirectedEdge};
+use crate::provide::{Edges, Graph, Vertices};
+
+/// Improves a bipartition of an undirected graph using Kernighan-Lin refinement.
+///
+/// Each pass computes, for each vertex, the weight of its edges to the other set minus the weight of its edges to its own set.
+/// Then repeatedly picks the pair of unlocked vertices from different sets whose swap reduces the cut the most, or increases it the least,
+/// and locks them. At the end of the pass, the prefix of swaps with the largest total gain is applied.
+/// Passes continue while they reduce the weight of the cut. Sizes of the sets never change.
+///
+/// The result can refine any partition, like the one returned by [`spectral_bisection`](crate::algo::spectral_bisection).
+///
+/// # Arguments
+/// * `graph`: Graph to partition.
+/// * `initial_partition`: Ids of vertices in the first set. All other vertices are in the second set.
+/// * `weight_fn`: Maps each edge to its weight. Weights of parallel edges are summed and loops are ignored.
+///
+/// # Returns
+/// (first set, second set, gains)
+/// * Ids of vertices in each set, sorted.
+/// * Reduction of the weight of the cut in each pass that improved it. So the weight of the cut is reduced by the sum of the gains.
+///
+/// # Complexity
+/// O(|V|<sup>3</sup>) for each pass, since each of the O(|V|) swaps searches all pairs of unlocked vertices.
+///
+/// # Panics
+/// If `initial_partition` contains an id that is not in the graph.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::List;
+/// use prepona::graph::ListGraph;
+/// use prepona::algo::kernighan_lin;
+///
+/// // Given: Graph
+/// //
+/// //      a  --  b
+/// //      |      |
+/// //      c  --  d
+/// //
+/// let mut graph = ListGraph::init(List::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(c, d, 1.into());
+/// graph.add_edge_unchecked(a, c, 5.into());
+/// graph.add_edge_unchecked(b, d, 5.into());
+///
+/// // When: Refining a partition that cuts the heavy edges.
+/// let (first, second, gains) = kernighan_lin(&graph, &[a, b], |edge| edge.get_weight().unwrap() as f64);
+///
+/// // Then: Only the light edges are cut.
+/// assert_eq!(first.len(), 2);
+/// assert_eq!(first.contains(&a), first.contains(&c));
+/// assert_eq!(second.len(), 2);
+/// assert_eq!(gains, vec![8.0]);
+/// ```
+pub fn kernighan_lin<W, E, G, F>(
+    graph: &G,
+    initial_partition: &[usize],
+    weight_fn: F,
+) -> (Vec<usize>, Vec<usize>, Vec<f64>)
+where
+    E: Edge<W>,
+    G: Vertices + Edges<W, E> + Graph<W, E, UndirectedEdge>,
+    F: Fn(&E) -> f64,
+{
+    let id_map = graph.continuos_id_map();
+    let vertex_count = id_map.len();
+
+    let mut in_first = vec![false; vertex_count];
+    for vertex_id in initial_partition.iter() {
+        in_first[id_map.virt_id_of(*vertex_id)] = true;
+    }
+
+    let mut weights: Vec<HashMap<usize, f64>> = vec![HashMap::new(); vertex_count];
+    for (virt_id, neighbor_weights) in weights.iter_mut().enumerate() {
+        for (dst_id, edge) in graph.edges_from_unchecked(id_map.real_id_of(virt_id)) {
+            let dst_virt_id = id_map.virt_id_of(dst_id);
+            if dst_virt_id != virt_id {
+                *neighbor_weights.entry(dst_virt_id).or_insert(0.0) += weight_fn(edge);
+            }
+        }
+    }
+
+    let mut gains = vec![];
+    loop {
+        let gain = pass(&weights, &mut in_first);
+        if gain <= 0.0 {
+            break;
+        }
+        gains.push(gain);
+    }
+
+    let mut first = vec![];
+    let mut second = vec![];
+    for (virt_id, in_first) in in_first.into_iter().enumerate() {
+        if in_first {
+            first.push(id_map.real_id_of(virt_id));
+        } else {
+            second.push(id_map.real_id_of(virt_id));
+        }
+    }
+    first.sort_unstable();
+    second.sort_unstable();
+
+    (first, second, gains)
+}
+
+// Runs a single pass and applies its best prefix of swaps if it has a positive gain.
+// Returns gain of the applied swaps.
+fn pass(weights: &[HashMap<usize, f64>], in_first: &mut [bool]) -> f64 {
+    let vertex_count = weights.len();
+
+    // External weight minus internal weight of each vertex.
+    let mut differences: Vec<f64> = (0..vertex_count)
+        .map(|virt_id| {
+            weights[virt_id]
+                .iter()
+                .map(|(n_virt_id, weight)| {
+                    if in_first[*n_virt_id] == in_first[virt_id] {
+                        -weight
+                    } else {
+                        *weight
+                    }
+                })
+                .sum()
+        })
+        .collect();
+
+    let mut locked = HashSet::new();
+    let mut swaps = vec![];
+    let mut total_gain = 0.0;
+    let mut best_gain = 0.0;
+    let mut best_swap_count = 0;
+
+    loop {
+        let mut best: Option<(usize, usize, f64)> = None;
+        for src_virt_id in
+            (0..vertex_count).filter(|virt_id| in_first[*virt_id] && !locked.contains(virt_id))
+        {
+            for dst_virt_id in
+                (0..vertex_count).filter(|virt_id| !in_first[*virt_id] && !locked.contains(virt_id))
+            {
+                let weight = weights[src_virt_id]
+                    .get(&dst_virt_id)
+                    .copied()
+                    .unwrap_or(0.0);
+                let gain = differences[src_virt_id] + differences[dst_virt_id] - 2.0 * weight;
+
+                if best.is_none_or(|(_, _, best_gain)| gain > best_gain) {
+                    best = Some((src_virt_id, dst_virt_id, gain));
+                }
+            }
+        }
+
+        let (src_virt_id, dst_virt_id, gain) = match best {
+            Some(best) => best,
+            None => break,
+        };
+
+        locked.insert(src_virt_id);
+        locked.insert(dst_virt_id);
+        swaps.push((src_virt_id, dst_virt_id));
+
+        total_gain += gain;
+        if total_gain > best_gain {
+            best_gain = total_gain;
+            best_swap_count = swaps.len();
+        }
+
+        // Moving a vertex to the other set turns its internal edges to external and vice versa,
+        // so differences of its unlocked neighbors change by twice the weight of the edge between them.
+        for (moved_virt_id, was_in_first) in [(src_virt_id, true), (dst_virt_id, false)] {
+            for (n_virt_id, weight) in weights[moved_virt_id].iter() {
+                if locked.contains(n_virt_id) {
+                    continue;
+                }
+
+                if in_first[*n_virt_id] == was_in_first {
+                    differences[*n_virt_id] += 2.0 * weight;
+                } else {
+                    differences[*n_virt_id] -= 2.0 * weight;
+                }
+            }
+        }
+    }
+
+    for (src_virt_id, dst_virt_id) in swaps.into_iter().take(best_swap_count) {
+        in_first[src_virt_id] = false;
+        in_first[dst_virt_id] = true;
+    }
+
+    best_gain
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::spectral_bisection;
+    use crate::graph::ListGraph;
+    use crate::misc::rng::Rng;
+    use crate::storage::List;
+
+    fn cut_weight(graph: &ListGraph<usize, UndirectedEdge>, first: &[usize]) -> f64 {
+        graph
+            .edges()
+            .into_iter()
+            .filter(|(src_id, dst_id, _)| first.contains(src_id) != first.contains(dst_id))
+            .map(|(_, _, edge)| edge.get_weight().unwrap() as f64)
+            .sum()
+    }
+
+    #[test]
+    fn empty_graph() {
+        // Given:
+        let graph = ListGraph::init(List::<usize>::init());
+
+        // When:
+        let (first, second, gains) = kernighan_lin(&graph, &[], |_| 1.0);
+
+        // Then:
+        assert!(first.is_empty());
+        assert!(second.is_empty());
+        assert!(gains.is_empty());
+    }
+
+    #[test]
+    fn separates_two_cliques() {
+        // Given: Two cliques of 4 vertices connected by an edge, and a partition that mixes them.
+        let mut graph = ListGraph::init(List::<usize>::init());
+        let vertices: Vec<usize> = (0..8).map(|_| graph.add_vertex()).collect();
+        for offset in [0, 4] {
+            for src in 0..4 {
+                for dst in src + 1..4 {
+                    graph.add_edge_unchecked(
+                        vertices[offset + src],
+                        vertices[offset + dst],
+                        1.into(),
+                    );
+                }
+            }
+        }
+        graph.add_edge_unchecked(vertices[0], vertices[4], 1.into());
+        let initial = [vertices[0], vertices[2], vertices[4], vertices[6]];
+
+        // When:
+        let (first, second, gains) = kernighan_lin(&graph, &initial, |_| 1.0);
+
+        // Then: Only the bridge is cut and the gains add up to the reduction of the cut.
+        assert_eq!(first.len(), 4);
+        assert_eq!(second.len(), 4);
+        assert_eq!(cut_weight(&graph, &first), 1.0);
+        assert_eq!(
+            cut_weight(&graph, &initial) - gains.iter().sum::<f64>(),
+            1.0
+        );
+        assert!(gains.iter().all(|gain| *gain > 0.0));
+    }
+
+    #[test]
+    fn never_worsens_the_partition() {
+        let mut rng = Rng::seed_from(3);
+        for _ in 0..20 {
+            // Given: Random weighted graph and an unbalanced random partition.
+            let vertex_count = 6 + rng.next_below(10);
+            let mut graph = ListGraph::init(List::<usize>::init());
+            for _ in 0..vertex_count {
+                graph.add_vertex();
+            }
+            for _ in 0..3 * vertex_count {
+                let src_id = rng.next_below(vertex_count);
+                let dst_id = rng.next_below(vertex_count);
+                if src_id != dst_id && !graph.has_any_edge_unchecked(src_id, dst_id) {
+                    graph.add_edge_unchecked(src_id, dst_id, (1 + rng.next_below(5)).into());
+                }
+            }
+            let initial: Vec<usize> = (0..vertex_count).filter(|_| rng.next_bool(0.3)).collect();
+
+            // When:
+            let (first, second, gains) =
+                kernighan_lin(&graph, &initial, |edge| edge.get_weight().unwrap() as f64);
+
+            // Then:
+            assert_eq!(first.len(), initial.len());
+            assert_eq!(first.len() + second.len(), vertex_count);
+            let expected = cut_weight(&graph, &initial) - gains.iter().sum::<f64>();
+            assert!((cut_weight(&graph, &first) - expected).abs() < 1e-9);
+            assert!(gains.iter().all(|gain| *gain > 0.0));
+
+            // Then: Refining a spectral bisection never makes it worse.
+            let (spectral, _) = spectral_bisection(&graph);
+            let (refined, _, _) =
+                kernighan_lin(&graph, &spectral, |edge| edge.get_weight().unwrap() as f64);
+            assert!(cut_weight(&graph, &refined) <= cut_weight(&graph, &spectral));
+        }
+    }
+}
@@ -0,0 +1,5 @@
+mod kernighan_lin;
+mod spectral;
+
+pub use kernighan_lin::kernighan_lin;
+pub use spectral::{fiedler_vector, spectral_bisection};
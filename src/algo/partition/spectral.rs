@@ -0,0 +1,325 @@
+use std::collections::HashMap;
+
+use crate::algo::linalg;
+use crate::graph::{Edge, UndirectedEdge};
+use crate::provide::{Edges, Graph, IdMap, Vertices};
+
+// Upper bound on the number of power iterations used by `spectral_bisection`.
+const BISECTION_ITERATIONS: usize = 1000;
+
+// Power iteration stops when the vector moves less than this between two iterations.
+const TOLERANCE: f64 = 1e-10;
+
+/// Approximates the Fiedler vector of an undirected graph: The eigenvector of the second smallest eigenvalue of its laplacian `L = D - A`.
+///
+/// The smallest eigenvalue of the laplacian is zero with the all ones vector as its eigenvector.
+/// So power iteration is applied to `c * I - L`, where `c` is twice the maximum weighted degree and therefore at least the largest eigenvalue of `L`,
+/// while removing the component of the vector along the all ones vector in each iteration.
+///
+/// Entries of vertices that are close to each other in the graph are close to each other in the Fiedler vector,
+/// which makes it useful for partitioning and ordering the vertices. If the graph is disconnected,
+/// the result is constant over each connected component.
+///
+/// # Arguments
+/// * `graph`: Graph to compute its Fiedler vector.
+/// * `weight_fn`: Maps each edge to its non negative weight in the laplacian.
+/// * `iterations`: Maximum number of power iterations. Iteration stops earlier if the vector converges.
+///
+/// # Returns
+/// Entry of each vertex in the Fiedler vector, which is normalized to have unit length.
+///
+/// # Complexity
+/// O(`iterations` * (|V| + |E|)). Number of iterations needed for convergence grows as the gap between the second and third smallest eigenvalues shrinks.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::List;
+/// use prepona::graph::ListGraph;
+/// use prepona::algo::fiedler_vector;
+///
+/// // Given: Graph
+/// //
+/// //      a  --  b  --  c
+/// //
+/// let mut graph = ListGraph::init(List::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+///
+/// // When:
+/// let fiedler = fiedler_vector(&graph, |_| 1.0, 100);
+///
+/// // Then: Vector is proportional to (1, 0, -1) or (-1, 0, 1).
+/// assert!(fiedler[&b].abs() < 1e-6);
+/// assert!((fiedler[&a] + fiedler[&c]).abs() < 1e-6);
+/// assert!((fiedler[&a].abs() - 0.5_f64.sqrt()).abs() < 1e-6);
+/// ```
+pub fn fiedler_vector<W, E, G, F>(graph: &G, weight_fn: F, iterations: usize) -> HashMap<usize, f64>
+where
+    E: Edge<W>,
+    G: Vertices + Edges<W, E> + Graph<W, E, UndirectedEdge>,
+    F: Fn(&E) -> f64,
+{
+    let id_map = graph.continuos_id_map();
+    let vector = fiedler(graph, &id_map, weight_fn, iterations);
+
+    vector
+        .into_iter()
+        .enumerate()
+        .map(|(virt_id, value)| (id_map.real_id_of(virt_id), value))
+        .collect()
+}
+
+/// Partitions vertices of an undirected graph into two sets of equal size, that have few edges between them.
+///
+/// Vertices are sorted by their entry in the [`Fiedler vector`](crate::algo::fiedler_vector), with every edge having weight of one,
+/// and the sorted order is split in half. For a weighted partition, sort the vertices by the result of [`fiedler_vector`](crate::algo::fiedler_vector) directly.
+///
+/// # Arguments
+/// `graph`: Graph to partition.
+///
+/// # Returns
+/// (first set, second set): Ids of vertices of each set, sorted. First set has ⌊|V| / 2⌋ vertices and the second set has the rest.
+///
+/// # Complexity
+/// O(k * (|V| + |E|) + |V| * log(|V|)), where k is the number of power iterations and is at most 1000.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::List;
+/// use prepona::graph::ListGraph;
+/// use prepona::algo::spectral_bisection;
+///
+/// // Given: Two triangles connected by an edge.
+/// //
+/// //      a           d
+/// //      | \       / |
+/// //      |  c --- e  |
+/// //      | /       \ |
+/// //      b           f
+/// //
+/// let mut graph = ListGraph::init(List::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// let e = graph.add_vertex();
+/// let f = graph.add_vertex();
+/// for (src_id, dst_id) in [(a, b), (b, c), (c, a), (d, e), (e, f), (f, d), (c, e)].iter() {
+///     graph.add_edge_unchecked(*src_id, *dst_id, 1.into());
+/// }
+///
+/// // When:
+/// let (first, second) = spectral_bisection(&graph);
+///
+/// // Then: Only c - e is cut.
+/// let mut sides = vec![first, second];
+/// sides.sort();
+/// assert_eq!(sides, vec![vec![a, b, c], vec![d, e, f]]);
+/// ```
+pub fn spectral_bisection<W, E, G>(graph: &G) -> (Vec<usize>, Vec<usize>)
+where
+    E: Edge<W>,
+    G: Vertices + Edges<W, E> + Graph<W, E, UndirectedEdge>,
+{
+    let id_map = graph.continuos_id_map();
+    let vector = fiedler(graph, &id_map, |_| 1.0, BISECTION_ITERATIONS);
+
+    let mut order: Vec<usize> = (0..vector.len()).collect();
+    order.sort_by(|virt_id1, virt_id2| {
+        vector[*virt_id1]
+            .total_cmp(&vector[*virt_id2])
+            .then(virt_id1.cmp(virt_id2))
+    });
+
+    let to_sorted_real_ids = |virt_ids: &[usize]| {
+        let mut real_ids: Vec<usize> = virt_ids
+            .iter()
+            .map(|virt_id| id_map.real_id_of(*virt_id))
+            .collect();
+        real_ids.sort_unstable();
+        real_ids
+    };
+
+    let (first, second) = order.split_at(order.len() / 2);
+
+    (to_sorted_real_ids(first), to_sorted_real_ids(second))
+}
+
+fn fiedler<W, E, G, F>(graph: &G, id_map: &IdMap, weight_fn: F, iterations: usize) -> Vec<f64>
+where
+    E: Edge<W>,
+    G: Vertices + Edges<W, E>,
+    F: Fn(&E) -> f64,
+{
+    let vertex_count = id_map.len();
+
+    let degrees = linalg::spmv(graph, id_map, &vec![1.0; vertex_count], &weight_fn);
+    let shift = 2.0 * degrees.iter().copied().fold(0.0, f64::max);
+
+    // Start from a vector that is not likely to be orthogonal to the Fiedler vector.
+    let mut vector: Vec<f64> = (0..vertex_count)
+        .map(|virt_id| if virt_id % 2 == 0 { 1.0 } else { -1.0 } * (1.0 + virt_id as f64))
+        .collect();
+    if !deflate_and_normalize(&mut vector) {
+        return vec![0.0; vertex_count];
+    }
+
+    for _ in 0..iterations {
+        // (c * I - L) * x = c * x - D * x + A * x
+        let mut next = linalg::spmv(graph, id_map, &vector, &weight_fn);
+        for virt_id in 0..vertex_count {
+            next[virt_id] += (shift - degrees[virt_id]) * vector[virt_id];
+        }
+
+        if !deflate_and_normalize(&mut next) {
+            break;
+        }
+
+        let change = next
+            .iter()
+            .zip(vector.iter())
+            .map(|(next_value, value)| (next_value - value).powi(2))
+            .sum::<f64>()
+            .sqrt();
+
+        vector = next;
+
+        if change < TOLERANCE {
+            break;
+        }
+    }
+
+    vector
+}
+
+// Removes the component of the vector along the all ones vector and normalizes it.
+// Returns false if nothing remains.
+fn deflate_and_normalize(vector: &mut [f64]) -> bool {
+    let mean = vector.iter().sum::<f64>() / vector.len() as f64;
+    vector.iter_mut().for_each(|value| *value -= mean);
+
+    let norm = vector.iter().map(|value| value * value).sum::<f64>().sqrt();
+    if norm <= f64::EPSILON {
+        return false;
+    }
+    vector.iter_mut().for_each(|value| *value /= norm);
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::ListGraph;
+    use crate::provide::Neighbors;
+    use crate::storage::List;
+
+    fn path(vertex_count: usize) -> ListGraph<usize, UndirectedEdge> {
+        let mut graph = ListGraph::init(List::init());
+        for _ in 0..vertex_count {
+            graph.add_vertex();
+        }
+        for vertex_id in 1..vertex_count {
+            graph.add_edge_unchecked(vertex_id - 1, vertex_id, 1.into());
+        }
+
+        graph
+    }
+
+    #[test]
+    fn empty_graph() {
+        // Given:
+        let graph = path(0);
+
+        // When:
+        let fiedler = fiedler_vector(&graph, |_| 1.0, 10);
+        let (first, second) = spectral_bisection(&graph);
+
+        // Then:
+        assert!(fiedler.is_empty());
+        assert!(first.is_empty());
+        assert!(second.is_empty());
+    }
+
+    #[test]
+    fn fiedler_vector_of_path() {
+        // Given: Path with 10 vertices.
+        let vertex_count = 10;
+        let graph = path(vertex_count);
+
+        // When:
+        let fiedler = fiedler_vector(&graph, |_| 1.0, 1000);
+
+        // Then: It is an eigenvector of the laplacian with eigenvalue 2 - 2cos(π / n), orthogonal to the all ones vector.
+        let eigenvalue = 2.0 - 2.0 * (std::f64::consts::PI / vertex_count as f64).cos();
+        assert!(fiedler.values().sum::<f64>().abs() < 1e-9);
+        for vertex_id in 0..vertex_count {
+            let neighbors: Vec<usize> = graph.neighbors_unchecked(vertex_id);
+            let laplacian_value = neighbors.len() as f64 * fiedler[&vertex_id]
+                - neighbors.iter().map(|n_id| fiedler[n_id]).sum::<f64>();
+            assert!((laplacian_value - eigenvalue * fiedler[&vertex_id]).abs() < 1e-6);
+        }
+
+        // Then: Entries are monotone along the path.
+        let increasing = fiedler[&0] < fiedler[&1];
+        for vertex_id in 1..vertex_count {
+            assert_eq!(fiedler[&(vertex_id - 1)] < fiedler[&vertex_id], increasing);
+        }
+    }
+
+    #[test]
+    fn bisection_of_grid() {
+        // Given: 4 by 8 grid, with a removed vertex in front to make ids non continuos.
+        let (rows, cols) = (4, 8);
+        let mut graph = ListGraph::init(List::<usize>::init());
+        for _ in 0..=rows * cols {
+            graph.add_vertex();
+        }
+        graph.remove_vertex_unchecked(0);
+        for row in 0..rows {
+            for col in 0..cols {
+                let vertex_id = 1 + row * cols + col;
+                if col + 1 < cols {
+                    graph.add_edge_unchecked(vertex_id, vertex_id + 1, 1.into());
+                }
+                if row + 1 < rows {
+                    graph.add_edge_unchecked(vertex_id, vertex_id + cols, 1.into());
+                }
+            }
+        }
+
+        // When:
+        let (first, second) = spectral_bisection(&graph);
+
+        // Then: Grid is cut across its long side, cutting only 4 edges.
+        assert_eq!(first.len(), 16);
+        assert_eq!(second.len(), 16);
+        let cut = graph
+            .edges()
+            .into_iter()
+            .filter(|(src_id, dst_id, _)| first.contains(src_id) != first.contains(dst_id))
+            .count();
+        assert_eq!(cut, rows);
+    }
+
+    #[test]
+    fn bisection_of_disconnected_graph() {
+        // Given: Two disjoint paths and an isolated vertex.
+        let mut graph = path(9);
+        graph.remove_edge_unchecked(3, 4, graph.edges_between_unchecked(3, 4)[0].get_id());
+        graph.remove_edge_unchecked(7, 8, graph.edges_between_unchecked(7, 8)[0].get_id());
+
+        // When:
+        let (first, second) = spectral_bisection(&graph);
+
+        // Then: Sizes are balanced and the partition is deterministic.
+        assert_eq!(first.len(), 4);
+        assert_eq!(second.len(), 5);
+        assert_eq!(spectral_bisection(&graph), (first, second));
+    }
+}
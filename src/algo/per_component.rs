@@ -0,0 +1,378 @@
+use std::collections::HashSet;
+
+use crate::algo::{Algorithm, ConnectedComponents, Orientation};
+use crate::graph::{Edge, EdgeDir};
+use crate::provide::{Edges, Graph, IdMap, Neighbors, Vertices};
+
+/// Runs an algorithm on each connected component of a graph separately.
+///
+/// Algorithms in this crate treat a disconnected graph as a whole:
+/// shortest path algorithms report unreachable vertices as infinitely far, traversals continue from a new root when a component is exhausted
+/// and [`color_with_k`](crate::algo::color_with_k) only compares neighbors, so vertices of different components never compete for colors.
+/// When results are only meaningful within a component(like the diameter or the center of a graph), use this adapter to compute them per component and merge the results as needed.
+///
+/// Each component is copied into a new graph created by `new_graph`, so any algorithm that accepts a graph can be used.
+/// Components of a directed graph are its weakly connected components, and edges keep their direction in the copies.
+/// To run an [`Algorithm`](crate::algo::Algorithm) instead of a closure, use [`run_per_component`](crate::algo::run_per_component).
+///
+/// # Arguments
+/// * `graph`: Graph to run the algorithm on its components.
+/// * `new_graph`: Creates an empty graph to copy a component into.
+/// * `algo`: Algorithm to run on each component.
+///   It receives the graph of the component and a map whose virtual ids are ids of vertices in the component graph and whose real ids are ids of vertices in `graph`.
+///
+/// # Returns
+/// Result of running `algo` on each component.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+/// use prepona::algo::per_component;
+///
+/// // Given: Graph
+/// //
+/// //      a  ---  b       c  ---  d  ---  e
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// let e = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(c, d, 1.into());
+/// graph.add_edge_unchecked(d, e, 1.into());
+///
+/// // When: Counting edges of each component.
+/// let mut edge_counts = per_component(
+///     &graph,
+///     || MatGraph::init(Mat::<usize>::init()),
+///     |component, _| component.edges_count(),
+/// );
+///
+/// // Then:
+/// edge_counts.sort();
+/// assert_eq!(edge_counts, vec![1, 2]);
+/// ```
+pub fn per_component<W, E, Dir, G, H, F, R>(
+    graph: &G,
+    new_graph: impl Fn() -> H,
+    mut algo: F,
+) -> Vec<R>
+where
+    E: Edge<W> + Clone,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Vertices + Neighbors + Edges<W, E>,
+    H: Graph<W, E, Dir>,
+    F: FnMut(&H, &IdMap) -> R,
+{
+    components_of(graph, new_graph)
+        .map(|(component, id_map)| algo(&component, &id_map))
+        .collect()
+}
+
+/// Runs an [`Algorithm`](crate::algo::Algorithm) on each connected component of a graph separately.
+///
+/// For more info checkout [`per_component`](crate::algo::per_component).
+///
+/// # Arguments
+/// * `graph`: Graph to run the algorithm on its components.
+/// * `new_graph`: Creates an empty graph to copy a component into.
+/// * `algorithm`: Algorithm to run on each component. Its output must not borrow from the graph of the component, which is dropped after running it.
+///
+/// # Returns
+/// Output of running `algorithm` on each component, along with a map whose virtual ids are ids of vertices in the component graph and whose real ids are ids of vertices in `graph`.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::DiList;
+/// use prepona::graph::ListGraph;
+/// use prepona::algo::{run_per_component, TopologicalSort};
+///
+/// // Given: Graph
+/// //
+/// //      a  -->  b  <--  c       d  -->  e
+/// //
+/// let mut graph = ListGraph::init(DiList::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// let e = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(c, b, 1.into());
+/// graph.add_edge_unchecked(d, e, 1.into());
+///
+/// // When: Sorting each weakly connected component topologically.
+/// let sorts = run_per_component(
+///     &graph,
+///     || ListGraph::init(DiList::<usize>::init()),
+///     &TopologicalSort::init(),
+/// );
+///
+/// // Then:
+/// let mut sizes: Vec<usize> = sorts.iter().map(|(sorted_ids, _)| sorted_ids.len()).collect();
+/// sizes.sort();
+/// assert_eq!(sizes, vec![2, 3]);
+/// ```
+pub fn run_per_component<W, E, Dir, G, H, M, A, R>(
+    graph: &G,
+    new_graph: impl Fn() -> H,
+    algorithm: &A,
+) -> Vec<(R, IdMap)>
+where
+    E: Edge<W> + Clone,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Vertices + Neighbors + Edges<W, E>,
+    H: Graph<W, E, Dir> + 'static,
+    M: 'static,
+    A: for<'a> Algorithm<H, M, Output<'a> = R>,
+{
+    components_of(graph, new_graph)
+        .map(|(component, id_map)| (algorithm.run(&component), id_map))
+        .collect()
+}
+
+// Copies each connected component of `graph` into a graph created by `new_graph`, one component at a time.
+// Components of a directed graph are found by ignoring the direction of its edges, which makes no difference for undirected graphs.
+//
+// # Returns
+// Graph of each component, along with a map whose virtual ids are ids of vertices in the component graph and whose real ids are ids of vertices in `graph`.
+fn components_of<'g, W, E, Dir, G, H>(
+    graph: &'g G,
+    new_graph: impl Fn() -> H + 'g,
+) -> impl Iterator<Item = (H, IdMap)> + 'g
+where
+    E: Edge<W> + Clone,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Vertices + Neighbors + Edges<W, E>,
+    H: Graph<W, E, Dir>,
+{
+    let ccs = ConnectedComponents::init_with_orientation(graph, Orientation::Ignore).execute(graph);
+
+    ccs.into_iter().map(move |cc| {
+        let mut component = new_graph();
+        let mut id_map = IdMap::init(cc.len());
+
+        for real_id in &cc {
+            let virt_id = component.add_vertex();
+
+            id_map.put_real_to_virt(*real_id, virt_id);
+            id_map.put_virt_to_real(virt_id, *real_id);
+        }
+
+        // Each undirected edge is listed from both of its end points, and some storages list a self loop twice from its only end point.
+        // So edges are copied once by their id.
+        let mut edge_ids = HashSet::new();
+        for real_id in &cc {
+            for (n_id, edge) in graph.edges_from_unchecked(*real_id) {
+                if edge_ids.insert(edge.get_id()) {
+                    component.add_edge_unchecked(
+                        id_map.virt_id_of(*real_id),
+                        id_map.virt_id_of(n_id),
+                        edge.clone(),
+                    );
+                }
+            }
+        }
+
+        (component, id_map)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::{color_with_k, is_dag, Dijkstra, KColoring};
+    use crate::graph::{DirectedEdge, ListGraph, MatGraph};
+    use crate::storage::{DiList, List, Mat};
+
+    #[test]
+    fn empty_graph() {
+        // Given: Empty graph.
+        let graph = MatGraph::init(Mat::<usize>::init());
+
+        // When: Running an algorithm per component.
+        let results = per_component(
+            &graph,
+            || MatGraph::init(Mat::<usize>::init()),
+            |component, _| component.vertex_count(),
+        );
+
+        // Then:
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn shortest_paths_per_component() {
+        // Given: Graph
+        //
+        //      a  --2--  b       c  --1--  d  --3--  e       f
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        let f = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 2.into());
+        graph.add_edge_unchecked(c, d, 1.into());
+        graph.add_edge_unchecked(d, e, 3.into());
+
+        // When: Computing the eccentricity of the smallest vertex of each component.
+        let mut eccentricities = per_component(
+            &graph,
+            || MatGraph::init(Mat::<usize>::init()),
+            |component, id_map| {
+                let root = (0..component.vertex_count())
+                    .min_by_key(|virt_id| id_map.real_id_of(*virt_id))
+                    .unwrap();
+
+                let sp = Dijkstra::init(component).execute(component, root);
+
+                let eccentricity = component
+                    .vertices()
+                    .into_iter()
+                    .map(|virt_id| sp.distance_to(virt_id).unwrap())
+                    .max()
+                    .unwrap();
+
+                (id_map.real_id_of(root), eccentricity)
+            },
+        );
+
+        // Then: No vertex is infinitely far.
+        eccentricities.sort_by_key(|(real_id, _)| *real_id);
+        assert_eq!(
+            eccentricities,
+            vec![(a, 2.into()), (c, 4.into()), (f, 0.into())]
+        );
+    }
+
+    #[test]
+    fn self_loops_are_copied_once() {
+        // Given: Graph
+        //
+        //      a  ---  b ---.       c
+        //              ^    |
+        //              '----'
+        //
+        let mut graph = ListGraph::init(List::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, b, 2.into());
+
+        // When: Counting edges of each component.
+        let mut edge_counts = per_component(
+            &graph,
+            || ListGraph::init(List::<usize>::init()),
+            |component, _| component.edges_count(),
+        );
+
+        // Then:
+        edge_counts.sort_unstable();
+        assert_eq!(edge_counts, vec![0, 2]);
+    }
+
+    #[test]
+    fn coloring_per_component() {
+        // Given: Graph
+        //
+        //      a  ---  b       d  ---  e       g
+        //       \     /        |       |
+        //         c            f  ---  h
+        //
+        let mut graph = ListGraph::init(List::<usize>::init());
+        let ids: Vec<usize> = (0..8).map(|_| graph.add_vertex()).collect();
+        let (a, b, c, d, e, f, g, h) = (
+            ids[0], ids[1], ids[2], ids[3], ids[4], ids[5], ids[6], ids[7],
+        );
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(c, a, 1.into());
+        graph.add_edge_unchecked(d, e, 1.into());
+        graph.add_edge_unchecked(e, h, 1.into());
+        graph.add_edge_unchecked(h, f, 1.into());
+        graph.add_edge_unchecked(f, d, 1.into());
+
+        // When: Coloring the whole graph and each component with two colors.
+        let global = color_with_k(&graph, 2, |_| 1.0);
+        let mut spilled: Vec<usize> = per_component(
+            &graph,
+            || ListGraph::init(List::<usize>::init()),
+            |component, id_map| match color_with_k(component, 2, |_| 1.0) {
+                KColoring::Colored(_) => vec![],
+                KColoring::Spilled(virt_ids) => virt_ids
+                    .into_iter()
+                    .map(|virt_id| id_map.real_id_of(virt_id))
+                    .collect(),
+            },
+        )
+        .into_iter()
+        .flatten()
+        .collect();
+
+        // Then: Only the triangle needs a spill, in both cases.
+        spilled.sort_unstable();
+        assert_eq!(global, KColoring::Spilled(spilled.clone()));
+        assert_eq!(spilled.len(), 1);
+        assert!([a, b, c].contains(&spilled[0]));
+        assert!(![d, e, f, g, h].contains(&spilled[0]));
+    }
+
+    #[test]
+    fn weakly_connected_components_of_directed_graph() {
+        // Given: Graph
+        //
+        //      a  -->  b  <--  c       d  <--> e       f
+        //
+        let mut graph = ListGraph::init(DiList::<usize>::init());
+        let ids: Vec<usize> = (0..6).map(|_| graph.add_vertex()).collect();
+        let (a, b, c, d, e, f) = (ids[0], ids[1], ids[2], ids[3], ids[4], ids[5]);
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(c, b, 1.into());
+        graph.add_edge_unchecked(d, e, 1.into());
+        graph.add_edge_unchecked(e, d, 1.into());
+
+        // When: Copying each component, and checking whether each one is acyclic.
+        let mut directions = per_component(
+            &graph,
+            || ListGraph::init(DiList::<usize>::init()),
+            |component, id_map| {
+                (
+                    component.edges_count(),
+                    id_map.try_virt_id_of(a).is_some()
+                        && component
+                            .has_any_edge_unchecked(id_map.virt_id_of(a), id_map.virt_id_of(b))
+                        && !component
+                            .has_any_edge_unchecked(id_map.virt_id_of(b), id_map.virt_id_of(a)),
+                )
+            },
+        );
+        let mut acyclic = run_per_component(
+            &graph,
+            || ListGraph::init(DiList::<usize>::init()),
+            &|component: &ListGraph<usize, DirectedEdge>| is_dag(component),
+        )
+        .into_iter()
+        .map(|(is_dag, id_map)| {
+            let min_id = (0..id_map.len())
+                .map(|virt_id| id_map.real_id_of(virt_id))
+                .min();
+            (min_id.unwrap(), is_dag)
+        })
+        .collect::<Vec<(usize, bool)>>();
+
+        // Then: Edges keep their direction in the copies.
+        directions.sort_unstable();
+        acyclic.sort_unstable();
+        assert_eq!(directions, vec![(0, false), (2, false), (2, true)]);
+        assert_eq!(acyclic, vec![(a, true), (d, false), (f, true)]);
+    }
+}
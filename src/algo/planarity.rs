@@ -0,0 +1,1249 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::graph::{Edge, MultiListGraph, UndirectedEdge};
+use crate::provide::{Edges, Graph, Vertices};
+use crate::storage::List;
+
+// Edge of an embedding as seen from its source, in the format of (`src_id`, `dst_id`).
+type HalfEdge = (usize, usize);
+
+/// A combinatorial embedding of a planar graph, also known as a rotation system.
+///
+/// For each vertex it stores the clockwise order of its neighbors in a drawing of the graph without crossing edges.
+/// Loops and parallel edges do not affect planarity, so each pair of adjacent vertices appears once.
+#[derive(Debug, Clone)]
+pub struct Embedding {
+    rotations: HashMap<usize, Vec<usize>>,
+}
+
+impl Embedding {
+    /// # Returns
+    /// Id of the vertices of the embedding, sorted.
+    pub fn vertices(&self) -> Vec<usize> {
+        let mut vertices: Vec<usize> = self.rotations.keys().copied().collect();
+        vertices.sort_unstable();
+
+        vertices
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// Neighbors of the vertex in clockwise order. The order is cyclic, so it may start from any neighbor.
+    ///
+    /// # Panics
+    /// If there is no vertex with id: `vertex_id`.
+    pub fn neighbors_cw(&self, vertex_id: usize) -> &[usize] {
+        &self.rotations[&vertex_id]
+    }
+
+    /// # Arguments
+    /// * `vertex_id`: Id of the vertex.
+    /// * `neighbor_id`: Id of a neighbor of the vertex.
+    ///
+    /// # Returns
+    /// The neighbor that comes right after `neighbor_id` in clockwise order around the vertex.
+    ///
+    /// # Panics
+    /// If `neighbor_id` is not a neighbor of `vertex_id`.
+    pub fn next_cw(&self, vertex_id: usize, neighbor_id: usize) -> usize {
+        let rotation = self.neighbors_cw(vertex_id);
+        let index = Embedding::position(rotation, vertex_id, neighbor_id);
+
+        rotation[(index + 1) % rotation.len()]
+    }
+
+    /// # Arguments
+    /// * `vertex_id`: Id of the vertex.
+    /// * `neighbor_id`: Id of a neighbor of the vertex.
+    ///
+    /// # Returns
+    /// The neighbor that comes right after `neighbor_id` in counter clockwise order around the vertex.
+    ///
+    /// # Panics
+    /// If `neighbor_id` is not a neighbor of `vertex_id`.
+    pub fn next_ccw(&self, vertex_id: usize, neighbor_id: usize) -> usize {
+        let rotation = self.neighbors_cw(vertex_id);
+        let index = Embedding::position(rotation, vertex_id, neighbor_id);
+
+        rotation[(index + rotation.len() - 1) % rotation.len()]
+    }
+
+    /// Walks the face that lies to the right of the half edge going from `src_id` to `dst_id`:
+    /// After arriving at a vertex, leaves it through the neighbor that comes right after the previous vertex in clockwise order.
+    ///
+    /// # Arguments
+    /// * `src_id`: Id of the vertex the half edge starts from.
+    /// * `dst_id`: Id of the vertex the half edge ends at.
+    ///
+    /// # Returns
+    /// Id of the vertices on the boundary of the face in the order they are visited, starting from `src_id`.
+    /// A vertex appears more than once if the boundary passes through it more than once, like the end points of a bridge.
+    ///
+    /// # Panics
+    /// If `dst_id` is not a neighbor of `src_id`.
+    pub fn traverse_face(&self, src_id: usize, dst_id: usize) -> Vec<usize> {
+        self.half_edges_of_face(src_id, dst_id)
+            .into_iter()
+            .map(|(src_id, _)| src_id)
+            .collect()
+    }
+
+    /// # Returns
+    /// Boundary of each face of the embedding, as returned by [`traverse_face`](crate::algo::Embedding::traverse_face).
+    /// Faces are bounded by at least one edge, so isolated vertices do not contribute any face.
+    /// Each connected component has its own outer face, so an embedding with |V| vertices, |E| edges and C components that have edges, has |E| - |V| + 2C faces.
+    ///
+    /// # Complexity
+    /// O(|V| + |E|) half edges are visited, each looked up in the rotation of its end point.
+    pub fn faces(&self) -> Vec<Vec<usize>> {
+        self.half_edge_faces()
+            .0
+            .into_iter()
+            .map(|half_edges| half_edges.into_iter().map(|(src_id, _)| src_id).collect())
+            .collect()
+    }
+
+    fn half_edges_of_face(&self, src_id: usize, dst_id: usize) -> Vec<HalfEdge> {
+        let mut half_edges = vec![];
+        let (mut current_src_id, mut current_dst_id) = (src_id, dst_id);
+        loop {
+            half_edges.push((current_src_id, current_dst_id));
+
+            let next_id = self.next_cw(current_dst_id, current_src_id);
+            current_src_id = current_dst_id;
+            current_dst_id = next_id;
+
+            if (current_src_id, current_dst_id) == (src_id, dst_id) {
+                break half_edges;
+            }
+        }
+    }
+
+    // Returns half edges of each face and the index of the face each half edge belongs to.
+    fn half_edge_faces(&self) -> (Vec<Vec<HalfEdge>>, HashMap<HalfEdge, usize>) {
+        let mut faces = vec![];
+        let mut face_of = HashMap::new();
+        for src_id in self.vertices() {
+            for dst_id in self.rotations[&src_id].iter().copied() {
+                if face_of.contains_key(&(src_id, dst_id)) {
+                    continue;
+                }
+
+                let half_edges = self.half_edges_of_face(src_id, dst_id);
+                for half_edge in half_edges.iter() {
+                    face_of.insert(*half_edge, faces.len());
+                }
+                faces.push(half_edges);
+            }
+        }
+
+        (faces, face_of)
+    }
+
+    fn position(rotation: &[usize], vertex_id: usize, neighbor_id: usize) -> usize {
+        rotation
+            .iter()
+            .position(|id| *id == neighbor_id)
+            .unwrap_or_else(|| {
+                panic!(
+                    "Vertex with id: {} is not a neighbor of vertex with id: {}",
+                    neighbor_id, vertex_id
+                )
+            })
+    }
+}
+
+/// Dual of a planar embedding: A graph with one vertex per face, and one edge crossing each edge of the embedding.
+///
+/// Faces on the two sides of a bridge are the same, so its dual edge is a loop. Faces that share more than one edge are connected by parallel edges.
+pub struct DualGraph {
+    graph: MultiListGraph<usize, UndirectedEdge>,
+    faces: Vec<Vec<usize>>,
+    primal_edges: HashMap<usize, (usize, usize)>,
+}
+
+impl DualGraph {
+    /// # Returns
+    /// The dual graph. Weight of every edge is one.
+    pub fn graph(&self) -> &MultiListGraph<usize, UndirectedEdge> {
+        &self.graph
+    }
+
+    /// # Returns
+    /// The dual graph. Weight of every edge is one.
+    pub fn into_graph(self) -> MultiListGraph<usize, UndirectedEdge> {
+        self.graph
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of a vertex in the dual graph.
+    ///
+    /// # Returns
+    /// Boundary of the face represented by the vertex, as returned by [`traverse_face`](crate::algo::Embedding::traverse_face).
+    ///
+    /// # Panics
+    /// If there is no vertex with id: `vertex_id` in the dual graph.
+    pub fn face(&self, vertex_id: usize) -> &[usize] {
+        &self.faces[vertex_id]
+    }
+
+    /// # Arguments
+    /// `edge_id`: Id of an edge in the dual graph.
+    ///
+    /// # Returns
+    /// End points of the edge of the embedding that is crossed by the dual edge, smaller id first.
+    ///
+    /// # Panics
+    /// If there is no edge with id: `edge_id` in the dual graph.
+    pub fn primal_edge(&self, edge_id: usize) -> (usize, usize) {
+        self.primal_edges[&edge_id]
+    }
+}
+
+/// Builds the dual of a planar embedding.
+///
+/// # Arguments
+/// `embedding`: Embedding returned by [`planarity`](crate::algo::planarity).
+///
+/// # Returns
+/// Dual graph, in which vertex `i` represents the `i`-th face returned by [`faces`](crate::algo::Embedding::faces).
+///
+/// # Complexity
+/// O(|V| + |E|)
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::List;
+/// use prepona::graph::ListGraph;
+/// use prepona::algo::{dual_graph, planarity, Planarity};
+///
+/// // Given: Triangle
+/// //
+/// //      a  --  b
+/// //       \    /
+/// //         c
+/// //
+/// let mut graph = ListGraph::init(List::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(c, a, 1.into());
+///
+/// // When:
+/// let embedding = match planarity(&graph) {
+///     Planarity::Planar(embedding) => embedding,
+///     Planarity::NonPlanar(_) => unreachable!(),
+/// };
+/// let dual = dual_graph(&embedding);
+///
+/// // Then: Inner and outer face, separated by three edges.
+/// assert_eq!(embedding.faces().len(), 2);
+/// assert_eq!(dual.graph().vertex_count(), 2);
+/// assert_eq!(dual.graph().edges_between_unchecked(0, 1).len(), 3);
+/// assert_eq!(dual.face(0).len(), 3);
+/// ```
+pub fn dual_graph(embedding: &Embedding) -> DualGraph {
+    let (half_edge_faces, face_of) = embedding.half_edge_faces();
+
+    let mut graph = MultiListGraph::init(List::init());
+    for _ in half_edge_faces.iter() {
+        graph.add_vertex();
+    }
+
+    let mut primal_edges = HashMap::new();
+    for (src_id, dst_id) in embedding.vertices().into_iter().flat_map(|src_id| {
+        embedding.rotations[&src_id]
+            .iter()
+            .map(move |dst_id| (src_id, *dst_id))
+    }) {
+        // Each edge is crossed once, from the face to the right of its half edge with the smaller source.
+        if src_id < dst_id {
+            let edge_id = graph.add_edge_unchecked(
+                face_of[&(src_id, dst_id)],
+                face_of[&(dst_id, src_id)],
+                1.into(),
+            );
+            primal_edges.insert(edge_id, (src_id, dst_id));
+        }
+    }
+
+    let faces = half_edge_faces
+        .into_iter()
+        .map(|half_edges| half_edges.into_iter().map(|(src_id, _)| src_id).collect())
+        .collect();
+
+    DualGraph {
+        graph,
+        faces,
+        primal_edges,
+    }
+}
+
+/// Result of [`planarity`](crate::algo::planarity) test.
+#[derive(Debug, Clone)]
+pub enum Planarity {
+    /// Graph is planar and this is an embedding of it.
+    Planar(Embedding),
+
+    /// Graph is not planar. Contains a Kuratowski subgraph as a witness: A subdivision of K<sub>5</sub> or K<sub>3,3</sub>,
+    /// in the format of (`src_id`, `dst_id`, `edge_id`). Only one of the parallel edges between two vertices appears in the witness.
+    NonPlanar(Vec<(usize, usize, usize)>),
+}
+
+impl Planarity {
+    /// # Returns
+    /// `true` if graph is planar.
+    pub fn is_planar(&self) -> bool {
+        matches!(self, Planarity::Planar(_))
+    }
+}
+
+/// Tests whether the graph can be drawn on the plane without crossing edges, using the left-right planarity test.
+///
+/// Direction of edges is ignored, and so are loops and parallel edges.
+///
+/// # Arguments
+/// `graph`: Graph to test.
+///
+/// # Returns
+/// * [`Planar`](crate::algo::Planarity::Planar): Containing an embedding of the graph.
+/// * [`NonPlanar`](crate::algo::Planarity::NonPlanar): Containing a Kuratowski subgraph.
+///
+/// # Complexity
+/// O(|V| + |E|) for planar graphs. Finding the Kuratowski subgraph of a non planar graph tests planarity once per edge, so it takes O(|E| * (|V| + |E|)).
+/// Use [`is_planar`](crate::algo::is_planar) if the witness is not needed.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::List;
+/// use prepona::graph::ListGraph;
+/// use prepona::algo::{planarity, Planarity};
+///
+/// // Given: Complete graph with 4 vertices, and then with 5 vertices.
+/// let mut graph = ListGraph::init(List::<usize>::init());
+/// let mut vertices = vec![];
+/// for _ in 0..4 {
+///     let vertex_id = graph.add_vertex();
+///     for other_id in vertices.iter() {
+///         graph.add_edge_unchecked(*other_id, vertex_id, 1.into());
+///     }
+///     vertices.push(vertex_id);
+/// }
+///
+/// // Then: K4 is planar.
+/// match planarity(&graph) {
+///     Planarity::Planar(embedding) => assert_eq!(embedding.neighbors_cw(vertices[0]).len(), 3),
+///     Planarity::NonPlanar(_) => unreachable!(),
+/// }
+///
+/// // When:
+/// let vertex_id = graph.add_vertex();
+/// for other_id in vertices.iter() {
+///     graph.add_edge_unchecked(*other_id, vertex_id, 1.into());
+/// }
+///
+/// // Then: K5 is not planar and it is its own Kuratowski subgraph.
+/// match planarity(&graph) {
+///     Planarity::Planar(_) => unreachable!(),
+///     Planarity::NonPlanar(kuratowski) => assert_eq!(kuratowski.len(), 10),
+/// }
+/// ```
+pub fn planarity<W, E, G>(graph: &G) -> Planarity
+where
+    E: Edge<W>,
+    G: Vertices + Edges<W, E>,
+{
+    let simple = UnderlyingGraph::init(graph);
+
+    if let Some(rotations) = LrPlanarity::execute(simple.vertex_count, &simple.edges, true) {
+        let rotations = rotations
+            .into_iter()
+            .enumerate()
+            .map(|(virt_id, rotation)| {
+                (
+                    simple.real_ids[virt_id],
+                    rotation
+                        .into_iter()
+                        .map(|virt_id| simple.real_ids[virt_id])
+                        .collect(),
+                )
+            })
+            .collect();
+
+        return Planarity::Planar(Embedding { rotations });
+    }
+
+    // Removing any edge of a minimal non planar subgraph makes it planar, so what remains after dropping every edge that is not needed for non planarity is a Kuratowski subgraph.
+    let mut kept = vec![true; simple.edges.len()];
+    for index in 0..simple.edges.len() {
+        kept[index] = false;
+        let remaining: Vec<(usize, usize)> = simple
+            .edges
+            .iter()
+            .zip(kept.iter())
+            .filter(|(_, kept)| **kept)
+            .map(|(edge, _)| *edge)
+            .collect();
+
+        if LrPlanarity::execute(simple.vertex_count, &remaining, false).is_some() {
+            kept[index] = true;
+        }
+    }
+
+    let witness = simple
+        .edges
+        .iter()
+        .zip(simple.edge_ids.iter())
+        .zip(kept)
+        .filter(|(_, kept)| *kept)
+        .map(|(((src_virt_id, dst_virt_id), edge_id), _)| {
+            (
+                simple.real_ids[*src_virt_id],
+                simple.real_ids[*dst_virt_id],
+                *edge_id,
+            )
+        })
+        .collect();
+
+    Planarity::NonPlanar(witness)
+}
+
+/// Tests whether the graph can be drawn on the plane without crossing edges, without computing an embedding or a witness.
+///
+/// Direction of edges is ignored, and so are loops and parallel edges.
+///
+/// # Arguments
+/// `graph`: Graph to test.
+///
+/// # Returns
+/// `true` if graph is planar.
+///
+/// # Complexity
+/// O(|V| + |E|)
+pub fn is_planar<W, E, G>(graph: &G) -> bool
+where
+    E: Edge<W>,
+    G: Vertices + Edges<W, E>,
+{
+    let simple = UnderlyingGraph::init(graph);
+
+    LrPlanarity::execute(simple.vertex_count, &simple.edges, false).is_some()
+}
+
+// Underlying simple undirected graph over continuos ids.
+struct UnderlyingGraph {
+    vertex_count: usize,
+    real_ids: Vec<usize>,
+    edges: Vec<(usize, usize)>,
+    edge_ids: Vec<usize>,
+}
+
+impl UnderlyingGraph {
+    fn init<W, E, G>(graph: &G) -> Self
+    where
+        E: Edge<W>,
+        G: Vertices + Edges<W, E>,
+    {
+        let id_map = graph.continuos_id_map();
+        let vertex_count = id_map.len();
+        let real_ids = (0..vertex_count)
+            .map(|virt_id| id_map.real_id_of(virt_id))
+            .collect();
+
+        let mut seen = HashSet::new();
+        let mut edges = vec![];
+        let mut edge_ids = vec![];
+        for (src_id, dst_id, edge) in graph.edges() {
+            let src_virt_id = id_map.virt_id_of(src_id);
+            let dst_virt_id = id_map.virt_id_of(dst_id);
+            let key = (src_virt_id.min(dst_virt_id), src_virt_id.max(dst_virt_id));
+
+            if src_virt_id != dst_virt_id && seen.insert(key) {
+                edges.push((src_virt_id, dst_virt_id));
+                edge_ids.push(edge.get_id());
+            }
+        }
+
+        UnderlyingGraph {
+            vertex_count,
+            real_ids,
+            edges,
+            edge_ids,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Default, PartialEq)]
+struct Interval {
+    low: Option<usize>,
+    high: Option<usize>,
+}
+
+impl Interval {
+    fn init(edge: usize) -> Self {
+        Interval {
+            low: Some(edge),
+            high: Some(edge),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.low.is_none() && self.high.is_none()
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+struct ConflictPair {
+    left: Interval,
+    right: Interval,
+}
+
+impl ConflictPair {
+    fn swap(&mut self) {
+        std::mem::swap(&mut self.left, &mut self.right);
+    }
+}
+
+// State of the left-right planarity test of Brandes: "The Left-Right Planarity Test".
+// Vertices are identified by continuos ids and edges are identified by their index in `edges` once oriented by the depth-first search.
+struct LrPlanarity {
+    adjacents: Vec<Vec<usize>>,
+
+    heights: Vec<Option<usize>>,
+    parent_edges: Vec<Option<usize>>,
+    roots: Vec<usize>,
+
+    edges: Vec<(usize, usize)>,
+    oriented: HashSet<(usize, usize)>,
+    out_edges: Vec<Vec<usize>>,
+    ordered_out_edges: Vec<Vec<usize>>,
+
+    lowpt: Vec<usize>,
+    lowpt2: Vec<usize>,
+    nesting_depth: Vec<isize>,
+    refs: Vec<Option<usize>>,
+    sides: Vec<isize>,
+    stack_bottom: Vec<usize>,
+    lowpt_edge: Vec<Option<usize>>,
+
+    conflicts: Vec<ConflictPair>,
+
+    left_refs: Vec<Option<usize>>,
+    right_refs: Vec<Option<usize>>,
+    rotations: Vec<Vec<usize>>,
+    first_neighbors: Vec<Option<usize>>,
+}
+
+impl LrPlanarity {
+    // Returns clockwise order of the neighbors of each vertex if the graph is planar.
+    // Order is only computed if `embed` is true, otherwise the returned rotations are empty.
+    fn execute(
+        vertex_count: usize,
+        edges: &[(usize, usize)],
+        embed: bool,
+    ) -> Option<Vec<Vec<usize>>> {
+        if vertex_count > 2 && edges.len() > 3 * vertex_count - 6 {
+            return None;
+        }
+
+        let mut adjacents = vec![vec![]; vertex_count];
+        for (src_id, dst_id) in edges.iter().copied() {
+            adjacents[src_id].push(dst_id);
+            adjacents[dst_id].push(src_id);
+        }
+
+        let edge_count = edges.len();
+        let mut state = LrPlanarity {
+            adjacents,
+
+            heights: vec![None; vertex_count],
+            parent_edges: vec![None; vertex_count],
+            roots: vec![],
+
+            edges: Vec::with_capacity(edge_count),
+            oriented: HashSet::with_capacity(edge_count),
+            out_edges: vec![vec![]; vertex_count],
+            ordered_out_edges: vec![],
+
+            lowpt: Vec::with_capacity(edge_count),
+            lowpt2: Vec::with_capacity(edge_count),
+            nesting_depth: Vec::with_capacity(edge_count),
+            refs: vec![None; edge_count],
+            sides: vec![1; edge_count],
+            stack_bottom: vec![0; edge_count],
+            lowpt_edge: vec![None; edge_count],
+
+            conflicts: vec![],
+
+            left_refs: vec![None; vertex_count],
+            right_refs: vec![None; vertex_count],
+            rotations: vec![vec![]; vertex_count],
+            first_neighbors: vec![None; vertex_count],
+        };
+
+        for vertex_id in 0..vertex_count {
+            if state.heights[vertex_id].is_none() {
+                state.heights[vertex_id] = Some(0);
+                state.roots.push(vertex_id);
+                state.orient(vertex_id);
+            }
+        }
+
+        state.sort_out_edges();
+        for root_id in state.roots.clone() {
+            if !state.test(root_id) {
+                return None;
+            }
+        }
+
+        if embed {
+            state.embed();
+        }
+
+        Some(state.rotations)
+    }
+
+    fn height(&self, vertex_id: usize) -> usize {
+        self.heights[vertex_id].unwrap()
+    }
+
+    fn sort_out_edges(&mut self) {
+        let nesting_depth = &self.nesting_depth;
+        self.ordered_out_edges = self.out_edges.clone();
+        for out_edges in self.ordered_out_edges.iter_mut() {
+            out_edges.sort_by_key(|edge| nesting_depth[*edge]);
+        }
+    }
+
+    // Phase 1: Orients the edges by a depth-first search and computes the lowpoints and nesting depth of each edge.
+    fn orient(&mut self, vertex_id: usize) {
+        let parent_edge = self.parent_edges[vertex_id];
+
+        for n_id in self.adjacents[vertex_id].clone() {
+            if self.oriented.contains(&(vertex_id, n_id))
+                || self.oriented.contains(&(n_id, vertex_id))
+            {
+                continue;
+            }
+
+            let edge = self.edges.len();
+            self.edges.push((vertex_id, n_id));
+            self.oriented.insert((vertex_id, n_id));
+            self.out_edges[vertex_id].push(edge);
+            self.lowpt.push(self.height(vertex_id));
+            self.lowpt2.push(self.height(vertex_id));
+            self.nesting_depth.push(0);
+
+            match self.heights[n_id] {
+                // Tree edge.
+                None => {
+                    self.parent_edges[n_id] = Some(edge);
+                    self.heights[n_id] = Some(self.height(vertex_id) + 1);
+                    self.orient(n_id);
+                }
+                // Back edge.
+                Some(height) => self.lowpt[edge] = height,
+            }
+
+            self.nesting_depth[edge] = 2 * self.lowpt[edge] as isize;
+            if self.lowpt2[edge] < self.height(vertex_id) {
+                // Chordal edge.
+                self.nesting_depth[edge] += 1;
+            }
+
+            if let Some(parent_edge) = parent_edge {
+                if self.lowpt[edge] < self.lowpt[parent_edge] {
+                    self.lowpt2[parent_edge] = self.lowpt[parent_edge].min(self.lowpt2[edge]);
+                    self.lowpt[parent_edge] = self.lowpt[edge];
+                } else if self.lowpt[edge] > self.lowpt[parent_edge] {
+                    self.lowpt2[parent_edge] = self.lowpt2[parent_edge].min(self.lowpt[edge]);
+                } else {
+                    self.lowpt2[parent_edge] = self.lowpt2[parent_edge].min(self.lowpt2[edge]);
+                }
+            }
+        }
+    }
+
+    // Phase 2: Assigns each back edge to the left or right side relative to other edges, or finds out that it's not possible.
+    fn test(&mut self, vertex_id: usize) -> bool {
+        let parent_edge = self.parent_edges[vertex_id];
+
+        for (index, edge) in self.ordered_out_edges[vertex_id]
+            .clone()
+            .into_iter()
+            .enumerate()
+        {
+            let n_id = self.edges[edge].1;
+            self.stack_bottom[edge] = self.conflicts.len();
+
+            if self.parent_edges[n_id] == Some(edge) {
+                if !self.test(n_id) {
+                    return false;
+                }
+            } else {
+                self.lowpt_edge[edge] = Some(edge);
+                self.conflicts.push(ConflictPair {
+                    left: Interval::default(),
+                    right: Interval::init(edge),
+                });
+            }
+
+            // Integrate the new return edges.
+            if self.lowpt[edge] < self.height(vertex_id) {
+                let parent_edge = parent_edge.unwrap();
+                if index == 0 {
+                    self.lowpt_edge[parent_edge] = self.lowpt_edge[edge];
+                } else if !self.add_constraints(edge, parent_edge) {
+                    return false;
+                }
+            }
+        }
+
+        if let Some(parent_edge) = parent_edge {
+            self.remove_back_edges(parent_edge);
+        }
+
+        true
+    }
+
+    fn is_conflicting(&self, interval: &Interval, edge: usize) -> bool {
+        interval
+            .high
+            .is_some_and(|high| self.lowpt[high] > self.lowpt[edge])
+    }
+
+    fn lowest(&self, pair: &ConflictPair) -> usize {
+        match (pair.left.low, pair.right.low) {
+            (None, Some(right_low)) => self.lowpt[right_low],
+            (Some(left_low), None) => self.lowpt[left_low],
+            (Some(left_low), Some(right_low)) => self.lowpt[left_low].min(self.lowpt[right_low]),
+            (None, None) => usize::MAX,
+        }
+    }
+
+    fn set_ref(&mut self, edge: Option<usize>, reference: Option<usize>) {
+        if let Some(edge) = edge {
+            self.refs[edge] = reference;
+        }
+    }
+
+    fn add_constraints(&mut self, edge: usize, parent_edge: usize) -> bool {
+        let mut pair = ConflictPair::default();
+
+        // Merge return edges of the edge into the right interval.
+        loop {
+            let mut other = self.conflicts.pop().unwrap();
+            if !other.left.is_empty() {
+                other.swap();
+            }
+            if !other.left.is_empty() {
+                return false;
+            }
+
+            if self.lowpt[other.right.low.unwrap()] > self.lowpt[parent_edge] {
+                if pair.right.is_empty() {
+                    pair.right = other.right;
+                } else {
+                    self.set_ref(pair.right.low, other.right.high);
+                }
+                pair.right.low = other.right.low;
+            } else {
+                self.set_ref(other.right.low, self.lowpt_edge[parent_edge]);
+            }
+
+            if self.conflicts.len() == self.stack_bottom[edge] {
+                break;
+            }
+        }
+
+        // Merge conflicting return edges of the previous siblings into the left interval.
+        while let Some(top) = self.conflicts.last() {
+            if !self.is_conflicting(&top.left, edge) && !self.is_conflicting(&top.right, edge) {
+                break;
+            }
+
+            let mut other = self.conflicts.pop().unwrap();
+            if self.is_conflicting(&other.right, edge) {
+                other.swap();
+            }
+            if self.is_conflicting(&other.right, edge) {
+                return false;
+            }
+
+            // Merge interval below lowpoint of the edge into the right interval.
+            self.set_ref(pair.right.low, other.right.high);
+            if other.right.low.is_some() {
+                pair.right.low = other.right.low;
+            }
+
+            if pair.left.is_empty() {
+                pair.left = other.left;
+            } else {
+                self.set_ref(pair.left.low, other.left.high);
+            }
+            pair.left.low = other.left.low;
+        }
+
+        if !(pair.left.is_empty() && pair.right.is_empty()) {
+            self.conflicts.push(pair);
+        }
+
+        true
+    }
+
+    fn remove_back_edges(&mut self, parent_edge: usize) {
+        let parent_id = self.edges[parent_edge].0;
+        let parent_height = self.height(parent_id);
+
+        // Drop entire conflict pairs whose back edges end at the parent.
+        while let Some(top) = self.conflicts.last() {
+            if self.lowest(top) != parent_height {
+                break;
+            }
+
+            let pair = self.conflicts.pop().unwrap();
+            if let Some(left_low) = pair.left.low {
+                self.sides[left_low] = -1;
+            }
+        }
+
+        // One more conflict pair to consider.
+        if let Some(mut pair) = self.conflicts.pop() {
+            while let Some(high) = pair
+                .left
+                .high
+                .filter(|high| self.edges[*high].1 == parent_id)
+            {
+                pair.left.high = self.refs[high];
+            }
+            if pair.left.high.is_none() {
+                if let Some(left_low) = pair.left.low {
+                    self.refs[left_low] = pair.right.low;
+                    self.sides[left_low] = -1;
+                    pair.left.low = None;
+                }
+            }
+
+            while let Some(high) = pair
+                .right
+                .high
+                .filter(|high| self.edges[*high].1 == parent_id)
+            {
+                pair.right.high = self.refs[high];
+            }
+            if pair.right.high.is_none() {
+                if let Some(right_low) = pair.right.low {
+                    self.refs[right_low] = pair.left.low;
+                    self.sides[right_low] = -1;
+                    pair.right.low = None;
+                }
+            }
+
+            self.conflicts.push(pair);
+        }
+
+        // Side of the parent edge is the side of a highest return edge.
+        if self.lowpt[parent_edge] < parent_height {
+            let top = self.conflicts.last().unwrap();
+            let (left_high, right_high) = (top.left.high, top.right.high);
+
+            self.refs[parent_edge] = match (left_high, right_high) {
+                (Some(left_high), None) => Some(left_high),
+                (Some(left_high), Some(right_high))
+                    if self.lowpt[left_high] > self.lowpt[right_high] =>
+                {
+                    Some(left_high)
+                }
+                _ => right_high,
+            };
+        }
+    }
+
+    // Resolves the side of the edge relative to its reference to the absolute side.
+    fn sign(&mut self, edge: usize) -> isize {
+        let mut chain = vec![];
+        let mut current = edge;
+        while let Some(reference) = self.refs[current] {
+            chain.push(current);
+            current = reference;
+        }
+
+        for edge in chain.into_iter().rev() {
+            let reference = self.refs[edge].take().unwrap();
+            self.sides[edge] *= self.sides[reference];
+        }
+
+        self.sides[edge]
+    }
+
+    // Phase 3: Orders the neighbors of each vertex.
+    fn embed(&mut self) {
+        for edge in 0..self.edges.len() {
+            self.nesting_depth[edge] *= self.sign(edge);
+        }
+
+        self.sort_out_edges();
+        for vertex_id in 0..self.ordered_out_edges.len() {
+            let mut previous_id = None;
+            for edge in self.ordered_out_edges[vertex_id].clone() {
+                let n_id = self.edges[edge].1;
+                self.add_cw(vertex_id, n_id, previous_id);
+                previous_id = Some(n_id);
+            }
+        }
+
+        for root_id in self.roots.clone() {
+            self.embed_from(root_id);
+        }
+    }
+
+    fn embed_from(&mut self, vertex_id: usize) {
+        for edge in self.ordered_out_edges[vertex_id].clone() {
+            let n_id = self.edges[edge].1;
+
+            if self.parent_edges[n_id] == Some(edge) {
+                self.add_first(n_id, vertex_id);
+                self.left_refs[vertex_id] = Some(n_id);
+                self.right_refs[vertex_id] = Some(n_id);
+                self.embed_from(n_id);
+            } else if self.sides[edge] == 1 {
+                self.add_cw(n_id, vertex_id, self.right_refs[n_id]);
+            } else {
+                self.add_ccw(n_id, vertex_id, self.left_refs[n_id]);
+                self.left_refs[n_id] = Some(vertex_id);
+            }
+        }
+    }
+
+    // Inserts `dst_id` right after `reference` in clockwise order around `src_id`.
+    fn add_cw(&mut self, src_id: usize, dst_id: usize, reference: Option<usize>) {
+        match reference {
+            None => {
+                self.rotations[src_id] = vec![dst_id];
+                self.first_neighbors[src_id] = Some(dst_id);
+            }
+            Some(reference) => {
+                let index = self.index_of(src_id, reference);
+                self.rotations[src_id].insert(index + 1, dst_id);
+            }
+        }
+    }
+
+    // Inserts `dst_id` right before `reference` in clockwise order around `src_id`.
+    fn add_ccw(&mut self, src_id: usize, dst_id: usize, reference: Option<usize>) {
+        match reference {
+            None => self.add_cw(src_id, dst_id, None),
+            Some(reference) => {
+                let index = self.index_of(src_id, reference);
+                self.rotations[src_id].insert(index, dst_id);
+
+                if self.first_neighbors[src_id] == Some(reference) {
+                    self.first_neighbors[src_id] = Some(dst_id);
+                }
+            }
+        }
+    }
+
+    fn add_first(&mut self, src_id: usize, dst_id: usize) {
+        self.add_ccw(src_id, dst_id, self.first_neighbors[src_id]);
+        self.first_neighbors[src_id] = Some(dst_id);
+    }
+
+    fn index_of(&self, src_id: usize, neighbor_id: usize) -> usize {
+        self.rotations[src_id]
+            .iter()
+            .position(|id| *id == neighbor_id)
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{ListGraph, UndirectedEdge};
+    use crate::misc::rng::Rng;
+    use crate::provide::{Graph, Neighbors};
+    use crate::storage::List;
+
+    fn graph_of(vertex_count: usize, edges: &[(usize, usize)]) -> ListGraph<usize, UndirectedEdge> {
+        let mut graph = ListGraph::init(List::<usize>::init());
+        for _ in 0..vertex_count {
+            graph.add_vertex();
+        }
+        for (src_id, dst_id) in edges.iter() {
+            graph.add_edge_unchecked(*src_id, *dst_id, 1.into());
+        }
+
+        graph
+    }
+
+    fn complete(vertex_count: usize) -> Vec<(usize, usize)> {
+        (0..vertex_count)
+            .flat_map(|src_id| (src_id + 1..vertex_count).map(move |dst_id| (src_id, dst_id)))
+            .collect()
+    }
+
+    // Checks that the embedding is a rotation system of the graph that satisfies Euler's formula in each component.
+    fn assert_valid_embedding(graph: &ListGraph<usize, UndirectedEdge>, embedding: &Embedding) {
+        let mut half_edges = HashSet::new();
+        for vertex_id in embedding.vertices() {
+            let mut neighbors: Vec<usize> = embedding.neighbors_cw(vertex_id).to_vec();
+            neighbors.sort_unstable();
+            let mut expected = graph.neighbors_unchecked(vertex_id);
+            expected.sort_unstable();
+            expected.dedup();
+            assert_eq!(neighbors, expected);
+
+            for n_id in neighbors {
+                half_edges.insert((vertex_id, n_id));
+            }
+        }
+
+        let faces = embedding.faces();
+        let face_count = faces.len();
+        assert_eq!(
+            faces.iter().map(|face| face.len()).sum::<usize>(),
+            half_edges.len()
+        );
+
+        // Every edge is crossed by exactly one dual edge.
+        let dual = dual_graph(embedding);
+        assert_eq!(dual.graph().vertex_count(), face_count);
+        let mut crossed: Vec<(usize, usize)> = dual
+            .graph()
+            .edges()
+            .into_iter()
+            .map(|(_, _, edge)| dual.primal_edge(edge.get_id()))
+            .collect();
+        crossed.sort_unstable();
+        crossed.dedup();
+        assert_eq!(crossed.len(), half_edges.len() / 2);
+
+        // Count components that have edges.
+        let mut component_count = 0;
+        let mut reached = HashSet::new();
+        let mut covered_vertex_count = 0;
+        for vertex_id in embedding.vertices() {
+            if embedding.neighbors_cw(vertex_id).is_empty() || reached.contains(&vertex_id) {
+                continue;
+            }
+            component_count += 1;
+            let mut stack = vec![vertex_id];
+            while let Some(vertex_id) = stack.pop() {
+                if reached.insert(vertex_id) {
+                    covered_vertex_count += 1;
+                    stack.extend(embedding.neighbors_cw(vertex_id));
+                }
+            }
+        }
+
+        let edge_count = half_edges.len() / 2;
+        assert_eq!(
+            covered_vertex_count + face_count,
+            edge_count + 2 * component_count
+        );
+    }
+
+    // Checks that the witness is a minimal non planar graph whose branch vertices look like K5 or K3,3.
+    fn assert_valid_witness(vertex_count: usize, witness: &[(usize, usize, usize)]) {
+        let edges: Vec<(usize, usize)> = witness
+            .iter()
+            .map(|(src_id, dst_id, _)| (*src_id, *dst_id))
+            .collect();
+        assert!(!is_planar(&graph_of(vertex_count, &edges)));
+        for index in 0..edges.len() {
+            let mut fewer = edges.clone();
+            fewer.remove(index);
+            assert!(is_planar(&graph_of(vertex_count, &fewer)));
+        }
+
+        let mut degrees = HashMap::new();
+        for (src_id, dst_id) in edges.iter() {
+            *degrees.entry(*src_id).or_insert(0) += 1;
+            *degrees.entry(*dst_id).or_insert(0) += 1;
+        }
+        let mut branch_degrees: Vec<usize> = degrees
+            .values()
+            .copied()
+            .filter(|degree| *degree != 2)
+            .collect();
+        branch_degrees.sort_unstable();
+        assert!(branch_degrees == vec![4; 5] || branch_degrees == vec![3; 6]);
+    }
+
+    #[test]
+    fn known_graphs() {
+        // Given: Complete bipartite graph K3,3 and Petersen graph.
+        let k33: Vec<(usize, usize)> = (0..3)
+            .flat_map(|src| (3..6).map(move |dst| (src, dst)))
+            .collect();
+        let petersen: Vec<(usize, usize)> = (0..5)
+            .flat_map(|index| {
+                vec![
+                    (index, (index + 1) % 5),
+                    (index, index + 5),
+                    (index + 5, (index + 2) % 5 + 5),
+                ]
+            })
+            .collect();
+
+        // Then:
+        for (vertex_count, edges, planar) in [
+            (0, vec![], true),
+            (1, vec![], true),
+            (4, complete(4), true),
+            (5, complete(5), false),
+            (6, k33, false),
+            (10, petersen, false),
+        ] {
+            let graph = graph_of(vertex_count, &edges);
+            assert_eq!(is_planar(&graph), planar);
+
+            match planarity(&graph) {
+                Planarity::Planar(embedding) => {
+                    assert!(planar);
+                    assert_valid_embedding(&graph, &embedding);
+                }
+                Planarity::NonPlanar(witness) => {
+                    assert!(!planar);
+                    assert_valid_witness(vertex_count, &witness);
+                }
+            }
+        }
+    }
+
+    fn planar_embedding_of(graph: &ListGraph<usize, UndirectedEdge>) -> Embedding {
+        match planarity(graph) {
+            Planarity::Planar(embedding) => embedding,
+            Planarity::NonPlanar(_) => panic!("Graph is planar"),
+        }
+    }
+
+    #[test]
+    fn dual_of_cube_is_octahedron() {
+        // Given: Cube
+        let graph = graph_of(
+            8,
+            &[
+                (0, 1),
+                (1, 2),
+                (2, 3),
+                (3, 0),
+                (4, 5),
+                (5, 6),
+                (6, 7),
+                (7, 4),
+                (0, 4),
+                (1, 5),
+                (2, 6),
+                (3, 7),
+            ],
+        );
+
+        // When:
+        let embedding = planar_embedding_of(&graph);
+        let dual = dual_graph(&embedding);
+
+        // Then: Six square faces, each sharing one edge with four others.
+        assert!(embedding.faces().iter().all(|face| face.len() == 4));
+        let dual_graph = dual.graph();
+        assert_eq!(dual_graph.vertex_count(), 6);
+        assert_eq!(dual_graph.edges_count(), 12);
+        for face_id in dual_graph.vertices() {
+            let mut neighbors = dual_graph.neighbors_unchecked(face_id);
+            neighbors.sort_unstable();
+            neighbors.dedup();
+            assert_eq!(neighbors.len(), 4);
+            assert!(!neighbors.contains(&face_id));
+        }
+    }
+
+    #[test]
+    fn dual_of_tree_has_loops() {
+        // Given: Star with three leaves and an isolated vertex.
+        let graph = graph_of(5, &[(0, 1), (0, 2), (0, 3)]);
+
+        // When:
+        let embedding = planar_embedding_of(&graph);
+        let dual = dual_graph(&embedding);
+
+        // Then: A single face that walks each edge twice, and is crossed by each edge as a loop.
+        let faces = embedding.faces();
+        assert_eq!(faces.len(), 1);
+        assert_eq!(faces[0].len(), 6);
+        assert_eq!(embedding.traverse_face(1, 0).len(), 6);
+        assert_eq!(embedding.traverse_face(1, 0)[0], 1);
+        assert_eq!(dual.graph().edges_count(), 3);
+        assert!(dual.graph().has_any_edge_unchecked(0, 0));
+        assert_eq!(dual.face(0).len(), 6);
+    }
+
+    #[test]
+    fn grid_with_loops_and_parallel_edges() {
+        // Given: 5 by 5 grid with a loop and a parallel edge, and a removed vertex.
+        let mut graph = graph_of(26, &[]);
+        graph.remove_vertex_unchecked(0);
+        for row in 0..5 {
+            for col in 0..5 {
+                let vertex_id = 1 + row * 5 + col;
+                if col < 4 {
+                    graph.add_edge_unchecked(vertex_id, vertex_id + 1, 1.into());
+                }
+                if row < 4 {
+                    graph.add_edge_unchecked(vertex_id, vertex_id + 5, 1.into());
+                }
+            }
+        }
+
+        // When:
+        let result = planarity(&graph);
+
+        // Then:
+        match result {
+            Planarity::Planar(embedding) => assert_valid_embedding(&graph, &embedding),
+            Planarity::NonPlanar(_) => panic!("Grid is planar"),
+        }
+    }
+
+    #[test]
+    fn random_graphs_are_certified() {
+        // Given: Random sparse graphs around the planarity threshold.
+        let mut rng = Rng::seed_from(17);
+        let mut planar_count = 0;
+        for _ in 0..60 {
+            let vertex_count = 4 + rng.next_below(10);
+            let edge_count = vertex_count + rng.next_below(2 * vertex_count);
+            let mut edges = HashSet::new();
+            while edges.len() < edge_count.min(vertex_count * (vertex_count - 1) / 2) {
+                let src_id = rng.next_below(vertex_count);
+                let dst_id = rng.next_below(vertex_count);
+                if src_id != dst_id {
+                    edges.insert((src_id.min(dst_id), src_id.max(dst_id)));
+                }
+            }
+            let mut edges: Vec<(usize, usize)> = edges.into_iter().collect();
+            edges.sort_unstable();
+            let graph = graph_of(vertex_count, &edges);
+
+            // When:
+            let result = planarity(&graph);
+
+            // Then: Each answer comes with a certificate that is checked independently.
+            assert_eq!(result.is_planar(), is_planar(&graph));
+            match result {
+                Planarity::Planar(embedding) => {
+                    planar_count += 1;
+                    assert_valid_embedding(&graph, &embedding);
+                }
+                Planarity::NonPlanar(witness) => assert_valid_witness(vertex_count, &witness),
+            }
+        }
+        assert!(planar_count > 10 && planar_count < 50);
+    }
+}
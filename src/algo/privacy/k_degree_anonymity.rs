@@ -0,0 +1,392 @@
+use anyhow::Result;
+use std::any::Any;
+use std::collections::HashSet;
+
+use crate::algo::Error;
+use crate::graph::{Edge, UndirectedEdge};
+use crate::provide::{Edges, Graph, IdMap, Vertices};
+
+/// Makes an undirected graph [k-degree anonymous](https://dl.acm.org/doi/10.1145/1376616.1376629) by only adding edges.
+///
+/// A graph is k-degree anonymous if for every vertex there are at least k-1 other vertices with the same degree.
+/// This implementation follows the two steps proposed by Liu and Terzi:
+/// * Degree anonymization: Using dynamic programming, partitions the sorted degree sequence into groups of size at least k
+///   so that raising every degree to the maximum degree of its group requires the minimum number of degree increases.
+/// * Graph construction: Realizes the required degree increases by connecting vertices that still need more edges, starting from the vertex that needs the most.
+///
+/// If the anonymized degree sequence can not be realized, the degree sequence is probed by increasing the smallest degree and the process is repeated.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+/// use prepona::algo::KDegreeAnonymity;
+///
+/// // Given: Graph
+/// //
+/// //      a  ---  b  ---  c  ---  d
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(c, d, 1.into());
+///
+/// // When: Making the graph 4-degree anonymous.
+/// let added_edges = KDegreeAnonymity::init(&graph, 4).execute(&mut graph, 1).unwrap();
+///
+/// // Then: Connecting a to d makes every vertex have degree 2.
+/// assert_eq!(added_edges.len(), 1);
+/// assert!(graph.vertices().into_iter().all(|v_id| graph.neighbors_unchecked(v_id).len() == 2));
+/// ```
+pub struct KDegreeAnonymity {
+    k: usize,
+    id_map: IdMap,
+    degrees: Vec<usize>,
+    is_adjacent: HashSet<(usize, usize)>,
+}
+
+impl KDegreeAnonymity {
+    /// Initializes the structure.
+    ///
+    /// # Arguments
+    /// * `graph`: Graph to anonymize.
+    /// * `k`: Minimum number of vertices that must share each degree.
+    pub fn init<W, E, G>(graph: &G, k: usize) -> Self
+    where
+        E: Edge<W>,
+        G: Vertices + Edges<W, E> + Graph<W, E, UndirectedEdge>,
+    {
+        let id_map = graph.continuos_id_map();
+
+        let mut degrees = vec![0; graph.vertex_count()];
+        let mut is_adjacent = HashSet::new();
+        for (src_id, dst_id, _) in graph.as_directed_edges() {
+            let src_virt_id = id_map.virt_id_of(src_id);
+            let dst_virt_id = id_map.virt_id_of(dst_id);
+
+            degrees[src_virt_id] += 1;
+            is_adjacent.insert((src_virt_id, dst_virt_id));
+        }
+
+        KDegreeAnonymity {
+            k,
+            id_map,
+            degrees,
+            is_adjacent,
+        }
+    }
+
+    /// Adds edges to the graph until it becomes k-degree anonymous.
+    ///
+    /// # Arguments
+    /// * `graph`: Graph to anonymize.
+    /// * `weight`: Weight of the edges that will be added to the graph.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing the added edges in the format of: (`src_id`, `dst_id`, `edge_id`). No edge is added if k is 0 or 1.
+    /// * `Err`: If graph has less than k vertices or no anonymization could be constructed.
+    pub fn execute<W, E, G>(self, graph: &mut G, weight: W) -> Result<Vec<(usize, usize, usize)>>
+    where
+        W: Any + Copy,
+        E: Edge<W>,
+        G: Vertices + Edges<W, E> + Graph<W, E, UndirectedEdge>,
+    {
+        let vertex_count = self.degrees.len();
+
+        // Every graph is 1-degree anonymous, because each vertex shares its degree with itself.
+        if vertex_count == 0 || self.k <= 1 {
+            return Ok(vec![]);
+        } else if self.k > vertex_count {
+            Err(Error::new_anf(self.k))?
+        }
+
+        let mut requested_degrees = self.degrees.clone();
+
+        for _ in 0..vertex_count {
+            let target_degrees = self.anonymize_degrees(&requested_degrees);
+
+            if let Some(pairs) = self.construct(&target_degrees) {
+                return Ok(pairs
+                    .into_iter()
+                    .map(|(src_virt_id, dst_virt_id)| {
+                        let src_id = self.id_map.real_id_of(src_virt_id);
+                        let dst_id = self.id_map.real_id_of(dst_virt_id);
+
+                        let edge_id =
+                            graph.add_edge_unchecked(src_id, dst_id, E::init(weight.into()));
+
+                        (src_id, dst_id, edge_id)
+                    })
+                    .collect());
+            }
+
+            // Probe a slightly different degree sequence in the next round.
+            let min_virt_id = (0..vertex_count)
+                .min_by_key(|virt_id| requested_degrees[*virt_id])
+                .unwrap();
+            requested_degrees[min_virt_id] += 1;
+        }
+
+        Err(Error::new_anf(self.k))?
+    }
+
+    // # Arguments
+    // `degrees`: Degree of each vertex indexed by its virtual id.
+    //
+    // # Returns
+    // Anonymized degree of each vertex indexed by its virtual id. Each degree is greater than or equal to the given degree.
+    fn anonymize_degrees(&self, degrees: &[usize]) -> Vec<usize> {
+        let vertex_count = degrees.len();
+
+        let mut sorted_virt_ids: Vec<usize> = (0..vertex_count).collect();
+        sorted_virt_ids.sort_by(|v1, v2| degrees[*v2].cmp(&degrees[*v1]));
+
+        let sorted_degrees: Vec<usize> =
+            sorted_virt_ids.iter().map(|v_id| degrees[*v_id]).collect();
+
+        let mut prefix_sum = vec![0; vertex_count + 1];
+        for i in 0..vertex_count {
+            prefix_sum[i + 1] = prefix_sum[i] + sorted_degrees[i];
+        }
+
+        // cost[j]: Minimum cost of anonymizing the first j degrees.
+        // group_start[j]: Start of the last group in the optimal anonymization of the first j degrees.
+        // Groups larger than 2k - 1 never need to be considered because they can be split without increasing the cost.
+        let mut cost = vec![None; vertex_count + 1];
+        let mut group_start = vec![0; vertex_count + 1];
+        cost[0] = Some(0);
+        for j in self.k..=vertex_count {
+            let lowest_start = j.saturating_sub(2 * self.k - 1);
+
+            for i in lowest_start..=(j - self.k) {
+                if let Some(prev_cost) = cost[i] {
+                    let group_cost = (j - i) * sorted_degrees[i] - (prefix_sum[j] - prefix_sum[i]);
+
+                    if cost[j].is_none_or(|current| prev_cost + group_cost < current) {
+                        cost[j] = Some(prev_cost + group_cost);
+                        group_start[j] = i;
+                    }
+                }
+            }
+        }
+
+        let mut target_degrees = vec![0; vertex_count];
+        let mut end = vertex_count;
+        while end > 0 {
+            let start = group_start[end];
+            for v_id in &sorted_virt_ids[start..end] {
+                target_degrees[*v_id] = sorted_degrees[start];
+            }
+            end = start;
+        }
+
+        target_degrees
+    }
+
+    // # Arguments
+    // `target_degrees`: Degree each vertex must have after adding the edges.
+    //
+    // # Returns
+    // * `Some`: Containing pairs of virtual ids that must get connected.
+    // * `None`: If target degrees can not be realized by adding edges to the graph.
+    fn construct(&self, target_degrees: &[usize]) -> Option<Vec<(usize, usize)>> {
+        let vertex_count = target_degrees.len();
+
+        let mut residual: Vec<usize> = (0..vertex_count)
+            .map(|virt_id| target_degrees[virt_id] - self.degrees[virt_id])
+            .collect();
+
+        if residual.iter().sum::<usize>() % 2 != 0 {
+            return None;
+        }
+
+        let mut is_adjacent = self.is_adjacent.clone();
+        let mut pairs = vec![];
+
+        while let Some(virt_id) = (0..vertex_count)
+            .filter(|virt_id| residual[*virt_id] > 0)
+            .max_by_key(|virt_id| residual[*virt_id])
+        {
+            let mut candidates: Vec<usize> = (0..vertex_count)
+                .filter(|other_id| {
+                    *other_id != virt_id
+                        && residual[*other_id] > 0
+                        && !is_adjacent.contains(&(virt_id, *other_id))
+                })
+                .collect();
+
+            if candidates.len() < residual[virt_id] {
+                return None;
+            }
+
+            candidates.sort_by(|v1, v2| residual[*v2].cmp(&residual[*v1]));
+
+            for other_id in candidates.into_iter().take(residual[virt_id]) {
+                residual[other_id] -= 1;
+                is_adjacent.insert((virt_id, other_id));
+                is_adjacent.insert((other_id, virt_id));
+                pairs.push((virt_id, other_id));
+            }
+            residual[virt_id] = 0;
+        }
+
+        Some(pairs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::MatGraph;
+    use crate::provide::*;
+    use crate::storage::Mat;
+    use std::collections::HashMap;
+
+    fn assert_k_anonymous<G: Vertices + Neighbors>(graph: &G, k: usize) {
+        let mut degree_count = HashMap::new();
+        for v_id in graph.vertices() {
+            *degree_count
+                .entry(graph.neighbors_unchecked(v_id).len())
+                .or_insert(0) += 1;
+        }
+
+        assert!(degree_count.values().all(|count| *count >= k));
+    }
+
+    #[test]
+    fn empty_graph() {
+        // Given: An empty graph.
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+
+        // When: Making the graph 2-degree anonymous.
+        let added_edges = KDegreeAnonymity::init(&graph, 2).execute(&mut graph, 1);
+
+        // Then:
+        assert!(added_edges.unwrap().is_empty());
+    }
+
+    #[test]
+    fn k_larger_than_vertex_count() {
+        // Given: Graph
+        //
+        //      a  ---  b
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+
+        // When: Making the graph 3-degree anonymous.
+        let added_edges = KDegreeAnonymity::init(&graph, 3).execute(&mut graph, 1);
+
+        // Then:
+        assert!(added_edges.is_err());
+    }
+
+    #[test]
+    fn k_of_zero_and_one() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  c
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+
+        // When: Making the graph 0-degree and 1-degree anonymous.
+        let zero_added_edges = KDegreeAnonymity::init(&graph, 0).execute(&mut graph, 1);
+        let one_added_edges = KDegreeAnonymity::init(&graph, 1).execute(&mut graph, 1);
+
+        // Then: Graph is already anonymous.
+        assert!(zero_added_edges.unwrap().is_empty());
+        assert!(one_added_edges.unwrap().is_empty());
+        assert_eq!(graph.edges_count(), 2);
+    }
+
+    #[test]
+    fn already_anonymous_graph() {
+        // Given: Graph
+        //
+        //      a  ---  b
+        //      |       |
+        //      d  ---  c
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(c, d, 1.into());
+        graph.add_edge_unchecked(d, a, 1.into());
+
+        // When: Making the graph 4-degree anonymous.
+        let added_edges = KDegreeAnonymity::init(&graph, 4).execute(&mut graph, 1);
+
+        // Then: No edge is needed.
+        assert!(added_edges.unwrap().is_empty());
+        assert_eq!(graph.edges_count(), 4);
+    }
+
+    #[test]
+    fn star_graph() {
+        // Given: Graph
+        //
+        //      b       c
+        //        \   /
+        //          a
+        //        /   \
+        //      d       e
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(a, c, 1.into());
+        graph.add_edge_unchecked(a, d, 1.into());
+        graph.add_edge_unchecked(a, e, 1.into());
+
+        // When: Making the graph 2-degree anonymous.
+        let added_edges = KDegreeAnonymity::init(&graph, 2)
+            .execute(&mut graph, 1)
+            .unwrap();
+
+        // Then:
+        assert_eq!(graph.edges_count(), 4 + added_edges.len());
+        assert_k_anonymous(&graph, 2);
+    }
+
+    #[test]
+    fn path_graph() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  c  ---  d  ---  e  ---  f
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let vertices: Vec<usize> = (0..6).map(|_| graph.add_vertex()).collect();
+        for window in vertices.windows(2) {
+            graph.add_edge_unchecked(window[0], window[1], 1.into());
+        }
+
+        // When: Making the graph 3-degree anonymous.
+        let added_edges = KDegreeAnonymity::init(&graph, 3)
+            .execute(&mut graph, 1)
+            .unwrap();
+
+        // Then:
+        assert!(!added_edges.is_empty());
+        assert_k_anonymous(&graph, 3);
+    }
+}
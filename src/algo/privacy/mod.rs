@@ -0,0 +1,3 @@
+mod k_degree_anonymity;
+
+pub use k_degree_anonymity::KDegreeAnonymity;
@@ -0,0 +1,410 @@
+mod node2vec;
+
+pub use node2vec::{node2vec_walks, Node2VecWalk};
+
+use std::marker::PhantomData;
+
+use crate::graph::Edge;
+use crate::misc::rng::{Rng, SplitRng};
+use crate::provide::{Edges, Vertices};
+
+/// An iterator over the vertices of a random walk.
+///
+/// The walk starts from a source vertex and in each step either restarts from the source, with probability of `restart_probability`,
+/// or moves along one of the edges going out of the current vertex. Each edge is chosen with a probability proportional to its transition weight,
+/// which is 1 for all edges by default and can be changed using [`with_weights`](crate::algo::RandomWalk::with_weights).
+/// So parallel edges make their destination more likely to be chosen.
+///
+/// Restarting makes the visit frequencies of vertices approximate their personalized PageRank with respect to the source,
+/// with `restart_probability` as the teleport probability.
+///
+/// The first vertex is the source itself. The walk ends after `length` steps if [`with_length`](crate::algo::RandomWalk::with_length) is used,
+/// or when it reaches a vertex with no outgoing edge of positive weight. Otherwise it never ends.
+///
+/// ## Generic Parameters
+/// * `W`: **W**eight type associated with edges.
+/// * `E`: **E**dge type that graph uses.
+/// * `G`: **G**raph to walk on.
+/// * `F`: **F**unction that computes transition weight of each edge.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::misc::rng::Rng;
+/// use prepona::storage::DiList;
+/// use prepona::graph::ListGraph;
+/// use prepona::algo::RandomWalk;
+///
+/// // Given: Graph
+/// //
+/// //      a  --1-->  b  --1-->  c
+/// //      |
+/// //      '---0--->  d
+/// //
+/// let mut graph = ListGraph::init(DiList::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(a, d, 0.into());
+///
+/// // When: Using weights of edges as transition weights.
+/// let walk: Vec<usize> = RandomWalk::init(&graph, a, Rng::seed_from(42))
+///     .with_weights(|edge| edge.get_weight().unwrap() as f64)
+///     .collect();
+///
+/// // Then: The edge with weight 0 is never taken and the walk ends at c.
+/// assert_eq!(walk, vec![a, b, c]);
+/// ```
+pub struct RandomWalk<'a, W, E, G, F = fn(&E) -> f64> {
+    graph: &'a G,
+    src_id: usize,
+    current_id: Option<usize>,
+
+    remaining_steps: Option<usize>,
+    restart_probability: f64,
+    weight_fn: F,
+
+    rng: Rng,
+
+    phantom_w: PhantomData<W>,
+    phantom_e: PhantomData<E>,
+}
+
+impl<'a, W, E, G> RandomWalk<'a, W, E, G>
+where
+    E: Edge<W>,
+    G: Vertices + Edges<W, E>,
+{
+    /// Initializes an endless walk with uniform transition weights and no restarts.
+    ///
+    /// # Arguments
+    /// * `graph`: Graph to walk on.
+    /// * `src_id`: Id of the vertex to start the walk from.
+    /// * `rng`: Source of randomness. Walks with the same seed are the same.
+    ///
+    /// # Panics
+    /// If there is no vertex with id: `src_id`.
+    pub fn init(graph: &'a G, src_id: usize, rng: Rng) -> Self {
+        assert!(
+            graph.contains_vertex(src_id),
+            "Vertex with id: {} not found",
+            src_id
+        );
+
+        RandomWalk {
+            graph,
+            src_id,
+            current_id: None,
+
+            remaining_steps: None,
+            restart_probability: 0.0,
+            weight_fn: |_| 1.0,
+
+            rng,
+
+            phantom_w: PhantomData,
+            phantom_e: PhantomData,
+        }
+    }
+}
+
+impl<'a, W, E, G, F> RandomWalk<'a, W, E, G, F>
+where
+    E: Edge<W>,
+    G: Vertices + Edges<W, E>,
+    F: FnMut(&E) -> f64,
+{
+    /// Ends the walk after the given number of steps, so at most `length + 1` vertices are visited.
+    ///
+    /// # Arguments
+    /// `length`: Maximum number of steps.
+    pub fn with_length(mut self, length: usize) -> Self {
+        self.remaining_steps = Some(length);
+
+        self
+    }
+
+    /// Restarts the walk from the source with the given probability in each step.
+    ///
+    /// # Arguments
+    /// `restart_probability`: Probability of restarting, in range [0, 1].
+    ///
+    /// # Panics
+    /// If `restart_probability` is not in range [0, 1].
+    pub fn with_restart(mut self, restart_probability: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&restart_probability),
+            "Restart probability must be in range [0, 1]"
+        );
+
+        self.restart_probability = restart_probability;
+
+        self
+    }
+
+    /// Uses the given function to compute transition weight of each edge.
+    ///
+    /// # Arguments
+    /// `weight_fn`: Receives an edge and returns its transition weight. Edges with zero or negative weight are never taken.
+    pub fn with_weights<F2>(self, weight_fn: F2) -> RandomWalk<'a, W, E, G, F2>
+    where
+        F2: FnMut(&E) -> f64,
+    {
+        RandomWalk {
+            graph: self.graph,
+            src_id: self.src_id,
+            current_id: self.current_id,
+
+            remaining_steps: self.remaining_steps,
+            restart_probability: self.restart_probability,
+            weight_fn,
+
+            rng: self.rng,
+
+            phantom_w: PhantomData,
+            phantom_e: PhantomData,
+        }
+    }
+
+    // Chooses the destination of an outgoing edge of `vertex_id` with probability proportional to the transition weight of the edge.
+    fn step_from(&mut self, vertex_id: usize) -> Option<usize> {
+        let weight_fn = &mut self.weight_fn;
+        let candidates = self
+            .graph
+            .edges_from_unchecked(vertex_id)
+            .into_iter()
+            .map(|(dst_id, edge)| (dst_id, weight_fn(edge)));
+
+        choose(&mut self.rng, candidates)
+    }
+}
+
+impl<'a, W, E, G, F> Iterator for RandomWalk<'a, W, E, G, F>
+where
+    E: Edge<W>,
+    G: Vertices + Edges<W, E>,
+    F: FnMut(&E) -> f64,
+{
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next_id = match self.current_id {
+            None => Some(self.src_id),
+            Some(current_id) => {
+                match self.remaining_steps.as_mut() {
+                    Some(0) => return None,
+                    Some(remaining_steps) => *remaining_steps -= 1,
+                    None => {}
+                }
+
+                if self.rng.next_bool(self.restart_probability) {
+                    Some(self.src_id)
+                } else {
+                    self.step_from(current_id)
+                }
+            }
+        };
+
+        // A dead end finishes the walk for good.
+        self.remaining_steps = if next_id.is_none() {
+            Some(0)
+        } else {
+            self.remaining_steps
+        };
+        self.current_id = next_id.or(self.current_id);
+
+        next_id
+    }
+}
+
+// Chooses one of the candidates, in the format of (`vertex_id`, `weight`), with probability proportional to its weight.
+// Candidates with zero or negative weight are never chosen.
+fn choose<I>(rng: &mut Rng, candidates: I) -> Option<usize>
+where
+    I: IntoIterator<Item = (usize, f64)>,
+{
+    let candidates: Vec<(usize, f64)> = candidates
+        .into_iter()
+        .filter(|(_, weight)| *weight > 0.0)
+        .collect();
+
+    if candidates.is_empty() {
+        return None;
+    }
+
+    let total_weight: f64 = candidates.iter().map(|(_, weight)| weight).sum();
+
+    let mut target = rng.next_f64() * total_weight;
+    for (vertex_id, weight) in candidates.iter() {
+        if target < *weight {
+            return Some(*vertex_id);
+        }
+
+        target -= weight;
+    }
+
+    // Rounding errors may leave a tiny part of the target.
+    candidates.last().map(|(vertex_id, _)| *vertex_id)
+}
+
+/// Generates a batch of random walks with uniform transition weights, like the ones used to train vertex embeddings.
+///
+/// Walk number `i` uses stream `i` of a [`SplitRng`](crate::misc::rng::SplitRng) seeded with `seed`,
+/// so each walk only depends on the seed and its index.
+///
+/// # Arguments
+/// * `graph`: Graph to walk on.
+/// * `starts`: Id of the vertices to start walks from.
+/// * `length`: Maximum number of steps of each walk. Walks that reach a dead end are shorter.
+/// * `count`: Number of walks to start from each vertex in `starts`.
+/// * `seed`: Seed of the random streams.
+///
+/// # Returns
+/// `count` walks for each vertex in `starts`, in the same order as `starts`. Each walk starts with its start vertex.
+///
+/// # Panics
+/// If any vertex in `starts` does not exist.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::List;
+/// use prepona::graph::ListGraph;
+/// use prepona::algo::random_walks;
+///
+/// // Given: Graph
+/// //
+/// //      a  --  b  --  c
+/// //
+/// let mut graph = ListGraph::init(List::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+///
+/// // When:
+/// let walks = random_walks(&graph, &[a, c], 4, 3, 42);
+///
+/// // Then:
+/// assert_eq!(walks.len(), 6);
+/// assert!(walks.iter().all(|walk| walk.len() == 5 && walk[1] == b));
+/// assert_eq!(walks, random_walks(&graph, &[a, c], 4, 3, 42));
+/// ```
+pub fn random_walks<W, E, G>(
+    graph: &G,
+    starts: &[usize],
+    length: usize,
+    count: usize,
+    seed: u64,
+) -> Vec<Vec<usize>>
+where
+    E: Edge<W>,
+    G: Vertices + Edges<W, E>,
+{
+    let split_rng = SplitRng::seed_from(seed);
+
+    starts
+        .iter()
+        .flat_map(|src_id| std::iter::repeat_n(*src_id, count))
+        .enumerate()
+        .map(|(index, src_id)| {
+            RandomWalk::init(graph, src_id, split_rng.stream(index))
+                .with_length(length)
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{ListGraph, MatGraph};
+    use crate::provide::Graph;
+    use crate::storage::{DiList, Mat};
+
+    #[test]
+    fn walks_follow_edges() {
+        // Given: Graph
+        //
+        //      a  --  b  --  c  --  d
+        //      |                    |
+        //      '--------------------'
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let vertices: Vec<_> = (0..4).map(|_| graph.add_vertex()).collect();
+        for index in 0..4 {
+            graph.add_edge_unchecked(vertices[index], vertices[(index + 1) % 4], 1.into());
+        }
+
+        // When:
+        let walk: Vec<usize> = RandomWalk::init(&graph, vertices[0], Rng::seed_from(1))
+            .with_length(100)
+            .collect();
+
+        // Then:
+        assert_eq!(walk.len(), 101);
+        assert_eq!(walk[0], vertices[0]);
+        assert!(walk
+            .windows(2)
+            .all(|step| graph.has_any_edge_unchecked(step[0], step[1])));
+    }
+
+    #[test]
+    fn dead_end_finishes_the_walk() {
+        // Given: Graph
+        //
+        //      a  -->  b
+        //
+        let mut graph = ListGraph::init(DiList::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+
+        // When:
+        let mut walk = RandomWalk::init(&graph, a, Rng::seed_from(1));
+
+        // Then:
+        assert_eq!(walk.next(), Some(a));
+        assert_eq!(walk.next(), Some(b));
+        assert_eq!(walk.next(), None);
+        assert_eq!(walk.next(), None);
+    }
+
+    #[test]
+    fn restart_and_weights() {
+        // Given: Graph
+        //
+        //      a  --1-->  b  --1-->  c
+        //      ^ |                   |
+        //      | '---3--->  d        |
+        //      '---------------------'
+        //
+        let mut graph = ListGraph::init(DiList::<usize>::init());
+        let vertices: Vec<_> = (0..4).map(|_| graph.add_vertex()).collect();
+        for (src, dst, weight) in [(0, 1, 1), (1, 2, 1), (2, 0, 1), (0, 3, 3), (3, 0, 1)].iter() {
+            graph.add_edge_unchecked(vertices[*src], vertices[*dst], (*weight).into());
+        }
+
+        // When:
+        let restarting: Vec<usize> = RandomWalk::init(&graph, vertices[0], Rng::seed_from(7))
+            .with_restart(1.0)
+            .with_length(10)
+            .collect();
+        let weighted: Vec<usize> = RandomWalk::init(&graph, vertices[0], Rng::seed_from(7))
+            .with_weights(|edge| edge.get_weight().unwrap() as f64)
+            .with_length(20_000)
+            .filter(|vertex_id| *vertex_id != vertices[0])
+            .take(10_000)
+            .collect();
+
+        // Then: Restarting in each step stays at the source and d is taken three times more often than b.
+        assert_eq!(restarting, vec![vertices[0]; 11]);
+        let d_count = weighted.iter().filter(|id| **id == vertices[3]).count();
+        let b_count = weighted.iter().filter(|id| **id == vertices[1]).count();
+        assert!((2.5..3.5).contains(&(d_count as f64 / b_count as f64)));
+    }
+}
@@ -0,0 +1,382 @@
+use std::collections::HashSet;
+use std::marker::PhantomData;
+
+use super::choose;
+use crate::graph::Edge;
+use crate::misc::rng::{Rng, SplitRng};
+use crate::provide::{Edges, Vertices};
+
+/// An iterator over the vertices of a biased second order random walk, as used by node2vec.
+///
+/// Unlike [`RandomWalk`](crate::algo::RandomWalk), the next step depends on the previous vertex as well as the current one.
+/// After moving from `t` to `v`, weight of each edge from `v` to `x` is multiplied by:
+/// * `1/p`: If `x` is `t`, so `p` is the return parameter and a high `p` discourages going back.
+/// * `1`: If there is an edge from `t` to `x`, so the walk stays around `t`.
+/// * `1/q`: Otherwise, so `q` is the in-out parameter and a low `q` pushes the walk outwards like a depth-first search, while a high `q` keeps it local like a breadth-first search.
+///
+/// The first step is a plain weighted step. Transition weight of each edge is 1 by default and can be changed using [`with_weights`](crate::algo::Node2VecWalk::with_weights).
+/// The walk ends after `length` steps if [`with_length`](crate::algo::Node2VecWalk::with_length) is used,
+/// or when it reaches a vertex with no outgoing edge of positive weight.
+///
+/// ## Generic Parameters
+/// * `W`: **W**eight type associated with edges.
+/// * `E`: **E**dge type that graph uses.
+/// * `G`: **G**raph to walk on.
+/// * `F`: **F**unction that computes transition weight of each edge.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::misc::rng::Rng;
+/// use prepona::storage::List;
+/// use prepona::graph::ListGraph;
+/// use prepona::algo::Node2VecWalk;
+///
+/// // Given: Graph
+/// //
+/// //      a  --  b  --  c  --  d
+/// //
+/// let mut graph = ListGraph::init(List::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(c, d, 1.into());
+///
+/// // When: Going back is practically forbidden.
+/// let walk: Vec<usize> = Node2VecWalk::init(&graph, a, 1e9, 1.0, Rng::seed_from(42))
+///     .with_length(3)
+///     .collect();
+///
+/// // Then:
+/// assert_eq!(walk, vec![a, b, c, d]);
+/// ```
+pub struct Node2VecWalk<'a, W, E, G, F = fn(&E) -> f64> {
+    graph: &'a G,
+    src_id: usize,
+    previous_id: Option<usize>,
+    current_id: Option<usize>,
+
+    remaining_steps: Option<usize>,
+    p: f64,
+    q: f64,
+    weight_fn: F,
+
+    rng: Rng,
+
+    phantom_w: PhantomData<W>,
+    phantom_e: PhantomData<E>,
+}
+
+impl<'a, W, E, G> Node2VecWalk<'a, W, E, G>
+where
+    E: Edge<W>,
+    G: Vertices + Edges<W, E>,
+{
+    /// Initializes an endless walk with uniform transition weights.
+    ///
+    /// # Arguments
+    /// * `graph`: Graph to walk on.
+    /// * `src_id`: Id of the vertex to start the walk from.
+    /// * `p`: Return parameter.
+    /// * `q`: In-out parameter.
+    /// * `rng`: Source of randomness. Walks with the same seed are the same.
+    ///
+    /// # Panics
+    /// * If there is no vertex with id: `src_id`.
+    /// * If `p` or `q` is not positive.
+    pub fn init(graph: &'a G, src_id: usize, p: f64, q: f64, rng: Rng) -> Self {
+        assert!(
+            graph.contains_vertex(src_id),
+            "Vertex with id: {} not found",
+            src_id
+        );
+        assert!(p > 0.0 && q > 0.0, "p and q must be positive");
+
+        Node2VecWalk {
+            graph,
+            src_id,
+            previous_id: None,
+            current_id: None,
+
+            remaining_steps: None,
+            p,
+            q,
+            weight_fn: |_| 1.0,
+
+            rng,
+
+            phantom_w: PhantomData,
+            phantom_e: PhantomData,
+        }
+    }
+}
+
+impl<'a, W, E, G, F> Node2VecWalk<'a, W, E, G, F>
+where
+    E: Edge<W>,
+    G: Vertices + Edges<W, E>,
+    F: FnMut(&E) -> f64,
+{
+    /// Ends the walk after the given number of steps, so at most `length + 1` vertices are visited.
+    ///
+    /// # Arguments
+    /// `length`: Maximum number of steps.
+    pub fn with_length(mut self, length: usize) -> Self {
+        self.remaining_steps = Some(length);
+
+        self
+    }
+
+    /// Uses the given function to compute transition weight of each edge, before it's biased by `p` and `q`.
+    ///
+    /// # Arguments
+    /// `weight_fn`: Receives an edge and returns its transition weight. Edges with zero or negative weight are never taken.
+    pub fn with_weights<F2>(self, weight_fn: F2) -> Node2VecWalk<'a, W, E, G, F2>
+    where
+        F2: FnMut(&E) -> f64,
+    {
+        Node2VecWalk {
+            graph: self.graph,
+            src_id: self.src_id,
+            previous_id: self.previous_id,
+            current_id: self.current_id,
+
+            remaining_steps: self.remaining_steps,
+            p: self.p,
+            q: self.q,
+            weight_fn,
+
+            rng: self.rng,
+
+            phantom_w: PhantomData,
+            phantom_e: PhantomData,
+        }
+    }
+
+    fn step_from(&mut self, vertex_id: usize) -> Option<usize> {
+        let previous = self.previous_id.map(|previous_id| {
+            let neighbors: HashSet<usize> = self
+                .graph
+                .edges_from_unchecked(previous_id)
+                .into_iter()
+                .map(|(dst_id, _)| dst_id)
+                .collect();
+
+            (previous_id, neighbors)
+        });
+
+        let (p, q) = (self.p, self.q);
+        let weight_fn = &mut self.weight_fn;
+        let candidates =
+            self.graph
+                .edges_from_unchecked(vertex_id)
+                .into_iter()
+                .map(|(dst_id, edge)| {
+                    let bias = match &previous {
+                        None => 1.0,
+                        Some((previous_id, _)) if *previous_id == dst_id => 1.0 / p,
+                        Some((_, neighbors)) if neighbors.contains(&dst_id) => 1.0,
+                        Some(_) => 1.0 / q,
+                    };
+
+                    (dst_id, weight_fn(edge) * bias)
+                });
+
+        choose(&mut self.rng, candidates)
+    }
+}
+
+impl<'a, W, E, G, F> Iterator for Node2VecWalk<'a, W, E, G, F>
+where
+    E: Edge<W>,
+    G: Vertices + Edges<W, E>,
+    F: FnMut(&E) -> f64,
+{
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next_id = match self.current_id {
+            None => Some(self.src_id),
+            Some(current_id) => {
+                match self.remaining_steps.as_mut() {
+                    Some(0) => return None,
+                    Some(remaining_steps) => *remaining_steps -= 1,
+                    None => {}
+                }
+
+                self.step_from(current_id)
+            }
+        };
+
+        match next_id {
+            // A dead end finishes the walk for good.
+            None => self.remaining_steps = Some(0),
+            Some(_) => {
+                self.previous_id = self.current_id;
+                self.current_id = next_id;
+            }
+        }
+
+        next_id
+    }
+}
+
+/// Generates a batch of node2vec walks with uniform transition weights, to be fed into embedding trainers like skip-gram.
+///
+/// Walk number `i` uses stream `i` of a [`SplitRng`](crate::misc::rng::SplitRng) seeded with `seed`,
+/// so each walk only depends on the seed and its index.
+///
+/// # Arguments
+/// * `graph`: Graph to walk on.
+/// * `starts`: Id of the vertices to start walks from.
+/// * `length`: Maximum number of steps of each walk. Walks that reach a dead end are shorter.
+/// * `count`: Number of walks to start from each vertex in `starts`.
+/// * `p`: Return parameter. For more info checkout [`Node2VecWalk`](crate::algo::Node2VecWalk).
+/// * `q`: In-out parameter. For more info checkout [`Node2VecWalk`](crate::algo::Node2VecWalk).
+/// * `seed`: Seed of the random streams.
+///
+/// # Returns
+/// `count` walks for each vertex in `starts`, in the same order as `starts`. Each walk starts with its start vertex.
+///
+/// # Panics
+/// * If any vertex in `starts` does not exist.
+/// * If `p` or `q` is not positive.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::List;
+/// use prepona::graph::ListGraph;
+/// use prepona::algo::node2vec_walks;
+///
+/// // Given: Graph
+/// //
+/// //      a  --  b  --  c
+/// //
+/// let mut graph = ListGraph::init(List::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+///
+/// // When:
+/// let walks = node2vec_walks(&graph, &[a, b, c], 5, 2, 1.0, 0.5, 42);
+///
+/// // Then:
+/// assert_eq!(walks.len(), 6);
+/// assert!(walks.iter().all(|walk| walk.len() == 6));
+/// ```
+pub fn node2vec_walks<W, E, G>(
+    graph: &G,
+    starts: &[usize],
+    length: usize,
+    count: usize,
+    p: f64,
+    q: f64,
+    seed: u64,
+) -> Vec<Vec<usize>>
+where
+    E: Edge<W>,
+    G: Vertices + Edges<W, E>,
+{
+    let split_rng = SplitRng::seed_from(seed);
+
+    starts
+        .iter()
+        .flat_map(|src_id| std::iter::repeat_n(*src_id, count))
+        .enumerate()
+        .map(|(index, src_id)| {
+            Node2VecWalk::init(graph, src_id, p, q, split_rng.stream(index))
+                .with_length(length)
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::MatGraph;
+    use crate::provide::Graph;
+    use crate::storage::Mat;
+
+    // Fraction of steps, after the first one, that go back to the vertex visited two steps earlier.
+    fn return_rate(walks: &[Vec<usize>]) -> f64 {
+        let (returns, steps) = walks
+            .iter()
+            .flat_map(|walk| walk.windows(3))
+            .fold((0, 0), |(returns, steps), window| {
+                (returns + (window[0] == window[2]) as usize, steps + 1)
+            });
+
+        returns as f64 / steps as f64
+    }
+
+    #[test]
+    fn return_parameter_controls_backtracking() {
+        // Given: Cycle with 6 vertices, where each step either goes back or forward.
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let vertices: Vec<_> = (0..6).map(|_| graph.add_vertex()).collect();
+        for index in 0..6 {
+            graph.add_edge_unchecked(vertices[index], vertices[(index + 1) % 6], 1.into());
+        }
+
+        // When:
+        let unbiased = node2vec_walks(&graph, &vertices, 20, 20, 1.0, 1.0, 7);
+        let returning = node2vec_walks(&graph, &vertices, 20, 20, 0.25, 1.0, 7);
+        let exploring = node2vec_walks(&graph, &vertices, 20, 20, 4.0, 1.0, 7);
+
+        // Then: Going back is 1/p times as likely as going forward.
+        assert!((0.45..0.55).contains(&return_rate(&unbiased)));
+        assert!((0.75..0.85).contains(&return_rate(&returning)));
+        assert!((0.15..0.25).contains(&return_rate(&exploring)));
+        assert!(unbiased
+            .iter()
+            .flat_map(|walk| walk.windows(2))
+            .all(|step| graph.has_any_edge_unchecked(step[0], step[1])));
+    }
+
+    #[test]
+    fn in_out_parameter_controls_distance() {
+        // Given: Graph
+        //
+        //      a  --  b  --  c
+        //       \    /
+        //        \  /
+        //         d
+        //
+        // From b, after coming from a: Going to d stays around a, going to c moves away from it.
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let vertices: Vec<_> = (0..4).map(|_| graph.add_vertex()).collect();
+        for (src, dst) in [(0, 1), (1, 2), (0, 3), (1, 3)].iter() {
+            graph.add_edge_unchecked(vertices[*src], vertices[*dst], 1.into());
+        }
+
+        // When: Counting the third vertex of walks that start with a, b.
+        let third_vertices = |q: f64| -> (usize, usize) {
+            (0..2000)
+                .map(|seed| {
+                    Node2VecWalk::init(&graph, vertices[0], 1e9, q, Rng::seed_from(seed))
+                        .with_length(2)
+                        .collect::<Vec<usize>>()
+                })
+                .filter(|walk| walk[1] == vertices[1])
+                .fold((0, 0), |(to_c, to_d), walk| {
+                    (
+                        to_c + (walk[2] == vertices[2]) as usize,
+                        to_d + (walk[2] == vertices[3]) as usize,
+                    )
+                })
+        };
+        let (outward_c, outward_d) = third_vertices(0.25);
+        let (inward_c, inward_d) = third_vertices(4.0);
+
+        // Then: Low q prefers c and high q prefers d.
+        assert!(outward_c > 3 * outward_d);
+        assert!(inward_d > 3 * inward_c);
+    }
+}
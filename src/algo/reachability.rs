@@ -0,0 +1,147 @@
+use crate::provide::{Neighbors, Vertices};
+
+/// Checks whether there is a path from one vertex to another.
+///
+/// # Arguments
+/// * `graph`: Graph to search in.
+/// * `src_id`: Id of the vertex the path starts from.
+/// * `dst_id`: Id of the vertex the path ends at.
+///
+/// # Returns
+/// `true` if `dst_id` can be reached from `src_id`. Each vertex can reach itself.
+/// `false` if there is no such path or either of the vertices is not in the graph.
+///
+/// # Complexity
+/// O(|V| + |E|), but the search stops as soon as `dst_id` is reached.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::DiList;
+/// use prepona::graph::ListGraph;
+/// use prepona::algo::has_path;
+///
+/// // Given: Graph
+/// //
+/// //      a  -->  b  -->  c
+/// //
+/// let mut graph = ListGraph::init(DiList::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+///
+/// // Then:
+/// assert!(has_path(&graph, a, c));
+/// assert!(!has_path(&graph, c, a));
+/// ```
+pub fn has_path<G>(graph: &G, src_id: usize, dst_id: usize) -> bool
+where
+    G: Vertices + Neighbors,
+{
+    is_reachable(graph, &[src_id], dst_id)
+}
+
+/// Checks whether a vertex can be reached from any of the given sources.
+///
+/// # Arguments
+/// * `graph`: Graph to search in.
+/// * `src_ids`: Ids of the vertices to start from. Ids that are not in the graph are ignored.
+/// * `dst_id`: Id of the vertex to reach.
+///
+/// # Returns
+/// `true` if `dst_id` is one of the sources or can be reached from one of them.
+///
+/// # Complexity
+/// O(|V| + |E|), but the search stops as soon as `dst_id` is reached.
+pub fn is_reachable<G>(graph: &G, src_ids: &[usize], dst_id: usize) -> bool
+where
+    G: Vertices + Neighbors,
+{
+    if !graph.contains_vertex(dst_id) {
+        return false;
+    }
+
+    let id_map = graph.continuos_id_map();
+    let mut is_visited = vec![false; id_map.len()];
+    let mut stack = vec![];
+
+    for src_id in src_ids.iter().copied() {
+        if graph.contains_vertex(src_id) && !is_visited[id_map.virt_id_of(src_id)] {
+            is_visited[id_map.virt_id_of(src_id)] = true;
+            stack.push(src_id);
+        }
+    }
+
+    while let Some(vertex_id) = stack.pop() {
+        if vertex_id == dst_id {
+            return true;
+        }
+
+        for n_id in graph.neighbors_unchecked(vertex_id) {
+            let n_virt_id = id_map.virt_id_of(n_id);
+            if !is_visited[n_virt_id] {
+                is_visited[n_virt_id] = true;
+                stack.push(n_id);
+            }
+        }
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{ListGraph, MatGraph};
+    use crate::provide::Graph;
+    use crate::storage::{DiList, Mat};
+
+    #[test]
+    fn directed_reachability() {
+        // Given: Graph
+        //
+        //      a  -->  b  <--  c       d
+        //
+        let mut graph = ListGraph::init(DiList::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(c, b, 1.into());
+
+        // Then:
+        assert!(has_path(&graph, a, b));
+        assert!(has_path(&graph, d, d));
+        assert!(!has_path(&graph, a, c));
+        assert!(!has_path(&graph, b, a));
+        assert!(!has_path(&graph, a, d + 1));
+        assert!(is_reachable(&graph, &[d, c], b));
+        assert!(!is_reachable(&graph, &[a, b], c));
+        assert!(!is_reachable(&graph, &[], a));
+    }
+
+    #[test]
+    fn undirected_reachability_with_removed_vertex() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  c       d
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let removed = graph.add_vertex();
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.remove_vertex_unchecked(removed);
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+
+        // Then:
+        assert!(has_path(&graph, c, a));
+        assert!(!has_path(&graph, a, d));
+        assert!(!has_path(&graph, removed, a));
+    }
+}
@@ -0,0 +1,217 @@
+use magnitude::Magnitude;
+use num_traits::{Unsigned, Zero};
+use std::any::Any;
+use std::collections::{HashMap, HashSet};
+use std::marker::PhantomData;
+
+use crate::algo::BestFirst;
+use crate::graph::{subgraph::ShortestPathSubgraph, Edge, EdgeDir};
+use crate::provide::{Edges, Graph, IdMap, Neighbors, Vertices};
+
+/// Finds shortest path between two vertices using [A*](https://en.wikipedia.org/wiki/A*_search_algorithm) algorithm.
+///
+/// A* is a [`BestFirst`](crate::algo::BestFirst) search that prioritizes each vertex by its distance from the source plus a heuristic estimate of its distance to the destination.
+/// Heuristic must never overestimate the real distance(must be admissible), otherwise the found path may not be the shortest one.
+/// A heuristic that always returns zero turns A* into Dijkstra.
+///
+/// Result has the same type as the other shortest path algorithms, but the search stops as soon as the destination is reached.
+/// So the subgraph only contains the path from the source to the destination, and only vertices on that path have a known distance.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+/// use prepona::algo::AStar;
+///
+/// // Given: Graph
+/// //
+/// //      a  --1--  b  --1--  c
+/// //      |                   |
+/// //      '---------3---------'
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(a, c, 3.into());
+///
+/// // When: Searching with a heuristic that knows b is one step away from c.
+/// let heuristic = |vertex_id| if vertex_id == c { 0 } else { 1 };
+/// let sp_subgraph = AStar::init(&graph).execute(&graph, a, c, heuristic);
+///
+/// // Then:
+/// assert_eq!(sp_subgraph.dist(c).unwrap(), 2.into());
+/// assert_eq!(sp_subgraph.path_to(c).unwrap(), vec![a, b, c]);
+/// assert_eq!(sp_subgraph.edges_count(), 2);
+/// ```
+pub struct AStar<W> {
+    id_map: IdMap,
+
+    phantom_w: PhantomData<W>,
+}
+
+impl<W: Copy + Ord + Zero + Any + Unsigned> AStar<W> {
+    /// Initializes the structure.
+    pub fn init<E, Ty, G>(graph: &G) -> Self
+    where
+        E: Edge<W>,
+        Ty: EdgeDir,
+        G: Edges<W, E> + Vertices + Graph<W, E, Ty>,
+    {
+        AStar {
+            id_map: graph.continuos_id_map(),
+
+            phantom_w: PhantomData,
+        }
+    }
+
+    /// Finds shortest path from `src_id` to `dst_id`.
+    ///
+    /// # Arguments
+    /// * `graph`: Graph to search for the shortest path in.
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    /// * `heuristic`: Estimates the distance from a vertex to `dst_id`.
+    ///
+    /// # Returns
+    /// The shortest path from `src_id` to `dst_id` as a subgraph of the original graph.
+    /// If `dst_id` is not reachable from `src_id`, its distance is infinite and the subgraph only contains `src_id`.
+    pub fn execute<'a, E, Ty, G, H>(
+        self,
+        graph: &'a G,
+        src_id: usize,
+        dst_id: usize,
+        heuristic: H,
+    ) -> ShortestPathSubgraph<'a, W, E, Ty, G>
+    where
+        E: Edge<W>,
+        Ty: EdgeDir,
+        G: Edges<W, E> + Neighbors + Vertices + Graph<W, E, Ty>,
+        H: Fn(usize) -> W,
+    {
+        let dst_virt_id = self.id_map.virt_id_of(dst_id);
+
+        let mut listener = ();
+        let mut best_first =
+            BestFirst::init_with_id_map(graph, &mut listener, self.id_map).with_goal(dst_id);
+        best_first.execute(
+            graph,
+            src_id,
+            W::zero().into(),
+            |dist: &Magnitude<W>, edge| *dist + *edge.get_weight(),
+            |vertex_id, dist| *dist + heuristic(vertex_id).into(),
+        );
+
+        let (dists, parents, id_map) = best_first.dissolve();
+
+        // Prefixes of a shortest path are shortest paths too, so distance of each vertex on the path is known.
+        let mut edges = vec![];
+        let mut distance_map = HashMap::new();
+        let mut virt_id = dst_virt_id;
+        match dists[virt_id] {
+            Some(dist) => {
+                distance_map.insert(dst_id, dist);
+                while let Some((parent_virt_id, edge_id)) = parents[virt_id] {
+                    let parent_id = id_map.real_id_of(parent_virt_id);
+                    edges.push((parent_id, id_map.real_id_of(virt_id), edge_id));
+                    distance_map.insert(parent_id, dists[parent_virt_id].unwrap());
+                    virt_id = parent_virt_id;
+                }
+            }
+            None => {
+                distance_map.insert(src_id, W::zero().into());
+                distance_map.insert(dst_id, Magnitude::PosInfinite);
+            }
+        }
+
+        let vertices = edges
+            .iter()
+            .flat_map(|(src_id, dst_id, _)| vec![*src_id, *dst_id])
+            .chain(std::iter::once(src_id))
+            .collect::<HashSet<usize>>();
+
+        ShortestPathSubgraph::init(graph, edges, vertices, distance_map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::Dijkstra;
+    use crate::graph::MatGraph;
+    use crate::storage::DiMat;
+
+    #[test]
+    fn unreachable_destination() {
+        // Given: Graph
+        //
+        //      a  -->  b       c
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+
+        // When:
+        let to_c = AStar::init(&graph).execute(&graph, a, c, |_| 0);
+        let to_a = AStar::init(&graph).execute(&graph, b, a, |_| 0);
+
+        // Then:
+        assert!(to_c.dist(c).unwrap().is_pos_infinite());
+        assert!(to_c.path_to(c).is_none());
+        assert!(to_a.path_to(a).is_none());
+        assert_eq!(to_a.path_to(b).unwrap(), vec![b]);
+        assert_eq!(to_a.vertices(), vec![b]);
+    }
+
+    #[test]
+    fn matches_dijkstra_with_zero_heuristic() {
+        // Given: Graph
+        //          6       1
+        //      a  -->  b  <--  c ---
+        //    1 |       |           |
+        //      |  2 /`````\ 2      |
+        //      |````       ````|   |
+        //      v               v   | 1
+        //      d  ---------->  e --'
+        //              1
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 6.into());
+        graph.add_edge_unchecked(a, d, 1.into());
+        graph.add_edge_unchecked(b, d, 2.into());
+        graph.add_edge_unchecked(b, e, 2.into());
+        graph.add_edge_unchecked(c, b, 1.into());
+        graph.add_edge_unchecked(e, c, 1.into());
+        graph.add_edge_unchecked(d, e, 1.into());
+
+        // When:
+        let sp_subgraph = Dijkstra::init(&graph).execute(&graph, a);
+
+        // Then:
+        for dst_id in [a, b, c, d, e] {
+            let astar = AStar::init(&graph).execute(&graph, a, dst_id, |_| 0);
+            let path = astar.path_to(dst_id).unwrap();
+
+            assert_eq!(astar.dist(dst_id), sp_subgraph.dist(dst_id));
+            assert_eq!(path, sp_subgraph.path_to(dst_id).unwrap());
+            assert_eq!(path.first(), Some(&a));
+            assert_eq!(path.last(), Some(&dst_id));
+        }
+        assert_eq!(
+            AStar::init(&graph)
+                .execute(&graph, a, b, |_| 0)
+                .path_to(b)
+                .unwrap(),
+            vec![a, d, e, c, b]
+        );
+    }
+}
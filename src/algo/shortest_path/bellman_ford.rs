@@ -250,6 +250,8 @@ mod tests {
         assert_eq!(sp_subgraph.distance_to(c).unwrap(), 7.into());
         assert_eq!(sp_subgraph.distance_to(d).unwrap(), 1.into());
         assert_eq!(sp_subgraph.distance_to(e).unwrap(), 2.into());
+        assert_eq!(sp_subgraph.parent(b), Some(d));
+        assert_eq!(sp_subgraph.path_to(c).unwrap(), vec![a, d, e, c]);
     }
 
     #[test]
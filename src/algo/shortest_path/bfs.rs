@@ -0,0 +1,207 @@
+use magnitude::Magnitude;
+use num_traits::{One, Zero};
+use std::any::Any;
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+
+use crate::algo::Orientation;
+use crate::graph::{subgraph::ShortestPathSubgraph, Edge, EdgeDir};
+use crate::provide::{Edges, Graph, IdMap, Neighbors, Vertices};
+
+/// Finds shortest path from a single source to all other vertices, when every edge counts as one regardless of its weight.
+///
+/// A plain breadth-first search is enough in this case, which is faster than [`Dijkstra`](crate::algo::Dijkstra).
+/// Distances are the number of edges on the shortest paths, expressed in the weight type of the graph.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::DiMat;
+/// use prepona::graph::MatGraph;
+/// use prepona::algo::BfsShortestPath;
+///
+/// // Given: Graph
+/// //          6       1
+/// //      a  -->  b  <--  c
+/// //    1 |               ^
+/// //      v               | 1
+/// //      d  ---------->  e
+/// //              1
+/// let mut graph = MatGraph::init(DiMat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// let e = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 6.into());
+/// graph.add_edge_unchecked(a, d, 1.into());
+/// graph.add_edge_unchecked(c, b, 1.into());
+/// graph.add_edge_unchecked(d, e, 1.into());
+/// graph.add_edge_unchecked(e, c, 1.into());
+///
+/// // When:
+/// let sp_subgraph = BfsShortestPath::init(&graph).execute(&graph, a);
+///
+/// // Then: Weight of a -> b does not matter.
+/// assert_eq!(sp_subgraph.dist(b).unwrap(), 1.into());
+/// assert_eq!(sp_subgraph.path_to(b).unwrap(), vec![a, b]);
+/// assert_eq!(sp_subgraph.dist(c).unwrap(), 3.into());
+/// assert_eq!(sp_subgraph.parent(c), Some(e));
+/// ```
+pub struct BfsShortestPath<W> {
+    id_map: IdMap,
+
+    phantom_w: PhantomData<W>,
+}
+
+impl<W: Copy + Ord + Zero + One + Any> BfsShortestPath<W> {
+    /// Initializes the structure.
+    pub fn init<E, Ty, G>(graph: &G) -> Self
+    where
+        E: Edge<W>,
+        Ty: EdgeDir,
+        G: Edges<W, E> + Vertices + Graph<W, E, Ty>,
+    {
+        BfsShortestPath {
+            id_map: graph.continuos_id_map(),
+
+            phantom_w: PhantomData,
+        }
+    }
+
+    /// Finds shortest path from a single source to all other vertices.
+    ///
+    /// # Arguments
+    /// * `graph`: Graph to search for the shortest paths in.
+    /// * `src_id`: Id of the source vertex(Shortest path will be calculated from this vertex to all other vertices)
+    ///
+    /// # Returns
+    /// The shortest path as a subgraph of the original graph.
+    /// You can query shortest path from source to each destination using api provided by `ShortestPathSubgraph`.
+    ///
+    /// # Complexity
+    /// O(|V| + |E|)
+    pub fn execute<'a, E, Ty, G>(
+        self,
+        graph: &'a G,
+        src_id: usize,
+    ) -> ShortestPathSubgraph<'a, W, E, Ty, G>
+    where
+        E: Edge<W>,
+        Ty: EdgeDir,
+        G: Edges<W, E> + Neighbors + Vertices + Graph<W, E, Ty>,
+    {
+        let (dists, parents) = search(graph, src_id, &self.id_map);
+
+        super::sp_subgraph_of(
+            graph,
+            src_id,
+            dists,
+            parents,
+            &self.id_map,
+            Orientation::Original,
+        )
+    }
+}
+
+// Breadth-first search from the source over the given graph, which may be a view of the graph that owns the edges.
+// Returns distance and parent of each vertex, indexed by virtual id. Parent is in the format of (virtual id of parent, id of the edge from parent).
+#[allow(clippy::type_complexity)]
+pub(super) fn search<W, E, G>(
+    graph: &G,
+    src_id: usize,
+    id_map: &IdMap,
+) -> (Vec<Option<Magnitude<W>>>, Vec<Option<(usize, usize)>>)
+where
+    W: Copy + Zero + One + Any,
+    E: Edge<W>,
+    G: Edges<W, E>,
+{
+    let vertex_count = id_map.len();
+    let mut dists: Vec<Option<Magnitude<W>>> = vec![None; vertex_count];
+    let mut parents = vec![None; vertex_count];
+
+    let src_virt_id = id_map.virt_id_of(src_id);
+    dists[src_virt_id] = Some(W::zero().into());
+
+    let mut queue = VecDeque::new();
+    queue.push_back(src_id);
+    while let Some(real_id) = queue.pop_front() {
+        let virt_id = id_map.virt_id_of(real_id);
+        let dist = dists[virt_id].unwrap() + W::one().into();
+
+        for (dst_real_id, edge) in graph.edges_from_unchecked(real_id) {
+            let dst_virt_id = id_map.virt_id_of(dst_real_id);
+            if dists[dst_virt_id].is_none() {
+                dists[dst_virt_id] = Some(dist);
+                parents[dst_virt_id] = Some((virt_id, edge.get_id()));
+                queue.push_back(dst_real_id);
+            }
+        }
+    }
+
+    (dists, parents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::Dijkstra;
+    use crate::graph::MatGraph;
+    use crate::storage::{DiMat, Mat};
+
+    #[test]
+    fn unreachable_vertex() {
+        // Given: Graph
+        //
+        //      a  -->  b       c
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 5.into());
+
+        // When:
+        let sp_subgraph = BfsShortestPath::init(&graph).execute(&graph, b);
+
+        // Then:
+        assert_eq!(sp_subgraph.dist(b).unwrap(), 0.into());
+        assert!(sp_subgraph.dist(a).unwrap().is_pos_infinite());
+        assert!(sp_subgraph.dist(c).unwrap().is_pos_infinite());
+        assert_eq!(sp_subgraph.path_to(b).unwrap(), vec![b]);
+        assert!(sp_subgraph.path_to(a).is_none());
+        assert_eq!(sp_subgraph.vertex_count(), 1);
+    }
+
+    #[test]
+    fn matches_dijkstra_on_unit_weights() {
+        // Given: Undirected grid with unit weights.
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let vertices: Vec<_> = (0..16).map(|_| graph.add_vertex()).collect();
+        for index in 0..16 {
+            if index % 4 != 3 {
+                graph.add_edge_unchecked(vertices[index], vertices[index + 1], 1.into());
+            }
+            if index < 12 {
+                graph.add_edge_unchecked(vertices[index], vertices[index + 4], 1.into());
+            }
+        }
+
+        // When:
+        let bfs = BfsShortestPath::init(&graph).execute(&graph, vertices[0]);
+        let dijkstra = Dijkstra::init(&graph).execute(&graph, vertices[0]);
+
+        // Then:
+        assert_eq!(bfs.edges_count(), 15);
+        for vertex_id in vertices {
+            assert_eq!(bfs.dist(vertex_id), dijkstra.dist(vertex_id));
+
+            let path = bfs.path_to(vertex_id).unwrap();
+            assert_eq!(path.len() - 1, bfs.dist(vertex_id).unwrap().unwrap());
+            assert!(path
+                .windows(2)
+                .all(|pair| graph.has_any_edge_unchecked(pair[0], pair[1])));
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use std::collections::HashSet;
+
+use crate::provide::{Neighbors, Vertices};
+
+/// Groups vertices by their distance, in number of edges, from the closest source.
+///
+/// # Arguments
+/// * `graph`: Graph to search.
+/// * `src_ids`: Id of the source vertices.
+///
+/// # Returns
+/// Layers of the search: First layer contains the sources, and each next layer contains the vertices one edge further away.
+/// Vertices in each layer are in the order they are discovered, and unreachable vertices are not in any layer.
+/// For directed graphs only outgoing edges are followed.
+///
+/// # Complexity
+/// O(|V| + |E|)
+///
+/// # Panics
+/// If any of the sources is not in the graph.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+/// use prepona::algo::bfs_layers;
+///
+/// // Given: Graph
+/// //
+/// //      a  ---  b  ---  c  ---  d      e
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// let _e = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(c, d, 1.into());
+///
+/// // When:
+/// let layers = bfs_layers(&graph, vec![a, d]);
+///
+/// // Then:
+/// assert_eq!(layers, vec![vec![a, d], vec![b, c]]);
+/// ```
+pub fn bfs_layers<G, I>(graph: &G, src_ids: I) -> Vec<Vec<usize>>
+where
+    G: Vertices + Neighbors,
+    I: IntoIterator<Item = usize>,
+{
+    let mut visited = HashSet::new();
+
+    let mut layer = vec![];
+    for src_id in src_ids {
+        assert!(
+            graph.contains_vertex(src_id),
+            "Vertex with id: {} not found",
+            src_id
+        );
+
+        if visited.insert(src_id) {
+            layer.push(src_id);
+        }
+    }
+
+    let mut layers = vec![];
+    while !layer.is_empty() {
+        let mut next_layer = vec![];
+        for vertex_id in &layer {
+            for neighbor_id in graph.neighbors_unchecked(*vertex_id) {
+                if visited.insert(neighbor_id) {
+                    next_layer.push(neighbor_id);
+                }
+            }
+        }
+
+        layers.push(layer);
+        layer = next_layer;
+    }
+
+    layers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::MatGraph;
+    use crate::provide::Graph;
+    use crate::storage::{DiMat, Mat};
+
+    #[test]
+    fn empty_sources() {
+        // Given:
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        graph.add_vertex();
+
+        // When:
+        let layers = bfs_layers(&graph, vec![]);
+
+        // Then:
+        assert!(layers.is_empty());
+    }
+
+    #[test]
+    fn directed_layers() {
+        // Given: Graph
+        //
+        //      a  -->  b  -->  c
+        //      ^       |
+        //      |       v
+        //      '-----  d
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(b, d, 1.into());
+        graph.add_edge_unchecked(d, a, 1.into());
+
+        // When: Source is given twice.
+        let from_b = bfs_layers(&graph, vec![b, b]);
+        let from_c = bfs_layers(&graph, vec![c]);
+
+        // Then:
+        assert_eq!(from_b.len(), 3);
+        assert_eq!(from_b[0], vec![b]);
+        let mut second_layer = from_b[1].clone();
+        second_layer.sort_unstable();
+        assert_eq!(second_layer, vec![c, d]);
+        assert_eq!(from_b[2], vec![a]);
+        assert_eq!(from_c, vec![vec![c]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Vertex with id: 3 not found")]
+    fn missing_source() {
+        // Given:
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        graph.add_vertex();
+
+        // When:
+        bfs_layers(&graph, vec![3]);
+    }
+}
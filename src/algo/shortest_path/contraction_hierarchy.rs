@@ -0,0 +1,610 @@
+use num_traits::Zero;
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::ops::Add;
+
+use crate::graph::{Edge, EdgeDir};
+use crate::provide::{Edges, Graph, IdMap, Vertices};
+
+// Witness searches give up after settling this many vertices. Missing a witness only adds an unnecessary shortcut.
+const WITNESS_SETTLE_LIMIT: usize = 64;
+
+// Entry of a search queue, ordered so the max heap pops the closest vertex first.
+struct Queued<T> {
+    dist: T,
+    vertex_id: usize,
+}
+
+impl<T: PartialOrd> PartialEq for Queued<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T: PartialOrd> Eq for Queued<T> {}
+
+impl<T: PartialOrd> PartialOrd for Queued<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: PartialOrd> Ord for Queued<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .dist
+            .partial_cmp(&self.dist)
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+// Graph that vertices get contracted in, indexed by virtual id.
+// Each arc keeps the lowest weight between its end points, and the vertex it skips if it's a shortcut.
+struct Overlay<T> {
+    out_arcs: Vec<HashMap<usize, T>>,
+    in_arcs: Vec<HashMap<usize, T>>,
+    middles: HashMap<(usize, usize), usize>,
+    is_contracted: Vec<bool>,
+}
+
+impl<T: Copy + Zero + Add<Output = T> + PartialOrd> Overlay<T> {
+    fn insert_arc(&mut self, src_id: usize, dst_id: usize, weight: T, middle_id: Option<usize>) {
+        if let Some(old_weight) = self.out_arcs[src_id].get(&dst_id) {
+            if *old_weight <= weight {
+                return;
+            }
+        }
+
+        self.out_arcs[src_id].insert(dst_id, weight);
+        self.in_arcs[dst_id].insert(src_id, weight);
+        match middle_id {
+            Some(middle_id) => self.middles.insert((src_id, dst_id), middle_id),
+            None => self.middles.remove(&(src_id, dst_id)),
+        };
+    }
+
+    // Distances from `src_id` found without passing through `excluded_id` or contracted vertices, up to `limit`.
+    fn witness_dists(&self, src_id: usize, excluded_id: usize, limit: T) -> HashMap<usize, T> {
+        let mut dists = HashMap::new();
+        let mut settled = HashSet::new();
+        let mut heap = BinaryHeap::new();
+        dists.insert(src_id, T::zero());
+        heap.push(Queued {
+            dist: T::zero(),
+            vertex_id: src_id,
+        });
+
+        while let Some(Queued { dist, vertex_id }) = heap.pop() {
+            if dist > limit || settled.len() == WITNESS_SETTLE_LIMIT {
+                break;
+            }
+            if !settled.insert(vertex_id) {
+                continue;
+            }
+
+            for (dst_id, weight) in &self.out_arcs[vertex_id] {
+                if *dst_id == excluded_id || self.is_contracted[*dst_id] {
+                    continue;
+                }
+
+                let alt = dist + *weight;
+                if dists.get(dst_id).is_none_or(|dst_dist| alt < *dst_dist) {
+                    dists.insert(*dst_id, alt);
+                    heap.push(Queued {
+                        dist: alt,
+                        vertex_id: *dst_id,
+                    });
+                }
+            }
+        }
+
+        dists
+    }
+
+    // Shortcuts in the format of (`src_id`, `dst_id`, `weight`) needed to keep distances if `vertex_id` gets contracted.
+    fn shortcuts_of(&self, vertex_id: usize) -> Vec<(usize, usize, T)> {
+        let mut shortcuts = vec![];
+
+        for (src_id, in_weight) in &self.in_arcs[vertex_id] {
+            if self.is_contracted[*src_id] {
+                continue;
+            }
+
+            let targets: Vec<(usize, T)> = self.out_arcs[vertex_id]
+                .iter()
+                .filter(|(dst_id, _)| !self.is_contracted[**dst_id] && **dst_id != *src_id)
+                .map(|(dst_id, out_weight)| (*dst_id, *in_weight + *out_weight))
+                .collect();
+
+            let limit = match targets
+                .iter()
+                .map(|(_, via_weight)| *via_weight)
+                .reduce(|max, weight| if weight > max { weight } else { max })
+            {
+                Some(limit) => limit,
+                None => continue,
+            };
+
+            let witness_dists = self.witness_dists(*src_id, vertex_id, limit);
+            for (dst_id, via_weight) in targets {
+                if witness_dists
+                    .get(&dst_id)
+                    .is_none_or(|witness_dist| *witness_dist > via_weight)
+                {
+                    shortcuts.push((*src_id, dst_id, via_weight));
+                }
+            }
+        }
+
+        shortcuts
+    }
+
+    fn remaining_degree_of(&self, vertex_id: usize) -> usize {
+        self.in_arcs[vertex_id]
+            .keys()
+            .chain(self.out_arcs[vertex_id].keys())
+            .filter(|other_id| !self.is_contracted[**other_id])
+            .count()
+    }
+}
+
+/// Preprocessed graph for answering many shortest path queries much faster than running [`Dijkstra`](crate::algo::Dijkstra) for each of them.
+///
+/// Vertices are contracted one by one, in order of how many shortcuts their removal needs, and each contraction adds shortcut edges
+/// that keep the distances between the remaining vertices. Each query then runs two small searches, one from each end, that only go up in the contraction order
+/// and meet at the most important vertex of the shortest path.
+/// It works best for sparse graphs with a hierarchical structure, like road networks.
+///
+/// The structure is a snapshot of the graph: Changes to the graph after preprocessing are not reflected in the queries.
+///
+/// ## Generic Parameters
+/// * `T`: **T**ype of the weights returned by the weight function.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+/// use prepona::algo::ContractionHierarchy;
+///
+/// // Given: Graph
+/// //
+/// //      a  --1--  b  --1--  c  --1--  d
+/// //      |                             |
+/// //      '--------------5--------------'
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(c, d, 1.into());
+/// graph.add_edge_unchecked(a, d, 5.into());
+///
+/// // When:
+/// let ch = ContractionHierarchy::preprocess(&graph, |edge: &DefaultEdge<usize>| edge.get_weight().unwrap());
+///
+/// // Then:
+/// assert_eq!(ch.distance(a, d), Some(3));
+/// assert_eq!(ch.path(a, d), Some((3, vec![a, b, c, d])));
+/// ```
+pub struct ContractionHierarchy<T> {
+    id_map: IdMap,
+    up_out_arcs: Vec<Vec<(usize, T)>>,
+    up_in_arcs: Vec<Vec<(usize, T)>>,
+    middles: HashMap<(usize, usize), usize>,
+}
+
+impl<T: Copy + Zero + Add<Output = T> + PartialOrd> ContractionHierarchy<T> {
+    /// Contracts the vertices of `graph`.
+    ///
+    /// # Arguments
+    /// * `graph`: Graph to preprocess.
+    /// * `weight_fn`: Receives an edge and returns its non negative weight, like `|edge| edge.get_weight().unwrap()` or `|_| 1`.
+    ///
+    /// # Returns
+    /// The preprocessed graph. Self loops are ignored and only the lightest of parallel edges is kept.
+    ///
+    /// # Panics
+    /// If `weight_fn` returns a negative weight.
+    pub fn preprocess<W, E, Ty, G, F>(graph: &G, weight_fn: F) -> Self
+    where
+        E: Edge<W>,
+        Ty: EdgeDir,
+        G: Edges<W, E> + Vertices + Graph<W, E, Ty>,
+        F: Fn(&E) -> T,
+    {
+        let id_map = graph.continuos_id_map();
+        let vertex_count = id_map.len();
+
+        let mut overlay = Overlay {
+            out_arcs: vec![HashMap::new(); vertex_count],
+            in_arcs: vec![HashMap::new(); vertex_count],
+            middles: HashMap::new(),
+            is_contracted: vec![false; vertex_count],
+        };
+        for src_real_id in graph.vertices() {
+            let src_id = id_map.virt_id_of(src_real_id);
+
+            for (dst_real_id, edge) in graph.edges_from_unchecked(src_real_id) {
+                let weight = weight_fn(edge);
+                assert!(weight >= T::zero(), "Weights must not be negative");
+
+                let dst_id = id_map.virt_id_of(dst_real_id);
+                if src_id != dst_id {
+                    overlay.insert_arc(src_id, dst_id, weight, None);
+                }
+            }
+        }
+
+        // Priority of a vertex is its edge difference plus its contracted neighbors, which spreads contractions across the graph.
+        // Priorities only grow as neighbors get contracted, so they are updated lazily when popped.
+        let mut contracted_neighbors = vec![0; vertex_count];
+        let priority_of = |overlay: &Overlay<T>,
+                           shortcut_count: usize,
+                           vertex_id: usize,
+                           contracted_neighbors: usize| {
+            shortcut_count as isize - overlay.remaining_degree_of(vertex_id) as isize
+                + contracted_neighbors as isize
+        };
+
+        let mut heap: BinaryHeap<Reverse<(isize, usize)>> = (0..vertex_count)
+            .map(|vertex_id| {
+                let shortcut_count = overlay.shortcuts_of(vertex_id).len();
+                Reverse((
+                    priority_of(&overlay, shortcut_count, vertex_id, 0),
+                    vertex_id,
+                ))
+            })
+            .collect();
+
+        let mut ranks = vec![0; vertex_count];
+        let mut next_rank = 0;
+        while let Some(Reverse((_, vertex_id))) = heap.pop() {
+            if overlay.is_contracted[vertex_id] {
+                continue;
+            }
+
+            let shortcuts = overlay.shortcuts_of(vertex_id);
+            let priority = priority_of(
+                &overlay,
+                shortcuts.len(),
+                vertex_id,
+                contracted_neighbors[vertex_id],
+            );
+            if let Some(Reverse((next_priority, _))) = heap.peek() {
+                if priority > *next_priority {
+                    heap.push(Reverse((priority, vertex_id)));
+                    continue;
+                }
+            }
+
+            for (src_id, dst_id, weight) in shortcuts {
+                overlay.insert_arc(src_id, dst_id, weight, Some(vertex_id));
+            }
+
+            overlay.is_contracted[vertex_id] = true;
+            ranks[vertex_id] = next_rank;
+            next_rank += 1;
+
+            let neighbors: HashSet<usize> = overlay.in_arcs[vertex_id]
+                .keys()
+                .chain(overlay.out_arcs[vertex_id].keys())
+                .copied()
+                .collect();
+            for neighbor_id in neighbors {
+                contracted_neighbors[neighbor_id] += 1;
+            }
+        }
+
+        // Each arc goes up from its lower end, so the forward search uses it from its source and the backward search from its destination.
+        let mut up_out_arcs = vec![vec![]; vertex_count];
+        let mut up_in_arcs = vec![vec![]; vertex_count];
+        for (src_id, arcs) in overlay.out_arcs.iter().enumerate() {
+            for (dst_id, weight) in arcs {
+                if ranks[src_id] < ranks[*dst_id] {
+                    up_out_arcs[src_id].push((*dst_id, *weight));
+                } else {
+                    up_in_arcs[*dst_id].push((src_id, *weight));
+                }
+            }
+        }
+
+        ContractionHierarchy {
+            id_map,
+            up_out_arcs,
+            up_in_arcs,
+            middles: overlay.middles,
+        }
+    }
+
+    /// # Returns
+    /// Number of shortcuts added during preprocessing.
+    pub fn shortcut_count(&self) -> usize {
+        self.middles.len()
+    }
+
+    fn virt_id_of(&self, real_id: usize) -> usize {
+        self.id_map
+            .try_virt_id_of(real_id)
+            .unwrap_or_else(|| panic!("Vertex with id: {} not found", real_id))
+    }
+
+    // Distance and the vertex where forward and backward searches meet, with the parent of each vertex in both searches.
+    #[allow(clippy::type_complexity)]
+    fn search(
+        &self,
+        src_id: usize,
+        dst_id: usize,
+    ) -> (Option<(T, usize)>, [HashMap<usize, usize>; 2]) {
+        let mut dists = [HashMap::new(), HashMap::new()];
+        let mut parents = [HashMap::new(), HashMap::new()];
+        let mut settled = [HashSet::new(), HashSet::new()];
+        let mut heaps = [BinaryHeap::new(), BinaryHeap::new()];
+        for (direction, vertex_id) in [src_id, dst_id].iter().enumerate() {
+            dists[direction].insert(*vertex_id, T::zero());
+            heaps[direction].push(Queued {
+                dist: T::zero(),
+                vertex_id: *vertex_id,
+            });
+        }
+
+        let mut best: Option<(T, usize)> = None;
+        loop {
+            // Continue in the direction with the closer vertex, until neither can improve the best distance.
+            let direction = match (
+                heaps[0]
+                    .peek()
+                    .filter(|top| best.is_none_or(|(dist, _)| top.dist < dist)),
+                heaps[1]
+                    .peek()
+                    .filter(|top| best.is_none_or(|(dist, _)| top.dist < dist)),
+            ) {
+                (None, None) => break,
+                (Some(_), None) => 0,
+                (None, Some(_)) => 1,
+                (Some(forward), Some(backward)) => usize::from(backward.dist < forward.dist),
+            };
+
+            let Queued { dist, vertex_id } = heaps[direction].pop().unwrap();
+            if !settled[direction].insert(vertex_id) {
+                continue;
+            }
+
+            if let Some(other_dist) = dists[1 - direction].get(&vertex_id) {
+                let total = dist + *other_dist;
+                if best.is_none_or(|(best_dist, _)| total < best_dist) {
+                    best = Some((total, vertex_id));
+                }
+            }
+
+            let arcs = if direction == 0 {
+                &self.up_out_arcs[vertex_id]
+            } else {
+                &self.up_in_arcs[vertex_id]
+            };
+            for (other_id, weight) in arcs {
+                let alt = dist + *weight;
+                if dists[direction]
+                    .get(other_id)
+                    .is_none_or(|other_dist| alt < *other_dist)
+                {
+                    dists[direction].insert(*other_id, alt);
+                    parents[direction].insert(*other_id, vertex_id);
+                    heaps[direction].push(Queued {
+                        dist: alt,
+                        vertex_id: *other_id,
+                    });
+                }
+            }
+        }
+
+        (best, parents)
+    }
+
+    // Appends the vertices that the arc from `src_id` to `dst_id` passes through, excluding `src_id`.
+    fn unpack(&self, src_id: usize, dst_id: usize, path: &mut Vec<usize>) {
+        match self.middles.get(&(src_id, dst_id)) {
+            Some(middle_id) => {
+                self.unpack(src_id, *middle_id, path);
+                self.unpack(*middle_id, dst_id, path);
+            }
+            None => path.push(self.id_map.real_id_of(dst_id)),
+        }
+    }
+
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    ///
+    /// # Returns
+    /// * `Some`: Containing the distance from source to destination.
+    /// * `None`: If destination is not reachable from source.
+    ///
+    /// # Panics
+    /// If either of the vertices was not in the graph during preprocessing.
+    pub fn distance(&self, src_id: usize, dst_id: usize) -> Option<T> {
+        let (best, _) = self.search(self.virt_id_of(src_id), self.virt_id_of(dst_id));
+
+        best.map(|(dist, _)| dist)
+    }
+
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    ///
+    /// # Returns
+    /// * `Some`: Containing the distance from source to destination and the ids of vertices on the shortest path, including both ends.
+    /// * `None`: If destination is not reachable from source.
+    ///
+    /// # Panics
+    /// If either of the vertices was not in the graph during preprocessing.
+    pub fn path(&self, src_id: usize, dst_id: usize) -> Option<(T, Vec<usize>)> {
+        let (src_id, dst_id) = (self.virt_id_of(src_id), self.virt_id_of(dst_id));
+        let (best, parents) = self.search(src_id, dst_id);
+        let (dist, meeting_id) = best?;
+
+        // Arcs of the forward search lead from source up to the meeting vertex, and arcs of the backward search lead from there down to destination.
+        let mut up_ids = vec![meeting_id];
+        while let Some(parent_id) = parents[0].get(up_ids.last().unwrap()) {
+            up_ids.push(*parent_id);
+        }
+        up_ids.reverse();
+
+        let mut down_ids = vec![meeting_id];
+        while let Some(parent_id) = parents[1].get(down_ids.last().unwrap()) {
+            down_ids.push(*parent_id);
+        }
+
+        let mut path = vec![self.id_map.real_id_of(src_id)];
+        for arc in up_ids.windows(2).chain(down_ids.windows(2)) {
+            self.unpack(arc[0], arc[1], &mut path);
+        }
+
+        Some((dist, path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::Dijkstra;
+    use crate::graph::{DefaultEdge, MultiListGraph};
+    use crate::misc::rng::Rng;
+    use crate::provide::Neighbors;
+    use crate::storage::{DiList, List};
+
+    fn weight_of(edge: &DefaultEdge<usize>) -> usize {
+        edge.get_weight().unwrap()
+    }
+
+    fn assert_matches_dijkstra<Ty, G>(graph: &G)
+    where
+        Ty: EdgeDir,
+        G: Edges<usize, DefaultEdge<usize>>
+            + Vertices
+            + Neighbors
+            + Graph<usize, DefaultEdge<usize>, Ty>,
+    {
+        let ch = ContractionHierarchy::preprocess(graph, weight_of);
+
+        for src_id in graph.vertices() {
+            let sp_subgraph = Dijkstra::init(graph).execute(graph, src_id);
+
+            for dst_id in graph.vertices() {
+                let expected = sp_subgraph
+                    .dist(dst_id)
+                    .filter(|dist| dist.is_finite())
+                    .map(|dist| dist.unwrap());
+
+                assert_eq!(ch.distance(src_id, dst_id), expected);
+
+                if let Some((dist, path)) = ch.path(src_id, dst_id) {
+                    assert_eq!(path.first(), Some(&src_id));
+                    assert_eq!(path.last(), Some(&dst_id));
+
+                    let path_weight: usize = path
+                        .windows(2)
+                        .map(|hop| {
+                            graph
+                                .edges_between_unchecked(hop[0], hop[1])
+                                .into_iter()
+                                .map(weight_of)
+                                .min()
+                                .unwrap()
+                        })
+                        .sum();
+                    assert_eq!(path_weight, dist);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn empty_and_single_vertex_graphs() {
+        // Given:
+        let mut graph = MultiListGraph::init(List::<usize>::init());
+        let ch = ContractionHierarchy::preprocess(&graph, weight_of);
+        assert_eq!(ch.shortcut_count(), 0);
+
+        // When:
+        let a = graph.add_vertex();
+        let ch = ContractionHierarchy::preprocess(&graph, weight_of);
+
+        // Then:
+        assert_eq!(ch.distance(a, a), Some(0));
+        assert_eq!(ch.path(a, a), Some((0, vec![a])));
+    }
+
+    #[test]
+    fn random_undirected_graphs() {
+        let mut rng = Rng::seed_from(3157);
+
+        for _ in 0..10 {
+            // Given: Random graph with parallel edges, loops and a removed vertex.
+            let mut graph = MultiListGraph::init(List::<usize>::init());
+            for _ in 0..25 {
+                graph.add_vertex();
+            }
+            for _ in 0..50 {
+                let src_id = rng.next_below(25);
+                let dst_id = rng.next_below(25);
+                graph.add_edge_unchecked(src_id, dst_id, (1 + rng.next_below(9)).into());
+            }
+            graph.remove_vertex_unchecked(rng.next_below(25));
+
+            // Then:
+            assert_matches_dijkstra(&graph);
+        }
+    }
+
+    #[test]
+    fn random_directed_graphs() {
+        let mut rng = Rng::seed_from(7513);
+
+        for _ in 0..10 {
+            // Given: Random graph with zero weights.
+            let mut graph = MultiListGraph::init(DiList::<usize>::init());
+            for _ in 0..25 {
+                graph.add_vertex();
+            }
+            for _ in 0..70 {
+                let src_id = rng.next_below(25);
+                let dst_id = rng.next_below(25);
+                graph.add_edge_unchecked(src_id, dst_id, rng.next_below(9).into());
+            }
+
+            // Then:
+            assert_matches_dijkstra(&graph);
+        }
+    }
+
+    #[test]
+    fn grid_needs_shortcuts() {
+        // Given: 10 x 10 grid.
+        let mut graph = MultiListGraph::init(List::<usize>::init());
+        for _ in 0..100 {
+            graph.add_vertex();
+        }
+        for row in 0..10 {
+            for col in 0..10 {
+                if col + 1 < 10 {
+                    graph.add_edge_unchecked(row * 10 + col, row * 10 + col + 1, 1.into());
+                }
+                if row + 1 < 10 {
+                    graph.add_edge_unchecked(row * 10 + col, (row + 1) * 10 + col, 1.into());
+                }
+            }
+        }
+
+        // When:
+        let ch = ContractionHierarchy::preprocess(&graph, weight_of);
+
+        // Then:
+        assert!(ch.shortcut_count() > 0);
+        assert_eq!(ch.distance(0, 99), Some(18));
+        assert_eq!(ch.path(0, 99).unwrap().1.len(), 19);
+    }
+}
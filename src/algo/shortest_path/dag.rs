@@ -0,0 +1,323 @@
+use anyhow::Result;
+use magnitude::Magnitude;
+use num_traits::Zero;
+use std::any::Any;
+use std::ops::Add;
+
+use crate::algo::{Error, Orientation, TopologicalSort};
+use crate::graph::subgraph::ShortestPathSubgraph;
+use crate::graph::{DirectedEdge, Edge};
+use crate::provide::{Edges, Graph, IdMap, Neighbors, Vertices};
+
+/// Finds shortest path from a single source to all other vertices of a directed acyclic graph.
+///
+/// Vertices are relaxed once in topological order, which is much faster than [`BellmanFord`](crate::algo::BellmanFord) and unlike [`Dijkstra`](crate::algo::Dijkstra), allows negative weights.
+///
+/// # Arguments
+/// * `graph`: Directed acyclic graph to search for the shortest paths in.
+/// * `src_id`: Id of the source vertex.
+///
+/// # Returns
+/// * `Ok`: The shortest path as a subgraph of the original graph.
+/// * `Err`: If graph contains a cycle.
+///
+/// # Complexity
+/// O(|V| + |E|)
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::DiMat;
+/// use prepona::graph::MatGraph;
+/// use prepona::algo::dag_shortest_path;
+///
+/// // Given: Graph
+/// //
+/// //      a  --2-->  b  --(-3)-->  c
+/// //      |                        ^
+/// //      '------------1-----------'
+/// //
+/// let mut graph = MatGraph::init(DiMat::<isize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 2.into());
+/// graph.add_edge_unchecked(b, c, (-3).into());
+/// graph.add_edge_unchecked(a, c, 1.into());
+///
+/// // When:
+/// let sp_subgraph = dag_shortest_path(&graph, a).unwrap();
+///
+/// // Then:
+/// assert_eq!(sp_subgraph.dist(c).unwrap(), (-1).into());
+/// assert_eq!(sp_subgraph.path_to(c).unwrap(), vec![a, b, c]);
+/// ```
+pub fn dag_shortest_path<'a, W, E, G>(
+    graph: &'a G,
+    src_id: usize,
+) -> Result<ShortestPathSubgraph<'a, W, E, DirectedEdge, G>>
+where
+    W: Copy + Zero + Any + Ord,
+    E: Edge<W>,
+    G: Edges<W, E> + Neighbors + Vertices + Graph<W, E, DirectedEdge>,
+{
+    let (sorted_vertex_ids, id_map) = topological_order(graph)?;
+
+    let vertex_count = sorted_vertex_ids.len();
+    let mut dists: Vec<Option<Magnitude<W>>> = vec![None; vertex_count];
+    let mut parents = vec![None; vertex_count];
+    dists[id_map.virt_id_of(src_id)] = Some(W::zero().into());
+
+    // Vertices before the source in topological order are not reachable from it, so they're skipped by the distance check.
+    for real_id in sorted_vertex_ids {
+        let virt_id = id_map.virt_id_of(real_id);
+        let dist = match dists[virt_id] {
+            Some(dist) => dist,
+            None => continue,
+        };
+
+        for (dst_real_id, edge) in graph.edges_from_unchecked(real_id) {
+            let dst_virt_id = id_map.virt_id_of(dst_real_id);
+            let alt = dist + *edge.get_weight();
+
+            if dists[dst_virt_id].is_none_or(|dst_dist| alt < dst_dist) {
+                dists[dst_virt_id] = Some(alt);
+                parents[dst_virt_id] = Some((virt_id, edge.get_id()));
+            }
+        }
+    }
+
+    Ok(super::sp_subgraph_of(
+        graph,
+        src_id,
+        dists,
+        parents,
+        &id_map,
+        Orientation::Original,
+    ))
+}
+
+/// Finds the path with maximum weight in a directed acyclic graph, among paths between any pair of vertices.
+///
+/// With non negative weights it's the longest path of the graph, and with unit weights the one with most edges.
+/// Weights may be negative too, in which case the path may be a single vertex with weight zero.
+///
+/// # Arguments
+/// * `graph`: Directed acyclic graph to search for the path in.
+/// * `weight_fn`: Receives an edge and returns its weight, like `|edge| edge.get_weight().unwrap()` or `|_| 1`.
+///
+/// # Returns
+/// * `Ok`: Containing weight of the path and ids of the vertices on it, including both ends. Both are empty for an empty graph.
+/// * `Err`: If graph contains a cycle.
+///
+/// # Complexity
+/// O(|V| + |E|)
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::DiMat;
+/// use prepona::graph::MatGraph;
+/// use prepona::algo::dag_longest_path;
+///
+/// // Given: Graph
+/// //
+/// //      a  --1-->  b  --1-->  c  --1-->  d
+/// //      |                                ^
+/// //      '----------------5---------------'
+/// //
+/// let mut graph = MatGraph::init(DiMat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(c, d, 1.into());
+/// graph.add_edge_unchecked(a, d, 5.into());
+///
+/// // When:
+/// let heaviest = dag_longest_path(&graph, |edge: &DefaultEdge<usize>| edge.get_weight().unwrap()).unwrap();
+/// let longest = dag_longest_path(&graph, |_: &DefaultEdge<usize>| 1).unwrap();
+///
+/// // Then:
+/// assert_eq!(heaviest, (5, vec![a, d]));
+/// assert_eq!(longest, (3, vec![a, b, c, d]));
+/// ```
+pub fn dag_longest_path<W, E, G, T, F>(graph: &G, weight_fn: F) -> Result<(T, Vec<usize>)>
+where
+    E: Edge<W>,
+    G: Edges<W, E> + Neighbors + Vertices + Graph<W, E, DirectedEdge>,
+    T: Copy + Zero + Add<Output = T> + PartialOrd,
+    F: Fn(&E) -> T,
+{
+    let (sorted_vertex_ids, id_map) = topological_order(graph)?;
+
+    // Weight of the heaviest path that ends at each vertex, which may start at the vertex itself.
+    let vertex_count = sorted_vertex_ids.len();
+    let mut weights = vec![T::zero(); vertex_count];
+    let mut parents = vec![None; vertex_count];
+
+    for real_id in sorted_vertex_ids.iter().copied() {
+        let virt_id = id_map.virt_id_of(real_id);
+
+        for (dst_real_id, edge) in graph.edges_from_unchecked(real_id) {
+            let dst_virt_id = id_map.virt_id_of(dst_real_id);
+            let alt = weights[virt_id] + weight_fn(edge);
+
+            if alt > weights[dst_virt_id] {
+                weights[dst_virt_id] = alt;
+                parents[dst_virt_id] = Some(virt_id);
+            }
+        }
+    }
+
+    let end_virt_id =
+        match (0..vertex_count).fold(None, |best: Option<usize>, virt_id| match best {
+            Some(best_virt_id) if weights[best_virt_id] >= weights[virt_id] => best,
+            _ => Some(virt_id),
+        }) {
+            Some(end_virt_id) => end_virt_id,
+            None => return Ok((T::zero(), vec![])),
+        };
+
+    let mut path = vec![id_map.real_id_of(end_virt_id)];
+    let mut virt_id = end_virt_id;
+    while let Some(parent_virt_id) = parents[virt_id] {
+        path.push(id_map.real_id_of(parent_virt_id));
+        virt_id = parent_virt_id;
+    }
+    path.reverse();
+
+    Ok((weights[end_virt_id], path))
+}
+
+// Topological order of the vertices and the id map of the graph.
+// Topological sort does not detect cycles itself, so order of end points of every edge is checked.
+fn topological_order<W, E, G>(graph: &G) -> Result<(Vec<usize>, IdMap)>
+where
+    E: Edge<W>,
+    G: Edges<W, E> + Neighbors + Vertices + Graph<W, E, DirectedEdge>,
+{
+    let sorted_vertex_ids = TopologicalSort::init().execute(graph);
+    let id_map = graph.continuos_id_map();
+
+    let mut position = vec![0; sorted_vertex_ids.len()];
+    for (index, real_id) in sorted_vertex_ids.iter().enumerate() {
+        position[id_map.virt_id_of(*real_id)] = index;
+    }
+
+    for (src_id, dst_id, _) in graph.edges() {
+        if position[id_map.virt_id_of(src_id)] >= position[id_map.virt_id_of(dst_id)] {
+            Err(Error::new_cd())?
+        }
+    }
+
+    Ok((sorted_vertex_ids, id_map))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::FloydWarshall;
+    use crate::graph::{DefaultEdge, ListGraph, MatGraph};
+    use crate::storage::{DiList, DiMat};
+
+    #[test]
+    fn cycle_is_rejected() {
+        // Given: Graph
+        //
+        //      a  -->  b  -->  c
+        //      ^               |
+        //      '---------------'
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(c, a, 1.into());
+
+        // Then:
+        assert!(dag_shortest_path(&graph, a).is_err());
+        assert!(dag_longest_path(&graph, |_: &DefaultEdge<usize>| 1).is_err());
+    }
+
+    #[test]
+    fn matches_floyd_warshall() {
+        // Given: Layered graph with negative weights and a removed vertex.
+        let mut graph = ListGraph::init(DiList::<isize>::init());
+        let removed = graph.add_vertex();
+        let vertices: Vec<_> = (0..9).map(|_| graph.add_vertex()).collect();
+        graph.remove_vertex_unchecked(removed);
+        for layer in 0..2 {
+            for src in 0..3 {
+                for dst in 0..3 {
+                    let weight = (src as isize * 3 + dst as isize) % 5 - 2;
+                    graph.add_edge_unchecked(
+                        vertices[layer * 3 + src],
+                        vertices[layer * 3 + 3 + dst],
+                        weight.into(),
+                    );
+                }
+            }
+        }
+
+        // When:
+        let dag = dag_shortest_path(&graph, vertices[1]).unwrap();
+        let floyd_warshall = FloydWarshall::init().execute_with_paths(&graph).unwrap();
+
+        // Then:
+        for vertex_id in vertices.iter().copied() {
+            let dist = dag.dist(vertex_id).unwrap();
+            if dist.is_finite() {
+                assert_eq!(
+                    dist,
+                    floyd_warshall.distance(vertices[1], vertex_id).unwrap()
+                );
+                assert_eq!(dag.path_to(vertex_id).unwrap()[0], vertices[1]);
+            } else {
+                assert!(floyd_warshall
+                    .distance(vertices[1], vertex_id)
+                    .unwrap()
+                    .is_pos_infinite());
+            }
+        }
+        assert!(dag.dist(vertices[0]).unwrap().is_pos_infinite());
+    }
+
+    #[test]
+    fn longest_path_with_negative_weights() {
+        // Given: Graph
+        //
+        //      a  --(-1)-->  b  --3-->  c  --(-5)-->  d
+        //
+        let mut graph = MatGraph::init(DiMat::<isize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, (-1).into());
+        graph.add_edge_unchecked(b, c, 3.into());
+        graph.add_edge_unchecked(c, d, (-5).into());
+
+        // When:
+        let (weight, path) = dag_longest_path(&graph, |edge: &DefaultEdge<isize>| {
+            edge.get_weight().unwrap()
+        })
+        .unwrap();
+
+        // Then: Negative edges at the ends are left out.
+        assert_eq!(weight, 3);
+        assert_eq!(path, vec![b, c]);
+        assert_eq!(
+            dag_longest_path(
+                &MatGraph::init(DiMat::<isize>::init()),
+                |_: &DefaultEdge<isize>| 1
+            )
+            .unwrap(),
+            (0, vec![])
+        );
+    }
+}
@@ -0,0 +1,299 @@
+use magnitude::Magnitude;
+use num_traits::{ToPrimitive, Unsigned, Zero};
+use std::any::Any;
+use std::collections::HashMap;
+use std::ops::Div;
+
+use crate::graph::{Edge, EdgeDir};
+use crate::provide::{Edges, Graph, IdMap, Vertices};
+
+/// Finds shortest path from a single source to all other vertices using [delta-stepping](https://doi.org/10.1016/S0196-6774(03)00076-2) algorithm.
+///
+/// Vertices are kept in buckets of width `delta` based on their tentative distance. Buckets are processed in increasing order
+/// and all vertices of a bucket are relaxed together, first through their light edges(weight <= `delta`) until the bucket stays empty
+/// and then through their heavy edges. Relaxation requests of vertices in the same bucket are independent,
+/// so with the `parallel` feature enabled, `par_execute` computes them using multiple threads.
+///
+/// With `delta` equal to the smallest edge weight, this algorithm behaves like Dijkstra and with a very large `delta`, like Bellman-Ford.
+/// A good choice for graphs with random weights is the maximum edge weight divided by the average degree.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::DiList;
+/// use prepona::graph::ListGraph;
+/// use prepona::algo::DeltaStepping;
+///
+/// // Given: Graph
+/// //
+/// //      a  --1-->  b  --1-->  c
+/// //      |                     ^
+/// //      '---------5-----------'
+/// //
+/// let mut graph = ListGraph::init(DiList::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(a, c, 5.into());
+///
+/// // When:
+/// let distances = DeltaStepping::init(&graph, 2).execute(&graph, a);
+///
+/// // Then:
+/// assert_eq!(distances[&a], 0.into());
+/// assert_eq!(distances[&b], 1.into());
+/// assert_eq!(distances[&c], 2.into());
+/// ```
+pub struct DeltaStepping<W> {
+    delta: W,
+    id_map: IdMap,
+    dist: Vec<Magnitude<W>>,
+    buckets: Vec<Vec<usize>>,
+}
+
+impl<W: Copy + Ord + Zero + Any + Unsigned + Div<Output = W> + ToPrimitive> DeltaStepping<W> {
+    /// Initializes the structure.
+    ///
+    /// # Arguments
+    /// * `graph`: Graph to search for the shortest paths in.
+    /// * `delta`: Width of each bucket.
+    ///
+    /// # Panics
+    /// If `delta` is zero.
+    pub fn init<E, Ty, G>(graph: &G, delta: W) -> Self
+    where
+        E: Edge<W>,
+        Ty: EdgeDir,
+        G: Edges<W, E> + Vertices + Graph<W, E, Ty>,
+    {
+        assert!(!delta.is_zero(), "Delta must be greater than zero");
+
+        DeltaStepping {
+            delta,
+            id_map: graph.continuos_id_map(),
+            dist: vec![Magnitude::PosInfinite; graph.vertex_count()],
+            buckets: vec![],
+        }
+    }
+
+    /// Finds shortest path from a single source to all other vertices.
+    ///
+    /// # Arguments
+    /// * `graph`: Graph to search for the shortest paths in.
+    /// * `src_id`: Id of the source vertex.
+    ///
+    /// # Returns
+    /// Distance of each vertex from the source, `PosInfinite` for unreachable vertices.
+    pub fn execute<E, Ty, G>(self, graph: &G, src_id: usize) -> HashMap<usize, Magnitude<W>>
+    where
+        E: Edge<W>,
+        Ty: EdgeDir,
+        G: Edges<W, E> + Vertices + Graph<W, E, Ty>,
+    {
+        let delta = self.delta;
+
+        self.execute_with(src_id, |id_map, dist, virt_ids, is_light| {
+            virt_ids
+                .iter()
+                .flat_map(|virt_id| requests_of(graph, id_map, dist, delta, *virt_id, is_light))
+                .collect()
+        })
+    }
+
+    /// Same as [`execute`](crate::algo::DeltaStepping::execute), but computes relaxation requests of each bucket using multiple threads.
+    ///
+    /// # Arguments
+    /// * `graph`: Graph to search for the shortest paths in.
+    /// * `src_id`: Id of the source vertex.
+    ///
+    /// # Returns
+    /// Distance of each vertex from the source, `PosInfinite` for unreachable vertices.
+    #[cfg(feature = "parallel")]
+    pub fn par_execute<E, Ty, G>(self, graph: &G, src_id: usize) -> HashMap<usize, Magnitude<W>>
+    where
+        W: Send + Sync,
+        E: Edge<W>,
+        Ty: EdgeDir,
+        G: Edges<W, E> + Vertices + Graph<W, E, Ty> + Sync,
+    {
+        let delta = self.delta;
+
+        self.execute_with(src_id, |id_map, dist, virt_ids, is_light| {
+            crate::misc::par::map(virt_ids, |virt_id| {
+                requests_of(graph, id_map, dist, delta, *virt_id, is_light)
+            })
+            .into_iter()
+            .flatten()
+            .collect()
+        })
+    }
+
+    // `requests` computes relaxation requests, as (virtual id, new distance), for light or heavy edges of the given vertices.
+    fn execute_with<F>(mut self, src_id: usize, requests: F) -> HashMap<usize, Magnitude<W>>
+    where
+        F: Fn(&IdMap, &[Magnitude<W>], &[usize], bool) -> Vec<(usize, W)>,
+    {
+        self.relax(self.id_map.virt_id_of(src_id), W::zero());
+
+        let mut is_removed = vec![false; self.dist.len()];
+        let mut index = 0;
+        while index < self.buckets.len() {
+            let mut removed = vec![];
+
+            while !self.buckets[index].is_empty() {
+                // A vertex may have moved to a lower bucket after it was added to this one.
+                let mut frontier = std::mem::take(&mut self.buckets[index]);
+                frontier.retain(|virt_id| self.bucket_of(*virt_id) == Some(index));
+                frontier.sort_unstable();
+                frontier.dedup();
+
+                for virt_id in &frontier {
+                    if !is_removed[*virt_id] {
+                        is_removed[*virt_id] = true;
+                        removed.push(*virt_id);
+                    }
+                }
+
+                for (virt_id, dist) in requests(&self.id_map, &self.dist, &frontier, true) {
+                    self.relax(virt_id, dist);
+                }
+            }
+
+            for (virt_id, dist) in requests(&self.id_map, &self.dist, &removed, false) {
+                self.relax(virt_id, dist);
+            }
+
+            for virt_id in removed {
+                is_removed[virt_id] = false;
+            }
+
+            index += 1;
+        }
+
+        let id_map = self.id_map;
+        self.dist
+            .into_iter()
+            .enumerate()
+            .map(|(virt_id, dist)| (id_map.real_id_of(virt_id), dist))
+            .collect()
+    }
+
+    fn bucket_of(&self, virt_id: usize) -> Option<usize> {
+        match self.dist[virt_id] {
+            Magnitude::Finite(dist) => (dist / self.delta).to_usize(),
+            _ => None,
+        }
+    }
+
+    fn relax(&mut self, virt_id: usize, dist: W) {
+        if Magnitude::Finite(dist) < self.dist[virt_id] {
+            self.dist[virt_id] = dist.into();
+
+            let index = self.bucket_of(virt_id).unwrap();
+            if index >= self.buckets.len() {
+                self.buckets.resize_with(index + 1, Vec::new);
+            }
+            self.buckets[index].push(virt_id);
+        }
+    }
+}
+
+fn requests_of<W, E, G>(
+    graph: &G,
+    id_map: &IdMap,
+    dist: &[Magnitude<W>],
+    delta: W,
+    virt_id: usize,
+    is_light: bool,
+) -> Vec<(usize, W)>
+where
+    W: Copy + Ord + Zero + Any,
+    E: Edge<W>,
+    G: Edges<W, E>,
+{
+    let src_dist = dist[virt_id].unwrap();
+
+    graph
+        .edges_from_unchecked(id_map.real_id_of(virt_id))
+        .into_iter()
+        .filter(|(_, edge)| edge.get_weight().is_finite())
+        .map(|(dst_id, edge)| (dst_id, edge.get_weight().unwrap()))
+        .filter(|(_, weight)| (*weight <= delta) == is_light)
+        .map(|(dst_id, weight)| (id_map.virt_id_of(dst_id), src_dist + weight))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::Dijkstra;
+    use crate::graph::ListGraph;
+    use crate::misc::rng::Rng;
+    use crate::storage::DiList;
+
+    fn random_graph(
+        vertex_count: usize,
+        edge_count: usize,
+        seed: u64,
+    ) -> ListGraph<usize, crate::graph::DirectedEdge> {
+        let mut rng = Rng::seed_from(seed);
+        let mut graph = ListGraph::init(DiList::<usize>::init());
+        for _ in 0..vertex_count {
+            graph.add_vertex();
+        }
+        for _ in 0..edge_count {
+            let src_id = rng.next_below(vertex_count);
+            let dst_id = rng.next_below(vertex_count);
+            let weight = rng.next_below(20);
+            graph.add_edge_unchecked(src_id, dst_id, weight.into());
+        }
+
+        graph
+    }
+
+    fn assert_same_distances(
+        distances: &HashMap<usize, Magnitude<usize>>,
+        graph: &ListGraph<usize, crate::graph::DirectedEdge>,
+    ) {
+        let sp_subgraph = Dijkstra::init(graph).execute(graph, 0);
+
+        assert_eq!(distances.len(), graph.vertex_count());
+        for (vertex_id, dist) in distances {
+            let expected = sp_subgraph.distance_to(*vertex_id).unwrap();
+            // Magnitude panics when comparing two infinities.
+            if expected.is_finite() {
+                assert_eq!(*dist, expected);
+            } else {
+                assert!(dist.is_pos_infinite());
+            }
+        }
+    }
+
+    #[test]
+    fn matches_dijkstra() {
+        // Given: Random graph with weights in 0..20, including zero weights and loops.
+        let graph = random_graph(200, 800, 3);
+
+        // Then: For small, medium and large deltas distances are the same as Dijkstra.
+        for delta in [1, 5, 100] {
+            let distances = DeltaStepping::init(&graph, delta).execute(&graph, 0);
+
+            assert_same_distances(&distances, &graph);
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_matches_dijkstra() {
+        // Given:
+        let graph = random_graph(500, 3000, 11);
+
+        // When:
+        let distances = DeltaStepping::init(&graph, 4).par_execute(&graph, 0);
+
+        // Then:
+        assert_same_distances(&distances, &graph);
+    }
+}
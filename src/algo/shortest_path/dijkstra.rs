@@ -1,9 +1,12 @@
 use magnitude::Magnitude;
 use num_traits::{Unsigned, Zero};
+use std::any::Any;
+#[cfg(feature = "parallel")]
 use std::collections::HashMap;
-use std::{any::Any, collections::HashSet};
+use std::marker::PhantomData;
 
-use crate::provide::{Edges, Graph, Vertices};
+use crate::algo::{BestFirst, Orientation, QueueKind};
+use crate::provide::{Direction, Edges, IdMap, Vertices};
 use crate::{
     graph::{subgraph::ShortestPathSubgraph, Edge, EdgeDir},
     prelude::Neighbors,
@@ -61,9 +64,11 @@ use crate::{
 /// assert_eq!(sp_subgraph.distance_to(e).unwrap(), 2.into());
 /// ```
 pub struct Dijkstra<W> {
-    visited: Vec<bool>,
-    dist: Vec<Magnitude<W>>,
-    prev: Vec<Magnitude<usize>>,
+    id_map: IdMap,
+    queue_kind: QueueKind,
+    orientation: Orientation,
+
+    phantom_w: PhantomData<W>,
 }
 
 impl<W: Copy + Ord + Zero + Any + Unsigned> Dijkstra<W> {
@@ -72,24 +77,40 @@ impl<W: Copy + Ord + Zero + Any + Unsigned> Dijkstra<W> {
     where
         E: Edge<W>,
         Ty: EdgeDir,
-        G: Edges<W, E> + Vertices + Graph<W, E, Ty>,
+        G: Edges<W, E> + Vertices + Direction<Ty>,
     {
-        let vertex_count = graph.vertex_count();
+        Dijkstra::init_with_queue(graph, QueueKind::default())
+    }
 
+    /// Initializes the structure with the given kind of priority queue.
+    ///
+    /// # Arguments
+    /// * `graph`: Graph to search for the shortest paths in.
+    /// * `queue_kind`: Kind of priority queue to use. Check out [`QueueKind`](crate::algo::QueueKind) for available options.
+    pub fn init_with_queue<E, Ty, G>(graph: &G, queue_kind: QueueKind) -> Self
+    where
+        E: Edge<W>,
+        Ty: EdgeDir,
+        G: Edges<W, E> + Vertices + Direction<Ty>,
+    {
         Dijkstra {
-            visited: vec![false; vertex_count],
-            dist: vec![Magnitude::PosInfinite; vertex_count],
-            prev: vec![Magnitude::PosInfinite; vertex_count],
+            id_map: graph.continuos_id_map(),
+            queue_kind,
+            orientation: Orientation::default(),
+
+            phantom_w: PhantomData,
         }
     }
 
-    fn next_id(&self) -> Option<usize> {
-        self.dist
-            .iter()
-            .enumerate()
-            .filter(|(virt_id, dist)| dist.is_finite() && self.visited[*virt_id] == false)
-            .min_by(|(_, dist1), (_, dist2)| dist1.cmp(dist2))
-            .and_then(|(v_id, _)| Some(v_id))
+    /// Follows edges in the given orientation instead of the [`Original`](crate::algo::Orientation::Original) one.
+    /// With [`Reversed`](crate::algo::Orientation::Reversed), distances are from each vertex to the source.
+    ///
+    /// # Arguments
+    /// `orientation`: How to follow the edges.
+    pub fn with_orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = orientation;
+
+        self
     }
 
     /// Finds shortest path from a single source to all other vertices.
@@ -101,56 +122,67 @@ impl<W: Copy + Ord + Zero + Any + Unsigned> Dijkstra<W> {
     /// # Returns
     /// The shortest path as a subgraph of the original graph.
     /// You can query shortest path from source to each destination using api provided by `ShortestPathSubgraph`.
-    pub fn execute<E, Ty, G>(
-        mut self,
-        graph: &G,
-        src_id: usize,
-    ) -> ShortestPathSubgraph<W, E, Ty, G>
+    /// [`parent`](crate::graph::subgraph::ShortestPathSubgraph::parent) and [`path_to`](crate::graph::subgraph::ShortestPathSubgraph::path_to) follow the orientation,
+    /// while edges of the subgraph keep their direction in the graph.
+    pub fn execute<E, Ty, G>(self, graph: &G, src_id: usize) -> ShortestPathSubgraph<W, E, Ty, G>
     where
         E: Edge<W>,
         Ty: EdgeDir,
-        G: Edges<W, E> + Neighbors + Vertices + Graph<W, E, Ty>,
+        G: Edges<W, E> + Neighbors + Vertices + Direction<Ty>,
     {
-        let mut edges = vec![];
-
-        let id_map = graph.continuos_id_map();
-
-        let src_virt_id = id_map.virt_id_of(src_id);
-
-        self.dist[src_virt_id] = W::zero().into();
-
-        while let Some(virt_id) = self.next_id() {
-            self.visited[virt_id] = true;
-
-            let real_id = id_map.real_id_of(virt_id);
-
-            for (n_id, edge) in graph.edges_from_unchecked(real_id) {
-                let n_virt_id = id_map.virt_id_of(n_id);
-
-                let alt = self.dist[virt_id] + *edge.get_weight();
-                if alt < self.dist[n_virt_id] {
-                    self.dist[n_virt_id] = alt;
-                    self.prev[n_virt_id] = virt_id.into();
-
-                    edges.retain(|(_, dst_id, _)| *dst_id != n_id); // remove edge to neighbor
-                    edges.push((real_id, n_id, edge.get_id())); // add new edge
-                }
-            }
-        }
-
-        let mut distance_map = HashMap::new();
-        for virt_id in 0..graph.vertex_count() {
-            let real_id = id_map.real_id_of(virt_id);
-            distance_map.insert(real_id, self.dist[virt_id]);
-        }
-
-        let vertices = edges
-            .iter()
-            .flat_map(|(src_id, dst_id, _)| vec![*src_id, *dst_id])
-            .chain(std::iter::once(src_id))
-            .collect::<HashSet<usize>>();
+        // Dijkstra is a best-first search that prioritizes vertices by their distance from the source.
+        let mut listener = ();
+        let mut best_first = BestFirst::init_with_id_map(graph, &mut listener, self.id_map)
+            .with_queue(self.queue_kind)
+            .with_orientation(self.orientation);
+        best_first.execute(
+            graph,
+            src_id,
+            W::zero().into(),
+            |dist: &Magnitude<W>, edge| *dist + *edge.get_weight(),
+            |_, dist| *dist,
+        );
+        let (dists, parents, id_map) = best_first.dissolve();
+
+        super::sp_subgraph_of(graph, src_id, dists, parents, &id_map, self.orientation)
+    }
 
-        ShortestPathSubgraph::init(graph, edges, vertices, distance_map)
+    /// Finds shortest paths between all pairs of vertices by running dijkstra from every vertex.
+    ///
+    /// Sources are independent of each other, so they are distributed between multiple threads.
+    ///
+    /// # Arguments
+    /// * `graph`: Graph to search for the shortest paths in.
+    ///
+    /// # Returns
+    /// Shortest path information in the form of: (src_id, dst_id) -> distance.
+    /// Same as the result of [`FloydWarshall`](crate::algo::FloydWarshall) for graphs with non negative weights.
+    #[cfg(feature = "parallel")]
+    pub fn par_all_pairs<E, Ty, G>(graph: &G) -> HashMap<(usize, usize), Magnitude<W>>
+    where
+        W: Send + Sync,
+        E: Edge<W>,
+        Ty: EdgeDir,
+        G: Edges<W, E> + Neighbors + Vertices + Direction<Ty> + Sync,
+    {
+        let vertices = graph.vertices();
+
+        crate::misc::par::map(&vertices, |src_id| {
+            let sp_subgraph = Dijkstra::init(graph).execute(graph, *src_id);
+
+            vertices
+                .iter()
+                .map(|dst_id| {
+                    (
+                        (*src_id, *dst_id),
+                        sp_subgraph.distance_to(*dst_id).unwrap(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        })
+        .into_iter()
+        .flatten()
+        .collect()
     }
 }
 
@@ -158,8 +190,51 @@ impl<W: Copy + Ord + Zero + Any + Unsigned> Dijkstra<W> {
 mod tests {
     use super::*;
     use crate::graph::MatGraph;
+    use crate::provide::Graph;
     use crate::storage::{DiMat, Mat};
 
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn par_all_pairs_matches_floyd_warshall() {
+        // Given: Graph
+        //          6       1
+        //      a  -->  b  <--  c ---
+        //    1 |       |           |
+        //      |  2 /`````\ 2      |
+        //      |````       ````|   |
+        //      v               v   | 1
+        //      d  ---------->  e --'
+        //              1
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 6.into());
+        graph.add_edge_unchecked(a, d, 1.into());
+        graph.add_edge_unchecked(b, d, 2.into());
+        graph.add_edge_unchecked(b, e, 2.into());
+        graph.add_edge_unchecked(c, b, 1.into());
+        graph.add_edge_unchecked(e, c, 1.into());
+        graph.add_edge_unchecked(d, e, 1.into());
+
+        // When:
+        let distances = Dijkstra::par_all_pairs(&graph);
+
+        // Then:
+        let expected = crate::algo::FloydWarshall::init().execute(&graph).unwrap();
+        assert_eq!(distances.len(), expected.len());
+        for (pair, distance) in expected {
+            // Magnitude panics when comparing two infinities.
+            if distance.is_finite() {
+                assert_eq!(distances[&pair], distance);
+            } else {
+                assert!(distances[&pair].is_pos_infinite());
+            }
+        }
+    }
+
     #[test]
     fn one_vertex_undirected_graph() {
         // Given: Graph
@@ -238,6 +313,9 @@ mod tests {
         assert_eq!(sp_subgraph.distance_to(c).unwrap(), 7.into());
         assert_eq!(sp_subgraph.distance_to(d).unwrap(), 1.into());
         assert_eq!(sp_subgraph.distance_to(e).unwrap(), 2.into());
+        assert_eq!(sp_subgraph.parent(a), None);
+        assert_eq!(sp_subgraph.parent(b), Some(d));
+        assert_eq!(sp_subgraph.path_to(c).unwrap(), vec![a, d, e, c]);
     }
 
     #[test]
@@ -284,4 +362,44 @@ mod tests {
         assert_eq!(sp_subgraph.distance_to(d).unwrap(), 1.into());
         assert_eq!(sp_subgraph.distance_to(e).unwrap(), 2.into());
     }
+
+    #[test]
+    fn lazy_queue_matches_indexed_queue() {
+        // Given: Complete graph with weights depending on the end points.
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let vertices: Vec<usize> = (0..8).map(|_| graph.add_vertex()).collect();
+        for src_id in &vertices {
+            for dst_id in &vertices {
+                if src_id != dst_id {
+                    let weight = (src_id * 7 + dst_id * 3) % 11 + 1;
+                    graph.add_edge_unchecked(*src_id, *dst_id, weight.into());
+                }
+            }
+        }
+
+        // When: Performing Dijkstra with both kinds of queue.
+        let indexed = Dijkstra::init_with_queue(&graph, QueueKind::Indexed).execute(&graph, 0);
+        let lazy = Dijkstra::init_with_queue(&graph, QueueKind::Lazy).execute(&graph, 0);
+
+        // Then: Same distances and same shortest path tree.
+        for vertex_id in &vertices {
+            assert_eq!(
+                indexed.distance_to(*vertex_id),
+                lazy.distance_to(*vertex_id)
+            );
+        }
+        let mut indexed_edges: Vec<usize> = indexed
+            .edges()
+            .into_iter()
+            .map(|(_, _, edge)| edge.get_id())
+            .collect();
+        let mut lazy_edges: Vec<usize> = lazy
+            .edges()
+            .into_iter()
+            .map(|(_, _, edge)| edge.get_id())
+            .collect();
+        indexed_edges.sort_unstable();
+        lazy_edges.sort_unstable();
+        assert_eq!(indexed_edges, lazy_edges);
+    }
 }
@@ -0,0 +1,441 @@
+use magnitude::Magnitude;
+use num_traits::{Unsigned, Zero};
+use std::any::Any;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+
+use crate::graph::{Edge, EdgeDir};
+use crate::provide::{Edges, Graph, Vertices};
+use crate::storage::{GraphObserver, StorageEvent};
+
+/// Maintains shortest paths from a single source while edges are inserted and removed.
+///
+/// `DynamicSSSP` keeps its own copy of the edges, so it must be notified of every change to the graph using
+/// [`edge_inserted`](crate::algo::DynamicSSSP::edge_inserted) and [`edge_removed`](crate::algo::DynamicSSSP::edge_removed).
+/// Alternatively it can subscribe to a graph that uses an [`ObservedStorage`](crate::storage::ObservedStorage) to get notified automatically.
+/// Following the approach of [Ramalingam and Reps](https://doi.org/10.1006/jagm.1996.0046), each update only touches the vertices whose distance changes:
+/// * Inserting an edge that shortens the distance of its destination propagates the improvement from the destination.
+/// * Removing an edge of the shortest path tree invalidates the subtree under it. Distances of the subtree are then recomputed
+///   starting from the best edges that enter the subtree from the rest of the tree.
+///
+/// Weights must be non negative.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::DiList;
+/// use prepona::graph::ListGraph;
+/// use prepona::algo::DynamicSSSP;
+///
+/// // Given: Graph
+/// //
+/// //      a  --1-->  b  --1-->  c
+/// //
+/// let mut graph = ListGraph::init(DiList::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// let bc = graph.add_edge_unchecked(b, c, 1.into());
+///
+/// let mut sssp = DynamicSSSP::init(&graph, a);
+/// assert_eq!(sssp.distance_to(c), 2.into());
+///
+/// // When: Adding a shortcut and removing the old path.
+/// graph.add_edge_unchecked(a, c, 5.into());
+/// sssp.edge_inserted(a, c, 5);
+/// graph.remove_edge_unchecked(b, c, bc);
+/// sssp.edge_removed(b, c, 1);
+///
+/// // Then:
+/// assert_eq!(sssp.distance_to(c), 5.into());
+/// assert_eq!(sssp.path_to(c), Some(vec![a, c]));
+/// ```
+pub struct DynamicSSSP<W> {
+    src_id: usize,
+    is_directed: bool,
+    outgoing: HashMap<usize, Vec<(usize, W)>>,
+    incoming: HashMap<usize, Vec<(usize, W)>>,
+    dist: HashMap<usize, W>,
+    parent: HashMap<usize, (usize, W)>,
+}
+
+impl<W: Copy + Ord + Zero + Any + Unsigned> DynamicSSSP<W> {
+    /// Initializes the structure and computes the shortest paths of the current graph.
+    ///
+    /// # Arguments
+    /// * `graph`: Graph to maintain the shortest paths of.
+    /// * `src_id`: Id of the source vertex.
+    pub fn init<E, Dir, G>(graph: &G, src_id: usize) -> Self
+    where
+        E: Edge<W>,
+        Dir: EdgeDir,
+        G: Edges<W, E> + Vertices + Graph<W, E, Dir>,
+    {
+        let mut sssp = DynamicSSSP {
+            src_id,
+            is_directed: Dir::is_directed(),
+            outgoing: HashMap::new(),
+            incoming: HashMap::new(),
+            dist: HashMap::new(),
+            parent: HashMap::new(),
+        };
+
+        // Undirected edges are returned in both directions.
+        for (src_id, dst_id, edge) in graph.as_directed_edges() {
+            if edge.get_weight().is_finite() {
+                sssp.add_arc(src_id, dst_id, edge.get_weight().unwrap());
+            }
+        }
+
+        sssp.dist.insert(src_id, W::zero());
+        sssp.propagate(vec![src_id]);
+
+        sssp
+    }
+
+    /// Updates the shortest paths after an edge is added to the graph.
+    ///
+    /// # Arguments
+    /// * `src_id`: Id of the source of the inserted edge.
+    /// * `dst_id`: Id of the destination of the inserted edge.
+    /// * `weight`: Weight of the inserted edge.
+    pub fn edge_inserted(&mut self, src_id: usize, dst_id: usize, weight: W) {
+        self.add_arc(src_id, dst_id, weight);
+        let improved = self.relax(src_id, dst_id, weight);
+        self.propagate(improved);
+
+        if !self.is_directed {
+            self.add_arc(dst_id, src_id, weight);
+            let improved = self.relax(dst_id, src_id, weight);
+            self.propagate(improved);
+        }
+    }
+
+    /// Updates the shortest paths after an edge is removed from the graph.
+    /// Does nothing if there is no edge from `src_id` to `dst_id` with the given weight.
+    ///
+    /// # Arguments
+    /// * `src_id`: Id of the source of the removed edge.
+    /// * `dst_id`: Id of the destination of the removed edge.
+    /// * `weight`: Weight of the removed edge.
+    pub fn edge_removed(&mut self, src_id: usize, dst_id: usize, weight: W) {
+        self.remove_arc(src_id, dst_id, weight);
+
+        if !self.is_directed {
+            self.remove_arc(dst_id, src_id, weight);
+        }
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// Distance of the vertex from the source, `PosInfinite` if it's not reachable.
+    pub fn distance_to(&self, vertex_id: usize) -> Magnitude<W> {
+        self.dist
+            .get(&vertex_id)
+            .map_or(Magnitude::PosInfinite, |dist| (*dist).into())
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// Id of the vertex before `vertex_id` on its shortest path, `None` for the source and unreachable vertices.
+    pub fn parent_of(&self, vertex_id: usize) -> Option<usize> {
+        self.parent.get(&vertex_id).map(|(parent_id, _)| *parent_id)
+    }
+
+    /// # Arguments
+    /// `dst_id`: Id of the destination vertex.
+    ///
+    /// # Returns
+    /// * `Some`: Containing ids of the vertices on the shortest path from the source to `dst_id`, including both ends.
+    /// * `None`: If `dst_id` is not reachable.
+    pub fn path_to(&self, dst_id: usize) -> Option<Vec<usize>> {
+        if !self.dist.contains_key(&dst_id) {
+            return None;
+        }
+
+        let mut path = vec![dst_id];
+        let mut vertex_id = dst_id;
+        while let Some(parent_id) = self.parent_of(vertex_id) {
+            path.push(parent_id);
+            vertex_id = parent_id;
+        }
+        path.reverse();
+
+        Some(path)
+    }
+
+    fn add_arc(&mut self, src_id: usize, dst_id: usize, weight: W) {
+        self.outgoing
+            .entry(src_id)
+            .or_default()
+            .push((dst_id, weight));
+        self.incoming
+            .entry(dst_id)
+            .or_default()
+            .push((src_id, weight));
+    }
+
+    fn remove_arc(&mut self, src_id: usize, dst_id: usize, weight: W) {
+        let removed = remove_first(self.outgoing.get_mut(&src_id), (dst_id, weight))
+            && remove_first(self.incoming.get_mut(&dst_id), (src_id, weight));
+        if !removed {
+            return;
+        }
+
+        let is_tree_arc = self.parent.get(&dst_id) == Some(&(src_id, weight));
+        let has_twin = self
+            .outgoing
+            .get(&src_id)
+            .is_some_and(|arcs| arcs.contains(&(dst_id, weight)));
+        if !is_tree_arc || has_twin {
+            return;
+        }
+
+        // Every vertex in the subtree of `dst_id` reached the source through the removed arc.
+        let mut affected = HashSet::new();
+        let mut queue = VecDeque::from(vec![dst_id]);
+        while let Some(vertex_id) = queue.pop_front() {
+            affected.insert(vertex_id);
+
+            for (n_id, _) in self.outgoing.get(&vertex_id).into_iter().flatten() {
+                let is_child =
+                    self.parent.get(n_id).map(|(parent_id, _)| *parent_id) == Some(vertex_id);
+                if is_child && !affected.contains(n_id) {
+                    queue.push_back(*n_id);
+                }
+            }
+        }
+
+        for vertex_id in &affected {
+            self.dist.remove(vertex_id);
+            self.parent.remove(vertex_id);
+        }
+
+        // Reconnect each affected vertex through its best arc from an unaffected vertex.
+        let entering_arcs: Vec<(usize, usize, W)> = affected
+            .iter()
+            .flat_map(|vertex_id| {
+                self.incoming
+                    .get(vertex_id)
+                    .into_iter()
+                    .flatten()
+                    .filter(|(n_id, _)| !affected.contains(n_id))
+                    .map(move |(n_id, weight)| (*n_id, *vertex_id, *weight))
+            })
+            .collect();
+
+        let mut seeds = vec![];
+        for (src_id, dst_id, weight) in entering_arcs {
+            seeds.extend(self.relax(src_id, dst_id, weight));
+        }
+
+        self.propagate(seeds);
+    }
+
+    // Returns the destination if its distance is improved.
+    fn relax(&mut self, src_id: usize, dst_id: usize, weight: W) -> Option<usize> {
+        let src_dist = *self.dist.get(&src_id)?;
+        let new_dist = src_dist + weight;
+
+        if self.dist.get(&dst_id).is_none_or(|dist| new_dist < *dist) {
+            self.dist.insert(dst_id, new_dist);
+            self.parent.insert(dst_id, (src_id, weight));
+
+            Some(dst_id)
+        } else {
+            None
+        }
+    }
+
+    // Runs dijkstra starting from vertices whose distances are improved.
+    fn propagate(&mut self, improved: impl IntoIterator<Item = usize>) {
+        let mut queue: BinaryHeap<Reverse<(W, usize)>> = improved
+            .into_iter()
+            .map(|vertex_id| Reverse((self.dist[&vertex_id], vertex_id)))
+            .collect();
+
+        while let Some(Reverse((dist, vertex_id))) = queue.pop() {
+            if dist > self.dist[&vertex_id] {
+                continue;
+            }
+
+            let arcs = self.outgoing.get(&vertex_id).cloned().unwrap_or_default();
+            for (n_id, weight) in arcs {
+                if n_id == self.src_id {
+                    continue;
+                }
+
+                if let Some(n_id) = self.relax(vertex_id, n_id, weight) {
+                    queue.push(Reverse((self.dist[&n_id], n_id)));
+                }
+            }
+        }
+    }
+}
+
+fn remove_first<T: PartialEq>(items: Option<&mut Vec<T>>, item: T) -> bool {
+    match items.and_then(|items| {
+        items
+            .iter()
+            .position(|i| *i == item)
+            .map(|index| (items, index))
+    }) {
+        Some((items, index)) => {
+            items.swap_remove(index);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Updates the shortest paths as edges with finite weights are added to or removed from an [`ObservedStorage`](crate::storage::ObservedStorage).
+impl<W, E> GraphObserver<W, E> for DynamicSSSP<W>
+where
+    W: Copy + Ord + Zero + Any + Unsigned,
+    E: Edge<W>,
+{
+    fn notify(&mut self, event: &StorageEvent<E>) {
+        match event {
+            StorageEvent::EdgeAdded {
+                src_id,
+                dst_id,
+                edge,
+            } => {
+                if let Magnitude::Finite(weight) = edge.get_weight() {
+                    self.edge_inserted(*src_id, *dst_id, *weight);
+                }
+            }
+            StorageEvent::EdgeRemoved {
+                src_id,
+                dst_id,
+                edge,
+            } => {
+                if let Magnitude::Finite(weight) = edge.get_weight() {
+                    self.edge_removed(*src_id, *dst_id, *weight);
+                }
+            }
+            StorageEvent::VertexAdded(_) | StorageEvent::VertexRemoved(_) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::Dijkstra;
+    use crate::graph::{ListGraph, UndirectedEdge};
+    use crate::misc::rng::Rng;
+    use crate::storage::{DiList, List};
+
+    fn assert_matches_dijkstra<Dir: EdgeDir, G>(sssp: &DynamicSSSP<usize>, graph: &G)
+    where
+        G: Edges<usize, crate::graph::DefaultEdge<usize>>
+            + crate::provide::Neighbors
+            + Vertices
+            + Graph<usize, crate::graph::DefaultEdge<usize>, Dir>,
+    {
+        let sp_subgraph = Dijkstra::init(graph).execute(graph, sssp.src_id);
+
+        for vertex_id in graph.vertices() {
+            let expected = sp_subgraph.distance_to(vertex_id).unwrap();
+            let dist = sssp.distance_to(vertex_id);
+            // Magnitude panics when comparing two infinities.
+            if expected.is_finite() {
+                assert_eq!(dist, expected);
+
+                let path = sssp.path_to(vertex_id).unwrap();
+                let length: usize = path
+                    .windows(2)
+                    .map(|pair| {
+                        graph
+                            .edges_between_unchecked(pair[0], pair[1])
+                            .into_iter()
+                            .map(|edge| edge.get_weight().unwrap())
+                            .min()
+                            .unwrap()
+                    })
+                    .sum();
+                assert_eq!(Magnitude::from(length), expected);
+            } else {
+                assert!(dist.is_pos_infinite());
+                assert!(sssp.path_to(vertex_id).is_none());
+            }
+        }
+    }
+
+    #[test]
+    fn random_updates_on_directed_graph() {
+        // Given: Empty directed graph with 30 vertices.
+        let mut rng = Rng::seed_from(5);
+        let mut graph = ListGraph::init(DiList::<usize>::init());
+        for _ in 0..30 {
+            graph.add_vertex();
+        }
+        let mut sssp = DynamicSSSP::init(&graph, 0);
+        let mut edges = vec![];
+
+        // When: Randomly inserting and removing edges.
+        for _ in 0..600 {
+            if edges.is_empty() || rng.next_bool(0.6) {
+                let src_id = rng.next_below(30);
+                let dst_id = rng.next_below(30);
+                let weight = rng.next_below(10);
+
+                let edge_id = graph.add_edge_unchecked(src_id, dst_id, weight.into());
+                sssp.edge_inserted(src_id, dst_id, weight);
+                edges.push((src_id, dst_id, weight, edge_id));
+            } else {
+                let index = rng.next_below(edges.len());
+                let (src_id, dst_id, weight, edge_id) = edges.swap_remove(index);
+
+                graph.remove_edge_unchecked(src_id, dst_id, edge_id);
+                sssp.edge_removed(src_id, dst_id, weight);
+            }
+
+            // Then: Distances always match a full recomputation.
+            assert_matches_dijkstra(&sssp, &graph);
+        }
+    }
+
+    #[test]
+    fn random_updates_on_undirected_graph() {
+        // Given: Undirected graph with 20 vertices.
+        let mut rng = Rng::seed_from(9);
+        let mut graph = ListGraph::<usize, UndirectedEdge>::init(List::<usize>::init());
+        for _ in 0..20 {
+            graph.add_vertex();
+        }
+        let mut sssp = DynamicSSSP::init(&graph, 3);
+        let mut edges = vec![];
+
+        // When: Randomly inserting and removing edges.
+        for _ in 0..400 {
+            if edges.is_empty() || rng.next_bool(0.55) {
+                let src_id = rng.next_below(20);
+                let dst_id = rng.next_below(20);
+                if src_id == dst_id {
+                    continue;
+                }
+                let weight = rng.next_below(10) + 1;
+
+                let edge_id = graph.add_edge_unchecked(src_id, dst_id, weight.into());
+                sssp.edge_inserted(src_id, dst_id, weight);
+                edges.push((src_id, dst_id, weight, edge_id));
+            } else {
+                let index = rng.next_below(edges.len());
+                let (src_id, dst_id, weight, edge_id) = edges.swap_remove(index);
+
+                graph.remove_edge_unchecked(src_id, dst_id, edge_id);
+                sssp.edge_removed(src_id, dst_id, weight);
+            }
+
+            // Then:
+            assert_matches_dijkstra(&sssp, &graph);
+        }
+    }
+}
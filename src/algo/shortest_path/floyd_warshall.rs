@@ -4,9 +4,9 @@ use num_traits::Zero;
 use std::any::Any;
 use std::collections::HashMap;
 
-use crate::algo::Error;
+use crate::algo::{Algorithm, Error};
 use crate::graph::Edge;
-use crate::provide;
+use crate::provide::{self, IdMap};
 
 /// Finds shortest path from all vertices to all the other ones using floyd-warshall algorithm.
 ///
@@ -103,15 +103,77 @@ impl FloydWarshall {
     ///
     /// # Arguments
     /// * `graph`: Graph to search for the shortest paths in.
-    /// * `src_id`: Id of the source vertex(Shortest path will be calculated from this vertex to all other vertices)
     ///
     /// # Returns
     /// * `Ok`: Containing shortest path information in the form of: (src_id, dst_id) -> distance.
-    /// * `Err`: If graph contains negative cycle.
+    /// * `Err`: If graph contains negative cycle. In undirected graphs, an edge with negative weight is a negative cycle itself.
     pub fn execute<G, W: Copy + Zero + Any + Ord, E: Edge<W>>(
         self,
         graph: &G,
     ) -> Result<HashMap<(usize, usize), Magnitude<W>>>
+    where
+        G: provide::Edges<W, E> + provide::Vertices,
+    {
+        Ok(self.execute_with_paths(graph)?.into_distance_map())
+    }
+
+    /// Finds shortest path from all vertices to all the other ones, and stores the successor of each vertex on each path so paths can be reconstructed.
+    ///
+    /// Successors take O(|V|<sup>2</sup>) extra memory, so use [`execute`](crate::algo::FloydWarshall::execute) if only distances are needed.
+    ///
+    /// # Arguments
+    /// * `graph`: Graph to search for the shortest paths in.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing distances and paths between all pairs of vertices.
+    /// * `Err`: If graph contains negative cycle. In undirected graphs, an edge with negative weight is a negative cycle itself.
+    ///
+    /// # Complexity
+    /// O(|V|<sup>3</sup>)
+    ///
+    /// # Examples
+    /// ```
+    /// use prepona::prelude::*;
+    /// use prepona::storage::DiMat;
+    /// use prepona::graph::MatGraph;
+    /// use prepona::algo::{ErrorKind, FloydWarshall};
+    ///
+    /// // Given: Graph
+    /// //          6
+    /// //      a  -->  b
+    /// //    1 |       ^
+    /// //      v       | 2
+    /// //      c  -----'
+    /// //
+    /// let mut graph = MatGraph::init(DiMat::<isize>::init());
+    /// let a = graph.add_vertex();
+    /// let b = graph.add_vertex();
+    /// let c = graph.add_vertex();
+    /// graph.add_edge_unchecked(a, b, 6.into());
+    /// graph.add_edge_unchecked(a, c, 1.into());
+    /// let cb = graph.add_edge_unchecked(c, b, 2.into());
+    ///
+    /// // When:
+    /// let paths = FloydWarshall::init().execute_with_paths(&graph).unwrap();
+    ///
+    /// // Then:
+    /// assert_eq!(paths.distance(a, b), Some(3.into()));
+    /// assert_eq!(paths.path(a, b), Some(vec![a, c, b]));
+    /// assert_eq!(paths.path(b, a), None);
+    ///
+    /// // When: c -> b becomes part of a negative cycle.
+    /// graph.update_edge_unchecked(c, b, cb, (-2).into());
+    /// graph.add_edge_unchecked(b, c, 1.into());
+    /// let error = FloydWarshall::init().execute_with_paths(&graph).err().unwrap();
+    ///
+    /// // Then:
+    /// let error = error.downcast::<prepona::algo::Error>().unwrap();
+    /// assert!(matches!(error.kind(), ErrorKind::NegativeCycleDetected));
+    /// ```
+    pub fn execute_with_paths<G, W: Copy + Zero + Any + Ord, E: Edge<W>>(
+        self,
+        graph: &G,
+    ) -> Result<AllPairsShortestPaths<W>>
     where
         G: provide::Edges<W, E> + provide::Vertices,
     {
@@ -121,10 +183,11 @@ impl FloydWarshall {
         let id_map = graph.continuos_id_map();
 
         let mut dist = vec![vec![Magnitude::PosInfinite; vertex_count]; vertex_count];
+        let mut next = vec![vec![None; vertex_count]; vertex_count];
 
-        for &u_real_id in &vertices {
-            let u_virt_id = id_map.virt_id_of(u_real_id);
-            dist[u_virt_id][u_virt_id] = W::zero().into();
+        for virt_id in 0..vertex_count {
+            dist[virt_id][virt_id] = W::zero().into();
+            next[virt_id][virt_id] = Some(virt_id);
         }
 
         for &u_real_id in &vertices {
@@ -132,53 +195,149 @@ impl FloydWarshall {
 
             for (v_real_id, edge) in graph.edges_from_unchecked(u_real_id) {
                 let v_virt_id = id_map.virt_id_of(v_real_id);
-                dist[u_virt_id][v_virt_id] = edge.get_weight().clone();
+
+                // Only the lightest one of parallel edges matters.
+                if *edge.get_weight() < dist[u_virt_id][v_virt_id] {
+                    dist[u_virt_id][v_virt_id] = *edge.get_weight();
+                    next[u_virt_id][v_virt_id] = Some(v_virt_id);
+                }
             }
         }
 
         for k in 0..vertex_count {
-            for &i in &vertices {
-                let i_virt_id = id_map.virt_id_of(i);
-                for &j in &vertices {
-                    let j_virt_id = id_map.virt_id_of(j);
-
-                    if (dist[i_virt_id][k] + dist[k][j_virt_id]).is_finite()
-                        && dist[i_virt_id][j_virt_id] > dist[i_virt_id][k] + dist[k][j_virt_id]
-                    {
-                        dist[i_virt_id][j_virt_id] = dist[i_virt_id][k] + dist[k][j_virt_id]
-                    }
+            for i in 0..vertex_count {
+                if !dist[i][k].is_finite() {
+                    continue;
                 }
 
-                // check for negative cycle
-                for v_id in &vertices {
-                    let v_virt_id = id_map.virt_id_of(*v_id);
-                    if dist[v_virt_id][v_virt_id] < W::zero().into() {
-                        Err(Error::new_ncd())?
+                for j in 0..vertex_count {
+                    let alt = dist[i][k] + dist[k][j];
+                    if alt.is_finite() && alt < dist[i][j] {
+                        dist[i][j] = alt;
+                        next[i][j] = next[i][k];
                     }
                 }
             }
+
+            // A vertex with negative distance to itself is on a negative cycle.
+            // Checking after each round stops distances from growing unboundedly negative.
+            if (0..vertex_count).any(|virt_id| dist[virt_id][virt_id] < W::zero().into()) {
+                Err(Error::new_ncd())?
+            }
         }
 
-        let mut distance_map = HashMap::new();
-        for i in 0..vertex_count {
-            let i_real_id = id_map.real_id_of(i);
-            for j in 0..vertex_count {
-                let j_real_id = id_map.real_id_of(j);
+        Ok(AllPairsShortestPaths { id_map, dist, next })
+    }
+}
+
+/// Distances and paths between all pairs of vertices, as computed by [`execute_with_paths`](crate::algo::FloydWarshall::execute_with_paths).
+pub struct AllPairsShortestPaths<W> {
+    id_map: IdMap,
+    dist: Vec<Vec<Magnitude<W>>>,
+
+    // Virtual id of the vertex after i on the shortest path from i to j in next[i][j], None if j is not reachable from i.
+    next: Vec<Vec<Option<usize>>>,
+}
+
+impl<W: Copy> AllPairsShortestPaths<W> {
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    ///
+    /// # Returns
+    /// * `Some`: Containing distance of `dst_id` from `src_id`, which is infinite if `dst_id` is not reachable from `src_id`.
+    /// * `None`: If either of the vertices is not in the graph.
+    ///
+    /// # Complexity
+    /// O(1)
+    pub fn distance(&self, src_id: usize, dst_id: usize) -> Option<Magnitude<W>> {
+        let (src_virt_id, dst_virt_id) = self.virt_ids_of(src_id, dst_id)?;
+
+        Some(self.dist[src_virt_id][dst_virt_id])
+    }
+
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    ///
+    /// # Returns
+    /// * `Some`: Containing id of the vertex right after `src_id` on the shortest path from `src_id` to `dst_id`. It's `src_id` itself if both are the same.
+    /// * `None`: If `dst_id` is not reachable from `src_id` or either of the vertices is not in the graph.
+    ///
+    /// # Complexity
+    /// O(1)
+    pub fn next_hop(&self, src_id: usize, dst_id: usize) -> Option<usize> {
+        let (src_virt_id, dst_virt_id) = self.virt_ids_of(src_id, dst_id)?;
+
+        self.next[src_virt_id][dst_virt_id].map(|virt_id| self.id_map.real_id_of(virt_id))
+    }
 
-                distance_map.insert((i_real_id, j_real_id), dist[i][j].clone());
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    ///
+    /// # Returns
+    /// * `Some`: Containing ids of the vertices on the shortest path from `src_id` to `dst_id`, including both ends.
+    /// * `None`: If `dst_id` is not reachable from `src_id` or either of the vertices is not in the graph.
+    ///
+    /// # Complexity
+    /// O(length of the path)
+    pub fn path(&self, src_id: usize, dst_id: usize) -> Option<Vec<usize>> {
+        let (mut virt_id, dst_virt_id) = self.virt_ids_of(src_id, dst_id)?;
+        self.next[virt_id][dst_virt_id]?;
+
+        let mut path = vec![src_id];
+        while virt_id != dst_virt_id {
+            virt_id = self.next[virt_id][dst_virt_id].unwrap();
+            path.push(self.id_map.real_id_of(virt_id));
+        }
+
+        Some(path)
+    }
+
+    /// # Returns
+    /// Distances between all pairs of vertices in the form of: (src_id, dst_id) -> distance.
+    pub fn into_distance_map(self) -> HashMap<(usize, usize), Magnitude<W>> {
+        let mut distance_map = HashMap::new();
+        for (i, row) in self.dist.into_iter().enumerate() {
+            let i_real_id = self.id_map.real_id_of(i);
+            for (j, dist) in row.into_iter().enumerate() {
+                distance_map.insert((i_real_id, self.id_map.real_id_of(j)), dist);
             }
         }
 
-        Ok(distance_map)
+        distance_map
+    }
+
+    fn virt_ids_of(&self, src_id: usize, dst_id: usize) -> Option<(usize, usize)> {
+        let src_virt_id = self.id_map.try_virt_id_of(src_id)?;
+        let dst_virt_id = self.id_map.try_virt_id_of(dst_id)?;
+
+        Some((src_virt_id, dst_virt_id))
+    }
+}
+
+impl<W: Copy + Zero + Any + Ord, E: Edge<W>, G> Algorithm<G, (W, E)> for FloydWarshall
+where
+    G: provide::Edges<W, E> + provide::Vertices,
+{
+    type Output<'a>
+        = Result<HashMap<(usize, usize), Magnitude<W>>>
+    where
+        G: 'a,
+        (W, E): 'a;
+
+    fn run(&self, graph: &G) -> Result<HashMap<(usize, usize), Magnitude<W>>> {
+        FloydWarshall::init().execute(graph)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::graph::MatGraph;
+    use crate::graph::{MatGraph, MultiListGraph};
     use crate::provide::*;
-    use crate::storage::{DiMat, Mat};
+    use crate::storage::{DiList, DiMat, Mat};
 
     #[test]
     fn empty_directed_graph() {
@@ -411,4 +570,62 @@ mod tests {
 
         assert!(shortest_paths.is_err());
     }
+
+    #[test]
+    fn paths_match_distances() {
+        // Given: Graph with a removed vertex, parallel edges and a vertex that can not be reached.
+        //          6       1
+        //      a  -->  b  <--  c ---
+        //    1 |       |           |
+        //      |  2 /`````\ 2      |
+        //      |````       ````|   |
+        //      v               v   | 1
+        //      d  ---------->  e --'
+        //              1
+        let mut graph = MultiListGraph::init(DiList::<usize>::init());
+        let removed = graph.add_vertex();
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        graph.remove_vertex_unchecked(removed);
+        graph.add_edge_unchecked(a, b, 6.into());
+        graph.add_edge_unchecked(a, d, 1.into());
+        graph.add_edge_unchecked(b, d, 2.into());
+        graph.add_edge_unchecked(b, e, 2.into());
+        graph.add_edge_unchecked(c, b, 1.into());
+        graph.add_edge_unchecked(e, c, 1.into());
+        graph.add_edge_unchecked(e, c, 7.into());
+        graph.add_edge_unchecked(d, e, 1.into());
+
+        // When:
+        let paths = FloydWarshall::init().execute_with_paths(&graph).unwrap();
+
+        // Then:
+        assert_eq!(paths.path(a, b).unwrap(), vec![a, d, e, c, b]);
+        assert_eq!(paths.path(a, a).unwrap(), vec![a]);
+        assert_eq!(paths.next_hop(b, c), Some(e));
+        assert!(paths.path(b, a).is_none());
+        assert!(paths.distance(b, a).unwrap().is_pos_infinite());
+        assert!(paths.distance(removed, a).is_none());
+        for src_id in graph.vertices() {
+            for dst_id in graph.vertices() {
+                if let Some(path) = paths.path(src_id, dst_id) {
+                    let length = path
+                        .windows(2)
+                        .map(|pair| {
+                            graph
+                                .edges_between_unchecked(pair[0], pair[1])
+                                .into_iter()
+                                .map(|edge| *edge.get_weight())
+                                .min()
+                                .unwrap()
+                        })
+                        .fold(Magnitude::Finite(0), |sum, weight| sum + weight);
+                    assert_eq!(paths.distance(src_id, dst_id).unwrap(), length);
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,416 @@
+use magnitude::Magnitude;
+use num_traits::{Unsigned, Zero};
+use std::any::Any;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use crate::graph::{Edge, EdgeDir};
+use crate::provide::{Edges, Graph, IdMap, Vertices};
+
+// Distances from `src_id` over `arcs`, indexed by virtual id. Unreachable vertices get `None`.
+fn dists_over<W>(arcs: &[Vec<(usize, W)>], src_id: usize) -> Vec<Option<W>>
+where
+    W: Copy + Ord + Zero,
+{
+    let mut dists = vec![None; arcs.len()];
+    let mut heap = BinaryHeap::new();
+    dists[src_id] = Some(W::zero());
+    heap.push(Reverse((W::zero(), src_id)));
+
+    while let Some(Reverse((dist, vertex_id))) = heap.pop() {
+        if dists[vertex_id].is_some_and(|known_dist| known_dist < dist) {
+            continue;
+        }
+
+        for (dst_id, weight) in &arcs[vertex_id] {
+            let alt = dist + *weight;
+            if dists[*dst_id].is_none_or(|dst_dist| alt < dst_dist) {
+                dists[*dst_id] = Some(alt);
+                heap.push(Reverse((alt, *dst_id)));
+            }
+        }
+    }
+
+    dists
+}
+
+// `minuend - subtrahend` if both are known and the difference is positive, zero otherwise.
+fn positive_difference<W>(minuend: Option<W>, subtrahend: Option<W>) -> W
+where
+    W: Copy + Ord + Zero + Unsigned,
+{
+    match (minuend, subtrahend) {
+        (Some(minuend), Some(subtrahend)) if minuend > subtrahend => minuend - subtrahend,
+        _ => W::zero(),
+    }
+}
+
+/// Precomputed distances from and to a few landmark vertices, which give lower bounds on the distance between any two vertices.
+///
+/// By the triangle inequality, distance from `v` to `t` is at least `dist(L, t) - dist(L, v)` and `dist(v, L) - dist(t, L)` for every landmark `L`.
+/// The largest of these bounds is an admissible [`heuristic`](crate::algo::Landmarks::heuristic) for [`AStar`](crate::algo::AStar),
+/// known as ALT (A*, landmarks and triangle inequality). Landmarks far away from each other, at the borders of the graph, give the tightest bounds,
+/// so the search explores far fewer vertices than Dijkstra.
+///
+/// The structure is a snapshot of the graph: After changing weights or removing edges, the bounds may overestimate.
+///
+/// ## Generic Parameters
+/// * `W`: **W**eight type associated with edges.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+/// use prepona::algo::{AStar, Landmarks};
+///
+/// // Given: Graph
+/// //
+/// //      a  --1--  b  --1--  c  --1--  d
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(c, d, 1.into());
+///
+/// // When:
+/// let landmarks = Landmarks::select(&graph, 1);
+/// let sp_subgraph = AStar::init(&graph).execute(&graph, a, c, landmarks.heuristic(c));
+///
+/// // Then: Landmark is at one end of the path, so the bounds are exact.
+/// assert_eq!(landmarks.lower_bound(b, d), 2);
+/// assert_eq!(sp_subgraph.dist(c).unwrap(), 2.into());
+/// ```
+pub struct Landmarks<W> {
+    id_map: IdMap,
+    landmark_ids: Vec<usize>,
+
+    // Distances from and to each landmark, indexed by landmark and then by virtual id of the vertex.
+    dists_from: Vec<Vec<Option<W>>>,
+    dists_to: Vec<Vec<Option<W>>>,
+}
+
+impl<W: Copy + Ord + Zero + Any + Unsigned> Landmarks<W> {
+    // Arcs of the graph and of its reverse, indexed by virtual id. Edges with infinite weight never lie on a shortest path.
+    #[allow(clippy::type_complexity)]
+    fn arcs_of<E, Ty, G>(graph: &G, id_map: &IdMap) -> (Vec<Vec<(usize, W)>>, Vec<Vec<(usize, W)>>)
+    where
+        E: Edge<W>,
+        Ty: EdgeDir,
+        G: Edges<W, E> + Vertices + Graph<W, E, Ty>,
+    {
+        let mut out_arcs = vec![vec![]; id_map.len()];
+        let mut in_arcs = vec![vec![]; id_map.len()];
+
+        for src_real_id in graph.vertices() {
+            let src_id = id_map.virt_id_of(src_real_id);
+
+            for (dst_real_id, edge) in graph.edges_from_unchecked(src_real_id) {
+                if let Magnitude::Finite(weight) = edge.get_weight() {
+                    let dst_id = id_map.virt_id_of(dst_real_id);
+                    out_arcs[src_id].push((dst_id, *weight));
+                    in_arcs[dst_id].push((src_id, *weight));
+                }
+            }
+        }
+
+        (out_arcs, in_arcs)
+    }
+
+    /// Precomputes distances from and to the given landmarks.
+    ///
+    /// # Arguments
+    /// * `graph`: Graph to compute the distances in.
+    /// * `landmark_ids`: Id of the landmark vertices.
+    ///
+    /// # Returns
+    /// The precomputed landmarks.
+    ///
+    /// # Complexity
+    /// O(k.(|V| + |E|).log(|V|)) where k is the number of landmarks.
+    ///
+    /// # Panics
+    /// If any of the landmarks is not in the graph.
+    pub fn init<E, Ty, G>(graph: &G, landmark_ids: Vec<usize>) -> Self
+    where
+        E: Edge<W>,
+        Ty: EdgeDir,
+        G: Edges<W, E> + Vertices + Graph<W, E, Ty>,
+    {
+        let id_map = graph.continuos_id_map();
+        let (out_arcs, in_arcs) = Self::arcs_of(graph, &id_map);
+
+        let mut landmarks = Landmarks {
+            id_map,
+            landmark_ids: vec![],
+            dists_from: vec![],
+            dists_to: vec![],
+        };
+        for landmark_id in landmark_ids {
+            landmarks.add_landmark(&out_arcs, &in_arcs, landmark_id);
+        }
+
+        landmarks
+    }
+
+    /// Selects landmarks that are far away from each other and precomputes distances from and to them.
+    ///
+    /// First landmark is the farthest vertex from the vertex with the smallest id,
+    /// and each next one is the vertex with the largest distance from its closest landmark. Vertices that no landmark reaches are preferred,
+    /// so each part of a disconnected graph gets a landmark.
+    ///
+    /// # Arguments
+    /// * `graph`: Graph to select the landmarks from.
+    /// * `count`: Number of landmarks to select. Fewer are selected if graph has fewer vertices.
+    ///
+    /// # Returns
+    /// The precomputed landmarks.
+    ///
+    /// # Complexity
+    /// O(k.(|V| + |E|).log(|V|)) where k is the number of landmarks.
+    pub fn select<E, Ty, G>(graph: &G, count: usize) -> Self
+    where
+        E: Edge<W>,
+        Ty: EdgeDir,
+        G: Edges<W, E> + Vertices + Graph<W, E, Ty>,
+    {
+        let id_map = graph.continuos_id_map();
+        let (out_arcs, in_arcs) = Self::arcs_of(graph, &id_map);
+        let count = count.min(id_map.len());
+
+        let mut landmarks = Landmarks {
+            id_map,
+            landmark_ids: vec![],
+            dists_from: vec![],
+            dists_to: vec![],
+        };
+        if count == 0 {
+            return landmarks;
+        }
+
+        // Distance of each vertex from its closest landmark, which starts with distances from the vertex with the smallest id.
+        let mut closest_dists = dists_over(&out_arcs, 0);
+        while landmarks.landmark_ids.len() < count {
+            let next_id = (0..closest_dists.len())
+                .filter(|vertex_id| {
+                    !landmarks
+                        .landmark_ids
+                        .contains(&landmarks.id_map.real_id_of(*vertex_id))
+                })
+                .max_by_key(|vertex_id| {
+                    (
+                        closest_dists[*vertex_id].is_none() && !landmarks.landmark_ids.is_empty(),
+                        closest_dists[*vertex_id],
+                        Reverse(*vertex_id),
+                    )
+                })
+                .unwrap();
+
+            let real_id = landmarks.id_map.real_id_of(next_id);
+            landmarks.add_landmark(&out_arcs, &in_arcs, real_id);
+
+            let new_dists = landmarks.dists_from.last().unwrap();
+            if landmarks.landmark_ids.len() == 1 {
+                closest_dists = new_dists.clone();
+            } else {
+                for (closest_dist, new_dist) in closest_dists.iter_mut().zip(new_dists) {
+                    *closest_dist = match (*closest_dist, *new_dist) {
+                        (Some(closest_dist), Some(new_dist)) => Some(closest_dist.min(new_dist)),
+                        (closest_dist, new_dist) => closest_dist.or(new_dist),
+                    };
+                }
+            }
+        }
+
+        landmarks
+    }
+
+    fn add_landmark(
+        &mut self,
+        out_arcs: &[Vec<(usize, W)>],
+        in_arcs: &[Vec<(usize, W)>],
+        landmark_id: usize,
+    ) {
+        let virt_id = self.virt_id_of(landmark_id);
+
+        self.landmark_ids.push(landmark_id);
+        self.dists_from.push(dists_over(out_arcs, virt_id));
+        self.dists_to.push(dists_over(in_arcs, virt_id));
+    }
+
+    fn virt_id_of(&self, real_id: usize) -> usize {
+        self.id_map
+            .try_virt_id_of(real_id)
+            .unwrap_or_else(|| panic!("Vertex with id: {} not found", real_id))
+    }
+
+    /// # Returns
+    /// Id of the landmarks in the order they are added.
+    pub fn landmark_ids(&self) -> &[usize] {
+        &self.landmark_ids
+    }
+
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    ///
+    /// # Returns
+    /// Lower bound on the distance from `src_id` to `dst_id`. Zero if no landmark gives a bound.
+    ///
+    /// # Complexity
+    /// O(k) where k is the number of landmarks.
+    ///
+    /// # Panics
+    /// If either of the vertices was not in the graph when the landmarks were computed.
+    pub fn lower_bound(&self, src_id: usize, dst_id: usize) -> W {
+        let (src_id, dst_id) = (self.virt_id_of(src_id), self.virt_id_of(dst_id));
+
+        self.dists_from
+            .iter()
+            .zip(self.dists_to.iter())
+            .map(|(dists_from, dists_to)| {
+                positive_difference(dists_from[dst_id], dists_from[src_id])
+                    .max(positive_difference(dists_to[src_id], dists_to[dst_id]))
+            })
+            .max()
+            .unwrap_or_else(W::zero)
+    }
+
+    /// # Arguments
+    /// `dst_id`: Id of the destination vertex.
+    ///
+    /// # Returns
+    /// Heuristic for [`AStar`](crate::algo::AStar) that estimates the distance from a vertex to `dst_id` using [`lower_bound`](crate::algo::Landmarks::lower_bound).
+    ///
+    /// # Panics
+    /// If `dst_id` was not in the graph when the landmarks were computed.
+    pub fn heuristic(&self, dst_id: usize) -> impl Fn(usize) -> W + '_ {
+        self.virt_id_of(dst_id);
+
+        move |vertex_id| self.lower_bound(vertex_id, dst_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::{AStar, Dijkstra};
+    use crate::graph::MultiListGraph;
+    use crate::misc::rng::Rng;
+    use crate::storage::{DiList, List};
+    use std::cell::RefCell;
+    use std::collections::HashSet;
+
+    #[test]
+    fn bounds_are_admissible_in_random_graphs() {
+        let mut rng = Rng::seed_from(3158);
+
+        for _ in 0..10 {
+            // Given: Random directed graph with a removed vertex.
+            let mut graph = MultiListGraph::init(DiList::<usize>::init());
+            for _ in 0..20 {
+                graph.add_vertex();
+            }
+            for _ in 0..50 {
+                let src_id = rng.next_below(20);
+                let dst_id = rng.next_below(20);
+                graph.add_edge_unchecked(src_id, dst_id, rng.next_below(10).into());
+            }
+            graph.remove_vertex_unchecked(rng.next_below(20));
+
+            // When:
+            let landmarks = Landmarks::select(&graph, 3);
+
+            // Then:
+            assert_eq!(landmarks.landmark_ids().len(), 3);
+            for src_id in graph.vertices() {
+                let sp_subgraph = Dijkstra::init(&graph).execute(&graph, src_id);
+
+                for dst_id in graph.vertices() {
+                    let dist = sp_subgraph.dist(dst_id).unwrap();
+                    if dist.is_finite() {
+                        assert!(landmarks.lower_bound(src_id, dst_id) <= dist.unwrap());
+
+                        let a_star = AStar::init(&graph).execute(
+                            &graph,
+                            src_id,
+                            dst_id,
+                            landmarks.heuristic(dst_id),
+                        );
+                        assert_eq!(a_star.dist(dst_id).unwrap(), dist);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn disconnected_parts_get_landmarks() {
+        // Given: Graph
+        //
+        //      a  ---  b      c  ---  d
+        //
+        let mut graph = MultiListGraph::init(List::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(c, d, 1.into());
+
+        // When:
+        let landmarks = Landmarks::select(&graph, 5);
+        let given = Landmarks::init(&graph, vec![d]);
+
+        // Then:
+        assert_eq!(landmarks.landmark_ids().len(), 4);
+        assert_eq!(&landmarks.landmark_ids()[..2], &[b, c]);
+        assert_eq!(given.lower_bound(c, d), 1);
+        assert_eq!(given.lower_bound(a, b), 0);
+    }
+
+    #[test]
+    fn explores_fewer_vertices_than_dijkstra() {
+        // Given: 20 x 20 grid.
+        let mut graph = MultiListGraph::init(List::<usize>::init());
+        for _ in 0..400 {
+            graph.add_vertex();
+        }
+        for row in 0..20 {
+            for col in 0..20 {
+                if col + 1 < 20 {
+                    graph.add_edge_unchecked(row * 20 + col, row * 20 + col + 1, 1.into());
+                }
+                if row + 1 < 20 {
+                    graph.add_edge_unchecked(row * 20 + col, (row + 1) * 20 + col, 1.into());
+                }
+            }
+        }
+        let landmarks = Landmarks::select(&graph, 4);
+
+        // When: Searching between two corners of the grid.
+        let explored = |use_landmarks: bool| {
+            let visited = RefCell::new(HashSet::new());
+            let heuristic = landmarks.heuristic(19);
+            let sp_subgraph = AStar::init(&graph).execute(&graph, 0, 19, |vertex_id| {
+                visited.borrow_mut().insert(vertex_id);
+                if use_landmarks {
+                    heuristic(vertex_id)
+                } else {
+                    0
+                }
+            });
+
+            assert_eq!(sp_subgraph.dist(19).unwrap(), 19.into());
+            let count = visited.borrow().len();
+            count
+        };
+
+        // Then:
+        assert!(explored(true) * 4 < explored(false));
+    }
+}
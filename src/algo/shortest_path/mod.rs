@@ -1,7 +1,124 @@
+use magnitude::Magnitude;
+use std::collections::{HashMap, HashSet};
+
+use crate::algo::Orientation;
+use crate::graph::subgraph::ShortestPathSubgraph;
+use crate::graph::{Edge, EdgeDir};
+use crate::provide::{Direction, Edges, IdMap, Neighbors};
+
+mod astar;
 mod bellman_ford;
+mod bfs;
+mod bfs_layers;
+mod contraction_hierarchy;
+mod dag;
+mod delta_stepping;
 mod dijkstra;
+mod dynamic_sssp;
 mod floyd_warshall;
+mod landmarks;
+mod path_count;
+mod sp_dag;
+mod voronoi;
+mod within;
 
+pub use astar::AStar;
 pub use bellman_ford::BellmanFord;
+pub use bfs::BfsShortestPath;
+pub use bfs_layers::bfs_layers;
+pub use contraction_hierarchy::ContractionHierarchy;
+pub use dag::{dag_longest_path, dag_shortest_path};
+pub use delta_stepping::DeltaStepping;
 pub use dijkstra::Dijkstra;
-pub use floyd_warshall::FloydWarshall;
+pub use dynamic_sssp::DynamicSSSP;
+pub use floyd_warshall::{AllPairsShortestPaths, FloydWarshall};
+pub use landmarks::Landmarks;
+pub use path_count::{count_all_shortest_paths, count_shortest_paths};
+pub use sp_dag::{shortest_path_dag, ShortestPathDag};
+pub use voronoi::{voronoi_partition, VoronoiPartition};
+pub use within::{bfs_within, dijkstra_filtered, dijkstra_within};
+
+// Builds the shortest path tree out of the distance and parent of each vertex, indexed by virtual id.
+// Parent of each vertex is in the format of (virtual id of parent, id of the edge from parent). Unreached vertices get infinite distance.
+// Edges of the tree keep their direction in the graph, even if the search followed them in another `orientation`.
+fn sp_subgraph_of<'a, W, E, Ty, G>(
+    graph: &'a G,
+    src_id: usize,
+    dists: Vec<Option<Magnitude<W>>>,
+    parents: Vec<Option<(usize, usize)>>,
+    id_map: &IdMap,
+    orientation: Orientation,
+) -> ShortestPathSubgraph<'a, W, E, Ty, G>
+where
+    W: Copy,
+    E: Edge<W>,
+    Ty: EdgeDir,
+    G: Edges<W, E> + Neighbors + Direction<Ty>,
+{
+    let tree_edges: Vec<(usize, usize, usize)> = parents
+        .into_iter()
+        .enumerate()
+        .filter_map(|(virt_id, parent)| {
+            parent.map(|(parent_virt_id, edge_id)| {
+                (
+                    id_map.real_id_of(parent_virt_id),
+                    id_map.real_id_of(virt_id),
+                    edge_id,
+                )
+            })
+        })
+        .collect();
+
+    // Under `Ignore`, an edge is followed in its direction if it goes out of the parent.
+    // Outgoing edges of each parent are only scanned once.
+    let mut outgoing_ids_of: HashMap<usize, HashSet<usize>> = HashMap::new();
+    let mut is_followed_forward = |parent_id: usize, edge_id: usize| match orientation {
+        _ if Ty::is_undirected() => true,
+        Orientation::Original => true,
+        Orientation::Reversed => false,
+        Orientation::Ignore => outgoing_ids_of
+            .entry(parent_id)
+            .or_insert_with(|| {
+                graph
+                    .edges_from_unchecked(parent_id)
+                    .into_iter()
+                    .map(|(_, edge)| edge.get_id())
+                    .collect()
+            })
+            .contains(&edge_id),
+    };
+
+    let parent_map: HashMap<usize, usize> = tree_edges
+        .iter()
+        .map(|(parent_id, child_id, _)| (*child_id, *parent_id))
+        .collect();
+    let edges: Vec<(usize, usize, usize)> = tree_edges
+        .into_iter()
+        .map(|(parent_id, child_id, edge_id)| {
+            if is_followed_forward(parent_id, edge_id) {
+                (parent_id, child_id, edge_id)
+            } else {
+                (child_id, parent_id, edge_id)
+            }
+        })
+        .collect();
+
+    let distance_map = dists
+        .into_iter()
+        .enumerate()
+        .map(|(virt_id, dist)| {
+            (
+                id_map.real_id_of(virt_id),
+                dist.unwrap_or(Magnitude::PosInfinite),
+            )
+        })
+        .collect();
+
+    let vertices = edges
+        .iter()
+        .flat_map(|(src_id, dst_id, _)| vec![*src_id, *dst_id])
+        .chain(std::iter::once(src_id))
+        .collect::<HashSet<usize>>();
+
+    ShortestPathSubgraph::init_with_parents(graph, edges, vertices, distance_map, parent_map)
+}
@@ -0,0 +1,407 @@
+use anyhow::Result;
+use magnitude::Magnitude;
+use num_traits::{Unsigned, Zero};
+use std::any::Any;
+use std::collections::HashMap;
+
+use super::{shortest_path_dag, ShortestPathDag};
+use crate::algo::Error;
+use crate::graph::{Edge, EdgeDir};
+use crate::provide::{Edges, Graph, Vertices};
+
+// Number of shortest paths to each reached vertex, or `None` if the number does not fit in `u128`.
+// Vertices on or after a cycle of edges with zero weight have infinitely many shortest paths.
+// Order of the dag is topological, so the counts of all predecessors are known when a vertex is reached.
+fn path_counts_of<W: Copy + PartialEq>(
+    dag: &ShortestPathDag<W>,
+) -> HashMap<usize, Option<Magnitude<u128>>> {
+    let mut counts: HashMap<usize, Option<Magnitude<u128>>> = HashMap::new();
+    for group in dag.order().chunk_by(|u, v| dag.dist(*u) == dag.dist(*v)) {
+        // Members of a component of undirected edges with zero weight reach each other at no cost,
+        // so each of them gets the paths to all of the members from outside the component.
+        let mut component_counts: HashMap<usize, Option<u128>> = HashMap::new();
+        for vertex_id in group {
+            if dag.has_infinitely_many_paths(*vertex_id) {
+                counts.insert(*vertex_id, Some(Magnitude::PosInfinite));
+                continue;
+            }
+
+            let component_id = dag.component_of(*vertex_id);
+            let initial_count = component_counts
+                .get(&component_id)
+                .copied()
+                .unwrap_or(Some(0));
+            let initial_count = if *vertex_id == dag.src_id() {
+                initial_count.and_then(|count| count.checked_add(1))
+            } else {
+                initial_count
+            };
+            // Predecessors of a vertex with finitely many paths also have finitely many.
+            let count = initial_count.and_then(|initial_count| {
+                dag.predecessors(*vertex_id)
+                    .iter()
+                    .filter(|(predecessor_id, _)| dag.component_of(*predecessor_id) != component_id)
+                    .try_fold(initial_count, |count, (predecessor_id, _)| {
+                        counts[predecessor_id].and_then(|predecessor_count| {
+                            count.checked_add(predecessor_count.unwrap())
+                        })
+                    })
+            });
+            component_counts.insert(component_id, count);
+            counts.insert(*vertex_id, count.map(Magnitude::Finite));
+        }
+
+        for vertex_id in group {
+            if !dag.has_infinitely_many_paths(*vertex_id) {
+                let count = component_counts[&dag.component_of(*vertex_id)];
+                counts.insert(*vertex_id, count.map(Magnitude::Finite));
+            }
+        }
+    }
+
+    counts
+}
+
+/// Counts the shortest paths from a single source to a destination.
+///
+/// Paths are counted as sequences of edges, so each parallel edge on a shortest path makes a separate path.
+/// To count paths by the number of edges instead of their weights, use a graph with the same weight for every edge.
+///
+/// # Arguments
+/// * `graph`: Graph to count the shortest paths in.
+/// * `src_id`: Id of the source vertex.
+/// * `dst_id`: Id of the destination vertex.
+///
+/// # Returns
+/// * `Ok`: Containing number of the shortest paths, which is zero if the destination is not reachable.
+/// * `Err`:
+///     * [`CountOverflow`](crate::algo::ErrorKind::CountOverflow) if the number does not fit in `u128`.
+///     * [`InfinitelyManyPaths`](crate::algo::ErrorKind::InfinitelyManyPaths) if a cycle of edges with zero weight lies on a shortest path to the destination.
+///
+/// # Complexity
+/// O((|V| + |E|).log(|V|))
+///
+/// # Panics
+/// If `src_id` is not in the graph.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+/// use prepona::algo::count_shortest_paths;
+///
+/// // Given: Graph
+/// //
+/// //      a  ---  b  ---  d
+/// //      |       |       |
+/// //      c  ---  e  ---  f
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// let e = graph.add_vertex();
+/// let f = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(a, c, 1.into());
+/// graph.add_edge_unchecked(b, d, 1.into());
+/// graph.add_edge_unchecked(b, e, 1.into());
+/// graph.add_edge_unchecked(c, e, 1.into());
+/// graph.add_edge_unchecked(d, f, 1.into());
+/// graph.add_edge_unchecked(e, f, 1.into());
+///
+/// // When:
+/// let count = count_shortest_paths(&graph, a, f).unwrap();
+///
+/// // Then: a-b-d-f, a-b-e-f and a-c-e-f.
+/// assert_eq!(count, 3);
+/// ```
+pub fn count_shortest_paths<W, E, Ty, G>(graph: &G, src_id: usize, dst_id: usize) -> Result<u128>
+where
+    W: Copy + Ord + Zero + Any + Unsigned,
+    E: Edge<W>,
+    Ty: EdgeDir,
+    G: Edges<W, E> + Vertices + Graph<W, E, Ty>,
+{
+    let dag = shortest_path_dag(graph, src_id);
+
+    match path_counts_of(&dag).get(&dst_id) {
+        None => Ok(0),
+        Some(None) => Err(Error::new_co(dst_id))?,
+        Some(Some(Magnitude::Finite(count))) => Ok(*count),
+        Some(Some(_)) => Err(Error::new_imp(dst_id))?,
+    }
+}
+
+/// Counts the shortest paths from a single source to all other vertices.
+///
+/// For more info checkout [`count_shortest_paths`](crate::algo::count_shortest_paths).
+///
+/// # Arguments
+/// * `graph`: Graph to count the shortest paths in.
+/// * `src_id`: Id of the source vertex.
+///
+/// # Returns
+/// * `Ok`: Containing number of the shortest paths to each reachable vertex. Source has one path to itself.
+///   Vertices with a cycle of edges with zero weight on their shortest paths have `PosInfinite` paths.
+/// * `Err`: [`CountOverflow`](crate::algo::ErrorKind::CountOverflow) if the finite number for any vertex does not fit in `u128`.
+///
+/// # Complexity
+/// O((|V| + |E|).log(|V|))
+///
+/// # Panics
+/// If `src_id` is not in the graph.
+pub fn count_all_shortest_paths<W, E, Ty, G>(
+    graph: &G,
+    src_id: usize,
+) -> Result<HashMap<usize, Magnitude<u128>>>
+where
+    W: Copy + Ord + Zero + Any + Unsigned,
+    E: Edge<W>,
+    Ty: EdgeDir,
+    G: Edges<W, E> + Vertices + Graph<W, E, Ty>,
+{
+    let dag = shortest_path_dag(graph, src_id);
+
+    let mut counts = HashMap::new();
+    for (vertex_id, count) in path_counts_of(&dag) {
+        match count {
+            Some(count) => counts.insert(vertex_id, count),
+            None => Err(Error::new_co(vertex_id))?,
+        };
+    }
+
+    Ok(counts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::ErrorKind;
+    use crate::graph::{ListGraph, MatGraph, MultiListGraph};
+    use crate::storage::{DiList, DiMat, List};
+
+    #[test]
+    fn counts_of_weighted_graph() {
+        // Given: Graph
+        //
+        //      a  --1-->  b  --1-->  d      e
+        //      |                     ^
+        //      |--1-->  c  ---1------|
+        //      |                     |
+        //      '----------3----------'
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, d, 1.into());
+        graph.add_edge_unchecked(a, c, 1.into());
+        graph.add_edge_unchecked(c, d, 1.into());
+        graph.add_edge_unchecked(a, d, 3.into());
+
+        // When:
+        let counts = count_all_shortest_paths(&graph, a).unwrap();
+
+        // Then: Direct edge is longer.
+        assert_eq!(counts[&a], 1.into());
+        assert_eq!(counts[&b], 1.into());
+        assert_eq!(counts[&d], 2.into());
+        assert!(!counts.contains_key(&e));
+        assert_eq!(count_shortest_paths(&graph, a, e).unwrap(), 0);
+        assert_eq!(count_shortest_paths(&graph, d, a).unwrap(), 0);
+    }
+
+    #[test]
+    fn parallel_edges_and_zero_weights() {
+        // Given: Graph
+        //
+        //           1              0
+        //      a  =====  b  -->  c  -->  d
+        //           1    '-------0-------^
+        //
+        let mut graph = MultiListGraph::init(DiList::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 0.into());
+        graph.add_edge_unchecked(c, d, 0.into());
+        graph.add_edge_unchecked(b, d, 0.into());
+
+        // When:
+        let count = count_shortest_paths(&graph, a, d).unwrap();
+
+        // Then:
+        assert_eq!(count, 4);
+
+        // When: Closing a zero weight cycle.
+        graph.add_edge_unchecked(d, c, 0.into());
+
+        // Then: Paths to b do not go through the cycle.
+        let error = count_shortest_paths(&graph, a, d).unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<Error>().unwrap().kind(),
+            ErrorKind::InfinitelyManyPaths
+        ));
+        assert_eq!(count_shortest_paths(&graph, a, b).unwrap(), 2);
+    }
+
+    #[test]
+    fn undirected_zero_weight_edges() {
+        // Given: Graph
+        //
+        //      a  --0--  b  --1--  d
+        //      |                   |
+        //      '--0--  c  ----1----'
+        //
+        let mut graph = ListGraph::init(List::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 0.into());
+        let ac = graph.add_edge_unchecked(a, c, 0.into());
+        graph.add_edge_unchecked(b, d, 1.into());
+        graph.add_edge_unchecked(c, d, 1.into());
+
+        // When:
+        let counts = count_all_shortest_paths(&graph, a).unwrap();
+
+        // Then:
+        assert_eq!(count_shortest_paths(&graph, a, b).unwrap(), 1);
+        assert_eq!(count_shortest_paths(&graph, b, a).unwrap(), 1);
+        assert_eq!(counts[&a], 1.into());
+        assert_eq!(counts[&c], 1.into());
+        assert_eq!(counts[&d], 2.into());
+
+        // When: Connecting b and c, which reach each other through the source.
+        let bc = graph.add_edge_unchecked(b, c, 0.into());
+
+        // Then:
+        let error = count_shortest_paths(&graph, a, b).unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<Error>().unwrap().kind(),
+            ErrorKind::InfinitelyManyPaths
+        ));
+
+        // When: Making b and c only reachable through each other.
+        graph.remove_edge_unchecked(b, c, bc);
+        graph.remove_edge_unchecked(a, c, ac);
+        graph.add_edge_unchecked(b, c, 0.into());
+        let counts = count_all_shortest_paths(&graph, a).unwrap();
+
+        // Then: a-b-c-d and a-b-d.
+        assert_eq!(counts[&c], 1.into());
+        assert_eq!(counts[&d], 2.into());
+    }
+
+    #[test]
+    fn zero_weight_cycle_away_from_destination() {
+        // Given: Graph
+        //
+        //      a  --1-->  b
+        //      |
+        //      '--1-->  c  <--0-->  d  --1-->  e
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(a, c, 1.into());
+        graph.add_edge_unchecked(c, d, 0.into());
+        graph.add_edge_unchecked(d, c, 0.into());
+        graph.add_edge_unchecked(d, e, 1.into());
+
+        // When:
+        let count = count_shortest_paths(&graph, a, b).unwrap();
+        let counts = count_all_shortest_paths(&graph, a).unwrap();
+
+        // Then: Only vertices on or after the cycle have infinitely many paths.
+        assert_eq!(count, 1);
+        assert_eq!(counts[&a], 1.into());
+        assert_eq!(counts[&b], 1.into());
+        assert!(counts[&c].is_pos_infinite());
+        assert!(counts[&d].is_pos_infinite());
+        assert!(counts[&e].is_pos_infinite());
+        let error = count_shortest_paths(&graph, a, c).unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<Error>().unwrap().kind(),
+            ErrorKind::InfinitelyManyPaths
+        ));
+    }
+
+    #[test]
+    fn undirected_zero_weight_cycle_away_from_destination() {
+        // Given: Graph
+        //
+        //      a  --1--  b
+        //      |
+        //      '--1--  c  --0--  d
+        //              |         |
+        //              '---0---  e
+        //
+        let mut graph = ListGraph::init(List::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(a, c, 1.into());
+        graph.add_edge_unchecked(c, d, 0.into());
+        graph.add_edge_unchecked(d, e, 0.into());
+        graph.add_edge_unchecked(c, e, 0.into());
+
+        // When:
+        let counts = count_all_shortest_paths(&graph, a).unwrap();
+
+        // Then:
+        assert_eq!(count_shortest_paths(&graph, a, b).unwrap(), 1);
+        assert_eq!(counts[&b], 1.into());
+        assert!(counts[&d].is_pos_infinite());
+        assert!(count_shortest_paths(&graph, a, e).is_err());
+    }
+
+    #[test]
+    fn overflowing_counts() {
+        // Given: Chain of 130 diamonds, doubling the count at each one.
+        let mut graph = ListGraph::init(List::<usize>::init());
+        let mut tail = graph.add_vertex();
+        let src_id = tail;
+        let mut tails = vec![tail];
+        for _ in 0..130 {
+            let top = graph.add_vertex();
+            let bottom = graph.add_vertex();
+            let next = graph.add_vertex();
+            graph.add_edge_unchecked(tail, top, 1.into());
+            graph.add_edge_unchecked(tail, bottom, 1.into());
+            graph.add_edge_unchecked(top, next, 1.into());
+            graph.add_edge_unchecked(bottom, next, 1.into());
+            tail = next;
+            tails.push(tail);
+        }
+
+        // When:
+        let fitting = count_shortest_paths(&graph, src_id, tails[127]).unwrap();
+        let overflowing = count_shortest_paths(&graph, src_id, tails[128]).unwrap_err();
+
+        // Then:
+        assert_eq!(fitting, 1 << 127);
+        assert!(matches!(
+            overflowing.downcast_ref::<Error>().unwrap().kind(),
+            ErrorKind::CountOverflow
+        ));
+        assert!(count_all_shortest_paths(&graph, src_id).is_err());
+    }
+}
@@ -0,0 +1,637 @@
+use magnitude::Magnitude;
+use num_traits::{Unsigned, Zero};
+use std::any::Any;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use crate::graph::{Edge, EdgeDir};
+use crate::provide::{Edges, Graph, Vertices};
+
+/// Directed acyclic graph of all shortest paths from a single source, as computed by [`shortest_path_dag`](crate::algo::shortest_path_dag).
+///
+/// Each reached vertex keeps every edge that ends a shortest path to it, so all shortest paths can be walked backwards from any vertex.
+/// The only exception is an undirected edge with zero weight between two vertices with the same distance:
+/// It's kept in the direction it was first followed, so paths that cross it the other way can not be walked backwards.
+/// Algorithms like betweenness centrality and shortest path counting traverse the vertices in [`order`](crate::algo::ShortestPathDag::order)
+/// or in reverse of it, to accumulate values along the predecessors.
+///
+/// ## Generic Parameters
+/// * `W`: **W**eight type associated with edges.
+#[derive(Debug, Clone)]
+pub struct ShortestPathDag<W> {
+    src_id: usize,
+    order: Vec<usize>,
+    dists: HashMap<usize, W>,
+    predecessors: HashMap<usize, Vec<(usize, usize)>>,
+    // Representative of the component of zero weight edges that each vertex of an undirected graph is in.
+    component_of: HashMap<usize, usize>,
+    infinite_ids: HashSet<usize>,
+}
+
+impl<W: Copy> ShortestPathDag<W> {
+    /// # Returns
+    /// Id of the source vertex.
+    pub fn src_id(&self) -> usize {
+        self.src_id
+    }
+
+    /// # Returns
+    /// Id of the reached vertices in non-decreasing order of their distance from the source, starting with the source itself.
+    /// Every vertex comes after its predecessors, even when edges have zero weight.
+    pub fn order(&self) -> &[usize] {
+        &self.order
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// * `Some`: Containing the distance of the vertex from the source.
+    /// * `None`: If the vertex is not reachable from the source.
+    pub fn dist(&self, vertex_id: usize) -> Option<Magnitude<W>> {
+        self.dists
+            .get(&vertex_id)
+            .map(|dist| Magnitude::Finite(*dist))
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// Last hops of the shortest paths to the vertex in the format of (`predecessor_id`, `edge_id`).
+    /// Each parallel edge is a separate hop. Empty for the source and for unreachable vertices.
+    /// Predecessors always have a smaller distance, or the same distance and an earlier position in [`order`](crate::algo::ShortestPathDag::order).
+    pub fn predecessors(&self, vertex_id: usize) -> &[(usize, usize)] {
+        self.predecessors
+            .get(&vertex_id)
+            .map_or(&[], |predecessors| predecessors.as_slice())
+    }
+
+    /// # Returns
+    /// All edges of the dag in the format of (`src_id`, `dst_id`, `edge_id`), where `src_id` is a predecessor of `dst_id`.
+    pub fn edges(&self) -> Vec<(usize, usize, usize)> {
+        self.order
+            .iter()
+            .flat_map(|dst_id| {
+                self.predecessors(*dst_id)
+                    .iter()
+                    .map(move |(src_id, edge_id)| (*src_id, *dst_id, *edge_id))
+            })
+            .collect()
+    }
+
+    /// # Returns
+    /// `true` if a cycle of edges with zero weight is reachable from the source.
+    /// Vertices on such a cycle are on each other's shortest paths, so one edge of the cycle is left out of the dag to keep it acyclic.
+    pub fn has_zero_weight_cycle(&self) -> bool {
+        !self.infinite_ids.is_empty()
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// `true` if a cycle of edges with zero weight is on a shortest path to the vertex, so there are infinitely many shortest paths to it.
+    /// Vertices that are not on or after such a cycle are not affected by it.
+    pub fn has_infinitely_many_paths(&self, vertex_id: usize) -> bool {
+        self.infinite_ids.contains(&vertex_id)
+    }
+
+    // Representative of the component of undirected edges with zero weight that the vertex is in.
+    // Every vertex of a component can be reached from the others at no cost, so they all have the same shortest paths up to their last edges.
+    // Vertices of directed graphs are each in their own component.
+    pub(super) fn component_of(&self, vertex_id: usize) -> usize {
+        self.component_of
+            .get(&vertex_id)
+            .copied()
+            .unwrap_or(vertex_id)
+    }
+}
+
+// Sorts the vertices of each group with the same distance so every vertex comes after its predecessors.
+// Only edges with zero weight connect vertices of the same group, so groups are sorted on their own with kahn algorithm.
+// When a group is stuck on a cycle, edges from unsorted vertices to the earliest settled unsorted vertex are dropped.
+//
+// # Returns
+// Id of vertices whose edges were dropped. Each of them is on or after a cycle.
+fn sort_groups<W: Copy + PartialEq>(
+    order: &mut Vec<usize>,
+    dists: &HashMap<usize, W>,
+    predecessors: &mut HashMap<usize, Vec<(usize, usize)>>,
+) -> Vec<usize> {
+    let mut dropped_ids = vec![];
+    let mut sorted = Vec::with_capacity(order.len());
+
+    for group in order.chunk_by(|u, v| dists[u] == dists[v]) {
+        let index_of: HashMap<usize, usize> = group
+            .iter()
+            .enumerate()
+            .map(|(index, vertex_id)| (*vertex_id, index))
+            .collect();
+
+        let mut in_degrees = vec![0; group.len()];
+        let mut successors = vec![vec![]; group.len()];
+        for (index, vertex_id) in group.iter().enumerate() {
+            for (predecessor_id, _) in predecessors.get(vertex_id).into_iter().flatten() {
+                if let Some(predecessor_index) = index_of.get(predecessor_id) {
+                    in_degrees[index] += 1;
+                    successors[*predecessor_index].push(index);
+                }
+            }
+        }
+
+        let mut is_sorted = vec![false; group.len()];
+        let mut ready: BinaryHeap<Reverse<usize>> = (0..group.len())
+            .filter(|index| in_degrees[*index] == 0)
+            .map(Reverse)
+            .collect();
+        let mut earliest_unsorted = 0;
+        let mut sorted_count = 0;
+        while sorted_count < group.len() {
+            let index = match ready.pop() {
+                Some(Reverse(index)) if is_sorted[index] => continue,
+                Some(Reverse(index)) => index,
+                None => {
+                    while is_sorted[earliest_unsorted] {
+                        earliest_unsorted += 1;
+                    }
+                    let vertex_id = group[earliest_unsorted];
+                    predecessors
+                        .get_mut(&vertex_id)
+                        .unwrap()
+                        .retain(|(predecessor_id, _)| {
+                            index_of
+                                .get(predecessor_id)
+                                .is_none_or(|predecessor_index| is_sorted[*predecessor_index])
+                        });
+                    dropped_ids.push(vertex_id);
+
+                    earliest_unsorted
+                }
+            };
+
+            is_sorted[index] = true;
+            sorted_count += 1;
+            sorted.push(group[index]);
+
+            for successor_index in &successors[index] {
+                if !is_sorted[*successor_index] {
+                    in_degrees[*successor_index] -= 1;
+                    if in_degrees[*successor_index] == 0 {
+                        ready.push(Reverse(*successor_index));
+                    }
+                }
+            }
+        }
+    }
+
+    *order = sorted;
+
+    dropped_ids
+}
+
+// Finds components of undirected edges with zero weight, which only connect vertices of the same group with the same distance.
+// Each such edge is kept in the predecessors exactly once, so a component has a cycle if an edge connects two of its vertices again.
+//
+// # Returns
+// (component of each vertex, representatives of components with a cycle)
+fn zero_weight_components<W: Copy + PartialEq>(
+    order: &[usize],
+    dists: &HashMap<usize, W>,
+    predecessors: &HashMap<usize, Vec<(usize, usize)>>,
+) -> (HashMap<usize, usize>, HashSet<usize>) {
+    let mut component_of = HashMap::new();
+    let mut cyclic_ids = HashSet::new();
+
+    for group in order.chunk_by(|u, v| dists[u] == dists[v]) {
+        let index_of: HashMap<usize, usize> = group
+            .iter()
+            .enumerate()
+            .map(|(index, vertex_id)| (*vertex_id, index))
+            .collect();
+
+        // Union-find over indices of the group with path halving.
+        let mut parents: Vec<usize> = (0..group.len()).collect();
+        let find = |parents: &mut Vec<usize>, mut index: usize| {
+            while parents[index] != index {
+                parents[index] = parents[parents[index]];
+                index = parents[index];
+            }
+            index
+        };
+
+        let mut is_cyclic = vec![false; group.len()];
+        for (index, vertex_id) in group.iter().enumerate() {
+            for (predecessor_id, _) in predecessors.get(vertex_id).into_iter().flatten() {
+                if let Some(predecessor_index) = index_of.get(predecessor_id) {
+                    let root = find(&mut parents, index);
+                    let predecessor_root = find(&mut parents, *predecessor_index);
+                    if root == predecessor_root {
+                        is_cyclic[root] = true;
+                    } else {
+                        parents[predecessor_root] = root;
+                        is_cyclic[root] |= is_cyclic[predecessor_root];
+                    }
+                }
+            }
+        }
+
+        for (index, vertex_id) in group.iter().enumerate() {
+            let root = find(&mut parents, index);
+            component_of.insert(*vertex_id, group[root]);
+            if is_cyclic[root] {
+                cyclic_ids.insert(group[root]);
+            }
+        }
+    }
+
+    (component_of, cyclic_ids)
+}
+
+/// Builds the directed acyclic graph of all shortest paths from a single source, using dijkstra algorithm.
+///
+/// Unlike [`Dijkstra`](crate::algo::Dijkstra), which keeps one parent for each vertex, every edge that ends a shortest path is kept.
+///
+/// # Arguments
+/// * `graph`: Graph to search for the shortest paths in.
+/// * `src_id`: Id of the source vertex.
+///
+/// # Returns
+/// The dag of shortest paths. Edges with infinite weight and self loops are ignored.
+///
+/// Edges with zero weight can connect vertices with the same distance in both directions, but the result is always acyclic:
+/// * The source never gets a predecessor.
+/// * An undirected edge with zero weight is only followed away from the source, not back along itself.
+/// * Cycles that contain edges with zero weight, and make vertices reachable from each other at no cost, are broken
+///   by leaving out the edge that closes them. They are reported by [`has_zero_weight_cycle`](crate::algo::ShortestPathDag::has_zero_weight_cycle).
+///
+/// # Complexity
+/// O((|V| + |E|).log(|V|))
+///
+/// # Panics
+/// If `src_id` is not in the graph.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+/// use prepona::algo::shortest_path_dag;
+///
+/// // Given: Graph
+/// //
+/// //      a  --1--  b  --1--  d
+/// //      |                   |
+/// //      '--1--  c  ----1----'
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, d, 1.into());
+/// graph.add_edge_unchecked(a, c, 1.into());
+/// graph.add_edge_unchecked(c, d, 1.into());
+///
+/// // When:
+/// let dag = shortest_path_dag(&graph, a);
+///
+/// // Then: d can be reached through both b and c.
+/// let mut predecessors: Vec<usize> = dag.predecessors(d).iter().map(|(vertex_id, _)| *vertex_id).collect();
+/// predecessors.sort_unstable();
+/// assert_eq!(predecessors, vec![b, c]);
+/// assert_eq!(dag.dist(d).unwrap(), 2.into());
+/// assert_eq!(dag.order().last(), Some(&d));
+/// ```
+pub fn shortest_path_dag<W, E, Ty, G>(graph: &G, src_id: usize) -> ShortestPathDag<W>
+where
+    W: Copy + Ord + Zero + Any + Unsigned,
+    E: Edge<W>,
+    Ty: EdgeDir,
+    G: Edges<W, E> + Vertices + Graph<W, E, Ty>,
+{
+    assert!(
+        graph.contains_vertex(src_id),
+        "Vertex with id: {} not found",
+        src_id
+    );
+
+    let mut dists = HashMap::new();
+    let mut predecessors: HashMap<usize, Vec<(usize, usize)>> = HashMap::new();
+    let mut order = vec![];
+
+    let mut is_source_on_cycle = false;
+
+    let mut settled_dists = HashMap::new();
+    let mut heap = BinaryHeap::new();
+    dists.insert(src_id, W::zero());
+    heap.push(Reverse((W::zero(), src_id)));
+
+    while let Some(Reverse((dist, vertex_id))) = heap.pop() {
+        if settled_dists.contains_key(&vertex_id) {
+            continue;
+        }
+        settled_dists.insert(vertex_id, dist);
+        order.push(vertex_id);
+
+        for (dst_id, edge) in graph.edges_from_unchecked(vertex_id) {
+            let weight = match edge.get_weight() {
+                Magnitude::Finite(weight) if dst_id != vertex_id => *weight,
+                _ => continue,
+            };
+            let new_dist = dist + weight;
+
+            // Going back along the undirected edge that reached this vertex is not a new path.
+            let is_back_edge = Ty::is_undirected()
+                && predecessors
+                    .get(&vertex_id)
+                    .is_some_and(|hops| hops.contains(&(dst_id, edge.get_id())));
+            if is_back_edge {
+                continue;
+            }
+            if dst_id == src_id {
+                // Only an edge with zero weight can be on a shortest path to the source, which closes a cycle through it.
+                // Undirected edges to the source are already kept from the side of the source.
+                is_source_on_cycle |= Ty::is_directed() && new_dist == W::zero();
+                continue;
+            }
+
+            match dists.get(&dst_id) {
+                Some(dst_dist) if new_dist > *dst_dist => {}
+                Some(dst_dist) if new_dist == *dst_dist => {
+                    predecessors
+                        .entry(dst_id)
+                        .or_default()
+                        .push((vertex_id, edge.get_id()));
+                }
+                _ => {
+                    dists.insert(dst_id, new_dist);
+                    predecessors.insert(dst_id, vec![(vertex_id, edge.get_id())]);
+                    heap.push(Reverse((new_dist, dst_id)));
+                }
+            }
+        }
+    }
+
+    // Components must be found before any edge of a cycle is dropped.
+    let (component_of, mut cyclic_ids) = if Ty::is_undirected() {
+        zero_weight_components(&order, &dists, &predecessors)
+    } else {
+        (HashMap::new(), HashSet::new())
+    };
+    let dropped_ids = sort_groups(&mut order, &dists, &mut predecessors);
+    cyclic_ids.extend(
+        dropped_ids
+            .into_iter()
+            .map(|vertex_id| *component_of.get(&vertex_id).unwrap_or(&vertex_id)),
+    );
+    if is_source_on_cycle {
+        cyclic_ids.insert(src_id);
+    }
+
+    let mut dag = ShortestPathDag {
+        src_id,
+        order,
+        dists,
+        predecessors,
+        component_of,
+        infinite_ids: HashSet::new(),
+    };
+
+    // A vertex has infinitely many shortest paths if its component has a cycle or any of its predecessors has infinitely many.
+    // Members of a component share their paths, so a component is only decided once all of its members are visited.
+    let mut infinite_components = cyclic_ids;
+    let mut infinite_ids = HashSet::new();
+    for group in dag.order.chunk_by(|u, v| dag.dists[u] == dag.dists[v]) {
+        for vertex_id in group {
+            let component_id = dag.component_of(*vertex_id);
+            if dag
+                .predecessors(*vertex_id)
+                .iter()
+                .any(|(predecessor_id, _)| infinite_ids.contains(predecessor_id))
+            {
+                infinite_components.insert(component_id);
+            }
+            if infinite_components.contains(&component_id) {
+                infinite_ids.insert(*vertex_id);
+            }
+        }
+        for vertex_id in group {
+            if infinite_components.contains(&dag.component_of(*vertex_id)) {
+                infinite_ids.insert(*vertex_id);
+            }
+        }
+    }
+    dag.infinite_ids = infinite_ids;
+
+    dag
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{MatGraph, MultiListGraph};
+    use crate::storage::{DiMat, List, Mat};
+
+    #[test]
+    fn unreachable_vertices() {
+        // Given: Graph
+        //
+        //      a  -->  b      c
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 3.into());
+
+        // When:
+        let from_a = shortest_path_dag(&graph, a);
+        let from_b = shortest_path_dag(&graph, b);
+
+        // Then:
+        assert_eq!(from_a.src_id(), a);
+        assert_eq!(from_a.order(), &[a, b]);
+        assert_eq!(from_a.dist(b).unwrap(), 3.into());
+        assert!(from_a.dist(c).is_none());
+        assert!(from_a.predecessors(a).is_empty());
+        assert!(from_a.predecessors(c).is_empty());
+        assert_eq!(from_b.order(), &[b]);
+        assert!(from_b.edges().is_empty());
+    }
+
+    #[test]
+    fn parallel_edges_and_weights() {
+        // Given: Graph
+        //
+        //           1
+        //      a  =====  b  --1--  d
+        //      |    1              |
+        //      |                   |
+        //      '--2--  c  ----2----'
+        //
+        let mut graph = MultiListGraph::init(List::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let ab1 = graph.add_edge_unchecked(a, b, 1.into());
+        let ab2 = graph.add_edge_unchecked(a, b, 1.into());
+        let bd = graph.add_edge_unchecked(b, d, 1.into());
+        graph.add_edge_unchecked(a, c, 2.into());
+        graph.add_edge_unchecked(c, d, 2.into());
+        graph.add_edge_unchecked(d, d, 0.into());
+
+        // When:
+        let dag = shortest_path_dag(&graph, a);
+
+        // Then: Path through c is longer.
+        let mut b_hops = dag.predecessors(b).to_vec();
+        b_hops.sort_unstable();
+        assert_eq!(b_hops, vec![(a, ab1), (a, ab2)]);
+        assert_eq!(dag.predecessors(d), &[(b, bd)]);
+        assert_eq!(dag.dist(c).unwrap(), 2.into());
+        assert_eq!(dag.edges().len(), 4);
+        assert_eq!(dag.order()[0], a);
+        assert_eq!(dag.order()[3], d);
+    }
+
+    #[test]
+    fn undirected_zero_weight_edge() {
+        // Given: Graph
+        //
+        //      a  --0--  b  --1--  c
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let ab = graph.add_edge_unchecked(a, b, 0.into());
+        let bc = graph.add_edge_unchecked(b, c, 1.into());
+
+        // When:
+        let from_a = shortest_path_dag(&graph, a);
+        let from_b = shortest_path_dag(&graph, b);
+
+        // Then: Source gets no predecessor and the edge is not followed back.
+        assert_eq!(from_a.edges(), vec![(a, b, ab), (b, c, bc)]);
+        assert_eq!(from_a.order(), &[a, b, c]);
+        assert!(from_a.predecessors(a).is_empty());
+        assert!(!from_a.has_zero_weight_cycle());
+        assert!(from_b.predecessors(b).is_empty());
+        assert_eq!(from_b.predecessors(a), &[(b, ab)]);
+        assert!(!from_b.has_zero_weight_cycle());
+    }
+
+    #[test]
+    fn zero_weight_edges_between_vertices_with_same_distance() {
+        // Given: Graph
+        //
+        //      a  --1-->  b
+        //      |          ^
+        //      |          | 0
+        //      '---1--->  c  <--0-->  d
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let ab = graph.add_edge_unchecked(a, b, 1.into());
+        let ac = graph.add_edge_unchecked(a, c, 1.into());
+        let cb = graph.add_edge_unchecked(c, b, 0.into());
+
+        // When: b is settled before c, which is one of its predecessors.
+        let dag = shortest_path_dag(&graph, a);
+
+        // Then: c still comes before b.
+        let mut b_hops = dag.predecessors(b).to_vec();
+        b_hops.sort_unstable();
+        assert_eq!(b_hops, vec![(a, ab), (c, cb)]);
+        assert_eq!(dag.predecessors(c), &[(a, ac)]);
+        assert_eq!(dag.order(), &[a, c, b]);
+        assert!(!dag.has_zero_weight_cycle());
+
+        // When: Closing zero weight cycles, one through the source.
+        graph.add_edge_unchecked(c, d, 0.into());
+        graph.add_edge_unchecked(d, c, 0.into());
+        let dag = shortest_path_dag(&graph, a);
+        let from_c = shortest_path_dag(&graph, c);
+
+        // Then: Cycles are reported and the dags stay acyclic.
+        for dag in [&dag, &from_c] {
+            assert!(dag.has_zero_weight_cycle());
+            for (src_id, dst_id, _) in dag.edges() {
+                let position_of = |vertex_id| dag.order().iter().position(|id| *id == vertex_id);
+                assert!(position_of(src_id) < position_of(dst_id));
+            }
+        }
+        assert!(from_c.predecessors(c).is_empty());
+        assert_eq!(dag.order().len(), 4);
+        assert!(!dag.has_infinitely_many_paths(a));
+        assert!(dag.has_infinitely_many_paths(b));
+        assert!(dag.has_infinitely_many_paths(c));
+        assert!(dag.has_infinitely_many_paths(d));
+    }
+
+    #[test]
+    fn directed_zero_weight_edge_back_to_source() {
+        // Given: Graph
+        //
+        //      a  --0-->  b  --0-->  a
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let ab = graph.add_edge_unchecked(a, b, 0.into());
+        graph.add_edge_unchecked(b, a, 0.into());
+
+        // When:
+        let dag = shortest_path_dag(&graph, a);
+
+        // Then:
+        assert_eq!(dag.edges(), vec![(a, b, ab)]);
+        assert!(dag.has_zero_weight_cycle());
+        assert!(dag.has_infinitely_many_paths(a));
+        assert!(dag.has_infinitely_many_paths(b));
+    }
+
+    #[test]
+    fn undirected_zero_weight_cycle_away_from_other_vertices() {
+        // Given: Graph
+        //
+        //      a  --1--  b
+        //      |
+        //      1     .--0--  d
+        //      |     |       |
+        //      '---  c       0
+        //            |       |
+        //            '--0--  e  --1--  f
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        let f = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(a, c, 1.into());
+        graph.add_edge_unchecked(c, d, 0.into());
+        graph.add_edge_unchecked(d, e, 0.into());
+        graph.add_edge_unchecked(c, e, 0.into());
+        graph.add_edge_unchecked(e, f, 1.into());
+
+        // When:
+        let dag = shortest_path_dag(&graph, a);
+
+        // Then: Only the cycle and what comes after it are affected.
+        assert!(dag.has_zero_weight_cycle());
+        assert!(!dag.has_infinitely_many_paths(a));
+        assert!(!dag.has_infinitely_many_paths(b));
+        for vertex_id in [c, d, e, f] {
+            assert!(dag.has_infinitely_many_paths(vertex_id));
+        }
+    }
+}
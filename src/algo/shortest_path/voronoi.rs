@@ -0,0 +1,341 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::ops::Add;
+
+use num_traits::Zero;
+
+use crate::graph::{Edge, EdgeDir};
+use crate::provide::{Edges, Graph, Vertices};
+
+// Entry of the search queue, ordered so the max heap pops the closest vertex first and breaks ties by the smaller seed id.
+struct Entry<T> {
+    dist: T,
+    seed_id: usize,
+    vertex_id: usize,
+}
+
+impl<T: PartialOrd> PartialEq for Entry<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T: PartialOrd> Eq for Entry<T> {}
+
+impl<T: PartialOrd> PartialOrd for Entry<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: PartialOrd> Ord for Entry<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .dist
+            .partial_cmp(&self.dist)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| other.seed_id.cmp(&self.seed_id))
+    }
+}
+
+/// Partition of vertices by their closest seed, as computed by [`voronoi_partition`](crate::algo::voronoi_partition).
+///
+/// ## Generic Parameters
+/// * `T`: **T**ype of the distances.
+#[derive(Debug, Clone)]
+pub struct VoronoiPartition<T> {
+    seed_of: HashMap<usize, usize>,
+    dists: HashMap<usize, T>,
+    boundary_edges: Vec<(usize, usize, usize)>,
+}
+
+impl<T: Copy> VoronoiPartition<T> {
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// * `Some`: Containing id of the closest seed to the vertex.
+    /// * `None`: If the vertex is not reachable from any seed.
+    pub fn seed_of(&self, vertex_id: usize) -> Option<usize> {
+        self.seed_of.get(&vertex_id).copied()
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// * `Some`: Containing distance of the vertex from its closest seed.
+    /// * `None`: If the vertex is not reachable from any seed.
+    pub fn dist(&self, vertex_id: usize) -> Option<T> {
+        self.dists.get(&vertex_id).copied()
+    }
+
+    /// # Arguments
+    /// `seed_id`: Id of the seed.
+    ///
+    /// # Returns
+    /// Id of the vertices assigned to the seed, sorted. Empty if `seed_id` is not a seed.
+    pub fn cell(&self, seed_id: usize) -> Vec<usize> {
+        let mut vertices: Vec<usize> = self
+            .seed_of
+            .iter()
+            .filter(|(_, vertex_seed_id)| **vertex_seed_id == seed_id)
+            .map(|(vertex_id, _)| *vertex_id)
+            .collect();
+
+        vertices.sort_unstable();
+
+        vertices
+    }
+
+    /// # Returns
+    /// Vertices assigned to each seed, sorted.
+    pub fn cells(&self) -> HashMap<usize, Vec<usize>> {
+        let mut cells: HashMap<usize, Vec<usize>> = HashMap::new();
+        for (vertex_id, seed_id) in &self.seed_of {
+            cells.entry(*seed_id).or_default().push(*vertex_id);
+        }
+
+        for vertices in cells.values_mut() {
+            vertices.sort_unstable();
+        }
+
+        cells
+    }
+
+    /// # Returns
+    /// Edges whose end points are assigned to different seeds, in the format of (`src_id`, `dst_id`, `edge_id`).
+    pub fn boundary_edges(&self) -> &[(usize, usize, usize)] {
+        &self.boundary_edges
+    }
+}
+
+/// Assigns each vertex to its closest seed, using dijkstra algorithm started from all seeds at once.
+///
+/// If a vertex is equally close to multiple seeds, it's assigned to the one with the smallest id.
+///
+/// # Arguments
+/// * `graph`: Graph to partition.
+/// * `seed_ids`: Id of the seed vertices.
+/// * `weight_fn`: Receives an edge and returns its non negative weight, like `|edge| edge.get_weight().unwrap()` or `|_| 1`.
+///
+/// # Returns
+/// The partition. Vertices that are not reachable from any seed are not assigned.
+/// For directed graphs, distance of a vertex is measured along paths from the seed to the vertex.
+///
+/// # Complexity
+/// O((|V| + |E|).log(|V|))
+///
+/// # Panics
+/// * If any of the seeds is not in the graph.
+/// * If `weight_fn` returns a negative weight.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+/// use prepona::algo::voronoi_partition;
+///
+/// // Given: Graph
+/// //
+/// //      a  --1--  b  --3--  c  --1--  d
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 3.into());
+/// graph.add_edge_unchecked(c, d, 1.into());
+///
+/// // When:
+/// let partition = voronoi_partition(&graph, vec![a, d], |edge: &DefaultEdge<usize>| edge.get_weight().unwrap() as f64);
+///
+/// // Then:
+/// assert_eq!(partition.cell(a), vec![a, b]);
+/// assert_eq!(partition.cell(d), vec![c, d]);
+/// assert_eq!(partition.dist(c), Some(1.0));
+/// assert_eq!(partition.boundary_edges().len(), 1);
+/// ```
+pub fn voronoi_partition<W, E, Ty, G, I, T, F>(
+    graph: &G,
+    seed_ids: I,
+    weight_fn: F,
+) -> VoronoiPartition<T>
+where
+    E: Edge<W>,
+    Ty: EdgeDir,
+    G: Edges<W, E> + Vertices + Graph<W, E, Ty>,
+    I: IntoIterator<Item = usize>,
+    T: Copy + Zero + Add<Output = T> + PartialOrd,
+    F: Fn(&E) -> T,
+{
+    let mut best: HashMap<usize, (T, usize)> = HashMap::new();
+    let mut heap = BinaryHeap::new();
+    for seed_id in seed_ids {
+        assert!(
+            graph.contains_vertex(seed_id),
+            "Vertex with id: {} not found",
+            seed_id
+        );
+
+        let is_better = best
+            .get(&seed_id)
+            .is_none_or(|(_, other_seed_id)| seed_id < *other_seed_id);
+        if is_better {
+            best.insert(seed_id, (T::zero(), seed_id));
+            heap.push(Entry {
+                dist: T::zero(),
+                seed_id,
+                vertex_id: seed_id,
+            });
+        }
+    }
+
+    let mut seed_of = HashMap::new();
+    let mut dists = HashMap::new();
+    while let Some(Entry {
+        dist,
+        seed_id,
+        vertex_id,
+    }) = heap.pop()
+    {
+        if seed_of.contains_key(&vertex_id) {
+            continue;
+        }
+        seed_of.insert(vertex_id, seed_id);
+        dists.insert(vertex_id, dist);
+
+        for (dst_id, edge) in graph.edges_from_unchecked(vertex_id) {
+            let weight = weight_fn(edge);
+            assert!(weight >= T::zero(), "Weights must not be negative");
+
+            let alt = dist + weight;
+            let is_better = match best.get(&dst_id) {
+                None => true,
+                Some((dst_dist, dst_seed_id)) => {
+                    alt < *dst_dist || (alt == *dst_dist && seed_id < *dst_seed_id)
+                }
+            };
+
+            if is_better && !seed_of.contains_key(&dst_id) {
+                best.insert(dst_id, (alt, seed_id));
+                heap.push(Entry {
+                    dist: alt,
+                    seed_id,
+                    vertex_id: dst_id,
+                });
+            }
+        }
+    }
+
+    let mut seen_ids = HashSet::new();
+    let boundary_edges = graph
+        .edges()
+        .into_iter()
+        .filter(|(src_id, dst_id, edge)| {
+            let crosses = matches!(
+                (seed_of.get(src_id), seed_of.get(dst_id)),
+                (Some(src_seed_id), Some(dst_seed_id)) if src_seed_id != dst_seed_id
+            );
+
+            crosses && seen_ids.insert(edge.get_id())
+        })
+        .map(|(src_id, dst_id, edge)| (src_id, dst_id, edge.get_id()))
+        .collect();
+
+    VoronoiPartition {
+        seed_of,
+        dists,
+        boundary_edges,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{DefaultEdge, ListGraph, MatGraph};
+    use crate::storage::{DiMat, List};
+
+    fn unit(_: &DefaultEdge<usize>) -> usize {
+        1
+    }
+
+    #[test]
+    fn ties_go_to_smaller_seed() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  c      d
+        //
+        let mut graph = ListGraph::init(List::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+
+        // When:
+        let partition = voronoi_partition(&graph, vec![c, a, a], unit);
+
+        // Then:
+        assert_eq!(partition.seed_of(b), Some(a));
+        assert_eq!(partition.dist(b), Some(1));
+        assert_eq!(partition.seed_of(d), None);
+        assert_eq!(partition.dist(d), None);
+        assert_eq!(partition.cells().len(), 2);
+        assert_eq!(partition.cell(c), vec![c]);
+        assert!(partition.cell(b).is_empty());
+
+        let mut boundary: Vec<(usize, usize)> = partition
+            .boundary_edges()
+            .iter()
+            .map(|(src_id, dst_id, _)| (*src_id, *dst_id))
+            .collect();
+        boundary.sort_unstable();
+        assert_eq!(boundary, vec![(b, c)]);
+    }
+
+    #[test]
+    fn directed_distances_follow_edges() {
+        // Given: Graph
+        //
+        //      a  -->  b  <--  c
+        //              |
+        //              v
+        //              d
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 5.into());
+        graph.add_edge_unchecked(c, b, 2.into());
+        graph.add_edge_unchecked(b, d, 1.into());
+
+        // When:
+        let partition = voronoi_partition(&graph, vec![a, c], |edge: &DefaultEdge<usize>| {
+            edge.get_weight().unwrap()
+        });
+
+        // Then:
+        assert_eq!(partition.cell(a), vec![a]);
+        assert_eq!(partition.cell(c), vec![b, c, d]);
+        assert_eq!(partition.dist(d), Some(3));
+        assert_eq!(partition.boundary_edges().len(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Vertex with id: 2 not found")]
+    fn missing_seed() {
+        // Given:
+        let mut graph = ListGraph::init(List::<usize>::init());
+        graph.add_vertex();
+
+        // When:
+        voronoi_partition(&graph, vec![2], unit);
+    }
+}
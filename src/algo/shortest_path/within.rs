@@ -0,0 +1,307 @@
+use magnitude::Magnitude;
+use num_traits::{One, Unsigned, Zero};
+use std::any::Any;
+
+use crate::algo::{BestFirst, Orientation};
+use crate::graph::subgraph::ShortestPathSubgraph;
+use crate::graph::{Edge, EdgeDir, EdgeFilter, FilterView, KeepAll, VertexFilter};
+use crate::provide::{Edges, Graph, Neighbors, Vertices};
+
+/// Finds shortest path from a single source to all other vertices, using dijkstra algorithm,
+/// where paths may only pass through the vertices kept by `vertex_filter`.
+///
+/// Search runs on a [`FilterView`](crate::graph::FilterView) of the graph, so the region is never materialized as a new graph.
+/// Useful for routing inside a region or searching inside a strongly connected component.
+///
+/// # Arguments
+/// * `graph`: Graph to search for the shortest paths in.
+/// * `src_id`: Id of the source vertex.
+/// * `vertex_filter`: Decides which vertices paths may pass through, like a set of vertex ids or a closure.
+///
+/// # Returns
+/// The shortest path as a subgraph of the original graph. Only kept vertices have a distance, which is infinite for the ones that are not reachable inside the region.
+///
+/// # Panics
+/// If `src_id` is not in the graph or it's not kept by `vertex_filter`.
+///
+/// # Examples
+/// ```
+/// use std::collections::HashSet;
+/// use prepona::prelude::*;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+/// use prepona::algo::dijkstra_within;
+///
+/// // Given: Graph
+/// //
+/// //      a  --1--  b  --1--  c
+/// //      |                   |
+/// //      '---------5---------'
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(a, c, 5.into());
+///
+/// // When: b is outside of the region.
+/// let region: HashSet<usize> = [a, c].iter().copied().collect();
+/// let sp_subgraph = dijkstra_within(&graph, a, region);
+///
+/// // Then:
+/// assert_eq!(sp_subgraph.dist(c).unwrap(), 5.into());
+/// assert_eq!(sp_subgraph.path_to(c).unwrap(), vec![a, c]);
+/// assert!(sp_subgraph.dist(b).is_none());
+/// ```
+pub fn dijkstra_within<'a, W, E, Ty, G, NF>(
+    graph: &'a G,
+    src_id: usize,
+    vertex_filter: NF,
+) -> ShortestPathSubgraph<'a, W, E, Ty, G>
+where
+    W: Copy + Ord + Zero + Any + Unsigned,
+    E: Edge<W>,
+    Ty: EdgeDir,
+    G: Edges<W, E> + Neighbors + Vertices + Graph<W, E, Ty>,
+    NF: VertexFilter,
+{
+    dijkstra_filtered(graph, src_id, vertex_filter, KeepAll)
+}
+
+/// Same as [`dijkstra_within`](crate::algo::dijkstra_within), but paths may only use the edges kept by `edge_filter` as well.
+///
+/// # Arguments
+/// * `graph`: Graph to search for the shortest paths in.
+/// * `src_id`: Id of the source vertex.
+/// * `vertex_filter`: Decides which vertices paths may pass through.
+/// * `edge_filter`: Decides which edges paths may use, like a set of edge ids or a closure.
+///
+/// # Returns
+/// The shortest path as a subgraph of the original graph. Only kept vertices have a distance, which is infinite for the ones that are not reachable using kept edges.
+///
+/// # Panics
+/// If `src_id` is not in the graph or it's not kept by `vertex_filter`.
+pub fn dijkstra_filtered<'a, W, E, Ty, G, NF, EF>(
+    graph: &'a G,
+    src_id: usize,
+    vertex_filter: NF,
+    edge_filter: EF,
+) -> ShortestPathSubgraph<'a, W, E, Ty, G>
+where
+    W: Copy + Ord + Zero + Any + Unsigned,
+    E: Edge<W>,
+    Ty: EdgeDir,
+    G: Edges<W, E> + Neighbors + Vertices + Graph<W, E, Ty>,
+    NF: VertexFilter,
+    EF: EdgeFilter<W, E>,
+{
+    let view = FilterView::init(graph, vertex_filter, edge_filter);
+    assert!(
+        view.contains_vertex(src_id),
+        "Vertex with id: {} not found",
+        src_id
+    );
+
+    let mut listener = ();
+    let mut best_first = BestFirst::init(&view, &mut listener);
+    best_first.execute(
+        &view,
+        src_id,
+        W::zero().into(),
+        |dist: &Magnitude<W>, edge| *dist + *edge.get_weight(),
+        |_, dist| *dist,
+    );
+    let (dists, parents, id_map) = best_first.dissolve();
+
+    super::sp_subgraph_of(
+        graph,
+        src_id,
+        dists,
+        parents,
+        &id_map,
+        Orientation::Original,
+    )
+}
+
+/// Finds shortest path from a single source to all other vertices when every edge counts as one,
+/// where paths may only pass through the vertices kept by `vertex_filter`.
+///
+/// It's the unweighted counterpart of [`dijkstra_within`](crate::algo::dijkstra_within), like [`BfsShortestPath`](crate::algo::BfsShortestPath) is for [`Dijkstra`](crate::algo::Dijkstra).
+///
+/// # Arguments
+/// * `graph`: Graph to search for the shortest paths in.
+/// * `src_id`: Id of the source vertex.
+/// * `vertex_filter`: Decides which vertices paths may pass through, like a set of vertex ids or a closure.
+///
+/// # Returns
+/// The shortest path as a subgraph of the original graph. Only kept vertices have a distance, which is infinite for the ones that are not reachable inside the region.
+///
+/// # Panics
+/// If `src_id` is not in the graph or it's not kept by `vertex_filter`.
+pub fn bfs_within<'a, W, E, Ty, G, NF>(
+    graph: &'a G,
+    src_id: usize,
+    vertex_filter: NF,
+) -> ShortestPathSubgraph<'a, W, E, Ty, G>
+where
+    W: Copy + Zero + One + Any,
+    E: Edge<W>,
+    Ty: EdgeDir,
+    G: Edges<W, E> + Neighbors + Vertices + Graph<W, E, Ty>,
+    NF: VertexFilter,
+{
+    let view = FilterView::vertices_only(graph, vertex_filter);
+    assert!(
+        view.contains_vertex(src_id),
+        "Vertex with id: {} not found",
+        src_id
+    );
+
+    let id_map = view.continuos_id_map();
+    let (dists, parents) = super::bfs::search(&view, src_id, &id_map);
+
+    super::sp_subgraph_of(
+        graph,
+        src_id,
+        dists,
+        parents,
+        &id_map,
+        Orientation::Original,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::{BfsShortestPath, Dijkstra, TarjanSCC};
+    use crate::graph::{DefaultEdge, MatGraph};
+    use crate::storage::{DiMat, Mat};
+    use std::collections::HashSet;
+
+    #[test]
+    fn search_inside_strongly_connected_component() {
+        // Given: Graph
+        //
+        //      a  --1-->  b  --1-->  c
+        //      ^          |          |
+        //      |          3          1
+        //      |          v          v
+        //      '----1---  d  <--1--  e
+        //                 |
+        //                 '--1-->  f
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        let f = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(c, e, 1.into());
+        graph.add_edge_unchecked(e, d, 1.into());
+        graph.add_edge_unchecked(b, d, 3.into());
+        graph.add_edge_unchecked(d, a, 1.into());
+        graph.add_edge_unchecked(d, f, 1.into());
+
+        // When: Searching inside the component of a, without c.
+        let component: HashSet<usize> = TarjanSCC::init(&graph)
+            .execute(&graph)
+            .into_iter()
+            .find(|component| component.contains(&a))
+            .unwrap()
+            .into_iter()
+            .filter(|vertex_id| *vertex_id != c)
+            .collect();
+        let weighted = dijkstra_within(&graph, a, component.clone());
+        let unweighted = bfs_within(&graph, a, component);
+
+        // Then:
+        assert_eq!(weighted.path_to(d).unwrap(), vec![a, b, d]);
+        assert_eq!(weighted.dist(d).unwrap(), 4.into());
+        assert!(weighted.dist(e).unwrap().is_pos_infinite());
+        assert!(weighted.dist(f).is_none());
+        assert_eq!(unweighted.dist(d).unwrap(), 2.into());
+        assert_eq!(
+            Dijkstra::init(&graph).execute(&graph, a).dist(d).unwrap(),
+            4.into()
+        );
+    }
+
+    #[test]
+    fn keeping_everything_matches_unrestricted_search() {
+        // Given: Undirected grid with varying weights.
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let vertices: Vec<_> = (0..16).map(|_| graph.add_vertex()).collect();
+        for index in 0..16 {
+            if index % 4 != 3 {
+                graph.add_edge_unchecked(
+                    vertices[index],
+                    vertices[index + 1],
+                    (index % 3 + 1).into(),
+                );
+            }
+            if index < 12 {
+                graph.add_edge_unchecked(
+                    vertices[index],
+                    vertices[index + 4],
+                    (index % 5 + 1).into(),
+                );
+            }
+        }
+
+        // When:
+        let dijkstra = Dijkstra::init(&graph).execute(&graph, vertices[5]);
+        let within = dijkstra_within(&graph, vertices[5], KeepAll);
+        let bfs = BfsShortestPath::init(&graph).execute(&graph, vertices[5]);
+        let bfs_within = bfs_within(&graph, vertices[5], |_| true);
+
+        // Then:
+        for vertex_id in vertices {
+            assert_eq!(within.dist(vertex_id), dijkstra.dist(vertex_id));
+            assert_eq!(bfs_within.dist(vertex_id), bfs.dist(vertex_id));
+        }
+    }
+
+    #[test]
+    fn edge_filter() {
+        // Given: Graph
+        //
+        //      a  --1--  b  --1--  c
+        //      |                   |
+        //      '---------5---------'
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        let bc = graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(a, c, 5.into());
+
+        // When: Edge b - c is closed.
+        let sp_subgraph =
+            dijkstra_filtered(&graph, a, KeepAll, |_, _, edge: &DefaultEdge<usize>| {
+                edge.get_id() != bc
+            });
+
+        // Then:
+        assert_eq!(sp_subgraph.path_to(c).unwrap(), vec![a, c]);
+        assert_eq!(sp_subgraph.dist(b).unwrap(), 1.into());
+    }
+
+    #[test]
+    #[should_panic]
+    fn source_outside_of_region() {
+        // Given:
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+
+        // When:
+        dijkstra_within(&graph, a, move |vertex_id| vertex_id == b);
+    }
+}
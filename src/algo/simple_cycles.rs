@@ -0,0 +1,447 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::graph::{DirectedEdge, Edge};
+use crate::provide::{Graph, Neighbors, Vertices};
+
+/// Enumerates all elementary circuits of a directed graph using Johnson's algorithm.
+///
+/// A circuit is elementary if no vertex appears on it more than once. Circuits are found lazily,
+/// so only the ones that are consumed are computed. This matters since the number of circuits can grow exponentially with the size of the graph.
+///
+/// # Arguments
+/// `graph`: Directed graph to enumerate its circuits.
+///
+/// # Returns
+/// An iterator over the circuits. Each circuit contains ids of its vertices in the order they are visited, and the last vertex has an edge to the first one.
+/// Each circuit is returned once, starting from one of its vertices. Parallel edges do not create extra circuits, and each loop is a circuit with one vertex.
+///
+/// # Complexity
+/// O((|V| + |E|) * (C + 1)) in total, where C is the number of circuits.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::DiList;
+/// use prepona::graph::ListGraph;
+/// use prepona::algo::simple_cycles;
+///
+/// // Given: Graph
+/// //
+/// //      a  <-->  b  <-->  c
+/// //
+/// let mut graph = ListGraph::init(DiList::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, a, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(c, b, 1.into());
+///
+/// // When:
+/// let mut cycles: Vec<Vec<usize>> = simple_cycles(&graph)
+///     .map(|mut cycle| {
+///         cycle.sort();
+///         cycle
+///     })
+///     .collect();
+/// cycles.sort();
+///
+/// // Then:
+/// assert_eq!(cycles, vec![vec![a, b], vec![b, c]]);
+/// ```
+pub fn simple_cycles<W, E, G>(graph: &G) -> SimpleCycles
+where
+    E: Edge<W>,
+    G: Vertices + Neighbors + Graph<W, E, DirectedEdge>,
+{
+    let id_map = graph.continuos_id_map();
+    let vertex_count = id_map.len();
+
+    let mut loops = vec![];
+    let mut successors = vec![vec![]; vertex_count];
+    for (virt_id, vertex_successors) in successors.iter_mut().enumerate() {
+        let mut seen = HashSet::new();
+        for n_id in graph.neighbors_unchecked(id_map.real_id_of(virt_id)) {
+            let n_virt_id = id_map.virt_id_of(n_id);
+
+            if n_virt_id == virt_id {
+                if seen.insert(n_virt_id) {
+                    loops.push(virt_id);
+                }
+            } else if seen.insert(n_virt_id) {
+                vertex_successors.push(n_virt_id);
+            }
+        }
+    }
+    loops.reverse();
+
+    let all_vertices: Vec<usize> = (0..vertex_count).collect();
+    let components =
+        strongly_connected_components(&successors, &all_vertices, &vec![true; vertex_count]);
+
+    SimpleCycles {
+        real_ids: (0..vertex_count)
+            .map(|virt_id| id_map.real_id_of(virt_id))
+            .collect(),
+        successors,
+        loops,
+        components,
+
+        component: vec![],
+        start: 0,
+        path: vec![],
+        stack: vec![],
+
+        in_component: vec![false; vertex_count],
+        is_blocked: vec![false; vertex_count],
+        is_closed: vec![false; vertex_count],
+        blocked_by: vec![vec![]; vertex_count],
+    }
+}
+
+/// Iterator over elementary circuits of a directed graph, returned by [`simple_cycles`](crate::algo::simple_cycles).
+pub struct SimpleCycles {
+    real_ids: Vec<usize>,
+    successors: Vec<Vec<usize>>,
+    loops: Vec<usize>,
+    // Strongly connected components with more than one vertex that are not searched yet.
+    components: Vec<Vec<usize>>,
+
+    // State of the search for circuits through `start` in `component`.
+    component: Vec<usize>,
+    start: usize,
+    path: Vec<usize>,
+    stack: Vec<(usize, Vec<usize>)>,
+
+    in_component: Vec<bool>,
+    is_blocked: Vec<bool>,
+    is_closed: Vec<bool>,
+    // Vertices to unblock once the vertex gets unblocked.
+    blocked_by: Vec<Vec<usize>>,
+}
+
+impl SimpleCycles {
+    fn successors_in_component(&self, virt_id: usize) -> Vec<usize> {
+        self.successors[virt_id]
+            .iter()
+            .copied()
+            .filter(|s_virt_id| self.in_component[*s_virt_id])
+            .collect()
+    }
+
+    // Finishes the search of the current component, and starts searching the next component.
+    // Returns false if there is no component left.
+    fn start_next_search(&mut self) -> bool {
+        if !self.component.is_empty() {
+            // Circuits through the start vertex are all found, so the rest of the component is split into smaller components without it.
+            let rest: Vec<usize> = self
+                .component
+                .iter()
+                .copied()
+                .filter(|virt_id| *virt_id != self.start)
+                .collect();
+            self.in_component[self.start] = false;
+            let components =
+                strongly_connected_components(&self.successors, &rest, &self.in_component);
+            self.components.extend(components);
+
+            for virt_id in std::mem::take(&mut self.component) {
+                self.in_component[virt_id] = false;
+                self.is_blocked[virt_id] = false;
+                self.is_closed[virt_id] = false;
+                self.blocked_by[virt_id].clear();
+            }
+        }
+
+        let mut component = match self.components.pop() {
+            Some(component) => component,
+            None => return false,
+        };
+
+        for virt_id in component.iter() {
+            self.in_component[*virt_id] = true;
+        }
+        self.start = component.pop().unwrap();
+        component.push(self.start);
+        self.component = component;
+
+        self.path = vec![self.start];
+        self.is_blocked[self.start] = true;
+        self.stack = vec![(self.start, self.successors_in_component(self.start))];
+
+        true
+    }
+
+    fn unblock(&mut self, virt_id: usize) {
+        let mut stack = vec![virt_id];
+        while let Some(virt_id) = stack.pop() {
+            if self.is_blocked[virt_id] {
+                self.is_blocked[virt_id] = false;
+                stack.append(&mut self.blocked_by[virt_id]);
+            }
+        }
+    }
+}
+
+impl Iterator for SimpleCycles {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(virt_id) = self.loops.pop() {
+            return Some(vec![self.real_ids[virt_id]]);
+        }
+
+        loop {
+            if self.stack.is_empty() && !self.start_next_search() {
+                return None;
+            }
+
+            while let Some((virt_id, successors)) = self.stack.last_mut() {
+                let virt_id = *virt_id;
+
+                if let Some(s_virt_id) = successors.pop() {
+                    if s_virt_id == self.start {
+                        for p_virt_id in self.path.iter() {
+                            self.is_closed[*p_virt_id] = true;
+                        }
+
+                        return Some(
+                            self.path
+                                .iter()
+                                .map(|p_virt_id| self.real_ids[*p_virt_id])
+                                .collect(),
+                        );
+                    } else if !self.is_blocked[s_virt_id] {
+                        self.path.push(s_virt_id);
+                        self.is_closed[s_virt_id] = false;
+                        self.is_blocked[s_virt_id] = true;
+                        let successors = self.successors_in_component(s_virt_id);
+                        self.stack.push((s_virt_id, successors));
+                        continue;
+                    }
+                }
+
+                if self.stack.last().unwrap().1.is_empty() {
+                    if self.is_closed[virt_id] {
+                        self.unblock(virt_id);
+                    } else {
+                        // Vertex stays blocked until one of its successors gets unblocked.
+                        for s_virt_id in self.successors_in_component(virt_id) {
+                            if !self.blocked_by[s_virt_id].contains(&virt_id) {
+                                self.blocked_by[s_virt_id].push(virt_id);
+                            }
+                        }
+                    }
+
+                    self.stack.pop();
+                    self.path.pop();
+                }
+            }
+        }
+    }
+}
+
+// Finds strongly connected components with more than one vertex, among the given vertices that are allowed.
+fn strongly_connected_components(
+    successors: &[Vec<usize>],
+    vertices: &[usize],
+    is_allowed: &[bool],
+) -> Vec<Vec<usize>> {
+    let mut indices: HashMap<usize, usize> = HashMap::new();
+    let mut low_links: HashMap<usize, usize> = HashMap::new();
+    let mut on_stack = HashSet::new();
+    let mut stack = vec![];
+    let mut components = vec![];
+
+    for root in vertices.iter().copied() {
+        if indices.contains_key(&root) {
+            continue;
+        }
+
+        indices.insert(root, indices.len());
+        low_links.insert(root, indices[&root]);
+        stack.push(root);
+        on_stack.insert(root);
+        let mut frames = vec![(root, 0)];
+
+        while let Some((virt_id, next_index)) = frames.last_mut() {
+            let virt_id = *virt_id;
+
+            if let Some(s_virt_id) = successors[virt_id].get(*next_index).copied() {
+                *next_index += 1;
+
+                if !is_allowed[s_virt_id] {
+                    continue;
+                }
+
+                if !indices.contains_key(&s_virt_id) {
+                    indices.insert(s_virt_id, indices.len());
+                    low_links.insert(s_virt_id, indices[&s_virt_id]);
+                    stack.push(s_virt_id);
+                    on_stack.insert(s_virt_id);
+                    frames.push((s_virt_id, 0));
+                } else if on_stack.contains(&s_virt_id) {
+                    let low_link = low_links[&virt_id].min(indices[&s_virt_id]);
+                    low_links.insert(virt_id, low_link);
+                }
+            } else {
+                frames.pop();
+                if let Some((parent_virt_id, _)) = frames.last() {
+                    let low_link = low_links[parent_virt_id].min(low_links[&virt_id]);
+                    low_links.insert(*parent_virt_id, low_link);
+                }
+
+                if low_links[&virt_id] == indices[&virt_id] {
+                    let mut component = vec![];
+                    while let Some(c_virt_id) = stack.pop() {
+                        on_stack.remove(&c_virt_id);
+                        component.push(c_virt_id);
+                        if c_virt_id == virt_id {
+                            break;
+                        }
+                    }
+
+                    if component.len() > 1 {
+                        components.push(component);
+                    }
+                }
+            }
+        }
+    }
+
+    components
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{ListGraph, MultiListGraph};
+    use crate::misc::rng::Rng;
+    use crate::storage::DiList;
+
+    // Rotates the cycle so it starts from its smallest vertex.
+    fn canonical(mut cycle: Vec<usize>) -> Vec<usize> {
+        let min_index = (0..cycle.len()).min_by_key(|index| cycle[*index]).unwrap();
+        cycle.rotate_left(min_index);
+        cycle
+    }
+
+    // Enumerates circuits by extending paths from their smallest vertex through larger vertices only.
+    fn brute_force(vertex_count: usize, edges: &HashSet<(usize, usize)>) -> HashSet<Vec<usize>> {
+        fn extend(
+            path: &mut Vec<usize>,
+            vertex_count: usize,
+            edges: &HashSet<(usize, usize)>,
+            cycles: &mut HashSet<Vec<usize>>,
+        ) {
+            let last = *path.last().unwrap();
+            if edges.contains(&(last, path[0])) {
+                cycles.insert(path.clone());
+            }
+            for next in path[0] + 1..vertex_count {
+                if !path.contains(&next) && edges.contains(&(last, next)) {
+                    path.push(next);
+                    extend(path, vertex_count, edges, cycles);
+                    path.pop();
+                }
+            }
+        }
+
+        let mut cycles = HashSet::new();
+        for start in 0..vertex_count {
+            extend(&mut vec![start], vertex_count, edges, &mut cycles);
+        }
+        cycles
+    }
+
+    #[test]
+    fn complete_graph_with_loops_and_parallel_edges() {
+        // Given: Complete directed graph with 4 vertices, a loop on each vertex and a parallel edge.
+        let mut graph = MultiListGraph::init(DiList::<usize>::init());
+        let vertices: Vec<usize> = (0..4).map(|_| graph.add_vertex()).collect();
+        for src_id in vertices.iter() {
+            for dst_id in vertices.iter() {
+                graph.add_edge_unchecked(*src_id, *dst_id, 1.into());
+            }
+        }
+        graph.add_edge_unchecked(vertices[0], vertices[1], 1.into());
+
+        // When:
+        let cycles: Vec<Vec<usize>> = simple_cycles(&graph).map(canonical).collect();
+
+        // Then: 4 loops, 6 circuits of length 2, 8 of length 3 and 6 of length 4.
+        let distinct: HashSet<Vec<usize>> = cycles.iter().cloned().collect();
+        assert_eq!(cycles.len(), 24);
+        assert_eq!(distinct.len(), 24);
+        for length in 1..=4 {
+            let expected = [0, 4, 6, 8, 6][length];
+            assert_eq!(
+                cycles.iter().filter(|cycle| cycle.len() == length).count(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn circuits_are_lazy() {
+        // Given: Complete directed graph with 12 vertices, which has billions of circuits.
+        let mut graph = ListGraph::init(DiList::<usize>::init());
+        let vertices: Vec<usize> = (0..12).map(|_| graph.add_vertex()).collect();
+        for src_id in vertices.iter() {
+            for dst_id in vertices.iter() {
+                if src_id != dst_id {
+                    graph.add_edge_unchecked(*src_id, *dst_id, 1.into());
+                }
+            }
+        }
+
+        // When:
+        let cycles: Vec<Vec<usize>> = simple_cycles(&graph).take(100).collect();
+
+        // Then:
+        assert_eq!(cycles.len(), 100);
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let mut rng = Rng::seed_from(11);
+        for _ in 0..50 {
+            // Given: Random directed graph with a removed vertex.
+            let vertex_count = 2 + rng.next_below(7);
+            let mut graph = ListGraph::init(DiList::<usize>::init());
+            let removed = graph.add_vertex();
+            let vertices: Vec<usize> = (0..vertex_count).map(|_| graph.add_vertex()).collect();
+            graph.remove_vertex_unchecked(removed);
+
+            let mut edges = HashSet::new();
+            for src in 0..vertex_count {
+                for dst in 0..vertex_count {
+                    if src != dst && rng.next_bool(0.35) {
+                        edges.insert((src, dst));
+                        graph.add_edge_unchecked(vertices[src], vertices[dst], 1.into());
+                    }
+                }
+            }
+
+            // When:
+            let cycles: Vec<Vec<usize>> = simple_cycles(&graph)
+                .map(|cycle| {
+                    canonical(
+                        cycle
+                            .into_iter()
+                            .map(|vertex_id| {
+                                vertices.iter().position(|id| *id == vertex_id).unwrap()
+                            })
+                            .collect(),
+                    )
+                })
+                .collect();
+
+            // Then:
+            let distinct: HashSet<Vec<usize>> = cycles.iter().cloned().collect();
+            assert_eq!(distinct.len(), cycles.len());
+            assert_eq!(distinct, brute_force(vertex_count, &edges));
+        }
+    }
+}
@@ -0,0 +1,377 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::graph::{Edge, EdgeDir};
+use crate::misc::rng::Rng;
+use crate::provide::{Edges, Graph, Vertices};
+
+// In neighbors of each vertex in terms of continuos ids, without duplicates.
+fn in_neighbors_of<W, E, Dir, G>(graph: &G) -> (Vec<usize>, Vec<Vec<usize>>)
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Vertices + Edges<W, E> + Graph<W, E, Dir>,
+{
+    let id_map = graph.continuos_id_map();
+    let vertex_count = graph.vertex_count();
+
+    let mut in_neighbors = vec![vec![]; vertex_count];
+    for (src_id, dst_id, _) in graph.as_directed_edges() {
+        in_neighbors[id_map.virt_id_of(dst_id)].push(id_map.virt_id_of(src_id));
+    }
+    for neighbors in in_neighbors.iter_mut() {
+        neighbors.sort_unstable();
+        neighbors.dedup();
+    }
+
+    let real_ids = (0..vertex_count)
+        .map(|virt_id| id_map.real_id_of(virt_id))
+        .collect();
+
+    (real_ids, in_neighbors)
+}
+
+// Keeps scores above the threshold, keyed by real ids with the smaller id first.
+fn sparse_scores(
+    real_ids: &[usize],
+    scores: impl Iterator<Item = ((usize, usize), f64)>,
+    threshold: f64,
+) -> HashMap<(usize, usize), f64> {
+    scores
+        .filter(|(_, score)| *score > threshold)
+        .map(|((u, v), score)| {
+            let (u_id, v_id) = (real_ids[u], real_ids[v]);
+            ((u_id.min(v_id), u_id.max(v_id)), score)
+        })
+        .collect()
+}
+
+/// Computes the [SimRank](https://en.wikipedia.org/wiki/SimRank) similarity of every pair of distinct vertices.
+///
+/// Two vertices are similar if they are pointed to by similar vertices:
+/// s(u, v) = c / (|I(u)| * |I(v)|) * Σ s(i, j) for i in I(u) and j in I(v), where I(u) is the set of vertices with an edge to u.
+/// Each vertex is completely similar to itself and vertices without in neighbors are not similar to any other vertex.
+/// Undirected edges are considered in both directions and parallel edges are counted once.
+///
+/// Scores are refined `iterations` times starting from the identity, using partial sums over the in neighbors so each iteration takes O(|V| * |E|).
+/// After k iterations each score is at most c<sup>k + 1</sup> less than its exact value.
+///
+/// # Arguments
+/// * `graph`: Graph to compute the similarities of its vertices.
+/// * `c`: Decay factor in range (0, 1).
+/// * `iterations`: Number of refinement iterations.
+/// * `threshold`: Only scores greater than this value are returned.
+///
+/// # Returns
+/// Map from each pair of distinct vertices, in the format of (`u_id`, `v_id`) with `u_id` < `v_id`, to their similarity.
+///
+/// # Complexity
+/// O(`iterations` * |V| * |E|) time and O(|V|<sup>2</sup>) space.
+///
+/// # Panics
+/// If `c` is not in range (0, 1).
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::DiList;
+/// use prepona::graph::ListGraph;
+/// use prepona::algo::simrank;
+///
+/// // Given: Graph
+/// //
+/// //           .-->  b
+/// //      a  --|
+/// //           '-->  c  <--  d
+/// //
+/// let mut graph = ListGraph::init(DiList::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(a, c, 1.into());
+/// graph.add_edge_unchecked(d, c, 1.into());
+///
+/// // When:
+/// let scores = simrank(&graph, 0.8, 10, 0.0);
+///
+/// // Then: Half of the in neighbors of c are the in neighbor of b.
+/// assert!((scores[&(b, c)] - 0.8 / 2.0).abs() < 1e-9);
+/// assert_eq!(scores.len(), 1);
+/// ```
+pub fn simrank<W, E, Dir, G>(
+    graph: &G,
+    c: f64,
+    iterations: usize,
+    threshold: f64,
+) -> HashMap<(usize, usize), f64>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Vertices + Edges<W, E> + Graph<W, E, Dir>,
+{
+    assert!(c > 0.0 && c < 1.0, "Decay factor must be in range (0, 1)");
+
+    let (real_ids, in_neighbors) = in_neighbors_of(graph);
+    let vertex_count = real_ids.len();
+
+    let mut scores = vec![vec![0.0; vertex_count]; vertex_count];
+    for (u, row) in scores.iter_mut().enumerate() {
+        row[u] = 1.0;
+    }
+
+    let mut partial_sums = vec![0.0; vertex_count];
+    for _ in 0..iterations {
+        let mut next_scores = vec![vec![0.0; vertex_count]; vertex_count];
+
+        for u in 0..vertex_count {
+            next_scores[u][u] = 1.0;
+            if in_neighbors[u].is_empty() {
+                continue;
+            }
+
+            // partial_sums[j] = Σ s(i, j) for i in I(u).
+            for (j, partial_sum) in partial_sums.iter_mut().enumerate() {
+                *partial_sum = in_neighbors[u].iter().map(|i| scores[*i][j]).sum();
+            }
+
+            for v in u + 1..vertex_count {
+                if in_neighbors[v].is_empty() {
+                    continue;
+                }
+
+                let sum: f64 = in_neighbors[v].iter().map(|j| partial_sums[*j]).sum();
+                let score = c * sum / (in_neighbors[u].len() * in_neighbors[v].len()) as f64;
+
+                next_scores[u][v] = score;
+                next_scores[v][u] = score;
+            }
+        }
+
+        scores = next_scores;
+    }
+
+    sparse_scores(
+        &real_ids,
+        (0..vertex_count)
+            .flat_map(|u| (u + 1..vertex_count).map(move |v| (u, v)))
+            .map(|(u, v)| ((u, v), scores[u][v])),
+        threshold,
+    )
+}
+
+/// Estimates the [SimRank](https://en.wikipedia.org/wiki/SimRank) similarity of pairs of distinct vertices using random walks.
+///
+/// SimRank of u and v is the expected value of c<sup>t</sup>, where t is the first step in which two random walks that start from u and v
+/// and follow edges backward meet each other. So similarities are estimated by sampling `walk_count` sets of walks, one walk from each vertex.
+/// In each step of a sample all walks that are at the same vertex move to the same random in neighbor, so walks that meet stay together
+/// and each pair of vertices only gets updated once per sample. Pairs that never meet within `walk_length` steps are not similar.
+///
+/// Unlike [`simrank`](crate::algo::simrank) it does not keep a score for every pair of vertices, so it suits large and sparse graphs.
+/// Undirected edges are considered in both directions and parallel edges are counted once.
+///
+/// # Arguments
+/// * `graph`: Graph to compute the similarities of its vertices.
+/// * `c`: Decay factor in range (0, 1).
+/// * `walk_count`: Number of samples.
+/// * `walk_length`: Maximum number of steps in each walk.
+/// * `threshold`: Only scores greater than this value are returned.
+/// * `rng`: Source of randomness.
+///
+/// # Returns
+/// Map from each pair of distinct vertices, in the format of (`u_id`, `v_id`) with `u_id` < `v_id`, to their estimated similarity.
+///
+/// # Complexity
+/// O(`walk_count` * (`walk_length` * |V| + P)), where P is the number of pairs that meet in a sample.
+///
+/// # Panics
+/// If `c` is not in range (0, 1) or `walk_count` is zero.
+pub fn approximate_simrank<W, E, Dir, G>(
+    graph: &G,
+    c: f64,
+    walk_count: usize,
+    walk_length: usize,
+    threshold: f64,
+    rng: &mut Rng,
+) -> HashMap<(usize, usize), f64>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Vertices + Edges<W, E> + Graph<W, E, Dir>,
+{
+    assert!(c > 0.0 && c < 1.0, "Decay factor must be in range (0, 1)");
+    assert!(walk_count > 0, "Number of walks must be greater than zero");
+
+    let (real_ids, in_neighbors) = in_neighbors_of(graph);
+
+    let mut sums: HashMap<(usize, usize), f64> = HashMap::new();
+    for _ in 0..walk_count {
+        // Walks that are at the same vertex, grouped by that vertex.
+        let mut groups: BTreeMap<usize, Vec<usize>> =
+            (0..real_ids.len()).map(|u| (u, vec![u])).collect();
+
+        let mut decay = 1.0;
+        for _ in 0..walk_length {
+            decay *= c;
+
+            let mut next_groups: BTreeMap<usize, Vec<Vec<usize>>> = BTreeMap::new();
+            for (vertex, walks) in groups {
+                // Walks that reach a vertex without in neighbors can not meet any other walk anymore.
+                if !in_neighbors[vertex].is_empty() {
+                    let next = in_neighbors[vertex][rng.next_below(in_neighbors[vertex].len())];
+                    next_groups.entry(next).or_default().push(walks);
+                }
+            }
+
+            groups = BTreeMap::new();
+            for (vertex, merging_groups) in next_groups {
+                // Walks of different groups meet for the first time.
+                for (index, walks) in merging_groups.iter().enumerate() {
+                    for other_walks in &merging_groups[index + 1..] {
+                        for u in walks {
+                            for v in other_walks {
+                                *sums.entry((*u.min(v), *u.max(v))).or_insert(0.0) += decay;
+                            }
+                        }
+                    }
+                }
+
+                groups.insert(vertex, merging_groups.concat());
+            }
+
+            if groups.is_empty() {
+                break;
+            }
+        }
+    }
+
+    sparse_scores(
+        &real_ids,
+        sums.into_iter()
+            .map(|(pair, sum)| (pair, sum / walk_count as f64)),
+        threshold,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{ListGraph, MultiListGraph};
+    use crate::storage::{DiList, List};
+
+    // Random directed graph where each vertex gets a few random in neighbors.
+    fn random_graph(rng: &mut Rng) -> ListGraph<usize, crate::graph::DirectedEdge> {
+        let mut graph = ListGraph::init(DiList::<usize>::init());
+        let vertices: Vec<usize> = (0..8).map(|_| graph.add_vertex()).collect();
+        for dst in 0..8 {
+            for src in 0..8 {
+                if src != dst && rng.next_bool(0.3) {
+                    graph.add_edge_unchecked(vertices[src], vertices[dst], 1.into());
+                }
+            }
+        }
+
+        graph
+    }
+
+    #[test]
+    fn empty_graph() {
+        // Given:
+        let graph = ListGraph::init(DiList::<usize>::init());
+
+        // Then:
+        assert!(simrank(&graph, 0.8, 5, 0.0).is_empty());
+        assert!(approximate_simrank(&graph, 0.8, 5, 5, 0.0, &mut Rng::seed_from(0)).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Decay factor must be in range (0, 1)")]
+    fn invalid_decay_factor() {
+        let graph = ListGraph::init(DiList::<usize>::init());
+
+        simrank(&graph, 1.0, 5, 0.0);
+    }
+
+    #[test]
+    fn scores_satisfy_simrank_equation() {
+        let mut rng = Rng::seed_from(17);
+        for _ in 0..20 {
+            // Given:
+            let graph = random_graph(&mut rng);
+            let (real_ids, in_neighbors) = in_neighbors_of(&graph);
+
+            // When: Iterating enough for the scores to converge.
+            let scores = simrank(&graph, 0.6, 60, 0.0);
+
+            // Then:
+            let score_of = |u: usize, v: usize| {
+                if u == v {
+                    1.0
+                } else {
+                    let (u_id, v_id) = (real_ids[u], real_ids[v]);
+                    *scores
+                        .get(&(u_id.min(v_id), u_id.max(v_id)))
+                        .unwrap_or(&0.0)
+                }
+            };
+            for u in 0..8 {
+                for v in u + 1..8 {
+                    let expected = if in_neighbors[u].is_empty() || in_neighbors[v].is_empty() {
+                        0.0
+                    } else {
+                        let sum: f64 = in_neighbors[u]
+                            .iter()
+                            .flat_map(|i| in_neighbors[v].iter().map(move |j| (*i, *j)))
+                            .map(|(i, j)| score_of(i, j))
+                            .sum();
+                        0.6 * sum / (in_neighbors[u].len() * in_neighbors[v].len()) as f64
+                    };
+
+                    assert!((score_of(u, v) - expected).abs() < 1e-9);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn threshold_filters_scores() {
+        // Given: Undirected star with 3 leaves and a parallel edge to one of them.
+        let mut graph = MultiListGraph::init(List::<usize>::init());
+        let center = graph.add_vertex();
+        let leaves: Vec<usize> = (0..3).map(|_| graph.add_vertex()).collect();
+        for leaf in &leaves {
+            graph.add_edge_unchecked(center, *leaf, 1.into());
+        }
+        graph.add_edge_unchecked(center, leaves[0], 1.into());
+
+        // When:
+        let scores = simrank(&graph, 0.8, 20, 0.5);
+
+        // Then: Leaves are completely similar up to the decay, and the center is less similar to the leaves.
+        assert_eq!(scores.len(), 3);
+        for (index, leaf) in leaves.iter().enumerate() {
+            for other_leaf in &leaves[index + 1..] {
+                assert!((scores[&(*leaf, *other_leaf)] - 0.8).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn approximate_scores_are_close_to_exact_scores() {
+        // Given:
+        let mut rng = Rng::seed_from(5);
+        let graph = random_graph(&mut rng);
+
+        // When:
+        let exact = simrank(&graph, 0.6, 30, 0.0);
+        let approximate = approximate_simrank(&graph, 0.6, 20000, 30, 0.0, &mut rng);
+
+        // Then:
+        for (pair, score) in &exact {
+            assert!((approximate.get(pair).unwrap_or(&0.0) - score).abs() < 0.03);
+        }
+        for pair in approximate.keys() {
+            assert!(exact.contains_key(pair));
+        }
+    }
+}
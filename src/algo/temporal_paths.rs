@@ -0,0 +1,506 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+use crate::graph::{Edge, EdgeDir, TemporalGraph};
+use crate::provide::{Edges, Graph, Vertices};
+
+/// One edge taken by a [`TemporalPath`](crate::algo::TemporalPath).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TemporalHop {
+    /// Id of the vertex the hop leaves.
+    pub src_id: usize,
+
+    /// Id of the vertex the hop reaches.
+    pub dst_id: usize,
+
+    /// Id of the taken edge.
+    pub edge_id: usize,
+
+    /// Time in which the edge is taken. The edge exists at this time.
+    pub departure_time: usize,
+
+    /// Time in which `dst_id` is reached.
+    pub arrival_time: usize,
+}
+
+/// A time respecting path: Each hop departs after the previous one arrives, so walks may wait at intermediate vertices.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemporalPath {
+    departure_time: usize,
+    arrival_time: usize,
+    hops: Vec<TemporalHop>,
+}
+
+impl TemporalPath {
+    fn from_hops(time: usize, hops: Vec<TemporalHop>) -> Self {
+        TemporalPath {
+            departure_time: hops.first().map_or(time, |hop| hop.departure_time),
+            arrival_time: hops.last().map_or(time, |hop| hop.arrival_time),
+            hops,
+        }
+    }
+
+    /// # Returns
+    /// Hops of the path in the order they are taken. Empty if source and destination are the same.
+    pub fn hops(&self) -> &[TemporalHop] {
+        &self.hops
+    }
+
+    /// # Returns
+    /// Departure time of the first hop.
+    pub fn departure_time(&self) -> usize {
+        self.departure_time
+    }
+
+    /// # Returns
+    /// Arrival time of the last hop.
+    pub fn arrival_time(&self) -> usize {
+        self.arrival_time
+    }
+
+    /// # Returns
+    /// Time from departure of the first hop until arrival of the last one, including the waits in between.
+    pub fn duration(&self) -> usize {
+        self.arrival_time - self.departure_time
+    }
+
+    /// # Returns
+    /// Ids of the vertices along the path, starting from the source.
+    pub fn vertices(&self) -> Vec<usize> {
+        self.hops
+            .first()
+            .map(|hop| hop.src_id)
+            .into_iter()
+            .chain(self.hops.iter().map(|hop| hop.dst_id))
+            .collect()
+    }
+}
+
+// Finds the earliest arrival time of each vertex leaving `src_id` at or after `start_time`,
+// and the hop each vertex is reached with.
+fn earliest_arrivals<W, E, Dir, G, F>(
+    graph: &TemporalGraph<W, E, Dir, G>,
+    src_id: usize,
+    start_time: usize,
+    duration_fn: &F,
+) -> HashMap<usize, Option<TemporalHop>>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Vertices + Edges<W, E> + Graph<W, E, Dir>,
+    F: Fn(&E) -> usize,
+{
+    let mut reached_by = HashMap::new();
+    let mut heap = BinaryHeap::from(vec![Reverse((start_time, src_id, None))]);
+
+    // Waiting is allowed, so arriving earlier is never worse and arrival times can be settled in increasing order.
+    while let Some(Reverse((time, vertex_id, hop))) = heap.pop() {
+        if reached_by.contains_key(&vertex_id) {
+            continue;
+        }
+        reached_by.insert(vertex_id, hop);
+
+        for (dst_id, edge) in graph.edges_from_unchecked(vertex_id) {
+            let interval = graph.interval_of(edge.get_id()).unwrap();
+            let departure_time = time.max(interval.start);
+
+            if departure_time < interval.end && !reached_by.contains_key(&dst_id) {
+                let hop = TemporalHop {
+                    src_id: vertex_id,
+                    dst_id,
+                    edge_id: edge.get_id(),
+                    departure_time,
+                    arrival_time: departure_time.saturating_add(duration_fn(edge)),
+                };
+                heap.push(Reverse((hop.arrival_time, dst_id, Some(hop))));
+            }
+        }
+    }
+
+    reached_by
+}
+
+// Finds the latest time each vertex can be left and still reach `dst_id` by `deadline`,
+// and the hop each vertex is left with.
+fn latest_departures<W, E, Dir, G, F>(
+    graph: &TemporalGraph<W, E, Dir, G>,
+    dst_id: usize,
+    deadline: usize,
+    duration_fn: &F,
+) -> HashMap<usize, Option<TemporalHop>>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Vertices + Edges<W, E> + Graph<W, E, Dir>,
+    F: Fn(&E) -> usize,
+{
+    let mut incoming: HashMap<usize, Vec<(usize, &E)>> = HashMap::new();
+    for (src_id, dst_id, edge) in graph.as_directed_edges() {
+        incoming.entry(dst_id).or_default().push((src_id, edge));
+    }
+
+    let mut left_by = HashMap::new();
+    let mut heap = BinaryHeap::from(vec![(deadline, dst_id, None)]);
+
+    while let Some((time, vertex_id, hop)) = heap.pop() {
+        if left_by.contains_key(&vertex_id) {
+            continue;
+        }
+        left_by.insert(vertex_id, hop);
+
+        for (src_id, edge) in incoming.get(&vertex_id).into_iter().flatten() {
+            let interval = graph.interval_of(edge.get_id()).unwrap();
+            let duration = duration_fn(edge);
+
+            if interval.start == interval.end || time < duration || left_by.contains_key(src_id) {
+                continue;
+            }
+
+            let departure_time = (interval.end - 1).min(time - duration);
+            if departure_time >= interval.start {
+                let hop = TemporalHop {
+                    src_id: *src_id,
+                    dst_id: vertex_id,
+                    edge_id: edge.get_id(),
+                    departure_time,
+                    arrival_time: departure_time + duration,
+                };
+                heap.push((departure_time, *src_id, Some(hop)));
+            }
+        }
+    }
+
+    left_by
+}
+
+/// Finds a path that leaves `src_id` at or after `start_time` and reaches `dst_id` as early as possible.
+///
+/// A hop can depart at any time in which its edge exists and takes `duration_fn(edge)` to arrive. Walks may wait at any vertex.
+///
+/// # Arguments
+/// * `graph`: Temporal graph to search in.
+/// * `src_id`: Id of the source vertex.
+/// * `dst_id`: Id of the destination vertex.
+/// * `start_time`: Earliest time in which the path can leave the source.
+/// * `duration_fn`: Maps each edge to the time it takes to traverse it.
+///
+/// # Returns
+/// * `Some`: Containing the path with the earliest arrival time.
+/// * `None`: If `dst_id` can not be reached.
+///
+/// # Complexity
+/// O((|V| + |E|) * log(|E|))
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::DiList;
+/// use prepona::graph::{ListGraph, TemporalGraph, TimeInterval};
+/// use prepona::algo::earliest_arrival_path;
+///
+/// // Given: Trains that leave at fixed times, where the weight of each edge is its travel time.
+/// //
+/// //      a  --(t: 1, takes 5)-->  c
+/// //      |                        ^
+/// //      '--(t: 2, takes 1)-->  b  --(t: 4, takes 1)--'
+/// //
+/// let mut graph = TemporalGraph::init(ListGraph::init(DiList::<usize>::init()));
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_at(a, c, 5.into(), 1).unwrap();
+/// graph.add_edge_at(a, b, 1.into(), 2).unwrap();
+/// graph.add_edge_at(b, c, 1.into(), 4).unwrap();
+///
+/// // When:
+/// let path = earliest_arrival_path(&graph, a, c, 0, |edge| edge.get_weight().unwrap()).unwrap();
+///
+/// // Then: Waiting one hour for the second train at b is faster.
+/// assert_eq!(path.vertices(), vec![a, b, c]);
+/// assert_eq!(path.hops()[0].arrival_time, 3);
+/// assert_eq!(path.hops()[1].departure_time, 4);
+/// assert_eq!(path.arrival_time(), 5);
+/// ```
+pub fn earliest_arrival_path<W, E, Dir, G, F>(
+    graph: &TemporalGraph<W, E, Dir, G>,
+    src_id: usize,
+    dst_id: usize,
+    start_time: usize,
+    duration_fn: F,
+) -> Option<TemporalPath>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Vertices + Edges<W, E> + Graph<W, E, Dir>,
+    F: Fn(&E) -> usize,
+{
+    let reached_by = earliest_arrivals(graph, src_id, start_time, &duration_fn);
+
+    let mut hops = vec![];
+    let mut vertex_id = dst_id;
+    while let Some(hop) = reached_by.get(&vertex_id)? {
+        hops.push(*hop);
+        vertex_id = hop.src_id;
+    }
+    hops.reverse();
+
+    Some(TemporalPath::from_hops(start_time, hops))
+}
+
+/// Finds a path that leaves `src_id` as late as possible and still reaches `dst_id` at or before `deadline`.
+///
+/// A hop can depart at any time in which its edge exists and takes `duration_fn(edge)` to arrive. Walks may wait at any vertex.
+///
+/// # Arguments
+/// * `graph`: Temporal graph to search in.
+/// * `src_id`: Id of the source vertex.
+/// * `dst_id`: Id of the destination vertex.
+/// * `deadline`: Latest time in which the path can reach the destination.
+/// * `duration_fn`: Maps each edge to the time it takes to traverse it.
+///
+/// # Returns
+/// * `Some`: Containing the path with the latest departure time.
+/// * `None`: If `dst_id` can not be reached by `deadline`.
+///
+/// # Complexity
+/// O((|V| + |E|) * log(|E|))
+pub fn latest_departure_path<W, E, Dir, G, F>(
+    graph: &TemporalGraph<W, E, Dir, G>,
+    src_id: usize,
+    dst_id: usize,
+    deadline: usize,
+    duration_fn: F,
+) -> Option<TemporalPath>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Vertices + Edges<W, E> + Graph<W, E, Dir>,
+    F: Fn(&E) -> usize,
+{
+    let left_by = latest_departures(graph, dst_id, deadline, &duration_fn);
+
+    let mut hops = vec![];
+    let mut vertex_id = src_id;
+    while let Some(hop) = left_by.get(&vertex_id)? {
+        hops.push(*hop);
+        vertex_id = hop.dst_id;
+    }
+
+    Some(TemporalPath::from_hops(deadline, hops))
+}
+
+/// Finds a path from `src_id` to `dst_id` with the minimum duration, regardless of when it departs.
+///
+/// Duration of a path is the time from its departure until its arrival, including the waits at intermediate vertices.
+/// For each possible arrival time, the path that departs the latest is found using [`latest_departure_path`](crate::algo::latest_departure_path).
+/// Arrival times are enumerated in increasing order using [`earliest_arrival_path`](crate::algo::earliest_arrival_path) by departing right after the latest departure of the previous arrival time.
+///
+/// # Arguments
+/// * `graph`: Temporal graph to search in.
+/// * `src_id`: Id of the source vertex.
+/// * `dst_id`: Id of the destination vertex.
+/// * `duration_fn`: Maps each edge to the time it takes to traverse it.
+///
+/// # Returns
+/// * `Some`: Containing the fastest path. Among the fastest paths, the one that arrives the earliest is returned.
+/// * `None`: If `dst_id` can not be reached.
+///
+/// # Complexity
+/// O(K * (|V| + |E|) * log(|E|)), where K is the number of distinct earliest arrival times at `dst_id` over all departure times.
+pub fn fastest_path<W, E, Dir, G, F>(
+    graph: &TemporalGraph<W, E, Dir, G>,
+    src_id: usize,
+    dst_id: usize,
+    duration_fn: F,
+) -> Option<TemporalPath>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Vertices + Edges<W, E> + Graph<W, E, Dir>,
+    F: Fn(&E) -> usize,
+{
+    if src_id == dst_id {
+        return earliest_arrival_path(graph, src_id, dst_id, 0, duration_fn);
+    }
+
+    let mut fastest: Option<TemporalPath> = None;
+    let mut start_time = 0;
+
+    while let Some(earliest) =
+        earliest_arrival_path(graph, src_id, dst_id, start_time, &duration_fn)
+    {
+        // Leaving later than this path can not arrive by the same time, so the next arrival time is strictly later.
+        let latest =
+            latest_departure_path(graph, src_id, dst_id, earliest.arrival_time(), &duration_fn)
+                .unwrap();
+
+        start_time = latest.departure_time() + 1;
+
+        if fastest
+            .as_ref()
+            .is_none_or(|path| latest.duration() < path.duration())
+        {
+            fastest = Some(latest);
+        }
+    }
+
+    fastest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{DefaultEdge, DirectedEdge, ListGraph, MultiListGraph, TimeInterval};
+    use crate::misc::rng::Rng;
+    use crate::storage::{DiList, List};
+
+    type DiTemporalGraph =
+        TemporalGraph<usize, DefaultEdge<usize>, DirectedEdge, MultiListGraph<usize, DirectedEdge>>;
+
+    fn is_time_respecting(graph: &DiTemporalGraph, path: &TemporalPath, duration: usize) -> bool {
+        path.hops().windows(2).all(|hops| {
+            hops[0].dst_id == hops[1].src_id && hops[0].arrival_time <= hops[1].departure_time
+        }) && path.hops().iter().all(|hop| {
+            graph
+                .interval_of(hop.edge_id)
+                .unwrap()
+                .contains(hop.departure_time)
+                && hop.arrival_time == hop.departure_time + duration
+        })
+    }
+
+    #[test]
+    fn same_source_and_destination() {
+        // Given:
+        let mut graph = TemporalGraph::init(ListGraph::init(List::<usize>::init()));
+        let a = graph.add_vertex();
+
+        // When:
+        let path = earliest_arrival_path(&graph, a, a, 7, |_| 1).unwrap();
+
+        // Then:
+        assert!(path.hops().is_empty());
+        assert_eq!(path.arrival_time(), 7);
+        assert_eq!(fastest_path(&graph, a, a, |_| 1).unwrap().duration(), 0);
+    }
+
+    #[test]
+    fn paths_in_undirected_graph() {
+        // Given: Undirected graph
+        //
+        //      a  --[0, 10)--  b  --[5, 6)--  c
+        //
+        let mut graph = TemporalGraph::init(ListGraph::init(List::<usize>::init()));
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph
+            .add_edge_during(a, b, 1.into(), TimeInterval::init(0, 10))
+            .unwrap();
+        graph
+            .add_edge_during(b, c, 1.into(), TimeInterval::init(5, 6))
+            .unwrap();
+
+        // When:
+        let earliest = earliest_arrival_path(&graph, a, c, 0, |_| 1).unwrap();
+        let latest = latest_departure_path(&graph, a, c, 20, |_| 1).unwrap();
+        let fastest = fastest_path(&graph, a, c, |_| 1).unwrap();
+
+        // Then: Leaving a early means waiting at b.
+        assert_eq!(earliest.departure_time(), 0);
+        assert_eq!(earliest.arrival_time(), 6);
+        assert_eq!(latest.departure_time(), 4);
+        assert_eq!(latest.arrival_time(), 6);
+        assert_eq!(fastest, latest);
+        assert_eq!(fastest.duration(), 2);
+
+        // And: c can not be reached after the edge to it disappears.
+        assert!(earliest_arrival_path(&graph, a, c, 5, |_| 1).is_none());
+        assert!(latest_departure_path(&graph, a, c, 5, |_| 1).is_none());
+        // But going backward is possible.
+        assert_eq!(
+            earliest_arrival_path(&graph, c, a, 0, |_| 1)
+                .unwrap()
+                .arrival_time(),
+            7
+        );
+    }
+
+    #[test]
+    fn fastest_path_is_not_earliest() {
+        // Given: A slow early route and a fast late route.
+        //
+        //      a  --(t: 0, takes 10)-->  c
+        //      a  --(t: 20, takes 1)-->  b  --(t: 21, takes 1)-->  c
+        //
+        let mut graph = TemporalGraph::init(ListGraph::init(DiList::<usize>::init()));
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_at(a, c, 10.into(), 0).unwrap();
+        graph.add_edge_at(a, b, 1.into(), 20).unwrap();
+        graph.add_edge_at(b, c, 1.into(), 21).unwrap();
+        let duration_fn = |edge: &DefaultEdge<usize>| edge.get_weight().unwrap();
+
+        // When:
+        let earliest = earliest_arrival_path(&graph, a, c, 0, duration_fn).unwrap();
+        let fastest = fastest_path(&graph, a, c, duration_fn).unwrap();
+
+        // Then:
+        assert_eq!(earliest.vertices(), vec![a, c]);
+        assert_eq!(earliest.arrival_time(), 10);
+        assert_eq!(fastest.vertices(), vec![a, b, c]);
+        assert_eq!(fastest.duration(), 2);
+    }
+
+    #[test]
+    fn fastest_path_matches_brute_force() {
+        let mut rng = Rng::seed_from(11);
+        for _ in 0..50 {
+            // Given: Random graph with random contacts, each taking one unit of time.
+            let mut graph = TemporalGraph::init(MultiListGraph::init(DiList::<usize>::init()));
+            let vertices: Vec<usize> = (0..5).map(|_| graph.add_vertex()).collect();
+            for _ in 0..12 {
+                let (src, dst) = (rng.next_below(5), rng.next_below(5));
+                if src != dst {
+                    let start = rng.next_below(10);
+                    let interval = TimeInterval::init(start, start + 1 + rng.next_below(3));
+                    graph
+                        .add_edge_during(vertices[src], vertices[dst], 1.into(), interval)
+                        .unwrap();
+                }
+            }
+
+            for dst in 1..5 {
+                // When:
+                let fastest = fastest_path(&graph, vertices[0], vertices[dst], |_| 1);
+
+                // Then: Minimum over every departure time of (earliest arrival - latest departure for that arrival).
+                let expected = (0..15)
+                    .filter_map(|time| {
+                        earliest_arrival_path(&graph, vertices[0], vertices[dst], time, |_| 1)
+                    })
+                    .map(|path| {
+                        path.arrival_time()
+                            - latest_departure_path(
+                                &graph,
+                                vertices[0],
+                                vertices[dst],
+                                path.arrival_time(),
+                                |_| 1,
+                            )
+                            .unwrap()
+                            .departure_time()
+                    })
+                    .min();
+                assert_eq!(fastest.as_ref().map(|path| path.duration()), expected);
+
+                if let Some(path) = fastest {
+                    assert!(is_time_respecting(&graph, &path, 1));
+                    assert_eq!(path.vertices().first(), Some(&vertices[0]));
+                    assert_eq!(path.vertices().last(), Some(&vertices[dst]));
+                }
+            }
+        }
+    }
+}
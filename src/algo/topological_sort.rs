@@ -1,4 +1,4 @@
-use crate::algo::{Dfs, DfsListener};
+use crate::algo::{Algorithm, Dfs, DfsListener};
 use crate::graph::{DirectedEdge, Edge};
 use crate::provide;
 
@@ -101,6 +101,21 @@ impl TopologicalSort {
     }
 }
 
+impl<W, E: Edge<W>, G> Algorithm<G, (W, E)> for TopologicalSort
+where
+    G: provide::Graph<W, E, DirectedEdge> + provide::Vertices + provide::Neighbors,
+{
+    type Output<'a>
+        = Vec<usize>
+    where
+        G: 'a,
+        (W, E): 'a;
+
+    fn run(&self, graph: &G) -> Vec<usize> {
+        TopologicalSort::init().execute(graph)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
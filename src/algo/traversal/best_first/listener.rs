@@ -0,0 +1,43 @@
+use super::BestFirst;
+
+#[allow(unused_variables)]
+/// Trait for structures that want to listen to `BestFirst` events.
+///
+/// `()` implements this trait by ignoring all events, so it can be used when no listener is needed.
+pub trait BestFirstListener<C, L: BestFirstListener<C, L> = Self> {
+    /// Gets called by `BestFirst` when starting the algorithm.
+    ///
+    /// # Arguments
+    /// * `best_first`: `BestFirst` struct. You can query it for costs, parents and etc.
+    /// * `virt_id`: Virtual id of the source vertex. You can access the real id of the vertex by using `IdMap` that `BestFirst` provides.
+    fn on_start(&mut self, best_first: &BestFirst<C, L>, virt_id: usize) {}
+
+    /// Gets called by `BestFirst` when a vertex is reached for the first time.
+    ///
+    /// # Arguments
+    /// * `best_first`: `BestFirst` struct. You can query it for costs, parents and etc.
+    /// * `virt_id`: Virtual id of the vertex. You can access the real id of the vertex by using `IdMap` that `BestFirst` provides.
+    fn on_discover(&mut self, best_first: &BestFirst<C, L>, virt_id: usize) {}
+
+    /// Gets called by `BestFirst` when a cheaper path to an already discovered vertex is found.
+    ///
+    /// # Arguments
+    /// * `best_first`: `BestFirst` struct. You can query it for costs, parents and etc.
+    /// * `virt_id`: Virtual id of the vertex. You can access the real id of the vertex by using `IdMap` that `BestFirst` provides.
+    fn on_relax(&mut self, best_first: &BestFirst<C, L>, virt_id: usize) {}
+
+    /// Gets called by `BestFirst` when a vertex is removed from the queue and its cost becomes final.
+    ///
+    /// # Arguments
+    /// * `best_first`: `BestFirst` struct. You can query it for costs, parents and etc.
+    /// * `virt_id`: Virtual id of the vertex. You can access the real id of the vertex by using `IdMap` that `BestFirst` provides.
+    fn on_settle(&mut self, best_first: &BestFirst<C, L>, virt_id: usize) {}
+
+    /// Gets called by `BestFirst` when finishing the algorithm.
+    ///
+    /// # Arguments
+    /// * `best_first`: `BestFirst` struct. You can query it for costs, parents and etc.
+    fn on_finish(&mut self, best_first: &BestFirst<C, L>) {}
+}
+
+impl<C> BestFirstListener<C> for () {}
@@ -0,0 +1,386 @@
+mod listener;
+mod queue;
+
+pub use listener::BestFirstListener;
+pub use queue::QueueKind;
+
+use queue::Queue;
+use std::cell::RefCell;
+
+use super::{Orientation, OrientedEdges};
+use crate::graph::Edge;
+use crate::provide::{Edges, IdMap, Vertices};
+
+/// Visits vertices in the order of a priority computed from the cost of reaching them.
+///
+/// Starting from a source with cost `zero`, cost of reaching a neighbor through an edge is computed by `combine(cost, edge)`.
+/// Whenever a cheaper cost is found for a vertex, it is (re)inserted into a priority queue with `priority(real_id, cost)`,
+/// and vertices are settled in increasing order of their priority. Each vertex is settled at most once.
+///
+/// Different searches are different choices of `priority`:
+/// * Dijkstra and uniform cost search: `priority = cost`.
+/// * A*: `priority = cost + heuristic(vertex)`.
+/// * Greedy best-first: `priority = heuristic(vertex)`.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::DiMat;
+/// use prepona::graph::MatGraph;
+/// use prepona::algo::BestFirst;
+///
+/// // Given: Graph
+/// //
+/// //      a  --1-->  b  --1-->  c
+/// //      |                     ^
+/// //      '---------5-----------'
+/// //
+/// let mut graph = MatGraph::init(DiMat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(a, c, 5.into());
+///
+/// // When: Performing uniform cost search.
+/// let mut listener = ();
+/// let mut best_first = BestFirst::init(&graph, &mut listener);
+/// best_first.execute(&graph, a, 0, |cost, edge| cost + edge.get_weight().unwrap(), |_, cost| *cost);
+///
+/// // Then:
+/// let id_map = best_first.get_id_map();
+/// assert_eq!(best_first.get_costs()[id_map.virt_id_of(c)], Some(2));
+/// assert_eq!(best_first.path_to(c), Some(vec![a, b, c]));
+/// ```
+pub struct BestFirst<'a, C, L: BestFirstListener<C>> {
+    costs: Vec<Option<C>>,
+    parents: Vec<Option<(usize, usize)>>,
+    settled: Vec<bool>,
+    goal_id: Option<usize>,
+    queue_kind: QueueKind,
+    orientation: Orientation,
+    id_map: IdMap,
+    listener: RefCell<&'a mut L>,
+}
+
+impl<'a, C, L: BestFirstListener<C>> BestFirst<'a, C, L> {
+    /// Initializes the structure.
+    ///
+    /// # Arguments
+    /// * `graph`: Graph to perform the search on.
+    /// * `listener`: To listen to search events.
+    pub fn init<G>(graph: &G, listener: &'a mut L) -> Self
+    where
+        G: Vertices,
+    {
+        BestFirst::init_with_id_map(graph, listener, IdMap::new(graph))
+    }
+
+    /// Initializes the structure with an already computed id map.
+    ///
+    /// # Arguments
+    /// * `graph`: Graph to perform the search on.
+    /// * `listener`: To listen to search events.
+    /// * `id_map`: Maps vertex ids of the graph to continuos ids in range 0..|V|, like the one returned by [`IdMap::new`](crate::provide::IdMap::new).
+    pub fn init_with_id_map<G>(graph: &G, listener: &'a mut L, id_map: IdMap) -> Self
+    where
+        G: Vertices,
+    {
+        let vertex_count = graph.vertex_count();
+
+        BestFirst {
+            costs: std::iter::repeat_with(|| None).take(vertex_count).collect(),
+            parents: vec![None; vertex_count],
+            settled: vec![false; vertex_count],
+            goal_id: None,
+            queue_kind: QueueKind::default(),
+            orientation: Orientation::default(),
+            id_map,
+            listener: RefCell::new(listener),
+        }
+    }
+
+    /// Stops the search as soon as the given vertex is settled.
+    ///
+    /// # Arguments
+    /// `goal_id`: Id of the vertex to stop at.
+    pub fn with_goal(mut self, goal_id: usize) -> Self {
+        self.goal_id = Some(goal_id);
+
+        self
+    }
+
+    /// Uses the given kind of priority queue instead of the default [`Indexed`](crate::algo::QueueKind::Indexed) one.
+    ///
+    /// # Arguments
+    /// `queue_kind`: Kind of priority queue to use.
+    pub fn with_queue(mut self, queue_kind: QueueKind) -> Self {
+        self.queue_kind = queue_kind;
+
+        self
+    }
+
+    /// Follows edges in the given orientation instead of the [`Original`](crate::algo::Orientation::Original) one.
+    ///
+    /// # Arguments
+    /// `orientation`: How to follow the edges.
+    pub fn with_orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = orientation;
+
+        self
+    }
+
+    /// Performs the search and calls the listener on every event.
+    ///
+    /// # Arguments
+    /// * `graph`: Graph to perform the search on.
+    /// * `src_id`: Id of the vertex to start the search from.
+    /// * `zero`: Cost of reaching the source.
+    /// * `combine`: Computes cost of reaching the destination of an edge from the cost of reaching its source.
+    /// * `priority`: Computes priority of a vertex from its real id and its current cost. Vertices with lower priority are settled first.
+    pub fn execute<W, E, G, P, FC, FP>(
+        &mut self,
+        graph: &G,
+        src_id: usize,
+        zero: C,
+        combine: FC,
+        priority: FP,
+    ) where
+        E: Edge<W>,
+        G: Vertices + Edges<W, E>,
+        P: Ord,
+        C: PartialOrd,
+        FC: Fn(&C, &E) -> C,
+        FP: Fn(usize, &C) -> P,
+    {
+        let src_virt_id = self.id_map.virt_id_of(src_id);
+        let goal_virt_id = self.goal_id.map(|goal_id| self.id_map.virt_id_of(goal_id));
+        let oriented = OrientedEdges::init(graph, &self.id_map, self.orientation);
+
+        let mut queue = Queue::init(self.queue_kind, self.costs.len());
+        queue.push(src_virt_id, priority(src_id, &zero));
+        self.costs[src_virt_id] = Some(zero);
+        self.listener.borrow_mut().on_start(self, src_virt_id);
+        self.listener.borrow_mut().on_discover(self, src_virt_id);
+
+        while let Some(virt_id) = queue.pop() {
+            // Vertex is already settled through an entry with lower priority, so this entry is stale(only happens with the lazy queue).
+            if self.settled[virt_id] {
+                continue;
+            }
+
+            self.settled[virt_id] = true;
+            self.listener.borrow_mut().on_settle(self, virt_id);
+
+            if Some(virt_id) == goal_virt_id {
+                break;
+            }
+
+            let real_id = self.id_map.real_id_of(virt_id);
+
+            for (n_id, edge) in oriented.of(graph, virt_id, real_id) {
+                let n_virt_id = self.id_map.virt_id_of(n_id);
+
+                if self.settled[n_virt_id] {
+                    continue;
+                }
+
+                let cost = combine(self.costs[virt_id].as_ref().unwrap(), edge);
+
+                let is_discovered = self.costs[n_virt_id].is_some();
+                let is_cheaper = self.costs[n_virt_id]
+                    .as_ref()
+                    .is_none_or(|n_cost| cost < *n_cost);
+
+                if is_cheaper {
+                    queue.push(n_virt_id, priority(n_id, &cost));
+                    self.costs[n_virt_id] = Some(cost);
+                    self.parents[n_virt_id] = Some((virt_id, edge.get_id()));
+
+                    if is_discovered {
+                        self.listener.borrow_mut().on_relax(self, n_virt_id);
+                    } else {
+                        self.listener.borrow_mut().on_discover(self, n_virt_id);
+                    }
+                }
+            }
+        }
+
+        self.listener.borrow_mut().on_finish(self);
+    }
+
+    /// # Arguments
+    /// `dst_id`: Id of the destination vertex.
+    ///
+    /// # Returns
+    /// * `Some`: Containing ids of the vertices on the best found path from the source to `dst_id`, including both ends.
+    /// * `None`: If `dst_id` is not reached.
+    pub fn path_to(&self, dst_id: usize) -> Option<Vec<usize>> {
+        let mut virt_id = self.id_map.virt_id_of(dst_id);
+        self.costs[virt_id].as_ref()?;
+
+        let mut path = vec![dst_id];
+        while let Some((parent_virt_id, _)) = self.parents[virt_id] {
+            path.push(self.id_map.real_id_of(parent_virt_id));
+            virt_id = parent_virt_id;
+        }
+        path.reverse();
+
+        Some(path)
+    }
+
+    /// # Returns
+    /// Cost of each vertex, `None` if vertex is not reached. Note that cost of vertex with virtual id of `i` is in `get_costs()[i]`.
+    pub fn get_costs(&self) -> &Vec<Option<C>> {
+        &self.costs
+    }
+
+    /// # Returns
+    /// Parent of each vertex in the search tree as (virtual id of parent, id of the edge from parent). `None` for the source and unreached vertices.
+    pub fn get_parents(&self) -> &Vec<Option<(usize, usize)>> {
+        &self.parents
+    }
+
+    /// # Returns
+    /// Whether cost of each vertex is final. Note that state of vertex with virtual id of `i` is in `get_settled()[i]`.
+    pub fn get_settled(&self) -> &Vec<bool> {
+        &self.settled
+    }
+
+    /// # Returns
+    /// `IdMap` used by `BestFirst` to map real ids to virtual ids(and vice versa).
+    pub fn get_id_map(&self) -> &IdMap {
+        &self.id_map
+    }
+
+    /// # Returns
+    /// (Costs, Parents, `IdMap`)
+    #[allow(clippy::type_complexity)]
+    pub fn dissolve(self) -> (Vec<Option<C>>, Vec<Option<(usize, usize)>>, IdMap) {
+        (self.costs, self.parents, self.id_map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::MatGraph;
+    use crate::provide::Graph;
+    use crate::storage::Mat;
+
+    struct OrderListener {
+        settled: Vec<usize>,
+        discovered: usize,
+        relaxed: usize,
+    }
+
+    impl BestFirstListener<usize> for OrderListener {
+        fn on_discover(&mut self, _: &BestFirst<usize, Self>, _: usize) {
+            self.discovered += 1;
+        }
+
+        fn on_relax(&mut self, _: &BestFirst<usize, Self>, _: usize) {
+            self.relaxed += 1;
+        }
+
+        fn on_settle(&mut self, best_first: &BestFirst<usize, Self>, virt_id: usize) {
+            self.settled
+                .push(best_first.get_id_map().real_id_of(virt_id));
+        }
+    }
+
+    // Grid
+    //
+    //      0  --  1  --  2
+    //      |      |      |
+    //      3  --  4  --  5
+    //      |      |      |
+    //      6  --  7  --  8
+    //
+    fn grid() -> MatGraph<usize, crate::graph::UndirectedEdge> {
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        for _ in 0..9 {
+            graph.add_vertex();
+        }
+        for row in 0..3 {
+            for col in 0..3 {
+                let id = row * 3 + col;
+                if col < 2 {
+                    graph.add_edge_unchecked(id, id + 1, 1.into());
+                }
+                if row < 2 {
+                    graph.add_edge_unchecked(id, id + 3, 1.into());
+                }
+            }
+        }
+
+        graph
+    }
+
+    fn manhattan(src: usize, dst: usize) -> usize {
+        (src / 3).abs_diff(dst / 3) + (src % 3).abs_diff(dst % 3)
+    }
+
+    #[test]
+    fn uniform_cost_settles_in_order_of_cost() {
+        // Given:
+        let graph = grid();
+        let mut listener = OrderListener {
+            settled: vec![],
+            discovered: 0,
+            relaxed: 0,
+        };
+
+        // When: Performing uniform cost search from the corner.
+        let mut best_first = BestFirst::init(&graph, &mut listener);
+        best_first.execute(
+            &graph,
+            0,
+            0,
+            |cost, edge| cost + edge.get_weight().unwrap(),
+            |_, cost| *cost,
+        );
+        let costs = best_first.get_costs().clone();
+
+        // Then: Vertices are settled in order of their distance and each vertex is discovered once.
+        for (virt_id, cost) in costs.iter().enumerate() {
+            assert_eq!(*cost, Some(manhattan(0, virt_id)));
+        }
+        let settled_costs: Vec<usize> = listener
+            .settled
+            .iter()
+            .map(|id| manhattan(0, *id))
+            .collect();
+        assert!(settled_costs.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert_eq!(listener.settled.len(), 9);
+        assert_eq!(listener.discovered, 9);
+        assert_eq!(listener.relaxed, 0);
+    }
+
+    #[test]
+    fn greedy_goes_straight_to_goal() {
+        // Given:
+        let graph = grid();
+        let mut listener = OrderListener {
+            settled: vec![],
+            discovered: 0,
+            relaxed: 0,
+        };
+
+        // When: Performing greedy best-first search towards the opposite corner.
+        let mut best_first = BestFirst::init(&graph, &mut listener).with_goal(8);
+        best_first.execute(
+            &graph,
+            0,
+            0,
+            |cost, edge| cost + edge.get_weight().unwrap(),
+            |id, _| manhattan(id, 8),
+        );
+        let path = best_first.path_to(8).unwrap();
+
+        // Then: Only vertices on the path are settled.
+        assert_eq!(path.len(), 5);
+        assert_eq!(listener.settled, path);
+    }
+}
@@ -0,0 +1,200 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// Priority queue that [`BestFirst`](crate::algo::BestFirst) uses to pick the next vertex to settle.
+///
+/// Both kinds settle vertices in exactly the same order, they only differ in how they handle a vertex whose priority changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QueueKind {
+    /// An indexed 4-ary heap that holds at most one entry per vertex and updates the entry in place(decrease-key).
+    /// Memory usage is bounded by the number of vertices.
+    #[default]
+    Indexed,
+
+    /// A binary heap that pushes a new entry every time priority of a vertex changes and skips stale entries when they are popped.
+    /// Memory usage is bounded by the number of edges.
+    Lazy,
+}
+
+pub(super) enum Queue<P> {
+    Indexed(IndexedHeap<P>),
+    Lazy(BinaryHeap<Reverse<(P, usize)>>),
+}
+
+impl<P: Ord> Queue<P> {
+    pub fn init(kind: QueueKind, vertex_count: usize) -> Self {
+        match kind {
+            QueueKind::Indexed => Queue::Indexed(IndexedHeap::init(vertex_count)),
+            QueueKind::Lazy => Queue::Lazy(BinaryHeap::new()),
+        }
+    }
+
+    pub fn push(&mut self, virt_id: usize, priority: P) {
+        match self {
+            Queue::Indexed(heap) => heap.push_or_update(virt_id, priority),
+            Queue::Lazy(heap) => heap.push(Reverse((priority, virt_id))),
+        }
+    }
+
+    pub fn pop(&mut self) -> Option<usize> {
+        match self {
+            Queue::Indexed(heap) => heap.pop().map(|(_, virt_id)| virt_id),
+            Queue::Lazy(heap) => heap.pop().map(|Reverse((_, virt_id))| virt_id),
+        }
+    }
+}
+
+const ARITY: usize = 4;
+
+// Min heap of (priority, virtual id) that knows the position of each virtual id in the heap.
+// Ties between equal priorities are broken by virtual id, so the order of pops is the same as the lazy binary heap.
+pub(super) struct IndexedHeap<P> {
+    heap: Vec<(P, usize)>,
+    positions: Vec<Option<usize>>,
+}
+
+impl<P: Ord> IndexedHeap<P> {
+    pub fn init(vertex_count: usize) -> Self {
+        IndexedHeap {
+            heap: vec![],
+            positions: vec![None; vertex_count],
+        }
+    }
+
+    pub fn push_or_update(&mut self, virt_id: usize, priority: P) {
+        match self.positions[virt_id] {
+            Some(position) => {
+                self.heap[position].0 = priority;
+
+                // New priority may be lower or higher than the old one.
+                let position = self.sift_up(position);
+                self.sift_down(position);
+            }
+            None => {
+                self.heap.push((priority, virt_id));
+                self.positions[virt_id] = Some(self.heap.len() - 1);
+                self.sift_up(self.heap.len() - 1);
+            }
+        }
+    }
+
+    pub fn pop(&mut self) -> Option<(P, usize)> {
+        if self.heap.is_empty() {
+            return None;
+        }
+
+        let last = self.heap.len() - 1;
+        self.swap(0, last);
+
+        let (priority, virt_id) = self.heap.pop().unwrap();
+        self.positions[virt_id] = None;
+
+        if !self.heap.is_empty() {
+            self.sift_down(0);
+        }
+
+        Some((priority, virt_id))
+    }
+
+    fn swap(&mut self, i: usize, j: usize) {
+        self.heap.swap(i, j);
+        self.positions[self.heap[i].1] = Some(i);
+        self.positions[self.heap[j].1] = Some(j);
+    }
+
+    // Returns the final position of the entry.
+    fn sift_up(&mut self, mut position: usize) -> usize {
+        while position > 0 {
+            let parent = (position - 1) / ARITY;
+            if self.heap[position] >= self.heap[parent] {
+                break;
+            }
+
+            self.swap(position, parent);
+            position = parent;
+        }
+
+        position
+    }
+
+    fn sift_down(&mut self, mut position: usize) {
+        loop {
+            let first_child = position * ARITY + 1;
+            let last_child = (first_child + ARITY).min(self.heap.len());
+
+            let min_child =
+                (first_child..last_child).min_by(|i, j| self.heap[*i].cmp(&self.heap[*j]));
+
+            match min_child {
+                Some(child) if self.heap[child] < self.heap[position] => {
+                    self.swap(position, child);
+                    position = child;
+                }
+                _ => break,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::misc::rng::Rng;
+
+    #[test]
+    fn indexed_heap_matches_lazy_heap() {
+        // Given: Random sequence of pushes, updates and pops over 50 vertices.
+        let mut rng = Rng::seed_from(7);
+        let mut indexed = Queue::init(QueueKind::Indexed, 50);
+        let mut lazy = Queue::init(QueueKind::Lazy, 50);
+        let mut priorities = vec![None; 50];
+
+        for _ in 0..2000 {
+            if rng.next_bool(0.6) {
+                let virt_id = rng.next_below(50);
+                let priority = rng.next_below(100);
+
+                indexed.push(virt_id, priority);
+                lazy.push(virt_id, priority);
+                priorities[virt_id] = Some(priority);
+            } else {
+                // Lazy heap may return stale entries, so skip the ones that do not match the latest priority.
+                let expected = loop {
+                    match lazy {
+                        Queue::Lazy(ref mut heap) => match heap.pop() {
+                            Some(Reverse((priority, virt_id))) => {
+                                if priorities[virt_id] == Some(priority) {
+                                    priorities[virt_id] = None;
+                                    break Some(virt_id);
+                                }
+                            }
+                            None => break None,
+                        },
+                        Queue::Indexed(_) => unreachable!(),
+                    }
+                };
+
+                // Then: Both heaps pop the same vertex.
+                assert_eq!(indexed.pop(), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn indexed_heap_increase_priority() {
+        // Given: Heap with three entries.
+        let mut heap = IndexedHeap::init(3);
+        heap.push_or_update(0, 1);
+        heap.push_or_update(1, 2);
+        heap.push_or_update(2, 3);
+
+        // When: Increasing priority of the minimum.
+        heap.push_or_update(0, 10);
+
+        // Then:
+        assert_eq!(heap.pop(), Some((2, 1)));
+        assert_eq!(heap.pop(), Some((3, 2)));
+        assert_eq!(heap.pop(), Some((10, 0)));
+        assert_eq!(heap.pop(), None);
+    }
+}
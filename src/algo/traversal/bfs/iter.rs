@@ -0,0 +1,114 @@
+use std::collections::{HashSet, VecDeque};
+
+use crate::algo::Walker;
+use crate::provide::{Neighbors, Vertices};
+
+/// Visits vertices in breadth-first order, one vertex per call.
+///
+/// Unlike [`Bfs`](crate::algo::Bfs), `BfsIter` does not borrow the graph or need a listener.
+/// Graph is passed to every call of [`next`](crate::algo::BfsIter::next), so traversal can be interleaved with custom logic.
+/// Vertices that are removed from the graph in the meantime are skipped.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+/// use prepona::algo::{BfsIter, Walker};
+///
+/// // Given: Graph
+/// //
+/// //      a  --  b  --  c
+/// //      |
+/// //      d  --  e
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// let e = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(a, d, 1.into());
+/// graph.add_edge_unchecked(d, e, 1.into());
+///
+/// // When: Performing BFS.
+/// let order: Vec<usize> = BfsIter::init(a).iter(&graph).collect();
+///
+/// // Then:
+/// assert_eq!(order, vec![a, b, d, c, e]);
+/// ```
+pub struct BfsIter {
+    queue: VecDeque<usize>,
+    discovered: HashSet<usize>,
+}
+
+impl BfsIter {
+    /// Initializes the structure.
+    ///
+    /// # Arguments
+    /// `start_id`: Id of the vertex to start the traversal from.
+    pub fn init(start_id: usize) -> Self {
+        BfsIter {
+            queue: VecDeque::from(vec![start_id]),
+            discovered: std::iter::once(start_id).collect(),
+        }
+    }
+
+    /// Continues the traversal from another vertex. Vertices that are already visited will not be visited again.
+    ///
+    /// # Arguments
+    /// `start_id`: Id of the vertex to continue the traversal from.
+    pub fn move_to(&mut self, start_id: usize) {
+        self.queue.clear();
+        if self.discovered.insert(start_id) {
+            self.queue.push_back(start_id);
+        }
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex to check.
+    ///
+    /// # Returns
+    /// `true` if the vertex is visited or is waiting in the queue to be visited.
+    pub fn is_discovered(&self, vertex_id: usize) -> bool {
+        self.discovered.contains(&vertex_id)
+    }
+
+    /// # Arguments
+    /// `graph`: Graph to traverse.
+    ///
+    /// # Returns
+    /// * `Some`: Containing id of the next visited vertex.
+    /// * `None`: If all vertices reachable from the start are visited.
+    pub fn next<G>(&mut self, graph: &G) -> Option<usize>
+    where
+        G: Vertices + Neighbors,
+    {
+        while let Some(vertex_id) = self.queue.pop_front() {
+            if !graph.contains_vertex(vertex_id) {
+                continue;
+            }
+
+            for n_id in graph.neighbors_unchecked(vertex_id) {
+                if self.discovered.insert(n_id) {
+                    self.queue.push_back(n_id);
+                }
+            }
+
+            return Some(vertex_id);
+        }
+
+        None
+    }
+}
+
+impl Walker for BfsIter {
+    fn walk_next<G>(&mut self, graph: &G) -> Option<usize>
+    where
+        G: Vertices + Neighbors,
+    {
+        self.next(graph)
+    }
+}
@@ -1,11 +1,13 @@
+mod iter;
 mod listener;
 
+pub use iter::BfsIter;
 pub use listener::BfsListener;
 
 use magnitude::Magnitude;
 use std::{cell::RefCell, collections::VecDeque};
 
-use super::Color;
+use super::{Color, Orientation, OrientedNeighbors};
 use crate::provide::{self, IdMap};
 
 /// Visits graph vertices in a breath-first manner.
@@ -17,6 +19,7 @@ pub struct Bfs<'a, L: BfsListener> {
     time: usize,
     id_map: IdMap,
     start_ids: Vec<usize>,
+    orientation: Orientation,
     listener: RefCell<&'a mut L>,
 }
 
@@ -39,13 +42,31 @@ impl<'a, L: BfsListener> Bfs<'a, L> {
     /// * `graph`: Graph to perform the BFS on.
     /// * `listener`: To listen to bfs events.
     /// * `start_ids`: List of ids to start the bfs from.
-    pub fn init_with_starts<G>(graph: &G, listener: &'a mut L, mut start_ids: Vec<usize>) -> Self
+    pub fn init_with_starts<G>(graph: &G, listener: &'a mut L, start_ids: Vec<usize>) -> Self
     where
         G: provide::Vertices + provide::Neighbors,
     {
-        let vertex_count = graph.vertex_count();
+        Bfs::init_with_id_map(graph, listener, start_ids, IdMap::new(graph))
+    }
 
-        let id_map = graph.continuos_id_map();
+    /// Initializes the structure with an already computed id map.
+    /// Use this function to avoid computing the id map again when you already have one for the graph.
+    ///
+    /// # Arguments
+    /// * `graph`: Graph to perform the BFS on.
+    /// * `listener`: To listen to bfs events.
+    /// * `start_ids`: List of ids to start the bfs from.
+    /// * `id_map`: Maps vertex ids of the graph to continuos ids in range 0..|V|, like the one returned by [`IdMap::new`](crate::provide::IdMap::new).
+    pub fn init_with_id_map<G>(
+        graph: &G,
+        listener: &'a mut L,
+        mut start_ids: Vec<usize>,
+        id_map: IdMap,
+    ) -> Self
+    where
+        G: provide::Vertices + provide::Neighbors,
+    {
+        let vertex_count = graph.vertex_count();
 
         start_ids = start_ids
             .into_iter()
@@ -58,12 +79,23 @@ impl<'a, L: BfsListener> Bfs<'a, L> {
             discovered: vec![Magnitude::PosInfinite; vertex_count],
             finished: vec![Magnitude::PosInfinite; vertex_count],
             time: 0,
-            id_map: graph.continuos_id_map(),
+            id_map,
             listener: RefCell::new(listener),
             start_ids,
+            orientation: Orientation::default(),
         }
     }
 
+    /// Follows edges in the given orientation instead of the [`Original`](crate::algo::Orientation::Original) one.
+    ///
+    /// # Arguments
+    /// `orientation`: How to follow the edges.
+    pub fn with_orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = orientation;
+
+        self
+    }
+
     fn next_start_id(&self) -> Option<usize> {
         if self.start_ids.is_empty() {
             self.colors.iter().position(|color| *color == Color::White)
@@ -80,6 +112,8 @@ impl<'a, L: BfsListener> Bfs<'a, L> {
     where
         G: provide::Vertices + provide::Neighbors,
     {
+        let oriented = OrientedNeighbors::init(graph, &self.id_map, self.orientation);
+
         while let Some(start_id) = self.next_start_id() {
             self.time += 1;
             self.queue.push_back(start_id);
@@ -98,9 +132,8 @@ impl<'a, L: BfsListener> Bfs<'a, L> {
 
                         let real_id = self.id_map.real_id_of(virt_id);
 
-                        let mut neighbors = graph
-                            .neighbors_unchecked(real_id)
-                            .into_iter()
+                        let mut neighbors = oriented
+                            .of(graph, virt_id, real_id)
                             .map(|real_id| self.id_map.virt_id_of(real_id))
                             .filter(|virt_id| self.colors[*virt_id] == Color::White)
                             .collect();
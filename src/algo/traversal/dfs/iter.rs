@@ -0,0 +1,175 @@
+use std::collections::HashSet;
+
+use crate::algo::Walker;
+use crate::provide::{Neighbors, Vertices};
+
+/// Visits vertices in depth-first(pre) order, one vertex per call.
+///
+/// Unlike [`Dfs`](crate::algo::Dfs), `DfsIter` does not borrow the graph or need a listener.
+/// Graph is passed to every call of [`next`](crate::algo::DfsIter::next), so traversal can be interleaved with custom logic.
+/// Vertices that are removed from the graph in the meantime are skipped.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::DiMat;
+/// use prepona::graph::MatGraph;
+/// use prepona::algo::{DfsIter, Walker};
+///
+/// // Given: Graph
+/// //
+/// //      a  -->  b  -->  c
+/// //      |
+/// //      '---->  d
+/// //
+/// let mut graph = MatGraph::init(DiMat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(a, d, 1.into());
+///
+/// // When: Visiting the first vertex and then modifying the graph.
+/// let mut dfs = DfsIter::init(a);
+/// assert_eq!(dfs.next(&graph), Some(a));
+///
+/// let e = graph.add_vertex();
+/// graph.add_edge_unchecked(c, e, 1.into());
+///
+/// // Then: Rest of the traversal sees the new vertex.
+/// let rest: Vec<usize> = dfs.iter(&graph).collect();
+/// assert_eq!(rest, vec![b, c, e, d]);
+/// ```
+pub struct DfsIter {
+    stack: Vec<usize>,
+    discovered: HashSet<usize>,
+}
+
+impl DfsIter {
+    /// Initializes the structure.
+    ///
+    /// # Arguments
+    /// `start_id`: Id of the vertex to start the traversal from.
+    pub fn init(start_id: usize) -> Self {
+        DfsIter {
+            stack: vec![start_id],
+            discovered: HashSet::new(),
+        }
+    }
+
+    /// Continues the traversal from another vertex. Vertices that are already visited will not be visited again.
+    ///
+    /// # Arguments
+    /// `start_id`: Id of the vertex to continue the traversal from.
+    pub fn move_to(&mut self, start_id: usize) {
+        self.stack.clear();
+        self.stack.push(start_id);
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex to check.
+    ///
+    /// # Returns
+    /// `true` if the vertex is already visited.
+    pub fn is_discovered(&self, vertex_id: usize) -> bool {
+        self.discovered.contains(&vertex_id)
+    }
+
+    /// # Arguments
+    /// `graph`: Graph to traverse.
+    ///
+    /// # Returns
+    /// * `Some`: Containing id of the next visited vertex.
+    /// * `None`: If all vertices reachable from the start are visited.
+    pub fn next<G>(&mut self, graph: &G) -> Option<usize>
+    where
+        G: Vertices + Neighbors,
+    {
+        while let Some(vertex_id) = self.stack.pop() {
+            if !graph.contains_vertex(vertex_id) || !self.discovered.insert(vertex_id) {
+                continue;
+            }
+
+            // Neighbors are pushed in reverse, so the first neighbor is visited first.
+            for n_id in graph.neighbors_unchecked(vertex_id).into_iter().rev() {
+                if !self.discovered.contains(&n_id) {
+                    self.stack.push(n_id);
+                }
+            }
+
+            return Some(vertex_id);
+        }
+
+        None
+    }
+}
+
+impl Walker for DfsIter {
+    fn walk_next<G>(&mut self, graph: &G) -> Option<usize>
+    where
+        G: Vertices + Neighbors,
+    {
+        self.next(graph)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::MatGraph;
+    use crate::provide::Graph;
+    use crate::storage::Mat;
+
+    #[test]
+    fn move_to_other_component() {
+        // Given: Graph
+        //
+        //      a  --  b      c  --  d
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(c, d, 1.into());
+
+        // When: Visiting the first component and then moving to the second one.
+        let mut dfs = DfsIter::init(a);
+        let mut order = vec![];
+        while let Some(vertex_id) = dfs.next(&graph) {
+            order.push(vertex_id);
+        }
+        dfs.move_to(d);
+        order.extend(dfs.iter(&graph));
+
+        // Then:
+        assert_eq!(order, vec![a, b, d, c]);
+    }
+
+    #[test]
+    fn removed_vertices_are_skipped() {
+        // Given: Graph
+        //
+        //      a  --  b  --  c
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+
+        // When: Removing b after visiting a.
+        let mut dfs = DfsIter::init(a);
+        assert_eq!(dfs.next(&graph), Some(a));
+        graph.remove_vertex_unchecked(b);
+
+        // Then: Traversal stops since c is only reachable through b.
+        assert_eq!(dfs.next(&graph), None);
+        assert!(dfs.is_discovered(a));
+        assert!(!dfs.is_discovered(c));
+    }
+}
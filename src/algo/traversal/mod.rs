@@ -1,8 +1,15 @@
+mod best_first;
 mod bfs;
 mod dfs;
+mod orientation;
+mod walker;
 
-pub use bfs::{Bfs, BfsListener};
-pub use dfs::{Dfs, DfsListener};
+pub use best_first::{BestFirst, BestFirstListener, QueueKind};
+pub use bfs::{Bfs, BfsIter, BfsListener};
+pub use dfs::{Dfs, DfsIter, DfsListener};
+pub use orientation::Orientation;
+pub(crate) use orientation::{OrientedEdges, OrientedNeighbors};
+pub use walker::{Walker, WalkerIter};
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum Color {
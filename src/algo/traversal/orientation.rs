@@ -0,0 +1,426 @@
+use std::collections::HashSet;
+
+use crate::graph::Edge;
+use crate::provide::{Edges, IdMap, Neighbors, Vertices};
+
+/// How an algorithm follows the edges of a directed graph.
+///
+/// It's accepted by [`Bfs`](crate::algo::Bfs), [`Dfs`](crate::algo::Dfs), [`BestFirst`](crate::algo::BestFirst), [`Dijkstra`](crate::algo::Dijkstra)
+/// and [`ConnectedComponents`](crate::algo::ConnectedComponents), so a directed graph can be traversed backwards or as if it was undirected without copying it.
+/// Outgoing edges are read from the graph as vertices are visited, and only incoming edges of vertices are indexed once when the algorithm starts.
+/// Undirected graphs are traversed the same way in every orientation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Orientation {
+    /// Edges are followed from their source to their destination.
+    #[default]
+    Original,
+
+    /// Edges are followed from their destination to their source.
+    Reversed,
+
+    /// Edges are followed in both directions.
+    Ignore,
+}
+
+// Incoming neighbors of each vertex, which are read along with the outgoing ones under the orientation.
+// Outgoing neighbors are always read from the graph itself when a vertex is visited, so they are never copied.
+pub(crate) struct OrientedNeighbors {
+    orientation: Orientation,
+
+    // Neighbors with an edge to vertex with virtual id of `i` are in the `i`th vector, as real ids.
+    // Empty if orientation is `Original`. Under `Ignore`, neighbors that are also outgoing ones are left out.
+    incoming_of: Vec<Vec<usize>>,
+}
+
+impl OrientedNeighbors {
+    pub fn init<G>(graph: &G, id_map: &IdMap, orientation: Orientation) -> Self
+    where
+        G: Vertices + Neighbors,
+    {
+        let mut incoming_of = vec![];
+
+        if orientation != Orientation::Original {
+            incoming_of = vec![vec![]; id_map.len()];
+
+            for virt_id in 0..id_map.len() {
+                let real_id = id_map.real_id_of(virt_id);
+
+                for dst_real_id in graph.neighbors_unchecked(real_id) {
+                    incoming_of[id_map.virt_id_of(dst_real_id)].push(real_id);
+                }
+            }
+
+            for (virt_id, incoming) in incoming_of.iter_mut().enumerate() {
+                let mut seen: HashSet<usize> = if orientation == Orientation::Ignore {
+                    graph
+                        .neighbors_unchecked(id_map.real_id_of(virt_id))
+                        .into_iter()
+                        .collect()
+                } else {
+                    HashSet::new()
+                };
+                incoming.retain(|real_id| seen.insert(*real_id));
+            }
+        }
+
+        OrientedNeighbors {
+            orientation,
+            incoming_of,
+        }
+    }
+
+    // # Returns
+    // Real id of neighbors of the vertex under the orientation.
+    pub fn of<'a, G>(
+        &'a self,
+        graph: &G,
+        virt_id: usize,
+        real_id: usize,
+    ) -> impl Iterator<Item = usize> + 'a
+    where
+        G: Neighbors,
+    {
+        let outgoing = if self.orientation == Orientation::Reversed {
+            vec![]
+        } else {
+            graph.neighbors_unchecked(real_id)
+        };
+        let incoming = self.incoming_of.get(virt_id).map_or(&[][..], Vec::as_slice);
+
+        outgoing.into_iter().chain(incoming.iter().copied())
+    }
+}
+
+// Same as `OrientedNeighbors` but each neighbor comes with the edge leading to it.
+// Each incoming edge appears at most once in the edges of a vertex, even if it's a loop or graph is undirected.
+pub(crate) struct OrientedEdges<'g, E> {
+    orientation: Orientation,
+
+    incoming_of: Vec<Vec<(usize, &'g E)>>,
+}
+
+impl<'g, E> OrientedEdges<'g, E> {
+    pub fn init<W, G>(graph: &'g G, id_map: &IdMap, orientation: Orientation) -> Self
+    where
+        E: Edge<W>,
+        G: Vertices + Edges<W, E>,
+    {
+        let mut incoming_of = vec![];
+
+        if orientation != Orientation::Original {
+            incoming_of = vec![vec![]; id_map.len()];
+
+            for virt_id in 0..id_map.len() {
+                let real_id = id_map.real_id_of(virt_id);
+
+                for (dst_real_id, edge) in graph.edges_from_unchecked(real_id) {
+                    incoming_of[id_map.virt_id_of(dst_real_id)].push((real_id, edge));
+                }
+            }
+
+            for (virt_id, incoming) in incoming_of.iter_mut().enumerate() {
+                let mut seen: HashSet<usize> = if orientation == Orientation::Ignore {
+                    graph
+                        .edges_from_unchecked(id_map.real_id_of(virt_id))
+                        .into_iter()
+                        .map(|(_, edge)| edge.get_id())
+                        .collect()
+                } else {
+                    HashSet::new()
+                };
+                incoming.retain(|(_, edge)| seen.insert(edge.get_id()));
+            }
+        }
+
+        OrientedEdges {
+            orientation,
+            incoming_of,
+        }
+    }
+
+    // # Returns
+    // Edges of the vertex under the orientation in the format of (`dst_id`, `edge`).
+    pub fn of<'a, W, G>(
+        &'a self,
+        graph: &'g G,
+        virt_id: usize,
+        real_id: usize,
+    ) -> impl Iterator<Item = (usize, &'g E)> + 'a
+    where
+        E: Edge<W>,
+        G: Edges<W, E>,
+    {
+        let outgoing = if self.orientation == Orientation::Reversed {
+            vec![]
+        } else {
+            graph.edges_from_unchecked(real_id)
+        };
+        let incoming = self.incoming_of.get(virt_id).map_or(&[][..], Vec::as_slice);
+
+        outgoing.into_iter().chain(incoming.iter().copied())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::{Bfs, BfsListener, ConnectedComponents, Dfs, DfsListener, Dijkstra};
+    use crate::graph::{DirectedEdge, ListGraph, MatGraph};
+    use crate::misc::rng::Rng;
+    use crate::provide::Graph;
+    use crate::storage::{DiList, DiMat, List};
+
+    #[derive(Default)]
+    struct Visited {
+        vertex_ids: Vec<usize>,
+    }
+
+    impl BfsListener for Visited {
+        fn on_white(&mut self, bfs: &Bfs<Self>, virt_id: usize) {
+            self.vertex_ids.push(bfs.get_id_map().real_id_of(virt_id));
+        }
+    }
+
+    impl DfsListener for Visited {
+        fn on_white(&mut self, dfs: &Dfs<Self>, virt_id: usize) {
+            self.vertex_ids.push(dfs.get_id_map().real_id_of(virt_id));
+        }
+    }
+
+    fn bfs_from<G: Vertices + Neighbors>(
+        graph: &G,
+        src_id: usize,
+        orientation: Orientation,
+    ) -> Vec<usize> {
+        let mut visited = Visited::default();
+        Bfs::init_with_starts(graph, &mut visited, vec![src_id])
+            .with_orientation(orientation)
+            .execute(graph);
+
+        visited.vertex_ids.sort_unstable();
+        visited.vertex_ids
+    }
+
+    fn dfs_from<G: Vertices + Neighbors>(
+        graph: &G,
+        src_id: usize,
+        orientation: Orientation,
+    ) -> Vec<usize> {
+        let mut visited = Visited::default();
+        Dfs::init_with_starts(graph, &mut visited, vec![src_id])
+            .with_orientation(orientation)
+            .execute(graph);
+
+        visited.vertex_ids.sort_unstable();
+        visited.vertex_ids
+    }
+
+    #[test]
+    fn traversals_follow_orientation() {
+        // Given: Graph
+        //
+        //      a  -->  b  -->  c
+        //              ^
+        //      d  -----'
+        //
+        let mut graph = ListGraph::init(DiList::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(d, b, 1.into());
+        graph.add_edge_unchecked(b, b, 1.into());
+
+        for traverse in [bfs_from::<ListGraph<usize, DirectedEdge>>, dfs_from] {
+            // When:
+            let original = traverse(&graph, b, Orientation::Original);
+            let reversed = traverse(&graph, b, Orientation::Reversed);
+            let ignored = traverse(&graph, c, Orientation::Ignore);
+
+            // Then:
+            assert_eq!(original, vec![b, c]);
+            assert_eq!(reversed, vec![a, b, d]);
+            assert_eq!(ignored, vec![a, b, c, d]);
+        }
+    }
+
+    #[test]
+    fn reversed_dijkstra_finds_distances_to_source() {
+        let mut rng = Rng::seed_from(3164);
+
+        for _ in 0..10 {
+            // Given: Random weighted directed graph.
+            let mut graph = MatGraph::init(DiMat::<usize>::init());
+            for _ in 0..10 {
+                graph.add_vertex();
+            }
+            for _ in 0..25 {
+                let src_id = rng.next_below(10);
+                let dst_id = rng.next_below(10);
+                if src_id != dst_id && !graph.has_any_edge_unchecked(src_id, dst_id) {
+                    graph.add_edge_unchecked(src_id, dst_id, (1 + rng.next_below(9)).into());
+                }
+            }
+
+            // When:
+            let reversed = Dijkstra::init(&graph)
+                .with_orientation(Orientation::Reversed)
+                .execute(&graph, 0);
+
+            // Then:
+            for vertex_id in graph.vertices() {
+                let forward = Dijkstra::init(&graph).execute(&graph, vertex_id);
+                let (to_src, from_vertex) = (
+                    reversed.distance_to(vertex_id).unwrap(),
+                    forward.distance_to(0).unwrap(),
+                );
+                assert_eq!(to_src.is_finite(), from_vertex.is_finite());
+                if to_src.is_finite() {
+                    assert_eq!(to_src, from_vertex);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn dijkstra_tree_keeps_direction_of_edges() {
+        // Given: Graph
+        //
+        //      a  -->  b  -->  d
+        //              ^
+        //      c  -----'
+        //
+        let mut graph = ListGraph::init(DiList::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(c, b, 1.into());
+        graph.add_edge_unchecked(b, d, 1.into());
+
+        for orientation in [
+            Orientation::Original,
+            Orientation::Reversed,
+            Orientation::Ignore,
+        ] {
+            // When:
+            let sp = Dijkstra::init(&graph)
+                .with_orientation(orientation)
+                .execute(&graph, b);
+
+            // Then: Every query sees the edges in their direction in the graph.
+            for src_id in sp.vertices() {
+                let mut neighbors = sp.neighbors_unchecked(src_id);
+                let mut dst_ids: Vec<usize> = sp
+                    .edges_from_unchecked(src_id)
+                    .into_iter()
+                    .map(|(dst_id, _)| dst_id)
+                    .collect();
+                neighbors.sort_unstable();
+                dst_ids.sort_unstable();
+                assert_eq!(neighbors, dst_ids);
+                for dst_id in sp.vertices() {
+                    assert_eq!(
+                        sp.has_any_edge_unchecked(src_id, dst_id),
+                        neighbors.contains(&dst_id)
+                    );
+                    if sp.has_any_edge_unchecked(src_id, dst_id) {
+                        assert!(graph.has_any_edge_unchecked(src_id, dst_id));
+                    }
+                }
+            }
+            if orientation != Orientation::Original {
+                assert_eq!(sp.path_to(a), Some(vec![b, a]));
+                assert!(sp.has_any_edge_unchecked(a, b));
+            }
+            if orientation != Orientation::Reversed {
+                assert_eq!(sp.path_to(d), Some(vec![b, d]));
+            }
+        }
+    }
+
+    #[test]
+    fn weakly_connected_components() {
+        // Given: Graph
+        //
+        //      a  -->  b  <--  c       d  -->  e       f
+        //
+        let mut graph = ListGraph::init(DiList::<usize>::init());
+        let ids: Vec<usize> = (0..6).map(|_| graph.add_vertex()).collect();
+        graph.add_edge_unchecked(ids[0], ids[1], 1.into());
+        graph.add_edge_unchecked(ids[2], ids[1], 1.into());
+        graph.add_edge_unchecked(ids[3], ids[4], 1.into());
+
+        // When:
+        let mut ccs =
+            ConnectedComponents::init_with_orientation(&graph, Orientation::Ignore).execute(&graph);
+
+        // Then:
+        for cc in ccs.iter_mut() {
+            cc.sort_unstable();
+        }
+        ccs.sort();
+        assert_eq!(
+            ccs,
+            vec![
+                vec![ids[0], ids[1], ids[2]],
+                vec![ids[3], ids[4]],
+                vec![ids[5]]
+            ]
+        );
+    }
+
+    #[test]
+    fn undirected_graph_ignores_orientation() {
+        // Given: a --- b    c
+        let mut graph = ListGraph::init(List::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(a, a, 1.into());
+
+        for orientation in [
+            Orientation::Original,
+            Orientation::Reversed,
+            Orientation::Ignore,
+        ] {
+            // When:
+            let reached = bfs_from(&graph, b, orientation);
+            let ccs =
+                ConnectedComponents::init_with_orientation(&graph, orientation).execute(&graph);
+            let id_map = graph.continuos_id_map();
+            let edges = OrientedEdges::init(&graph, &id_map, orientation);
+
+            // Then:
+            assert_eq!(reached, vec![a, b]);
+            assert_eq!(ccs.len(), 2);
+            let edge_count: usize = (0..id_map.len())
+                .map(|virt_id| {
+                    let mut edge_ids: Vec<usize> = edges
+                        .of(&graph, virt_id, id_map.real_id_of(virt_id))
+                        .map(|(_, edge)| edge.get_id())
+                        .collect();
+                    edge_ids.sort_unstable();
+                    edge_ids.dedup();
+                    edge_ids.len()
+                })
+                .sum();
+            assert_eq!(edge_count, 3);
+            assert!(!reached.contains(&c));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Direction of edges must be ignored")]
+    fn directed_components_without_ignoring_direction() {
+        // Given:
+        let graph = ListGraph::init(DiList::<usize>::init());
+
+        // When:
+        let _ = ConnectedComponents::init_with_orientation(&graph, Orientation::Reversed);
+    }
+}
@@ -0,0 +1,56 @@
+use crate::provide::{Neighbors, Vertices};
+
+/// A traversal that yields vertices one at a time without holding a borrow of the graph.
+///
+/// The graph is passed to each call of [`walk_next`](crate::algo::Walker::walk_next), so the traversal can be paused
+/// and the graph can be inspected or even modified between steps. Use [`iter`](crate::algo::Walker::iter) to get an [`Iterator`]
+/// when the graph does not need to change during the traversal.
+pub trait Walker {
+    /// # Arguments
+    /// `graph`: Graph to traverse.
+    ///
+    /// # Returns
+    /// * `Some`: Containing id of the next visited vertex.
+    /// * `None`: If traversal is finished.
+    fn walk_next<G>(&mut self, graph: &G) -> Option<usize>
+    where
+        G: Vertices + Neighbors;
+
+    /// # Arguments
+    /// `graph`: Graph to traverse.
+    ///
+    /// # Returns
+    /// An iterator over the rest of the traversal.
+    fn iter<G>(self, graph: &G) -> WalkerIter<'_, Self, G>
+    where
+        Self: Sized,
+        G: Vertices + Neighbors,
+    {
+        WalkerIter {
+            walker: self,
+            graph,
+        }
+    }
+}
+
+/// An [`Iterator`] that drives a [`Walker`](crate::algo::Walker) over a borrowed graph.
+pub struct WalkerIter<'a, W, G> {
+    walker: W,
+    graph: &'a G,
+}
+
+impl<'a, W, G> WalkerIter<'a, W, G> {
+    /// # Returns
+    /// The walker, so traversal can be continued later without borrowing the graph.
+    pub fn into_walker(self) -> W {
+        self.walker
+    }
+}
+
+impl<'a, W: Walker, G: Vertices + Neighbors> Iterator for WalkerIter<'a, W, G> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.walker.walk_next(self.graph)
+    }
+}
@@ -0,0 +1,303 @@
+use std::collections::VecDeque;
+
+use crate::provide::{IdMap, Neighbors, Vertices};
+
+/// Reachability and distance index built with pruned landmark labeling, a kind of 2-hop labeling.
+///
+/// Each vertex gets two labels: Hubs it can reach and hubs that can reach it, each with the number of edges in between.
+/// Distance between two vertices is the smallest sum over the hubs their labels share, so a query only merges two short sorted lists
+/// instead of searching the graph. Vertices with higher degree are used as hubs first, and searches from later hubs stop wherever
+/// the existing labels already give the distance, which keeps the labels small for most real world graphs.
+///
+/// The index is a snapshot of the graph: Changes to the graph after building it are not reflected in the queries.
+/// Distances are in number of edges and edge weights are ignored.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::DiList;
+/// use prepona::graph::ListGraph;
+/// use prepona::algo::TwoHopIndex;
+///
+/// // Given: Graph
+/// //
+/// //      a  -->  b  -->  c
+/// //      |               ^
+/// //      '-->  d  -------'
+/// //
+/// let mut graph = ListGraph::init(DiList::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(a, d, 1.into());
+/// graph.add_edge_unchecked(d, c, 1.into());
+///
+/// // When:
+/// let index = TwoHopIndex::build(&graph);
+///
+/// // Then:
+/// assert_eq!(index.query(a, c), Some(2));
+/// assert_eq!(index.query(c, a), None);
+/// assert!(index.is_reachable(d, c));
+/// assert!(!index.is_reachable_within(a, c, 1));
+/// ```
+pub struct TwoHopIndex {
+    id_map: IdMap,
+
+    // Labels of each vertex in the format of (rank of hub, distance), sorted by rank.
+    out_labels: Vec<Vec<(usize, usize)>>,
+    in_labels: Vec<Vec<(usize, usize)>>,
+}
+
+// Smallest distance through a hub shared by both labels.
+fn merged_dist(out_label: &[(usize, usize)], in_label: &[(usize, usize)]) -> Option<usize> {
+    let mut best: Option<usize> = None;
+    let (mut out_index, mut in_index) = (0, 0);
+
+    while out_index < out_label.len() && in_index < in_label.len() {
+        let (out_rank, out_dist) = out_label[out_index];
+        let (in_rank, in_dist) = in_label[in_index];
+
+        if out_rank < in_rank {
+            out_index += 1;
+        } else if in_rank < out_rank {
+            in_index += 1;
+        } else {
+            let dist = out_dist + in_dist;
+            best = Some(best.map_or(dist, |best| best.min(dist)));
+            out_index += 1;
+            in_index += 1;
+        }
+    }
+
+    best
+}
+
+impl TwoHopIndex {
+    /// Builds the index.
+    ///
+    /// # Arguments
+    /// `graph`: Graph to index.
+    ///
+    /// # Returns
+    /// The index.
+    ///
+    /// # Complexity
+    /// O(|V|.(|V| + |E|).L) in the worst case, where L is the size of the largest label.
+    /// It's much smaller in practice because most searches are pruned early.
+    pub fn build<G>(graph: &G) -> Self
+    where
+        G: Vertices + Neighbors,
+    {
+        let id_map = graph.continuos_id_map();
+        let vertex_count = id_map.len();
+
+        let mut successors = vec![vec![]; vertex_count];
+        let mut predecessors = vec![vec![]; vertex_count];
+        for src_real_id in graph.vertices() {
+            let src_id = id_map.virt_id_of(src_real_id);
+
+            for dst_real_id in graph.neighbors_unchecked(src_real_id) {
+                let dst_id = id_map.virt_id_of(dst_real_id);
+                successors[src_id].push(dst_id);
+                predecessors[dst_id].push(src_id);
+            }
+        }
+
+        let mut order: Vec<usize> = (0..vertex_count).collect();
+        order.sort_by_key(|vertex_id| {
+            std::cmp::Reverse(successors[*vertex_id].len() + predecessors[*vertex_id].len())
+        });
+
+        let mut index = TwoHopIndex {
+            id_map,
+            out_labels: vec![vec![]; vertex_count],
+            in_labels: vec![vec![]; vertex_count],
+        };
+
+        let mut dists = vec![None; vertex_count];
+        for (rank, hub_id) in order.into_iter().enumerate() {
+            index.pruned_bfs(rank, hub_id, &successors, &mut dists, true);
+            index.pruned_bfs(rank, hub_id, &predecessors, &mut dists, false);
+        }
+
+        index
+    }
+
+    // Labels every vertex reachable from the hub, or every vertex that reaches it when searching backwards,
+    // unless the existing labels already give a distance that is not longer.
+    fn pruned_bfs(
+        &mut self,
+        rank: usize,
+        hub_id: usize,
+        arcs: &[Vec<usize>],
+        dists: &mut [Option<usize>],
+        is_forward: bool,
+    ) {
+        let mut visited = vec![hub_id];
+        let mut queue = VecDeque::from(vec![hub_id]);
+        dists[hub_id] = Some(0);
+
+        while let Some(vertex_id) = queue.pop_front() {
+            let dist = dists[vertex_id].unwrap();
+
+            let known_dist = if is_forward {
+                merged_dist(&self.out_labels[hub_id], &self.in_labels[vertex_id])
+            } else {
+                merged_dist(&self.out_labels[vertex_id], &self.in_labels[hub_id])
+            };
+            if known_dist.is_some_and(|known_dist| known_dist <= dist) {
+                continue;
+            }
+
+            if is_forward {
+                self.in_labels[vertex_id].push((rank, dist));
+            } else {
+                self.out_labels[vertex_id].push((rank, dist));
+            }
+
+            for next_id in &arcs[vertex_id] {
+                if dists[*next_id].is_none() {
+                    dists[*next_id] = Some(dist + 1);
+                    visited.push(*next_id);
+                    queue.push_back(*next_id);
+                }
+            }
+        }
+
+        for vertex_id in visited {
+            dists[vertex_id] = None;
+        }
+    }
+
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    ///
+    /// # Returns
+    /// * `Some`: Containing number of edges on the shortest path from `src_id` to `dst_id`.
+    /// * `None`: If `dst_id` is not reachable from `src_id` or either of the vertices was not in the graph when the index was built.
+    ///
+    /// # Complexity
+    /// O(L) where L is the size of the labels of the two vertices.
+    pub fn query(&self, src_id: usize, dst_id: usize) -> Option<usize> {
+        let src_id = self.id_map.try_virt_id_of(src_id)?;
+        let dst_id = self.id_map.try_virt_id_of(dst_id)?;
+
+        merged_dist(&self.out_labels[src_id], &self.in_labels[dst_id])
+    }
+
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    ///
+    /// # Returns
+    /// `true` if `dst_id` can be reached from `src_id`. Each vertex can reach itself.
+    pub fn is_reachable(&self, src_id: usize, dst_id: usize) -> bool {
+        self.query(src_id, dst_id).is_some()
+    }
+
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    /// * `max_hops`: Maximum number of edges on the path.
+    ///
+    /// # Returns
+    /// `true` if `dst_id` can be reached from `src_id` using at most `max_hops` edges.
+    pub fn is_reachable_within(&self, src_id: usize, dst_id: usize, max_hops: usize) -> bool {
+        self.query(src_id, dst_id)
+            .is_some_and(|dist| dist <= max_hops)
+    }
+
+    /// # Returns
+    /// Total number of entries in the labels of all vertices, which is what the index spends its memory on.
+    pub fn label_count(&self) -> usize {
+        self.out_labels
+            .iter()
+            .chain(self.in_labels.iter())
+            .map(|label| label.len())
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::bfs_layers;
+    use crate::graph::ListGraph;
+    use crate::misc::rng::Rng;
+    use crate::provide::{Edges, Graph};
+    use crate::storage::{DiList, List};
+
+    fn assert_matches_bfs<G: Vertices + Neighbors>(graph: &G, index: &TwoHopIndex) {
+        for src_id in graph.vertices() {
+            let layers = bfs_layers(graph, vec![src_id]);
+
+            for dst_id in graph.vertices() {
+                let expected = layers.iter().position(|layer| layer.contains(&dst_id));
+                assert_eq!(index.query(src_id, dst_id), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn random_dags() {
+        let mut rng = Rng::seed_from(3159);
+
+        for _ in 0..10 {
+            // Given: Random dag with edges from smaller ids to larger ones.
+            let mut graph = ListGraph::init(DiList::<usize>::init());
+            for _ in 0..40 {
+                graph.add_vertex();
+            }
+            for _ in 0..80 {
+                let src_id = rng.next_below(40);
+                let dst_id = rng.next_below(40);
+                if src_id < dst_id && !graph.has_any_edge_unchecked(src_id, dst_id) {
+                    graph.add_edge_unchecked(src_id, dst_id, 1.into());
+                }
+            }
+
+            // When:
+            let index = TwoHopIndex::build(&graph);
+
+            // Then:
+            assert_matches_bfs(&graph, &index);
+            assert!(index.label_count() < 40 * 40);
+        }
+    }
+
+    #[test]
+    fn undirected_graph_with_removed_vertex() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  c      e
+        //              |
+        //              d
+        //
+        let mut graph = ListGraph::init(List::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let removed = graph.add_vertex();
+        let e = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(b, d, 1.into());
+        graph.remove_vertex_unchecked(removed);
+
+        // When:
+        let index = TwoHopIndex::build(&graph);
+
+        // Then:
+        assert_matches_bfs(&graph, &index);
+        assert_eq!(index.query(c, a), Some(2));
+        assert_eq!(index.query(e, e), Some(0));
+        assert_eq!(index.query(a, removed), None);
+        assert!(index.is_reachable_within(d, a, 2));
+    }
+}
@@ -3,6 +3,7 @@ use std::{cmp::min, marker::PhantomData};
 use magnitude::Magnitude;
 
 use crate::{
+    algo::Algorithm,
     graph::{Edge, UndirectedEdge},
     provide::{Edges, Graph, IdMap, Vertices},
 };
@@ -135,6 +136,21 @@ impl<'a, W, E: Edge<W>> VertexEdgeCut<'a, W, E> {
     }
 }
 
+impl<'v, W, E: Edge<W>, G> Algorithm<G, (W, E)> for VertexEdgeCut<'v, W, E>
+where
+    G: Vertices + Edges<W, E> + Graph<W, E, UndirectedEdge>,
+{
+    type Output<'a>
+        = (Vec<usize>, Vec<(usize, usize, &'a E)>)
+    where
+        G: 'a,
+        (W, E): 'a;
+
+    fn run<'a>(&self, graph: &'a G) -> (Vec<usize>, Vec<(usize, usize, &'a E)>) {
+        VertexEdgeCut::init(graph).execute(graph)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,335 @@
+use std::collections::HashSet;
+
+use crate::gen::rewire::double_edge_swap;
+use crate::graph::{Edge, ListGraph, UndirectedEdge};
+use crate::misc::rng::Rng;
+use crate::provide::{Edges, Graph, Vertices};
+use crate::storage::List;
+
+// Degrees and edges of the simple graph underlying `graph` in terms of continuos ids.
+// Loops are dropped and parallel edges are counted once.
+fn simple_edges_of<W, E, G>(graph: &G) -> (Vec<usize>, Vec<(usize, usize)>)
+where
+    E: Edge<W>,
+    G: Vertices + Edges<W, E> + Graph<W, E, UndirectedEdge>,
+{
+    let id_map = graph.continuos_id_map();
+
+    let mut degrees = vec![0; graph.vertex_count()];
+    let mut edges = HashSet::new();
+    for (src_id, dst_id, _) in graph.edges() {
+        let (src, dst) = (id_map.virt_id_of(src_id), id_map.virt_id_of(dst_id));
+
+        if src != dst && edges.insert((src.min(dst), src.max(dst))) {
+            degrees[src] += 1;
+            degrees[dst] += 1;
+        }
+    }
+
+    // Order of a set changes from run to run. Edges are sorted so rewiring them with the same seed gives the same graph.
+    let mut edges: Vec<(usize, usize)> = edges.into_iter().collect();
+    edges.sort_unstable();
+
+    (degrees, edges)
+}
+
+fn coefficients_of(degrees: &[usize], edges: &[(usize, usize)]) -> Vec<f64> {
+    let max_degree = degrees.iter().copied().max().unwrap_or(0);
+
+    // An edge is among the vertices with degree more than k iff k is less than the smaller degree of its end points.
+    let mut vertex_counts = vec![0; max_degree + 1];
+    for degree in degrees {
+        vertex_counts[*degree] += 1;
+    }
+    let mut edge_counts = vec![0; max_degree + 1];
+    for (src, dst) in edges {
+        edge_counts[degrees[*src].min(degrees[*dst])] += 1;
+    }
+
+    let mut coefficients = vec![];
+    let (mut rich_vertex_count, mut rich_edge_count) = (degrees.len(), edges.len());
+    for k in 0..max_degree {
+        rich_vertex_count -= vertex_counts[k];
+        rich_edge_count -= edge_counts[k];
+
+        if rich_vertex_count < 2 {
+            break;
+        }
+
+        coefficients.push(
+            2.0 * rich_edge_count as f64 / (rich_vertex_count * (rich_vertex_count - 1)) as f64,
+        );
+    }
+
+    coefficients
+}
+
+/// Computes the [rich-club coefficient](https://en.wikipedia.org/wiki/Rich-club_coefficient) of an undirected graph for each degree threshold.
+///
+/// Coefficient for degree k is the density of the subgraph induced by the vertices with degree more than k: 2 * E<sub>k</sub> / (N<sub>k</sub> * (N<sub>k</sub> - 1)).
+/// It's computed on the simple graph underlying `graph`, so loops are ignored and parallel edges are counted once.
+///
+/// # Arguments
+/// `graph`: Graph to compute its rich-club coefficients.
+///
+/// # Returns
+/// Coefficients where `coefficients[k]` is the coefficient for degree k.
+/// Coefficients are only defined while there are at least two vertices with degree more than k, so the length of the returned vector is the first k for which it's not.
+///
+/// # Complexity
+/// O(|V| + |E|)
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::List;
+/// use prepona::graph::ListGraph;
+/// use prepona::analysis::rich_club_coefficient;
+///
+/// // Given: Triangle a, b, c where each vertex also has a leaf.
+/// let mut graph = ListGraph::init(List::<usize>::init());
+/// let hubs: Vec<usize> = (0..3).map(|_| graph.add_vertex()).collect();
+/// for i in 0..3 {
+///     graph.add_edge_unchecked(hubs[i], hubs[(i + 1) % 3], 1.into());
+///     let leaf = graph.add_vertex();
+///     graph.add_edge_unchecked(hubs[i], leaf, 1.into());
+/// }
+///
+/// // When:
+/// let coefficients = rich_club_coefficient(&graph);
+///
+/// // Then: 6 edges among all 6 vertices, then 3 edges among the 3 hubs.
+/// assert_eq!(coefficients, vec![0.4, 1.0, 1.0]);
+/// ```
+pub fn rich_club_coefficient<W, E, G>(graph: &G) -> Vec<f64>
+where
+    E: Edge<W>,
+    G: Vertices + Edges<W, E> + Graph<W, E, UndirectedEdge>,
+{
+    let (degrees, edges) = simple_edges_of(graph);
+
+    coefficients_of(&degrees, &edges)
+}
+
+/// Computes the rich-club coefficient of an undirected graph, normalized against a random graph with the same degrees.
+///
+/// The random graph is made by [`double_edge_swap`](crate::gen::rewire::double_edge_swap) on a copy of the simple graph underlying `graph`.
+/// Each normalized coefficient is the ratio of the coefficient of `graph` to the coefficient of the random graph for the same degree.
+/// Ratios greater than one indicate that vertices with high degree are more connected to each other than expected by chance.
+///
+/// # Arguments
+/// * `graph`: Graph to compute its normalized rich-club coefficients.
+/// * `swaps_per_edge`: Number of swaps to perform on the copy for each of its edges.
+/// * `rng`: Source of randomness.
+///
+/// # Returns
+/// Normalized coefficients where `coefficients[k]` is for degree k, with the same length as the result of [`rich_club_coefficient`](crate::analysis::rich_club_coefficient).
+/// * `Some`: Containing the ratio.
+/// * `None`: If the coefficient of the random graph is zero.
+///
+/// # Complexity
+/// O(|V| + |E| * `swaps_per_edge`) plus the cost of rewiring the copy.
+pub fn normalized_rich_club_coefficient<W, E, G>(
+    graph: &G,
+    swaps_per_edge: usize,
+    rng: &mut Rng,
+) -> Vec<Option<f64>>
+where
+    E: Edge<W>,
+    G: Vertices + Edges<W, E> + Graph<W, E, UndirectedEdge>,
+{
+    let (degrees, edges) = simple_edges_of(graph);
+    let coefficients = coefficients_of(&degrees, &edges);
+
+    let mut random_graph = ListGraph::init(List::<usize>::init());
+    for _ in 0..degrees.len() {
+        random_graph.add_vertex();
+    }
+    for (src, dst) in &edges {
+        random_graph.add_edge_unchecked(*src, *dst, 1.into());
+    }
+    double_edge_swap(&mut random_graph, swaps_per_edge * edges.len(), rng);
+
+    let (random_degrees, random_edges) = simple_edges_of(&random_graph);
+    let random_coefficients = coefficients_of(&random_degrees, &random_edges);
+
+    // Rewiring preserves the degrees, so both have the same number of defined coefficients.
+    coefficients
+        .iter()
+        .zip(random_coefficients.iter())
+        .map(|(coefficient, random_coefficient)| {
+            if *random_coefficient == 0.0 {
+                None
+            } else {
+                Some(coefficient / random_coefficient)
+            }
+        })
+        .collect()
+}
+
+/// Computes the s-metric of an undirected graph: Sum of deg(u) * deg(v) over all edges {u, v}.
+///
+/// Graphs with the same degree sequence have larger s-metric when vertices with high degree are connected to each other,
+/// so like [assortativity](crate::gen::rewire::assortativity) it measures how vertices with similar degrees are mixed.
+/// It's computed on the simple graph underlying `graph`, so loops are ignored and parallel edges are counted once.
+///
+/// # Arguments
+/// `graph`: Graph to compute its s-metric.
+///
+/// # Returns
+/// The s-metric of the graph.
+///
+/// # Complexity
+/// O(|V| + |E|)
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::List;
+/// use prepona::graph::ListGraph;
+/// use prepona::analysis::s_metric;
+///
+/// // Given: Path a --- b --- c --- d
+/// let mut graph = ListGraph::init(List::<usize>::init());
+/// let vertices: Vec<usize> = (0..4).map(|_| graph.add_vertex()).collect();
+/// for i in 1..4 {
+///     graph.add_edge_unchecked(vertices[i - 1], vertices[i], 1.into());
+/// }
+///
+/// // When:
+/// let s = s_metric(&graph);
+///
+/// // Then: 1 * 2 + 2 * 2 + 2 * 1
+/// assert_eq!(s, 8);
+/// ```
+pub fn s_metric<W, E, G>(graph: &G) -> usize
+where
+    E: Edge<W>,
+    G: Vertices + Edges<W, E> + Graph<W, E, UndirectedEdge>,
+{
+    let (degrees, edges) = simple_edges_of(graph);
+
+    edges
+        .iter()
+        .map(|(src, dst)| degrees[*src] * degrees[*dst])
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::MultiListGraph;
+
+    fn complete_graph(vertex_count: usize) -> ListGraph<usize, UndirectedEdge> {
+        let mut graph = ListGraph::init(List::<usize>::init());
+        let vertices: Vec<usize> = (0..vertex_count).map(|_| graph.add_vertex()).collect();
+        for i in 0..vertex_count {
+            for j in i + 1..vertex_count {
+                graph.add_edge_unchecked(vertices[i], vertices[j], 1.into());
+            }
+        }
+
+        graph
+    }
+
+    #[test]
+    fn empty_graph() {
+        // Given:
+        let graph = ListGraph::init(List::<usize>::init());
+
+        // Then:
+        assert!(rich_club_coefficient(&graph).is_empty());
+        assert!(normalized_rich_club_coefficient(&graph, 10, &mut Rng::seed_from(0)).is_empty());
+        assert_eq!(s_metric(&graph), 0);
+    }
+
+    #[test]
+    fn complete_graph_is_one_club() {
+        // Given:
+        let graph = complete_graph(5);
+
+        // When:
+        let coefficients = rich_club_coefficient(&graph);
+
+        // Then: Every vertex has degree 4.
+        assert_eq!(coefficients, vec![1.0; 4]);
+        assert_eq!(s_metric(&graph), 10 * 16);
+    }
+
+    #[test]
+    fn loops_and_parallel_edges_are_ignored() {
+        // Given: Path a --- b --- c with a loop on b and a parallel edge between a and b.
+        let mut graph = MultiListGraph::init(List::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+
+        // Then: Only b has degree more than 1.
+        assert_eq!(rich_club_coefficient(&graph), vec![2.0 / 3.0]);
+        assert_eq!(s_metric(&graph), 4);
+    }
+
+    #[test]
+    fn normalized_coefficients_of_connected_hubs() {
+        // Given: Clique of 5 hubs, each one connected to 4 vertices of a cycle of length 20.
+        let mut graph = complete_graph(5);
+        let cycle: Vec<usize> = (0..20).map(|_| graph.add_vertex()).collect();
+        for i in 0..20 {
+            graph.add_edge_unchecked(cycle[i], cycle[(i + 1) % 20], 1.into());
+            graph.add_edge_unchecked(i / 4, cycle[i], 1.into());
+        }
+
+        // When:
+        let coefficients = rich_club_coefficient(&graph);
+        let normalized = normalized_rich_club_coefficient(&graph, 20, &mut Rng::seed_from(3));
+
+        // Then:
+        assert_eq!(normalized.len(), coefficients.len());
+        // Coefficients up to degree 2 only depend on the number of vertices and edges.
+        assert!((normalized[0].unwrap() - 1.0).abs() < 1e-9);
+        // Hubs with degree 8 are more connected to each other than in a random graph with the same degrees.
+        assert_eq!(coefficients[3], 1.0);
+        assert!(normalized[3].unwrap() > 1.0);
+    }
+
+    #[test]
+    fn normalized_coefficients_are_reproducible() {
+        // Given: Two copies of a graph with hubs and a long cycle, with edges added in different orders.
+        let mut graph = complete_graph(6);
+        let mut reversed = complete_graph(6);
+        let mut edges = vec![];
+        for i in 0..30 {
+            edges.push((6 + i, 6 + (i + 1) % 30));
+            edges.push((i % 6, 6 + i));
+        }
+        for _ in 0..30 {
+            graph.add_vertex();
+            reversed.add_vertex();
+        }
+        for (src_id, dst_id) in &edges {
+            graph.add_edge_unchecked(*src_id, *dst_id, 1.into());
+        }
+        for (src_id, dst_id) in edges.iter().rev() {
+            reversed.add_edge_unchecked(*src_id, *dst_id, 1.into());
+        }
+
+        // When: Normalizing with the same seed several times.
+        let normalized: Vec<Vec<Option<f64>>> = (0..5)
+            .map(|_| normalized_rich_club_coefficient(&graph, 10, &mut Rng::seed_from(8)))
+            .chain(std::iter::once(normalized_rich_club_coefficient(
+                &reversed,
+                10,
+                &mut Rng::seed_from(8),
+            )))
+            .collect();
+
+        // Then:
+        assert!(normalized
+            .iter()
+            .all(|coefficients| *coefficients == normalized[0]));
+    }
+}
@@ -0,0 +1,5 @@
+mod degree_mixing;
+mod summary;
+
+pub use degree_mixing::{normalized_rich_club_coefficient, rich_club_coefficient, s_metric};
+pub use summary::{summary, GraphSummary};
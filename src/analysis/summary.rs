@@ -0,0 +1,316 @@
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
+
+use crate::graph::{Edge, EdgeDir};
+use crate::provide::{Edges, Graph, Vertices};
+
+/// Overview of the structure of a graph, computed by [`summary`](crate::analysis::summary).
+///
+/// Degree of a vertex in a directed graph is the sum of its in and out degree. Connectivity is computed by ignoring direction of edges(weak connectivity).
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphSummary {
+    /// Whether edges of the graph are directed.
+    pub is_directed: bool,
+
+    /// Number of vertices.
+    pub vertex_count: usize,
+
+    /// Number of edges.
+    pub edge_count: usize,
+
+    /// Number of edges divided by the number of possible edges between distinct vertices. Zero for graphs with less than two vertices.
+    pub density: f64,
+
+    /// Minimum degree. Zero for the empty graph.
+    pub min_degree: usize,
+
+    /// Maximum degree. Zero for the empty graph.
+    pub max_degree: usize,
+
+    /// Average degree. Zero for the empty graph.
+    pub average_degree: f64,
+
+    /// `degree_histogram[d]` is the number of vertices with degree `d`.
+    pub degree_histogram: Vec<usize>,
+
+    /// Number of vertices without any edge.
+    pub isolated_count: usize,
+
+    /// Number of edges from a vertex to itself.
+    pub loop_count: usize,
+
+    /// Number of connected components.
+    pub component_count: usize,
+
+    /// Number of vertices in the largest connected component.
+    pub largest_component_size: usize,
+
+    /// Whether vertices can be split into two sets so that no edge has both of its end points in the same set.
+    pub is_bipartite: bool,
+}
+
+impl fmt::Display for GraphSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = if self.is_directed {
+            "directed"
+        } else {
+            "undirected"
+        };
+
+        writeln!(
+            f,
+            "{} graph: {} vertices, {} edges, density {:.4}",
+            kind, self.vertex_count, self.edge_count, self.density
+        )?;
+        writeln!(
+            f,
+            "degree: min {}, max {}, average {:.2}",
+            self.min_degree, self.max_degree, self.average_degree
+        )?;
+        writeln!(
+            f,
+            "isolated vertices: {}, loops: {}",
+            self.isolated_count, self.loop_count
+        )?;
+        write!(
+            f,
+            "components: {} (largest {}), bipartite: {}",
+            self.component_count, self.largest_component_size, self.is_bipartite
+        )
+    }
+}
+
+/// Computes a [`GraphSummary`](crate::analysis::GraphSummary) of the graph.
+///
+/// Edges are iterated once to compute degrees and build an undirected view of the graph,
+/// which is then traversed once to find the components and check bipartiteness. Loops are counted in a separate pass over the edges.
+///
+/// # Arguments
+/// `graph`: Graph to summarize.
+///
+/// # Returns
+/// Summary of the graph.
+///
+/// # Complexity
+/// O(|V| + |E|)
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+/// use prepona::analysis;
+///
+/// // Given: Graph
+/// //
+/// //      a  --  b  --  c      d
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+///
+/// // When:
+/// let summary = analysis::summary(&graph);
+///
+/// // Then:
+/// assert_eq!(summary.vertex_count, 4);
+/// assert_eq!(summary.edge_count, 2);
+/// assert_eq!(summary.degree_histogram, vec![1, 2, 1]);
+/// assert_eq!(summary.component_count, 2);
+/// assert_eq!(summary.largest_component_size, 3);
+/// assert!(summary.is_bipartite);
+/// ```
+pub fn summary<W, E, Dir, G>(graph: &G) -> GraphSummary
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Vertices + Edges<W, E> + Graph<W, E, Dir>,
+{
+    let is_directed = Dir::is_directed();
+    let id_map = graph.continuos_id_map();
+    let vertex_count = graph.vertex_count();
+    let edge_count = graph.edges_count();
+
+    let mut degrees = vec![0; vertex_count];
+    let mut adjacency = vec![vec![]; vertex_count];
+
+    // Undirected edges are returned in both directions, so each one is seen once from each end point.
+    for (src_id, dst_id, _) in graph.as_directed_edges() {
+        let (src, dst) = (id_map.virt_id_of(src_id), id_map.virt_id_of(dst_id));
+
+        degrees[src] += 1;
+        adjacency[src].push(dst);
+
+        if is_directed {
+            degrees[dst] += 1;
+            adjacency[dst].push(src);
+        }
+    }
+
+    // Storages differ in how many times they return an undirected loop, so loops are counted by their ids.
+    let loop_count = graph
+        .edges()
+        .into_iter()
+        .filter(|(src_id, dst_id, _)| src_id == dst_id)
+        .map(|(_, _, edge)| edge.get_id())
+        .collect::<HashSet<usize>>()
+        .len();
+
+    let mut degree_histogram = vec![0; degrees.iter().max().map_or(0, |max| max + 1)];
+    for degree in &degrees {
+        degree_histogram[*degree] += 1;
+    }
+
+    let (component_count, largest_component_size, is_bipartite) = components(&adjacency);
+
+    let possible_edges = if vertex_count < 2 {
+        0
+    } else if is_directed {
+        vertex_count * (vertex_count - 1)
+    } else {
+        vertex_count * (vertex_count - 1) / 2
+    };
+
+    GraphSummary {
+        is_directed,
+        vertex_count,
+        edge_count,
+        density: if possible_edges == 0 {
+            0.0
+        } else {
+            edge_count as f64 / possible_edges as f64
+        },
+        min_degree: degrees.iter().copied().min().unwrap_or(0),
+        max_degree: degrees.iter().copied().max().unwrap_or(0),
+        average_degree: if vertex_count == 0 {
+            0.0
+        } else {
+            degrees.iter().sum::<usize>() as f64 / vertex_count as f64
+        },
+        degree_histogram,
+        isolated_count: degrees.iter().filter(|degree| **degree == 0).count(),
+        loop_count,
+        component_count,
+        largest_component_size,
+        is_bipartite,
+    }
+}
+
+// Returns (number of components, size of the largest component, whether graph is bipartite) using a breadth-first two coloring.
+fn components(adjacency: &[Vec<usize>]) -> (usize, usize, bool) {
+    let mut sides: Vec<Option<bool>> = vec![None; adjacency.len()];
+    let mut component_count = 0;
+    let mut largest_component_size = 0;
+    let mut is_bipartite = true;
+
+    for root in 0..adjacency.len() {
+        if sides[root].is_some() {
+            continue;
+        }
+
+        component_count += 1;
+        sides[root] = Some(false);
+        let mut size = 0;
+        let mut queue = VecDeque::from(vec![root]);
+
+        while let Some(v) = queue.pop_front() {
+            size += 1;
+            let side = sides[v].unwrap();
+
+            for n in &adjacency[v] {
+                match sides[*n] {
+                    None => {
+                        sides[*n] = Some(!side);
+                        queue.push_back(*n);
+                    }
+                    Some(n_side) if n_side == side => is_bipartite = false,
+                    _ => {}
+                }
+            }
+        }
+
+        largest_component_size = largest_component_size.max(size);
+    }
+
+    (component_count, largest_component_size, is_bipartite)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{MatGraph, MultiListGraph};
+    use crate::storage::{DiMat, List, Mat};
+
+    #[test]
+    fn empty_graph() {
+        // Given:
+        let graph = MatGraph::init(Mat::<usize>::init());
+
+        // When:
+        let summary = summary(&graph);
+
+        // Then:
+        assert_eq!(summary.vertex_count, 0);
+        assert_eq!(summary.density, 0.0);
+        assert!(summary.degree_histogram.is_empty());
+        assert_eq!(summary.component_count, 0);
+        assert!(summary.is_bipartite);
+    }
+
+    #[test]
+    fn directed_triangle() {
+        // Given: Graph
+        //
+        //      a  -->  b  -->  c
+        //      ^               |
+        //      |_______________|
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(c, a, 1.into());
+
+        // When:
+        let summary = summary(&graph);
+
+        // Then:
+        assert!(summary.is_directed);
+        assert_eq!(summary.edge_count, 3);
+        assert_eq!(summary.density, 0.5);
+        assert_eq!(summary.min_degree, 2);
+        assert_eq!(summary.max_degree, 2);
+        assert_eq!(summary.average_degree, 2.0);
+        assert_eq!(summary.component_count, 1);
+        assert!(!summary.is_bipartite);
+        assert_eq!(
+            summary.to_string().lines().next(),
+            Some("directed graph: 3 vertices, 3 edges, density 0.5000")
+        );
+    }
+
+    #[test]
+    fn loops_in_multigraph() {
+        // Given: Graph with a loop on a and two parallel edges between a and b.
+        let mut graph = MultiListGraph::init(List::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        graph.add_edge_unchecked(a, a, 1.into());
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(a, b, 1.into());
+
+        // When:
+        let summary = summary(&graph);
+
+        // Then:
+        assert_eq!(summary.loop_count, 1);
+        assert!(!summary.is_bipartite);
+        assert_eq!(summary.isolated_count, 0);
+    }
+}
@@ -0,0 +1,151 @@
+use magnitude::Magnitude;
+use num_traits::One;
+use std::any::Any;
+use std::collections::HashSet;
+
+use crate::graph::{Edge, UndirectedEdge};
+use crate::misc::rng::Rng;
+use crate::storage::GraphStorage;
+
+/// Adds a random undirected graph with the given degree sequence to the storage, using the [configuration model](https://en.wikipedia.org/wiki/Configuration_model).
+///
+/// Each vertex gets as many stubs(half edges) as its degree and stubs are paired uniformly at random.
+/// The result is a multigraph that may contain loops and parallel edges, so use a storage that allows them, like the one of [`MultiGraph`](crate::graph::MultiGraph).
+/// A loop contributes two to the degree of its vertex.
+///
+/// When `simplify` is `true`, loops and parallel edges are dropped instead of being added. The graph is then simple,
+/// but degrees of some vertices may be lower than requested(for sparse sequences only a few edges are dropped).
+/// For a random simple graph with exact degrees, build one with [`from_degree_sequence`](crate::gen::degree_sequence::from_degree_sequence) and randomize it using [`double_edge_swap`](crate::gen::rewire::double_edge_swap).
+///
+/// All edges get weight one.
+///
+/// # Arguments
+/// * `storage`: Storage to add the graph to.
+/// * `sequence`: Degree of each vertex.
+/// * `simplify`: Whether to drop loops and parallel edges.
+/// * `rng`: Source of randomness.
+///
+/// # Returns
+/// * `Some`: Containing id of the vertices, where vertex `ids[i]` is generated for `sequence[i]`.
+/// * `None`: If sum of the degrees is odd. Storage is left untouched in this case.
+///
+/// # Complexity
+/// O(|V| + |E|)
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::List;
+/// use prepona::graph::MultiListGraph;
+/// use prepona::gen::configuration_model::configuration_model;
+/// use prepona::misc::rng::Rng;
+///
+/// let mut storage = List::<usize>::init();
+/// let ids = configuration_model(&mut storage, &[3, 2, 2, 1], false, &mut Rng::seed_from(1)).unwrap();
+/// let graph = MultiListGraph::init(storage);
+///
+/// assert_eq!(graph.vertex_count(), 4);
+/// ```
+pub fn configuration_model<W, E, S>(
+    storage: &mut S,
+    sequence: &[usize],
+    simplify: bool,
+    rng: &mut Rng,
+) -> Option<Vec<usize>>
+where
+    W: Copy + One + Any,
+    E: Edge<W>,
+    S: GraphStorage<W, E, UndirectedEdge>,
+{
+    if sequence.iter().sum::<usize>() % 2 != 0 {
+        return None;
+    }
+
+    let mut stubs: Vec<usize> = sequence
+        .iter()
+        .enumerate()
+        .flat_map(|(index, degree)| std::iter::repeat_n(index, *degree))
+        .collect();
+    rng.shuffle(&mut stubs);
+
+    let ids: Vec<usize> = sequence.iter().map(|_| storage.add_vertex()).collect();
+    let mut added = HashSet::new();
+
+    for pair in stubs.chunks(2) {
+        let (i, j) = (pair[0].min(pair[1]), pair[0].max(pair[1]));
+
+        if simplify && (i == j || !added.insert((i, j))) {
+            continue;
+        }
+
+        let weight: Magnitude<W> = W::one().into();
+        storage.add_edge_unchecked(ids[i], ids[j], E::init(weight));
+    }
+
+    Some(ids)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::List;
+
+    #[test]
+    fn odd_sum() {
+        // Given:
+        let mut storage = List::<usize>::init();
+
+        // When:
+        let ids = configuration_model(&mut storage, &[2, 1, 1, 1], false, &mut Rng::seed_from(0));
+
+        // Then:
+        assert!(ids.is_none());
+        assert_eq!(storage.vertex_count(), 0);
+    }
+
+    #[test]
+    fn multigraph_has_exact_degrees() {
+        // Given: Dense sequence which makes loops and parallel edges likely.
+        let sequence = vec![6, 5, 4, 3, 2, 2, 1, 1];
+
+        // When:
+        let mut storage = List::<usize>::init();
+        let ids =
+            configuration_model(&mut storage, &sequence, false, &mut Rng::seed_from(3)).unwrap();
+
+        // Then: Each stub is used exactly once.
+        let edge_ids: HashSet<usize> = storage
+            .edges()
+            .into_iter()
+            .map(|(_, _, edge)| edge.get_id())
+            .collect();
+        assert_eq!(edge_ids.len(), sequence.iter().sum::<usize>() / 2);
+        for (index, vertex_id) in ids.iter().enumerate() {
+            // List storage returns an undirected loop once from each of its end points.
+            let degree = storage.edges_from_unchecked(*vertex_id).len();
+
+            assert_eq!(degree, sequence[index]);
+        }
+    }
+
+    #[test]
+    fn simplified_graph_is_simple() {
+        // Given:
+        let sequence = vec![6, 5, 4, 3, 2, 2, 1, 1];
+
+        // When:
+        let mut storage = List::<usize>::init();
+        let ids =
+            configuration_model(&mut storage, &sequence, true, &mut Rng::seed_from(3)).unwrap();
+
+        // Then: No loops, no parallel edges and degrees never exceed the requested ones.
+        for (index, vertex_id) in ids.iter().enumerate() {
+            let neighbors = storage.neighbors_unchecked(*vertex_id);
+            let distinct: HashSet<usize> = neighbors.iter().copied().collect();
+
+            assert!(!distinct.contains(vertex_id));
+            assert_eq!(distinct.len(), neighbors.len());
+            assert!(neighbors.len() <= sequence[index]);
+        }
+    }
+}
@@ -0,0 +1,230 @@
+use magnitude::Magnitude;
+use num_traits::One;
+use std::any::Any;
+
+use crate::graph::{Edge, UndirectedEdge};
+use crate::provide::{Edges, Graph, Vertices};
+use crate::storage::GraphStorage;
+
+/// # Arguments
+/// `graph`: Graph to compute the degree sequence of.
+///
+/// # Returns
+/// Degree of each vertex, sorted in non-increasing order.
+pub fn degree_sequence<W, E, G>(graph: &G) -> Vec<usize>
+where
+    E: Edge<W>,
+    G: Vertices + Edges<W, E> + Graph<W, E, UndirectedEdge>,
+{
+    let mut sequence: Vec<usize> = graph
+        .vertices()
+        .into_iter()
+        .map(|vertex_id| graph.edges_from_unchecked(vertex_id).len())
+        .collect();
+
+    sequence.sort_unstable_by(|d1, d2| d2.cmp(d1));
+
+    sequence
+}
+
+/// Checks whether a sequence is the degree sequence of a simple graph using [Erdős–Gallai](https://en.wikipedia.org/wiki/Erd%C5%91s%E2%80%93Gallai_theorem) theorem.
+///
+/// A non-increasing sequence d<sub>1</sub>, ..., d<sub>n</sub> is graphical if and only if its sum is even and for every k:
+///
+/// d<sub>1</sub> + ... + d<sub>k</sub> <= k(k - 1) + min(d<sub>k+1</sub>, k) + ... + min(d<sub>n</sub>, k)
+///
+/// # Arguments
+/// `sequence`: Degrees in any order.
+///
+/// # Returns
+/// `true` if there is a simple graph(without loops and parallel edges) with the given degrees.
+///
+/// # Complexity
+/// O(n log(n))
+///
+/// # Examples
+/// ```
+/// use prepona::gen::degree_sequence::is_graphical;
+///
+/// assert!(is_graphical(&[3, 3, 2, 2, 2]));
+/// // Sum is odd.
+/// assert!(!is_graphical(&[3, 2, 2]));
+/// // Two vertices can not have degree 3 in a graph with 4 vertices if one of the other vertices has degree 0.
+/// assert!(!is_graphical(&[3, 3, 2, 0]));
+/// ```
+pub fn is_graphical(sequence: &[usize]) -> bool {
+    let mut degrees = sequence.to_vec();
+    degrees.sort_unstable_by(|d1, d2| d2.cmp(d1));
+
+    let n = degrees.len();
+    if degrees.iter().sum::<usize>() % 2 != 0 || degrees.first().is_some_and(|max| *max >= n) {
+        return false;
+    }
+
+    // suffix_sums[i] = degrees[i] + ... + degrees[n - 1]
+    let mut suffix_sums = vec![0; n + 1];
+    for i in (0..n).rev() {
+        suffix_sums[i] = suffix_sums[i + 1] + degrees[i];
+    }
+
+    let mut prefix_sum = 0;
+    for k in 1..=n {
+        prefix_sum += degrees[k - 1];
+
+        // Degrees are non-increasing, so degrees that are at least k form a prefix.
+        let at_least_k = degrees.partition_point(|d| *d >= k).max(k);
+        let rest = k * (at_least_k - k) + suffix_sums[at_least_k];
+
+        if prefix_sum > k * (k - 1) + rest {
+            return false;
+        }
+    }
+
+    true
+}
+
+/// Adds a simple undirected graph with the given degrees to the storage, using [Havel–Hakimi](https://en.wikipedia.org/wiki/Havel%E2%80%93Hakimi_algorithm) algorithm.
+///
+/// Repeatedly, the vertex with the largest remaining degree d is connected to the d vertices with the next largest remaining degrees.
+/// Edges are computed before anything is added to the storage, so storage is left untouched if the sequence is not graphical.
+/// All edges get weight one.
+///
+/// # Arguments
+/// * `storage`: Storage to add the graph to.
+/// * `sequence`: Degree of each vertex.
+///
+/// # Returns
+/// * `Some`: Containing id of the vertices, where vertex `ids[i]` has degree `sequence[i]`.
+/// * `None`: If the sequence is not graphical.
+///
+/// # Complexity
+/// O(n<sup>2</sup> log(n))
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::List;
+/// use prepona::graph::ListGraph;
+/// use prepona::gen::degree_sequence::{degree_sequence, from_degree_sequence};
+///
+/// let mut storage = List::<usize>::init();
+/// let ids = from_degree_sequence(&mut storage, &[1, 3, 2, 2, 2]).unwrap();
+/// let graph = ListGraph::init(storage);
+///
+/// assert_eq!(graph.edges_from_unchecked(ids[1]).len(), 3);
+/// assert_eq!(degree_sequence(&graph), vec![3, 2, 2, 2, 1]);
+/// ```
+pub fn from_degree_sequence<W, E, S>(storage: &mut S, sequence: &[usize]) -> Option<Vec<usize>>
+where
+    W: Copy + One + Any,
+    E: Edge<W>,
+    S: GraphStorage<W, E, UndirectedEdge>,
+{
+    let edges = havel_hakimi(sequence)?;
+
+    let ids: Vec<usize> = sequence.iter().map(|_| storage.add_vertex()).collect();
+    for (i, j) in edges {
+        let weight: Magnitude<W> = W::one().into();
+        storage.add_edge_unchecked(ids[i], ids[j], E::init(weight));
+    }
+
+    Some(ids)
+}
+
+// Returns edges between indices of the sequence, or `None` if the sequence is not graphical.
+fn havel_hakimi(sequence: &[usize]) -> Option<Vec<(usize, usize)>> {
+    let mut remaining: Vec<(usize, usize)> = sequence.iter().copied().zip(0..).collect();
+    let mut edges = vec![];
+
+    loop {
+        remaining.retain(|(degree, _)| *degree > 0);
+        remaining.sort_unstable_by(|(d1, i1), (d2, i2)| d2.cmp(d1).then(i1.cmp(i2)));
+
+        let (degree, index) = match remaining.first() {
+            Some(first) => *first,
+            None => return Some(edges),
+        };
+
+        if degree >= remaining.len() {
+            return None;
+        }
+
+        remaining[0].0 = 0;
+        for (n_degree, n_index) in remaining[1..=degree].iter_mut() {
+            *n_degree -= 1;
+            edges.push((index, *n_index));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::ListGraph;
+    use crate::misc::rng::Rng;
+    use crate::provide::Neighbors;
+    use crate::storage::List;
+
+    #[test]
+    fn empty_and_zero_sequences() {
+        assert!(is_graphical(&[]));
+        assert!(is_graphical(&[0, 0, 0]));
+        assert_eq!(havel_hakimi(&[0, 0]), Some(vec![]));
+        assert!(!is_graphical(&[1]));
+        assert!(!is_graphical(&[2, 2]));
+    }
+
+    #[test]
+    fn erdos_gallai_agrees_with_havel_hakimi() {
+        // Given: Random sequences of small degrees.
+        let mut rng = Rng::seed_from(17);
+
+        for _ in 0..2000 {
+            let length = rng.next_below(9);
+            let sequence: Vec<usize> = (0..length).map(|_| rng.next_below(length.max(1))).collect();
+
+            // Then: Both tests agree on whether the sequence is graphical.
+            assert_eq!(
+                is_graphical(&sequence),
+                havel_hakimi(&sequence).is_some(),
+                "{:?}",
+                sequence
+            );
+        }
+    }
+
+    #[test]
+    fn generated_graph_has_the_sequence() {
+        // Given: Degree sequence of a 3-regular graph with 10 vertices.
+        let sequence = vec![3; 10];
+
+        // When:
+        let mut storage = List::<usize>::init();
+        let ids = from_degree_sequence(&mut storage, &sequence).unwrap();
+        let graph = ListGraph::init(storage);
+
+        // Then: Graph is simple and has the requested degrees.
+        assert_eq!(ids.len(), 10);
+        assert_eq!(degree_sequence(&graph), sequence);
+        for src_id in graph.vertices() {
+            let mut neighbors = graph.neighbors_unchecked(src_id);
+            assert!(!neighbors.contains(&src_id));
+            neighbors.sort_unstable();
+            neighbors.dedup();
+            assert_eq!(neighbors.len(), 3);
+        }
+    }
+
+    #[test]
+    fn storage_is_untouched_for_non_graphical_sequence() {
+        // Given:
+        let mut storage = List::<usize>::init();
+
+        // When:
+        let ids = from_degree_sequence(&mut storage, &[4, 2, 1, 1, 0, 0]);
+
+        // Then:
+        assert!(ids.is_none());
+        assert_eq!(storage.vertex_count(), 0);
+    }
+}
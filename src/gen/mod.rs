@@ -0,0 +1,14 @@
+/// Random graphs with a prescribed degree sequence.
+pub mod configuration_model;
+
+/// Degree sequences: computing them, checking whether a sequence is graphical and building graphs with a prescribed sequence.
+pub mod degree_sequence;
+
+/// Sampling smaller graphs out of a large one, to benchmark algorithms on scaled-down inputs.
+pub mod sample;
+
+/// Rewiring the edges of a graph while preserving its degree sequence.
+pub mod rewire;
+
+/// Random graphs of specific classes: trees, forests, connected graphs and directed acyclic graphs.
+pub mod random;
@@ -0,0 +1,400 @@
+use magnitude::Magnitude;
+use num_traits::One;
+use std::any::Any;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet};
+
+use crate::graph::{DirectedEdge, Edge, EdgeDir, UndirectedEdge};
+use crate::misc::rng::Rng;
+use crate::storage::GraphStorage;
+
+/// Adds a uniformly random labeled tree to the storage.
+///
+/// The tree is decoded from a random [Prüfer sequence](https://en.wikipedia.org/wiki/Pr%C3%BCfer_sequence), so each of the n^(n-2) trees over the vertices is equally likely.
+///
+/// All edges get weight one.
+///
+/// # Arguments
+/// * `storage`: Storage to add the tree to.
+/// * `vertex_count`: Number of vertices of the tree.
+/// * `rng`: Source of randomness.
+///
+/// # Returns
+/// Id of the added vertices.
+///
+/// # Complexity
+/// O(|V|.log(|V|))
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::List;
+/// use prepona::graph::ListGraph;
+/// use prepona::gen::random::random_tree;
+/// use prepona::misc::rng::Rng;
+///
+/// let mut storage = List::<usize>::init();
+/// let ids = random_tree(&mut storage, 10, &mut Rng::seed_from(1));
+/// let graph = ListGraph::init(storage);
+///
+/// assert_eq!(ids.len(), 10);
+/// assert_eq!(graph.edges_count(), 9);
+/// ```
+pub fn random_tree<W, E, S>(storage: &mut S, vertex_count: usize, rng: &mut Rng) -> Vec<usize>
+where
+    W: Copy + One + Any,
+    E: Edge<W>,
+    S: GraphStorage<W, E, UndirectedEdge>,
+{
+    let ids: Vec<usize> = (0..vertex_count).map(|_| storage.add_vertex()).collect();
+
+    for (i, j) in tree_edges(vertex_count, rng) {
+        add_edge(storage, ids[i], ids[j]);
+    }
+
+    ids
+}
+
+/// Adds a random forest with exactly `tree_count` trees to the storage.
+///
+/// The forest is made by removing `tree_count - 1` random edges from a uniformly random tree.
+///
+/// All edges get weight one.
+///
+/// # Arguments
+/// * `storage`: Storage to add the forest to.
+/// * `vertex_count`: Number of vertices of the forest.
+/// * `tree_count`: Number of trees(connected components) of the forest.
+/// * `rng`: Source of randomness.
+///
+/// # Returns
+/// * `Some`: Containing id of the added vertices.
+/// * `None`: If `tree_count` is zero while there are vertices, or if it's greater than `vertex_count`. Storage is left untouched in this case.
+///
+/// # Complexity
+/// O(|V|.log(|V|))
+pub fn random_forest<W, E, S>(
+    storage: &mut S,
+    vertex_count: usize,
+    tree_count: usize,
+    rng: &mut Rng,
+) -> Option<Vec<usize>>
+where
+    W: Copy + One + Any,
+    E: Edge<W>,
+    S: GraphStorage<W, E, UndirectedEdge>,
+{
+    if tree_count > vertex_count || (tree_count == 0 && vertex_count > 0) {
+        return None;
+    }
+
+    let mut edges = tree_edges(vertex_count, rng);
+    rng.shuffle(&mut edges);
+    edges.truncate(vertex_count.saturating_sub(tree_count));
+
+    let ids: Vec<usize> = (0..vertex_count).map(|_| storage.add_vertex()).collect();
+
+    for (i, j) in edges {
+        add_edge(storage, ids[i], ids[j]);
+    }
+
+    Some(ids)
+}
+
+/// Adds a random connected simple graph to the storage.
+///
+/// The graph is a uniformly random tree plus `extra_edge_count` distinct edges chosen uniformly among the missing ones.
+/// If there are not enough missing edges, the complete graph is added.
+///
+/// All edges get weight one.
+///
+/// # Arguments
+/// * `storage`: Storage to add the graph to.
+/// * `vertex_count`: Number of vertices of the graph.
+/// * `extra_edge_count`: Number of edges to add on top of the spanning tree.
+/// * `rng`: Source of randomness.
+///
+/// # Returns
+/// Id of the added vertices.
+///
+/// # Complexity
+/// O(|V|.log(|V|) + |E|) expected, as long as the graph is not close to complete.
+pub fn random_connected<W, E, S>(
+    storage: &mut S,
+    vertex_count: usize,
+    extra_edge_count: usize,
+    rng: &mut Rng,
+) -> Vec<usize>
+where
+    W: Copy + One + Any,
+    E: Edge<W>,
+    S: GraphStorage<W, E, UndirectedEdge>,
+{
+    let mut edges: HashSet<(usize, usize)> = tree_edges(vertex_count, rng)
+        .into_iter()
+        .map(|(i, j)| (i.min(j), i.max(j)))
+        .collect();
+
+    let max_edge_count = vertex_count * vertex_count.saturating_sub(1) / 2;
+    let target = (edges.len() + extra_edge_count).min(max_edge_count);
+
+    if target * 2 > max_edge_count {
+        // Dense: pick the missing edges from a shuffled list of all pairs instead of rejection sampling.
+        let mut missing: Vec<(usize, usize)> = (0..vertex_count)
+            .flat_map(|i| (i + 1..vertex_count).map(move |j| (i, j)))
+            .filter(|pair| !edges.contains(pair))
+            .collect();
+        rng.shuffle(&mut missing);
+        missing.truncate(target - edges.len());
+        edges.extend(missing);
+    } else {
+        while edges.len() < target {
+            let i = rng.next_below(vertex_count);
+            let j = rng.next_below(vertex_count);
+
+            if i != j {
+                edges.insert((i.min(j), i.max(j)));
+            }
+        }
+    }
+
+    let mut edges: Vec<(usize, usize)> = edges.into_iter().collect();
+    edges.sort_unstable();
+
+    let ids: Vec<usize> = (0..vertex_count).map(|_| storage.add_vertex()).collect();
+
+    for (i, j) in edges {
+        add_edge(storage, ids[i], ids[j]);
+    }
+
+    ids
+}
+
+/// Adds a random directed acyclic graph to the storage.
+///
+/// Vertices are put in a random order and each pair of vertices is connected with probability `p`, from the earlier vertex to the later one.
+/// So the returned order is a topological order of the graph.
+///
+/// All edges get weight one.
+///
+/// # Arguments
+/// * `storage`: Storage to add the graph to.
+/// * `vertex_count`: Number of vertices of the graph.
+/// * `p`: Probability of each edge being present.
+/// * `rng`: Source of randomness.
+///
+/// # Returns
+/// Id of the added vertices in a topological order.
+///
+/// # Complexity
+/// O(|V|^2)
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::DiList;
+/// use prepona::graph::ListGraph;
+/// use prepona::gen::random::random_dag;
+/// use prepona::misc::rng::Rng;
+///
+/// let mut storage = DiList::<usize>::init();
+/// let order = random_dag(&mut storage, 10, 0.3, &mut Rng::seed_from(1));
+/// let graph = ListGraph::init(storage);
+///
+/// // Every edge goes forward in the returned order.
+/// let position = |vertex_id| order.iter().position(|id| *id == vertex_id).unwrap();
+/// assert!(graph
+///     .edges()
+///     .into_iter()
+///     .all(|(src_id, dst_id, _)| position(src_id) < position(dst_id)));
+/// ```
+pub fn random_dag<W, E, S>(
+    storage: &mut S,
+    vertex_count: usize,
+    p: f64,
+    rng: &mut Rng,
+) -> Vec<usize>
+where
+    W: Copy + One + Any,
+    E: Edge<W>,
+    S: GraphStorage<W, E, DirectedEdge>,
+{
+    let mut order: Vec<usize> = (0..vertex_count).map(|_| storage.add_vertex()).collect();
+    rng.shuffle(&mut order);
+
+    for i in 0..vertex_count {
+        for j in i + 1..vertex_count {
+            if rng.next_bool(p) {
+                add_edge(storage, order[i], order[j]);
+            }
+        }
+    }
+
+    order
+}
+
+fn add_edge<W, E, Dir, S>(storage: &mut S, src_id: usize, dst_id: usize)
+where
+    W: Copy + One + Any,
+    E: Edge<W>,
+    Dir: EdgeDir,
+    S: GraphStorage<W, E, Dir>,
+{
+    let weight: Magnitude<W> = W::one().into();
+    storage.add_edge_unchecked(src_id, dst_id, E::init(weight));
+}
+
+// Edges of a uniformly random tree over 0..vertex_count, decoded from a random prüfer sequence.
+fn tree_edges(vertex_count: usize, rng: &mut Rng) -> Vec<(usize, usize)> {
+    if vertex_count < 2 {
+        return vec![];
+    }
+
+    let prufer: Vec<usize> = (0..vertex_count - 2)
+        .map(|_| rng.next_below(vertex_count))
+        .collect();
+
+    let mut degree = vec![1; vertex_count];
+    for vertex in &prufer {
+        degree[*vertex] += 1;
+    }
+
+    // Leaves are kept in a min heap so the smallest leaf is always attached first.
+    let mut leaves: BinaryHeap<Reverse<usize>> = (0..vertex_count)
+        .filter(|vertex| degree[*vertex] == 1)
+        .map(Reverse)
+        .collect();
+
+    let mut edges = Vec::with_capacity(vertex_count - 1);
+    for vertex in prufer {
+        let Reverse(leaf) = leaves.pop().unwrap();
+        edges.push((leaf, vertex));
+
+        degree[vertex] -= 1;
+        if degree[vertex] == 1 {
+            leaves.push(Reverse(vertex));
+        }
+    }
+
+    let Reverse(u) = leaves.pop().unwrap();
+    let Reverse(v) = leaves.pop().unwrap();
+    edges.push((u, v));
+
+    edges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::{ConnectedComponents, HasCycle, TopologicalSort};
+    use crate::graph::{ListGraph, MatGraph};
+    use crate::provide::{Edges, Vertices};
+    use crate::storage::{DiList, List, Mat};
+
+    #[test]
+    fn trees_are_connected_and_acyclic() {
+        for seed in 0..20 {
+            // Given:
+            let mut storage = List::<usize>::init();
+
+            // When:
+            random_tree(&mut storage, 15, &mut Rng::seed_from(seed));
+            let graph = ListGraph::init(storage);
+
+            // Then:
+            assert_eq!(graph.vertex_count(), 15);
+            assert_eq!(graph.edges_count(), 14);
+            assert_eq!(ConnectedComponents::init(&graph).execute(&graph).len(), 1);
+        }
+    }
+
+    #[test]
+    fn every_tree_over_three_vertices_is_generated() {
+        // Given: There are 3 labeled trees over 3 vertices, identified by their center.
+        let mut centers = HashSet::new();
+
+        // When:
+        for seed in 0..30 {
+            let edges = tree_edges(3, &mut Rng::seed_from(seed));
+            let center = (0..3)
+                .find(|vertex| edges.iter().all(|(i, j)| i == vertex || j == vertex))
+                .unwrap();
+            centers.insert(center);
+        }
+
+        // Then:
+        assert_eq!(centers.len(), 3);
+    }
+
+    #[test]
+    fn forests_have_the_requested_tree_count() {
+        for tree_count in 1..=6 {
+            // Given:
+            let mut storage = List::<usize>::init();
+
+            // When:
+            random_forest(
+                &mut storage,
+                12,
+                tree_count,
+                &mut Rng::seed_from(tree_count as u64),
+            )
+            .unwrap();
+            let graph = ListGraph::init(storage);
+
+            // Then:
+            assert_eq!(graph.edges_count(), 12 - tree_count);
+            assert_eq!(
+                ConnectedComponents::init(&graph).execute(&graph).len(),
+                tree_count
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_tree_counts() {
+        // Given:
+        let mut storage = List::<usize>::init();
+
+        // When:
+        let too_many = random_forest(&mut storage, 3, 4, &mut Rng::seed_from(0));
+        let none = random_forest(&mut storage, 3, 0, &mut Rng::seed_from(0));
+
+        // Then:
+        assert!(too_many.is_none());
+        assert!(none.is_none());
+        assert_eq!(storage.vertex_count(), 0);
+    }
+
+    #[test]
+    fn connected_graphs() {
+        for (extra_edge_count, expected) in [(0, 9), (10, 19), (100, 45)].iter() {
+            // Given:
+            let mut storage = Mat::<usize>::init();
+
+            // When:
+            random_connected(&mut storage, 10, *extra_edge_count, &mut Rng::seed_from(3));
+            let graph = MatGraph::init(storage);
+
+            // Then:
+            assert_eq!(graph.edges_count(), *expected);
+            assert_eq!(ConnectedComponents::init(&graph).execute(&graph).len(), 1);
+        }
+    }
+
+    #[test]
+    fn dags_are_acyclic() {
+        for seed in 0..20 {
+            // Given:
+            let mut storage = DiList::<usize>::init();
+
+            // When:
+            random_dag(&mut storage, 12, 0.4, &mut Rng::seed_from(seed));
+            let graph = ListGraph::init(storage);
+
+            // Then:
+            assert!(HasCycle::init(&graph).execute(&graph).is_none());
+            assert_eq!(TopologicalSort::init().execute(&graph).len(), 12);
+        }
+    }
+}
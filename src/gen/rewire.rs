@@ -0,0 +1,496 @@
+use std::collections::HashMap;
+
+use crate::graph::{Edge, UndirectedEdge};
+use crate::misc::rng::Rng;
+use crate::provide::{Edges, Graph};
+
+// Sums over the edges that are needed to compute the assortativity coefficient.
+// Each sum is over the degrees of the two end points of each edge.
+struct Sums {
+    edge_count: f64,
+    // Σ j * k
+    product: f64,
+    // Σ (j + k) / 2
+    half_sum: f64,
+    // Σ (j² + k²) / 2
+    half_square_sum: f64,
+}
+
+impl Sums {
+    fn of(degrees: &HashMap<usize, usize>, edges: &[(usize, usize)]) -> Self {
+        let mut sums = Sums {
+            edge_count: edges.len() as f64,
+            product: 0.0,
+            half_sum: 0.0,
+            half_square_sum: 0.0,
+        };
+
+        for (src_id, dst_id) in edges {
+            let (j, k) = (degrees[src_id] as f64, degrees[dst_id] as f64);
+
+            sums.product += j * k;
+            sums.half_sum += (j + k) / 2.0;
+            sums.half_square_sum += (j * j + k * k) / 2.0;
+        }
+
+        sums
+    }
+
+    // Assortativity coefficient if Σ j * k was `product`.
+    fn coefficient_with(&self, product: f64) -> Option<f64> {
+        if self.edge_count == 0.0 {
+            return None;
+        }
+
+        let mean = self.half_sum / self.edge_count;
+        let variance = self.half_square_sum / self.edge_count - mean * mean;
+
+        if variance.abs() < f64::EPSILON {
+            None
+        } else {
+            Some((product / self.edge_count - mean * mean) / variance)
+        }
+    }
+}
+
+// Swap of two edges {a, b} and {c, d} into {a, d} and {c, b}.
+struct Swap {
+    i: usize,
+    j: usize,
+    a: usize,
+    b: usize,
+    c: usize,
+    d: usize,
+}
+
+impl Swap {
+    // Picks two random edges and one of their two possible swaps.
+    // Returns `None` if the swap would create a loop or a parallel edge.
+    fn pick<W, E, G>(graph: &G, edges: &[(usize, usize, usize)], rng: &mut Rng) -> Option<Swap>
+    where
+        E: Edge<W>,
+        G: Edges<W, E> + Graph<W, E, UndirectedEdge>,
+    {
+        let i = rng.next_below(edges.len());
+        let j = rng.next_below(edges.len());
+        if i == j {
+            return None;
+        }
+
+        let (a, b, _) = edges[i];
+        let (c, d, _) = edges[j];
+        let (c, d) = if rng.next_bool(0.5) { (c, d) } else { (d, c) };
+
+        if a == d
+            || c == b
+            || graph.has_any_edge_unchecked(a, d)
+            || graph.has_any_edge_unchecked(c, b)
+        {
+            None
+        } else {
+            Some(Swap { i, j, a, b, c, d })
+        }
+    }
+
+    // Edges keep their weights but get new ids.
+    fn apply<W, E, G>(&self, graph: &mut G, edges: &mut [(usize, usize, usize)])
+    where
+        E: Edge<W>,
+        G: Edges<W, E> + Graph<W, E, UndirectedEdge>,
+    {
+        let ab_edge = graph.remove_edge_unchecked(self.a, self.b, edges[self.i].2);
+        let cd_edge = graph.remove_edge_unchecked(self.c, self.d, edges[self.j].2);
+
+        let ad_id = graph.add_edge_unchecked(self.a, self.d, ab_edge);
+        let cb_id = graph.add_edge_unchecked(self.c, self.b, cd_edge);
+
+        edges[self.i] = (self.a, self.d, ad_id);
+        edges[self.j] = (self.c, self.b, cb_id);
+    }
+}
+
+// (src_id, dst_id, edge_id) of each edge.
+type EdgeTriplets = Vec<(usize, usize, usize)>;
+
+fn degrees_of<W, E, G>(graph: &G) -> (HashMap<usize, usize>, EdgeTriplets)
+where
+    E: Edge<W>,
+    G: Edges<W, E>,
+{
+    let mut degrees = HashMap::new();
+    let mut edges = vec![];
+
+    for (src_id, dst_id, edge) in graph.edges() {
+        *degrees.entry(src_id).or_insert(0) += 1;
+        *degrees.entry(dst_id).or_insert(0) += 1;
+
+        edges.push((src_id, dst_id, edge.get_id()));
+    }
+
+    (degrees, edges)
+}
+
+/// Computes the degree [assortativity](https://en.wikipedia.org/wiki/Assortativity) coefficient of an undirected graph.
+///
+/// # Arguments
+/// `graph`: Graph to compute its assortativity.
+///
+/// # Returns
+/// * `Some`: Containing the coefficient which is in range [-1, 1].
+/// * `None`: If the coefficient is not defined. This happens when the graph has no edges or all edges connect vertices with the same degrees(like in regular graphs).
+pub fn assortativity<W, E, G>(graph: &G) -> Option<f64>
+where
+    E: Edge<W>,
+    G: Edges<W, E> + Graph<W, E, UndirectedEdge>,
+{
+    let (degrees, edges) = degrees_of(graph);
+    let edges: Vec<(usize, usize)> = edges
+        .into_iter()
+        .map(|(src_id, dst_id, _)| (src_id, dst_id))
+        .collect();
+
+    let sums = Sums::of(&degrees, &edges);
+    sums.coefficient_with(sums.product)
+}
+
+/// Rewires the graph to move its degree assortativity toward `target_r` while preserving the degree of every vertex.
+///
+/// In each iteration two edges {a, b} and {c, d} are picked at random and swapped into {a, d} and {c, b}.
+/// Swaps that create a loop or a parallel edge are rejected.
+/// Other swaps are only accepted if they move the assortativity closer to `target_r`.
+///
+/// Edges keep their weights when rewired but their ids change.
+///
+/// # Arguments
+/// * `graph`: Graph to rewire.
+/// * `target_r`: Target assortativity coefficient in range [-1, 1].
+/// * `rng`: Source of randomness.
+/// * `max_iters`: Number of swaps to try.
+///
+/// # Returns
+/// * `Some`: Containing the assortativity of the rewired graph.
+/// * `None`: If assortativity of the graph is not defined. In this case graph is left untouched.
+///
+/// # Complexity
+/// O(|E| + `max_iters`) plus the cost of checking the existence of, adding and removing edges in each accepted swap.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::List;
+/// use prepona::graph::ListGraph;
+/// use prepona::gen::rewire;
+/// use prepona::misc::rng::Rng;
+///
+/// // Given: Two stars joined at their leaves.
+/// let mut graph = ListGraph::init(List::<usize>::init());
+/// let centers = [graph.add_vertex(), graph.add_vertex()];
+/// let leaves: Vec<usize> = (0..6).map(|_| graph.add_vertex()).collect();
+/// for (index, leaf) in leaves.iter().enumerate() {
+///     graph.add_edge_unchecked(centers[index % 2], *leaf, 1.into());
+/// }
+/// graph.add_edge_unchecked(leaves[0], leaves[1], 1.into());
+/// graph.add_edge_unchecked(leaves[2], leaves[3], 1.into());
+///
+/// let initial_r = rewire::assortativity(&graph).unwrap();
+///
+/// // When: Rewiring toward a fully disassortative graph.
+/// let r = rewire::target_assortativity(&mut graph, -1.0, &mut Rng::seed_from(1), 100).unwrap();
+///
+/// // Then:
+/// assert!(r <= initial_r);
+/// assert_eq!(graph.edges_count(), 8);
+/// ```
+pub fn target_assortativity<W, E, G>(
+    graph: &mut G,
+    target_r: f64,
+    rng: &mut Rng,
+    max_iters: usize,
+) -> Option<f64>
+where
+    E: Edge<W>,
+    G: Edges<W, E> + Graph<W, E, UndirectedEdge>,
+{
+    let (degrees, edges) = degrees_of(graph);
+
+    let end_points: Vec<(usize, usize)> = edges
+        .iter()
+        .map(|(src_id, dst_id, _)| (*src_id, *dst_id))
+        .collect();
+    let mut sums = Sums::of(&degrees, &end_points);
+    let mut r = sums.coefficient_with(sums.product)?;
+
+    // Loops never take part in a swap.
+    let mut edges: Vec<(usize, usize, usize)> = edges
+        .into_iter()
+        .filter(|(src_id, dst_id, _)| src_id != dst_id)
+        .collect();
+
+    if edges.len() < 2 {
+        return Some(r);
+    }
+
+    for _ in 0..max_iters {
+        let swap = match Swap::pick(graph, &edges, rng) {
+            Some(swap) => swap,
+            None => continue,
+        };
+        let (a, b, c, d) = (swap.a, swap.b, swap.c, swap.d);
+
+        let product = sums.product - (degrees[&a] * degrees[&b] + degrees[&c] * degrees[&d]) as f64
+            + (degrees[&a] * degrees[&d] + degrees[&c] * degrees[&b]) as f64;
+
+        // Degrees are preserved, so only Σ j * k changes and the coefficient stays defined.
+        let new_r = sums.coefficient_with(product).unwrap();
+
+        if (new_r - target_r).abs() < (r - target_r).abs() {
+            swap.apply(graph, &mut edges);
+
+            sums.product = product;
+            r = new_r;
+        }
+    }
+
+    Some(r)
+}
+
+/// Randomizes the graph by performing `n_swaps` random double edge swaps, preserving the degree of every vertex.
+///
+/// In each try two edges {a, b} and {c, d} are picked at random and swapped into {a, d} and {c, b}.
+/// Swaps that create a loop or a parallel edge are rejected, so the graph stays simple. Graphs randomized this way are
+/// commonly used as null models, since they keep the degree sequence but lose any other structure.
+///
+/// Edges keep their weights when rewired but their ids change. Loops never take part in a swap.
+///
+/// # Arguments
+/// * `graph`: Graph to randomize.
+/// * `n_swaps`: Number of swaps to perform.
+/// * `rng`: Source of randomness.
+///
+/// # Returns
+/// Number of performed swaps. It's less than `n_swaps` if swaps could not be found after `100 * n_swaps` tries,
+/// which happens for graphs with less than two edges or graphs whose degree sequence has few realizations(like complete graphs).
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::List;
+/// use prepona::graph::ListGraph;
+/// use prepona::gen::rewire;
+/// use prepona::gen::degree_sequence::degree_sequence;
+/// use prepona::misc::rng::Rng;
+///
+/// // Given: Cycle of length 8.
+/// let mut graph = ListGraph::init(List::<usize>::init());
+/// let vertices: Vec<usize> = (0..8).map(|_| graph.add_vertex()).collect();
+/// for i in 0..8 {
+///     graph.add_edge_unchecked(vertices[i], vertices[(i + 1) % 8], 1.into());
+/// }
+///
+/// // When:
+/// let swaps = rewire::double_edge_swap(&mut graph, 20, &mut Rng::seed_from(4));
+///
+/// // Then:
+/// assert_eq!(swaps, 20);
+/// assert_eq!(degree_sequence(&graph), vec![2; 8]);
+/// ```
+pub fn double_edge_swap<W, E, G>(graph: &mut G, n_swaps: usize, rng: &mut Rng) -> usize
+where
+    E: Edge<W>,
+    G: Edges<W, E> + Graph<W, E, UndirectedEdge>,
+{
+    let (_, edges) = degrees_of(graph);
+    let mut edges: Vec<(usize, usize, usize)> = edges
+        .into_iter()
+        .filter(|(src_id, dst_id, _)| src_id != dst_id)
+        .collect();
+
+    if edges.len() < 2 {
+        return 0;
+    }
+
+    let mut swaps = 0;
+    let mut tries = 0;
+    while swaps < n_swaps && tries < 100 * n_swaps {
+        tries += 1;
+
+        if let Some(swap) = Swap::pick(graph, &edges, rng) {
+            swap.apply(graph, &mut edges);
+            swaps += 1;
+        }
+    }
+
+    swaps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::MatGraph;
+    use crate::provide::Vertices;
+    use crate::storage::Mat;
+
+    // Two triangles and two stars with their centers connected to one of the triangles.
+    fn mixed_graph() -> MatGraph<usize, UndirectedEdge> {
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let vertices: Vec<usize> = (0..14).map(|_| graph.add_vertex()).collect();
+
+        for (src, dst) in [
+            (0, 1),
+            (1, 2),
+            (2, 0),
+            (3, 4),
+            (4, 5),
+            (5, 3),
+            (6, 8),
+            (6, 9),
+            (6, 10),
+            (7, 11),
+            (7, 12),
+            (7, 13),
+            (6, 0),
+            (7, 3),
+        ]
+        .iter()
+        {
+            graph.add_edge_unchecked(vertices[*src], vertices[*dst], 1.into());
+        }
+
+        graph
+    }
+
+    fn degree_sequence(graph: &MatGraph<usize, UndirectedEdge>) -> Vec<usize> {
+        graph
+            .vertices()
+            .into_iter()
+            .map(|vertex_id| graph.edges_from_unchecked(vertex_id).len())
+            .collect()
+    }
+
+    #[test]
+    fn assortativity_of_star() {
+        // Given: Star with 4 leaves.
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let center = graph.add_vertex();
+        for _ in 0..4 {
+            let leaf = graph.add_vertex();
+            graph.add_edge_unchecked(center, leaf, 1.into());
+        }
+
+        // When: Computing assortativity.
+        let r = assortativity(&graph).unwrap();
+
+        // Then:
+        assert!((r + 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn assortativity_of_regular_graph() {
+        // Given: Triangle.
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(c, a, 1.into());
+
+        // When: Rewiring the graph.
+        let r = target_assortativity(&mut graph, 1.0, &mut Rng::seed_from(0), 10);
+
+        // Then: Assortativity is not defined.
+        assert!(assortativity(&graph).is_none());
+        assert!(r.is_none());
+    }
+
+    #[test]
+    fn rewire_toward_both_ends() {
+        for target_r in [-1.0, 1.0].iter() {
+            // Given: Graph with mixed degrees.
+            let mut graph = mixed_graph();
+            let initial_r = assortativity(&graph).unwrap();
+            let initial_degrees = degree_sequence(&graph);
+
+            // When: Rewiring toward the target.
+            let r =
+                target_assortativity(&mut graph, *target_r, &mut Rng::seed_from(5), 500).unwrap();
+
+            // Then: Assortativity moved toward the target and degrees are preserved.
+            assert!((r - target_r).abs() < (initial_r - target_r).abs());
+            assert!((assortativity(&graph).unwrap() - r).abs() < 1e-9);
+            assert_eq!(degree_sequence(&graph), initial_degrees);
+            assert_eq!(graph.edges_count(), 14);
+            assert!(graph
+                .edges()
+                .iter()
+                .all(|(src_id, dst_id, _)| src_id != dst_id
+                    && graph.edges_between_unchecked(*src_id, *dst_id).len() == 1));
+        }
+    }
+
+    #[test]
+    fn rewire_is_reproducible() {
+        // Given: Two copies of the same graph.
+        let mut graph1 = mixed_graph();
+        let mut graph2 = mixed_graph();
+
+        // When: Rewiring both with the same seed.
+        let r1 = target_assortativity(&mut graph1, -1.0, &mut Rng::seed_from(9), 200);
+        let r2 = target_assortativity(&mut graph2, -1.0, &mut Rng::seed_from(9), 200);
+
+        // Then:
+        assert_eq!(r1, r2);
+        let mut edges1: Vec<(usize, usize)> = graph1
+            .edges()
+            .into_iter()
+            .map(|(s, d, _)| (s.min(d), s.max(d)))
+            .collect();
+        let mut edges2: Vec<(usize, usize)> = graph2
+            .edges()
+            .into_iter()
+            .map(|(s, d, _)| (s.min(d), s.max(d)))
+            .collect();
+        edges1.sort_unstable();
+        edges2.sort_unstable();
+        assert_eq!(edges1, edges2);
+    }
+
+    #[test]
+    fn double_edge_swap_preserves_degrees() {
+        // Given: Graph with mixed degrees.
+        let mut graph = mixed_graph();
+        let initial_degrees = degree_sequence(&graph);
+
+        // When:
+        let swaps = double_edge_swap(&mut graph, 50, &mut Rng::seed_from(2));
+
+        // Then: Degrees are preserved and graph stays simple.
+        assert_eq!(swaps, 50);
+        assert_eq!(degree_sequence(&graph), initial_degrees);
+        assert_eq!(graph.edges_count(), 14);
+        assert!(graph
+            .edges()
+            .iter()
+            .all(|(src_id, dst_id, _)| src_id != dst_id
+                && graph.edges_between_unchecked(*src_id, *dst_id).len() == 1));
+    }
+
+    #[test]
+    fn double_edge_swap_on_complete_graph() {
+        // Given: Complete graph with 4 vertices, which is the only realization of its degree sequence.
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let vertices: Vec<usize> = (0..4).map(|_| graph.add_vertex()).collect();
+        for i in 0..4 {
+            for j in i + 1..4 {
+                graph.add_edge_unchecked(vertices[i], vertices[j], 1.into());
+            }
+        }
+
+        // When:
+        let swaps = double_edge_swap(&mut graph, 10, &mut Rng::seed_from(2));
+
+        // Then: No swap is possible.
+        assert_eq!(swaps, 0);
+        assert_eq!(graph.edges_count(), 6);
+    }
+}
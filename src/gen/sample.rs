@@ -0,0 +1,558 @@
+use std::collections::{HashSet, VecDeque};
+
+use crate::graph::{Edge, EdgeDir};
+use crate::misc::rng::Rng;
+use crate::provide::{Edges, Graph, IdMap, Neighbors, Vertices};
+use crate::storage::GraphStorage;
+
+/// Copies the subgraph induced by `count` vertices of `graph`, chosen uniformly at random, into `storage`.
+///
+/// # Arguments
+/// * `graph`: Graph to sample from.
+/// * `storage`: Storage to copy the sample into.
+/// * `count`: Number of vertices to sample. If it's more than the number of vertices, every vertex is sampled.
+/// * `rng`: Source of randomness.
+///
+/// # Returns
+/// Mapping between id of the sampled vertices in `graph`(real ids) and their id in `storage`(virtual ids).
+///
+/// # Complexity
+/// O(|V| + |E|) plus the cost of adding the sample to `storage`.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::List;
+/// use prepona::graph::ListGraph;
+/// use prepona::gen::sample;
+/// use prepona::misc::rng::Rng;
+///
+/// // Given: Complete graph with 5 vertices.
+/// let mut graph = ListGraph::init(List::<usize>::init());
+/// let vertices: Vec<_> = (0..5).map(|_| graph.add_vertex()).collect();
+/// for (index, src_id) in vertices.iter().enumerate() {
+///     for dst_id in vertices[index + 1..].iter() {
+///         graph.add_edge_unchecked(*src_id, *dst_id, 1.into());
+///     }
+/// }
+///
+/// // When:
+/// let mut storage = List::<usize>::init();
+/// let id_map = sample::uniform_vertices(&graph, &mut storage, 3, &mut Rng::seed_from(1));
+///
+/// // Then: Every edge between the sampled vertices is kept.
+/// assert_eq!(id_map.len(), 3);
+/// assert_eq!(storage.vertex_count(), 3);
+/// assert_eq!(storage.edge_count(), 3);
+/// ```
+pub fn uniform_vertices<W, E, Dir, G, S>(
+    graph: &G,
+    storage: &mut S,
+    count: usize,
+    rng: &mut Rng,
+) -> IdMap
+where
+    E: Edge<W> + Copy,
+    Dir: EdgeDir,
+    G: Vertices + Edges<W, E> + Graph<W, E, Dir>,
+    S: GraphStorage<W, E, Dir>,
+{
+    let mut vertices = graph.vertices();
+    rng.shuffle(&mut vertices);
+    vertices.truncate(count);
+
+    copy_induced(graph, storage, vertices)
+}
+
+/// Copies `count` edges of `graph`, chosen uniformly at random, together with their end points into `storage`.
+///
+/// Unlike the other samplers, the sample is not induced: Edges between the sampled vertices that are not chosen themselves are left out.
+///
+/// # Arguments
+/// * `graph`: Graph to sample from.
+/// * `storage`: Storage to copy the sample into.
+/// * `count`: Number of edges to sample. If it's more than the number of edges, every edge is sampled.
+/// * `rng`: Source of randomness.
+///
+/// # Returns
+/// Mapping between id of the end points of the sampled edges in `graph`(real ids) and their id in `storage`(virtual ids).
+///
+/// # Complexity
+/// O(|V| + |E|) plus the cost of adding the sample to `storage`.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::DiList;
+/// use prepona::graph::ListGraph;
+/// use prepona::gen::sample;
+/// use prepona::misc::rng::Rng;
+///
+/// // Given: Graph
+/// //
+/// //      a  -->  b  -->  c
+/// //
+/// let mut graph = ListGraph::init(DiList::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 2.into());
+///
+/// // When:
+/// let mut storage = DiList::<usize>::init();
+/// let id_map = sample::uniform_edges(&graph, &mut storage, 1, &mut Rng::seed_from(3));
+///
+/// // Then: Only the end points of the sampled edge are copied.
+/// assert_eq!(storage.vertex_count(), 2);
+/// assert_eq!(storage.edge_count(), 1);
+/// assert!(id_map.len() == 2);
+/// ```
+pub fn uniform_edges<W, E, Dir, G, S>(
+    graph: &G,
+    storage: &mut S,
+    count: usize,
+    rng: &mut Rng,
+) -> IdMap
+where
+    E: Edge<W> + Copy,
+    Dir: EdgeDir,
+    G: Vertices + Edges<W, E> + Graph<W, E, Dir>,
+    S: GraphStorage<W, E, Dir>,
+{
+    let mut edges = distinct_edges(graph);
+    rng.shuffle(&mut edges);
+    edges.truncate(count);
+
+    let mut vertices = vec![];
+    let mut seen = HashSet::new();
+    for (src_id, dst_id, _) in edges.iter() {
+        for vertex_id in [*src_id, *dst_id].iter() {
+            if seen.insert(*vertex_id) {
+                vertices.push(*vertex_id);
+            }
+        }
+    }
+
+    let id_map = add_vertices(storage, &vertices);
+    for (src_id, dst_id, edge) in edges {
+        storage.add_edge_unchecked(id_map.virt_id_of(src_id), id_map.virt_id_of(dst_id), *edge);
+    }
+
+    id_map
+}
+
+/// Copies the subgraph induced by the first `count` distinct vertices that a random walk visits into `storage`.
+///
+/// The walk starts from a vertex chosen uniformly at random and at each step, goes back to it with probability `restart_probability`
+/// or moves to a random neighbor otherwise. If the walk reaches a dead end, or visits no new vertex in |V| steps,
+/// it's stuck in a small region and starts over from a new random vertex.
+/// So unlike [`uniform_vertices`](crate::gen::sample::uniform_vertices), the sample keeps the local structure of the graph.
+///
+/// # Arguments
+/// * `graph`: Graph to sample from.
+/// * `storage`: Storage to copy the sample into.
+/// * `count`: Number of vertices to sample. If it's more than the number of vertices, every vertex is sampled.
+/// * `restart_probability`: Probability of going back to the start vertex at each step. 0.15 is a common choice.
+/// * `rng`: Source of randomness.
+///
+/// # Returns
+/// Mapping between id of the sampled vertices in `graph`(real ids) and their id in `storage`(virtual ids).
+///
+/// # Complexity
+/// O(|V| + |E|) plus the cost of the walk and adding the sample to `storage`.
+///
+/// # Panics
+/// If `restart_probability` is not in range [0, 1].
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::List;
+/// use prepona::graph::ListGraph;
+/// use prepona::gen::sample;
+/// use prepona::misc::rng::Rng;
+///
+/// // Given: Path with 10 vertices.
+/// let mut graph = ListGraph::init(List::<usize>::init());
+/// let vertices: Vec<_> = (0..10).map(|_| graph.add_vertex()).collect();
+/// for pair in vertices.windows(2) {
+///     graph.add_edge_unchecked(pair[0], pair[1], 1.into());
+/// }
+///
+/// // When:
+/// let mut storage = List::<usize>::init();
+/// sample::random_walk(&graph, &mut storage, 4, 0.15, &mut Rng::seed_from(7));
+///
+/// // Then: Sampled vertices are consecutive vertices of the path.
+/// assert_eq!(storage.vertex_count(), 4);
+/// assert_eq!(storage.edge_count(), 3);
+/// ```
+pub fn random_walk<W, E, Dir, G, S>(
+    graph: &G,
+    storage: &mut S,
+    count: usize,
+    restart_probability: f64,
+    rng: &mut Rng,
+) -> IdMap
+where
+    E: Edge<W> + Copy,
+    Dir: EdgeDir,
+    G: Vertices + Neighbors + Edges<W, E> + Graph<W, E, Dir>,
+    S: GraphStorage<W, E, Dir>,
+{
+    assert!(
+        (0.0..=1.0).contains(&restart_probability),
+        "Restart probability must be in range [0, 1]"
+    );
+
+    let all_vertices = graph.vertices();
+    let count = count.min(all_vertices.len());
+
+    let mut visited = HashSet::new();
+    let mut vertices = vec![];
+    while vertices.len() < count {
+        let start_id = all_vertices[rng.next_below(all_vertices.len())];
+        let mut current_id = start_id;
+        let mut idle_steps = 0;
+        loop {
+            if visited.insert(current_id) {
+                vertices.push(current_id);
+                idle_steps = 0;
+            } else {
+                idle_steps += 1;
+            }
+
+            if vertices.len() == count || idle_steps > all_vertices.len() {
+                break;
+            }
+
+            let neighbors = graph.neighbors_unchecked(current_id);
+            if neighbors.is_empty() {
+                break;
+            }
+
+            current_id = if rng.next_bool(restart_probability) {
+                start_id
+            } else {
+                neighbors[rng.next_below(neighbors.len())]
+            };
+        }
+    }
+
+    copy_induced(graph, storage, vertices)
+}
+
+/// Copies the subgraph induced by `count` vertices burned by forest fire model of Leskovec and Faloutsos into `storage`.
+///
+/// Fire starts from a vertex chosen uniformly at random. Each burning vertex sets fire to a geometrically distributed number
+/// of its unburned neighbors with mean `forward_probability / (1 - forward_probability)`, chosen at random, and so on.
+/// When the fire dies out, it starts again from a new unburned vertex.
+/// The sample tends to keep the degree distribution and densification of the original graph.
+///
+/// For directed graphs fire only spreads along direction of the edges.
+///
+/// # Arguments
+/// * `graph`: Graph to sample from.
+/// * `storage`: Storage to copy the sample into.
+/// * `count`: Number of vertices to sample. If it's more than the number of vertices, every vertex is sampled.
+/// * `forward_probability`: Controls how far the fire spreads. 0.7 is a common choice.
+/// * `rng`: Source of randomness.
+///
+/// # Returns
+/// Mapping between id of the sampled vertices in `graph`(real ids) and their id in `storage`(virtual ids).
+///
+/// # Complexity
+/// O(|V| + |E|) plus the cost of adding the sample to `storage`.
+///
+/// # Panics
+/// If `forward_probability` is not in range [0, 1).
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::List;
+/// use prepona::graph::ListGraph;
+/// use prepona::gen::sample;
+/// use prepona::misc::rng::Rng;
+///
+/// // Given: Star with 6 leaves.
+/// let mut graph = ListGraph::init(List::<usize>::init());
+/// let center = graph.add_vertex();
+/// for _ in 0..6 {
+///     let leaf = graph.add_vertex();
+///     graph.add_edge_unchecked(center, leaf, 1.into());
+/// }
+///
+/// // When:
+/// let mut storage = List::<usize>::init();
+/// let id_map = sample::forest_fire(&graph, &mut storage, 4, 0.7, &mut Rng::seed_from(2));
+///
+/// // Then:
+/// assert_eq!(id_map.len(), 4);
+/// assert_eq!(storage.vertex_count(), 4);
+/// ```
+pub fn forest_fire<W, E, Dir, G, S>(
+    graph: &G,
+    storage: &mut S,
+    count: usize,
+    forward_probability: f64,
+    rng: &mut Rng,
+) -> IdMap
+where
+    E: Edge<W> + Copy,
+    Dir: EdgeDir,
+    G: Vertices + Neighbors + Edges<W, E> + Graph<W, E, Dir>,
+    S: GraphStorage<W, E, Dir>,
+{
+    assert!(
+        (0.0..1.0).contains(&forward_probability),
+        "Forward probability must be in range [0, 1)"
+    );
+
+    // Fire restarts from unburned vertices in a random order.
+    let mut seeds = graph.vertices();
+    rng.shuffle(&mut seeds);
+    let count = count.min(seeds.len());
+
+    let mut burned = HashSet::new();
+    let mut vertices = vec![];
+    for seed_id in seeds {
+        if vertices.len() == count {
+            break;
+        }
+        if !burned.insert(seed_id) {
+            continue;
+        }
+        vertices.push(seed_id);
+
+        let mut queue = VecDeque::new();
+        queue.push_back(seed_id);
+        while let Some(vertex_id) = queue.pop_front() {
+            let mut unburned: Vec<usize> = graph
+                .neighbors_unchecked(vertex_id)
+                .into_iter()
+                .filter(|neighbor_id| !burned.contains(neighbor_id))
+                .collect::<HashSet<usize>>()
+                .into_iter()
+                .collect();
+            // Neighbors are collected into a set to remove parallel edges, so they're sorted to keep the result deterministic.
+            unburned.sort_unstable();
+            rng.shuffle(&mut unburned);
+
+            let mut spread = 0;
+            while rng.next_bool(forward_probability) {
+                spread += 1;
+            }
+
+            for neighbor_id in unburned.into_iter().take(spread) {
+                if vertices.len() == count {
+                    break;
+                }
+
+                burned.insert(neighbor_id);
+                vertices.push(neighbor_id);
+                queue.push_back(neighbor_id);
+            }
+        }
+    }
+
+    copy_induced(graph, storage, vertices)
+}
+
+// Adds a vertex to the storage for each of the given vertices, in the same order.
+fn add_vertices<W, E, Dir, S>(storage: &mut S, vertices: &[usize]) -> IdMap
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    S: GraphStorage<W, E, Dir>,
+{
+    let mut id_map = IdMap::init(vertices.len());
+    for real_id in vertices.iter().copied() {
+        let virt_id = storage.add_vertex();
+        id_map.put_real_to_virt(real_id, virt_id);
+        id_map.put_virt_to_real(virt_id, real_id);
+    }
+
+    id_map
+}
+
+// Edges of the graph, each reported once. Some storages report an undirected loop twice.
+fn distinct_edges<W, E, G>(graph: &G) -> Vec<(usize, usize, &E)>
+where
+    E: Edge<W>,
+    G: Edges<W, E>,
+{
+    let mut seen = HashSet::new();
+    graph
+        .edges()
+        .into_iter()
+        .filter(|(_, _, edge)| seen.insert(edge.get_id()))
+        .collect()
+}
+
+// Copies the given vertices, in the same order, and every edge between them into the storage.
+fn copy_induced<W, E, Dir, G, S>(graph: &G, storage: &mut S, vertices: Vec<usize>) -> IdMap
+where
+    E: Edge<W> + Copy,
+    Dir: EdgeDir,
+    G: Edges<W, E>,
+    S: GraphStorage<W, E, Dir>,
+{
+    let id_map = add_vertices(storage, &vertices);
+    let sampled: HashSet<usize> = vertices.into_iter().collect();
+
+    for (src_id, dst_id, edge) in distinct_edges(graph) {
+        if sampled.contains(&src_id) && sampled.contains(&dst_id) {
+            storage.add_edge_unchecked(id_map.virt_id_of(src_id), id_map.virt_id_of(dst_id), *edge);
+        }
+    }
+
+    id_map
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{ListGraph, UndirectedEdge};
+    use crate::storage::{DiList, List};
+
+    // Grid with `size` rows and columns where each vertex is connected to the one on its right and the one below it.
+    fn grid(size: usize) -> ListGraph<usize, UndirectedEdge> {
+        let mut graph = ListGraph::init(List::<usize>::init());
+        let vertices: Vec<_> = (0..size * size).map(|_| graph.add_vertex()).collect();
+        for row in 0..size {
+            for col in 0..size {
+                let vertex_id = vertices[row * size + col];
+                if col + 1 < size {
+                    graph.add_edge_unchecked(vertex_id, vertices[row * size + col + 1], 1.into());
+                }
+                if row + 1 < size {
+                    graph.add_edge_unchecked(vertex_id, vertices[(row + 1) * size + col], 1.into());
+                }
+            }
+        }
+
+        graph
+    }
+
+    // Checks that sample is the subgraph of `graph` induced by the vertices in `id_map`.
+    fn assert_induced(
+        graph: &ListGraph<usize, UndirectedEdge>,
+        sample: &List<usize>,
+        id_map: &IdMap,
+    ) {
+        for src_id in sample.vertices() {
+            for dst_id in sample.vertices() {
+                assert_eq!(
+                    sample.has_any_edge_unchecked(src_id, dst_id),
+                    graph.has_any_edge_unchecked(
+                        id_map.real_id_of(src_id),
+                        id_map.real_id_of(dst_id)
+                    )
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn samplers_are_induced_and_deterministic() {
+        // Given:
+        let graph = grid(6);
+
+        for count in [0, 1, 10, 36, 100].iter() {
+            // When:
+            let samples: Vec<(List<usize>, IdMap)> = (0..2)
+                .flat_map(|_| {
+                    let mut storages = vec![List::init(), List::init(), List::init()];
+                    let id_maps = vec![
+                        uniform_vertices(&graph, &mut storages[0], *count, &mut Rng::seed_from(5)),
+                        random_walk(
+                            &graph,
+                            &mut storages[1],
+                            *count,
+                            0.15,
+                            &mut Rng::seed_from(5),
+                        ),
+                        forest_fire(
+                            &graph,
+                            &mut storages[2],
+                            *count,
+                            0.7,
+                            &mut Rng::seed_from(5),
+                        ),
+                    ];
+                    storages.into_iter().zip(id_maps)
+                })
+                .collect();
+
+            // Then:
+            for (sample, id_map) in samples.iter() {
+                assert_eq!(sample.vertex_count(), (*count).min(36));
+                assert_eq!(id_map.len(), (*count).min(36));
+                assert_induced(&graph, sample, id_map);
+            }
+            for (first, second) in samples[..3].iter().zip(samples[3..].iter()) {
+                let real_ids = |(sample, id_map): &(List<usize>, IdMap)| -> Vec<usize> {
+                    sample
+                        .vertices()
+                        .into_iter()
+                        .map(|id| id_map.real_id_of(id))
+                        .collect()
+                };
+                assert_eq!(real_ids(first), real_ids(second));
+            }
+        }
+    }
+
+    #[test]
+    fn local_samplers_stay_connected() {
+        // Given: Grid is connected, so a walk and a fire that never die out only sample connected vertices.
+        let graph = grid(8);
+
+        // When:
+        let mut walk_sample = List::<usize>::init();
+        let mut fire_sample = List::<usize>::init();
+        random_walk(&graph, &mut walk_sample, 20, 0.15, &mut Rng::seed_from(11));
+        forest_fire(&graph, &mut fire_sample, 20, 0.99, &mut Rng::seed_from(11));
+
+        // Then:
+        for sample in [&walk_sample, &fire_sample].iter() {
+            let mut reached = HashSet::new();
+            let mut stack = vec![sample.vertices()[0]];
+            while let Some(vertex_id) = stack.pop() {
+                if reached.insert(vertex_id) {
+                    stack.extend(sample.neighbors_unchecked(vertex_id));
+                }
+            }
+            assert_eq!(reached.len(), 20);
+        }
+    }
+
+    #[test]
+    fn uniform_edges_keeps_weights_and_direction() {
+        // Given: Directed cycle with distinct weights and a loop.
+        let mut graph = ListGraph::init(DiList::<usize>::init());
+        let vertices: Vec<_> = (0..5).map(|_| graph.add_vertex()).collect();
+        for index in 0..5 {
+            graph.add_edge_unchecked(vertices[index], vertices[(index + 1) % 5], index.into());
+        }
+        graph.add_edge_unchecked(vertices[0], vertices[0], 9.into());
+
+        // When:
+        let mut storage = DiList::<usize>::init();
+        let id_map = uniform_edges(&graph, &mut storage, 3, &mut Rng::seed_from(4));
+        let mut everything = DiList::<usize>::init();
+        uniform_edges(&graph, &mut everything, 10, &mut Rng::seed_from(4));
+
+        // Then:
+        assert_eq!(storage.edge_count(), 3);
+        for (src_id, dst_id, edge) in storage.edges() {
+            let original =
+                graph.edges_between_unchecked(id_map.real_id_of(src_id), id_map.real_id_of(dst_id));
+            assert_eq!(original[0].get_weight(), edge.get_weight());
+        }
+        assert_eq!(everything.edge_count(), 6);
+        assert_eq!(everything.vertex_count(), 5);
+    }
+}
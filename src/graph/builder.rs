@@ -0,0 +1,295 @@
+use anyhow::Result;
+use magnitude::Magnitude;
+use num_traits::One;
+use std::any::Any;
+
+use crate::graph::{Edge, EdgeDir};
+use crate::provide::Graph;
+
+/// Collects vertices and edges and adds them to a graph in one go.
+///
+/// Vertices are referred to by their index, from 0 to `vertex_count - 1`.
+/// Any index used as an end point of an edge is added automatically, and [`vertices`](crate::graph::GraphBuilder::vertices) can be used to add isolated vertices.
+/// When building into an empty graph, index of each vertex is the same as its id in the graph.
+///
+/// For a more compact way of constructing graphs in tests and examples, checkout [`graph!`](crate::graph!) macro.
+///
+/// ## Generic Parameters
+/// * `W`: **W**eight type associated with edges.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::DiList;
+/// use prepona::graph::{GraphBuilder, ListGraph};
+///
+/// // Given: Graph
+/// //
+/// //      0  --1-->  1  --1-->  2      3
+/// //      |                     ^
+/// //      '----------5----------'
+/// //
+/// let graph = GraphBuilder::init()
+///     .vertices(4)
+///     .edges(vec![(0, 1), (1, 2)])
+///     .weighted_edge(0, 2, 5)
+///     .build(ListGraph::init(DiList::<usize>::init()))
+///     .unwrap();
+///
+/// assert_eq!(graph.vertex_count(), 4);
+/// assert_eq!(graph.edges_count(), 3);
+/// assert!(graph.neighbors_unchecked(3).is_empty());
+/// ```
+pub struct GraphBuilder<W> {
+    vertex_count: usize,
+    edges: Vec<(usize, usize, Magnitude<W>)>,
+}
+
+impl<W> GraphBuilder<W> {
+    /// # Returns
+    /// A builder with no vertices and no edges.
+    pub fn init() -> Self {
+        GraphBuilder {
+            vertex_count: 0,
+            edges: vec![],
+        }
+    }
+
+    /// Makes sure the graph has at least `count` vertices.
+    ///
+    /// # Arguments
+    /// `count`: Minimum number of vertices.
+    pub fn vertices(mut self, count: usize) -> Self {
+        self.vertex_count = self.vertex_count.max(count);
+
+        self
+    }
+
+    /// Adds an edge with the specified weight.
+    ///
+    /// # Arguments
+    /// * `src`: Index of the source vertex.
+    /// * `dst`: Index of the destination vertex.
+    /// * `weight`: Weight of the edge.
+    pub fn weighted_edge(
+        mut self,
+        src: usize,
+        dst: usize,
+        weight: impl Into<Magnitude<W>>,
+    ) -> Self {
+        self.vertex_count = self.vertex_count.max(src.max(dst) + 1);
+        self.edges.push((src, dst, weight.into()));
+
+        self
+    }
+
+    /// Adds edges with the specified weights.
+    ///
+    /// # Arguments
+    /// `edges`: Edges in the format of (`src`, `dst`, `weight`).
+    pub fn weighted_edges<I, M>(self, edges: I) -> Self
+    where
+        I: IntoIterator<Item = (usize, usize, M)>,
+        M: Into<Magnitude<W>>,
+    {
+        edges.into_iter().fold(self, |builder, (src, dst, weight)| {
+            builder.weighted_edge(src, dst, weight)
+        })
+    }
+
+    /// # Returns
+    /// Number of vertices the built graph will have.
+    pub fn vertex_count(&self) -> usize {
+        self.vertex_count
+    }
+
+    /// # Returns
+    /// Number of edges the built graph will have.
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Adds the collected vertices and edges to `graph`.
+    ///
+    /// # Arguments
+    /// `graph`: Graph to add vertices and edges to. It's usually empty.
+    ///
+    /// # Returns
+    /// * `Err`: If `graph` rejects one of the edges. For example when adding a loop to a simple graph.
+    /// * `Ok`: Containing `graph` after adding the vertices and edges.
+    pub fn build<E, Dir, G>(self, graph: G) -> Result<G>
+    where
+        E: Edge<W>,
+        Dir: EdgeDir,
+        G: Graph<W, E, Dir>,
+    {
+        self.build_with_ids(graph).map(|(graph, _)| graph)
+    }
+
+    /// Adds the collected vertices and edges to `graph`.
+    ///
+    /// # Arguments
+    /// `graph`: Graph to add vertices and edges to.
+    ///
+    /// # Returns
+    /// * `Err`: If `graph` rejects one of the edges. For example when adding a loop to a simple graph.
+    /// * `Ok`: Containing `graph` after adding the vertices and edges, and id of each vertex in the graph where `ids[i]` is the id of vertex with index `i`.
+    pub fn build_with_ids<E, Dir, G>(self, mut graph: G) -> Result<(G, Vec<usize>)>
+    where
+        E: Edge<W>,
+        Dir: EdgeDir,
+        G: Graph<W, E, Dir>,
+    {
+        let ids: Vec<usize> = (0..self.vertex_count).map(|_| graph.add_vertex()).collect();
+
+        for (src, dst, weight) in self.edges {
+            graph.add_edge(ids[src], ids[dst], E::init(weight))?;
+        }
+
+        Ok((graph, ids))
+    }
+}
+
+impl<W: One + Any> GraphBuilder<W> {
+    /// Adds an edge with weight one.
+    ///
+    /// # Arguments
+    /// * `src`: Index of the source vertex.
+    /// * `dst`: Index of the destination vertex.
+    pub fn edge(self, src: usize, dst: usize) -> Self {
+        self.weighted_edge(src, dst, W::one())
+    }
+
+    /// Adds edges with weight one.
+    ///
+    /// # Arguments
+    /// `edges`: Edges in the format of (`src`, `dst`).
+    pub fn edges<I>(self, edges: I) -> Self
+    where
+        I: IntoIterator<Item = (usize, usize)>,
+    {
+        edges
+            .into_iter()
+            .fold(self, |builder, (src, dst)| builder.edge(src, dst))
+    }
+}
+
+impl<W> Default for GraphBuilder<W> {
+    fn default() -> Self {
+        GraphBuilder::init()
+    }
+}
+
+/// Constructs a graph from a list of edges.
+///
+/// It expands to a [`GraphBuilder`](crate::graph::GraphBuilder) that builds into the given empty graph, and panics if the graph rejects one of the edges.
+/// Each edge is either `(src, dst)` which gets weight one, or `(src, dst, weight)`.
+/// Number of vertices can be specified after the graph, otherwise it's inferred from the edges.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::graph;
+/// use prepona::storage::{DiMat, Mat};
+/// use prepona::graph::MatGraph;
+///
+/// // Triangle with one heavy edge.
+/// let triangle = graph!(MatGraph::init(Mat::<usize>::init()) => (0, 1), (1, 2), (2, 0, 10));
+/// assert_eq!(triangle.vertex_count(), 3);
+/// assert_eq!(triangle.edges_count(), 3);
+///
+/// // Directed path with an isolated vertex.
+/// let path = graph!(MatGraph::init(DiMat::<usize>::init()), 4 => (0, 1), (1, 2));
+/// assert_eq!(path.vertex_count(), 4);
+/// assert!(path.has_any_edge_unchecked(0, 1));
+/// assert!(!path.has_any_edge_unchecked(1, 0));
+/// ```
+#[macro_export]
+macro_rules! graph {
+    ($graph:expr => $( ( $($edge:tt)* ) ),* $(,)?) => {
+        $crate::graph!($graph, 0 => $( ( $($edge)* ) ),*)
+    };
+    ($graph:expr, $vertex_count:expr => $( ( $($edge:tt)* ) ),* $(,)?) => {{
+        let builder = $crate::graph::GraphBuilder::init().vertices($vertex_count);
+        $( let builder = $crate::graph!(@edge builder, $($edge)*); )*
+        builder.build($graph).unwrap()
+    }};
+    (@edge $builder:ident, $src:expr, $dst:expr) => {
+        $builder.edge($src, $dst)
+    };
+    (@edge $builder:ident, $src:expr, $dst:expr, $weight:expr) => {
+        $builder.weighted_edge($src, $dst, $weight)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{MatGraph, MultiListGraph};
+    use crate::provide::{Edges, Vertices};
+    use crate::storage::{DiMat, List, Mat};
+
+    #[test]
+    fn build_into_non_empty_graph() {
+        // Given: Graph with a vertex already in it.
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+
+        // When:
+        let (graph, ids) = GraphBuilder::init()
+            .edges(vec![(0, 1), (1, 2)])
+            .build_with_ids(graph)
+            .unwrap();
+
+        // Then:
+        assert_eq!(ids.len(), 3);
+        assert!(!ids.contains(&a));
+        assert_eq!(graph.vertex_count(), 4);
+        assert!(graph.has_any_edge_unchecked(ids[0], ids[1]));
+        assert!(graph.has_any_edge_unchecked(ids[1], ids[2]));
+    }
+
+    #[test]
+    fn rejected_edges_are_reported() {
+        // Given: Builder with a loop.
+        let builder = GraphBuilder::init().edge(0, 1).edge(1, 1);
+
+        // When: Building into a simple graph.
+        let result = builder.build(MatGraph::init(Mat::<usize>::init()));
+
+        // Then:
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn macro_builds_multigraphs() {
+        // Given: Edges with a loop and a parallel edge.
+
+        // When:
+        let graph =
+            graph!(MultiListGraph::init(List::<usize>::init()) => (0, 1), (0, 1, 5), (1, 1));
+
+        // Then:
+        assert_eq!(graph.vertex_count(), 2);
+        assert_eq!(graph.edges_between_unchecked(0, 1).len(), 2);
+        assert!(graph.has_any_edge_unchecked(1, 1));
+    }
+
+    #[test]
+    fn weighted_edges() {
+        // Given:
+        let builder = GraphBuilder::init()
+            .vertices(2)
+            .weighted_edges(vec![(0, 3, 2), (3, 4, 7)]);
+
+        // When:
+        let graph = builder.build(MatGraph::init(Mat::<usize>::init())).unwrap();
+
+        // Then:
+        assert_eq!(graph.vertex_count(), 5);
+        assert_eq!(
+            *graph.edges_between_unchecked(3, 4)[0].get_weight(),
+            7.into()
+        );
+    }
+}
@@ -0,0 +1,414 @@
+use anyhow::Result;
+use magnitude::Magnitude;
+use std::collections::{HashMap, HashSet};
+
+use crate::graph::{Edge, EdgeDir, Error, ErrorKind};
+use crate::provide::{Edges, Graph, Vertices};
+
+/// Changes that turn one version of a graph into another, computed by [`diff`](crate::graph::diff).
+///
+/// Vertices are identified by their ids. Edges are identified by their end points and weight rather than their ids,
+/// because ids of edges are assigned by the storage and usually differ between two graphs loaded from the same data.
+/// Parallel edges are counted, so if the new version has one more edge with the same end points and weight, it appears in `added_edges` once.
+/// Edges of undirected graphs are stored with the smaller id as their source.
+///
+/// ## Generic Parameters
+/// * `W`: **W**eight type associated with edges.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphDiff<W> {
+    /// Ids of the vertices that only exist in the new version, sorted.
+    pub added_nodes: Vec<usize>,
+
+    /// Ids of the vertices that only exist in the old version, sorted.
+    pub removed_nodes: Vec<usize>,
+
+    /// Edges that only exist in the new version in the format of (`src_id`, `dst_id`, `weight`), sorted by their end points.
+    pub added_edges: Vec<(usize, usize, Magnitude<W>)>,
+
+    /// Edges that only exist in the old version in the format of (`src_id`, `dst_id`, `weight`), sorted by their end points.
+    /// Edges of removed vertices are included.
+    pub removed_edges: Vec<(usize, usize, Magnitude<W>)>,
+}
+
+impl<W> GraphDiff<W> {
+    /// # Returns
+    /// * `true`: If two versions are the same.
+    /// * `false`: Otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.added_nodes.is_empty()
+            && self.removed_nodes.is_empty()
+            && self.added_edges.is_empty()
+            && self.removed_edges.is_empty()
+    }
+}
+
+// Weights of the edges between each pair of vertices. Pairs of undirected edges are ordered.
+fn weights_by_end_points<W, E, Dir, G>(graph: &G) -> HashMap<(usize, usize), Vec<Magnitude<W>>>
+where
+    W: Clone,
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Edges<W, E> + Graph<W, E, Dir>,
+{
+    let mut weights: HashMap<(usize, usize), Vec<Magnitude<W>>> = HashMap::new();
+    let mut seen_ids = HashSet::new();
+
+    // Storages differ in how many times they return an undirected loop, so edges are deduplicated by their ids.
+    for (src_id, dst_id, edge) in graph.edges() {
+        if seen_ids.insert(edge.get_id()) {
+            weights
+                .entry(end_points_of::<Dir>(src_id, dst_id))
+                .or_default()
+                .push(edge.get_weight().clone());
+        }
+    }
+
+    weights
+}
+
+fn end_points_of<Dir: EdgeDir>(src_id: usize, dst_id: usize) -> (usize, usize) {
+    if Dir::is_undirected() && dst_id < src_id {
+        (dst_id, src_id)
+    } else {
+        (src_id, dst_id)
+    }
+}
+
+// Edges of `first` that are not matched by an edge of `second` with the same end points and weight.
+fn unmatched_edges<W: Clone + PartialEq>(
+    first: &HashMap<(usize, usize), Vec<Magnitude<W>>>,
+    second: &HashMap<(usize, usize), Vec<Magnitude<W>>>,
+) -> Vec<(usize, usize, Magnitude<W>)> {
+    let mut edges = vec![];
+
+    for ((src_id, dst_id), weights) in first {
+        let mut candidates: Vec<&Magnitude<W>> = second
+            .get(&(*src_id, *dst_id))
+            .map_or(vec![], |weights| weights.iter().collect());
+
+        for weight in weights {
+            match candidates.iter().position(|candidate| *candidate == weight) {
+                Some(index) => {
+                    candidates.swap_remove(index);
+                }
+                None => edges.push((*src_id, *dst_id, weight.clone())),
+            }
+        }
+    }
+
+    edges.sort_by_key(|(src_id, dst_id, _)| (*src_id, *dst_id));
+    edges
+}
+
+/// Computes the changes that turn `old` into `new`.
+///
+/// # Arguments
+/// * `old`: Old version of the graph.
+/// * `new`: New version of the graph.
+///
+/// # Returns
+/// Vertices and edges that are added to or removed from `old` to get `new`.
+///
+/// # Complexity
+/// O(|V| + |E| * k), where k is the maximum number of parallel edges between two vertices.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::DiList;
+/// use prepona::graph::{diff, apply, ListGraph};
+///
+/// // Given: Two versions of a graph.
+/// //
+/// //      old:    a  --1-->  b  --1-->  c
+/// //      new:    a  --2-->  b          c  --1-->  d
+/// //
+/// let mut old = ListGraph::init(DiList::<usize>::init());
+/// let a = old.add_vertex();
+/// let b = old.add_vertex();
+/// let c = old.add_vertex();
+/// old.add_edge_unchecked(a, b, 1.into());
+/// old.add_edge_unchecked(b, c, 1.into());
+///
+/// let mut new = ListGraph::init(DiList::<usize>::init());
+/// for _ in 0..4 {
+///     new.add_vertex();
+/// }
+/// let d = 3;
+/// new.add_edge_unchecked(a, b, 2.into());
+/// new.add_edge_unchecked(c, d, 1.into());
+///
+/// // When:
+/// let changes = diff(&old, &new);
+///
+/// // Then:
+/// assert_eq!(changes.added_nodes, vec![d]);
+/// assert_eq!(changes.added_edges, vec![(a, b, 2.into()), (c, d, 1.into())]);
+/// assert_eq!(changes.removed_edges, vec![(a, b, 1.into()), (b, c, 1.into())]);
+///
+/// // And: Applying the changes to the old version.
+/// apply(&changes, &mut old).unwrap();
+/// assert!(diff(&old, &new).is_empty());
+/// ```
+pub fn diff<W, E, Dir, G1, G2>(old: &G1, new: &G2) -> GraphDiff<W>
+where
+    W: Clone + PartialEq,
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G1: Vertices + Edges<W, E> + Graph<W, E, Dir>,
+    G2: Vertices + Edges<W, E> + Graph<W, E, Dir>,
+{
+    let mut added_nodes: Vec<usize> = new
+        .vertices()
+        .into_iter()
+        .filter(|vertex_id| !old.contains_vertex(*vertex_id))
+        .collect();
+    added_nodes.sort_unstable();
+
+    let mut removed_nodes: Vec<usize> = old
+        .vertices()
+        .into_iter()
+        .filter(|vertex_id| !new.contains_vertex(*vertex_id))
+        .collect();
+    removed_nodes.sort_unstable();
+
+    let (old_weights, new_weights) = (weights_by_end_points(old), weights_by_end_points(new));
+
+    GraphDiff {
+        added_nodes,
+        removed_nodes,
+        added_edges: unmatched_edges(&new_weights, &old_weights),
+        removed_edges: unmatched_edges(&old_weights, &new_weights),
+    }
+}
+
+/// Applies the changes in `diff` to `graph`.
+///
+/// Storages choose ids of new vertices themselves, so added vertices may get different ids than the ones in `diff`.
+/// Added edges refer to vertices by their ids in `diff` and are connected to the corresponding new vertices.
+/// Each removed edge removes one edge of `graph` with the same end points and weight.
+///
+/// `diff` is validated before `graph` is changed, so `graph` is left untouched if an error is returned.
+///
+/// # Arguments
+/// * `diff`: Changes to apply.
+/// * `graph`: Graph to change. It's expected to be the old version that `diff` is computed against.
+///
+/// # Returns
+/// * `Err`:
+///     * [`VertexNotFound`](crate::graph::ErrorKind::VertexNotFound): If a removed vertex or an end point of an added edge does not exist.
+///     * [`EdgeNotFound`](crate::graph::ErrorKind::EdgeNotFound): If there is no edge matching a removed edge.
+/// * `Ok`: Containing map from id of each added vertex in `diff` to its id in `graph`.
+pub fn apply<W, E, Dir, G>(diff: &GraphDiff<W>, graph: &mut G) -> Result<HashMap<usize, usize>>
+where
+    W: Clone + PartialEq,
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Vertices + Edges<W, E> + Graph<W, E, Dir>,
+{
+    for vertex_id in &diff.removed_nodes {
+        if !graph.contains_vertex(*vertex_id) {
+            Err(Error::new_vnf(*vertex_id))?
+        }
+    }
+
+    // Pick an edge of the graph for each removed edge, without picking an edge twice.
+    let mut removed_ids = HashSet::new();
+    let mut removed_edges = vec![];
+    for (src_id, dst_id, weight) in &diff.removed_edges {
+        if !graph.contains_vertex(*src_id) || !graph.contains_vertex(*dst_id) {
+            Err(Error::new_vnf(if graph.contains_vertex(*src_id) {
+                *dst_id
+            } else {
+                *src_id
+            }))?
+        }
+
+        let edge_id = graph
+            .edges_between_unchecked(*src_id, *dst_id)
+            .into_iter()
+            .find(|edge| edge.get_weight() == weight && !removed_ids.contains(&edge.get_id()))
+            .map(|edge| edge.get_id())
+            .ok_or_else(|| {
+                Error::new(
+                    ErrorKind::EdgeNotFound,
+                    format!(
+                        "There is no edge from {} to {} with the removed weight",
+                        src_id, dst_id
+                    ),
+                )
+            })?;
+
+        removed_ids.insert(edge_id);
+        removed_edges.push((*src_id, *dst_id, edge_id));
+    }
+
+    let removed_nodes: HashSet<usize> = diff.removed_nodes.iter().copied().collect();
+    let added_nodes: HashSet<usize> = diff.added_nodes.iter().copied().collect();
+    for (src_id, dst_id, _) in &diff.added_edges {
+        for vertex_id in [src_id, dst_id].iter() {
+            if !added_nodes.contains(vertex_id)
+                && (!graph.contains_vertex(**vertex_id) || removed_nodes.contains(vertex_id))
+            {
+                Err(Error::new_vnf(**vertex_id))?
+            }
+        }
+    }
+
+    for (src_id, dst_id, edge_id) in removed_edges {
+        graph.remove_edge_unchecked(src_id, dst_id, edge_id);
+    }
+    for vertex_id in &diff.removed_nodes {
+        graph.remove_vertex_unchecked(*vertex_id);
+    }
+
+    let new_ids: HashMap<usize, usize> = diff
+        .added_nodes
+        .iter()
+        .map(|vertex_id| (*vertex_id, graph.add_vertex()))
+        .collect();
+    let id_of = |vertex_id: usize| *new_ids.get(&vertex_id).unwrap_or(&vertex_id);
+
+    for (src_id, dst_id, weight) in &diff.added_edges {
+        graph.add_edge_unchecked(id_of(*src_id), id_of(*dst_id), E::init(weight.clone()));
+    }
+
+    Ok(new_ids)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{ListGraph, MultiListGraph};
+    use crate::storage::{DiList, List};
+
+    #[test]
+    fn same_graphs() {
+        // Given: Two graphs with the same edges added in different orders.
+        let mut first = ListGraph::init(List::<usize>::init());
+        let mut second = ListGraph::init(List::<usize>::init());
+        for _ in 0..3 {
+            first.add_vertex();
+            second.add_vertex();
+        }
+        first.add_edge_unchecked(0, 1, 1.into());
+        first.add_edge_unchecked(1, 2, 2.into());
+        second.add_edge_unchecked(2, 1, 2.into());
+        second.add_edge_unchecked(1, 0, 1.into());
+
+        // Then:
+        assert!(diff(&first, &second).is_empty());
+    }
+
+    #[test]
+    fn parallel_edges_and_loops() {
+        // Given: Undirected multigraphs where the new one has one more parallel edge and lost its loop.
+        let mut old = MultiListGraph::init(List::<usize>::init());
+        let a = old.add_vertex();
+        let b = old.add_vertex();
+        old.add_edge_unchecked(a, b, 1.into());
+        old.add_edge_unchecked(a, a, 3.into());
+
+        let mut new = MultiListGraph::init(List::<usize>::init());
+        new.add_vertex();
+        new.add_vertex();
+        new.add_edge_unchecked(b, a, 1.into());
+        new.add_edge_unchecked(a, b, 1.into());
+
+        // When:
+        let changes = diff(&old, &new);
+
+        // Then:
+        assert_eq!(changes.added_edges, vec![(a, b, 1.into())]);
+        assert_eq!(changes.removed_edges, vec![(a, a, 3.into())]);
+
+        // When:
+        apply(&changes, &mut old).unwrap();
+
+        // Then:
+        assert!(diff(&old, &new).is_empty());
+    }
+
+    #[test]
+    fn removed_and_added_vertices() {
+        // Given: Vertex b is replaced by a new vertex in the new version.
+        //
+        //      old:    a  --1-->  b  --1-->  c
+        //      new:    a  --1-->  d  --1-->  c
+        //
+        let mut old = ListGraph::init(DiList::<usize>::init());
+        let a = old.add_vertex();
+        let b = old.add_vertex();
+        let c = old.add_vertex();
+        old.add_edge_unchecked(a, b, 1.into());
+        old.add_edge_unchecked(b, c, 1.into());
+
+        let mut new = ListGraph::init(DiList::<usize>::init());
+        for _ in 0..4 {
+            new.add_vertex();
+        }
+        new.remove_vertex_unchecked(b);
+        let d = 3;
+        new.add_edge_unchecked(a, d, 1.into());
+        new.add_edge_unchecked(d, c, 1.into());
+
+        // When:
+        let changes = diff(&old, &new);
+        let new_ids = apply(&changes, &mut old).unwrap();
+
+        // Then:
+        assert_eq!(changes.removed_nodes, vec![b]);
+        assert_eq!(changes.added_nodes, vec![d]);
+        assert_eq!(changes.removed_edges.len(), 2);
+        // Storage may reuse the id of b for d.
+        assert_eq!(old.vertex_count(), 3);
+        let new_d = new_ids[&d];
+        assert!(old.has_any_edge_unchecked(a, new_d));
+        assert!(old.has_any_edge_unchecked(new_d, c));
+        assert_eq!(old.edges_count(), 2);
+    }
+
+    #[test]
+    fn invalid_diff_leaves_graph_untouched() {
+        // Given:
+        let mut graph = ListGraph::init(DiList::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+
+        // And: Diff that removes an edge with a different weight.
+        let changes = GraphDiff {
+            added_nodes: vec![],
+            removed_nodes: vec![b],
+            added_edges: vec![],
+            removed_edges: vec![(a, b, 2.into())],
+        };
+
+        // When:
+        let error = apply(&changes, &mut graph).unwrap_err();
+
+        // Then:
+        assert!(matches!(
+            error.downcast_ref::<Error>().unwrap().kind(),
+            ErrorKind::EdgeNotFound
+        ));
+        assert_eq!(graph.vertex_count(), 2);
+        assert_eq!(graph.edges_count(), 1);
+
+        // When: Adding an edge to a removed vertex.
+        let changes = GraphDiff {
+            added_nodes: vec![],
+            removed_nodes: vec![b],
+            added_edges: vec![(a, b, 1.into())],
+            removed_edges: vec![],
+        };
+        let error = apply(&changes, &mut graph).unwrap_err();
+
+        // Then:
+        assert!(matches!(
+            error.downcast_ref::<Error>().unwrap().kind(),
+            ErrorKind::VertexNotFound
+        ));
+        assert_eq!(graph.vertex_count(), 2);
+    }
+}
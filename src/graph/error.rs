@@ -6,6 +6,8 @@ pub enum ErrorKind {
     EdgeNotFound,
     EdgeAlreadyExists,
     RootAlreadyExists,
+    KeyNotFound,
+    NotATree,
 }
 
 /// Error type returns in [`graph`](crate::graph) module.
@@ -113,6 +115,39 @@ impl Error {
         }
     }
 
+    /// Creates a new [`KeyNotFound`](crate::graph::ErrorKind::KeyNotFound) kind of error.
+    /// This error is thrown in [`InternedGraph`](crate::graph::InternedGraph) when there is no vertex associated with a key.
+    ///
+    /// # Arguments
+    /// `key`: Debug representation of the key.
+    ///
+    /// # Returns
+    /// `Error` with `KeyNotFound` kind and predefined message.
+    pub fn new_knf(key: &str) -> Self {
+        Error {
+            kind: ErrorKind::KeyNotFound,
+            msg: format!("No vertex is associated with key: {}", key),
+        }
+    }
+
+    /// Creates a new [`NotATree`](crate::graph::ErrorKind::NotATree) kind of error.
+    /// This error is thrown in [`Tree`](crate::graph::Tree) when a vertex is not reachable from the root through exactly one path.
+    ///
+    /// # Arguments
+    /// `vertex_id`: Id of the offending vertex.
+    ///
+    /// # Returns
+    /// `Error` with `NotATree` kind and predefined message.
+    pub fn new_nat(vertex_id: usize) -> Self {
+        Error {
+            kind: ErrorKind::NotATree,
+            msg: format!(
+                "Graph is not a tree: Vertex with id: {} is not reachable from the root through exactly one path",
+                vertex_id
+            ),
+        }
+    }
+
     /// # Returns
     /// Message inside of the error.
     pub fn msg(&self) -> &str {
@@ -1,3 +1,5 @@
+mod builder;
+mod diff;
 mod edge;
 mod error;
 mod structs;
@@ -17,6 +19,13 @@ mod structs;
 /// So it just forwards every call of `AsSubgraph` functions to the inner `Subgraph`.
 pub mod subgraph;
 
+pub use builder::GraphBuilder;
+pub use diff::{apply, diff, GraphDiff};
 pub use edge::{DefaultEdge, DirectedEdge, Edge, EdgeDir, FlowEdge, UndirectedEdge};
 pub use error::{Error, ErrorKind};
-pub use structs::{FlowListGraph, FlowMatGraph, ListGraph, MatGraph, SimpleGraph};
+pub use structs::{
+    freeze, CowGraph, EdgeFilter, EulerTour, FilterView, FlowListGraph, FlowMatGraph, Frozen,
+    InternedGraph, KHopView, KeepAll, ListGraph, MatGraph, MultiGraph, MultiListGraph,
+    MultiMatGraph, PropGraph, RelabelView, ReverseView, SimpleGraph, SnapshotFilter, SyncView,
+    TemporalGraph, TimeInterval, Tree, VertexFilter, WeightedView,
+};
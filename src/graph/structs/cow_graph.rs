@@ -0,0 +1,565 @@
+use std::collections::{HashMap, HashSet};
+use std::marker::PhantomData;
+
+use anyhow::Result;
+
+use crate::graph::{error::Error, Edge, EdgeDir};
+use crate::provide::{Direction, Edges, Graph, Neighbors, Vertices};
+
+/// A copy-on-write view of a graph.
+///
+/// `CowGraph` borrows a base graph and records every mutation in an overlay instead of applying it to the base graph.
+/// Queries merge the base graph with the overlay, so the view behaves like a mutated copy of the base graph without cloning it.
+/// Only edges that get updated or removed are cloned from the base graph. This enables cheap what-if experiments on large graphs,
+/// like removing a few edges and running an algorithm again, while other code keeps using the base graph.
+///
+/// ## Note
+/// Newly added vertices and edges get ids that are larger than every id in the base graph.
+/// Removing a vertex is O(|E|) because incoming edges of the vertex must be found as well.
+///
+/// ## Generic Parameters
+/// * `W`: **W**eight type associated with edges.
+/// * `E`: **E**dge type that graph uses.
+/// * `Dir`: **Dir**ection of edges: [`Directed`](crate::graph::DirectedEdge) or [`Undirected`](crate::graph::UndirectedEdge).
+/// * `G`: **G**raph type of the base graph.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::Mat;
+/// use prepona::graph::{MatGraph, CowGraph};
+/// use prepona::algo::Dijkstra;
+///
+/// // Given: Graph
+/// //
+/// //      a  --1--  b  --1--  c
+/// //      |                   |
+/// //      '--------5----------'
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let ab = graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(a, c, 5.into());
+///
+/// // When: Removing edge ab in a copy-on-write view.
+/// let mut cow = CowGraph::init(&graph);
+/// cow.remove_edge_unchecked(a, b, ab);
+///
+/// // Then: Only the view is affected.
+/// let sp = Dijkstra::init(&cow).execute(&cow, a);
+/// assert_eq!(sp.distance_to(c).unwrap(), 5.into());
+///
+/// let sp = Dijkstra::init(&graph).execute(&graph, a);
+/// assert_eq!(sp.distance_to(c).unwrap(), 2.into());
+/// ```
+pub struct CowGraph<'a, W, E, Dir, G>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir>,
+{
+    graph: &'a G,
+
+    removed_vertices: HashSet<usize>,
+    added_vertices: HashSet<usize>,
+    next_vertex_id: usize,
+
+    removed_edges: HashSet<usize>,
+    updated_edges: HashMap<usize, E>,
+    added_edges: Vec<(usize, usize, E)>,
+    next_edge_id: usize,
+
+    phantom_w: PhantomData<W>,
+    phantom_dir: PhantomData<Dir>,
+}
+
+impl<'a, W, E, Dir, G> CowGraph<'a, W, E, Dir, G>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Vertices + Edges<W, E>,
+{
+    /// # Arguments
+    /// `graph`: Base graph of the view.
+    ///
+    /// # Returns
+    /// A view that is identical to `graph`.
+    pub fn init(graph: &'a G) -> Self {
+        let next_vertex_id = graph
+            .vertices()
+            .into_iter()
+            .max()
+            .map_or(0, |max_id| max_id + 1);
+
+        let next_edge_id = graph
+            .edges()
+            .into_iter()
+            .map(|(_, _, edge)| edge.get_id())
+            .max()
+            .map_or(0, |max_id| max_id + 1);
+
+        CowGraph {
+            graph,
+
+            removed_vertices: HashSet::new(),
+            added_vertices: HashSet::new(),
+            next_vertex_id,
+
+            removed_edges: HashSet::new(),
+            updated_edges: HashMap::new(),
+            added_edges: vec![],
+            next_edge_id,
+
+            phantom_w: PhantomData,
+            phantom_dir: PhantomData,
+        }
+    }
+
+    /// # Returns
+    /// The base graph.
+    pub fn base(&self) -> &G {
+        self.graph
+    }
+
+    /// # Returns
+    /// `true` if the view has not been mutated.
+    pub fn is_unchanged(&self) -> bool {
+        self.removed_vertices.is_empty()
+            && self.added_vertices.is_empty()
+            && self.removed_edges.is_empty()
+            && self.updated_edges.is_empty()
+            && self.added_edges.is_empty()
+    }
+
+    // Replaces the edge of the base graph with its updated version if there is one.
+    fn current<'b>(&'b self, edge: &'b E) -> &'b E {
+        self.updated_edges.get(&edge.get_id()).unwrap_or(edge)
+    }
+}
+
+/// For documentation about each function checkout [`Neighbors`](crate::provide::Neighbors) trait.
+impl<'a, W, E, Dir, G> Neighbors for CowGraph<'a, W, E, Dir, G>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Vertices + Edges<W, E>,
+{
+    fn neighbors(&self, src_id: usize) -> Result<Vec<usize>> {
+        if !self.contains_vertex(src_id) {
+            Err(Error::new_vnf(src_id))?
+        } else {
+            Ok(self.neighbors_unchecked(src_id))
+        }
+    }
+
+    fn neighbors_unchecked(&self, src_id: usize) -> Vec<usize> {
+        let mut visited = HashSet::new();
+
+        self.edges_from_unchecked(src_id)
+            .into_iter()
+            .map(|(dst_id, _)| dst_id)
+            .filter(|dst_id| visited.insert(*dst_id))
+            .collect()
+    }
+}
+
+/// For documentation about each function checkout [`Vertices`](crate::provide::Vertices) trait.
+impl<'a, W, E, Dir, G> Vertices for CowGraph<'a, W, E, Dir, G>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Vertices + Edges<W, E>,
+{
+    fn vertices(&self) -> Vec<usize> {
+        let mut vertices: Vec<usize> = self
+            .graph
+            .vertices()
+            .into_iter()
+            .filter(|vertex_id| !self.removed_vertices.contains(vertex_id))
+            .collect();
+
+        let mut added_vertices: Vec<usize> = self.added_vertices.iter().copied().collect();
+        added_vertices.sort_unstable();
+
+        vertices.append(&mut added_vertices);
+
+        vertices
+    }
+
+    fn contains_vertex(&self, vertex_id: usize) -> bool {
+        self.added_vertices.contains(&vertex_id)
+            || (self.graph.contains_vertex(vertex_id)
+                && !self.removed_vertices.contains(&vertex_id))
+    }
+}
+
+/// For documentation about each function checkout [`Edges`](crate::provide::Edges) trait.
+impl<'a, W, E, Dir, G> Edges<W, E> for CowGraph<'a, W, E, Dir, G>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Vertices + Edges<W, E>,
+{
+    fn edges_from(&self, src_id: usize) -> Result<Vec<(usize, &E)>> {
+        if !self.contains_vertex(src_id) {
+            Err(Error::new_vnf(src_id))?
+        } else {
+            Ok(self.edges_from_unchecked(src_id))
+        }
+    }
+
+    fn edges_from_unchecked(&self, src_id: usize) -> Vec<(usize, &E)> {
+        let mut edges = vec![];
+
+        if !self.added_vertices.contains(&src_id) {
+            edges = self
+                .graph
+                .edges_from_unchecked(src_id)
+                .into_iter()
+                .filter(|(_, edge)| !self.removed_edges.contains(&edge.get_id()))
+                .map(|(dst_id, edge)| (dst_id, self.current(edge)))
+                .collect();
+        }
+
+        for (s_id, d_id, edge) in &self.added_edges {
+            if *s_id == src_id {
+                edges.push((*d_id, edge));
+            } else if Dir::is_undirected() && *d_id == src_id {
+                edges.push((*s_id, edge));
+            }
+        }
+
+        edges
+    }
+
+    fn edges_between(&self, src_id: usize, dst_id: usize) -> Result<Vec<&E>> {
+        if !self.contains_vertex(src_id) {
+            Err(Error::new_vnf(src_id))?
+        } else if !self.contains_vertex(dst_id) {
+            Err(Error::new_vnf(dst_id))?
+        } else {
+            Ok(self.edges_between_unchecked(src_id, dst_id))
+        }
+    }
+
+    fn edges_between_unchecked(&self, src_id: usize, dst_id: usize) -> Vec<&E> {
+        self.edges_from_unchecked(src_id)
+            .into_iter()
+            .filter(|(n_id, _)| *n_id == dst_id)
+            .map(|(_, edge)| edge)
+            .collect()
+    }
+
+    fn edge_between(&self, src_id: usize, dst_id: usize, edge_id: usize) -> Result<&E> {
+        self.edges_between(src_id, dst_id)?
+            .into_iter()
+            .find(|edge| edge.get_id() == edge_id)
+            .ok_or_else(|| Error::new_enf(edge_id).into())
+    }
+
+    fn edge_between_unchecked(&self, src_id: usize, dst_id: usize, edge_id: usize) -> &E {
+        self.edge_between(src_id, dst_id, edge_id).unwrap()
+    }
+
+    fn edge(&self, edge_id: usize) -> Result<&E> {
+        if !self.contains_edge(edge_id) {
+            Err(Error::new_enf(edge_id))?
+        } else {
+            Ok(self.edge_unchecked(edge_id))
+        }
+    }
+
+    fn edge_unchecked(&self, edge_id: usize) -> &E {
+        if let Some(edge) = self.updated_edges.get(&edge_id) {
+            edge
+        } else if let Some((_, _, edge)) = self
+            .added_edges
+            .iter()
+            .find(|(_, _, edge)| edge.get_id() == edge_id)
+        {
+            edge
+        } else {
+            self.graph.edge_unchecked(edge_id)
+        }
+    }
+
+    fn has_any_edge(&self, src_id: usize, dst_id: usize) -> Result<bool> {
+        Ok(!self.edges_between(src_id, dst_id)?.is_empty())
+    }
+
+    fn has_any_edge_unchecked(&self, src_id: usize, dst_id: usize) -> bool {
+        !self.edges_between_unchecked(src_id, dst_id).is_empty()
+    }
+
+    fn edges(&self) -> Vec<(usize, usize, &E)> {
+        if Dir::is_directed() {
+            self.as_directed_edges()
+        } else {
+            self.as_directed_edges()
+                .into_iter()
+                .filter(|(src_id, dst_id, _)| src_id <= dst_id)
+                .collect()
+        }
+    }
+
+    fn as_directed_edges(&self) -> Vec<(usize, usize, &E)> {
+        self.vertices()
+            .into_iter()
+            .flat_map(|src_id| {
+                self.edges_from_unchecked(src_id)
+                    .into_iter()
+                    .map(move |(dst_id, edge)| (src_id, dst_id, edge))
+            })
+            .collect()
+    }
+
+    fn edges_count(&self) -> usize {
+        self.edges().len()
+    }
+
+    fn contains_edge(&self, edge_id: usize) -> bool {
+        !self.removed_edges.contains(&edge_id)
+            && (self.graph.contains_edge(edge_id)
+                || self
+                    .added_edges
+                    .iter()
+                    .any(|(_, _, edge)| edge.get_id() == edge_id))
+    }
+}
+
+/// For documentation checkout [`Direction`](crate::provide::Direction) trait.
+impl<'a, W, E, Dir, G> Direction<Dir> for CowGraph<'a, W, E, Dir, G>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir>,
+{
+}
+
+/// For documentation about each function checkout [`Graph`](crate::provide::Graph) trait.
+///
+/// Mutations never reach the base graph. Updating or removing an edge of the base graph clones it.
+impl<'a, W, E, Dir, G> Graph<W, E, Dir> for CowGraph<'a, W, E, Dir, G>
+where
+    E: Edge<W> + Clone,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Vertices + Edges<W, E>,
+{
+    fn add_vertex(&mut self) -> usize {
+        let vertex_id = self.next_vertex_id;
+
+        self.next_vertex_id += 1;
+        self.added_vertices.insert(vertex_id);
+
+        vertex_id
+    }
+
+    fn remove_vertex(&mut self, vertex_id: usize) -> Result<()> {
+        if !self.contains_vertex(vertex_id) {
+            Err(Error::new_vnf(vertex_id))?
+        } else {
+            self.remove_vertex_unchecked(vertex_id);
+
+            Ok(())
+        }
+    }
+
+    fn remove_vertex_unchecked(&mut self, vertex_id: usize) {
+        let incident_edge_ids: Vec<usize> = self
+            .as_directed_edges()
+            .into_iter()
+            .filter(|(src_id, dst_id, _)| *src_id == vertex_id || *dst_id == vertex_id)
+            .map(|(_, _, edge)| edge.get_id())
+            .collect();
+
+        self.added_edges
+            .retain(|(src_id, dst_id, _)| *src_id != vertex_id && *dst_id != vertex_id);
+        for edge_id in incident_edge_ids {
+            self.updated_edges.remove(&edge_id);
+            self.removed_edges.insert(edge_id);
+        }
+
+        if !self.added_vertices.remove(&vertex_id) {
+            self.removed_vertices.insert(vertex_id);
+        }
+    }
+
+    fn add_edge(&mut self, src_id: usize, dst_id: usize, edge: E) -> Result<usize> {
+        if !self.contains_vertex(src_id) {
+            Err(Error::new_vnf(src_id))?
+        } else if !self.contains_vertex(dst_id) {
+            Err(Error::new_vnf(dst_id))?
+        } else {
+            Ok(self.add_edge_unchecked(src_id, dst_id, edge))
+        }
+    }
+
+    fn add_edge_unchecked(&mut self, src_id: usize, dst_id: usize, mut edge: E) -> usize {
+        let edge_id = self.next_edge_id;
+
+        self.next_edge_id += 1;
+        edge.set_id(edge_id);
+        self.added_edges.push((src_id, dst_id, edge));
+
+        edge_id
+    }
+
+    fn update_edge(&mut self, src_id: usize, dst_id: usize, edge_id: usize, edge: E) -> Result<()> {
+        self.edge_between(src_id, dst_id, edge_id)?;
+
+        self.update_edge_unchecked(src_id, dst_id, edge_id, edge);
+
+        Ok(())
+    }
+
+    fn update_edge_unchecked(&mut self, _: usize, _: usize, edge_id: usize, mut edge: E) {
+        edge.set_id(edge_id);
+
+        if let Some((_, _, added_edge)) = self
+            .added_edges
+            .iter_mut()
+            .find(|(_, _, added_edge)| added_edge.get_id() == edge_id)
+        {
+            *added_edge = edge;
+        } else {
+            self.updated_edges.insert(edge_id, edge);
+        }
+    }
+
+    fn remove_edge(&mut self, src_id: usize, dst_id: usize, edge_id: usize) -> Result<E> {
+        self.edge_between(src_id, dst_id, edge_id)?;
+
+        Ok(self.remove_edge_unchecked(src_id, dst_id, edge_id))
+    }
+
+    fn remove_edge_unchecked(&mut self, _: usize, _: usize, edge_id: usize) -> E {
+        if let Some(index) = self
+            .added_edges
+            .iter()
+            .position(|(_, _, edge)| edge.get_id() == edge_id)
+        {
+            self.added_edges.remove(index).2
+        } else {
+            self.removed_edges.insert(edge_id);
+
+            self.updated_edges
+                .remove(&edge_id)
+                .unwrap_or_else(|| self.graph.edge_unchecked(edge_id).clone())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{ListGraph, MatGraph};
+    use crate::storage::{DiList, Mat};
+
+    #[test]
+    fn unchanged_view() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  c
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+
+        // When: Creating a view.
+        let cow = CowGraph::init(&graph);
+
+        // Then:
+        assert!(cow.is_unchanged());
+        assert_eq!(cow.vertex_count(), 3);
+        assert_eq!(cow.edges_count(), 2);
+        assert_eq!(cow.neighbors_unchecked(b).len(), 2);
+    }
+
+    #[test]
+    fn mutations_do_not_reach_base() {
+        // Given: Directed graph
+        //
+        //      a  -->  b  -->  c
+        //
+        let mut graph = ListGraph::init(DiList::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let ab_id = graph.add_edge_unchecked(a, b, 1.into());
+        let bc_id = graph.add_edge_unchecked(b, c, 2.into());
+
+        // When: Mutating the view.
+        let mut cow = CowGraph::init(&graph);
+        let d = cow.add_vertex();
+        let cd_id = cow.add_edge(c, d, 3.into()).unwrap();
+        cow.update_edge(b, c, bc_id, 20.into()).unwrap();
+        let removed = cow.remove_edge(a, b, ab_id).unwrap();
+
+        // Then: View reflects the mutations.
+        assert!(!cow.is_unchanged());
+        assert_eq!(removed.get_weight(), &1.into());
+        assert!(!cow.contains_edge(ab_id));
+        assert!(!cow.has_any_edge_unchecked(a, b));
+        assert_eq!(cow.edge_unchecked(bc_id).get_weight(), &20.into());
+        assert_eq!(cow.edges_between_unchecked(c, d)[0].get_id(), cd_id);
+        assert_eq!(cow.vertex_count(), 4);
+        assert_eq!(cow.edges_count(), 2);
+        assert!(!graph.contains_edge(cd_id));
+
+        // Then: Base graph is untouched.
+        assert_eq!(graph.vertex_count(), 3);
+        assert_eq!(graph.edges_count(), 2);
+        assert_eq!(graph.edge_unchecked(bc_id).get_weight(), &2.into());
+    }
+
+    #[test]
+    fn remove_vertex_removes_incident_edges() {
+        // Given: Directed graph
+        //
+        //      a  -->  b  -->  c
+        //
+        let mut graph = ListGraph::init(DiList::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let ab_id = graph.add_edge_unchecked(a, b, 1.into());
+        let bc_id = graph.add_edge_unchecked(b, c, 1.into());
+
+        // When: Removing b from the view.
+        let mut cow = CowGraph::init(&graph);
+        cow.remove_vertex(b).unwrap();
+
+        // Then:
+        assert!(!cow.contains_vertex(b));
+        assert!(!cow.contains_edge(ab_id));
+        assert!(!cow.contains_edge(bc_id));
+        assert!(cow.neighbors_unchecked(a).is_empty());
+        assert_eq!(cow.vertices(), vec![a, c]);
+        assert!(cow.remove_vertex(b).is_err());
+        assert!(graph.contains_vertex(b));
+    }
+
+    #[test]
+    fn undirected_added_edges() {
+        // Given: Graph with two vertices.
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+
+        // When: Adding an edge to the view.
+        let mut cow = CowGraph::init(&graph);
+        cow.add_edge_unchecked(a, b, 1.into());
+
+        // Then: Edge is visible from both ends.
+        assert_eq!(cow.neighbors_unchecked(a), vec![b]);
+        assert_eq!(cow.neighbors_unchecked(b), vec![a]);
+        assert_eq!(cow.edges_count(), 1);
+        assert_eq!(cow.as_directed_edges().len(), 2);
+    }
+}
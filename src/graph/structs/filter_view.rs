@@ -0,0 +1,584 @@
+use std::collections::HashSet;
+use std::marker::PhantomData;
+
+use anyhow::Result;
+
+use crate::graph::{Edge, EdgeDir, Error};
+use crate::provide::{Direction, Edges, Neighbors, Vertices};
+
+/// Decides which vertices are visible through a [`FilterView`](crate::graph::FilterView).
+///
+/// It is implemented for closures of type `Fn(usize) -> bool`, for sets of vertex ids and for [`KeepAll`](crate::graph::KeepAll).
+pub trait VertexFilter {
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// * `true`: If the vertex must be kept.
+    /// * `false`: Otherwise.
+    fn keep_vertex(&self, vertex_id: usize) -> bool;
+}
+
+/// Decides which edges are visible through a [`FilterView`](crate::graph::FilterView).
+///
+/// It is implemented for closures of type `Fn(usize, usize, &E) -> bool` receiving (`src_id`, `dst_id`, `edge`), for sets of edge ids and for [`KeepAll`](crate::graph::KeepAll).
+///
+/// Undirected edges may be passed to the filter in either direction, so a closure filter used on an undirected graph should not depend on the order of `src_id` and `dst_id`.
+pub trait EdgeFilter<W, E: Edge<W>> {
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    /// * `edge`: The edge from source to destination.
+    ///
+    /// # Returns
+    /// * `true`: If the edge must be kept.
+    /// * `false`: Otherwise.
+    fn keep_edge(&self, src_id: usize, dst_id: usize, edge: &E) -> bool;
+}
+
+/// A filter that keeps every vertex and every edge.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct KeepAll;
+
+impl VertexFilter for KeepAll {
+    fn keep_vertex(&self, _: usize) -> bool {
+        true
+    }
+}
+
+impl<W, E: Edge<W>> EdgeFilter<W, E> for KeepAll {
+    fn keep_edge(&self, _: usize, _: usize, _: &E) -> bool {
+        true
+    }
+}
+
+impl<F: Fn(usize) -> bool> VertexFilter for F {
+    fn keep_vertex(&self, vertex_id: usize) -> bool {
+        self(vertex_id)
+    }
+}
+
+impl VertexFilter for HashSet<usize> {
+    fn keep_vertex(&self, vertex_id: usize) -> bool {
+        self.contains(&vertex_id)
+    }
+}
+
+impl<W, E: Edge<W>, F: Fn(usize, usize, &E) -> bool> EdgeFilter<W, E> for F {
+    fn keep_edge(&self, src_id: usize, dst_id: usize, edge: &E) -> bool {
+        self(src_id, dst_id, edge)
+    }
+}
+
+impl<W, E: Edge<W>> EdgeFilter<W, E> for HashSet<usize> {
+    fn keep_edge(&self, _: usize, _: usize, edge: &E) -> bool {
+        self.contains(&edge.get_id())
+    }
+}
+
+/// A read only view of a graph that hides the vertices and edges rejected by its filters.
+///
+/// An edge is visible only if its edge filter keeps it and both of its end points are visible.
+/// Filters are evaluated lazily on every query, so the view never copies the graph.
+///
+/// ## Generic Parameters
+/// * `W`: **W**eight type associated with edges.
+/// * `E`: **E**dge type that graph uses.
+/// * `G`: **G**raph to filter.
+/// * `NF`: **N**ode **F**ilter. Any [`VertexFilter`](crate::graph::VertexFilter).
+/// * `EF`: **E**dge **F**ilter. Any [`EdgeFilter`](crate::graph::EdgeFilter).
+///
+/// # Examples
+/// ```
+/// use std::collections::HashSet;
+/// use prepona::prelude::*;
+/// use prepona::storage::Mat;
+/// use prepona::graph::{FilterView, MatGraph};
+///
+/// // Given: Graph
+/// //
+/// //      a  --1--  b  --5--  c  --1--  d
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 5.into());
+/// graph.add_edge_unchecked(c, d, 1.into());
+///
+/// // When: Hiding vertex d and every edge heavier than 2.
+/// let vertices: HashSet<usize> = [a, b, c].iter().copied().collect();
+/// let view = FilterView::init(&graph, vertices, |_, _, edge: &DefaultEdge<usize>| {
+///     *edge.get_weight() <= 2.into()
+/// });
+///
+/// // Then:
+/// assert_eq!(view.vertex_count(), 3);
+/// assert_eq!(view.edges_count(), 1);
+/// assert_eq!(view.neighbors_unchecked(b), vec![a]);
+/// assert_eq!(view.degree(c), 0);
+/// ```
+pub struct FilterView<'a, W, E, G, NF, EF> {
+    graph: &'a G,
+    vertex_filter: NF,
+    edge_filter: EF,
+
+    phantom_w: PhantomData<W>,
+    phantom_e: PhantomData<E>,
+}
+
+impl<'a, W, E, G, NF, EF> FilterView<'a, W, E, G, NF, EF>
+where
+    E: Edge<W>,
+    G: Edges<W, E>,
+{
+    /// # Arguments
+    /// * `graph`: Graph to filter.
+    /// * `vertex_filter`: Decides which vertices are visible.
+    /// * `edge_filter`: Decides which edges are visible.
+    ///
+    /// # Returns
+    /// A view of `graph` that only contains vertices and edges accepted by the filters.
+    pub fn init(graph: &'a G, vertex_filter: NF, edge_filter: EF) -> Self {
+        FilterView {
+            graph,
+            vertex_filter,
+            edge_filter,
+
+            phantom_w: PhantomData,
+            phantom_e: PhantomData,
+        }
+    }
+
+    /// # Returns
+    /// The graph behind the view.
+    pub fn graph(&self) -> &'a G {
+        self.graph
+    }
+
+    /// # Returns
+    /// The vertex filter of the view.
+    pub fn vertex_filter(&self) -> &NF {
+        &self.vertex_filter
+    }
+
+    /// # Returns
+    /// The edge filter of the view.
+    pub fn edge_filter(&self) -> &EF {
+        &self.edge_filter
+    }
+}
+
+impl<'a, W, E, G, NF> FilterView<'a, W, E, G, NF, KeepAll>
+where
+    E: Edge<W>,
+    G: Edges<W, E>,
+{
+    /// # Arguments
+    /// * `graph`: Graph to filter.
+    /// * `vertex_filter`: Decides which vertices are visible.
+    ///
+    /// # Returns
+    /// A view of `graph` induced by the vertices accepted by `vertex_filter`.
+    pub fn vertices_only(graph: &'a G, vertex_filter: NF) -> Self {
+        FilterView::init(graph, vertex_filter, KeepAll)
+    }
+}
+
+impl<'a, W, E, G, EF> FilterView<'a, W, E, G, KeepAll, EF>
+where
+    E: Edge<W>,
+    G: Edges<W, E>,
+{
+    /// # Arguments
+    /// * `graph`: Graph to filter.
+    /// * `edge_filter`: Decides which edges are visible.
+    ///
+    /// # Returns
+    /// A view of `graph` that contains all of its vertices but only the edges accepted by `edge_filter`.
+    pub fn edges_only(graph: &'a G, edge_filter: EF) -> Self {
+        FilterView::init(graph, KeepAll, edge_filter)
+    }
+}
+
+impl<'a, W, E, G, NF, EF> FilterView<'a, W, E, G, NF, EF>
+where
+    E: Edge<W>,
+    G: Vertices + Edges<W, E>,
+    NF: VertexFilter,
+    EF: EdgeFilter<W, E>,
+{
+    fn is_visible(&self, vertex_id: usize) -> bool {
+        self.graph.contains_vertex(vertex_id) && self.vertex_filter.keep_vertex(vertex_id)
+    }
+
+    fn keeps(&self, src_id: usize, dst_id: usize, edge: &E) -> bool {
+        self.is_visible(src_id)
+            && self.is_visible(dst_id)
+            && self.edge_filter.keep_edge(src_id, dst_id, edge)
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// Number of visible edges going out of the vertex.
+    /// For undirected graphs it's the same as [`degree`](crate::graph::FilterView::degree).
+    pub fn out_degree(&self, vertex_id: usize) -> usize {
+        self.edges_from_unchecked(vertex_id).len()
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// Number of visible edges coming into the vertex.
+    /// For undirected graphs it's the same as [`degree`](crate::graph::FilterView::degree).
+    ///
+    /// # Complexity
+    /// O(|V| + |E|) because the view has to look at the outgoing edges of every visible vertex.
+    pub fn in_degree(&self, vertex_id: usize) -> usize {
+        if !self.is_visible(vertex_id) {
+            return 0;
+        }
+
+        self.vertices()
+            .into_iter()
+            .map(|src_id| {
+                self.edges_from_unchecked(src_id)
+                    .into_iter()
+                    .filter(|(dst_id, _)| *dst_id == vertex_id)
+                    .count()
+            })
+            .sum()
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// Number of visible edges incident to the vertex.
+    /// For directed graphs it's the same as [`out_degree`](crate::graph::FilterView::out_degree).
+    pub fn degree(&self, vertex_id: usize) -> usize {
+        self.out_degree(vertex_id)
+    }
+}
+
+/// For documentation about each function checkout [`Neighbors`](crate::provide::Neighbors) trait.
+///
+/// Each neighbor is reported once, even if multiple visible edges lead to it.
+impl<'a, W, E, G, NF, EF> Neighbors for FilterView<'a, W, E, G, NF, EF>
+where
+    E: Edge<W>,
+    G: Vertices + Edges<W, E>,
+    NF: VertexFilter,
+    EF: EdgeFilter<W, E>,
+{
+    fn neighbors(&self, src_id: usize) -> Result<Vec<usize>> {
+        if !self.is_visible(src_id) {
+            Err(Error::new_vnf(src_id))?
+        } else {
+            Ok(self.neighbors_unchecked(src_id))
+        }
+    }
+
+    fn neighbors_unchecked(&self, src_id: usize) -> Vec<usize> {
+        let mut seen = HashSet::new();
+
+        self.edges_from_unchecked(src_id)
+            .into_iter()
+            .map(|(dst_id, _)| dst_id)
+            .filter(|dst_id| seen.insert(*dst_id))
+            .collect()
+    }
+}
+
+/// For documentation about each function checkout [`Vertices`](crate::provide::Vertices) trait.
+impl<'a, W, E, G, NF, EF> Vertices for FilterView<'a, W, E, G, NF, EF>
+where
+    E: Edge<W>,
+    G: Vertices + Edges<W, E>,
+    NF: VertexFilter,
+    EF: EdgeFilter<W, E>,
+{
+    fn vertices(&self) -> Vec<usize> {
+        self.graph
+            .vertices()
+            .into_iter()
+            .filter(|vertex_id| self.vertex_filter.keep_vertex(*vertex_id))
+            .collect()
+    }
+
+    fn contains_vertex(&self, vertex_id: usize) -> bool {
+        self.is_visible(vertex_id)
+    }
+}
+
+/// Edges of the view have the same direction as edges of the underlying graph.
+impl<'a, W, E, G, NF, EF, Dir> Direction<Dir> for FilterView<'a, W, E, G, NF, EF>
+where
+    Dir: EdgeDir,
+    G: Direction<Dir>,
+{
+}
+
+/// For documentation about each function checkout [`Edges`](crate::provide::Edges) trait.
+impl<'a, W, E, G, NF, EF> Edges<W, E> for FilterView<'a, W, E, G, NF, EF>
+where
+    E: Edge<W>,
+    G: Vertices + Edges<W, E>,
+    NF: VertexFilter,
+    EF: EdgeFilter<W, E>,
+{
+    fn edges_from(&self, src_id: usize) -> Result<Vec<(usize, &E)>> {
+        if !self.is_visible(src_id) {
+            Err(Error::new_vnf(src_id))?
+        } else {
+            Ok(self.edges_from_unchecked(src_id))
+        }
+    }
+
+    fn edges_from_unchecked(&self, src_id: usize) -> Vec<(usize, &E)> {
+        self.graph
+            .edges_from_unchecked(src_id)
+            .into_iter()
+            .filter(|(dst_id, edge)| self.keeps(src_id, *dst_id, *edge))
+            .collect()
+    }
+
+    fn edges_between(&self, src_id: usize, dst_id: usize) -> Result<Vec<&E>> {
+        if !self.is_visible(src_id) {
+            Err(Error::new_vnf(src_id))?
+        } else if !self.is_visible(dst_id) {
+            Err(Error::new_vnf(dst_id))?
+        } else {
+            Ok(self.edges_between_unchecked(src_id, dst_id))
+        }
+    }
+
+    fn edges_between_unchecked(&self, src_id: usize, dst_id: usize) -> Vec<&E> {
+        self.graph
+            .edges_between_unchecked(src_id, dst_id)
+            .into_iter()
+            .filter(|edge| self.keeps(src_id, dst_id, *edge))
+            .collect()
+    }
+
+    fn edge_between(&self, src_id: usize, dst_id: usize, edge_id: usize) -> Result<&E> {
+        if !self.is_visible(src_id) {
+            Err(Error::new_vnf(src_id))?
+        } else if !self.is_visible(dst_id) {
+            Err(Error::new_vnf(dst_id))?
+        } else {
+            let edge = self.graph.edge_between(src_id, dst_id, edge_id)?;
+
+            if self.edge_filter.keep_edge(src_id, dst_id, edge) {
+                Ok(edge)
+            } else {
+                Err(Error::new_enf(edge_id))?
+            }
+        }
+    }
+
+    fn edge_between_unchecked(&self, src_id: usize, dst_id: usize, edge_id: usize) -> &E {
+        self.graph.edge_between_unchecked(src_id, dst_id, edge_id)
+    }
+
+    fn edge(&self, edge_id: usize) -> Result<&E> {
+        self.edges()
+            .into_iter()
+            .find(|(_, _, edge)| edge.get_id() == edge_id)
+            .map(|(_, _, edge)| edge)
+            .ok_or_else(|| Error::new_enf(edge_id).into())
+    }
+
+    fn edge_unchecked(&self, edge_id: usize) -> &E {
+        self.graph.edge_unchecked(edge_id)
+    }
+
+    fn has_any_edge(&self, src_id: usize, dst_id: usize) -> Result<bool> {
+        if !self.is_visible(src_id) {
+            Err(Error::new_vnf(src_id))?
+        } else if !self.is_visible(dst_id) {
+            Err(Error::new_vnf(dst_id))?
+        } else {
+            Ok(self.has_any_edge_unchecked(src_id, dst_id))
+        }
+    }
+
+    fn has_any_edge_unchecked(&self, src_id: usize, dst_id: usize) -> bool {
+        !self.edges_between_unchecked(src_id, dst_id).is_empty()
+    }
+
+    fn edges(&self) -> Vec<(usize, usize, &E)> {
+        self.graph
+            .edges()
+            .into_iter()
+            .filter(|(src_id, dst_id, edge)| self.keeps(*src_id, *dst_id, *edge))
+            .collect()
+    }
+
+    fn as_directed_edges(&self) -> Vec<(usize, usize, &E)> {
+        self.graph
+            .as_directed_edges()
+            .into_iter()
+            .filter(|(src_id, dst_id, edge)| self.keeps(*src_id, *dst_id, *edge))
+            .collect()
+    }
+
+    fn edges_count(&self) -> usize {
+        self.edges().len()
+    }
+
+    fn contains_edge(&self, edge_id: usize) -> bool {
+        self.edge(edge_id).is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::{BestFirst, DfsIter, Walker};
+    use crate::graph::{DefaultEdge, DirectedEdge, ListGraph, MatGraph};
+    use crate::provide::Graph;
+    use crate::storage::{DiList, Mat};
+
+    #[test]
+    fn closure_vertex_filter() {
+        // Given: Graph
+        //
+        //      a  --  b  --  c  --  d
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(c, d, 1.into());
+
+        // When: Hiding b.
+        let view = FilterView::vertices_only(&graph, |vertex_id| vertex_id != b);
+
+        // Then:
+        assert_eq!(view.vertices(), vec![a, c, d]);
+        assert!(!view.contains_vertex(b));
+        assert!(view.neighbors(b).is_err());
+        assert_eq!(view.neighbors_unchecked(a), vec![]);
+        assert_eq!(view.edges_count(), 1);
+        assert_eq!(view.degree(c), 1);
+        let reachable: Vec<_> = DfsIter::init(c).iter(&view).collect();
+        assert_eq!(reachable, vec![c, d]);
+    }
+
+    #[test]
+    fn set_edge_filter_on_directed_graph() {
+        // Given: Graph
+        //
+        //      a  -->  b  -->  c
+        //      |               ^
+        //      '---------------'
+        //
+        let mut graph = ListGraph::init(DiList::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let ab = graph.add_edge_unchecked(a, b, 1.into());
+        let bc = graph.add_edge_unchecked(b, c, 1.into());
+        let ac = graph.add_edge_unchecked(a, c, 5.into());
+
+        // When: Only keeping edges ab and ac.
+        let edge_ids: HashSet<usize> = [ab, ac].iter().copied().collect();
+        let view = FilterView::edges_only(&graph, edge_ids);
+
+        // Then:
+        assert_eq!(view.edges_count(), 2);
+        assert!(view.contains_edge(ab));
+        assert!(!view.contains_edge(bc));
+        assert!(view.edge(bc).is_err());
+        assert!(view.edge_between(b, c, bc).is_err());
+        assert!(!view.has_any_edge_unchecked(b, c));
+        assert_eq!(view.out_degree(a), 2);
+        assert_eq!(view.in_degree(c), 1);
+        assert_eq!(view.in_degree(b), 1);
+        assert_eq!(view.out_degree(b), 0);
+        let mut listener = ();
+        let mut best_first = BestFirst::init(&view, &mut listener);
+        best_first.execute(
+            &view,
+            a,
+            0,
+            |cost, edge| cost + edge.get_weight().unwrap(),
+            |_, cost| *cost,
+        );
+        assert_eq!(best_first.path_to(c), Some(vec![a, c]));
+    }
+
+    #[test]
+    fn vertex_and_edge_filters_combined() {
+        // Given: Graph
+        //
+        //      a  --1--  b  --9--  c
+        //      |         |
+        //      2         1
+        //      |         |
+        //      d  --1--  e
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 9.into());
+        graph.add_edge_unchecked(a, d, 2.into());
+        graph.add_edge_unchecked(b, e, 1.into());
+        graph.add_edge_unchecked(d, e, 1.into());
+
+        // When: Hiding a and every edge heavier than 5.
+        let view = FilterView::init(
+            &graph,
+            |vertex_id| vertex_id != a,
+            |_, _, edge: &DefaultEdge<usize>| *edge.get_weight() <= 5.into(),
+        );
+
+        // Then:
+        assert_eq!(view.vertex_count(), 4);
+        assert_eq!(view.edges_count(), 2);
+        assert_eq!(view.as_directed_edges().len(), 4);
+        assert_eq!(view.neighbors_unchecked(b), vec![e]);
+        assert_eq!(view.degree(c), 0);
+        for vertex_id in view.vertices() {
+            assert_eq!(view.in_degree(vertex_id), view.out_degree(vertex_id));
+        }
+    }
+
+    #[test]
+    fn degrees_match_unfiltered_graph() {
+        // Given: Directed graph.
+        let mut graph = ListGraph::<usize, DirectedEdge>::init(DiList::init());
+        let vertices: Vec<_> = (0..5).map(|_| graph.add_vertex()).collect();
+        for (src, dst) in [(0, 1), (0, 2), (1, 2), (2, 0), (3, 4), (4, 4)].iter() {
+            graph.add_edge_unchecked(vertices[*src], vertices[*dst], 1.into());
+        }
+
+        // When: Filtering nothing.
+        let view = FilterView::init(&graph, KeepAll, KeepAll);
+
+        // Then:
+        assert_eq!(view.edges_count(), graph.edges_count());
+        assert_eq!(view.in_degree(vertices[2]), 2);
+        assert_eq!(view.in_degree(vertices[4]), 2);
+        assert_eq!(view.out_degree(vertices[0]), 2);
+        for vertex_id in graph.vertices() {
+            assert_eq!(
+                view.neighbors_unchecked(vertex_id),
+                graph.neighbors_unchecked(vertex_id)
+            );
+        }
+    }
+}
@@ -0,0 +1,519 @@
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+use anyhow::Result;
+
+use crate::graph::{Edge, Error};
+use crate::provide::{Edges, Neighbors, Vertices};
+
+/// An immutable graph optimized for reads.
+///
+/// Freezing a graph takes ownership of it, so it can no longer get mutated, and builds a compressed sparse row index of its edges:
+/// Edges going out of each vertex are stored next to each other and sorted by their destination.
+/// So listing edges of a vertex needs no allocation in the graph, finding edges between two vertices is a binary search,
+/// and vertex and edge counts, degrees and lookups of edges by id take constant time.
+///
+/// Edges are copied into the index, so freezing needs `E: Clone` and roughly doubles the memory used by edges.
+/// Use [`thaw`](crate::graph::Frozen::thaw) to get the original graph back and mutate it again.
+///
+/// ## Generic Parameters
+/// * `W`: **W**eight type associated with edges.
+/// * `E`: **E**dge type that graph uses.
+/// * `G`: **G**raph to freeze.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::DiList;
+/// use prepona::graph::{freeze, ListGraph};
+///
+/// // Given: Graph
+/// //
+/// //      a  -->  b  -->  c
+/// //      |               ^
+/// //      '---------------'
+/// //
+/// let mut graph = ListGraph::init(DiList::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(a, c, 1.into());
+///
+/// // When:
+/// let frozen = freeze(graph);
+///
+/// // Then:
+/// assert_eq!(frozen.out_degree(a), 2);
+/// assert_eq!(frozen.in_degree(c), 2);
+/// assert!(frozen.has_any_edge_unchecked(b, c));
+///
+/// // When: Mutating the graph again.
+/// let mut graph = frozen.thaw();
+/// graph.add_edge_unchecked(c, a, 1.into());
+///
+/// // Then:
+/// assert_eq!(graph.edges_count(), 4);
+/// ```
+pub struct Frozen<W, E, G> {
+    graph: G,
+
+    vertices: Vec<usize>,
+    // Virtual id of each real id, or None if real id is not a vertex.
+    virt_ids: Vec<Option<usize>>,
+
+    // Edges going out of virtual id i are successors[offsets[i]..offsets[i + 1]], sorted by destination.
+    offsets: Vec<usize>,
+    successors: Vec<(usize, E)>,
+
+    // Neighbors of virtual id i are neighbors[neighbor_offsets[i]..neighbor_offsets[i + 1]].
+    neighbor_offsets: Vec<usize>,
+    neighbors: Vec<usize>,
+
+    in_degrees: Vec<usize>,
+
+    // Slot of each edge id in successors.
+    slots: HashMap<usize, usize>,
+
+    // (src_id, dst_id, slot) in the order the graph reports them.
+    edges: Vec<(usize, usize, usize)>,
+    directed_edges: Vec<(usize, usize, usize)>,
+
+    phantom_w: PhantomData<W>,
+}
+
+impl<W, E, G> Frozen<W, E, G>
+where
+    E: Edge<W> + Clone,
+    G: Vertices + Neighbors + Edges<W, E>,
+{
+    /// # Arguments
+    /// `graph`: Graph to freeze.
+    ///
+    /// # Returns
+    /// Frozen graph that owns `graph`.
+    ///
+    /// # Complexity
+    /// O(|V| + |E|log(|E|))
+    pub fn init(graph: G) -> Self {
+        let vertices = graph.vertices();
+
+        let mut virt_ids = vec![None; vertices.iter().max().map_or(0, |max_id| max_id + 1)];
+        for (virt_id, real_id) in vertices.iter().enumerate() {
+            virt_ids[*real_id] = Some(virt_id);
+        }
+
+        let mut offsets = Vec::with_capacity(vertices.len() + 1);
+        let mut successors = vec![];
+        let mut neighbor_offsets = Vec::with_capacity(vertices.len() + 1);
+        let mut neighbors = vec![];
+        let mut in_degrees = vec![0; vertices.len()];
+        let mut slots = HashMap::new();
+        offsets.push(0);
+        neighbor_offsets.push(0);
+        for src_id in vertices.iter().copied() {
+            let mut edges: Vec<(usize, E)> = graph
+                .edges_from_unchecked(src_id)
+                .into_iter()
+                .map(|(dst_id, edge)| (dst_id, edge.clone()))
+                .collect();
+            edges.sort_by_key(|(dst_id, edge)| (*dst_id, edge.get_id()));
+
+            for (dst_id, edge) in edges {
+                in_degrees[virt_ids[dst_id].unwrap()] += 1;
+                slots.entry(edge.get_id()).or_insert(successors.len());
+                successors.push((dst_id, edge));
+            }
+            offsets.push(successors.len());
+
+            neighbors.extend(graph.neighbors_unchecked(src_id));
+            neighbor_offsets.push(neighbors.len());
+        }
+
+        let to_slots = |edges: Vec<(usize, usize, &E)>| {
+            edges
+                .into_iter()
+                .map(|(src_id, dst_id, edge)| (src_id, dst_id, slots[&edge.get_id()]))
+                .collect()
+        };
+        let edges = to_slots(graph.edges());
+        let directed_edges = to_slots(graph.as_directed_edges());
+
+        Frozen {
+            graph,
+
+            vertices,
+            virt_ids,
+
+            offsets,
+            successors,
+
+            neighbor_offsets,
+            neighbors,
+
+            in_degrees,
+
+            slots,
+
+            edges,
+            directed_edges,
+
+            phantom_w: PhantomData,
+        }
+    }
+
+    /// # Returns
+    /// The frozen graph, so it can get mutated again.
+    pub fn thaw(self) -> G {
+        self.graph
+    }
+
+    /// # Returns
+    /// The frozen graph.
+    pub fn graph(&self) -> &G {
+        &self.graph
+    }
+
+    fn virt_id_of(&self, vertex_id: usize) -> Option<usize> {
+        self.virt_ids.get(vertex_id).copied().flatten()
+    }
+
+    fn successors_of(&self, src_id: usize) -> &[(usize, E)] {
+        let virt_id = self.virt_id_of(src_id).unwrap();
+
+        &self.successors[self.offsets[virt_id]..self.offsets[virt_id + 1]]
+    }
+
+    // Edges from `src_id` to `dst_id`, which are next to each other since successors are sorted by destination.
+    fn successors_between(&self, src_id: usize, dst_id: usize) -> &[(usize, E)] {
+        let successors = self.successors_of(src_id);
+
+        let start = successors.partition_point(|(id, _)| *id < dst_id);
+        let end = successors.partition_point(|(id, _)| *id <= dst_id);
+
+        &successors[start..end]
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// Number of edges going out of the vertex.
+    /// For undirected graphs it's the same as [`degree`](crate::graph::Frozen::degree).
+    ///
+    /// # Complexity
+    /// O(1)
+    ///
+    /// # Panics
+    /// If there is no vertex with id: `vertex_id`.
+    pub fn out_degree(&self, vertex_id: usize) -> usize {
+        self.successors_of(vertex_id).len()
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// Number of edges coming into the vertex.
+    /// For undirected graphs it's the same as [`degree`](crate::graph::Frozen::degree).
+    ///
+    /// # Complexity
+    /// O(1)
+    ///
+    /// # Panics
+    /// If there is no vertex with id: `vertex_id`.
+    pub fn in_degree(&self, vertex_id: usize) -> usize {
+        self.in_degrees[self.virt_id_of(vertex_id).unwrap()]
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// Number of edges incident to the vertex.
+    /// For directed graphs it's the same as [`out_degree`](crate::graph::Frozen::out_degree).
+    ///
+    /// # Complexity
+    /// O(1)
+    ///
+    /// # Panics
+    /// If there is no vertex with id: `vertex_id`.
+    pub fn degree(&self, vertex_id: usize) -> usize {
+        self.out_degree(vertex_id)
+    }
+}
+
+/// Freezes `graph`.
+///
+/// For more info checkout [`Frozen`](crate::graph::Frozen).
+///
+/// # Arguments
+/// `graph`: Graph to freeze.
+///
+/// # Returns
+/// Frozen graph that owns `graph`.
+pub fn freeze<W, E, G>(graph: G) -> Frozen<W, E, G>
+where
+    E: Edge<W> + Clone,
+    G: Vertices + Neighbors + Edges<W, E>,
+{
+    Frozen::init(graph)
+}
+
+/// For documentation about each function checkout [`Neighbors`](crate::provide::Neighbors) trait.
+///
+/// Neighbors are reported in the same order as the frozen graph reports them.
+impl<W, E, G> Neighbors for Frozen<W, E, G>
+where
+    E: Edge<W> + Clone,
+    G: Vertices + Neighbors + Edges<W, E>,
+{
+    fn neighbors(&self, src_id: usize) -> Result<Vec<usize>> {
+        if !self.contains_vertex(src_id) {
+            Err(Error::new_vnf(src_id))?
+        } else {
+            Ok(self.neighbors_unchecked(src_id))
+        }
+    }
+
+    fn neighbors_unchecked(&self, src_id: usize) -> Vec<usize> {
+        let virt_id = self.virt_id_of(src_id).unwrap();
+
+        self.neighbors[self.neighbor_offsets[virt_id]..self.neighbor_offsets[virt_id + 1]].to_vec()
+    }
+}
+
+/// For documentation about each function checkout [`Vertices`](crate::provide::Vertices) trait.
+impl<W, E, G> Vertices for Frozen<W, E, G>
+where
+    E: Edge<W> + Clone,
+    G: Vertices + Neighbors + Edges<W, E>,
+{
+    fn vertices(&self) -> Vec<usize> {
+        self.vertices.clone()
+    }
+
+    fn vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+
+    fn contains_vertex(&self, vertex_id: usize) -> bool {
+        self.virt_id_of(vertex_id).is_some()
+    }
+}
+
+/// For documentation about each function checkout [`Edges`](crate::provide::Edges) trait.
+///
+/// Edges going out of a vertex are reported sorted by their destination.
+impl<W, E, G> Edges<W, E> for Frozen<W, E, G>
+where
+    E: Edge<W> + Clone,
+    G: Vertices + Neighbors + Edges<W, E>,
+{
+    fn edges_from(&self, src_id: usize) -> Result<Vec<(usize, &E)>> {
+        if !self.contains_vertex(src_id) {
+            Err(Error::new_vnf(src_id))?
+        } else {
+            Ok(self.edges_from_unchecked(src_id))
+        }
+    }
+
+    fn edges_from_unchecked(&self, src_id: usize) -> Vec<(usize, &E)> {
+        self.successors_of(src_id)
+            .iter()
+            .map(|(dst_id, edge)| (*dst_id, edge))
+            .collect()
+    }
+
+    fn edges_between(&self, src_id: usize, dst_id: usize) -> Result<Vec<&E>> {
+        if !self.contains_vertex(src_id) {
+            Err(Error::new_vnf(src_id))?
+        } else if !self.contains_vertex(dst_id) {
+            Err(Error::new_vnf(dst_id))?
+        } else {
+            Ok(self.edges_between_unchecked(src_id, dst_id))
+        }
+    }
+
+    fn edges_between_unchecked(&self, src_id: usize, dst_id: usize) -> Vec<&E> {
+        self.successors_between(src_id, dst_id)
+            .iter()
+            .map(|(_, edge)| edge)
+            .collect()
+    }
+
+    fn edge_between(&self, src_id: usize, dst_id: usize, edge_id: usize) -> Result<&E> {
+        if !self.contains_vertex(src_id) {
+            Err(Error::new_vnf(src_id))?
+        } else if !self.contains_vertex(dst_id) {
+            Err(Error::new_vnf(dst_id))?
+        } else {
+            self.successors_between(src_id, dst_id)
+                .iter()
+                .find(|(_, edge)| edge.get_id() == edge_id)
+                .map(|(_, edge)| edge)
+                .ok_or_else(|| Error::new_enf(edge_id).into())
+        }
+    }
+
+    fn edge_between_unchecked(&self, src_id: usize, dst_id: usize, edge_id: usize) -> &E {
+        self.edge_between(src_id, dst_id, edge_id).unwrap()
+    }
+
+    fn edge(&self, edge_id: usize) -> Result<&E> {
+        self.slots
+            .get(&edge_id)
+            .map(|slot| &self.successors[*slot].1)
+            .ok_or_else(|| Error::new_enf(edge_id).into())
+    }
+
+    fn edge_unchecked(&self, edge_id: usize) -> &E {
+        &self.successors[self.slots[&edge_id]].1
+    }
+
+    fn has_any_edge(&self, src_id: usize, dst_id: usize) -> Result<bool> {
+        if !self.contains_vertex(src_id) {
+            Err(Error::new_vnf(src_id))?
+        } else if !self.contains_vertex(dst_id) {
+            Err(Error::new_vnf(dst_id))?
+        } else {
+            Ok(self.has_any_edge_unchecked(src_id, dst_id))
+        }
+    }
+
+    fn has_any_edge_unchecked(&self, src_id: usize, dst_id: usize) -> bool {
+        !self.successors_between(src_id, dst_id).is_empty()
+    }
+
+    fn edges(&self) -> Vec<(usize, usize, &E)> {
+        self.edges
+            .iter()
+            .map(|(src_id, dst_id, slot)| (*src_id, *dst_id, &self.successors[*slot].1))
+            .collect()
+    }
+
+    fn as_directed_edges(&self) -> Vec<(usize, usize, &E)> {
+        self.directed_edges
+            .iter()
+            .map(|(src_id, dst_id, slot)| (*src_id, *dst_id, &self.successors[*slot].1))
+            .collect()
+    }
+
+    fn edges_count(&self) -> usize {
+        self.slots.len()
+    }
+
+    fn contains_edge(&self, edge_id: usize) -> bool {
+        self.slots.contains_key(&edge_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    use crate::algo::BitAdj;
+    use crate::graph::{MatGraph, MultiListGraph};
+    use crate::provide::Graph;
+    use crate::storage::{List, Mat};
+
+    #[test]
+    fn reads_match_the_graph() {
+        // Given: Undirected multigraph with a removed vertex, parallel edges and a loop.
+        let mut graph = MultiListGraph::init(List::<usize>::init());
+        let vertices: Vec<_> = (0..5).map(|_| graph.add_vertex()).collect();
+        for (src, dst) in [(0, 1), (0, 1), (1, 2), (2, 2), (3, 0), (4, 1)].iter() {
+            graph.add_edge_unchecked(vertices[*src], vertices[*dst], 1.into());
+        }
+        graph.remove_vertex_unchecked(vertices[4]);
+        let expected_vertices = graph.vertices();
+        let expected_edges: HashSet<_> = graph
+            .edges()
+            .into_iter()
+            .map(|(src_id, dst_id, edge)| (src_id, dst_id, edge.get_id()))
+            .collect();
+
+        // When:
+        let frozen = freeze(graph);
+
+        // Then:
+        assert_eq!(frozen.vertices(), expected_vertices);
+        assert!(!frozen.contains_vertex(vertices[4]));
+        assert_eq!(frozen.edges_count(), frozen.graph().edges_count());
+        assert_eq!(
+            frozen
+                .edges()
+                .into_iter()
+                .map(|(src_id, dst_id, edge)| (src_id, dst_id, edge.get_id()))
+                .collect::<HashSet<_>>(),
+            expected_edges
+        );
+        for src_id in frozen.vertices() {
+            assert_eq!(
+                frozen.neighbors_unchecked(src_id),
+                frozen.graph().neighbors_unchecked(src_id)
+            );
+            assert_eq!(
+                frozen.out_degree(src_id),
+                frozen.graph().edges_from_unchecked(src_id).len()
+            );
+            for dst_id in frozen.vertices() {
+                let mut ids: Vec<_> = frozen
+                    .graph()
+                    .edges_between_unchecked(src_id, dst_id)
+                    .into_iter()
+                    .map(|edge| edge.get_id())
+                    .collect();
+                ids.sort_unstable();
+
+                let frozen_ids: Vec<_> = frozen
+                    .edges_between_unchecked(src_id, dst_id)
+                    .into_iter()
+                    .map(|edge| edge.get_id())
+                    .collect();
+                assert_eq!(frozen_ids, ids);
+                for edge_id in ids {
+                    assert!(frozen.edge_between(src_id, dst_id, edge_id).is_ok());
+                    assert_eq!(frozen.edge_unchecked(edge_id).get_id(), edge_id);
+                }
+            }
+        }
+        assert_eq!(
+            frozen
+                .edges_between_unchecked(vertices[0], vertices[1])
+                .len(),
+            2
+        );
+        assert!(frozen.edges_from(vertices[4]).is_err());
+        assert!(frozen.edge(100).is_err());
+    }
+
+    #[test]
+    fn algorithms_run_on_frozen_graph() {
+        // Given: Graph
+        //
+        //      a  --1--  b  --1--  c
+        //      |                   |
+        //      '---------5---------'
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(a, c, 5.into());
+        let frozen = freeze(graph);
+
+        // When:
+        let triangle_count = BitAdj::init(&frozen).triangle_count();
+
+        // Then:
+        assert_eq!(triangle_count, 1);
+        assert_eq!(frozen.in_degree(c), 2);
+        assert_eq!(frozen.degree(b), 2);
+        assert_eq!(frozen.thaw().edges_count(), 3);
+    }
+}
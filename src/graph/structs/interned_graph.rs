@@ -0,0 +1,325 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+use anyhow::Result;
+
+use crate::graph::{error::Error, Edge, EdgeDir};
+use crate::provide::{Edges, Graph, Neighbors, Vertices};
+
+/// Wraps a graph so vertices can be identified by external keys(like 64 bit ids of a database or strings) instead of vertex ids.
+///
+/// Each key is interned into a vertex id of the inner graph the first time it's seen.
+/// All key based functions translate keys to ids and forward the call to the inner graph, so the inner graph still decides what is allowed(like loops or multiple edges).
+/// To run algorithms, use [`graph`](crate::graph::InternedGraph::graph) to access the inner graph and [`key_of`](crate::graph::InternedGraph::key_of) to translate the results back.
+///
+/// ## Generic Parameters
+/// * `K`: **K**ey type that identifies vertices.
+/// * `W`: **W**eight type associated with edges.
+/// * `E`: **E**dge type that graph uses.
+/// * `Dir`: **Dir**ection of edges: [`Directed`](crate::graph::DirectedEdge) or [`Undirected`](crate::graph::UndirectedEdge).
+/// * `G`: **G**raph type that is being wrapped.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::DiList;
+/// use prepona::graph::{ListGraph, InternedGraph};
+///
+/// let mut graph = InternedGraph::init(ListGraph::init(DiList::<usize>::init()));
+///
+/// graph.add_edge("alice", "bob", 1.into()).unwrap();
+/// graph.add_edge("bob", "carol", 2.into()).unwrap();
+///
+/// assert_eq!(graph.neighbors(&"bob").unwrap(), vec![&"carol"]);
+/// assert!(graph.has_any_edge(&"alice", &"bob").unwrap());
+/// assert_eq!(graph.graph().vertex_count(), 3);
+/// ```
+pub struct InternedGraph<K, W, E, Dir, G>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir>,
+{
+    graph: G,
+
+    id_of: HashMap<K, usize>,
+    key_of: HashMap<usize, K>,
+
+    phantom_w: PhantomData<W>,
+    phantom_e: PhantomData<E>,
+    phantom_dir: PhantomData<Dir>,
+}
+
+impl<K, W, E, Dir, G> InternedGraph<K, W, E, Dir, G>
+where
+    K: Hash + Eq + Clone + Debug,
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Vertices + Neighbors + Edges<W, E>,
+{
+    /// # Arguments
+    /// `graph`: Graph to wrap. Vertices that are already in the graph have no keys, so it's best to pass an empty graph.
+    ///
+    /// # Returns
+    /// An interned graph that wraps `graph`.
+    pub fn init(graph: G) -> Self {
+        InternedGraph {
+            graph,
+
+            id_of: HashMap::new(),
+            key_of: HashMap::new(),
+
+            phantom_w: PhantomData,
+            phantom_e: PhantomData,
+            phantom_dir: PhantomData,
+        }
+    }
+
+    /// # Returns
+    /// The inner graph.
+    pub fn graph(&self) -> &G {
+        &self.graph
+    }
+
+    /// # Returns
+    /// The inner graph and the map from keys to vertex ids.
+    pub fn into_inner(self) -> (G, HashMap<K, usize>) {
+        (self.graph, self.id_of)
+    }
+
+    /// # Arguments
+    /// `key`: Key of the vertex.
+    ///
+    /// # Returns
+    /// * `Some`: Containing the id of the vertex associated with `key`.
+    /// * `None`: If no vertex is associated with `key`.
+    pub fn id_of(&self, key: &K) -> Option<usize> {
+        self.id_of.get(key).copied()
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// * `Some`: Containing the key associated with the vertex.
+    /// * `None`: If vertex has no key.
+    pub fn key_of(&self, vertex_id: usize) -> Option<&K> {
+        self.key_of.get(&vertex_id)
+    }
+
+    /// # Arguments
+    /// `key`: Key of the vertex.
+    ///
+    /// # Returns
+    /// `true` if a vertex is associated with `key`.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.id_of.contains_key(key)
+    }
+
+    fn checked_id_of(&self, key: &K) -> Result<usize> {
+        match self.id_of(key) {
+            Some(vertex_id) => Ok(vertex_id),
+            None => Err(Error::new_knf(&format!("{:?}", key)))?,
+        }
+    }
+
+    /// Adds a vertex associated with `key` if there is none.
+    ///
+    /// # Arguments
+    /// `key`: Key of the vertex.
+    ///
+    /// # Returns
+    /// Id of the vertex associated with `key`.
+    pub fn add_vertex(&mut self, key: K) -> usize {
+        if let Some(vertex_id) = self.id_of(&key) {
+            vertex_id
+        } else {
+            let vertex_id = self.graph.add_vertex();
+
+            self.id_of.insert(key.clone(), vertex_id);
+            self.key_of.insert(vertex_id, key);
+
+            vertex_id
+        }
+    }
+
+    /// Removes the vertex associated with `key`.
+    ///
+    /// # Arguments
+    /// `key`: Key of the vertex.
+    ///
+    /// # Returns
+    /// * `Err`: If no vertex is associated with `key` or the inner graph fails to remove the vertex.
+    /// * `Ok`: If removal was successful.
+    pub fn remove_vertex(&mut self, key: &K) -> Result<()> {
+        let vertex_id = self.checked_id_of(key)?;
+
+        self.graph.remove_vertex(vertex_id)?;
+
+        self.id_of.remove(key);
+        self.key_of.remove(&vertex_id);
+
+        Ok(())
+    }
+
+    /// Adds an edge from the vertex associated with `src` to the vertex associated with `dst`.
+    /// Vertices are added if they are not already in the graph.
+    ///
+    /// # Arguments
+    /// * `src`: Key of the source vertex.
+    /// * `dst`: Key of the destination vertex.
+    /// * `edge`: Edge to add.
+    ///
+    /// # Returns
+    /// * `Err`: If the inner graph fails to add the edge.
+    /// * `Ok`: Containing the id of the added edge.
+    pub fn add_edge(&mut self, src: K, dst: K, edge: E) -> Result<usize> {
+        let src_id = self.add_vertex(src);
+        let dst_id = self.add_vertex(dst);
+
+        self.graph.add_edge(src_id, dst_id, edge)
+    }
+
+    /// Removes an edge between the vertices associated with `src` and `dst`.
+    ///
+    /// # Arguments
+    /// * `src`: Key of the source vertex.
+    /// * `dst`: Key of the destination vertex.
+    /// * `edge_id`: Id of the edge to remove.
+    ///
+    /// # Returns
+    /// * `Err`: If any of the keys is unknown or the inner graph fails to remove the edge.
+    /// * `Ok`: Containing the removed edge.
+    pub fn remove_edge(&mut self, src: &K, dst: &K, edge_id: usize) -> Result<E> {
+        let src_id = self.checked_id_of(src)?;
+        let dst_id = self.checked_id_of(dst)?;
+
+        self.graph.remove_edge(src_id, dst_id, edge_id)
+    }
+
+    /// # Arguments
+    /// `key`: Key of the vertex.
+    ///
+    /// # Returns
+    /// * `Err`: If `key` is unknown.
+    /// * `Ok`: Containing keys of the neighbors of the vertex.
+    pub fn neighbors(&self, key: &K) -> Result<Vec<&K>> {
+        let vertex_id = self.checked_id_of(key)?;
+
+        Ok(self
+            .graph
+            .neighbors(vertex_id)?
+            .into_iter()
+            .filter_map(|n_id| self.key_of(n_id))
+            .collect())
+    }
+
+    /// # Arguments
+    /// * `src`: Key of the source vertex.
+    /// * `dst`: Key of the destination vertex.
+    ///
+    /// # Returns
+    /// * `Err`: If any of the keys is unknown.
+    /// * `Ok`: Containing edges from the vertex associated with `src` to the vertex associated with `dst`.
+    pub fn edges_between(&self, src: &K, dst: &K) -> Result<Vec<&E>> {
+        let src_id = self.checked_id_of(src)?;
+        let dst_id = self.checked_id_of(dst)?;
+
+        self.graph.edges_between(src_id, dst_id)
+    }
+
+    /// # Arguments
+    /// * `src`: Key of the source vertex.
+    /// * `dst`: Key of the destination vertex.
+    ///
+    /// # Returns
+    /// * `Err`: If any of the keys is unknown.
+    /// * `Ok`: Containing `true` if there is any edge from the vertex associated with `src` to the vertex associated with `dst`.
+    pub fn has_any_edge(&self, src: &K, dst: &K) -> Result<bool> {
+        let src_id = self.checked_id_of(src)?;
+        let dst_id = self.checked_id_of(dst)?;
+
+        self.graph.has_any_edge(src_id, dst_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{ListGraph, MatGraph};
+    use crate::storage::{DiList, Mat};
+
+    #[test]
+    fn interning_keys() {
+        // Given: Empty interned graph.
+        let mut graph = InternedGraph::init(MatGraph::init(Mat::<usize>::init()));
+
+        // When: Adding the same key twice.
+        let a_id1 = graph.add_vertex(10_u64);
+        let a_id2 = graph.add_vertex(10_u64);
+        let b_id = graph.add_vertex(20_u64);
+
+        // Then:
+        assert_eq!(a_id1, a_id2);
+        assert_ne!(a_id1, b_id);
+        assert_eq!(graph.graph().vertex_count(), 2);
+        assert_eq!(graph.id_of(&10), Some(a_id1));
+        assert_eq!(graph.key_of(b_id), Some(&20));
+        assert!(!graph.contains_key(&30));
+    }
+
+    #[test]
+    fn unknown_keys() {
+        // Given: Interned graph with one vertex.
+        let mut graph = InternedGraph::init(MatGraph::init(Mat::<usize>::init()));
+        graph.add_vertex("a".to_string());
+
+        // Then:
+        let b = "b".to_string();
+        assert!(graph.neighbors(&b).is_err());
+        assert!(graph.has_any_edge(&"a".to_string(), &b).is_err());
+        assert!(graph.remove_vertex(&b).is_err());
+    }
+
+    #[test]
+    fn edges_by_key() {
+        // Given: Interned directed graph.
+        let mut graph = InternedGraph::init(ListGraph::init(DiList::<usize>::init()));
+
+        // When: Adding edges by key.
+        let ab_id = graph.add_edge("a", "b", 1.into()).unwrap();
+        graph.add_edge("a", "c", 2.into()).unwrap();
+
+        // Then:
+        let mut neighbors = graph.neighbors(&"a").unwrap();
+        neighbors.sort();
+        assert_eq!(neighbors, vec![&"b", &"c"]);
+        assert!(graph.neighbors(&"b").unwrap().is_empty());
+        assert_eq!(
+            graph.edges_between(&"a", &"c").unwrap()[0].get_weight(),
+            &2.into()
+        );
+
+        // When: Removing an edge and a vertex.
+        graph.remove_edge(&"a", &"b", ab_id).unwrap();
+        graph.remove_vertex(&"c").unwrap();
+
+        // Then:
+        assert!(!graph.has_any_edge(&"a", &"b").unwrap());
+        assert!(!graph.contains_key(&"c"));
+        assert!(graph.neighbors(&"a").unwrap().is_empty());
+    }
+
+    #[test]
+    fn inner_graph_rules_apply() {
+        // Given: Interned simple graph.
+        let mut graph = InternedGraph::init(MatGraph::init(Mat::<usize>::init()));
+
+        // Then: Loops and multiple edges are rejected by the inner graph.
+        assert!(graph.add_edge('a', 'a', 1.into()).is_err());
+        assert!(graph.add_edge('a', 'b', 1.into()).is_ok());
+        assert!(graph.add_edge('b', 'a', 1.into()).is_err());
+    }
+}
@@ -0,0 +1,214 @@
+use std::collections::{HashSet, VecDeque};
+
+use anyhow::Result;
+
+use crate::graph::Error;
+use crate::provide::{Neighbors, Vertices};
+
+/// A read only view of the `k`-th power of a graph, in which a vertex is a neighbor of another if it can be reached in at most `k` hops.
+///
+/// Nothing is precomputed: Each neighbor query runs a breadth-first search that stops after `k` hops,
+/// so the view is cheap to create and suits occasional queries like "friends of friends" in a large social network.
+/// Use [`graph_power`](crate::algo::graph_power) to materialize the edges instead when every vertex is going to be queried.
+///
+/// For directed graphs only paths that follow direction of the edges are considered.
+///
+/// ## Generic Parameters
+/// * `G`: **G**raph to view.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::List;
+/// use prepona::graph::{KHopView, ListGraph};
+///
+/// // Given: Graph
+/// //
+/// //      a  --  b  --  c  --  d
+/// //
+/// let mut graph = ListGraph::init(List::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(c, d, 1.into());
+///
+/// // When: Looking for friends of friends.
+/// let view = KHopView::init(&graph, 2);
+///
+/// // Then:
+/// assert_eq!(view.neighbors_unchecked(a), vec![b, c]);
+/// assert_eq!(view.hops_between(a, c), Some(2));
+/// assert_eq!(view.hops_between(a, d), None);
+/// ```
+pub struct KHopView<'a, G> {
+    graph: &'a G,
+    k: usize,
+}
+
+impl<'a, G> KHopView<'a, G>
+where
+    G: Vertices + Neighbors,
+{
+    /// # Arguments
+    /// * `graph`: Graph to view.
+    /// * `k`: Maximum number of hops between neighbors.
+    ///
+    /// # Returns
+    /// View of the `k`-th power of `graph`.
+    pub fn init(graph: &'a G, k: usize) -> Self {
+        KHopView { graph, k }
+    }
+
+    /// # Returns
+    /// The viewed graph.
+    pub fn graph(&self) -> &'a G {
+        self.graph
+    }
+
+    /// # Returns
+    /// Maximum number of hops between neighbors.
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// # Arguments
+    /// `src_id`: Id of the source vertex.
+    ///
+    /// # Returns
+    /// Vertices reachable from the source in at most `k` hops, other than the source itself, in the format of (`vertex_id`, `hops`).
+    /// Vertices are sorted by their number of hops, in the order breadth-first search visits them.
+    ///
+    /// # Complexity
+    /// O(|V<sub>k</sub>| + |E<sub>k</sub>|) where V<sub>k</sub> and E<sub>k</sub> are the vertices and edges within `k` hops of the source.
+    ///
+    /// # Panics
+    /// If there is no vertex with id: `src_id`.
+    pub fn hops_from(&self, src_id: usize) -> Vec<(usize, usize)> {
+        let mut visited = HashSet::new();
+        visited.insert(src_id);
+
+        let mut reached = vec![];
+        let mut queue = VecDeque::new();
+        queue.push_back((src_id, 0));
+        while let Some((vertex_id, hops)) = queue.pop_front() {
+            if hops == self.k {
+                continue;
+            }
+
+            for dst_id in self.graph.neighbors_unchecked(vertex_id) {
+                if visited.insert(dst_id) {
+                    reached.push((dst_id, hops + 1));
+                    queue.push_back((dst_id, hops + 1));
+                }
+            }
+        }
+
+        reached
+    }
+
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    ///
+    /// # Returns
+    /// * `Some`: Containing the minimum number of hops from source to destination, if it's at most `k`.
+    /// * `None`: Otherwise.
+    ///
+    /// # Panics
+    /// If there is no vertex with id: `src_id`.
+    pub fn hops_between(&self, src_id: usize, dst_id: usize) -> Option<usize> {
+        if src_id == dst_id {
+            return Some(0);
+        }
+
+        self.hops_from(src_id)
+            .into_iter()
+            .find(|(vertex_id, _)| *vertex_id == dst_id)
+            .map(|(_, hops)| hops)
+    }
+}
+
+/// For documentation about each function checkout [`Neighbors`](crate::provide::Neighbors) trait.
+///
+/// Neighbors are the vertices within `k` hops, sorted by their number of hops. A vertex is never its own neighbor.
+impl<'a, G> Neighbors for KHopView<'a, G>
+where
+    G: Vertices + Neighbors,
+{
+    fn neighbors(&self, src_id: usize) -> Result<Vec<usize>> {
+        if !self.graph.contains_vertex(src_id) {
+            Err(Error::new_vnf(src_id))?
+        } else {
+            Ok(self.neighbors_unchecked(src_id))
+        }
+    }
+
+    fn neighbors_unchecked(&self, src_id: usize) -> Vec<usize> {
+        self.hops_from(src_id)
+            .into_iter()
+            .map(|(vertex_id, _)| vertex_id)
+            .collect()
+    }
+}
+
+/// For documentation about each function checkout [`Vertices`](crate::provide::Vertices) trait.
+impl<'a, G> Vertices for KHopView<'a, G>
+where
+    G: Vertices + Neighbors,
+{
+    fn vertices(&self) -> Vec<usize> {
+        self.graph.vertices()
+    }
+
+    fn vertex_count(&self) -> usize {
+        self.graph.vertex_count()
+    }
+
+    fn contains_vertex(&self, vertex_id: usize) -> bool {
+        self.graph.contains_vertex(vertex_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::ListGraph;
+    use crate::provide::Graph;
+    use crate::storage::DiList;
+
+    #[test]
+    fn directed_cycle() {
+        // Given: Graph
+        //
+        //      a  -->  b  -->  c  -->  d
+        //      ^                       |
+        //      '-----------------------'
+        //
+        let mut graph = ListGraph::init(DiList::<usize>::init());
+        let vertices: Vec<_> = (0..4).map(|_| graph.add_vertex()).collect();
+        for index in 0..4 {
+            graph.add_edge_unchecked(vertices[index], vertices[(index + 1) % 4], 1.into());
+        }
+
+        // When:
+        let view = KHopView::init(&graph, 2);
+
+        // Then:
+        assert_eq!(
+            view.hops_from(vertices[2]),
+            vec![(vertices[3], 1), (vertices[0], 2)]
+        );
+        assert_eq!(view.hops_between(vertices[1], vertices[0]), None);
+        assert_eq!(
+            KHopView::init(&graph, 10).neighbors_unchecked(vertices[0]),
+            vec![vertices[1], vertices[2], vertices[3]]
+        );
+        assert!(KHopView::init(&graph, 0)
+            .neighbors_unchecked(vertices[0])
+            .is_empty());
+        assert!(view.neighbors(10).is_err());
+    }
+}
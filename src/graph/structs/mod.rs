@@ -1,3 +1,29 @@
+mod cow_graph;
+mod filter_view;
+mod frozen;
+mod interned_graph;
+mod khop_view;
+mod multi_graph;
+mod prop_graph;
+mod relabel_view;
+mod reverse_view;
 mod simple_graph;
+mod sync_view;
+mod temporal_graph;
+mod tree;
+mod weighted_view;
 
+pub use cow_graph::CowGraph;
+pub use filter_view::{EdgeFilter, FilterView, KeepAll, VertexFilter};
+pub use frozen::{freeze, Frozen};
+pub use interned_graph::InternedGraph;
+pub use khop_view::KHopView;
+pub use multi_graph::{MultiGraph, MultiListGraph, MultiMatGraph};
+pub use prop_graph::PropGraph;
+pub use relabel_view::RelabelView;
+pub use reverse_view::ReverseView;
 pub use simple_graph::{FlowListGraph, FlowMatGraph, ListGraph, MatGraph, SimpleGraph};
+pub use sync_view::SyncView;
+pub use temporal_graph::{SnapshotFilter, TemporalGraph, TimeInterval};
+pub use tree::{EulerTour, Tree};
+pub use weighted_view::WeightedView;
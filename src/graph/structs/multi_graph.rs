@@ -0,0 +1,344 @@
+use std::any::Any;
+use std::cell::RefCell;
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+use anyhow::Result;
+use provide::{Direction, Edges, Graph, Neighbors, Vertices};
+
+use crate::graph::{DefaultEdge, Edge, EdgeDir};
+use crate::provide;
+use crate::storage::{GraphObserver, GraphStorage, List, Mat, ObservedStorage};
+
+/// A `MultiGraph` that uses [`Mat`](crate::storage::Mat) as its storage.
+pub type MultiMatGraph<W, Dir> = MultiGraph<W, DefaultEdge<W>, Dir, Mat<W, Dir>>;
+
+/// A `MultiGraph` that uses [`List`](crate::storage::List) as its storage.
+pub type MultiListGraph<W, Dir> = MultiGraph<W, DefaultEdge<W>, Dir, List<W, Dir>>;
+
+/// Representing a graph that allows multiple(parallel) edges between two vertices and loops.
+///
+/// Parallel edges are distinguished by their ids, which are stable until the edge is removed.
+/// Use [`edges_between`](crate::provide::Edges::edges_between) to iterate all edges between two vertices
+/// and [`edge_between`](crate::provide::Edges::edge_between) to access one of them by id.
+///
+/// ## Note
+/// `MultiGraph` forwards all of its function calls to its underlying storage. So the complexities of its functions are dependent to what storage you use to initialize the graph.
+///
+/// ## Generic Parameters
+/// * `W`: **W**eight type associated with edges.
+/// * `E`: **E**dge type that graph uses.
+/// * `Dir`: **Dir**ection of edges: [`Directed`](crate::graph::DirectedEdge) or [`Undirected`](crate::graph::UndirectedEdge).
+/// * `S`: **S**torage to use: one of the storages defined in [`storage`](crate::storage) module or your custom storage.
+pub struct MultiGraph<W, E: Edge<W>, Dir: EdgeDir, S: GraphStorage<W, E, Dir>> {
+    storage: S,
+
+    phantom_w: PhantomData<W>,
+    phantom_e: PhantomData<E>,
+    phantom_dir: PhantomData<Dir>,
+}
+
+impl<W: Any, E: Edge<W>, Dir: EdgeDir, S: GraphStorage<W, E, Dir>> MultiGraph<W, E, Dir, S> {
+    /// `MultiGraph` defines multiple types with different combination of values for generic parameters.
+    /// These types are:
+    /// * [`MultiMatGraph`](crate::graph::MultiMatGraph): A multigraph using [`Mat`](crate::storage::Mat) as its storage.
+    /// * [`MultiListGraph`](crate::graph::MultiListGraph): A multigraph using [`List`](crate::storage::List) as its storage.
+    ///
+    /// # Arguments
+    /// `storage`: Storage to use.
+    ///
+    /// # Returns
+    /// An empty multigraph.
+    ///
+    /// # Examples
+    /// ```
+    /// use prepona::prelude::*;
+    /// use prepona::storage::List;
+    /// use prepona::graph::MultiListGraph;
+    ///
+    /// let mut graph = MultiListGraph::init(List::<usize>::init());
+    /// let a = graph.add_vertex();
+    /// let b = graph.add_vertex();
+    ///
+    /// let e1 = graph.add_edge(a, b, 1.into()).unwrap();
+    /// let e2 = graph.add_edge(a, b, 2.into()).unwrap();
+    ///
+    /// assert_ne!(e1, e2);
+    /// assert_eq!(graph.edges_between(a, b).unwrap().len(), 2);
+    /// assert_eq!(graph.edge_between(b, a, e2).unwrap().get_weight(), &2.into());
+    /// ```
+    pub fn init(storage: S) -> Self {
+        MultiGraph {
+            storage,
+
+            phantom_e: PhantomData,
+            phantom_w: PhantomData,
+            phantom_dir: PhantomData,
+        }
+    }
+
+    /// Adds a vertex to the graph without panicking.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing unique id of the newly added vertex.
+    /// * `Err`: If the storage can not hold any more vertices. For more info checkout [`try_add_vertex`](crate::storage::GraphStorage::try_add_vertex).
+    pub fn try_add_vertex(&mut self) -> Result<usize> {
+        self.storage.try_add_vertex()
+    }
+
+    /// Removes every vertex for which `keep` returns `false`, along with its edges.
+    ///
+    /// For more info checkout [`retain_vertices`](crate::storage::GraphStorage::retain_vertices) of the storage you use.
+    ///
+    /// # Arguments
+    /// `keep`: Receives id of each vertex and returns `true` if the vertex must be kept.
+    pub fn retain_vertices<F>(&mut self, keep: F)
+    where
+        F: FnMut(usize) -> bool,
+    {
+        self.storage.retain_vertices(keep)
+    }
+
+    /// Removes every edge for which `keep` returns `false`.
+    ///
+    /// For more info checkout [`retain_edges`](crate::storage::GraphStorage::retain_edges) of the storage you use.
+    ///
+    /// # Arguments
+    /// `keep`: Receives (`src_id`, `dst_id`, `edge`) of each edge and returns `true` if the edge must be kept.
+    pub fn retain_edges<F>(&mut self, keep: F)
+    where
+        F: FnMut(usize, usize, &E) -> bool,
+    {
+        self.storage.retain_edges(keep)
+    }
+
+    /// Gives access to functionalities specific to the storage, like cached degrees of [`AdjMap`](crate::storage::AdjMap).
+    ///
+    /// # Returns
+    /// The wrapped storage.
+    pub fn storage(&self) -> &S {
+        &self.storage
+    }
+}
+
+impl<W: Any, E: Edge<W>, Dir: EdgeDir, S: GraphStorage<W, E, Dir>>
+    MultiGraph<W, E, Dir, ObservedStorage<W, E, Dir, S>>
+{
+    /// Adds an observer that gets notified of all subsequent changes to the graph.
+    ///
+    /// For more info checkout [`ObservedStorage`](crate::storage::ObservedStorage).
+    ///
+    /// # Arguments
+    /// `observer`: Observer to add.
+    pub fn subscribe<O>(&mut self, observer: Rc<RefCell<O>>)
+    where
+        O: GraphObserver<W, E> + 'static,
+    {
+        self.storage.subscribe(observer)
+    }
+}
+
+/// For documentation about each function checkout [`Neighbors`](crate::provide::Neighbors) trait and the storage you use.
+impl<W, E: Edge<W>, Dir: EdgeDir, S: GraphStorage<W, E, Dir>> Neighbors
+    for MultiGraph<W, E, Dir, S>
+{
+    fn neighbors(&self, src_id: usize) -> Result<Vec<usize>> {
+        self.storage.neighbors(src_id)
+    }
+
+    fn neighbors_unchecked(&self, src_id: usize) -> Vec<usize> {
+        self.storage.neighbors_unchecked(src_id)
+    }
+}
+
+/// For documentation about each function checkout [`Vertices`](crate::provide::Vertices) trait and the storage you use.
+impl<W, E: Edge<W>, Dir: EdgeDir, S: GraphStorage<W, E, Dir>> Vertices
+    for MultiGraph<W, E, Dir, S>
+{
+    fn vertices(&self) -> Vec<usize> {
+        self.storage.vertices()
+    }
+
+    fn vertex_count(&self) -> usize {
+        self.storage.vertex_count()
+    }
+
+    fn contains_vertex(&self, vertex_id: usize) -> bool {
+        self.storage.contains_vertex(vertex_id)
+    }
+}
+
+/// For documentation about each function checkout [`Edges`](crate::provide::Edges) trait and the storage you use.
+impl<W, E: Edge<W>, Dir: EdgeDir, S: GraphStorage<W, E, Dir>> Edges<W, E>
+    for MultiGraph<W, E, Dir, S>
+{
+    fn edges_from(&self, src_id: usize) -> Result<Vec<(usize, &E)>> {
+        self.storage.edges_from(src_id)
+    }
+
+    fn edges_from_unchecked(&self, src_id: usize) -> Vec<(usize, &E)> {
+        self.storage.edges_from_unchecked(src_id)
+    }
+
+    fn edges_between(&self, src_id: usize, dst_id: usize) -> Result<Vec<&E>> {
+        self.storage.edges_between(src_id, dst_id)
+    }
+
+    fn edges_between_unchecked(&self, src_id: usize, dst_id: usize) -> Vec<&E> {
+        self.storage.edges_between_unchecked(src_id, dst_id)
+    }
+
+    fn edge_between(&self, src_id: usize, dst_id: usize, edge_id: usize) -> Result<&E> {
+        self.storage.edge_between(src_id, dst_id, edge_id)
+    }
+
+    fn edge_between_unchecked(&self, src_id: usize, dst_id: usize, edge_id: usize) -> &E {
+        self.storage.edge_between_unchecked(src_id, dst_id, edge_id)
+    }
+
+    fn edge(&self, edge_id: usize) -> Result<&E> {
+        self.storage.edge(edge_id)
+    }
+
+    fn edge_unchecked(&self, edge_id: usize) -> &E {
+        self.storage.edge_unchecked(edge_id)
+    }
+
+    fn has_any_edge(&self, src_id: usize, dst_id: usize) -> Result<bool> {
+        self.storage.has_any_edge(src_id, dst_id)
+    }
+
+    fn has_any_edge_unchecked(&self, src_id: usize, dst_id: usize) -> bool {
+        self.storage.has_any_edge_unchecked(src_id, dst_id)
+    }
+
+    fn edges(&self) -> Vec<(usize, usize, &E)> {
+        self.storage.edges()
+    }
+
+    fn as_directed_edges(&self) -> Vec<(usize, usize, &E)> {
+        self.storage.as_directed_edges()
+    }
+
+    fn edges_count(&self) -> usize {
+        self.storage.edge_count()
+    }
+
+    fn contains_edge(&self, edge_id: usize) -> bool {
+        self.storage.contains_edge(edge_id)
+    }
+}
+
+/// For documentation checkout [`Direction`](crate::provide::Direction) trait.
+impl<W, E: Edge<W>, Dir: EdgeDir, S: GraphStorage<W, E, Dir>> Direction<Dir>
+    for MultiGraph<W, E, Dir, S>
+{
+}
+
+/// For documentation about each function checkout [`Graph`](crate::provide::Graph) trait.
+impl<W, E: Edge<W>, Dir: EdgeDir, S: GraphStorage<W, E, Dir>> Graph<W, E, Dir>
+    for MultiGraph<W, E, Dir, S>
+{
+    fn add_vertex(&mut self) -> usize {
+        self.storage.add_vertex()
+    }
+
+    fn remove_vertex(&mut self, vertex_id: usize) -> Result<()> {
+        self.storage.remove_vertex(vertex_id)
+    }
+
+    fn remove_vertex_unchecked(&mut self, vertex_id: usize) {
+        self.storage.remove_vertex_unchecked(vertex_id)
+    }
+
+    fn add_edge(&mut self, src_id: usize, dst_id: usize, edge: E) -> Result<usize> {
+        self.storage.add_edge(src_id, dst_id, edge)
+    }
+
+    fn add_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge: E) -> usize {
+        self.storage.add_edge_unchecked(src_id, dst_id, edge)
+    }
+
+    fn update_edge(&mut self, src_id: usize, dst_id: usize, edge_id: usize, edge: E) -> Result<()> {
+        self.storage.update_edge(src_id, dst_id, edge_id, edge)
+    }
+
+    fn update_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge_id: usize, edge: E) {
+        self.storage
+            .update_edge_unchecked(src_id, dst_id, edge_id, edge)
+    }
+
+    fn remove_edge(&mut self, src_id: usize, dst_id: usize, edge_id: usize) -> Result<E> {
+        self.storage.remove_edge(src_id, dst_id, edge_id)
+    }
+
+    fn remove_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge_id: usize) -> E {
+        self.storage.remove_edge_unchecked(src_id, dst_id, edge_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provide::Degrees;
+    use crate::storage::{DiMat, Map};
+
+    #[test]
+    fn add_parallel_edges_and_loops() {
+        // Given: An empty multigraph.
+        let mut graph = MultiMatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+
+        // When: Adding parallel edges and a loop.
+        let ab1 = graph.add_edge(a, b, 1.into()).unwrap();
+        let ab2 = graph.add_edge(a, b, 2.into()).unwrap();
+        let aa = graph.add_edge(a, a, 3.into()).unwrap();
+
+        // Then:
+        assert_eq!(graph.edges_count(), 3);
+        assert_eq!(graph.edges_between(a, b).unwrap().len(), 2);
+        assert_eq!(
+            graph.edge_between(a, a, aa).unwrap().get_weight(),
+            &3.into()
+        );
+
+        // When: Removing one of the parallel edges.
+        graph.remove_edge(a, b, ab1).unwrap();
+
+        // Then: The other one keeps its id.
+        let edges = graph.edges_between(a, b).unwrap();
+        assert_eq!(edges.len(), 1);
+        assert_eq!(edges[0].get_id(), ab2);
+    }
+
+    #[test]
+    fn add_edge_to_absent_vertex() {
+        // Given: Multigraph with one vertex.
+        let mut graph = MultiListGraph::init(List::<usize>::init());
+        let a = graph.add_vertex();
+
+        // When: Adding an edge to a vertex that does not exist.
+        assert!(graph.add_edge(a, a + 1, 1.into()).is_err());
+    }
+
+    #[test]
+    fn cached_degrees_of_storage() {
+        // Given: Multigraph over a map with parallel edges and a loop.
+        let mut graph = MultiGraph::init(Map::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        graph.add_edge(a, b, 1.into()).unwrap();
+        graph.add_edge(a, b, 2.into()).unwrap();
+        graph.add_edge(b, b, 3.into()).unwrap();
+
+        // When:
+        let storage = graph.storage();
+
+        // Then: Cached degrees agree with the computed ones.
+        assert_eq!(storage.degree(a), graph.degree(a));
+        assert_eq!(storage.degree(b), graph.degree(b));
+        assert_eq!(storage.max_degree(), Some(4));
+        assert_eq!(storage.min_degree(), Some(2));
+    }
+}
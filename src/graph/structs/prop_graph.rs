@@ -0,0 +1,482 @@
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+use anyhow::Result;
+
+use crate::graph::{error::Error, Edge, EdgeDir};
+use crate::provide::{Direction, Edges, Graph, Neighbors, Vertices};
+
+/// Wraps a graph and attaches typed attributes to its vertices and edges.
+///
+/// `PropGraph` implements the same traits as the graph it wraps, so it can be passed to any algorithm that the inner graph can be passed to.
+/// Vertex attributes are addressed by vertex id and edge attributes are addressed by edge id.
+/// Attributes are optional: vertices and edges added through the [`Graph`](crate::provide::Graph) trait have no attribute until one is set.
+/// Attributes of removed vertices and edges are dropped.
+///
+/// ## Generic Parameters
+/// * `W`: **W**eight type associated with edges.
+/// * `E`: **E**dge type that graph uses.
+/// * `Dir`: **Dir**ection of edges: [`Directed`](crate::graph::DirectedEdge) or [`Undirected`](crate::graph::UndirectedEdge).
+/// * `G`: **G**raph type that is being wrapped.
+/// * `VA`: **V**ertex **A**ttribute type.
+/// * `EA`: **E**dge **A**ttribute type.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::Mat;
+/// use prepona::graph::{MatGraph, PropGraph};
+///
+/// let mut graph = PropGraph::init(MatGraph::init(Mat::<usize>::init()));
+///
+/// let a = graph.add_vertex_with("Tehran");
+/// let b = graph.add_vertex_with("Shiraz");
+/// let road_id = graph.add_edge_with(a, b, 900.into(), "Highway 65").unwrap();
+///
+/// assert_eq!(graph.vertex_attr(a), Some(&"Tehran"));
+/// assert_eq!(graph.edge_attr(road_id), Some(&"Highway 65"));
+///
+/// // Algorithms see a regular graph.
+/// assert_eq!(graph.neighbors_unchecked(a), vec![b]);
+/// ```
+pub struct PropGraph<W, E, Dir, G, VA, EA>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir>,
+{
+    graph: G,
+
+    vertex_attrs: HashMap<usize, VA>,
+    edge_attrs: HashMap<usize, EA>,
+
+    phantom_w: PhantomData<W>,
+    phantom_e: PhantomData<E>,
+    phantom_dir: PhantomData<Dir>,
+}
+
+impl<W, E, Dir, G, VA, EA> PropGraph<W, E, Dir, G, VA, EA>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Vertices + Edges<W, E>,
+{
+    /// # Arguments
+    /// `graph`: Graph to wrap.
+    ///
+    /// # Returns
+    /// A property graph without any attributes.
+    pub fn init(graph: G) -> Self {
+        PropGraph {
+            graph,
+
+            vertex_attrs: HashMap::new(),
+            edge_attrs: HashMap::new(),
+
+            phantom_w: PhantomData,
+            phantom_e: PhantomData,
+            phantom_dir: PhantomData,
+        }
+    }
+
+    /// # Returns
+    /// The inner graph.
+    pub fn graph(&self) -> &G {
+        &self.graph
+    }
+
+    /// Adds a new vertex with attribute: `attr`.
+    ///
+    /// # Arguments
+    /// `attr`: Attribute of the new vertex.
+    ///
+    /// # Returns
+    /// Id of the new vertex.
+    pub fn add_vertex_with(&mut self, attr: VA) -> usize {
+        let vertex_id = self.graph.add_vertex();
+
+        self.vertex_attrs.insert(vertex_id, attr);
+
+        vertex_id
+    }
+
+    /// Adds a new edge with attribute: `attr`.
+    ///
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    /// * `edge`: Edge to add.
+    /// * `attr`: Attribute of the new edge.
+    ///
+    /// # Returns
+    /// * `Err`: If the inner graph fails to add the edge.
+    /// * `Ok`: Containing the id of the new edge.
+    pub fn add_edge_with(
+        &mut self,
+        src_id: usize,
+        dst_id: usize,
+        edge: E,
+        attr: EA,
+    ) -> Result<usize> {
+        let edge_id = self.graph.add_edge(src_id, dst_id, edge)?;
+
+        self.edge_attrs.insert(edge_id, attr);
+
+        Ok(edge_id)
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// * `Some`: Containing the attribute of the vertex.
+    /// * `None`: If vertex has no attribute.
+    pub fn vertex_attr(&self, vertex_id: usize) -> Option<&VA> {
+        self.vertex_attrs.get(&vertex_id)
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// * `Some`: Containing a mutable reference to the attribute of the vertex.
+    /// * `None`: If vertex has no attribute.
+    pub fn vertex_attr_mut(&mut self, vertex_id: usize) -> Option<&mut VA> {
+        self.vertex_attrs.get_mut(&vertex_id)
+    }
+
+    /// Sets the attribute of a vertex.
+    ///
+    /// # Arguments
+    /// * `vertex_id`: Id of the vertex.
+    /// * `attr`: New attribute of the vertex.
+    ///
+    /// # Returns
+    /// * `Some`: Containing the previous attribute of the vertex.
+    /// * `None`: If vertex had no attribute.
+    ///
+    /// # Panics
+    /// If vertex does not exist.
+    pub fn set_vertex_attr(&mut self, vertex_id: usize, attr: VA) -> Option<VA> {
+        assert!(
+            self.graph.contains_vertex(vertex_id),
+            "Vertex with id: {} does not exist",
+            vertex_id
+        );
+
+        self.vertex_attrs.insert(vertex_id, attr)
+    }
+
+    /// # Arguments
+    /// `edge_id`: Id of the edge.
+    ///
+    /// # Returns
+    /// * `Some`: Containing the attribute of the edge.
+    /// * `None`: If edge has no attribute.
+    pub fn edge_attr(&self, edge_id: usize) -> Option<&EA> {
+        self.edge_attrs.get(&edge_id)
+    }
+
+    /// # Arguments
+    /// `edge_id`: Id of the edge.
+    ///
+    /// # Returns
+    /// * `Some`: Containing a mutable reference to the attribute of the edge.
+    /// * `None`: If edge has no attribute.
+    pub fn edge_attr_mut(&mut self, edge_id: usize) -> Option<&mut EA> {
+        self.edge_attrs.get_mut(&edge_id)
+    }
+
+    /// Sets the attribute of an edge.
+    ///
+    /// # Arguments
+    /// * `edge_id`: Id of the edge.
+    /// * `attr`: New attribute of the edge.
+    ///
+    /// # Returns
+    /// * `Some`: Containing the previous attribute of the edge.
+    /// * `None`: If edge had no attribute.
+    ///
+    /// # Panics
+    /// If edge does not exist.
+    pub fn set_edge_attr(&mut self, edge_id: usize, attr: EA) -> Option<EA> {
+        assert!(
+            self.graph.contains_edge(edge_id),
+            "Edge with id: {} does not exist",
+            edge_id
+        );
+
+        self.edge_attrs.insert(edge_id, attr)
+    }
+
+    // Removing a vertex removes its edges as well, so attributes of those edges must be dropped.
+    fn drop_attrs_of(&mut self, vertex_id: usize) {
+        self.vertex_attrs.remove(&vertex_id);
+
+        for (src_id, dst_id, edge) in self.graph.edges() {
+            if src_id == vertex_id || dst_id == vertex_id {
+                self.edge_attrs.remove(&edge.get_id());
+            }
+        }
+    }
+}
+
+/// For documentation about each function checkout [`Neighbors`](crate::provide::Neighbors) trait.
+impl<W, E, Dir, G, VA, EA> Neighbors for PropGraph<W, E, Dir, G, VA, EA>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Neighbors,
+{
+    fn neighbors(&self, src_id: usize) -> Result<Vec<usize>> {
+        self.graph.neighbors(src_id)
+    }
+
+    fn neighbors_unchecked(&self, src_id: usize) -> Vec<usize> {
+        self.graph.neighbors_unchecked(src_id)
+    }
+}
+
+/// For documentation about each function checkout [`Vertices`](crate::provide::Vertices) trait.
+impl<W, E, Dir, G, VA, EA> Vertices for PropGraph<W, E, Dir, G, VA, EA>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Vertices,
+{
+    fn vertices(&self) -> Vec<usize> {
+        self.graph.vertices()
+    }
+
+    fn vertex_count(&self) -> usize {
+        self.graph.vertex_count()
+    }
+
+    fn contains_vertex(&self, vertex_id: usize) -> bool {
+        self.graph.contains_vertex(vertex_id)
+    }
+}
+
+/// For documentation about each function checkout [`Edges`](crate::provide::Edges) trait.
+impl<W, E, Dir, G, VA, EA> Edges<W, E> for PropGraph<W, E, Dir, G, VA, EA>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Edges<W, E>,
+{
+    fn edges_from(&self, src_id: usize) -> Result<Vec<(usize, &E)>> {
+        self.graph.edges_from(src_id)
+    }
+
+    fn edges_from_unchecked(&self, src_id: usize) -> Vec<(usize, &E)> {
+        self.graph.edges_from_unchecked(src_id)
+    }
+
+    fn edges_between(&self, src_id: usize, dst_id: usize) -> Result<Vec<&E>> {
+        self.graph.edges_between(src_id, dst_id)
+    }
+
+    fn edges_between_unchecked(&self, src_id: usize, dst_id: usize) -> Vec<&E> {
+        self.graph.edges_between_unchecked(src_id, dst_id)
+    }
+
+    fn edge_between(&self, src_id: usize, dst_id: usize, edge_id: usize) -> Result<&E> {
+        self.graph.edge_between(src_id, dst_id, edge_id)
+    }
+
+    fn edge_between_unchecked(&self, src_id: usize, dst_id: usize, edge_id: usize) -> &E {
+        self.graph.edge_between_unchecked(src_id, dst_id, edge_id)
+    }
+
+    fn edge(&self, edge_id: usize) -> Result<&E> {
+        self.graph.edge(edge_id)
+    }
+
+    fn edge_unchecked(&self, edge_id: usize) -> &E {
+        self.graph.edge_unchecked(edge_id)
+    }
+
+    fn has_any_edge(&self, src_id: usize, dst_id: usize) -> Result<bool> {
+        self.graph.has_any_edge(src_id, dst_id)
+    }
+
+    fn has_any_edge_unchecked(&self, src_id: usize, dst_id: usize) -> bool {
+        self.graph.has_any_edge_unchecked(src_id, dst_id)
+    }
+
+    fn edges(&self) -> Vec<(usize, usize, &E)> {
+        self.graph.edges()
+    }
+
+    fn as_directed_edges(&self) -> Vec<(usize, usize, &E)> {
+        self.graph.as_directed_edges()
+    }
+
+    fn edges_count(&self) -> usize {
+        self.graph.edges_count()
+    }
+
+    fn contains_edge(&self, edge_id: usize) -> bool {
+        self.graph.contains_edge(edge_id)
+    }
+}
+
+/// For documentation checkout [`Direction`](crate::provide::Direction) trait.
+impl<W, E, Dir, G, VA, EA> Direction<Dir> for PropGraph<W, E, Dir, G, VA, EA>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir>,
+{
+}
+
+/// For documentation about each function checkout [`Graph`](crate::provide::Graph) trait.
+impl<W, E, Dir, G, VA, EA> Graph<W, E, Dir> for PropGraph<W, E, Dir, G, VA, EA>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Vertices + Edges<W, E>,
+{
+    fn add_vertex(&mut self) -> usize {
+        self.graph.add_vertex()
+    }
+
+    fn remove_vertex(&mut self, vertex_id: usize) -> Result<()> {
+        if !self.graph.contains_vertex(vertex_id) {
+            Err(Error::new_vnf(vertex_id))?
+        }
+
+        self.remove_vertex_unchecked(vertex_id);
+
+        Ok(())
+    }
+
+    fn remove_vertex_unchecked(&mut self, vertex_id: usize) {
+        self.drop_attrs_of(vertex_id);
+
+        self.graph.remove_vertex_unchecked(vertex_id);
+    }
+
+    fn add_edge(&mut self, src_id: usize, dst_id: usize, edge: E) -> Result<usize> {
+        self.graph.add_edge(src_id, dst_id, edge)
+    }
+
+    fn add_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge: E) -> usize {
+        self.graph.add_edge_unchecked(src_id, dst_id, edge)
+    }
+
+    fn update_edge(&mut self, src_id: usize, dst_id: usize, edge_id: usize, edge: E) -> Result<()> {
+        self.graph.update_edge(src_id, dst_id, edge_id, edge)
+    }
+
+    fn update_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge_id: usize, edge: E) {
+        self.graph
+            .update_edge_unchecked(src_id, dst_id, edge_id, edge)
+    }
+
+    fn remove_edge(&mut self, src_id: usize, dst_id: usize, edge_id: usize) -> Result<E> {
+        let edge = self.graph.remove_edge(src_id, dst_id, edge_id)?;
+
+        self.edge_attrs.remove(&edge_id);
+
+        Ok(edge)
+    }
+
+    fn remove_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge_id: usize) -> E {
+        self.edge_attrs.remove(&edge_id);
+
+        self.graph.remove_edge_unchecked(src_id, dst_id, edge_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::Dijkstra;
+    use crate::graph::{ListGraph, MatGraph};
+    use crate::storage::{DiList, Mat};
+
+    #[test]
+    fn attributes_of_vertices_and_edges() {
+        // Given: Empty property graph.
+        let mut graph: PropGraph<_, _, _, _, String, u8> =
+            PropGraph::init(ListGraph::init(DiList::<usize>::init()));
+
+        // When: Adding vertices and edges with and without attributes.
+        let a = graph.add_vertex_with("a".to_string());
+        let b = graph.add_vertex();
+        let ab_id = graph.add_edge_with(a, b, 1.into(), 7).unwrap();
+        let ba_id = graph.add_edge(b, a, 1.into()).unwrap();
+
+        // Then:
+        assert_eq!(graph.vertex_attr(a).unwrap(), "a");
+        assert!(graph.vertex_attr(b).is_none());
+        assert_eq!(graph.edge_attr(ab_id), Some(&7));
+        assert!(graph.edge_attr(ba_id).is_none());
+
+        // When: Mutating attributes.
+        graph.vertex_attr_mut(a).unwrap().push('!');
+        assert!(graph.set_vertex_attr(b, "b".to_string()).is_none());
+        assert_eq!(graph.set_edge_attr(ab_id, 8), Some(7));
+
+        // Then:
+        assert_eq!(graph.vertex_attr(a).unwrap(), "a!");
+        assert_eq!(graph.vertex_attr(b).unwrap(), "b");
+        assert_eq!(graph.edge_attr(ab_id), Some(&8));
+    }
+
+    #[test]
+    fn removal_drops_attributes() {
+        // Given: Property graph
+        //
+        //      a  ---  b  ---  c
+        //
+        let mut graph = PropGraph::init(MatGraph::init(Mat::<usize>::init()));
+        let a = graph.add_vertex_with('a');
+        let b = graph.add_vertex_with('b');
+        let c = graph.add_vertex_with('c');
+        let ab_id = graph.add_edge_with(a, b, 1.into(), "ab").unwrap();
+        let bc_id = graph.add_edge_with(b, c, 1.into(), "bc").unwrap();
+
+        // When: Removing an edge and a vertex.
+        graph.remove_edge(a, b, ab_id).unwrap();
+        graph.remove_vertex(c).unwrap();
+
+        // Then:
+        assert!(graph.edge_attr(ab_id).is_none());
+        assert!(graph.edge_attr(bc_id).is_none());
+        assert!(graph.vertex_attr(c).is_none());
+        assert_eq!(graph.vertex_attr(b), Some(&'b'));
+    }
+
+    #[test]
+    #[should_panic]
+    fn set_attribute_of_absent_vertex() {
+        // Given: Empty property graph.
+        let mut graph: PropGraph<_, _, _, _, u8, u8> =
+            PropGraph::init(MatGraph::init(Mat::<usize>::init()));
+
+        // When: Setting the attribute of a vertex that does not exist.
+        graph.set_vertex_attr(0, 1);
+    }
+
+    #[test]
+    fn algorithms_run_on_property_graph() {
+        // Given: Property graph
+        //
+        //      a  --1--  b  --2--  c
+        //
+        let mut graph = PropGraph::init(MatGraph::init(Mat::<usize>::init()));
+        let a = graph.add_vertex_with("a");
+        let b = graph.add_vertex_with("b");
+        let c = graph.add_vertex_with("c");
+        graph.add_edge_with(a, b, 1.into(), ()).unwrap();
+        graph.add_edge_with(b, c, 2.into(), ()).unwrap();
+
+        // When: Finding shortest paths from a.
+        let sp = Dijkstra::init(&graph).execute(&graph, a);
+
+        // Then:
+        assert_eq!(sp.distance_to(c).unwrap(), 3.into());
+    }
+}
@@ -0,0 +1,414 @@
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+use anyhow::Result;
+
+use crate::graph::{Edge, Error};
+use crate::provide::{Edges, Neighbors, Vertices};
+
+/// A read only view of a graph that exposes its vertices under new ids.
+///
+/// The view applies a bijective mapping from the ids of the underlying graph (original ids) to new ids (relabeled ids).
+/// Every function of the view receives and returns relabeled ids. Edge ids are not changed.
+/// Since the view only relies on the provider traits, it can wrap other views like [`FilterView`](crate::graph::FilterView).
+///
+/// ## Generic Parameters
+/// * `W`: **W**eight type associated with edges.
+/// * `E`: **E**dge type that graph uses.
+/// * `G`: **G**raph to relabel.
+///
+/// # Examples
+/// ```
+/// use std::collections::HashMap;
+/// use prepona::prelude::*;
+/// use prepona::storage::Mat;
+/// use prepona::graph::{MatGraph, RelabelView};
+///
+/// // Given: Graph
+/// //
+/// //      a  --  b  --  c
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+///
+/// // When: Renaming a, b and c to 10, 20 and 30.
+/// let mapping: HashMap<usize, usize> = vec![(a, 10), (b, 20), (c, 30)].into_iter().collect();
+/// let view = RelabelView::init(&graph, mapping).unwrap();
+///
+/// // Then:
+/// assert!(view.contains_vertex(20));
+/// assert!(!view.contains_vertex(b));
+/// let mut neighbors = view.neighbors_unchecked(20);
+/// neighbors.sort();
+/// assert_eq!(neighbors, vec![10, 30]);
+/// assert_eq!(view.original_id_of(30), Some(c));
+/// ```
+pub struct RelabelView<'a, W, E, G> {
+    graph: &'a G,
+
+    to_new: HashMap<usize, usize>,
+    to_original: HashMap<usize, usize>,
+
+    phantom_w: PhantomData<W>,
+    phantom_e: PhantomData<E>,
+}
+
+impl<'a, W, E, G> RelabelView<'a, W, E, G>
+where
+    E: Edge<W>,
+    G: Vertices + Edges<W, E>,
+{
+    /// # Arguments
+    /// * `graph`: Graph to relabel.
+    /// * `mapping`: Maps each vertex id of `graph` to its new id.
+    ///
+    /// # Returns
+    /// * `Some`: Containing the relabeled view.
+    /// * `None`: If `mapping` does not map every vertex of `graph` or maps two vertices to the same id.
+    pub fn init(graph: &'a G, mapping: HashMap<usize, usize>) -> Option<Self> {
+        let vertices = graph.vertices();
+
+        if mapping.len() != vertices.len()
+            || !vertices
+                .iter()
+                .all(|vertex_id| mapping.contains_key(vertex_id))
+        {
+            return None;
+        }
+
+        let to_original: HashMap<usize, usize> = mapping
+            .iter()
+            .map(|(original_id, new_id)| (*new_id, *original_id))
+            .collect();
+
+        if to_original.len() != mapping.len() {
+            return None;
+        }
+
+        Some(RelabelView {
+            graph,
+            to_new: mapping,
+            to_original,
+
+            phantom_w: PhantomData,
+            phantom_e: PhantomData,
+        })
+    }
+
+    /// Relabels vertices of `graph` to 0..|V| in the order returned by [`vertices`](crate::provide::Vertices::vertices).
+    ///
+    /// It's specifically useful to remove the gaps left by removed vertices before exporting the graph to a compact format.
+    ///
+    /// # Arguments
+    /// `graph`: Graph to relabel.
+    ///
+    /// # Returns
+    /// A view in which vertex ids are continuos.
+    pub fn compact(graph: &'a G) -> Self {
+        let mapping = graph
+            .vertices()
+            .into_iter()
+            .enumerate()
+            .map(|(new_id, original_id)| (original_id, new_id))
+            .collect();
+
+        RelabelView::init(graph, mapping).unwrap()
+    }
+
+    /// # Returns
+    /// The graph behind the view.
+    pub fn graph(&self) -> &'a G {
+        self.graph
+    }
+
+    /// # Arguments
+    /// `original_id`: Id of a vertex in the underlying graph.
+    ///
+    /// # Returns
+    /// * `Some`: Containing id of the vertex in the view.
+    /// * `None`: If the underlying graph does not contain the vertex.
+    pub fn new_id_of(&self, original_id: usize) -> Option<usize> {
+        self.to_new.get(&original_id).copied()
+    }
+
+    /// # Arguments
+    /// `new_id`: Id of a vertex in the view.
+    ///
+    /// # Returns
+    /// * `Some`: Containing id of the vertex in the underlying graph.
+    /// * `None`: If the view does not contain the vertex.
+    pub fn original_id_of(&self, new_id: usize) -> Option<usize> {
+        self.to_original.get(&new_id).copied()
+    }
+
+    fn to_new_unchecked(&self, original_id: usize) -> usize {
+        self.to_new[&original_id]
+    }
+
+    fn to_original_checked(&self, new_id: usize) -> Result<usize> {
+        self.original_id_of(new_id)
+            .ok_or_else(|| Error::new_vnf(new_id).into())
+    }
+
+    fn to_original_unchecked(&self, new_id: usize) -> usize {
+        self.to_original[&new_id]
+    }
+}
+
+/// For documentation about each function checkout [`Neighbors`](crate::provide::Neighbors) trait.
+impl<'a, W, E, G> Neighbors for RelabelView<'a, W, E, G>
+where
+    E: Edge<W>,
+    G: Vertices + Edges<W, E> + Neighbors,
+{
+    fn neighbors(&self, src_id: usize) -> Result<Vec<usize>> {
+        let src_id = self.to_original_checked(src_id)?;
+
+        Ok(self
+            .graph
+            .neighbors(src_id)?
+            .into_iter()
+            .map(|dst_id| self.to_new_unchecked(dst_id))
+            .collect())
+    }
+
+    fn neighbors_unchecked(&self, src_id: usize) -> Vec<usize> {
+        self.graph
+            .neighbors_unchecked(self.to_original_unchecked(src_id))
+            .into_iter()
+            .map(|dst_id| self.to_new_unchecked(dst_id))
+            .collect()
+    }
+}
+
+/// For documentation about each function checkout [`Vertices`](crate::provide::Vertices) trait.
+impl<'a, W, E, G> Vertices for RelabelView<'a, W, E, G>
+where
+    E: Edge<W>,
+    G: Vertices + Edges<W, E>,
+{
+    fn vertices(&self) -> Vec<usize> {
+        self.graph
+            .vertices()
+            .into_iter()
+            .map(|vertex_id| self.to_new_unchecked(vertex_id))
+            .collect()
+    }
+
+    fn vertex_count(&self) -> usize {
+        self.graph.vertex_count()
+    }
+
+    fn contains_vertex(&self, vertex_id: usize) -> bool {
+        self.to_original.contains_key(&vertex_id)
+    }
+}
+
+/// For documentation about each function checkout [`Edges`](crate::provide::Edges) trait.
+impl<'a, W, E, G> Edges<W, E> for RelabelView<'a, W, E, G>
+where
+    E: Edge<W>,
+    G: Vertices + Edges<W, E>,
+{
+    fn edges_from(&self, src_id: usize) -> Result<Vec<(usize, &E)>> {
+        let src_id = self.to_original_checked(src_id)?;
+
+        Ok(self
+            .graph
+            .edges_from(src_id)?
+            .into_iter()
+            .map(|(dst_id, edge)| (self.to_new_unchecked(dst_id), edge))
+            .collect())
+    }
+
+    fn edges_from_unchecked(&self, src_id: usize) -> Vec<(usize, &E)> {
+        self.graph
+            .edges_from_unchecked(self.to_original_unchecked(src_id))
+            .into_iter()
+            .map(|(dst_id, edge)| (self.to_new_unchecked(dst_id), edge))
+            .collect()
+    }
+
+    fn edges_between(&self, src_id: usize, dst_id: usize) -> Result<Vec<&E>> {
+        let src_id = self.to_original_checked(src_id)?;
+        let dst_id = self.to_original_checked(dst_id)?;
+
+        self.graph.edges_between(src_id, dst_id)
+    }
+
+    fn edges_between_unchecked(&self, src_id: usize, dst_id: usize) -> Vec<&E> {
+        self.graph.edges_between_unchecked(
+            self.to_original_unchecked(src_id),
+            self.to_original_unchecked(dst_id),
+        )
+    }
+
+    fn edge_between(&self, src_id: usize, dst_id: usize, edge_id: usize) -> Result<&E> {
+        let src_id = self.to_original_checked(src_id)?;
+        let dst_id = self.to_original_checked(dst_id)?;
+
+        self.graph.edge_between(src_id, dst_id, edge_id)
+    }
+
+    fn edge_between_unchecked(&self, src_id: usize, dst_id: usize, edge_id: usize) -> &E {
+        self.graph.edge_between_unchecked(
+            self.to_original_unchecked(src_id),
+            self.to_original_unchecked(dst_id),
+            edge_id,
+        )
+    }
+
+    fn edge(&self, edge_id: usize) -> Result<&E> {
+        self.graph.edge(edge_id)
+    }
+
+    fn edge_unchecked(&self, edge_id: usize) -> &E {
+        self.graph.edge_unchecked(edge_id)
+    }
+
+    fn has_any_edge(&self, src_id: usize, dst_id: usize) -> Result<bool> {
+        let src_id = self.to_original_checked(src_id)?;
+        let dst_id = self.to_original_checked(dst_id)?;
+
+        self.graph.has_any_edge(src_id, dst_id)
+    }
+
+    fn has_any_edge_unchecked(&self, src_id: usize, dst_id: usize) -> bool {
+        self.graph.has_any_edge_unchecked(
+            self.to_original_unchecked(src_id),
+            self.to_original_unchecked(dst_id),
+        )
+    }
+
+    fn edges(&self) -> Vec<(usize, usize, &E)> {
+        self.graph
+            .edges()
+            .into_iter()
+            .map(|(src_id, dst_id, edge)| {
+                (
+                    self.to_new_unchecked(src_id),
+                    self.to_new_unchecked(dst_id),
+                    edge,
+                )
+            })
+            .collect()
+    }
+
+    fn as_directed_edges(&self) -> Vec<(usize, usize, &E)> {
+        self.graph
+            .as_directed_edges()
+            .into_iter()
+            .map(|(src_id, dst_id, edge)| {
+                (
+                    self.to_new_unchecked(src_id),
+                    self.to_new_unchecked(dst_id),
+                    edge,
+                )
+            })
+            .collect()
+    }
+
+    fn edges_count(&self) -> usize {
+        self.graph.edges_count()
+    }
+
+    fn contains_edge(&self, edge_id: usize) -> bool {
+        self.graph.contains_edge(edge_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{FilterView, ListGraph, MatGraph};
+    use crate::provide::Graph;
+    use crate::storage::{DiList, Mat};
+
+    #[test]
+    fn rejects_non_bijective_mappings() {
+        // Given: Graph with three vertices.
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+
+        // When: Mapping two vertices to the same id or leaving a vertex out.
+        let duplicate: HashMap<_, _> = vec![(a, 0), (b, 0), (c, 1)].into_iter().collect();
+        let partial: HashMap<_, _> = vec![(a, 0), (b, 1)].into_iter().collect();
+        let unknown: HashMap<_, _> = vec![(a, 0), (b, 1), (7, 2)].into_iter().collect();
+
+        // Then:
+        assert!(RelabelView::init(&graph, duplicate).is_none());
+        assert!(RelabelView::init(&graph, partial).is_none());
+        assert!(RelabelView::init(&graph, unknown).is_none());
+    }
+
+    #[test]
+    fn relabeled_directed_graph() {
+        // Given: Graph
+        //
+        //      a  -->  b  -->  c
+        //      ^               |
+        //      '---------------'
+        //
+        let mut graph = ListGraph::init(DiList::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let ab = graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 2.into());
+        graph.add_edge_unchecked(c, a, 3.into());
+
+        // When: Reversing the ids.
+        let mapping: HashMap<_, _> = vec![(a, c), (b, b), (c, a)].into_iter().collect();
+        let view = RelabelView::init(&graph, mapping).unwrap();
+
+        // Then:
+        assert_eq!(view.neighbors_unchecked(c), vec![b]);
+        assert_eq!(view.neighbors_unchecked(a), vec![c]);
+        assert!(view.has_any_edge_unchecked(a, c));
+        assert!(!view.has_any_edge_unchecked(c, a));
+        assert_eq!(view.edge_between_unchecked(c, b, ab).get_id(), ab);
+        let mut edges: Vec<_> = view
+            .edges()
+            .into_iter()
+            .map(|(src_id, dst_id, edge)| (src_id, dst_id, edge.get_weight().unwrap()))
+            .collect();
+        edges.sort();
+        assert_eq!(edges, vec![(a, c, 3), (b, a, 2), (c, b, 1)]);
+        assert!(view.neighbors(7).is_err());
+    }
+
+    #[test]
+    fn compact_after_removal_and_filtering() {
+        // Given: Graph
+        //
+        //      a  --  b  --  c  --  d
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(c, d, 1.into());
+
+        // When: Hiding a and compacting the remaining ids.
+        let filtered = FilterView::vertices_only(&graph, |vertex_id| vertex_id != a);
+        let view = RelabelView::compact(&filtered);
+
+        // Then:
+        assert_eq!(view.vertices(), vec![0, 1, 2]);
+        assert_eq!(view.original_id_of(0), Some(b));
+        assert_eq!(view.new_id_of(d), Some(2));
+        assert_eq!(view.new_id_of(a), None);
+        assert_eq!(view.edges_count(), 2);
+        assert_eq!(view.neighbors_unchecked(0), vec![1]);
+        assert!(view.continuos_id_map().is_identity());
+    }
+}
@@ -0,0 +1,405 @@
+use std::collections::{HashMap, HashSet};
+use std::marker::PhantomData;
+
+use anyhow::Result;
+
+use crate::graph::{Edge, Error};
+use crate::provide::{Edges, Neighbors, Vertices};
+
+/// A read only view of a graph in which direction of every edge is reversed.
+///
+/// Outgoing edges of a vertex in the view are the incoming edges of that vertex in the underlying graph and vice versa,
+/// so [`in_degree`](crate::graph::ReverseView::in_degree) and [`out_degree`](crate::graph::ReverseView::out_degree) are swapped as well.
+/// Reversing an undirected graph results in the same graph.
+///
+/// Incoming edges of the underlying graph are indexed once when the view is created.
+/// Edges themselves are not copied and edge ids are preserved.
+///
+/// ## Generic Parameters
+/// * `W`: **W**eight type associated with edges.
+/// * `E`: **E**dge type that graph uses.
+/// * `G`: **G**raph to reverse.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::DiMat;
+/// use prepona::graph::{MatGraph, ReverseView};
+///
+/// // Given: Graph
+/// //
+/// //      a  -->  b  -->  c
+/// //      |               ^
+/// //      '---------------'
+/// //
+/// let mut graph = MatGraph::init(DiMat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(a, c, 1.into());
+///
+/// // When: Reversing the graph.
+/// let view = ReverseView::init(&graph);
+///
+/// // Then:
+/// let mut neighbors = view.neighbors_unchecked(c);
+/// neighbors.sort();
+/// assert_eq!(neighbors, vec![a, b]);
+/// assert_eq!(view.out_degree(c), 2);
+/// assert_eq!(view.in_degree(a), 2);
+/// assert!(view.has_any_edge_unchecked(b, a));
+/// ```
+pub struct ReverseView<'a, W, E, G> {
+    graph: &'a G,
+
+    incoming: HashMap<usize, Vec<(usize, &'a E)>>,
+
+    phantom_w: PhantomData<W>,
+}
+
+impl<'a, W, E, G> ReverseView<'a, W, E, G>
+where
+    E: Edge<W>,
+    G: Vertices + Edges<W, E>,
+{
+    /// # Arguments
+    /// `graph`: Graph to reverse.
+    ///
+    /// # Returns
+    /// A view of `graph` with reversed edges.
+    ///
+    /// # Complexity
+    /// O(|V| + |E|)
+    pub fn init(graph: &'a G) -> Self {
+        let mut incoming: HashMap<usize, Vec<(usize, &'a E)>> = graph
+            .vertices()
+            .into_iter()
+            .map(|vertex_id| (vertex_id, vec![]))
+            .collect();
+
+        for (src_id, dst_id, edge) in graph.as_directed_edges() {
+            incoming.get_mut(&dst_id).unwrap().push((src_id, edge));
+        }
+
+        ReverseView {
+            graph,
+            incoming,
+
+            phantom_w: PhantomData,
+        }
+    }
+
+    /// # Returns
+    /// The graph behind the view.
+    pub fn graph(&self) -> &'a G {
+        self.graph
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// Edges going out of the vertex in the view, which are the edges coming into it in the underlying graph.
+    /// Edges are in the format of (`dst_id`, `edge`).
+    pub fn outgoing_edges(&self, vertex_id: usize) -> Vec<(usize, &E)> {
+        self.edges_from_unchecked(vertex_id)
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// Edges coming into the vertex in the view, which are the edges going out of it in the underlying graph.
+    /// Edges are in the format of (`src_id`, `edge`).
+    pub fn incoming_edges(&self, vertex_id: usize) -> Vec<(usize, &E)> {
+        self.graph.edges_from_unchecked(vertex_id)
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// Number of edges going out of the vertex in the view.
+    pub fn out_degree(&self, vertex_id: usize) -> usize {
+        self.incoming.get(&vertex_id).map_or(0, |edges| edges.len())
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// Number of edges coming into the vertex in the view.
+    pub fn in_degree(&self, vertex_id: usize) -> usize {
+        if self.graph.contains_vertex(vertex_id) {
+            self.graph.edges_from_unchecked(vertex_id).len()
+        } else {
+            0
+        }
+    }
+}
+
+/// For documentation about each function checkout [`Neighbors`](crate::provide::Neighbors) trait.
+///
+/// Each neighbor is reported once, even if multiple edges lead to it.
+impl<'a, W, E, G> Neighbors for ReverseView<'a, W, E, G>
+where
+    E: Edge<W>,
+    G: Vertices + Edges<W, E>,
+{
+    fn neighbors(&self, src_id: usize) -> Result<Vec<usize>> {
+        if !self.graph.contains_vertex(src_id) {
+            Err(Error::new_vnf(src_id))?
+        } else {
+            Ok(self.neighbors_unchecked(src_id))
+        }
+    }
+
+    fn neighbors_unchecked(&self, src_id: usize) -> Vec<usize> {
+        let mut seen = HashSet::new();
+
+        self.incoming[&src_id]
+            .iter()
+            .map(|(dst_id, _)| *dst_id)
+            .filter(|dst_id| seen.insert(*dst_id))
+            .collect()
+    }
+}
+
+/// For documentation about each function checkout [`Vertices`](crate::provide::Vertices) trait.
+impl<'a, W, E, G> Vertices for ReverseView<'a, W, E, G>
+where
+    G: Vertices,
+{
+    fn vertices(&self) -> Vec<usize> {
+        self.graph.vertices()
+    }
+
+    fn vertex_count(&self) -> usize {
+        self.graph.vertex_count()
+    }
+
+    fn contains_vertex(&self, vertex_id: usize) -> bool {
+        self.graph.contains_vertex(vertex_id)
+    }
+}
+
+/// For documentation about each function checkout [`Edges`](crate::provide::Edges) trait.
+impl<'a, W, E, G> Edges<W, E> for ReverseView<'a, W, E, G>
+where
+    E: Edge<W>,
+    G: Vertices + Edges<W, E>,
+{
+    fn edges_from(&self, src_id: usize) -> Result<Vec<(usize, &E)>> {
+        if !self.graph.contains_vertex(src_id) {
+            Err(Error::new_vnf(src_id))?
+        } else {
+            Ok(self.edges_from_unchecked(src_id))
+        }
+    }
+
+    fn edges_from_unchecked(&self, src_id: usize) -> Vec<(usize, &E)> {
+        self.incoming[&src_id].clone()
+    }
+
+    fn edges_between(&self, src_id: usize, dst_id: usize) -> Result<Vec<&E>> {
+        self.graph.edges_between(dst_id, src_id)
+    }
+
+    fn edges_between_unchecked(&self, src_id: usize, dst_id: usize) -> Vec<&E> {
+        self.graph.edges_between_unchecked(dst_id, src_id)
+    }
+
+    fn edge_between(&self, src_id: usize, dst_id: usize, edge_id: usize) -> Result<&E> {
+        self.graph.edge_between(dst_id, src_id, edge_id)
+    }
+
+    fn edge_between_unchecked(&self, src_id: usize, dst_id: usize, edge_id: usize) -> &E {
+        self.graph.edge_between_unchecked(dst_id, src_id, edge_id)
+    }
+
+    fn edge(&self, edge_id: usize) -> Result<&E> {
+        self.graph.edge(edge_id)
+    }
+
+    fn edge_unchecked(&self, edge_id: usize) -> &E {
+        self.graph.edge_unchecked(edge_id)
+    }
+
+    fn has_any_edge(&self, src_id: usize, dst_id: usize) -> Result<bool> {
+        self.graph.has_any_edge(dst_id, src_id)
+    }
+
+    fn has_any_edge_unchecked(&self, src_id: usize, dst_id: usize) -> bool {
+        self.graph.has_any_edge_unchecked(dst_id, src_id)
+    }
+
+    fn edges(&self) -> Vec<(usize, usize, &E)> {
+        self.graph
+            .edges()
+            .into_iter()
+            .map(|(src_id, dst_id, edge)| (dst_id, src_id, edge))
+            .collect()
+    }
+
+    fn as_directed_edges(&self) -> Vec<(usize, usize, &E)> {
+        self.graph
+            .as_directed_edges()
+            .into_iter()
+            .map(|(src_id, dst_id, edge)| (dst_id, src_id, edge))
+            .collect()
+    }
+
+    fn edges_count(&self) -> usize {
+        self.graph.edges_count()
+    }
+
+    fn contains_edge(&self, edge_id: usize) -> bool {
+        self.graph.contains_edge(edge_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::{DfsIter, TarjanSCC, Walker};
+    use crate::graph::{DirectedEdge, ListGraph, MatGraph};
+    use crate::misc::rng::Rng;
+    use crate::provide::Graph;
+    use crate::storage::{DiList, Mat};
+
+    // Kosaraju style: scc of a vertex is the intersection of the vertices it reaches in the graph and in its reverse.
+    fn sccs_of<W, E: Edge<W>, G: Vertices + Neighbors + Edges<W, E>>(graph: &G) -> Vec<Vec<usize>> {
+        let reversed = ReverseView::init(graph);
+        let mut assigned = HashSet::new();
+        let mut sccs = vec![];
+
+        for vertex_id in graph.vertices() {
+            if assigned.contains(&vertex_id) {
+                continue;
+            }
+
+            let forward: HashSet<usize> = DfsIter::init(vertex_id).iter(graph).collect();
+            let mut scc: Vec<usize> = DfsIter::init(vertex_id)
+                .iter(&reversed)
+                .filter(|vertex_id| forward.contains(vertex_id))
+                .collect();
+            scc.sort_unstable();
+
+            assigned.extend(scc.iter().copied());
+            sccs.push(scc);
+        }
+
+        sccs.sort();
+        sccs
+    }
+
+    #[test]
+    fn degrees_and_edges_are_swapped() {
+        // Given: Graph
+        //
+        //      a  -->  b  -->  c
+        //      ^       |
+        //      '-------'
+        //
+        let mut graph = ListGraph::init(DiList::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let ab = graph.add_edge_unchecked(a, b, 1.into());
+        let bc = graph.add_edge_unchecked(b, c, 2.into());
+        graph.add_edge_unchecked(b, a, 3.into());
+
+        // When: Reversing the graph.
+        let view = ReverseView::init(&graph);
+
+        // Then:
+        for vertex_id in graph.vertices() {
+            assert_eq!(
+                view.out_degree(vertex_id),
+                view.outgoing_edges(vertex_id).len()
+            );
+            assert_eq!(
+                view.in_degree(vertex_id),
+                graph.edges_from_unchecked(vertex_id).len()
+            );
+        }
+        assert_eq!(view.out_degree(b), 1);
+        assert_eq!(view.in_degree(b), 2);
+        assert_eq!(view.out_degree(c), 1);
+        assert_eq!(view.in_degree(c), 0);
+        assert_eq!(view.edges_from_unchecked(c)[0].1.get_id(), bc);
+        assert_eq!(view.edge_between_unchecked(b, a, ab).get_id(), ab);
+        assert!(view.edge_between(a, b, ab).is_err());
+        assert!(view.has_any_edge_unchecked(c, b));
+        assert!(!view.has_any_edge_unchecked(b, c));
+        assert!(view.edges().into_iter().all(|(src_id, dst_id, edge)| graph
+            .edges_between_unchecked(dst_id, src_id)
+            .iter()
+            .any(|e| e.get_id() == edge.get_id())));
+        assert!(view.edges_from(7).is_err());
+    }
+
+    #[test]
+    fn reversing_undirected_graph_keeps_it_intact() {
+        // Given: Graph
+        //
+        //      a  --  b  --  c
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+
+        // When: Reversing the graph.
+        let view = ReverseView::init(&graph);
+
+        // Then:
+        for vertex_id in graph.vertices() {
+            let mut expected = graph.neighbors_unchecked(vertex_id);
+            let mut neighbors = view.neighbors_unchecked(vertex_id);
+            expected.sort_unstable();
+            neighbors.sort_unstable();
+            assert_eq!(neighbors, expected);
+            assert_eq!(view.in_degree(vertex_id), view.out_degree(vertex_id));
+        }
+    }
+
+    #[test]
+    fn reversed_reachability_matches_tarjan() {
+        // Given: Random directed graphs.
+        let mut rng = Rng::seed_from(7);
+        for _ in 0..20 {
+            let mut graph = ListGraph::<usize, DirectedEdge>::init(DiList::init());
+            let vertex_count = 12;
+            for _ in 0..vertex_count {
+                graph.add_vertex();
+            }
+            for _ in 0..18 {
+                let src_id = rng.next_below(vertex_count);
+                let dst_id = rng.next_below(vertex_count);
+                if src_id != dst_id && !graph.has_any_edge_unchecked(src_id, dst_id) {
+                    graph.add_edge_unchecked(src_id, dst_id, 1.into());
+                }
+            }
+
+            // When: Computing sccs using the reversed view.
+            let sccs = sccs_of(&graph);
+
+            // Then:
+            let mut expected: Vec<Vec<usize>> = TarjanSCC::init(&graph)
+                .execute(&graph)
+                .into_iter()
+                .map(|mut scc| {
+                    scc.sort_unstable();
+                    scc
+                })
+                .collect();
+            expected.sort();
+            assert_eq!(sccs, expected);
+        }
+    }
+}
@@ -1,12 +1,14 @@
 use std::any::Any;
+use std::cell::RefCell;
 use std::marker::PhantomData;
+use std::rc::Rc;
 
 use anyhow::Result;
-use provide::{Edges, Graph, Neighbors, Vertices};
+use provide::{Direction, Edges, Graph, Neighbors, Vertices};
 
 use crate::graph::{error::Error, DefaultEdge, Edge, EdgeDir, FlowEdge};
 use crate::provide;
-use crate::storage::{FlowList, FlowMat, GraphStorage, List, Mat};
+use crate::storage::{FlowList, FlowMat, GraphObserver, GraphStorage, List, Mat, ObservedStorage};
 
 /// A `SimpleGraph` that uses [`Mat`](crate::storage::Mat) as its storage.
 pub type MatGraph<W, Dir> = SimpleGraph<W, DefaultEdge<W>, Dir, Mat<W, Dir>>;
@@ -74,6 +76,66 @@ impl<W: Any, E: Edge<W>, Dir: EdgeDir, S: GraphStorage<W, E, Dir>> SimpleGraph<W
             phantom_dir: PhantomData,
         }
     }
+
+    /// Adds a vertex to the graph without panicking.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing unique id of the newly added vertex.
+    /// * `Err`: If the storage can not hold any more vertices. For more info checkout [`try_add_vertex`](crate::storage::GraphStorage::try_add_vertex).
+    pub fn try_add_vertex(&mut self) -> Result<usize> {
+        self.storage.try_add_vertex()
+    }
+
+    /// Removes every vertex for which `keep` returns `false`, along with its edges.
+    ///
+    /// For more info checkout [`retain_vertices`](crate::storage::GraphStorage::retain_vertices) of the storage you use.
+    ///
+    /// # Arguments
+    /// `keep`: Receives id of each vertex and returns `true` if the vertex must be kept.
+    pub fn retain_vertices<F>(&mut self, keep: F)
+    where
+        F: FnMut(usize) -> bool,
+    {
+        self.storage.retain_vertices(keep)
+    }
+
+    /// Removes every edge for which `keep` returns `false`.
+    ///
+    /// For more info checkout [`retain_edges`](crate::storage::GraphStorage::retain_edges) of the storage you use.
+    ///
+    /// # Arguments
+    /// `keep`: Receives (`src_id`, `dst_id`, `edge`) of each edge and returns `true` if the edge must be kept.
+    pub fn retain_edges<F>(&mut self, keep: F)
+    where
+        F: FnMut(usize, usize, &E) -> bool,
+    {
+        self.storage.retain_edges(keep)
+    }
+
+    /// Gives access to functionalities specific to the storage, like cached degrees of [`AdjMap`](crate::storage::AdjMap).
+    ///
+    /// # Returns
+    /// The wrapped storage.
+    pub fn storage(&self) -> &S {
+        &self.storage
+    }
+}
+
+impl<W: Any, E: Edge<W>, Dir: EdgeDir, S: GraphStorage<W, E, Dir>>
+    SimpleGraph<W, E, Dir, ObservedStorage<W, E, Dir, S>>
+{
+    /// Adds an observer that gets notified of all subsequent changes to the graph.
+    ///
+    /// For more info checkout [`ObservedStorage`](crate::storage::ObservedStorage).
+    ///
+    /// # Arguments
+    /// `observer`: Observer to add.
+    pub fn subscribe<O>(&mut self, observer: Rc<RefCell<O>>)
+    where
+        O: GraphObserver<W, E> + 'static,
+    {
+        self.storage.subscribe(observer)
+    }
 }
 
 /// For documentation about each function checkout [`Neighbors`](crate::provide::Neighbors) trait and the storage you use.
@@ -167,6 +229,12 @@ impl<W, E: Edge<W>, Dir: EdgeDir, S: GraphStorage<W, E, Dir>> Edges<W, E>
     }
 }
 
+/// For documentation checkout [`Direction`](crate::provide::Direction) trait.
+impl<W, E: Edge<W>, Dir: EdgeDir, S: GraphStorage<W, E, Dir>> Direction<Dir>
+    for SimpleGraph<W, E, Dir, S>
+{
+}
+
 /// For documentation about each function checkout [`Graph`](crate::provide::Graph) trait.
 impl<W, E: Edge<W>, Dir: EdgeDir, S: GraphStorage<W, E, Dir>> Graph<W, E, Dir>
     for SimpleGraph<W, E, Dir, S>
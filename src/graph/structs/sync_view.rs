@@ -0,0 +1,275 @@
+use std::ops::Deref;
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::graph::Edge;
+use crate::provide::{Edges, Neighbors, Vertices};
+
+/// A read only view of a graph that can be shared between threads.
+///
+/// `SyncView` owns the graph behind an [`Arc`](std::sync::Arc), so cloning it is cheap and each clone can be moved to a different thread.
+/// It can only be constructed from graphs that are `Send + Sync`, which all graphs and storages of this crate are as long as their weights and edges are.
+/// So a graph that does not satisfy this requirement is rejected at compile time instead of at the point where threads are spawned.
+///
+/// `SyncView` dereferences to the graph it wraps, so it can be passed to any algorithm using `&*view`.
+///
+/// ## Generic Parameters
+/// * `G`: **G**raph to share.
+///
+/// # Examples
+/// ```
+/// use std::thread;
+/// use prepona::prelude::*;
+/// use prepona::storage::Mat;
+/// use prepona::graph::{MatGraph, SyncView};
+/// use prepona::algo::Dijkstra;
+///
+/// // Given: Graph
+/// //
+/// //      a  --1--  b  --1--  c
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+///
+/// let view = SyncView::init(graph);
+///
+/// // When: Running dijkstra from every vertex in a separate thread.
+/// let handles: Vec<_> = vec![a, b, c]
+///     .into_iter()
+///     .map(|src_id| {
+///         let view = view.clone();
+///         thread::spawn(move || {
+///             let sp_subgraph = Dijkstra::init(&*view).execute(&*view, src_id);
+///             sp_subgraph.distance_to(c).unwrap()
+///         })
+///     })
+///     .collect();
+///
+/// let distances: Vec<_> = handles.into_iter().map(|handle| handle.join().unwrap()).collect();
+///
+/// // Then:
+/// assert_eq!(distances, vec![2.into(), 1.into(), 0.into()]);
+/// ```
+pub struct SyncView<G> {
+    graph: Arc<G>,
+}
+
+impl<G: Send + Sync> SyncView<G> {
+    /// # Arguments
+    /// `graph`: Graph to share.
+    ///
+    /// # Returns
+    /// A view that shares ownership of `graph`.
+    pub fn init(graph: G) -> Self {
+        SyncView {
+            graph: Arc::new(graph),
+        }
+    }
+
+    /// # Arguments
+    /// `graph`: Already shared graph.
+    ///
+    /// # Returns
+    /// A view that shares ownership of `graph` with other owners of the `Arc`.
+    pub fn from_arc(graph: Arc<G>) -> Self {
+        SyncView { graph }
+    }
+}
+
+impl<G> SyncView<G> {
+    /// # Returns
+    /// The graph behind the view.
+    pub fn graph(&self) -> &G {
+        &self.graph
+    }
+
+    /// # Returns
+    /// The `Arc` that owns the graph.
+    pub fn as_arc(&self) -> &Arc<G> {
+        &self.graph
+    }
+
+    /// # Returns
+    /// * `Ok`: Containing the graph, if this is the only view of it.
+    /// * `Err`: Containing the view itself, if the graph is still shared.
+    pub fn into_inner(self) -> std::result::Result<G, Self> {
+        Arc::try_unwrap(self.graph).map_err(|graph| SyncView { graph })
+    }
+}
+
+impl<G> Clone for SyncView<G> {
+    fn clone(&self) -> Self {
+        SyncView {
+            graph: Arc::clone(&self.graph),
+        }
+    }
+}
+
+impl<G> Deref for SyncView<G> {
+    type Target = G;
+
+    fn deref(&self) -> &G {
+        &self.graph
+    }
+}
+
+/// For documentation about each function checkout [`Neighbors`](crate::provide::Neighbors) trait.
+impl<G: Neighbors> Neighbors for SyncView<G> {
+    fn neighbors(&self, src_id: usize) -> Result<Vec<usize>> {
+        self.graph.neighbors(src_id)
+    }
+
+    fn neighbors_unchecked(&self, src_id: usize) -> Vec<usize> {
+        self.graph.neighbors_unchecked(src_id)
+    }
+}
+
+/// For documentation about each function checkout [`Vertices`](crate::provide::Vertices) trait.
+impl<G: Vertices> Vertices for SyncView<G> {
+    fn vertices(&self) -> Vec<usize> {
+        self.graph.vertices()
+    }
+
+    fn vertex_count(&self) -> usize {
+        self.graph.vertex_count()
+    }
+
+    fn contains_vertex(&self, vertex_id: usize) -> bool {
+        self.graph.contains_vertex(vertex_id)
+    }
+}
+
+/// For documentation about each function checkout [`Edges`](crate::provide::Edges) trait.
+impl<W, E: Edge<W>, G: Edges<W, E>> Edges<W, E> for SyncView<G> {
+    fn edges_from(&self, src_id: usize) -> Result<Vec<(usize, &E)>> {
+        self.graph.edges_from(src_id)
+    }
+
+    fn edges_from_unchecked(&self, src_id: usize) -> Vec<(usize, &E)> {
+        self.graph.edges_from_unchecked(src_id)
+    }
+
+    fn edges_between(&self, src_id: usize, dst_id: usize) -> Result<Vec<&E>> {
+        self.graph.edges_between(src_id, dst_id)
+    }
+
+    fn edges_between_unchecked(&self, src_id: usize, dst_id: usize) -> Vec<&E> {
+        self.graph.edges_between_unchecked(src_id, dst_id)
+    }
+
+    fn edge_between(&self, src_id: usize, dst_id: usize, edge_id: usize) -> Result<&E> {
+        self.graph.edge_between(src_id, dst_id, edge_id)
+    }
+
+    fn edge_between_unchecked(&self, src_id: usize, dst_id: usize, edge_id: usize) -> &E {
+        self.graph.edge_between_unchecked(src_id, dst_id, edge_id)
+    }
+
+    fn edge(&self, edge_id: usize) -> Result<&E> {
+        self.graph.edge(edge_id)
+    }
+
+    fn edge_unchecked(&self, edge_id: usize) -> &E {
+        self.graph.edge_unchecked(edge_id)
+    }
+
+    fn has_any_edge(&self, src_id: usize, dst_id: usize) -> Result<bool> {
+        self.graph.has_any_edge(src_id, dst_id)
+    }
+
+    fn has_any_edge_unchecked(&self, src_id: usize, dst_id: usize) -> bool {
+        self.graph.has_any_edge_unchecked(src_id, dst_id)
+    }
+
+    fn edges(&self) -> Vec<(usize, usize, &E)> {
+        self.graph.edges()
+    }
+
+    fn as_directed_edges(&self) -> Vec<(usize, usize, &E)> {
+        self.graph.as_directed_edges()
+    }
+
+    fn edges_count(&self) -> usize {
+        self.graph.edges_count()
+    }
+
+    fn contains_edge(&self, edge_id: usize) -> bool {
+        self.graph.contains_edge(edge_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{DirectedEdge, ListGraph, MatGraph, MultiListGraph, UndirectedEdge};
+    use crate::provide::Graph;
+    use crate::storage::{DiList, DiMap, DiMat, List, Map, Mat};
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn storages_and_graphs_are_send_sync() {
+        assert_send_sync::<Mat<usize>>();
+        assert_send_sync::<DiMat<usize>>();
+        assert_send_sync::<List<usize>>();
+        assert_send_sync::<DiList<usize>>();
+        assert_send_sync::<Map<usize>>();
+        assert_send_sync::<DiMap<usize>>();
+        assert_send_sync::<MatGraph<usize, UndirectedEdge>>();
+        assert_send_sync::<ListGraph<f64, DirectedEdge>>();
+        assert_send_sync::<MultiListGraph<usize, UndirectedEdge>>();
+        assert_send_sync::<SyncView<MatGraph<usize, UndirectedEdge>>>();
+    }
+
+    #[test]
+    fn shared_between_threads() {
+        // Given: Graph
+        //
+        //      a  --  b  --  c  --  d
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let vertices: Vec<usize> = (0..4).map(|_| graph.add_vertex()).collect();
+        for i in 1..4 {
+            graph.add_edge_unchecked(vertices[i - 1], vertices[i], 1.into());
+        }
+        let view = SyncView::init(graph);
+
+        // When: Counting edges from every vertex in separate threads.
+        let degrees: Vec<usize> = vertices
+            .iter()
+            .map(|vertex_id| {
+                let view = view.clone();
+                let vertex_id = *vertex_id;
+                std::thread::spawn(move || view.edges_from_unchecked(vertex_id).len())
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect();
+
+        // Then:
+        assert_eq!(degrees, vec![1, 2, 2, 1]);
+    }
+
+    #[test]
+    fn into_inner() {
+        // Given: View of a graph with one vertex.
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        graph.add_vertex();
+        let view = SyncView::init(graph);
+
+        // When: View is still shared.
+        let other = view.clone();
+        let view = view.into_inner().err().unwrap();
+
+        // Then: Graph can be taken back after dropping other views.
+        drop(other);
+        let graph = view.into_inner().ok().unwrap();
+        assert_eq!(graph.vertex_count(), 1);
+    }
+}
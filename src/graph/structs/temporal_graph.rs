@@ -0,0 +1,584 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::marker::PhantomData;
+
+use anyhow::Result;
+
+use crate::graph::{error::Error, Edge, EdgeDir, EdgeFilter, FilterView, KeepAll};
+use crate::provide::{Direction, Edges, Graph, Neighbors, Vertices};
+
+/// Period of time in which an edge of a [`TemporalGraph`](crate::graph::TemporalGraph) exists.
+///
+/// Intervals are half open: an edge with interval [`start`, `end`) exists at time t if `start` <= t < `end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TimeInterval {
+    /// First time in which the edge exists.
+    pub start: usize,
+
+    /// First time after `start` in which the edge does not exist anymore.
+    pub end: usize,
+}
+
+impl TimeInterval {
+    /// # Arguments
+    /// * `start`: First time in which the edge exists.
+    /// * `end`: First time after `start` in which the edge does not exist anymore.
+    ///
+    /// # Panics
+    /// If `end` is less than `start`.
+    pub fn init(start: usize, end: usize) -> Self {
+        assert!(start <= end, "Interval can not end before it starts");
+
+        TimeInterval { start, end }
+    }
+
+    /// # Arguments
+    /// `time`: The only time in which the edge exists.
+    ///
+    /// # Returns
+    /// Interval [`time`, `time` + 1).
+    pub fn at(time: usize) -> Self {
+        TimeInterval::init(time, time + 1)
+    }
+
+    /// # Returns
+    /// Interval that contains every time.
+    pub fn always() -> Self {
+        TimeInterval::init(0, usize::MAX)
+    }
+
+    /// # Arguments
+    /// `time`: Time to check.
+    ///
+    /// # Returns
+    /// * `true`: If `time` is in the interval.
+    /// * `false`: Otherwise.
+    pub fn contains(&self, time: usize) -> bool {
+        self.start <= time && time < self.end
+    }
+}
+
+/// Edge filter of the views returned by [`snapshot_at`](crate::graph::TemporalGraph::snapshot_at), which keeps the edges that exist at a specific time.
+pub struct SnapshotFilter<'a> {
+    intervals: &'a HashMap<usize, TimeInterval>,
+    time: usize,
+}
+
+impl<'a, W, E: Edge<W>> EdgeFilter<W, E> for SnapshotFilter<'a> {
+    fn keep_edge(&self, _: usize, _: usize, edge: &E) -> bool {
+        self.intervals
+            .get(&edge.get_id())
+            .is_none_or(|interval| interval.contains(self.time))
+    }
+}
+
+/// Wraps a graph and attaches a [`TimeInterval`](crate::graph::TimeInterval) to each of its edges, for graphs that change over time.
+///
+/// Vertices exist at all times. Edges added through the [`Graph`](crate::provide::Graph) trait have no interval and exist at all times.
+/// `TemporalGraph` implements the same traits as the graph it wraps, so algorithms that receive it see every edge regardless of time.
+/// To run an algorithm on the graph as it was at a specific time, pass it a [`snapshot_at`](crate::graph::TemporalGraph::snapshot_at) that time.
+///
+/// ## Generic Parameters
+/// * `W`: **W**eight type associated with edges.
+/// * `E`: **E**dge type that graph uses.
+/// * `Dir`: **Dir**ection of edges: [`Directed`](crate::graph::DirectedEdge) or [`Undirected`](crate::graph::UndirectedEdge).
+/// * `G`: **G**raph type that is being wrapped.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::DiList;
+/// use prepona::graph::{ListGraph, TemporalGraph, TimeInterval};
+///
+/// // Given: Flights that are available in different hours.
+/// //
+/// //      a  --[1, 3)-->  b  --[2, 4)-->  c
+/// //
+/// let mut graph = TemporalGraph::init(ListGraph::init(DiList::<usize>::init()));
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_during(a, b, 1.into(), TimeInterval::init(1, 3)).unwrap();
+/// graph.add_edge_during(b, c, 1.into(), TimeInterval::init(2, 4)).unwrap();
+///
+/// // Then: At time 3 only the second flight is available.
+/// let snapshot = graph.snapshot_at(3);
+/// assert_eq!(snapshot.edges_count(), 1);
+/// assert!(snapshot.has_any_edge_unchecked(b, c));
+///
+/// // And: Leaving a at time 0 reaches c at time 2, but leaving at time 3 never leaves a.
+/// assert_eq!(graph.earliest_arrival_times(a, 0)[&c], 2);
+/// assert_eq!(graph.earliest_arrival_times(a, 3).len(), 1);
+/// ```
+pub struct TemporalGraph<W, E, Dir, G>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir>,
+{
+    graph: G,
+
+    intervals: HashMap<usize, TimeInterval>,
+
+    phantom_w: PhantomData<W>,
+    phantom_e: PhantomData<E>,
+    phantom_dir: PhantomData<Dir>,
+}
+
+impl<W, E, Dir, G> TemporalGraph<W, E, Dir, G>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Vertices + Edges<W, E>,
+{
+    /// # Arguments
+    /// `graph`: Graph to wrap.
+    ///
+    /// # Returns
+    /// A temporal graph in which every edge of `graph` exists at all times.
+    pub fn init(graph: G) -> Self {
+        TemporalGraph {
+            graph,
+
+            intervals: HashMap::new(),
+
+            phantom_w: PhantomData,
+            phantom_e: PhantomData,
+            phantom_dir: PhantomData,
+        }
+    }
+
+    /// # Returns
+    /// The inner graph.
+    pub fn graph(&self) -> &G {
+        &self.graph
+    }
+
+    /// Adds a new edge that only exists during `interval`.
+    ///
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    /// * `edge`: Edge to add.
+    /// * `interval`: Period of time in which the edge exists.
+    ///
+    /// # Returns
+    /// * `Err`: If the inner graph fails to add the edge.
+    /// * `Ok`: Containing the id of the new edge.
+    pub fn add_edge_during(
+        &mut self,
+        src_id: usize,
+        dst_id: usize,
+        edge: E,
+        interval: TimeInterval,
+    ) -> Result<usize> {
+        let edge_id = self.graph.add_edge(src_id, dst_id, edge)?;
+
+        self.intervals.insert(edge_id, interval);
+
+        Ok(edge_id)
+    }
+
+    /// Adds a new edge that only exists at `time`.
+    ///
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    /// * `edge`: Edge to add.
+    /// * `time`: Time stamp of the edge.
+    ///
+    /// # Returns
+    /// * `Err`: If the inner graph fails to add the edge.
+    /// * `Ok`: Containing the id of the new edge.
+    pub fn add_edge_at(
+        &mut self,
+        src_id: usize,
+        dst_id: usize,
+        edge: E,
+        time: usize,
+    ) -> Result<usize> {
+        self.add_edge_during(src_id, dst_id, edge, TimeInterval::at(time))
+    }
+
+    /// # Arguments
+    /// `edge_id`: Id of the edge.
+    ///
+    /// # Returns
+    /// * `Some`: Containing the period of time in which the edge exists.
+    /// * `None`: If edge does not exist.
+    pub fn interval_of(&self, edge_id: usize) -> Option<TimeInterval> {
+        if self.graph.contains_edge(edge_id) {
+            Some(
+                self.intervals
+                    .get(&edge_id)
+                    .copied()
+                    .unwrap_or_else(TimeInterval::always),
+            )
+        } else {
+            None
+        }
+    }
+
+    /// Sets the period of time in which an edge exists.
+    ///
+    /// # Arguments
+    /// * `edge_id`: Id of the edge.
+    /// * `interval`: New interval of the edge.
+    ///
+    /// # Panics
+    /// If edge does not exist.
+    pub fn set_interval(&mut self, edge_id: usize, interval: TimeInterval) {
+        assert!(
+            self.graph.contains_edge(edge_id),
+            "Edge with id: {} does not exist",
+            edge_id
+        );
+
+        self.intervals.insert(edge_id, interval);
+    }
+
+    /// # Arguments
+    /// `time`: Time of the snapshot.
+    ///
+    /// # Returns
+    /// A read only view of the graph that only contains the edges that exist at `time`.
+    pub fn snapshot_at(&self, time: usize) -> FilterView<'_, W, E, G, KeepAll, SnapshotFilter<'_>> {
+        FilterView::edges_only(
+            &self.graph,
+            SnapshotFilter {
+                intervals: &self.intervals,
+                time,
+            },
+        )
+    }
+
+    /// Finds the earliest time in which each vertex can be reached, leaving `src_id` at `start_time`.
+    ///
+    /// A walk may wait at any vertex and can take an edge at any time in which the edge exists. Taking an edge takes no time,
+    /// so a walk can take multiple edges at the same time. Vertices are visited in the order of their arrival times, like breadth-first search visits vertices in the order of their distances.
+    ///
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `start_time`: Time in which walks leave the source.
+    ///
+    /// # Returns
+    /// Earliest arrival time of each reachable vertex. Arrival time of the source is `start_time`.
+    ///
+    /// # Complexity
+    /// O((|V| + |E|) * log(|V|))
+    ///
+    /// # Panics
+    /// If `src_id` does not exist.
+    pub fn earliest_arrival_times(
+        &self,
+        src_id: usize,
+        start_time: usize,
+    ) -> HashMap<usize, usize> {
+        assert!(
+            self.graph.contains_vertex(src_id),
+            "Vertex with id: {} does not exist",
+            src_id
+        );
+
+        let mut arrival_times = HashMap::new();
+        let mut heap = BinaryHeap::from(vec![Reverse((start_time, src_id))]);
+
+        while let Some(Reverse((time, vertex_id))) = heap.pop() {
+            if arrival_times.contains_key(&vertex_id) {
+                continue;
+            }
+            arrival_times.insert(vertex_id, time);
+
+            for (dst_id, edge) in self.graph.edges_from_unchecked(vertex_id) {
+                if arrival_times.contains_key(&dst_id) {
+                    continue;
+                }
+
+                let interval = self.interval_of(edge.get_id()).unwrap();
+                let departure_time = time.max(interval.start);
+                if departure_time < interval.end {
+                    heap.push(Reverse((departure_time, dst_id)));
+                }
+            }
+        }
+
+        arrival_times
+    }
+
+    // Removing a vertex removes its edges as well, so intervals of those edges must be dropped.
+    fn drop_intervals_of(&mut self, vertex_id: usize) {
+        for (src_id, dst_id, edge) in self.graph.edges() {
+            if src_id == vertex_id || dst_id == vertex_id {
+                self.intervals.remove(&edge.get_id());
+            }
+        }
+    }
+}
+
+/// For documentation about each function checkout [`Neighbors`](crate::provide::Neighbors) trait.
+impl<W, E, Dir, G> Neighbors for TemporalGraph<W, E, Dir, G>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Neighbors,
+{
+    fn neighbors(&self, src_id: usize) -> Result<Vec<usize>> {
+        self.graph.neighbors(src_id)
+    }
+
+    fn neighbors_unchecked(&self, src_id: usize) -> Vec<usize> {
+        self.graph.neighbors_unchecked(src_id)
+    }
+}
+
+/// For documentation about each function checkout [`Vertices`](crate::provide::Vertices) trait.
+impl<W, E, Dir, G> Vertices for TemporalGraph<W, E, Dir, G>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Vertices,
+{
+    fn vertices(&self) -> Vec<usize> {
+        self.graph.vertices()
+    }
+
+    fn vertex_count(&self) -> usize {
+        self.graph.vertex_count()
+    }
+
+    fn contains_vertex(&self, vertex_id: usize) -> bool {
+        self.graph.contains_vertex(vertex_id)
+    }
+}
+
+/// For documentation about each function checkout [`Edges`](crate::provide::Edges) trait.
+impl<W, E, Dir, G> Edges<W, E> for TemporalGraph<W, E, Dir, G>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Edges<W, E>,
+{
+    fn edges_from(&self, src_id: usize) -> Result<Vec<(usize, &E)>> {
+        self.graph.edges_from(src_id)
+    }
+
+    fn edges_from_unchecked(&self, src_id: usize) -> Vec<(usize, &E)> {
+        self.graph.edges_from_unchecked(src_id)
+    }
+
+    fn edges_between(&self, src_id: usize, dst_id: usize) -> Result<Vec<&E>> {
+        self.graph.edges_between(src_id, dst_id)
+    }
+
+    fn edges_between_unchecked(&self, src_id: usize, dst_id: usize) -> Vec<&E> {
+        self.graph.edges_between_unchecked(src_id, dst_id)
+    }
+
+    fn edge_between(&self, src_id: usize, dst_id: usize, edge_id: usize) -> Result<&E> {
+        self.graph.edge_between(src_id, dst_id, edge_id)
+    }
+
+    fn edge_between_unchecked(&self, src_id: usize, dst_id: usize, edge_id: usize) -> &E {
+        self.graph.edge_between_unchecked(src_id, dst_id, edge_id)
+    }
+
+    fn edge(&self, edge_id: usize) -> Result<&E> {
+        self.graph.edge(edge_id)
+    }
+
+    fn edge_unchecked(&self, edge_id: usize) -> &E {
+        self.graph.edge_unchecked(edge_id)
+    }
+
+    fn has_any_edge(&self, src_id: usize, dst_id: usize) -> Result<bool> {
+        self.graph.has_any_edge(src_id, dst_id)
+    }
+
+    fn has_any_edge_unchecked(&self, src_id: usize, dst_id: usize) -> bool {
+        self.graph.has_any_edge_unchecked(src_id, dst_id)
+    }
+
+    fn edges(&self) -> Vec<(usize, usize, &E)> {
+        self.graph.edges()
+    }
+
+    fn as_directed_edges(&self) -> Vec<(usize, usize, &E)> {
+        self.graph.as_directed_edges()
+    }
+
+    fn edges_count(&self) -> usize {
+        self.graph.edges_count()
+    }
+
+    fn contains_edge(&self, edge_id: usize) -> bool {
+        self.graph.contains_edge(edge_id)
+    }
+}
+
+/// For documentation checkout [`Direction`](crate::provide::Direction) trait.
+impl<W, E, Dir, G> Direction<Dir> for TemporalGraph<W, E, Dir, G>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir>,
+{
+}
+
+/// For documentation about each function checkout [`Graph`](crate::provide::Graph) trait.
+impl<W, E, Dir, G> Graph<W, E, Dir> for TemporalGraph<W, E, Dir, G>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Vertices + Edges<W, E>,
+{
+    fn add_vertex(&mut self) -> usize {
+        self.graph.add_vertex()
+    }
+
+    fn remove_vertex(&mut self, vertex_id: usize) -> Result<()> {
+        if !self.graph.contains_vertex(vertex_id) {
+            Err(Error::new_vnf(vertex_id))?
+        }
+
+        self.remove_vertex_unchecked(vertex_id);
+
+        Ok(())
+    }
+
+    fn remove_vertex_unchecked(&mut self, vertex_id: usize) {
+        self.drop_intervals_of(vertex_id);
+
+        self.graph.remove_vertex_unchecked(vertex_id);
+    }
+
+    fn add_edge(&mut self, src_id: usize, dst_id: usize, edge: E) -> Result<usize> {
+        self.graph.add_edge(src_id, dst_id, edge)
+    }
+
+    fn add_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge: E) -> usize {
+        self.graph.add_edge_unchecked(src_id, dst_id, edge)
+    }
+
+    fn update_edge(&mut self, src_id: usize, dst_id: usize, edge_id: usize, edge: E) -> Result<()> {
+        self.graph.update_edge(src_id, dst_id, edge_id, edge)
+    }
+
+    fn update_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge_id: usize, edge: E) {
+        self.graph
+            .update_edge_unchecked(src_id, dst_id, edge_id, edge)
+    }
+
+    fn remove_edge(&mut self, src_id: usize, dst_id: usize, edge_id: usize) -> Result<E> {
+        let edge = self.graph.remove_edge(src_id, dst_id, edge_id)?;
+
+        self.intervals.remove(&edge_id);
+
+        Ok(edge)
+    }
+
+    fn remove_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge_id: usize) -> E {
+        self.intervals.remove(&edge_id);
+
+        self.graph.remove_edge_unchecked(src_id, dst_id, edge_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::has_path;
+    use crate::graph::{ListGraph, MatGraph};
+    use crate::storage::{DiList, List, Mat};
+
+    #[test]
+    fn intervals_of_edges() {
+        // Given:
+        let mut graph = TemporalGraph::init(ListGraph::init(DiList::<usize>::init()));
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+
+        // When:
+        let ab_id = graph.add_edge_at(a, b, 1.into(), 5).unwrap();
+        let ba_id = graph.add_edge(b, a, 1.into()).unwrap();
+
+        // Then:
+        assert_eq!(graph.interval_of(ab_id), Some(TimeInterval::init(5, 6)));
+        assert_eq!(graph.interval_of(ba_id), Some(TimeInterval::always()));
+        assert!(graph.interval_of(100).is_none());
+
+        // When: Changing the interval and removing the edges.
+        graph.set_interval(ba_id, TimeInterval::init(1, 2));
+        assert_eq!(graph.interval_of(ba_id), Some(TimeInterval::init(1, 2)));
+        graph.remove_vertex(b).unwrap();
+
+        // Then:
+        assert!(graph.intervals.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Interval can not end before it starts")]
+    fn invalid_interval() {
+        TimeInterval::init(2, 1);
+    }
+
+    #[test]
+    fn algorithms_run_on_snapshots() {
+        // Given: Undirected graph
+        //
+        //      a  --[0, 5)--  b  --[3, 8)--  c
+        //
+        let mut graph = TemporalGraph::init(MatGraph::init(Mat::<usize>::init()));
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph
+            .add_edge_during(a, b, 1.into(), TimeInterval::init(0, 5))
+            .unwrap();
+        graph
+            .add_edge_during(b, c, 1.into(), TimeInterval::init(3, 8))
+            .unwrap();
+
+        // Then: Reachability changes over time.
+        let connects_a_and_c = |time: usize| has_path(&graph.snapshot_at(time), a, c);
+        assert!(!connects_a_and_c(0));
+        assert!(connects_a_and_c(4));
+        assert!(!connects_a_and_c(5));
+        assert!(!has_path(&graph.snapshot_at(8), a, b));
+
+        // And: Algorithms that receive the temporal graph see every edge.
+        assert!(has_path(&graph, a, c));
+    }
+
+    #[test]
+    fn earliest_arrival_waits_for_edges() {
+        // Given: Undirected graph where a --- d is faster to take through b and c.
+        //
+        //      a  --[2, 3)--  b  --[4, 5)--  c  --[6, 7)--  d
+        //      |                                            |
+        //      '------------------[9, 10)-------------------'
+        //
+        let mut graph = TemporalGraph::init(ListGraph::init(List::<usize>::init()));
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_at(a, b, 1.into(), 2).unwrap();
+        graph.add_edge_at(b, c, 1.into(), 4).unwrap();
+        graph.add_edge_at(c, d, 1.into(), 6).unwrap();
+        graph.add_edge_at(a, d, 1.into(), 9).unwrap();
+
+        // When:
+        let from_start = graph.earliest_arrival_times(a, 0);
+        let from_three = graph.earliest_arrival_times(a, 3);
+        let backward = graph.earliest_arrival_times(d, 0);
+
+        // Then:
+        assert_eq!(
+            from_start,
+            vec![(a, 0), (b, 2), (c, 4), (d, 6)].into_iter().collect()
+        );
+        assert_eq!(from_three, vec![(a, 3), (d, 9)].into_iter().collect());
+        // Going backward the edges appear in the wrong order.
+        assert_eq!(backward, vec![(d, 0), (c, 6), (a, 9)].into_iter().collect());
+    }
+}
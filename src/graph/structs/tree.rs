@@ -0,0 +1,349 @@
+use anyhow::Result;
+use std::collections::VecDeque;
+
+use crate::graph::{Edge, EdgeDir, Error};
+use crate::provide::{Graph, IdMap, Neighbors, Vertices};
+
+/// A graph that is validated to be a tree, rooted at a given vertex.
+///
+/// Parent, children, depth and subtree size of every vertex are computed once when the tree is created,
+/// so they can be queried repeatedly without traversing the graph again.
+/// The graph is borrowed, so it can not change while the tree exists.
+///
+/// In undirected graphs any connected graph without cycles is a tree. In directed graphs edges must point from parents to children.
+///
+/// ## Generic Parameters
+/// * `G`: **G**raph that stores the tree.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::List;
+/// use prepona::graph::{ListGraph, Tree};
+///
+/// // Given: Graph
+/// //
+/// //            a
+/// //          /   \
+/// //         b     c
+/// //        / \
+/// //       d   e
+/// //
+/// let mut graph = ListGraph::init(List::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// let e = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(a, c, 1.into());
+/// graph.add_edge_unchecked(b, d, 1.into());
+/// graph.add_edge_unchecked(b, e, 1.into());
+///
+/// // When:
+/// let tree = Tree::init(&graph, a).unwrap();
+///
+/// // Then:
+/// assert_eq!(tree.parent(d), Some(b));
+/// assert_eq!(tree.children(b), &[d, e]);
+/// assert_eq!(tree.subtree_size(b), 3);
+/// assert_eq!(tree.depth(e), 2);
+/// assert_eq!(tree.euler_tour().collect::<Vec<_>>(), vec![a, b, d, b, e, b, a, c, a]);
+///
+/// // When: Adding an edge that makes a cycle.
+/// graph.add_edge_unchecked(c, d, 1.into());
+///
+/// // Then:
+/// assert!(Tree::init(&graph, a).is_err());
+/// ```
+pub struct Tree<'a, G> {
+    graph: &'a G,
+    root_id: usize,
+    id_map: IdMap,
+
+    // All of the following are indexed by virtual id.
+    parents: Vec<Option<usize>>,
+    children: Vec<Vec<usize>>,
+    depths: Vec<usize>,
+    subtree_sizes: Vec<usize>,
+}
+
+impl<'a, G> Tree<'a, G>
+where
+    G: Vertices + Neighbors,
+{
+    /// # Arguments
+    /// * `graph`: Graph that stores the tree.
+    /// * `root_id`: Id of the root.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing the tree.
+    /// * `Err`:
+    ///     * If there is no vertex with id: `root_id`.
+    ///     * If a vertex is not reachable from the root, or it's reachable through more than one path.
+    ///
+    /// # Complexity
+    /// O(|V| + |E|)
+    pub fn init<W, E, Dir>(graph: &'a G, root_id: usize) -> Result<Self>
+    where
+        E: Edge<W>,
+        Dir: EdgeDir,
+        G: Graph<W, E, Dir>,
+    {
+        if !graph.contains_vertex(root_id) {
+            Err(Error::new_vnf(root_id))?
+        }
+
+        let id_map = graph.continuos_id_map();
+        let vertex_count = id_map.len();
+
+        let mut parents = vec![None; vertex_count];
+        let mut children = vec![vec![]; vertex_count];
+        let mut depths = vec![0; vertex_count];
+        let mut discovered = vec![false; vertex_count];
+
+        let root_virt_id = id_map.virt_id_of(root_id);
+        discovered[root_virt_id] = true;
+
+        let mut order = vec![root_virt_id];
+        let mut queue = VecDeque::new();
+        queue.push_back(root_virt_id);
+        while let Some(virt_id) = queue.pop_front() {
+            let real_id = id_map.real_id_of(virt_id);
+
+            // In undirected graphs the parent is a neighbor too, but only through a single edge.
+            let mut parent_virt_id = parents[virt_id].filter(|_| Dir::is_undirected());
+            for n_id in graph.neighbors_unchecked(real_id) {
+                let n_virt_id = id_map.virt_id_of(n_id);
+
+                if parent_virt_id == Some(n_virt_id) {
+                    parent_virt_id = None;
+                } else if discovered[n_virt_id] {
+                    Err(Error::new_nat(n_id))?
+                } else {
+                    discovered[n_virt_id] = true;
+                    parents[n_virt_id] = Some(virt_id);
+                    children[virt_id].push(n_id);
+                    depths[n_virt_id] = depths[virt_id] + 1;
+                    order.push(n_virt_id);
+                    queue.push_back(n_virt_id);
+                }
+            }
+        }
+
+        if let Some(virt_id) = discovered.iter().position(|discovered| !discovered) {
+            Err(Error::new_nat(id_map.real_id_of(virt_id)))?
+        }
+
+        // Children come after their parent in breadth-first order, so sizes are accumulated in reverse.
+        let mut subtree_sizes = vec![1; vertex_count];
+        for virt_id in order.into_iter().rev() {
+            if let Some(parent_virt_id) = parents[virt_id] {
+                subtree_sizes[parent_virt_id] += subtree_sizes[virt_id];
+            }
+        }
+
+        Ok(Tree {
+            graph,
+            root_id,
+            id_map,
+
+            parents,
+            children,
+            depths,
+            subtree_sizes,
+        })
+    }
+
+    /// # Returns
+    /// The graph that stores the tree.
+    pub fn graph(&self) -> &'a G {
+        self.graph
+    }
+
+    /// # Returns
+    /// Id of the root.
+    pub fn root(&self) -> usize {
+        self.root_id
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// * `Some`: Containing id of the parent of the vertex.
+    /// * `None`: If vertex is the root.
+    ///
+    /// # Panics
+    /// If there is no vertex with id: `vertex_id`.
+    pub fn parent(&self, vertex_id: usize) -> Option<usize> {
+        self.parents[self.id_map.virt_id_of(vertex_id)]
+            .map(|parent_virt_id| self.id_map.real_id_of(parent_virt_id))
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// Id of the children of the vertex, in the same order as the neighbors of the vertex in the graph.
+    ///
+    /// # Panics
+    /// If there is no vertex with id: `vertex_id`.
+    pub fn children(&self, vertex_id: usize) -> &[usize] {
+        &self.children[self.id_map.virt_id_of(vertex_id)]
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// Number of edges between the vertex and the root.
+    ///
+    /// # Panics
+    /// If there is no vertex with id: `vertex_id`.
+    pub fn depth(&self, vertex_id: usize) -> usize {
+        self.depths[self.id_map.virt_id_of(vertex_id)]
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// Number of vertices in the subtree rooted at the vertex, including the vertex itself.
+    ///
+    /// # Panics
+    /// If there is no vertex with id: `vertex_id`.
+    pub fn subtree_size(&self, vertex_id: usize) -> usize {
+        self.subtree_sizes[self.id_map.virt_id_of(vertex_id)]
+    }
+
+    /// # Returns
+    /// Iterator over the Euler tour of the tree: A depth-first walk from the root that yields each vertex when it's entered,
+    /// and again after returning from each of its children. So it yields 2|V| - 1 vertices.
+    pub fn euler_tour(&self) -> EulerTour<'_, 'a, G> {
+        EulerTour {
+            tree: self,
+            stack: vec![(self.root_id, 0)],
+        }
+    }
+}
+
+/// Iterator over the Euler tour of a [`Tree`](crate::graph::Tree). For more info checkout [`euler_tour`](crate::graph::Tree::euler_tour).
+pub struct EulerTour<'t, 'a, G> {
+    tree: &'t Tree<'a, G>,
+
+    // Each entry is (id of a vertex, index of its next child to visit).
+    stack: Vec<(usize, usize)>,
+}
+
+impl<'t, 'a, G> Iterator for EulerTour<'t, 'a, G>
+where
+    G: Vertices + Neighbors,
+{
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (vertex_id, child_index) = self.stack.pop()?;
+
+        // Frame of the parent stays below the child, so the parent is yielded again once the child is finished.
+        if let Some(child_id) = self.tree.children(vertex_id).get(child_index) {
+            self.stack.push((vertex_id, child_index + 1));
+            self.stack.push((*child_id, 0));
+        }
+
+        Some(vertex_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{ListGraph, MultiListGraph};
+    use crate::storage::{DiList, List};
+
+    #[test]
+    fn invalid_trees() {
+        // Given: Graph
+        //
+        //      a  --  b  --  c       d
+        //
+        let mut graph = MultiListGraph::init(List::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+
+        // Then: d is not reachable.
+        assert!(Tree::init(&graph, a).is_err());
+        assert!(Tree::init(&graph, 10).is_err());
+
+        // When: Connecting d.
+        graph.add_edge_unchecked(c, d, 1.into());
+
+        // Then:
+        assert!(Tree::init(&graph, a).is_ok());
+        assert!(Tree::init(&graph, c).is_ok());
+
+        // When: Adding a parallel edge.
+        graph.add_edge_unchecked(c, b, 1.into());
+
+        // Then:
+        assert!(Tree::init(&graph, a).is_err());
+    }
+
+    #[test]
+    fn directed_tree() {
+        // Given: Graph
+        //
+        //      a  -->  b  -->  c
+        //      |
+        //      '-->  d
+        //
+        let mut graph = ListGraph::init(DiList::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(a, d, 1.into());
+
+        // When:
+        let tree = Tree::init(&graph, a).unwrap();
+
+        // Then:
+        assert_eq!(tree.root(), a);
+        assert_eq!(tree.parent(a), None);
+        assert_eq!(tree.parent(c), Some(b));
+        assert_eq!(tree.depth(c), 2);
+        assert_eq!(tree.subtree_size(a), 4);
+        assert_eq!(tree.subtree_size(d), 1);
+        assert_eq!(
+            tree.euler_tour().collect::<Vec<_>>(),
+            vec![a, b, c, b, a, d, a]
+        );
+        assert!(Tree::init(&graph, b).is_err());
+
+        // When: Adding an edge from child to its parent.
+        graph.add_edge_unchecked(c, b, 1.into());
+
+        // Then:
+        assert!(Tree::init(&graph, a).is_err());
+    }
+
+    #[test]
+    fn single_vertex() {
+        // Given:
+        let mut graph = ListGraph::init(List::<usize>::init());
+        let a = graph.add_vertex();
+
+        // When:
+        let tree = Tree::init(&graph, a).unwrap();
+
+        // Then:
+        assert!(tree.children(a).is_empty());
+        assert_eq!(tree.euler_tour().collect::<Vec<_>>(), vec![a]);
+    }
+}
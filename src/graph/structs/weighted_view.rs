@@ -0,0 +1,375 @@
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+use anyhow::Result;
+use magnitude::Magnitude;
+
+use crate::graph::{DefaultEdge, Edge, EdgeDir, Error};
+use crate::provide::{Direction, Edges, Neighbors, Vertices};
+
+/// A read only view of a graph that replaces the weights of its edges with weights computed by a closure.
+///
+/// It's specifically useful for graphs that are stored without weights while their weights live in an external table.
+/// The closure is called once for each edge when the view is created and the computed edges are kept by edge id,
+/// so the structure of the graph is never copied and every query is forwarded to the underlying graph.
+/// Edge ids are preserved.
+///
+/// The view provides the [`Direction`](crate::provide::Direction) of the underlying graph, so algorithms that only read the graph,
+/// like [`Dijkstra`](crate::algo::Dijkstra) and [`Kruskal`](crate::algo::Kruskal), can run on it.
+///
+/// For undirected graphs the closure is called with end points of each edge in the order returned by [`edges`](crate::provide::Edges::edges),
+/// so it should not depend on the order of its arguments.
+///
+/// ## Generic Parameters
+/// * `W`: **W**eight type associated with edges of the underlying graph.
+/// * `E`: **E**dge type that the underlying graph uses.
+/// * `G`: **G**raph to attach weights to.
+/// * `V`: Type of the weights computed by the closure(**V**alue).
+///
+/// # Examples
+/// ```
+/// use std::collections::HashMap;
+/// use prepona::prelude::*;
+/// use prepona::storage::DiList;
+/// use prepona::graph::{ListGraph, WeightedView};
+/// use prepona::algo::BestFirst;
+///
+/// // Given: Unweighted graph
+/// //
+/// //      a  -->  b  -->  c
+/// //      |               ^
+/// //      '---------------'
+/// //
+/// let mut graph = ListGraph::init(DiList::<()>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, ().into());
+/// graph.add_edge_unchecked(b, c, ().into());
+/// graph.add_edge_unchecked(a, c, ().into());
+///
+/// // And: Weights stored in a separate table.
+/// let mut table = HashMap::new();
+/// table.insert((a, b), 1);
+/// table.insert((b, c), 1);
+/// table.insert((a, c), 5);
+///
+/// // When: Running a shortest path search on the weighted view.
+/// let view = WeightedView::init(&graph, |src_id, dst_id| table[&(src_id, dst_id)]);
+/// let mut listener = ();
+/// let mut best_first = BestFirst::init(&view, &mut listener);
+/// best_first.execute(&view, a, 0, |cost, edge| cost + edge.get_weight().unwrap(), |_, cost| *cost);
+///
+/// // Then:
+/// assert_eq!(best_first.path_to(c), Some(vec![a, b, c]));
+/// ```
+pub struct WeightedView<'a, W, E, G, V> {
+    graph: &'a G,
+
+    weighted_edges: HashMap<usize, DefaultEdge<V>>,
+
+    phantom_w: PhantomData<W>,
+    phantom_e: PhantomData<E>,
+}
+
+impl<'a, W, E, G, V> WeightedView<'a, W, E, G, V>
+where
+    E: Edge<W>,
+    G: Vertices + Edges<W, E>,
+{
+    /// # Arguments
+    /// * `graph`: Graph to attach weights to.
+    /// * `weight_of`: Receives (`src_id`, `dst_id`) of an edge and returns its weight.
+    ///
+    /// # Returns
+    /// A view of `graph` in which weight of each edge is computed by `weight_of`.
+    ///
+    /// # Complexity
+    /// O(|E|) calls to `weight_of`.
+    pub fn init<F>(graph: &'a G, weight_of: F) -> Self
+    where
+        F: Fn(usize, usize) -> V,
+    {
+        let weighted_edges = graph
+            .edges()
+            .into_iter()
+            .map(|(src_id, dst_id, edge)| {
+                let mut weighted_edge =
+                    DefaultEdge::init(Magnitude::Finite(weight_of(src_id, dst_id)));
+                weighted_edge.set_id(edge.get_id());
+
+                (edge.get_id(), weighted_edge)
+            })
+            .collect();
+
+        WeightedView {
+            graph,
+            weighted_edges,
+
+            phantom_w: PhantomData,
+            phantom_e: PhantomData,
+        }
+    }
+
+    /// # Returns
+    /// The graph behind the view.
+    pub fn graph(&self) -> &'a G {
+        self.graph
+    }
+
+    fn weighted(&self, edge: &E) -> &DefaultEdge<V> {
+        &self.weighted_edges[&edge.get_id()]
+    }
+}
+
+/// For documentation about each function checkout [`Neighbors`](crate::provide::Neighbors) trait.
+impl<'a, W, E, G, V> Neighbors for WeightedView<'a, W, E, G, V>
+where
+    G: Neighbors,
+{
+    fn neighbors(&self, src_id: usize) -> Result<Vec<usize>> {
+        self.graph.neighbors(src_id)
+    }
+
+    fn neighbors_unchecked(&self, src_id: usize) -> Vec<usize> {
+        self.graph.neighbors_unchecked(src_id)
+    }
+}
+
+/// For documentation about each function checkout [`Vertices`](crate::provide::Vertices) trait.
+impl<'a, W, E, G, V> Vertices for WeightedView<'a, W, E, G, V>
+where
+    G: Vertices,
+{
+    fn vertices(&self) -> Vec<usize> {
+        self.graph.vertices()
+    }
+
+    fn vertex_count(&self) -> usize {
+        self.graph.vertex_count()
+    }
+
+    fn contains_vertex(&self, vertex_id: usize) -> bool {
+        self.graph.contains_vertex(vertex_id)
+    }
+}
+
+/// Edges of the view have the same direction as edges of the underlying graph.
+impl<'a, W, E, G, V, Dir> Direction<Dir> for WeightedView<'a, W, E, G, V>
+where
+    Dir: EdgeDir,
+    G: Direction<Dir>,
+{
+}
+
+/// For documentation about each function checkout [`Edges`](crate::provide::Edges) trait.
+impl<'a, W, E, G, V> Edges<V, DefaultEdge<V>> for WeightedView<'a, W, E, G, V>
+where
+    E: Edge<W>,
+    G: Vertices + Edges<W, E>,
+{
+    fn edges_from(&self, src_id: usize) -> Result<Vec<(usize, &DefaultEdge<V>)>> {
+        Ok(self
+            .graph
+            .edges_from(src_id)?
+            .into_iter()
+            .map(|(dst_id, edge)| (dst_id, self.weighted(edge)))
+            .collect())
+    }
+
+    fn edges_from_unchecked(&self, src_id: usize) -> Vec<(usize, &DefaultEdge<V>)> {
+        self.graph
+            .edges_from_unchecked(src_id)
+            .into_iter()
+            .map(|(dst_id, edge)| (dst_id, self.weighted(edge)))
+            .collect()
+    }
+
+    fn edges_between(&self, src_id: usize, dst_id: usize) -> Result<Vec<&DefaultEdge<V>>> {
+        Ok(self
+            .graph
+            .edges_between(src_id, dst_id)?
+            .into_iter()
+            .map(|edge| self.weighted(edge))
+            .collect())
+    }
+
+    fn edges_between_unchecked(&self, src_id: usize, dst_id: usize) -> Vec<&DefaultEdge<V>> {
+        self.graph
+            .edges_between_unchecked(src_id, dst_id)
+            .into_iter()
+            .map(|edge| self.weighted(edge))
+            .collect()
+    }
+
+    fn edge_between(
+        &self,
+        src_id: usize,
+        dst_id: usize,
+        edge_id: usize,
+    ) -> Result<&DefaultEdge<V>> {
+        let edge = self.graph.edge_between(src_id, dst_id, edge_id)?;
+
+        Ok(self.weighted(edge))
+    }
+
+    fn edge_between_unchecked(&self, _: usize, _: usize, edge_id: usize) -> &DefaultEdge<V> {
+        &self.weighted_edges[&edge_id]
+    }
+
+    fn edge(&self, edge_id: usize) -> Result<&DefaultEdge<V>> {
+        self.weighted_edges
+            .get(&edge_id)
+            .ok_or_else(|| Error::new_enf(edge_id).into())
+    }
+
+    fn edge_unchecked(&self, edge_id: usize) -> &DefaultEdge<V> {
+        &self.weighted_edges[&edge_id]
+    }
+
+    fn has_any_edge(&self, src_id: usize, dst_id: usize) -> Result<bool> {
+        self.graph.has_any_edge(src_id, dst_id)
+    }
+
+    fn has_any_edge_unchecked(&self, src_id: usize, dst_id: usize) -> bool {
+        self.graph.has_any_edge_unchecked(src_id, dst_id)
+    }
+
+    fn edges(&self) -> Vec<(usize, usize, &DefaultEdge<V>)> {
+        self.graph
+            .edges()
+            .into_iter()
+            .map(|(src_id, dst_id, edge)| (src_id, dst_id, self.weighted(edge)))
+            .collect()
+    }
+
+    fn as_directed_edges(&self) -> Vec<(usize, usize, &DefaultEdge<V>)> {
+        self.graph
+            .as_directed_edges()
+            .into_iter()
+            .map(|(src_id, dst_id, edge)| (src_id, dst_id, self.weighted(edge)))
+            .collect()
+    }
+
+    fn edges_count(&self) -> usize {
+        self.graph.edges_count()
+    }
+
+    fn contains_edge(&self, edge_id: usize) -> bool {
+        self.weighted_edges.contains_key(&edge_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::{Dijkstra, Kruskal};
+    use crate::graph::{FilterView, ListGraph, MatGraph};
+    use crate::provide::Graph;
+    use crate::storage::{DiList, List, Mat};
+
+    #[test]
+    fn weights_are_computed_per_edge() {
+        // Given: Graph
+        //
+        //      a  --  b  --  c
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let ab = graph.add_edge_unchecked(a, b, 1.into());
+        let bc = graph.add_edge_unchecked(b, c, 1.into());
+
+        // When: Using the sum of end points as weight.
+        let view = WeightedView::init(&graph, |src_id, dst_id| (src_id + dst_id) as f64 / 2.0);
+
+        // Then:
+        assert_eq!(view.edge(ab).unwrap().get_weight().unwrap(), 0.5);
+        assert_eq!(view.edge(bc).unwrap().get_weight().unwrap(), 1.5);
+        assert_eq!(view.edge_between(c, b, bc).unwrap().get_id(), bc);
+        assert_eq!(view.edges_from_unchecked(b).len(), 2);
+        assert_eq!(view.edges_count(), 2);
+        assert!(view.edge(7).is_err());
+        assert!(view.edges_from(7).is_err());
+        assert_eq!(view.neighbors_unchecked(a), vec![b]);
+    }
+
+    #[test]
+    fn composes_with_filter_view() {
+        // Given: Graph
+        //
+        //      a  --  b  --  c  --  d
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(c, d, 1.into());
+
+        // When: Weighting a filtered view.
+        let filtered = FilterView::vertices_only(&graph, |vertex_id| vertex_id != d);
+        let view = WeightedView::init(&filtered, |_, _| 10);
+
+        // Then:
+        assert_eq!(view.edges_count(), 2);
+        assert!(view
+            .edges()
+            .into_iter()
+            .all(|(_, _, edge)| *edge.get_weight() == 10.into()));
+        assert!(view.edges_from_unchecked(c).len() == 1);
+    }
+
+    #[test]
+    fn dijkstra_and_kruskal_on_view() {
+        // Given: Unweighted graph
+        //
+        //      a  -->  b  -->  c
+        //      |               ^
+        //      '---------------'
+        //
+        let mut graph = ListGraph::init(DiList::<()>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, ().into());
+        graph.add_edge_unchecked(b, c, ().into());
+        graph.add_edge_unchecked(a, c, ().into());
+
+        // When: Running dijkstra with a weight of 5 for a --> c and 1 for the rest.
+        let view = WeightedView::init(&graph, |src_id, dst_id| {
+            if (src_id, dst_id) == (a, c) {
+                5usize
+            } else {
+                1
+            }
+        });
+        let sp = Dijkstra::init(&view).execute(&view, a);
+
+        // Then:
+        assert_eq!(sp.distance_to(c), Some(2.into()));
+        assert_eq!(sp.path_to(c), Some(vec![a, b, c]));
+
+        // Given: Undirected version of the graph.
+        let mut graph = ListGraph::init(List::<()>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let ab = graph.add_edge_unchecked(a, b, ().into());
+        let bc = graph.add_edge_unchecked(b, c, ().into());
+        let ac = graph.add_edge_unchecked(a, c, ().into());
+
+        // When: Finding the minimum spanning tree of the view.
+        let view = WeightedView::init(&graph, |src_id, dst_id| src_id + dst_id);
+        let mst = Kruskal::init(&view).execute(&view);
+
+        // Then: b --- c is the heaviest edge.
+        assert_eq!(mst.edges_count(), 2);
+        assert!(mst.edge(ab).is_ok());
+        assert!(mst.edge(ac).is_ok());
+        assert!(mst.edge(bc).is_err());
+    }
+}
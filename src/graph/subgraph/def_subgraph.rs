@@ -4,7 +4,7 @@ use anyhow::Result;
 
 use crate::{
     graph::{error::Error, EdgeDir},
-    prelude::{Edge, Edges, Graph, Neighbors, Vertices},
+    prelude::{Direction, Edge, Edges, Neighbors, Vertices},
 };
 
 use super::{AsFrozenSubgraph, AsSubgraph};
@@ -22,7 +22,7 @@ pub struct Subgraph<'a, W, E, Dir, G>
 where
     E: Edge<W>,
     Dir: EdgeDir,
-    G: Graph<W, E, Dir>,
+    G: Direction<Dir>,
 {
     graph: &'a G,
 
@@ -38,7 +38,7 @@ impl<'a, W, E, Dir, G> Subgraph<'a, W, E, Dir, G>
 where
     E: Edge<W>,
     Dir: EdgeDir,
-    G: Graph<W, E, Dir> + Edges<W, E> + Neighbors,
+    G: Direction<Dir> + Edges<W, E> + Neighbors,
 {
     /// Initializes a subgraph with provided `edges` and `vertex_ids`.
     ///
@@ -70,7 +70,7 @@ impl<'a, W, E, Dir, G> Neighbors for Subgraph<'a, W, E, Dir, G>
 where
     E: Edge<W>,
     Dir: EdgeDir,
-    G: Graph<W, E, Dir> + Edges<W, E> + Neighbors,
+    G: Direction<Dir> + Edges<W, E> + Neighbors,
 {
     /// # Arguments:
     /// `src_id`: Id of the source vertex.
@@ -103,7 +103,7 @@ impl<'a, W, E, Dir, G> Vertices for Subgraph<'a, W, E, Dir, G>
 where
     E: Edge<W>,
     Dir: EdgeDir,
-    G: Graph<W, E, Dir>,
+    G: Direction<Dir>,
 {
     /// # Returns
     /// Id of vertices that are present in the graph.
@@ -123,7 +123,7 @@ impl<'a, W, E, Dir, G> Edges<W, E> for Subgraph<'a, W, E, Dir, G>
 where
     E: Edge<W>,
     Dir: EdgeDir,
-    G: Graph<W, E, Dir> + Edges<W, E> + Neighbors,
+    G: Direction<Dir> + Edges<W, E> + Neighbors,
 {
     /// # Arguments
     /// `src_id`: Id of the source vertex.
@@ -343,7 +343,7 @@ impl<'a, W, E, Dir, G> AsFrozenSubgraph<W, E> for Subgraph<'a, W, E, Dir, G>
 where
     E: Edge<W>,
     Dir: EdgeDir,
-    G: Graph<W, E, Dir> + Edges<W, E> + Neighbors,
+    G: Direction<Dir> + Edges<W, E> + Neighbors,
 {
 }
 
@@ -351,7 +351,7 @@ impl<'a, W, E, Dir, G> AsSubgraph<W, E> for Subgraph<'a, W, E, Dir, G>
 where
     E: Edge<W>,
     Dir: EdgeDir,
-    G: Graph<W, E, Dir> + Vertices + Neighbors + Edges<W, E>,
+    G: Direction<Dir> + Vertices + Neighbors + Edges<W, E>,
 {
     /// Removes an edge from the subgraph.
     ///
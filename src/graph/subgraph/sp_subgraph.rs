@@ -1,7 +1,7 @@
 use std::collections::{HashMap, HashSet};
 
 use magnitude::Magnitude;
-use provide::{Edges, Graph, Neighbors, Vertices};
+use provide::{Direction, Edges, Neighbors, Vertices};
 
 use super::{AsFrozenSubgraph, Subgraph};
 use crate::graph::{Edge, EdgeDir};
@@ -9,8 +9,10 @@ use crate::provide;
 
 /// Subgraph containing edges and vertices that participate in the shortest path tree.
 ///
-/// It also carries a distance map to answer queries about shortest paths from source vertex to any destination vertex in O(1).
-/// This subgraph will be returned from algorithms like [`Dijkstra`](crate::algo::Dijkstra) or [BellmanFord](crate::algo::BellmanFord).
+/// It also carries a distance map to answer queries about shortest paths from source vertex to any destination vertex in O(1),
+/// and the parent of each vertex in the tree to reconstruct the paths themselves.
+/// All single source shortest path algorithms return this subgraph, so code that consumes the result does not depend on the algorithm:
+/// [`BfsShortestPath`](crate::algo::BfsShortestPath), [`Dijkstra`](crate::algo::Dijkstra), [`BellmanFord`](crate::algo::BellmanFord) and [`AStar`](crate::algo::AStar).
 ///
 /// ## Generic Parameters
 /// * `W`: **W**eight type associated with edges.
@@ -21,9 +23,10 @@ pub struct ShortestPathSubgraph<'a, W, E, Dir, G>
 where
     E: Edge<W>,
     Dir: EdgeDir,
-    G: Graph<W, E, Dir> + Edges<W, E> + Neighbors,
+    G: Direction<Dir> + Edges<W, E> + Neighbors,
 {
     distance_map: HashMap<usize, Magnitude<W>>,
+    parents: HashMap<usize, usize>,
     subgraph: Subgraph<'a, W, E, Dir, G>,
 }
 
@@ -32,11 +35,11 @@ where
     W: Copy,
     E: Edge<W>,
     Dir: EdgeDir,
-    G: Graph<W, E, Dir> + Edges<W, E> + Neighbors,
+    G: Direction<Dir> + Edges<W, E> + Neighbors,
 {
     /// # Arguments
     /// * `graph`: Graph that owns the `edges` and `vertices`.
-    /// * `edges`: Edges that are in the subgraph in the format of: (src_id, dst_id, edge).
+    /// * `edges`: Edges that are in the subgraph in the format of: (src_id, dst_id, edge_id). `src_id` must be the parent of `dst_id` in the tree.
     /// * `vertices`: Vertices that are in the subgraph.
     /// * `distance_map`: Maps each vertex with id: `dst_id` to its (shortest)distance from vertex with id: `src_id`.
     ///
@@ -47,9 +50,37 @@ where
         edges: Vec<(usize, usize, usize)>,
         vertices: HashSet<usize>,
         distance_map: HashMap<usize, Magnitude<W>>,
+    ) -> Self {
+        let parents = edges
+            .iter()
+            .map(|(src_id, dst_id, _)| (*dst_id, *src_id))
+            .collect();
+
+        ShortestPathSubgraph::init_with_parents(graph, edges, vertices, distance_map, parents)
+    }
+
+    /// Same as [`init`](crate::graph::subgraph::ShortestPathSubgraph::init), but parents of vertices are given separately from the edges.
+    /// It's used when the tree is searched against the direction of the edges, like with [`Reversed`](crate::algo::Orientation::Reversed) orientation.
+    ///
+    /// # Arguments
+    /// * `graph`: Graph that owns the `edges` and `vertices`.
+    /// * `edges`: Edges that are in the subgraph in the format of: (src_id, dst_id, edge_id), in their direction in the graph.
+    /// * `vertices`: Vertices that are in the subgraph.
+    /// * `distance_map`: Maps each vertex with id: `dst_id` to its (shortest)distance from vertex with id: `src_id`.
+    /// * `parents`: Maps each vertex to its parent in the tree.
+    ///
+    /// # Returns
+    /// Initialized subgraph containing the specified `edges` and `vertices`.
+    pub fn init_with_parents(
+        graph: &'a G,
+        edges: Vec<(usize, usize, usize)>,
+        vertices: HashSet<usize>,
+        distance_map: HashMap<usize, Magnitude<W>>,
+        parents: HashMap<usize, usize>,
     ) -> Self {
         ShortestPathSubgraph {
             distance_map,
+            parents,
             subgraph: Subgraph::init(graph, edges, vertices),
         }
     }
@@ -66,6 +97,47 @@ where
     pub fn distance_to(&self, dst_id: usize) -> Option<Magnitude<W>> {
         self.distance_map.get(&dst_id).copied()
     }
+
+    /// Same as [`distance_to`](crate::graph::subgraph::ShortestPathSubgraph::distance_to).
+    pub fn dist(&self, dst_id: usize) -> Option<Magnitude<W>> {
+        self.distance_to(dst_id)
+    }
+
+    /// # Arguments
+    /// * `dst_id`: Id of the destination vertex.
+    ///
+    /// # Returns
+    /// * `Some`: Containing id of the vertex right before `dst_id` on the shortest path from source vertex.
+    /// * `None`: If `dst_id` is the source vertex or it's not in the tree.
+    ///
+    /// # Complexity
+    /// O(1)
+    pub fn parent(&self, dst_id: usize) -> Option<usize> {
+        self.parents.get(&dst_id).copied()
+    }
+
+    /// # Arguments
+    /// * `dst_id`: Id of the destination vertex.
+    ///
+    /// # Returns
+    /// * `Some`: Containing ids of the vertices on the shortest path from source vertex to `dst_id`, including both ends.
+    /// * `None`: If distance of `dst_id` is unknown or infinite.
+    ///
+    /// # Complexity
+    /// O(length of the path)
+    pub fn path_to(&self, dst_id: usize) -> Option<Vec<usize>> {
+        if !self.dist(dst_id)?.is_finite() {
+            return None;
+        }
+
+        let mut path = vec![dst_id];
+        while let Some(parent_id) = self.parent(*path.last().unwrap()) {
+            path.push(parent_id);
+        }
+        path.reverse();
+
+        Some(path)
+    }
 }
 
 /// `ShortestPathSubgraph` uses `Subgraph` internally so for more info checkout [`Subgraph`](crate::graph::subgraph::Subgraph).
@@ -73,7 +145,7 @@ impl<'a, W, E, Dir, G> Neighbors for ShortestPathSubgraph<'a, W, E, Dir, G>
 where
     E: Edge<W>,
     Dir: EdgeDir,
-    G: Graph<W, E, Dir> + Edges<W, E> + Neighbors,
+    G: Direction<Dir> + Edges<W, E> + Neighbors,
 {
     fn neighbors(&self, src_id: usize) -> anyhow::Result<Vec<usize>> {
         self.subgraph.neighbors(src_id)
@@ -89,7 +161,7 @@ impl<'a, W, E, Dir, G> Vertices for ShortestPathSubgraph<'a, W, E, Dir, G>
 where
     E: Edge<W>,
     Dir: EdgeDir,
-    G: Graph<W, E, Dir> + Edges<W, E> + Neighbors,
+    G: Direction<Dir> + Edges<W, E> + Neighbors,
 {
     fn vertices(&self) -> Vec<usize> {
         self.subgraph.vertices()
@@ -105,7 +177,7 @@ impl<'a, W, E, Dir, G> Edges<W, E> for ShortestPathSubgraph<'a, W, E, Dir, G>
 where
     E: Edge<W>,
     Dir: EdgeDir,
-    G: Graph<W, E, Dir> + Edges<W, E> + Neighbors,
+    G: Direction<Dir> + Edges<W, E> + Neighbors,
 {
     fn edges_from(&self, src_id: usize) -> anyhow::Result<Vec<(usize, &E)>> {
         self.subgraph.edges_from(src_id)
@@ -169,6 +241,6 @@ impl<'a, W, E, Dir, G> AsFrozenSubgraph<W, E> for ShortestPathSubgraph<'a, W, E,
 where
     E: Edge<W>,
     Dir: EdgeDir,
-    G: Graph<W, E, Dir> + Edges<W, E> + Neighbors,
+    G: Direction<Dir> + Edges<W, E> + Neighbors,
 {
 }
@@ -0,0 +1,160 @@
+use num_traits::One;
+use std::any::Any;
+use std::collections::{HashMap, HashSet};
+
+use super::{is_skippable, tokens_of, vertex_of};
+use crate::graph::{Edge, EdgeDir};
+use crate::storage::GraphStorage;
+
+/// Reads the adjacency list described in `text` into `storage`.
+///
+/// Vertices are labeled in the text and each label that is seen for the first time gets a new vertex in the storage.
+///
+/// # Arguments
+/// * `storage`: Storage to add the vertices and edges to.
+/// * `text`: Adjacency list.
+///
+/// # Returns
+/// A map from each label to the id of its vertex in the storage.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::List;
+/// use prepona::io::adj_list;
+///
+/// let mut storage = List::<usize>::init();
+///
+/// let labels = adj_list::read(&mut storage, "a b c\nb c\nd\n");
+///
+/// assert_eq!(storage.vertex_count(), 4);
+/// assert_eq!(storage.edge_count(), 3);
+/// assert!(storage.neighbors_unchecked(labels["d"]).is_empty());
+/// ```
+pub fn read<W, E, Dir, S>(storage: &mut S, text: &str) -> HashMap<String, usize>
+where
+    W: Any + One,
+    E: Edge<W>,
+    Dir: EdgeDir,
+    S: GraphStorage<W, E, Dir>,
+{
+    let mut labels = HashMap::new();
+
+    for line in text.lines().map(|line| line.trim()) {
+        if is_skippable(line) {
+            continue;
+        }
+
+        let tokens = tokens_of(line);
+        if tokens.is_empty() {
+            continue;
+        }
+
+        let src_id = vertex_of(storage, &mut labels, tokens[0]);
+        for dst_label in &tokens[1..] {
+            let dst_id = vertex_of(storage, &mut labels, dst_label);
+
+            storage.add_edge_unchecked(src_id, dst_id, E::init(W::one().into()));
+        }
+    }
+
+    labels
+}
+
+/// Writes the `storage` as an adjacency list.
+///
+/// Vertices are labeled by their ids. For undirected storages each edge is written only once.
+///
+/// # Arguments
+/// `storage`: Storage to write.
+///
+/// # Returns
+/// Adjacency list in the format of: `src_id dst_id1 dst_id2 ...` per line.
+pub fn write<W, E, Dir, S>(storage: &S) -> String
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    S: GraphStorage<W, E, Dir>,
+{
+    let mut written_edges = HashSet::new();
+
+    storage
+        .vertices()
+        .into_iter()
+        .map(|src_id| {
+            let mut line = src_id.to_string();
+
+            for (dst_id, edge) in storage.edges_from_unchecked(src_id) {
+                // In an undirected storage each edge is visited from both of its ends.
+                if Dir::is_directed() || written_edges.insert(edge.get_id()) {
+                    line.push_str(&format!(" {}", dst_id));
+                }
+            }
+            line.push('\n');
+
+            line
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{DiList, List};
+
+    #[test]
+    fn read_empty_text() {
+        // Given: An empty storage.
+        let mut storage = List::<usize>::init();
+
+        // When: Reading text with only comments.
+        let labels = read(&mut storage, "# comment\n");
+
+        // Then:
+        assert!(labels.is_empty());
+        assert_eq!(storage.vertex_count(), 0);
+    }
+
+    #[test]
+    fn read_directed() {
+        // Given: An empty storage.
+        let mut storage = DiList::<usize>::init();
+
+        // When: Reading an adjacency list.
+        let labels = read(&mut storage, "a b c\nb, c\nc\nd\n");
+
+        // Then:
+        let (a, b, c, d) = (labels["a"], labels["b"], labels["c"], labels["d"]);
+        assert_eq!(storage.vertex_count(), 4);
+        assert_eq!(storage.edge_count(), 3);
+        assert!(storage.has_any_edge_unchecked(a, b));
+        assert!(storage.has_any_edge_unchecked(a, c));
+        assert!(storage.has_any_edge_unchecked(b, c));
+        assert!(!storage.has_any_edge_unchecked(c, a));
+        assert!(storage.neighbors_unchecked(d).is_empty());
+    }
+
+    #[test]
+    fn write_then_read_undirected() {
+        // Given: Storage
+        //
+        //      a  ---  b  ---  c       d
+        //
+        let mut storage = List::<usize>::init();
+        let a = storage.add_vertex();
+        let b = storage.add_vertex();
+        let c = storage.add_vertex();
+        storage.add_vertex();
+        storage.add_edge_unchecked(a, b, 1.into());
+        storage.add_edge_unchecked(b, c, 1.into());
+
+        // When: Writing the storage and reading it back.
+        let text = write(&storage);
+        let mut read_storage = List::<usize>::init();
+        read(&mut read_storage, &text);
+
+        // Then:
+        assert_eq!(read_storage.vertex_count(), 4);
+        assert_eq!(read_storage.edge_count(), 2);
+    }
+}
@@ -0,0 +1,537 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::algo::ShortestPathDag;
+use crate::graph::{Edge, EdgeDir};
+use crate::provide::{Edges, Graph, Vertices};
+
+// Color of highlighted vertices and edges.
+const HIGHLIGHT_COLOR: &str = "red";
+
+// Graphviz color scheme used for coloring classes of vertices. Its colors are referred to by `1` to `PALETTE_SIZE`.
+const PALETTE: &str = "set312";
+const PALETTE_SIZE: usize = 12;
+
+/// DOT attributes of vertices and edges, to be written by [`write_annotated`](crate::io::dot::write_annotated).
+///
+/// Adapters in this module like [`highlight_path`](crate::io::dot::highlight_path) turn results of algorithms into attributes,
+/// and attributes of several results can be combined using [`merge`](crate::io::dot::DotAttributes::merge).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DotAttributes {
+    vertex_attrs: HashMap<usize, BTreeMap<String, String>>,
+    edge_attrs: HashMap<usize, BTreeMap<String, String>>,
+}
+
+impl DotAttributes {
+    /// # Returns
+    /// Attributes without any entry.
+    pub fn init() -> Self {
+        DotAttributes::default()
+    }
+
+    /// Sets attribute `key` of vertex with id: `vertex_id` to `value`.
+    ///
+    /// # Arguments
+    /// * `vertex_id`: Id of the vertex.
+    /// * `key`: Name of the attribute, like `color`.
+    /// * `value`: Value of the attribute.
+    pub fn set_vertex_attr(&mut self, vertex_id: usize, key: &str, value: &str) {
+        self.vertex_attrs
+            .entry(vertex_id)
+            .or_default()
+            .insert(key.to_string(), value.to_string());
+    }
+
+    /// Sets attribute `key` of edge with id: `edge_id` to `value`.
+    ///
+    /// # Arguments
+    /// * `edge_id`: Id of the edge.
+    /// * `key`: Name of the attribute, like `color`.
+    /// * `value`: Value of the attribute.
+    pub fn set_edge_attr(&mut self, edge_id: usize, key: &str, value: &str) {
+        self.edge_attrs
+            .entry(edge_id)
+            .or_default()
+            .insert(key.to_string(), value.to_string());
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// Attributes of the vertex in the format of (`key`, `value`), sorted by key.
+    pub fn vertex_attrs(&self, vertex_id: usize) -> Vec<(String, String)> {
+        Self::entries_of(self.vertex_attrs.get(&vertex_id))
+    }
+
+    /// # Arguments
+    /// `edge_id`: Id of the edge.
+    ///
+    /// # Returns
+    /// Attributes of the edge in the format of (`key`, `value`), sorted by key.
+    pub fn edge_attrs(&self, edge_id: usize) -> Vec<(String, String)> {
+        Self::entries_of(self.edge_attrs.get(&edge_id))
+    }
+
+    fn entries_of(attrs: Option<&BTreeMap<String, String>>) -> Vec<(String, String)> {
+        attrs.map_or(vec![], |attrs| {
+            attrs
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect()
+        })
+    }
+
+    /// Adds attributes of `other` to these attributes. Attributes set in both are overwritten by `other`.
+    ///
+    /// # Arguments
+    /// `other`: Attributes to add.
+    ///
+    /// # Returns
+    /// Merged attributes.
+    pub fn merge(mut self, other: DotAttributes) -> Self {
+        for (vertex_id, attrs) in other.vertex_attrs {
+            self.vertex_attrs
+                .entry(vertex_id)
+                .or_default()
+                .extend(attrs);
+        }
+        for (edge_id, attrs) in other.edge_attrs {
+            self.edge_attrs.entry(edge_id).or_default().extend(attrs);
+        }
+
+        self
+    }
+}
+
+// Formats attributes as a DOT attribute list, or returns an empty string if there is no attribute.
+fn format_attrs(attrs: &[(String, String)]) -> String {
+    if attrs.is_empty() {
+        String::new()
+    } else {
+        let attrs: Vec<String> = attrs
+            .iter()
+            .map(|(key, value)| format!("{}=\"{}\"", key, value.replace('"', "\\\"")))
+            .collect();
+
+        format!(" [{}]", attrs.join(", "))
+    }
+}
+
+/// Writes the `graph` in DOT format.
+///
+/// Each vertex is written as a node with its vertex id as the DOT id. Loops of undirected graphs are written once.
+///
+/// # Arguments
+/// * `graph`: Graph to write.
+/// * `vertex_attrs_of`: Returns attributes of a vertex given its id, in the format of (`key`, `value`).
+/// * `edge_attrs_of`: Returns attributes of an edge given its source, destination and the edge itself, in the format of (`key`, `value`).
+///
+/// # Returns
+/// DOT text.
+pub fn write<W, E, Dir, G, VF, EF>(graph: &G, vertex_attrs_of: VF, edge_attrs_of: EF) -> String
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Vertices + Edges<W, E> + Graph<W, E, Dir>,
+    VF: Fn(usize) -> Vec<(String, String)>,
+    EF: Fn(usize, usize, &E) -> Vec<(String, String)>,
+{
+    let (keyword, connector) = if Dir::is_directed() {
+        ("digraph", "->")
+    } else {
+        ("graph", "--")
+    };
+
+    let mut text = format!("{} {{\n", keyword);
+
+    for vertex_id in graph.vertices() {
+        text.push_str(&format!(
+            "  {}{};\n",
+            vertex_id,
+            format_attrs(&vertex_attrs_of(vertex_id))
+        ));
+    }
+
+    let mut written_edge_ids = HashSet::new();
+    for (src_id, dst_id, edge) in graph.edges() {
+        if written_edge_ids.insert(edge.get_id()) {
+            text.push_str(&format!(
+                "  {} {} {}{};\n",
+                src_id,
+                connector,
+                dst_id,
+                format_attrs(&edge_attrs_of(src_id, dst_id, edge))
+            ));
+        }
+    }
+
+    text.push_str("}\n");
+
+    text
+}
+
+/// Writes the `graph` in DOT format, with the given attributes.
+///
+/// # Arguments
+/// * `graph`: Graph to write.
+/// * `attributes`: Attributes of vertices and edges.
+///
+/// # Returns
+/// DOT text.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::DiList;
+/// use prepona::graph::ListGraph;
+/// use prepona::io::dot;
+///
+/// // Given: Graph
+/// //
+/// //      a  -->  b  -->  c
+/// //      |               ^
+/// //      '---------------'
+/// //
+/// let mut graph = ListGraph::init(DiList::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(a, c, 1.into());
+///
+/// // When: Highlighting path a --> b --> c.
+/// let text = dot::write_annotated(&graph, &dot::highlight_path(&graph, &[a, b, c]));
+///
+/// // Then:
+/// assert!(text.contains(&format!("{} -> {} [color=\"red\", penwidth=\"2\"];", a, b)));
+/// assert!(text.contains(&format!("{} -> {};", a, c)));
+/// ```
+pub fn write_annotated<W, E, Dir, G>(graph: &G, attributes: &DotAttributes) -> String
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Vertices + Edges<W, E> + Graph<W, E, Dir>,
+{
+    write(
+        graph,
+        |vertex_id| attributes.vertex_attrs(vertex_id),
+        |_, _, edge| attributes.edge_attrs(edge.get_id()),
+    )
+}
+
+fn highlight_edge(attributes: &mut DotAttributes, edge_id: usize, color: &str) {
+    attributes.set_edge_attr(edge_id, "color", color);
+    attributes.set_edge_attr(edge_id, "penwidth", "2");
+}
+
+fn fill_vertex(attributes: &mut DotAttributes, vertex_id: usize, class: usize) {
+    attributes.set_vertex_attr(vertex_id, "style", "filled");
+    attributes.set_vertex_attr(vertex_id, "colorscheme", PALETTE);
+    attributes.set_vertex_attr(
+        vertex_id,
+        "fillcolor",
+        &(class % PALETTE_SIZE + 1).to_string(),
+    );
+}
+
+/// Highlights vertices of a path and the edges connecting them.
+///
+/// If there are parallel edges between two consecutive vertices, the one with the smallest id is highlighted.
+///
+/// # Arguments
+/// * `graph`: Graph containing the path.
+/// * `path`: Ids of vertices on the path, in order.
+///
+/// # Returns
+/// Attributes that color the path.
+pub fn highlight_path<W, E, Dir, G>(graph: &G, path: &[usize]) -> DotAttributes
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Edges<W, E> + Graph<W, E, Dir>,
+{
+    let mut attributes = DotAttributes::init();
+
+    for vertex_id in path {
+        attributes.set_vertex_attr(*vertex_id, "color", HIGHLIGHT_COLOR);
+    }
+
+    for pair in path.windows(2) {
+        let edge_id = graph
+            .edges_between_unchecked(pair[0], pair[1])
+            .into_iter()
+            .map(|edge| edge.get_id())
+            .min();
+
+        if let Some(edge_id) = edge_id {
+            highlight_edge(&mut attributes, edge_id, HIGHLIGHT_COLOR);
+        }
+    }
+
+    attributes
+}
+
+/// Fills each vertex with the color of its class, like the colors computed by [`color_with_k`](crate::algo::color_with_k).
+///
+/// Classes are mapped to the 12 colors of the `set312` Graphviz color scheme, so classes that differ by a multiple of 12 look the same.
+///
+/// # Arguments
+/// `classes`: Maps id of each vertex to its class.
+///
+/// # Returns
+/// Attributes that fill the vertices.
+pub fn color_classes(classes: &HashMap<usize, usize>) -> DotAttributes {
+    let mut attributes = DotAttributes::init();
+
+    for (vertex_id, class) in classes {
+        fill_vertex(&mut attributes, *vertex_id, *class);
+    }
+
+    attributes
+}
+
+/// Fills vertices of each component with the color of the component, like the components found by [`TarjanSCC`](crate::algo::TarjanSCC).
+///
+/// Edges inside a component are drawn solid and edges between components are drawn dashed.
+///
+/// # Arguments
+/// * `graph`: Graph containing the components.
+/// * `components`: Each component is a vector of its vertex ids.
+///
+/// # Returns
+/// Attributes that color the components.
+pub fn components<W, E, Dir, G>(graph: &G, components: &[Vec<usize>]) -> DotAttributes
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Edges<W, E> + Graph<W, E, Dir>,
+{
+    let mut attributes = DotAttributes::init();
+    let mut component_of = HashMap::new();
+
+    for (index, component) in components.iter().enumerate() {
+        for vertex_id in component {
+            fill_vertex(&mut attributes, *vertex_id, index);
+            component_of.insert(*vertex_id, index);
+        }
+    }
+
+    for (src_id, dst_id, edge) in graph.edges() {
+        match (component_of.get(&src_id), component_of.get(&dst_id)) {
+            (Some(src_component), Some(dst_component)) if src_component != dst_component => {
+                attributes.set_edge_attr(edge.get_id(), "style", "dashed")
+            }
+            _ => {}
+        }
+    }
+
+    attributes
+}
+
+/// Highlights a shortest path tree: The source and, for each reached vertex, the first edge among the last hops of its shortest paths.
+///
+/// # Arguments
+/// `dag`: Shortest paths computed by [`shortest_path_dag`](crate::algo::shortest_path_dag).
+///
+/// # Returns
+/// Attributes that color the tree.
+pub fn shortest_path_tree<W: Copy>(dag: &ShortestPathDag<W>) -> DotAttributes {
+    let mut attributes = DotAttributes::init();
+
+    attributes.set_vertex_attr(dag.src_id(), "shape", "doublecircle");
+    for vertex_id in dag.order() {
+        attributes.set_vertex_attr(*vertex_id, "color", HIGHLIGHT_COLOR);
+
+        if let Some((_, edge_id)) = dag.predecessors(*vertex_id).first() {
+            highlight_edge(&mut attributes, *edge_id, HIGHLIGHT_COLOR);
+        }
+    }
+
+    attributes
+}
+
+/// Highlights matched edges and fills matched vertices, like the pairs found by [`hungarian`](crate::algo::hungarian).
+///
+/// If there are parallel edges between a matched pair, the one with the smallest id is highlighted.
+///
+/// # Arguments
+/// * `graph`: Graph containing the matching.
+/// * `pairs`: Matched pairs in the format of (`src_id`, `dst_id`).
+///
+/// # Returns
+/// Attributes that color the matching.
+pub fn matching<W, E, Dir, G>(graph: &G, pairs: &[(usize, usize)]) -> DotAttributes
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Edges<W, E> + Graph<W, E, Dir>,
+{
+    let mut attributes = DotAttributes::init();
+
+    for (src_id, dst_id) in pairs {
+        for vertex_id in [*src_id, *dst_id] {
+            attributes.set_vertex_attr(vertex_id, "style", "filled");
+            attributes.set_vertex_attr(vertex_id, "fillcolor", "lightgray");
+        }
+
+        let edge_id = graph
+            .edges_between_unchecked(*src_id, *dst_id)
+            .into_iter()
+            .map(|edge| edge.get_id())
+            .min();
+        if let Some(edge_id) = edge_id {
+            attributes.set_edge_attr(edge_id, "penwidth", "3");
+        }
+    }
+
+    attributes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::{shortest_path_dag, TarjanSCC};
+    use crate::graph::{ListGraph, MatGraph};
+    use crate::storage::{DiList, List, Mat};
+
+    #[test]
+    fn write_empty_graph() {
+        // Given:
+        let graph = ListGraph::init(DiList::<usize>::init());
+
+        // When:
+        let text = write_annotated(&graph, &DotAttributes::init());
+
+        // Then:
+        assert_eq!(text, "digraph {\n}\n");
+    }
+
+    #[test]
+    fn write_undirected_loop_once() {
+        // Given: a --- b with a loop on a.
+        let mut graph = ListGraph::init(List::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(a, a, 1.into());
+
+        // When:
+        let text = write(
+            &graph,
+            |vertex_id| vec![("label".to_string(), format!("v\"{}\"", vertex_id))],
+            |_, _, _| vec![],
+        );
+
+        // Then:
+        assert!(text.starts_with("graph {\n"));
+        assert_eq!(text.matches(&format!("{} -- {};", a, a)).count(), 1);
+        assert!(text.contains(&format!("{} [label=\"v\\\"{}\\\"\"];", a, a)));
+    }
+
+    #[test]
+    fn merge_overwrites() {
+        // Given:
+        let mut first = DotAttributes::init();
+        first.set_vertex_attr(0, "color", "red");
+        first.set_vertex_attr(0, "shape", "box");
+        let mut second = DotAttributes::init();
+        second.set_vertex_attr(0, "color", "blue");
+        second.set_edge_attr(1, "style", "dashed");
+
+        // When:
+        let merged = first.merge(second);
+
+        // Then:
+        assert_eq!(
+            merged.vertex_attrs(0),
+            vec![
+                ("color".to_string(), "blue".to_string()),
+                ("shape".to_string(), "box".to_string())
+            ]
+        );
+        assert_eq!(
+            merged.edge_attrs(1),
+            vec![("style".to_string(), "dashed".to_string())]
+        );
+        assert!(merged.vertex_attrs(1).is_empty());
+    }
+
+    #[test]
+    fn scc_components() {
+        // Given: a <--> b --> c
+        let mut graph = ListGraph::init(DiList::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let ab = graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, a, 1.into());
+        let bc = graph.add_edge_unchecked(b, c, 1.into());
+
+        // When:
+        let sccs = TarjanSCC::init(&graph).execute(&graph);
+        let attributes = components(&graph, &sccs);
+
+        // Then:
+        let fill_of = |vertex_id| {
+            attributes
+                .vertex_attrs(vertex_id)
+                .into_iter()
+                .find(|(key, _)| key == "fillcolor")
+                .unwrap()
+                .1
+        };
+        assert_eq!(fill_of(a), fill_of(b));
+        assert_ne!(fill_of(a), fill_of(c));
+        assert!(attributes.edge_attrs(ab).is_empty());
+        assert_eq!(
+            attributes.edge_attrs(bc),
+            vec![("style".to_string(), "dashed".to_string())]
+        );
+    }
+
+    #[test]
+    fn tree_and_matching() {
+        // Given: a --- b --- c --- d
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let ab = graph.add_edge_unchecked(a, b, 1.into());
+        let bc = graph.add_edge_unchecked(b, c, 1.into());
+        let cd = graph.add_edge_unchecked(c, d, 1.into());
+
+        // When:
+        let tree = shortest_path_tree(&shortest_path_dag(&graph, b));
+        let matched = matching(&graph, &[(a, b), (d, c)]);
+
+        // Then:
+        assert_eq!(
+            tree.vertex_attrs(b),
+            vec![
+                ("color".to_string(), "red".to_string()),
+                ("shape".to_string(), "doublecircle".to_string())
+            ]
+        );
+        assert!([ab, bc, cd]
+            .iter()
+            .all(|edge_id| !tree.edge_attrs(*edge_id).is_empty()));
+        assert!(!matched.edge_attrs(ab).is_empty());
+        assert!(!matched.edge_attrs(cd).is_empty());
+        assert!(matched.edge_attrs(bc).is_empty());
+    }
+
+    #[test]
+    fn coloring_classes() {
+        // Given:
+        let classes: HashMap<usize, usize> = vec![(0, 0), (1, 12), (2, 1)].into_iter().collect();
+
+        // When:
+        let attributes = color_classes(&classes);
+
+        // Then:
+        assert_eq!(attributes.vertex_attrs(0), attributes.vertex_attrs(1));
+        assert_ne!(attributes.vertex_attrs(0), attributes.vertex_attrs(2));
+    }
+}
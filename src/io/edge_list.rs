@@ -0,0 +1,237 @@
+use anyhow::Result;
+use num_traits::One;
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use super::{format_weight, is_skippable, parse_weight, tokens_of, vertex_of, Error};
+use crate::graph::{Edge, EdgeDir};
+use crate::storage::GraphStorage;
+
+/// Reads edges described in `text` into `storage`.
+///
+/// Vertices are labeled in the text and each label that is seen for the first time gets a new vertex in the storage.
+///
+/// # Arguments
+/// * `storage`: Storage to add the vertices and edges to.
+/// * `text`: Edge list.
+///
+/// # Returns
+/// * `Ok`: Containing a map from each label to the id of its vertex in the storage.
+/// * `Err`: If any line of the text is malformed. In this case nothing is added to the storage.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::DiList;
+/// use prepona::io::edge_list;
+///
+/// let mut storage = DiList::<usize>::init();
+///
+/// let labels = edge_list::read(&mut storage, "# a comment\n1 2 5\n2,3\n").unwrap();
+///
+/// assert_eq!(storage.vertex_count(), 3);
+/// assert_eq!(storage.edge_count(), 2);
+/// let edges = storage.edges_between_unchecked(labels["1"], labels["2"]);
+/// assert_eq!(edges[0].get_weight(), &5.into());
+/// ```
+pub fn read<W, E, Dir, S>(storage: &mut S, text: &str) -> Result<HashMap<String, usize>>
+where
+    W: FromStr + Any + One,
+    E: Edge<W>,
+    Dir: EdgeDir,
+    S: GraphStorage<W, E, Dir>,
+{
+    // Parse all lines before touching the storage, so a malformed line leaves the storage untouched.
+    let mut parsed_edges = vec![];
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if is_skippable(line) {
+            continue;
+        }
+
+        let tokens = tokens_of(line);
+        if tokens.len() < 2 {
+            Err(Error::new_il(index + 1, line))?
+        }
+
+        let weight = if let Some(token) = tokens.get(2) {
+            match parse_weight(token) {
+                Some(weight) => weight,
+                None => Err(Error::new_iw(index + 1, token))?,
+            }
+        } else {
+            W::one().into()
+        };
+
+        parsed_edges.push((tokens[0], tokens[1], weight));
+    }
+
+    let mut labels = HashMap::new();
+    for (src_label, dst_label, weight) in parsed_edges {
+        let src_id = vertex_of(storage, &mut labels, src_label);
+        let dst_id = vertex_of(storage, &mut labels, dst_label);
+
+        storage.add_edge_unchecked(src_id, dst_id, E::init(weight));
+    }
+
+    Ok(labels)
+}
+
+/// Writes edges of the `storage` as an edge list.
+///
+/// Vertices are labeled by their ids. Note that isolated vertices can not be represented in an edge list.
+///
+/// # Arguments
+/// `storage`: Storage to write its edges.
+///
+/// # Returns
+/// Edge list in the format of: `src_id dst_id weight` per line.
+pub fn write<W, E, Dir, S>(storage: &S) -> String
+where
+    W: Display,
+    E: Edge<W>,
+    Dir: EdgeDir,
+    S: GraphStorage<W, E, Dir>,
+{
+    storage
+        .edges()
+        .into_iter()
+        .map(|(src_id, dst_id, edge)| {
+            format!(
+                "{} {} {}\n",
+                src_id,
+                dst_id,
+                format_weight(edge.get_weight())
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{DiList, DiMat, Mat};
+
+    #[test]
+    fn read_empty_text() {
+        // Given: An empty storage.
+        let mut storage = DiMat::<usize>::init();
+
+        // When: Reading text with only comments.
+        let labels = read(&mut storage, "# comment\n\n% another comment\n").unwrap();
+
+        // Then:
+        assert!(labels.is_empty());
+        assert_eq!(storage.vertex_count(), 0);
+    }
+
+    #[test]
+    fn read_mixed_separators() {
+        // Given: An empty storage.
+        let mut storage = DiList::<isize>::init();
+
+        // When: Reading edges with different separators.
+        let labels = read(&mut storage, "a b 2\nb,c,-3\nc, a\na\tc inf\n").unwrap();
+
+        // Then:
+        assert_eq!(storage.vertex_count(), 3);
+        assert_eq!(storage.edge_count(), 4);
+        let (a, b, c) = (labels["a"], labels["b"], labels["c"]);
+        assert_eq!(
+            storage.edges_between_unchecked(a, b)[0].get_weight(),
+            &2.into()
+        );
+        assert_eq!(
+            storage.edges_between_unchecked(b, c)[0].get_weight(),
+            &(-3).into()
+        );
+        assert_eq!(
+            storage.edges_between_unchecked(c, a)[0].get_weight(),
+            &1.into()
+        );
+        assert!(storage.edges_between_unchecked(a, c)[0]
+            .get_weight()
+            .is_pos_infinite());
+    }
+
+    #[test]
+    fn read_ignores_extra_columns() {
+        // Given: An empty storage.
+        let mut storage = DiList::<usize>::init();
+
+        // When: Reading a Konect style edge list with timestamps.
+        let labels = read(&mut storage, "% sym unweighted\n1 2 4 1230768000\n").unwrap();
+
+        // Then:
+        assert_eq!(storage.edge_count(), 1);
+        assert_eq!(
+            storage.edges_between_unchecked(labels["1"], labels["2"])[0].get_weight(),
+            &4.into()
+        );
+    }
+
+    #[test]
+    fn read_invalid_line() {
+        // Given: An empty storage.
+        let mut storage = DiList::<usize>::init();
+
+        // When: Reading a line with a single token.
+        let result = read(&mut storage, "1 2\n3\n");
+
+        // Then: Nothing is added to the storage.
+        assert!(result.is_err());
+        assert_eq!(storage.vertex_count(), 0);
+    }
+
+    #[test]
+    fn read_invalid_weight() {
+        // Given: An empty storage.
+        let mut storage = DiList::<usize>::init();
+
+        // When: Reading a weight that is not a number.
+        let result = read(&mut storage, "1 2 heavy\n");
+
+        // Then:
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn write_then_read() {
+        // Given: Storage
+        //          3
+        //      a  ---  b
+        //       \
+        //      2 \
+        //          c
+        let mut storage = Mat::<usize>::init();
+        let a = storage.add_vertex();
+        let b = storage.add_vertex();
+        let c = storage.add_vertex();
+        storage.add_edge_unchecked(a, b, 3.into());
+        storage.add_edge_unchecked(a, c, 2.into());
+
+        // When: Writing the storage and reading it back.
+        let text = write(&storage);
+        let mut read_storage = Mat::<usize>::init();
+        let labels = read(&mut read_storage, &text).unwrap();
+
+        // Then:
+        assert_eq!(read_storage.vertex_count(), 3);
+        assert_eq!(read_storage.edge_count(), 2);
+        let (a, b, c) = (
+            labels[&a.to_string()],
+            labels[&b.to_string()],
+            labels[&c.to_string()],
+        );
+        assert_eq!(
+            read_storage.edges_between_unchecked(b, a)[0].get_weight(),
+            &3.into()
+        );
+        assert_eq!(
+            read_storage.edges_between_unchecked(c, a)[0].get_weight(),
+            &2.into()
+        );
+    }
+}
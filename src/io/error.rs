@@ -0,0 +1,108 @@
+/// Types of errors that may happen when reading a graph from text.
+pub enum ErrorKind {
+    InvalidLine,
+    InvalidWeight,
+    InvalidFormat,
+    DirectionMismatch,
+}
+
+/// Error type returned in [`io`](crate::io) module.
+pub struct Error {
+    kind: ErrorKind,
+    msg: String,
+}
+
+impl Error {
+    /// # Arguments
+    /// * `kind`: Specifies what kind of error is being created.
+    /// * `msg`: Cause of the error.
+    ///
+    /// # Returns
+    /// Constructed `Error`.
+    pub fn new(kind: ErrorKind, msg: String) -> Self {
+        Error { kind, msg }
+    }
+
+    /// Creates a new [`InvalidLine`](crate::io::ErrorKind::InvalidLine) kind of error.
+    ///
+    /// # Arguments
+    /// * `line_number`: Number of the line that could not be parsed(starting from 1).
+    /// * `line`: Content of the line.
+    ///
+    /// # Returns
+    /// `Error` with `InvalidLine` kind and predefined message.
+    pub fn new_il(line_number: usize, line: &str) -> Self {
+        Error {
+            kind: ErrorKind::InvalidLine,
+            msg: format!("Can not parse line {}: {}", line_number, line),
+        }
+    }
+
+    /// Creates a new [`InvalidWeight`](crate::io::ErrorKind::InvalidWeight) kind of error.
+    ///
+    /// # Arguments
+    /// * `line_number`: Number of the line that contains the weight(starting from 1).
+    /// * `token`: The token that could not be parsed as weight.
+    ///
+    /// # Returns
+    /// `Error` with `InvalidWeight` kind and predefined message.
+    pub fn new_iw(line_number: usize, token: &str) -> Self {
+        Error {
+            kind: ErrorKind::InvalidWeight,
+            msg: format!("Invalid weight at line {}: {}", line_number, token),
+        }
+    }
+
+    /// Creates a new [`InvalidFormat`](crate::io::ErrorKind::InvalidFormat) kind of error.
+    /// This error is returned when the structure of the text does not match the expected format.
+    ///
+    /// # Arguments
+    /// `cause`: Explanation of what is wrong with the text.
+    ///
+    /// # Returns
+    /// `Error` with `InvalidFormat` kind and predefined message.
+    pub fn new_if(cause: &str) -> Self {
+        Error {
+            kind: ErrorKind::InvalidFormat,
+            msg: format!("Invalid format: {}", cause),
+        }
+    }
+
+    /// Creates a new [`DirectionMismatch`](crate::io::ErrorKind::DirectionMismatch) kind of error.
+    /// This error is returned when trying to read directed edges into an undirected storage.
+    ///
+    /// # Returns
+    /// `Error` with `DirectionMismatch` kind and predefined message.
+    pub fn new_dm() -> Self {
+        Error {
+            kind: ErrorKind::DirectionMismatch,
+            msg: "Can not read directed edges into an undirected storage".to_string(),
+        }
+    }
+
+    /// # Returns
+    /// Message inside of the error.
+    pub fn msg(&self) -> &str {
+        &self.msg
+    }
+
+    /// # Returns
+    /// What kind the error is.
+    pub fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
+}
+
+impl std::fmt::Debug for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.msg())
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.msg())
+    }
+}
+
+impl std::error::Error for Error {}
@@ -0,0 +1,438 @@
+use anyhow::Result;
+use num_traits::One;
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use super::{add_read_edge, format_weight, parse_weight, Error};
+use crate::graph::{Edge, EdgeDir};
+use crate::storage::GraphStorage;
+
+// Value of a key in GML. Numbers are kept as strings and parsed when needed.
+enum Value {
+    Scalar(String),
+    List(Vec<(String, Value)>),
+}
+
+impl Value {
+    fn scalar(&self) -> Option<&str> {
+        match self {
+            Value::Scalar(value) => Some(value),
+            Value::List(_) => None,
+        }
+    }
+}
+
+// Finds the first scalar value of `key` in `list`.
+fn scalar_of<'a>(list: &'a [(String, Value)], key: &str) -> Option<&'a str> {
+    list.iter()
+        .find(|(k, _)| k == key)
+        .and_then(|(_, value)| value.scalar())
+}
+
+// Splits the text into keys, values, brackets and quoted strings.
+// Quoted strings keep their opening quote so they can be distinguished from brackets.
+fn tokenize(text: &str) -> Result<Vec<String>> {
+    let mut tokens = vec![];
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c.is_whitespace() {
+            continue;
+        } else if c == '#' {
+            // Skip the comment until the end of the line.
+            for c in chars.by_ref() {
+                if c == '\n' {
+                    break;
+                }
+            }
+        } else if c == '[' || c == ']' {
+            tokens.push(c.to_string());
+        } else if c == '"' {
+            let mut token = String::from('"');
+            let mut is_closed = false;
+            for c in chars.by_ref() {
+                if c == '"' {
+                    is_closed = true;
+                    break;
+                }
+                token.push(c);
+            }
+
+            if !is_closed {
+                Err(Error::new_if("unclosed string"))?
+            }
+
+            tokens.push(token);
+        } else {
+            let mut token = c.to_string();
+            while let Some(c) = chars.peek() {
+                if c.is_whitespace() || *c == '[' || *c == ']' {
+                    break;
+                }
+                token.push(*c);
+                chars.next();
+            }
+            tokens.push(token);
+        }
+    }
+
+    Ok(tokens)
+}
+
+// Maximum number of lists that can be nested in each other. It keeps the recursion of `parse_list` from overflowing the stack.
+const MAX_DEPTH: usize = 64;
+
+// Parses key-value pairs starting from `tokens[*index]` until the closing bracket(if list is nested) or end of the tokens.
+// `depth` is the number of lists that contain the list, which is 0 for the whole document.
+fn parse_list(tokens: &[String], index: &mut usize, depth: usize) -> Result<Vec<(String, Value)>> {
+    if depth > MAX_DEPTH {
+        Err(Error::new_if("lists are nested too deeply"))?
+    }
+
+    let is_nested = depth > 0;
+    let mut list = vec![];
+
+    loop {
+        let key = match tokens.get(*index) {
+            None if is_nested => Err(Error::new_if("unclosed list"))?,
+            None => return Ok(list),
+            Some(token) if token == "]" => {
+                if is_nested {
+                    *index += 1;
+                    return Ok(list);
+                } else {
+                    Err(Error::new_if("unexpected ]"))?
+                }
+            }
+            Some(token) => token.clone(),
+        };
+        *index += 1;
+
+        let value = match tokens.get(*index).map(|token| token.as_str()) {
+            None | Some("]") => Err(Error::new_if(&format!("key {} has no value", key)))?,
+            Some("[") => {
+                *index += 1;
+                Value::List(parse_list(tokens, index, depth + 1)?)
+            }
+            Some(token) => {
+                *index += 1;
+                Value::Scalar(token.trim_start_matches('"').replace("&quot;", "\""))
+            }
+        };
+
+        list.push((key, value));
+    }
+}
+
+/// Reads the graph described in `text` into `storage`.
+///
+/// If graph is undirected but storage is directed, each edge is added in both directions.
+///
+/// # Arguments
+/// * `storage`: Storage to add the vertices and edges to.
+/// * `text`: GML text.
+///
+/// # Returns
+/// * `Ok`: Containing a map from label of each node(or its id if node has no label) to the id of its vertex in the storage.
+/// * `Err`:
+///     * If text is not a valid GML.
+///     * If text does not contain a graph or an edge refers to a node that does not exist.
+///     * If graph is directed but storage is undirected.
+///
+/// In case of an error, nothing is added to the storage.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::DiList;
+/// use prepona::io::gml;
+///
+/// let text = r#"
+/// graph [
+///   directed 1
+///   node [ id 1 label "a" ]
+///   node [ id 2 label "b" ]
+///   edge [ source 1 target 2 weight 3 ]
+/// ]"#;
+///
+/// let mut storage = DiList::<usize>::init();
+/// let labels = gml::read(&mut storage, text).unwrap();
+///
+/// assert_eq!(storage.vertex_count(), 2);
+/// assert_eq!(storage.edges_between_unchecked(labels["a"], labels["b"])[0].get_weight(), &3.into());
+/// ```
+pub fn read<W, E, Dir, S>(storage: &mut S, text: &str) -> Result<HashMap<String, usize>>
+where
+    W: FromStr + Any + One + Copy,
+    E: Edge<W>,
+    Dir: EdgeDir,
+    S: GraphStorage<W, E, Dir>,
+{
+    let tokens = tokenize(text)?;
+    let document = parse_list(&tokens, &mut 0, 0)?;
+
+    let graph = match document.iter().find(|(key, _)| key == "graph") {
+        Some((_, Value::List(graph))) => graph,
+        _ => Err(Error::new_if("no graph found"))?,
+    };
+
+    let is_directed = scalar_of(graph, "directed") == Some("1");
+    if is_directed && Dir::is_undirected() {
+        Err(Error::new_dm())?
+    }
+
+    // Collect and validate everything before touching the storage.
+    let mut nodes = vec![];
+    let mut edges = vec![];
+    for (key, value) in graph {
+        match (key.as_str(), value) {
+            ("node", Value::List(node)) => {
+                let id = scalar_of(node, "id").ok_or_else(|| Error::new_if("node without id"))?;
+                let label = scalar_of(node, "label").unwrap_or(id);
+
+                nodes.push((id, label));
+            }
+            ("edge", Value::List(edge)) => {
+                let source = scalar_of(edge, "source")
+                    .ok_or_else(|| Error::new_if("edge without source"))?;
+                let target = scalar_of(edge, "target")
+                    .ok_or_else(|| Error::new_if("edge without target"))?;
+
+                let weight = match scalar_of(edge, "weight").or_else(|| scalar_of(edge, "value")) {
+                    Some(token) => parse_weight(token)
+                        .ok_or_else(|| Error::new_if(&format!("invalid weight {}", token)))?,
+                    None => W::one().into(),
+                };
+
+                edges.push((source, target, weight));
+            }
+            _ => {}
+        }
+    }
+
+    let node_index: HashMap<&str, usize> = nodes
+        .iter()
+        .enumerate()
+        .map(|(index, (id, _))| (*id, index))
+        .collect();
+
+    for (source, target, _) in &edges {
+        for id in [source, target].iter() {
+            if !node_index.contains_key(**id) {
+                Err(Error::new_if(&format!(
+                    "edge refers to unknown node {}",
+                    id
+                )))?
+            }
+        }
+    }
+
+    let vertex_ids: Vec<usize> = nodes.iter().map(|_| storage.add_vertex()).collect();
+
+    for (source, target, weight) in edges {
+        let src_id = vertex_ids[node_index[source]];
+        let dst_id = vertex_ids[node_index[target]];
+
+        add_read_edge(storage, src_id, dst_id, weight, is_directed)?;
+    }
+
+    Ok(nodes
+        .into_iter()
+        .zip(vertex_ids)
+        .map(|((_, label), vertex_id)| (label.to_string(), vertex_id))
+        .collect())
+}
+
+/// Writes the `storage` in GML format.
+///
+/// Each vertex is written as a node with its vertex id as the GML id.
+///
+/// # Arguments
+/// * `storage`: Storage to write.
+/// * `label_of`: Returns the label of a vertex given its id.
+///
+/// # Returns
+/// GML text.
+pub fn write<W, E, Dir, S, F>(storage: &S, label_of: F) -> String
+where
+    W: Display,
+    E: Edge<W>,
+    Dir: EdgeDir,
+    S: GraphStorage<W, E, Dir>,
+    F: Fn(usize) -> String,
+{
+    let mut text = String::from("graph [\n");
+
+    text.push_str(&format!(
+        "  directed {}\n",
+        if Dir::is_directed() { 1 } else { 0 }
+    ));
+
+    for vertex_id in storage.vertices() {
+        text.push_str(&format!(
+            "  node [\n    id {}\n    label \"{}\"\n  ]\n",
+            vertex_id,
+            label_of(vertex_id).replace('"', "&quot;")
+        ));
+    }
+
+    for (src_id, dst_id, edge) in storage.edges() {
+        text.push_str(&format!(
+            "  edge [\n    source {}\n    target {}\n    weight {}\n  ]\n",
+            src_id,
+            dst_id,
+            format_weight(edge.get_weight())
+        ));
+    }
+
+    text.push_str("]\n");
+
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{DiList, DiMat, List};
+
+    #[test]
+    fn read_empty_graph() {
+        // Given: An empty storage.
+        let mut storage = List::<usize>::init();
+
+        // When: Reading an empty graph.
+        let labels = read(&mut storage, "graph [ ]").unwrap();
+
+        // Then:
+        assert!(labels.is_empty());
+        assert_eq!(storage.vertex_count(), 0);
+    }
+
+    #[test]
+    fn read_without_graph() {
+        // Given: An empty storage.
+        let mut storage = List::<usize>::init();
+
+        // When: Reading a text without a graph.
+        let result = read(&mut storage, "creator \"me\"");
+
+        // Then:
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn read_unclosed_list() {
+        // Given: An empty storage.
+        let mut storage = List::<usize>::init();
+
+        // When: Reading a graph that is not closed.
+        let result = read(&mut storage, "graph [ node [ id 1 ]");
+
+        // Then:
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn read_deeply_nested_lists() {
+        // Given: An empty storage.
+        let mut storage = List::<usize>::init();
+
+        // When: Reading lists nested deeper than the stack could handle.
+        let text = format!("{}{}", "a [ ".repeat(1_000_000), "] ".repeat(1_000_000));
+        let result = read(&mut storage, &text);
+
+        // Then: An error is returned instead of overflowing the stack.
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn read_unknown_node() {
+        // Given: An empty storage.
+        let mut storage = List::<usize>::init();
+
+        // When: Reading an edge to a node that does not exist.
+        let result = read(
+            &mut storage,
+            "graph [ node [ id 1 ] edge [ source 1 target 2 ] ]",
+        );
+
+        // Then: Nothing is added to the storage.
+        assert!(result.is_err());
+        assert_eq!(storage.vertex_count(), 0);
+    }
+
+    #[test]
+    fn read_directed_into_undirected() {
+        // Given: An empty undirected storage.
+        let mut storage = List::<usize>::init();
+
+        // When: Reading a directed graph.
+        let result = read(&mut storage, "graph [ directed 1 ]");
+
+        // Then:
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn read_undirected_into_directed() {
+        // Given: An empty directed storage.
+        let mut storage = DiList::<usize>::init();
+
+        // When: Reading an undirected graph.
+        let text = r#"
+        # comment
+        graph [
+            node [ id 0 label "first node" ]
+            node [ id 1 ]
+            edge [ source 0 target 1 value 2 ]
+        ]"#;
+        let labels = read(&mut storage, text).unwrap();
+
+        // Then: Edge is added in both directions.
+        let (a, b) = (labels["first node"], labels["1"]);
+        assert_eq!(storage.edge_count(), 2);
+        assert_eq!(
+            storage.edges_between_unchecked(a, b)[0].get_weight(),
+            &2.into()
+        );
+        assert_eq!(
+            storage.edges_between_unchecked(b, a)[0].get_weight(),
+            &2.into()
+        );
+    }
+
+    #[test]
+    fn write_then_read() {
+        // Given: Storage
+        //          3
+        //      a  -->  b
+        //      ^       |
+        //    1 |_______| 2
+        //
+        let mut storage = DiMat::<usize>::init();
+        let a = storage.add_vertex();
+        let b = storage.add_vertex();
+        storage.add_edge_unchecked(a, b, 3.into());
+        storage.add_edge_unchecked(b, a, 2.into());
+
+        // When: Writing the storage and reading it back.
+        let text = write(&storage, |vertex_id| format!("\"v{}\"", vertex_id));
+        let mut read_storage = DiMat::<usize>::init();
+        let labels = read(&mut read_storage, &text).unwrap();
+
+        // Then:
+        let (a, b) = (labels["\"v0\""], labels["\"v1\""]);
+        assert_eq!(read_storage.vertex_count(), 2);
+        assert_eq!(read_storage.edge_count(), 2);
+        assert_eq!(
+            read_storage.edges_between_unchecked(a, b)[0].get_weight(),
+            &3.into()
+        );
+        assert_eq!(
+            read_storage.edges_between_unchecked(b, a)[0].get_weight(),
+            &2.into()
+        );
+    }
+}
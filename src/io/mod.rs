@@ -0,0 +1,222 @@
+/// Reading and writing graphs in the adjacency list format.
+///
+/// Each line of an adjacency list describes a vertex followed by its neighbors: `src dst1 dst2 ...`.
+///
+/// * Tokens can be separated by whitespaces, commas or both.
+/// * A line with a single token describes a vertex without any outgoing edge, so isolated vertices can be represented.
+/// * Edges are unweighted and get the weight of one when read.
+/// * Empty lines and lines starting with `#` or `%` are ignored.
+///
+/// This is the same format that [NetworkX](https://networkx.org/documentation/stable/reference/readwrite/adjlist.html) uses.
+pub mod adj_list;
+
+/// Writing graphs in the DOT format, annotated with results of algorithms.
+pub mod dot;
+
+/// Reading and writing graphs in the edge list format.
+///
+/// Each line of an edge list describes one edge in the format of: `src dst [weight]`.
+///
+/// * Tokens can be separated by whitespaces, commas or both.
+/// * Weight is optional and defaults to one. `inf` and `-inf` are accepted as infinite weights.
+/// * Columns after the weight(like timestamps in Konect datasets) are ignored.
+/// * Empty lines and lines starting with `#` or `%` are ignored.
+///
+/// This is the format that [SNAP](https://snap.stanford.edu/data/) and [Konect](http://konect.cc/) datasets are distributed in.
+pub mod edge_list;
+
+/// Reading and writing graphs in the GML format.
+///
+/// [GML](https://en.wikipedia.org/wiki/Graph_Modelling_Language) describes a graph as nested lists of key-value pairs:
+///
+/// ```text
+/// graph [
+///   directed 1
+///   node [ id 1 label "a" ]
+///   node [ id 2 label "b" ]
+///   edge [ source 1 target 2 weight 3 ]
+/// ]
+/// ```
+///
+/// * Only `id` and `label` keys of nodes and `source`, `target` and `weight`(or `value`) keys of edges are used. Other keys are ignored.
+/// * Weight is optional and defaults to one.
+/// * Lines starting with `#` are comments.
+/// * Lists can be nested at most 64 levels deep.
+///
+/// This is the format used by igraph, NetworkX and Cytoscape among others.
+pub mod gml;
+
+/// Reading and writing graphs in the Pajek NET format.
+///
+/// [Pajek NET](http://mrvar.fdv.uni-lj.si/pajek/) describes a graph in sections:
+///
+/// ```text
+/// *Vertices 3
+/// 1 "a"
+/// 2 "b"
+/// 3 "c"
+/// *Arcs
+/// 1 2 3
+/// *Edges
+/// 2 3
+/// ```
+///
+/// * Vertices are numbered from 1 to the number specified in `*Vertices` line. Labels are optional and default to the number of the vertex.
+///   The number of vertices can not be larger than the length of the text.
+/// * Each line in `*Arcs` section is a directed edge and each line in `*Edges` section is an undirected edge in the format of: `src dst [weight]`.
+/// * Weight is optional and defaults to one.
+/// * Anything after the label of a vertex(like coordinates) is ignored.
+/// * Lines starting with `%` are comments.
+pub mod pajek;
+
+/// Reading, writing and applying patches of changes between versions of a graph.
+///
+/// A patch is a stream of changes to a storage, one change per line:
+///
+/// | Line               | Change                                                   |
+/// |--------------------|----------------------------------------------------------|
+/// | `+v id`            | Adds a vertex that is referred to as `id` in the rest of the patch |
+/// | `-v id`            | Removes vertex `id` and its edges                        |
+/// | `+e src dst weight`| Adds an edge from `src` to `dst`                         |
+/// | `-e src dst weight`| Removes one edge from `src` to `dst` with the given weight |
+///
+/// * Tokens can be separated by whitespaces, commas or both. `inf` and `-inf` are accepted as infinite weights.
+/// * Empty lines and lines starting with `#` or `%` are ignored.
+///
+/// Patches are plain text, so they can be sent to another process to keep a copy of a graph synchronized.
+/// A patch between two versions of a graph can be built from their [`diff`](crate::graph::diff) using [`Patch::from_diff`](crate::io::patch::Patch::from_diff).
+pub mod patch;
+
+/// Saving and loading storages as compact binary snapshots.
+///
+/// Snapshots are a compact binary representation of a storage in [CSR](https://en.wikipedia.org/wiki/Sparse_matrix#Compressed_sparse_row_(CSR,_CRS_or_Yale_format)) layout:
+///
+/// | Section   | Content                                                                                     |
+/// |-----------|---------------------------------------------------------------------------------------------|
+/// | Header    | Magic bytes `PRPN`, version, whether the storage is directed, name and size of the weight type |
+/// | Counts    | Number of vertices and number of edges                                                      |
+/// | Offsets   | Number of outgoing edges of each vertex(difference between consecutive CSR offsets)          |
+/// | Targets   | Destination of outgoing edges of each vertex in ascending order, each stored as difference to the previous one |
+/// | Weights   | Weight of each edge in the same order as targets                                            |
+///
+/// All integers are stored as [LEB128](https://en.wikipedia.org/wiki/LEB128) variable length integers.
+/// Since destinations are sorted and delta encoded, most of them fit in one or two bytes.
+///
+/// Vertices are numbered continuously in the snapshot in ascending order of their ids, so vertex ids of the loaded storage may differ from the saved one.
+pub mod snapshot;
+
+/// Generating large graphs with analytically known properties.
+///
+/// Generators of graphs whose properties are known analytically.
+///
+/// Small hand made graphs can not reveal bugs that only show up at scale(like overflows or mistakes in optimized code paths).
+/// Graphs generated in this module can be made arbitrarily large while answers to questions like "what is the distance between these two vertices" or "which vertices are in the same component" remain known.
+/// Each generator is accompanied by functions that compute these answers directly.
+///
+/// All edges get weight one. Undirected edges are added in both directions to directed storages.
+///
+/// Stress tests that run algorithms of this crate on large generated graphs are compiled only when the `stress` feature is enabled:
+/// ```text
+/// cargo test --release --features stress
+/// ```
+pub mod synthetic;
+
+mod error;
+
+pub use error::{Error, ErrorKind};
+
+use magnitude::Magnitude;
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::graph::{Edge, EdgeDir};
+use crate::storage::GraphStorage;
+
+// # Returns
+// `true` if line carries no graph information(it's empty or it's a comment).
+// Lines starting with `#` are comments in SNAP datasets and lines starting with `%` are comments in Konect datasets.
+fn is_skippable(line: &str) -> bool {
+    line.is_empty() || line.starts_with('#') || line.starts_with('%')
+}
+
+// Splits a line into its tokens. Tokens can be separated by whitespaces, commas or both.
+fn tokens_of(line: &str) -> Vec<&str> {
+    line.split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|token| !token.is_empty())
+        .collect()
+}
+
+// # Returns
+// * `Some`: Containing the parsed weight. `inf` and `-inf` are parsed as infinities.
+// * `None`: If token can not be parsed.
+fn parse_weight<W: FromStr + Any>(token: &str) -> Option<Magnitude<W>> {
+    match token {
+        "inf" | "+inf" => Some(Magnitude::PosInfinite),
+        "-inf" => Some(Magnitude::NegInfinite),
+        _ => token.parse::<W>().ok().map(|weight| weight.into()),
+    }
+}
+
+// Formats the weight so it can be parsed back by `parse_weight`.
+fn format_weight<W: Display>(weight: &Magnitude<W>) -> String {
+    match weight {
+        Magnitude::Finite(weight) => weight.to_string(),
+        Magnitude::PosInfinite => "inf".to_string(),
+        Magnitude::NegInfinite => "-inf".to_string(),
+    }
+}
+
+// # Returns
+// Id of the vertex labeled with `label`. If there is no such vertex, a new one gets added to the storage.
+fn vertex_of<W, E, Dir, S>(
+    storage: &mut S,
+    labels: &mut HashMap<String, usize>,
+    label: &str,
+) -> usize
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    S: GraphStorage<W, E, Dir>,
+{
+    if let Some(vertex_id) = labels.get(label) {
+        *vertex_id
+    } else {
+        let vertex_id = storage.add_vertex();
+        labels.insert(label.to_string(), vertex_id);
+
+        vertex_id
+    }
+}
+
+// Adds an edge read from text to the storage.
+// If edge is undirected but storage is directed, edge is added in both directions.
+//
+// # Returns
+// * `Ok`: If edge is added successfully.
+// * `Err`: If edge is directed but storage is undirected.
+fn add_read_edge<W, E, Dir, S>(
+    storage: &mut S,
+    src_id: usize,
+    dst_id: usize,
+    weight: Magnitude<W>,
+    is_directed: bool,
+) -> Result<(), Error>
+where
+    W: Copy,
+    E: Edge<W>,
+    Dir: EdgeDir,
+    S: GraphStorage<W, E, Dir>,
+{
+    if is_directed && Dir::is_undirected() {
+        return Err(Error::new_dm());
+    }
+
+    storage.add_edge_unchecked(src_id, dst_id, E::init(weight));
+    if !is_directed && Dir::is_directed() && src_id != dst_id {
+        storage.add_edge_unchecked(dst_id, src_id, E::init(weight));
+    }
+
+    Ok(())
+}
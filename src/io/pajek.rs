@@ -0,0 +1,346 @@
+use anyhow::Result;
+use num_traits::One;
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use super::{add_read_edge, format_weight, is_skippable, parse_weight, tokens_of, Error};
+use crate::graph::{Edge, EdgeDir};
+use crate::storage::GraphStorage;
+
+enum Section {
+    None,
+    Vertices,
+    Arcs,
+    Edges,
+}
+
+// # Returns
+// * `Some`: Containing the label at the start of `text`. Label can be quoted.
+// * `None`: If there is no label.
+fn label_of(text: &str) -> Option<String> {
+    let text = text.trim_start();
+
+    if let Some(quoted) = text.strip_prefix('"') {
+        quoted.find('"').map(|end| quoted[..end].to_string())
+    } else {
+        text.split_whitespace()
+            .next()
+            .map(|label| label.to_string())
+    }
+}
+
+/// Reads the graph described in `text` into `storage`.
+///
+/// Undirected edges are added in both directions if storage is directed.
+///
+/// # Arguments
+/// * `storage`: Storage to add the vertices and edges to.
+/// * `text`: Pajek NET text.
+///
+/// # Returns
+/// * `Ok`: Containing a map from label of each vertex to its id in the storage.
+/// * `Err`:
+///     * If any line of the text is malformed or refers to a vertex that does not exist.
+///     * If text contains arcs but storage is undirected.
+///
+/// In case of an error, nothing is added to the storage.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::DiList;
+/// use prepona::io::pajek;
+///
+/// let text = "*Vertices 2\n1 \"a\"\n2 \"b\"\n*Arcs\n1 2 3\n";
+///
+/// let mut storage = DiList::<usize>::init();
+/// let labels = pajek::read(&mut storage, text).unwrap();
+///
+/// assert_eq!(storage.vertex_count(), 2);
+/// assert_eq!(storage.edges_between_unchecked(labels["a"], labels["b"])[0].get_weight(), &3.into());
+/// ```
+pub fn read<W, E, Dir, S>(storage: &mut S, text: &str) -> Result<HashMap<String, usize>>
+where
+    W: FromStr + Any + One + Copy,
+    E: Edge<W>,
+    Dir: EdgeDir,
+    S: GraphStorage<W, E, Dir>,
+{
+    let mut section = Section::None;
+    let mut labels: Vec<String> = vec![];
+    let mut edges = vec![];
+
+    // Collect and validate everything before touching the storage.
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if is_skippable(line) {
+            continue;
+        }
+
+        if line.starts_with('*') {
+            let tokens = tokens_of(line);
+            section = match tokens[0].to_lowercase().as_str() {
+                "*vertices" => {
+                    // Every vertex occupies at least one byte of a sensible text, which bounds the allocation of labels.
+                    let vertex_count = tokens
+                        .get(1)
+                        .and_then(|token| token.parse::<usize>().ok())
+                        .filter(|vertex_count| *vertex_count <= text.len())
+                        .ok_or_else(|| Error::new_il(index + 1, line))?;
+
+                    labels = (1..=vertex_count)
+                        .map(|number| number.to_string())
+                        .collect();
+
+                    Section::Vertices
+                }
+                "*arcs" => {
+                    if Dir::is_undirected() {
+                        Err(Error::new_dm())?
+                    }
+                    Section::Arcs
+                }
+                "*edges" => Section::Edges,
+                _ => Err(Error::new_il(index + 1, line))?,
+            };
+            continue;
+        }
+
+        match section {
+            Section::None => Err(Error::new_il(index + 1, line))?,
+            Section::Vertices => {
+                let (number, rest) =
+                    line.split_at(line.find(char::is_whitespace).unwrap_or(line.len()));
+
+                let number = number
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|number| *number >= 1 && *number <= labels.len())
+                    .ok_or_else(|| Error::new_il(index + 1, line))?;
+
+                if let Some(label) = label_of(rest) {
+                    labels[number - 1] = label;
+                }
+            }
+            Section::Arcs | Section::Edges => {
+                let tokens = tokens_of(line);
+
+                let mut numbers = tokens.iter().take(2).map(|token| {
+                    token
+                        .parse::<usize>()
+                        .ok()
+                        .filter(|number| *number >= 1 && *number <= labels.len())
+                });
+
+                let (src_number, dst_number) =
+                    match (numbers.next().flatten(), numbers.next().flatten()) {
+                        (Some(src_number), Some(dst_number)) => (src_number, dst_number),
+                        _ => Err(Error::new_il(index + 1, line))?,
+                    };
+
+                let weight = match tokens.get(2) {
+                    Some(token) => {
+                        parse_weight(token).ok_or_else(|| Error::new_iw(index + 1, token))?
+                    }
+                    None => W::one().into(),
+                };
+
+                let is_directed = matches!(section, Section::Arcs);
+
+                edges.push((src_number, dst_number, weight, is_directed));
+            }
+        }
+    }
+
+    let vertex_ids: Vec<usize> = labels.iter().map(|_| storage.add_vertex()).collect();
+
+    for (src_number, dst_number, weight, is_directed) in edges {
+        let src_id = vertex_ids[src_number - 1];
+        let dst_id = vertex_ids[dst_number - 1];
+
+        add_read_edge(storage, src_id, dst_id, weight, is_directed)?;
+    }
+
+    Ok(labels.into_iter().zip(vertex_ids).collect())
+}
+
+/// Writes the `storage` in Pajek NET format.
+///
+/// Edges of a directed storage are written in `*Arcs` section and edges of an undirected storage are written in `*Edges` section.
+///
+/// # Arguments
+/// * `storage`: Storage to write.
+/// * `label_of`: Returns the label of a vertex given its id.
+///
+/// # Returns
+/// Pajek NET text.
+pub fn write<W, E, Dir, S, F>(storage: &S, label_of: F) -> String
+where
+    W: Display,
+    E: Edge<W>,
+    Dir: EdgeDir,
+    S: GraphStorage<W, E, Dir>,
+    F: Fn(usize) -> String,
+{
+    let vertices = storage.vertices();
+
+    // Pajek numbers vertices from 1.
+    let number_of: HashMap<usize, usize> = vertices
+        .iter()
+        .enumerate()
+        .map(|(index, vertex_id)| (*vertex_id, index + 1))
+        .collect();
+
+    let mut text = format!("*Vertices {}\n", vertices.len());
+    for vertex_id in &vertices {
+        text.push_str(&format!(
+            "{} \"{}\"\n",
+            number_of[vertex_id],
+            label_of(*vertex_id).replace('"', "")
+        ));
+    }
+
+    text.push_str(if Dir::is_directed() {
+        "*Arcs\n"
+    } else {
+        "*Edges\n"
+    });
+    for (src_id, dst_id, edge) in storage.edges() {
+        text.push_str(&format!(
+            "{} {} {}\n",
+            number_of[&src_id],
+            number_of[&dst_id],
+            format_weight(edge.get_weight())
+        ));
+    }
+
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{DiList, List, Mat};
+
+    #[test]
+    fn read_empty_graph() {
+        // Given: An empty storage.
+        let mut storage = List::<usize>::init();
+
+        // When: Reading an empty graph.
+        let labels = read(&mut storage, "*Vertices 0\n").unwrap();
+
+        // Then:
+        assert!(labels.is_empty());
+        assert_eq!(storage.vertex_count(), 0);
+    }
+
+    #[test]
+    fn read_labels_and_coordinates() {
+        // Given: An empty storage.
+        let mut storage = List::<usize>::init();
+
+        // When: Reading vertices with labels and coordinates.
+        let text =
+            "% comment\n*Vertices 3\n1 \"first vertex\" 0.1 0.2\n2 second\n*Edges\n1 2\n2 3 4\n";
+        let labels = read(&mut storage, text).unwrap();
+
+        // Then:
+        assert_eq!(storage.vertex_count(), 3);
+        assert_eq!(storage.edge_count(), 2);
+        let (a, b, c) = (labels["first vertex"], labels["second"], labels["3"]);
+        assert!(storage.has_any_edge_unchecked(a, b));
+        assert_eq!(
+            storage.edges_between_unchecked(c, b)[0].get_weight(),
+            &4.into()
+        );
+    }
+
+    #[test]
+    fn read_mixed_arcs_and_edges() {
+        // Given: An empty directed storage.
+        let mut storage = DiList::<usize>::init();
+
+        // When: Reading both arcs and edges.
+        let labels = read(&mut storage, "*Vertices 3\n*Arcs\n1 2\n*Edges\n2 3\n").unwrap();
+
+        // Then: Undirected edge is added in both directions.
+        let (a, b, c) = (labels["1"], labels["2"], labels["3"]);
+        assert_eq!(storage.edge_count(), 3);
+        assert!(storage.has_any_edge_unchecked(a, b));
+        assert!(!storage.has_any_edge_unchecked(b, a));
+        assert!(storage.has_any_edge_unchecked(b, c));
+        assert!(storage.has_any_edge_unchecked(c, b));
+    }
+
+    #[test]
+    fn read_arcs_into_undirected() {
+        // Given: An empty undirected storage.
+        let mut storage = List::<usize>::init();
+
+        // When: Reading arcs.
+        let result = read(&mut storage, "*Vertices 2\n*Arcs\n1 2\n");
+
+        // Then:
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn read_huge_vertex_count() {
+        // Given: An empty storage.
+        let mut storage = List::<usize>::init();
+
+        // When: Reading a text that claims more vertices than it could describe.
+        let result = read(
+            &mut storage,
+            "*Vertices 18446744073709551615
+",
+        );
+
+        // Then: An error is returned instead of running out of memory.
+        assert!(result.is_err());
+        assert_eq!(storage.vertex_count(), 0);
+    }
+
+    #[test]
+    fn read_unknown_vertex() {
+        // Given: An empty storage.
+        let mut storage = List::<usize>::init();
+
+        // When: Reading an edge to a vertex that does not exist.
+        let result = read(&mut storage, "*Vertices 2\n*Edges\n1 3\n");
+
+        // Then: Nothing is added to the storage.
+        assert!(result.is_err());
+        assert_eq!(storage.vertex_count(), 0);
+    }
+
+    #[test]
+    fn write_then_read() {
+        // Given: Storage
+        //          3
+        //      a  ---  b       c
+        //
+        let mut storage = Mat::<usize>::init();
+        let a = storage.add_vertex();
+        let b = storage.add_vertex();
+        storage.add_vertex();
+        storage.add_edge_unchecked(a, b, 3.into());
+
+        // When: Writing the storage and reading it back.
+        let text = write(&storage, |vertex_id| format!("v {}", vertex_id));
+        let mut read_storage = Mat::<usize>::init();
+        let labels = read(&mut read_storage, &text).unwrap();
+
+        // Then:
+        assert_eq!(read_storage.vertex_count(), 3);
+        assert_eq!(read_storage.edge_count(), 1);
+        assert_eq!(
+            read_storage.edges_between_unchecked(labels["v 1"], labels["v 0"])[0].get_weight(),
+            &3.into()
+        );
+        assert!(labels.contains_key("v 2"));
+    }
+}
@@ -0,0 +1,524 @@
+use anyhow::Result;
+use magnitude::Magnitude;
+use std::any::Any;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+use std::str::FromStr;
+
+use super::{format_weight, is_skippable, parse_weight, tokens_of, Error};
+use crate::graph::{Edge, EdgeDir, GraphDiff};
+use crate::storage::GraphStorage;
+
+/// One change in a [`Patch`](crate::io::patch::Patch).
+///
+/// Vertices are referred to by their ids in the graph the patch is made from.
+#[derive(Debug, Clone)]
+pub enum PatchOp<W> {
+    /// Adds a vertex with the given id.
+    AddVertex(usize),
+
+    /// Removes the vertex with the given id and its edges.
+    RemoveVertex(usize),
+
+    /// Adds an edge in the format of (`src_id`, `dst_id`, `weight`).
+    AddEdge(usize, usize, Magnitude<W>),
+
+    /// Removes one edge in the format of (`src_id`, `dst_id`, `weight`).
+    RemoveEdge(usize, usize, Magnitude<W>),
+}
+
+// Unlike `==` on magnitudes, infinities of the same sign are considered equal.
+fn same_weight<W: PartialEq>(weight1: &Magnitude<W>, weight2: &Magnitude<W>) -> bool {
+    match (weight1, weight2) {
+        (Magnitude::Finite(weight1), Magnitude::Finite(weight2)) => weight1 == weight2,
+        (Magnitude::PosInfinite, Magnitude::PosInfinite)
+        | (Magnitude::NegInfinite, Magnitude::NegInfinite) => true,
+        _ => false,
+    }
+}
+
+impl<W: PartialEq> PartialEq for PatchOp<W> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (PatchOp::AddVertex(id1), PatchOp::AddVertex(id2))
+            | (PatchOp::RemoveVertex(id1), PatchOp::RemoveVertex(id2)) => id1 == id2,
+            (PatchOp::AddEdge(src1, dst1, weight1), PatchOp::AddEdge(src2, dst2, weight2))
+            | (
+                PatchOp::RemoveEdge(src1, dst1, weight1),
+                PatchOp::RemoveEdge(src2, dst2, weight2),
+            ) => src1 == src2 && dst1 == dst2 && same_weight(weight1, weight2),
+            _ => false,
+        }
+    }
+}
+
+/// Ordered list of changes to apply to a storage.
+///
+/// ## Generic Parameters
+/// * `W`: **W**eight type associated with edges.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Patch<W> {
+    ops: Vec<PatchOp<W>>,
+}
+
+impl<W> Patch<W> {
+    /// # Returns
+    /// A patch without any change.
+    pub fn init() -> Self {
+        Patch { ops: vec![] }
+    }
+
+    /// Appends `op` to the end of the patch.
+    pub fn push(&mut self, op: PatchOp<W>) {
+        self.ops.push(op);
+    }
+
+    /// # Returns
+    /// Changes of the patch in the order they are applied.
+    pub fn ops(&self) -> &[PatchOp<W>] {
+        &self.ops
+    }
+
+    /// # Arguments
+    /// `diff`: Changes between two versions of a graph.
+    ///
+    /// # Returns
+    /// A patch that turns the old version into the new one: Removed edges and vertices come first, then added vertices and edges.
+    pub fn from_diff(diff: &GraphDiff<W>) -> Self
+    where
+        W: Clone,
+    {
+        let removed_edges = diff
+            .removed_edges
+            .iter()
+            .map(|(src_id, dst_id, weight)| PatchOp::RemoveEdge(*src_id, *dst_id, weight.clone()));
+        let removed_nodes = diff
+            .removed_nodes
+            .iter()
+            .map(|vertex_id| PatchOp::RemoveVertex(*vertex_id));
+        let added_nodes = diff
+            .added_nodes
+            .iter()
+            .map(|vertex_id| PatchOp::AddVertex(*vertex_id));
+        let added_edges = diff
+            .added_edges
+            .iter()
+            .map(|(src_id, dst_id, weight)| PatchOp::AddEdge(*src_id, *dst_id, weight.clone()));
+
+        Patch {
+            ops: removed_edges
+                .chain(removed_nodes)
+                .chain(added_nodes)
+                .chain(added_edges)
+                .collect(),
+        }
+    }
+}
+
+/// Why a change of a patch can not be applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictKind {
+    /// Change refers to a vertex that does not exist.
+    VertexNotFound,
+
+    /// Change adds a vertex with an id that already exists.
+    VertexAlreadyExists,
+
+    /// Change removes an edge that does not exist.
+    EdgeNotFound,
+}
+
+/// A change of a patch that can not be applied, returned by [`apply_patch`](crate::io::patch::apply_patch).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Conflict {
+    /// Index of the change in [`ops`](crate::io::patch::Patch::ops).
+    pub op_index: usize,
+
+    /// Why the change can not be applied.
+    pub kind: ConflictKind,
+}
+
+/// Writes `patch` in the patch format.
+///
+/// # Arguments
+/// `patch`: Patch to write.
+///
+/// # Returns
+/// One line per change of the patch.
+pub fn write<W: Display>(patch: &Patch<W>) -> String {
+    patch
+        .ops
+        .iter()
+        .map(|op| match op {
+            PatchOp::AddVertex(vertex_id) => format!("+v {}\n", vertex_id),
+            PatchOp::RemoveVertex(vertex_id) => format!("-v {}\n", vertex_id),
+            PatchOp::AddEdge(src_id, dst_id, weight) => {
+                format!("+e {} {} {}\n", src_id, dst_id, format_weight(weight))
+            }
+            PatchOp::RemoveEdge(src_id, dst_id, weight) => {
+                format!("-e {} {} {}\n", src_id, dst_id, format_weight(weight))
+            }
+        })
+        .collect()
+}
+
+/// Reads a patch written in the patch format.
+///
+/// # Arguments
+/// `text`: Patch to read.
+///
+/// # Returns
+/// * `Ok`: Containing the patch.
+/// * `Err`: If any line of the text is malformed.
+pub fn read<W: FromStr + Any>(text: &str) -> Result<Patch<W>> {
+    let mut patch = Patch::init();
+
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if is_skippable(line) {
+            continue;
+        }
+
+        let tokens = tokens_of(line);
+        let ids: Option<Vec<usize>> = tokens
+            .iter()
+            .skip(1)
+            .take(2)
+            .map(|token| token.parse().ok())
+            .collect();
+
+        let op = match (tokens[0], ids, tokens.len()) {
+            ("+v", Some(ids), 2) => PatchOp::AddVertex(ids[0]),
+            ("-v", Some(ids), 2) => PatchOp::RemoveVertex(ids[0]),
+            (kind @ "+e", Some(ids), 4) | (kind @ "-e", Some(ids), 4) => {
+                let weight = match parse_weight(tokens[3]) {
+                    Some(weight) => weight,
+                    None => Err(Error::new_iw(index + 1, tokens[3]))?,
+                };
+
+                if kind == "+e" {
+                    PatchOp::AddEdge(ids[0], ids[1], weight)
+                } else {
+                    PatchOp::RemoveEdge(ids[0], ids[1], weight)
+                }
+            }
+            _ => Err(Error::new_il(index + 1, line))?,
+        };
+
+        patch.push(op);
+    }
+
+    Ok(patch)
+}
+
+// Vertices and edges of a storage as seen by the changes of a patch, used to find conflicts without touching the storage.
+struct Replica<W> {
+    vertices: HashSet<usize>,
+    edges: HashMap<(usize, usize), Vec<Magnitude<W>>>,
+    is_undirected: bool,
+}
+
+impl<W: Clone + PartialEq> Replica<W> {
+    fn of<E, Dir, S>(storage: &S) -> Self
+    where
+        E: Edge<W>,
+        Dir: EdgeDir,
+        S: GraphStorage<W, E, Dir>,
+    {
+        let mut replica = Replica {
+            vertices: storage.vertices().into_iter().collect(),
+            edges: HashMap::new(),
+            is_undirected: Dir::is_undirected(),
+        };
+
+        // Storages differ in how many times they return an undirected loop, so edges are deduplicated by their ids.
+        let mut seen_ids = HashSet::new();
+        for (src_id, dst_id, edge) in storage.edges() {
+            if seen_ids.insert(edge.get_id()) {
+                let key = replica.key_of(src_id, dst_id);
+                replica
+                    .edges
+                    .entry(key)
+                    .or_default()
+                    .push(edge.get_weight().clone());
+            }
+        }
+
+        replica
+    }
+
+    fn key_of(&self, src_id: usize, dst_id: usize) -> (usize, usize) {
+        if self.is_undirected && dst_id < src_id {
+            (dst_id, src_id)
+        } else {
+            (src_id, dst_id)
+        }
+    }
+
+    // Applies the change to the replica if possible.
+    fn apply(&mut self, op: &PatchOp<W>) -> Option<ConflictKind> {
+        match op {
+            PatchOp::AddVertex(vertex_id) => {
+                if !self.vertices.insert(*vertex_id) {
+                    return Some(ConflictKind::VertexAlreadyExists);
+                }
+            }
+            PatchOp::RemoveVertex(vertex_id) => {
+                if !self.vertices.remove(vertex_id) {
+                    return Some(ConflictKind::VertexNotFound);
+                }
+
+                self.edges
+                    .retain(|(src_id, dst_id), _| src_id != vertex_id && dst_id != vertex_id);
+            }
+            PatchOp::AddEdge(src_id, dst_id, weight) => {
+                if !self.vertices.contains(src_id) || !self.vertices.contains(dst_id) {
+                    return Some(ConflictKind::VertexNotFound);
+                }
+
+                let key = self.key_of(*src_id, *dst_id);
+                self.edges.entry(key).or_default().push(weight.clone());
+            }
+            PatchOp::RemoveEdge(src_id, dst_id, weight) => {
+                let key = self.key_of(*src_id, *dst_id);
+                let weights = self.edges.entry(key).or_default();
+
+                match weights.iter().position(|other| same_weight(other, weight)) {
+                    Some(index) => {
+                        weights.swap_remove(index);
+                    }
+                    None => return Some(ConflictKind::EdgeNotFound),
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// Applies changes of `patch` to `storage` in order.
+///
+/// Every change is checked against the state of the storage after the previous changes before anything is applied:
+/// Removed vertices and end points of added edges must exist, added vertices must not exist and removed edges must match an edge with the same end points and weight.
+/// If any change conflicts, all conflicts are reported and the storage is left untouched.
+///
+/// Storages choose ids of new vertices themselves, so vertices added by the patch may get different ids than the ones in the patch.
+///
+/// # Arguments
+/// * `storage`: Storage to change.
+/// * `patch`: Patch to apply.
+///
+/// # Returns
+/// * `Ok`: Containing map from the id of each vertex added by the patch to its id in the storage.
+/// * `Err`: Containing the conflicts in the order of their changes.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::DiList;
+/// use prepona::io::patch::{self, ConflictKind};
+///
+/// // Given: Storage a --> b and a patch that reverses its edge and adds a new vertex.
+/// let mut storage = DiList::<usize>::init();
+/// let a = storage.add_vertex();
+/// let b = storage.add_vertex();
+/// storage.add_edge_unchecked(a, b, DefaultEdge::init(1.into()));
+///
+/// let text = "-e 0 1 1\n+e 1 0 1\n+v 7\n+e 7 0 2\n";
+/// let patch = patch::read::<usize>(text).unwrap();
+///
+/// // When:
+/// let new_ids = patch::apply_patch(&mut storage, &patch).unwrap();
+///
+/// // Then:
+/// assert!(storage.has_any_edge_unchecked(b, a));
+/// assert!(storage.has_any_edge_unchecked(new_ids[&7], a));
+/// assert!(!storage.has_any_edge_unchecked(a, b));
+///
+/// // When: Applying the same patch again.
+/// let conflicts = patch::apply_patch(&mut storage, &patch).unwrap_err();
+///
+/// // Then: Edge a --> b is already removed.
+/// assert_eq!(conflicts[0].op_index, 0);
+/// assert_eq!(conflicts[0].kind, ConflictKind::EdgeNotFound);
+/// ```
+pub fn apply_patch<W, E, Dir, S>(
+    storage: &mut S,
+    patch: &Patch<W>,
+) -> std::result::Result<HashMap<usize, usize>, Vec<Conflict>>
+where
+    W: Clone + PartialEq,
+    E: Edge<W>,
+    Dir: EdgeDir,
+    S: GraphStorage<W, E, Dir>,
+{
+    let mut replica = Replica::of(storage);
+
+    let conflicts: Vec<Conflict> = patch
+        .ops
+        .iter()
+        .enumerate()
+        .filter_map(|(op_index, op)| replica.apply(op).map(|kind| Conflict { op_index, kind }))
+        .collect();
+    if !conflicts.is_empty() {
+        return Err(conflicts);
+    }
+
+    // Vertices that are not added by the patch keep their ids.
+    let mut new_ids = HashMap::new();
+    for op in &patch.ops {
+        let id_of = |vertex_id: &usize| *new_ids.get(vertex_id).unwrap_or(vertex_id);
+
+        match op {
+            PatchOp::AddVertex(vertex_id) => {
+                new_ids.insert(*vertex_id, storage.add_vertex());
+            }
+            PatchOp::RemoveVertex(vertex_id) => {
+                storage.remove_vertex_unchecked(id_of(vertex_id));
+                new_ids.remove(vertex_id);
+            }
+            PatchOp::AddEdge(src_id, dst_id, weight) => {
+                storage.add_edge_unchecked(id_of(src_id), id_of(dst_id), E::init(weight.clone()));
+            }
+            PatchOp::RemoveEdge(src_id, dst_id, weight) => {
+                let (src_id, dst_id) = (id_of(src_id), id_of(dst_id));
+                let edge_id = storage
+                    .edges_between_unchecked(src_id, dst_id)
+                    .into_iter()
+                    .find(|edge| same_weight(edge.get_weight(), weight))
+                    .unwrap()
+                    .get_id();
+
+                storage.remove_edge_unchecked(src_id, dst_id, edge_id);
+            }
+        }
+    }
+
+    Ok(new_ids)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{diff, DefaultEdge, MultiListGraph};
+    use crate::provide::{Edges, Graph, Vertices};
+    use crate::storage::{DiList, List};
+
+    #[test]
+    fn write_and_read_patch() {
+        // Given:
+        let mut patch = Patch::init();
+        patch.push(PatchOp::AddVertex(3));
+        patch.push(PatchOp::AddEdge(3, 0, 2.5.into()));
+        patch.push(PatchOp::RemoveEdge(0, 1, Magnitude::PosInfinite));
+        patch.push(PatchOp::RemoveVertex(1));
+
+        // When:
+        let text = write(&patch);
+
+        // Then:
+        assert_eq!(text, "+v 3\n+e 3 0 2.5\n-e 0 1 inf\n-v 1\n");
+        assert_eq!(read::<f64>(&text).unwrap(), patch);
+    }
+
+    #[test]
+    fn read_malformed_lines() {
+        assert!(read::<usize>("# comment\n+v 1\n").is_ok());
+        assert!(read::<usize>("+v\n").is_err());
+        assert!(read::<usize>("+v a\n").is_err());
+        assert!(read::<usize>("+e 1 2\n").is_err());
+        assert!(read::<usize>("+e 1 2 x\n").is_err());
+        assert!(read::<usize>("*v 1\n").is_err());
+    }
+
+    #[test]
+    fn conflicts_leave_storage_untouched() {
+        // Given: Undirected storage a --- b.
+        let mut storage = List::<usize>::init();
+        let a = storage.add_vertex();
+        let b = storage.add_vertex();
+        storage.add_edge_unchecked(a, b, DefaultEdge::init(1.into()));
+
+        // And: Patch with a conflict for each kind.
+        let patch = read::<usize>("-e 1 0 1\n+v 0\n-v 5\n-e 0 1 1\n+e 0 9 1\n").unwrap();
+
+        // When:
+        let conflicts = apply_patch(&mut storage, &patch).unwrap_err();
+
+        // Then: Removing b --- a matches the undirected edge, so removing it again conflicts.
+        let conflicts: Vec<(usize, ConflictKind)> = conflicts
+            .into_iter()
+            .map(|conflict| (conflict.op_index, conflict.kind))
+            .collect();
+        assert_eq!(
+            conflicts,
+            vec![
+                (1, ConflictKind::VertexAlreadyExists),
+                (2, ConflictKind::VertexNotFound),
+                (3, ConflictKind::EdgeNotFound),
+                (4, ConflictKind::VertexNotFound),
+            ]
+        );
+        assert_eq!(storage.edge_count(), 1);
+    }
+
+    #[test]
+    fn removed_vertex_removes_its_edges() {
+        // Given: Storage a --> b.
+        let mut storage = DiList::<usize>::init();
+        let a = storage.add_vertex();
+        let b = storage.add_vertex();
+        storage.add_edge_unchecked(a, b, DefaultEdge::init(1.into()));
+
+        // When: Removing b and then its edge.
+        let patch = read::<usize>("-v 1\n-e 0 1 1\n").unwrap();
+
+        // Then:
+        assert_eq!(
+            apply_patch(&mut storage, &patch).unwrap_err(),
+            vec![Conflict {
+                op_index: 1,
+                kind: ConflictKind::EdgeNotFound
+            }]
+        );
+    }
+
+    #[test]
+    fn synchronize_through_text() {
+        // Given: Two versions of a multigraph.
+        let mut old = MultiListGraph::init(List::<usize>::init());
+        let mut new = MultiListGraph::init(List::<usize>::init());
+        for _ in 0..4 {
+            old.add_vertex();
+            new.add_vertex();
+        }
+        old.add_edge_unchecked(0, 1, 1.into());
+        old.add_edge_unchecked(1, 2, 1.into());
+        old.add_edge_unchecked(2, 3, 1.into());
+        new.add_edge_unchecked(0, 1, 1.into());
+        new.add_edge_unchecked(0, 1, 1.into());
+        new.add_edge_unchecked(3, 3, 4.into());
+        new.remove_vertex_unchecked(2);
+        let e = new.add_vertex();
+        new.add_edge_unchecked(e, 0, 5.into());
+
+        // And: A replica of the old version in another storage.
+        let mut replica = List::<usize>::init();
+        for _ in 0..4 {
+            replica.add_vertex();
+        }
+        for (src_id, dst_id, edge) in old.edges() {
+            replica.add_edge_unchecked(src_id, dst_id, DefaultEdge::init(*edge.get_weight()));
+        }
+
+        // When: Sending the changes as text.
+        let text = write(&Patch::from_diff(&diff(&old, &new)));
+        apply_patch(&mut replica, &read::<usize>(&text).unwrap()).unwrap();
+
+        // Then:
+        let replica = MultiListGraph::init(replica);
+        assert_eq!(replica.vertex_count(), 4);
+        assert_eq!(replica.edges_count(), new.edges_count());
+        assert!(diff(&replica, &new).is_empty());
+    }
+}
@@ -0,0 +1,599 @@
+use anyhow::Result;
+use magnitude::Magnitude;
+use std::any::Any;
+use std::collections::{HashMap, HashSet};
+
+use super::Error;
+use crate::graph::{Edge, EdgeDir};
+use crate::storage::GraphStorage;
+
+const MAGIC: &[u8; 4] = b"PRPN";
+const VERSION: u8 = 1;
+
+/// Weights that can be stored in a snapshot.
+///
+/// It's implemented for all primitive integer and floating point types.
+pub trait Encode: Sized {
+    /// Name of the weight type, which is stored in the snapshot along with its size so it's only loaded as the same type.
+    const NAME: &'static str;
+
+    /// Appends the little endian bytes of the weight to `bytes`.
+    fn encode(&self, bytes: &mut Vec<u8>);
+
+    /// # Returns
+    /// * `Some`: Containing the weight decoded from the start of `bytes` and the number of bytes it occupied.
+    /// * `None`: If there are not enough bytes.
+    fn decode(bytes: &[u8]) -> Option<(Self, usize)>;
+}
+
+macro_rules! impl_encode {
+    ($($t:ty),*) => {
+        $(
+            impl Encode for $t {
+                const NAME: &'static str = stringify!($t);
+
+                fn encode(&self, bytes: &mut Vec<u8>) {
+                    bytes.extend_from_slice(&self.to_le_bytes());
+                }
+
+                fn decode(bytes: &[u8]) -> Option<(Self, usize)> {
+                    const SIZE: usize = std::mem::size_of::<$t>();
+
+                    let mut array = [0u8; SIZE];
+                    array.copy_from_slice(bytes.get(..SIZE)?);
+
+                    Some((<$t>::from_le_bytes(array), SIZE))
+                }
+            }
+        )*
+    };
+}
+
+impl_encode!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+// Tags that specify the kind of magnitude of a weight.
+const FINITE: u8 = 0;
+const POS_INFINITE: u8 = 1;
+const NEG_INFINITE: u8 = 2;
+
+// Direction flags.
+const UNDIRECTED: u8 = 0;
+const DIRECTED: u8 = 1;
+
+fn push_header<W: Encode>(bytes: &mut Vec<u8>, is_directed: bool) {
+    bytes.extend_from_slice(MAGIC);
+    bytes.push(VERSION);
+    bytes.push(if is_directed { DIRECTED } else { UNDIRECTED });
+    bytes.push(W::NAME.len() as u8);
+    bytes.extend_from_slice(W::NAME.as_bytes());
+    bytes.push(std::mem::size_of::<W>() as u8);
+}
+
+fn push_varint(bytes: &mut Vec<u8>, mut value: usize) {
+    while value >= 0x80 {
+        bytes.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+// Reads snapshot sections one after another.
+struct Reader<'a> {
+    bytes: &'a [u8],
+    index: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn byte(&mut self) -> Result<u8> {
+        let byte = *self
+            .bytes
+            .get(self.index)
+            .ok_or_else(|| Error::new_if("truncated snapshot"))?;
+        self.index += 1;
+
+        Ok(byte)
+    }
+
+    fn slice(&mut self, len: usize) -> Result<&'a [u8]> {
+        let slice = self
+            .bytes
+            .get(self.index..self.index + len)
+            .ok_or_else(|| Error::new_if("truncated snapshot"))?;
+        self.index += len;
+
+        Ok(slice)
+    }
+
+    fn varint(&mut self) -> Result<usize> {
+        let mut value = 0usize;
+        let mut shift = 0;
+
+        loop {
+            let byte = self.byte()?;
+            let bits = (byte & 0x7f) as usize;
+            // Bits that are shifted out of the last byte would be lost.
+            if shift >= usize::BITS || (bits << shift) >> shift != bits {
+                Err(Error::new_if("varint overflow"))?
+            }
+
+            value |= bits << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+            shift += 7;
+        }
+    }
+
+    fn weight<W: Encode + Any>(&mut self) -> Result<Magnitude<W>> {
+        match self.byte()? {
+            FINITE => {
+                let (weight, size) = W::decode(&self.bytes[self.index..])
+                    .ok_or_else(|| Error::new_if("truncated snapshot"))?;
+                self.index += size;
+
+                Ok(weight.into())
+            }
+            POS_INFINITE => Ok(Magnitude::PosInfinite),
+            NEG_INFINITE => Ok(Magnitude::NegInfinite),
+            _ => Err(Error::new_if("invalid weight tag"))?,
+        }
+    }
+}
+
+/// Saves the `storage` as a binary snapshot.
+///
+/// # Arguments
+/// `storage`: Storage to save.
+///
+/// # Returns
+/// Bytes of the snapshot.
+///
+/// # Complexity
+/// O(|V| + |E|log|E|)
+pub fn save<W, E, Dir, S>(storage: &S) -> Vec<u8>
+where
+    W: Encode,
+    E: Edge<W>,
+    Dir: EdgeDir,
+    S: GraphStorage<W, E, Dir>,
+{
+    let mut vertices = storage.vertices();
+    vertices.sort_unstable();
+
+    let virt_id_of: HashMap<usize, usize> = vertices
+        .into_iter()
+        .enumerate()
+        .map(|(virt_id, real_id)| (real_id, virt_id))
+        .collect();
+    let vertex_count = virt_id_of.len();
+
+    // Outgoing edges of each vertex in the form of (dst, weight). Edges of undirected storages are stored once.
+    // Some undirected storages list a self loop once from each of its end points, so edges are deduplicated by their id.
+    let mut rows: Vec<Vec<(usize, &Magnitude<W>)>> = vec![vec![]; vertex_count];
+    let mut edge_ids = HashSet::new();
+    for (src_id, dst_id, edge) in storage.edges() {
+        if edge_ids.insert(edge.get_id()) {
+            rows[virt_id_of[&src_id]].push((virt_id_of[&dst_id], edge.get_weight()));
+        }
+    }
+    let edge_count = edge_ids.len();
+
+    let mut bytes =
+        Vec::with_capacity(16 + vertex_count + edge_count * (2 + std::mem::size_of::<W>()));
+
+    push_header::<W>(&mut bytes, Dir::is_directed());
+
+    push_varint(&mut bytes, vertex_count);
+    push_varint(&mut bytes, edge_count);
+
+    for row in &mut rows {
+        row.sort_by_key(|(dst, _)| *dst);
+        push_varint(&mut bytes, row.len());
+    }
+
+    for row in &rows {
+        let mut previous = 0;
+        for (dst, _) in row {
+            push_varint(&mut bytes, dst - previous);
+            previous = *dst;
+        }
+    }
+
+    for (_, weight) in rows.iter().flatten() {
+        match weight {
+            Magnitude::Finite(weight) => {
+                bytes.push(FINITE);
+                weight.encode(&mut bytes);
+            }
+            Magnitude::PosInfinite => bytes.push(POS_INFINITE),
+            Magnitude::NegInfinite => bytes.push(NEG_INFINITE),
+        }
+    }
+
+    bytes
+}
+
+/// Loads a snapshot into `storage`.
+///
+/// If snapshot is undirected but storage is directed, each edge is added in both directions.
+///
+/// # Arguments
+/// * `storage`: Storage to add the vertices and edges to.
+/// * `bytes`: Bytes of the snapshot.
+///
+/// # Returns
+/// * `Ok`: Containing the id of each vertex of the snapshot in the storage. The i-th element is the id of the i-th vertex.
+/// * `Err`:
+///     * If bytes are not a valid snapshot.
+///     * If snapshot is saved from a storage with another weight type.
+///     * If snapshot is directed but storage is undirected.
+///
+/// In case of an error, nothing is added to the storage.
+///
+/// # Complexity
+/// O(|V| + |E|) plus the cost of adding the vertices and edges to the storage.
+/// Vertices and edges are added in bulk through [`extend_with_vertices`](crate::storage::GraphStorage::extend_with_vertices)
+/// and [`extend_with_edges_unchecked`](crate::storage::GraphStorage::extend_with_edges_unchecked), so storages can allocate room for them once.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::DiList;
+/// use prepona::io::snapshot;
+///
+/// let mut storage = DiList::<usize>::init();
+/// let a = storage.add_vertex();
+/// let b = storage.add_vertex();
+/// storage.add_edge_unchecked(a, b, 5.into());
+///
+/// let bytes = snapshot::save(&storage);
+///
+/// let mut loaded = DiList::<usize>::init();
+/// let ids = snapshot::load(&mut loaded, &bytes).unwrap();
+///
+/// assert_eq!(loaded.edges_between_unchecked(ids[0], ids[1])[0].get_weight(), &5.into());
+/// ```
+pub fn load<W, E, Dir, S>(storage: &mut S, bytes: &[u8]) -> Result<Vec<usize>>
+where
+    W: Encode + Any + Copy,
+    E: Edge<W>,
+    Dir: EdgeDir,
+    S: GraphStorage<W, E, Dir>,
+{
+    if bytes.get(..4) != Some(&MAGIC[..]) {
+        Err(Error::new_if("not a snapshot"))?
+    }
+
+    let mut reader = Reader { bytes, index: 4 };
+
+    let version = reader.byte()?;
+    if version != VERSION {
+        Err(Error::new_if(&format!(
+            "unsupported snapshot version {}",
+            version
+        )))?
+    }
+
+    let is_directed = match reader.byte()? {
+        UNDIRECTED => false,
+        DIRECTED => true,
+        flag => Err(Error::new_if(&format!("invalid direction flag {}", flag)))?,
+    };
+
+    let name_len = reader.byte()? as usize;
+    let name = reader.slice(name_len)?;
+    let size = reader.byte()? as usize;
+    if name != W::NAME.as_bytes() || size != std::mem::size_of::<W>() {
+        Err(Error::new_if(&format!(
+            "snapshot weights are not of type {}",
+            W::NAME
+        )))?
+    }
+
+    if is_directed && Dir::is_undirected() {
+        Err(Error::new_dm())?
+    }
+
+    let vertex_count = reader.varint()?;
+    let edge_count = reader.varint()?;
+
+    // Every vertex and edge occupies at least one byte, which bounds the allocations below.
+    if vertex_count > bytes.len() || edge_count > bytes.len() {
+        Err(Error::new_if("truncated snapshot"))?
+    }
+
+    let mut degrees = Vec::with_capacity(vertex_count);
+    for _ in 0..vertex_count {
+        degrees.push(reader.varint()?);
+    }
+    let degree_sum = degrees
+        .iter()
+        .try_fold(0usize, |sum, degree| sum.checked_add(*degree));
+    if degree_sum != Some(edge_count) {
+        Err(Error::new_if("offsets do not match the number of edges"))?
+    }
+
+    let mut edges = Vec::with_capacity(edge_count);
+    for (src, degree) in degrees.iter().enumerate() {
+        let mut dst = 0usize;
+        for _ in 0..*degree {
+            dst = dst
+                .checked_add(reader.varint()?)
+                .filter(|dst| *dst < vertex_count)
+                .ok_or_else(|| Error::new_if("edge to unknown vertex"))?;
+            edges.push((src, dst));
+        }
+    }
+
+    let mut weights = Vec::with_capacity(edge_count);
+    for _ in 0..edge_count {
+        weights.push(reader.weight::<W>()?);
+    }
+
+    if reader.index != bytes.len() {
+        Err(Error::new_if("trailing bytes after snapshot"))?
+    }
+
+    let vertex_ids = storage.extend_with_vertices(vertex_count);
+
+    // Edges of an undirected snapshot are added in both directions to a directed storage.
+    let is_mirrored = !is_directed && Dir::is_directed();
+    let mut storage_edges = Vec::with_capacity(if is_mirrored {
+        2 * edge_count
+    } else {
+        edge_count
+    });
+    for ((src, dst), weight) in edges.into_iter().zip(weights) {
+        let (src_id, dst_id) = (vertex_ids[src], vertex_ids[dst]);
+        storage_edges.push((src_id, dst_id, E::init(weight)));
+        if is_mirrored && src_id != dst_id {
+            storage_edges.push((dst_id, src_id, E::init(weight)));
+        }
+    }
+    storage.extend_with_edges_unchecked(storage_edges);
+
+    Ok(vertex_ids)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::DefaultEdge;
+    use crate::storage::{DiList, DiMat, List, Map};
+
+    #[test]
+    fn empty_storage() {
+        // Given: An empty storage.
+        let storage = List::<usize>::init();
+
+        // When: Saving and loading it.
+        let bytes = save(&storage);
+        let mut loaded = List::<usize>::init();
+        let ids = load(&mut loaded, &bytes).unwrap();
+
+        // Then:
+        assert!(ids.is_empty());
+        assert_eq!(loaded.vertex_count(), 0);
+    }
+
+    #[test]
+    fn save_then_load_undirected() {
+        // Given: Storage
+        //
+        //      a  --1--  b  --inf--  c       d
+        //      |                     |
+        //      '---------300---------'
+        //
+        let mut storage = Map::<i32>::init();
+        let a = storage.add_vertex();
+        let b = storage.add_vertex();
+        let c = storage.add_vertex();
+        storage.add_vertex();
+        storage.add_edge_unchecked(a, b, 1.into());
+        storage.add_edge_unchecked(b, c, DefaultEdge::init(Magnitude::PosInfinite));
+        storage.add_edge_unchecked(a, c, 300.into());
+
+        // When: Saving and loading it.
+        let bytes = save(&storage);
+        let mut loaded = Map::<i32>::init();
+        let ids = load(&mut loaded, &bytes).unwrap();
+
+        // Then:
+        assert_eq!(loaded.vertex_count(), 4);
+        assert_eq!(loaded.edge_count(), 3);
+        let (a, b, c) = (ids[0], ids[1], ids[2]);
+        assert_eq!(
+            loaded.edges_between_unchecked(b, a)[0].get_weight(),
+            &1.into()
+        );
+        assert!(loaded.edges_between_unchecked(c, b)[0]
+            .get_weight()
+            .is_pos_infinite());
+        assert_eq!(
+            loaded.edges_between_unchecked(a, c)[0].get_weight(),
+            &300.into()
+        );
+    }
+
+    #[test]
+    fn save_then_load_directed() {
+        // Given: Directed storage with a removed vertex.
+        let mut storage = DiMat::<f64>::init();
+        let a = storage.add_vertex();
+        let b = storage.add_vertex();
+        let c = storage.add_vertex();
+        storage.add_edge_unchecked(a, c, 0.5.into());
+        storage.add_edge_unchecked(c, a, 1.5.into());
+        storage.add_edge_unchecked(c, c, 2.0.into());
+        storage.remove_vertex_unchecked(b);
+
+        // When: Saving and loading it.
+        let bytes = save(&storage);
+        let mut loaded = DiMat::<f64>::init();
+        let ids = load(&mut loaded, &bytes).unwrap();
+
+        // Then:
+        assert_eq!(loaded.vertex_count(), 2);
+        assert_eq!(loaded.edge_count(), 3);
+        let (a, c) = (ids[0], ids[1]);
+        assert_eq!(
+            loaded.edges_between_unchecked(a, c)[0].get_weight(),
+            &0.5.into()
+        );
+        assert_eq!(
+            loaded.edges_between_unchecked(c, a)[0].get_weight(),
+            &1.5.into()
+        );
+        assert_eq!(
+            loaded.edges_between_unchecked(c, c)[0].get_weight(),
+            &2.0.into()
+        );
+    }
+
+    #[test]
+    fn load_directed_into_undirected() {
+        // Given: Snapshot of a directed storage.
+        let mut storage = DiMat::<usize>::init();
+        storage.add_vertex();
+        let bytes = save(&storage);
+
+        // When: Loading it into an undirected storage.
+        let mut loaded = List::<usize>::init();
+
+        // Then:
+        assert!(load(&mut loaded, &bytes).is_err());
+    }
+
+    #[test]
+    fn load_invalid_bytes() {
+        // Given: Snapshot of a storage.
+        let mut storage = List::<usize>::init();
+        let a = storage.add_vertex();
+        let b = storage.add_vertex();
+        storage.add_edge_unchecked(a, b, 1.into());
+        let bytes = save(&storage);
+
+        // When: Loading corrupted versions of it.
+        let mut loaded = List::<usize>::init();
+
+        // Then: Nothing is added to the storage.
+        assert!(load(&mut loaded, b"not a snapshot").is_err());
+        assert!(load(&mut loaded, &bytes[..bytes.len() - 1]).is_err());
+        assert_eq!(loaded.vertex_count(), 0);
+    }
+
+    #[test]
+    fn load_overflowing_counts() {
+        // Given: Crafted snapshots whose degrees and destination deltas overflow when added up.
+        let max = {
+            let mut bytes = vec![];
+            push_varint(&mut bytes, usize::MAX);
+            bytes
+        };
+        let mut header = vec![];
+        push_header::<usize>(&mut header, true);
+        let overflowing_degrees = [&header[..], &[2, 1], &max, &[1], &[0]].concat();
+        let overflowing_targets = [&header[..], &[2, 2, 2, 0, 1], &max].concat();
+
+        // When: Loading them.
+        let mut loaded = DiList::<usize>::init();
+
+        // Then: An error is returned instead of a panic, and nothing is added to the storage.
+        assert!(load(&mut loaded, &overflowing_degrees).is_err());
+        assert!(load(&mut loaded, &overflowing_targets).is_err());
+        assert_eq!(loaded.vertex_count(), 0);
+    }
+
+    #[test]
+    fn load_undirected_into_directed() {
+        // Given: Snapshot of storage
+        //
+        //      a  --1--  b ---.
+        //                ^    | 2
+        //                '----'
+        //
+        let mut storage = List::<usize>::init();
+        let a = storage.add_vertex();
+        let b = storage.add_vertex();
+        storage.add_edge_unchecked(a, b, 1.into());
+        storage.add_edge_unchecked(b, b, 2.into());
+        let bytes = save(&storage);
+
+        // When: Loading it into a directed storage.
+        let mut loaded = DiList::<usize>::init();
+        let ids = load(&mut loaded, &bytes).unwrap();
+
+        // Then: Each edge is added in both directions, and the self loop once.
+        let (a, b) = (ids[0], ids[1]);
+        assert_eq!(loaded.edge_count(), 3);
+        assert_eq!(loaded.edges_between_unchecked(a, b).len(), 1);
+        assert_eq!(loaded.edges_between_unchecked(b, a).len(), 1);
+        assert_eq!(loaded.edges_between_unchecked(b, b).len(), 1);
+    }
+
+    #[test]
+    fn load_into_another_weight_type() {
+        // Given: Snapshot of a storage with `u32` weights.
+        let mut storage = List::<u32>::init();
+        let a = storage.add_vertex();
+        let b = storage.add_vertex();
+        storage.add_edge_unchecked(a, b, 70000.into());
+        let bytes = save(&storage);
+
+        // When: Loading it into storages with a smaller type and a type of the same size.
+        let mut smaller = List::<u16>::init();
+        let mut same_size = List::<f32>::init();
+
+        // Then:
+        assert!(load(&mut smaller, &bytes).is_err());
+        assert!(load(&mut same_size, &bytes).is_err());
+        assert_eq!(smaller.vertex_count(), 0);
+        assert_eq!(same_size.vertex_count(), 0);
+    }
+
+    #[test]
+    fn load_invalid_direction_flag() {
+        // Given: Snapshot with a direction flag other than 0 or 1.
+        let mut bytes = save(&DiList::<usize>::init());
+        bytes[5] = 7;
+
+        // When: Loading it.
+        let mut loaded = DiList::<usize>::init();
+
+        // Then:
+        assert!(load(&mut loaded, &bytes).is_err());
+    }
+
+    #[test]
+    fn load_trailing_bytes() {
+        // Given: Snapshot followed by another byte.
+        let mut storage = DiList::<usize>::init();
+        storage.add_vertex();
+        let mut bytes = save(&storage);
+        bytes.push(0);
+
+        // When: Loading it.
+        let mut loaded = DiList::<usize>::init();
+
+        // Then:
+        assert!(load(&mut loaded, &bytes).is_err());
+        assert_eq!(loaded.vertex_count(), 0);
+    }
+
+    #[test]
+    fn load_varint_with_lost_bits() {
+        // Given: Snapshot whose vertex count has bits beyond the size of `usize` in its last byte.
+        let mut bytes = vec![];
+        push_header::<usize>(&mut bytes, true);
+        let last_shift = (usize::BITS - 1) / 7 * 7;
+        bytes.extend(std::iter::repeat_n(0x80, (last_shift / 7) as usize));
+        bytes.push(1 << (usize::BITS - last_shift));
+        push_varint(&mut bytes, 0);
+
+        // When: Loading it.
+        let mut loaded = DiList::<usize>::init();
+
+        // Then: Count is not read as zero.
+        assert!(load(&mut loaded, &bytes).is_err());
+    }
+}
@@ -0,0 +1,353 @@
+use magnitude::Magnitude;
+use num_traits::One;
+use std::any::Any;
+
+use super::add_read_edge;
+use crate::graph::{Edge, EdgeDir};
+use crate::misc::rng::Rng;
+use crate::storage::GraphStorage;
+
+fn add_unit_edge<W, E, Dir, S>(storage: &mut S, src_id: usize, dst_id: usize)
+where
+    W: Copy + One + Any,
+    E: Edge<W>,
+    Dir: EdgeDir,
+    S: GraphStorage<W, E, Dir>,
+{
+    // Adding an undirected edge never fails.
+    let weight: Magnitude<W> = W::one().into();
+    let _ = add_read_edge(storage, src_id, dst_id, weight, false);
+}
+
+fn add_vertices<W, E, Dir, S>(storage: &mut S, rows: usize, cols: usize) -> Vec<Vec<usize>>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    S: GraphStorage<W, E, Dir>,
+{
+    (0..rows)
+        .map(|_| (0..cols).map(|_| storage.add_vertex()).collect())
+        .collect()
+}
+
+/// Adds a `rows` x `cols` grid to the storage. Each vertex is connected to the vertices above, below, left and right of it.
+///
+/// # Arguments
+/// * `storage`: Storage to add the grid to.
+/// * `rows`: Number of rows.
+/// * `cols`: Number of columns.
+///
+/// # Returns
+/// Id of the vertices in the form of `ids[row][col]`.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::List;
+/// use prepona::io::synthetic;
+///
+/// let mut storage = List::<usize>::init();
+/// let ids = synthetic::grid(&mut storage, 3, 4);
+///
+/// assert_eq!(storage.vertex_count(), 12);
+/// assert_eq!(storage.edge_count(), 3 * 3 + 2 * 4);
+/// assert_eq!(synthetic::grid_distance((0, 0), (2, 3)), 5);
+/// ```
+pub fn grid<W, E, Dir, S>(storage: &mut S, rows: usize, cols: usize) -> Vec<Vec<usize>>
+where
+    W: Copy + One + Any,
+    E: Edge<W>,
+    Dir: EdgeDir,
+    S: GraphStorage<W, E, Dir>,
+{
+    let ids = add_vertices(storage, rows, cols);
+
+    for row in 0..rows {
+        for col in 0..cols {
+            if col + 1 < cols {
+                add_unit_edge(storage, ids[row][col], ids[row][col + 1]);
+            }
+            if row + 1 < rows {
+                add_unit_edge(storage, ids[row][col], ids[row + 1][col]);
+            }
+        }
+    }
+
+    ids
+}
+
+/// # Arguments
+/// * `src`: Position of the source vertex in the form of (row, col).
+/// * `dst`: Position of the destination vertex in the form of (row, col).
+///
+/// # Returns
+/// Distance between the two vertices in a grid generated by [`grid`](crate::io::synthetic::grid).
+pub fn grid_distance(src: (usize, usize), dst: (usize, usize)) -> usize {
+    src.0.abs_diff(dst.0) + src.1.abs_diff(dst.1)
+}
+
+/// Adds a `rows` x `cols` torus to the storage.
+/// A torus is a grid whose first and last rows, and first and last columns are connected as well.
+///
+/// # Arguments
+/// * `storage`: Storage to add the torus to.
+/// * `rows`: Number of rows.
+/// * `cols`: Number of columns.
+///
+/// # Returns
+/// Id of the vertices in the form of `ids[row][col]`.
+///
+/// # Panics
+/// If `rows` or `cols` is less than 3. Smaller tori would contain loops or parallel edges.
+pub fn torus<W, E, Dir, S>(storage: &mut S, rows: usize, cols: usize) -> Vec<Vec<usize>>
+where
+    W: Copy + One + Any,
+    E: Edge<W>,
+    Dir: EdgeDir,
+    S: GraphStorage<W, E, Dir>,
+{
+    assert!(
+        rows >= 3 && cols >= 3,
+        "Torus must have at least 3 rows and 3 columns"
+    );
+
+    let ids = add_vertices(storage, rows, cols);
+
+    for row in 0..rows {
+        for col in 0..cols {
+            add_unit_edge(storage, ids[row][col], ids[row][(col + 1) % cols]);
+            add_unit_edge(storage, ids[row][col], ids[(row + 1) % rows][col]);
+        }
+    }
+
+    ids
+}
+
+/// # Arguments
+/// * `rows`: Number of rows of the torus.
+/// * `cols`: Number of columns of the torus.
+/// * `src`: Position of the source vertex in the form of (row, col).
+/// * `dst`: Position of the destination vertex in the form of (row, col).
+///
+/// # Returns
+/// Distance between the two vertices in a torus generated by [`torus`](crate::io::synthetic::torus).
+pub fn torus_distance(rows: usize, cols: usize, src: (usize, usize), dst: (usize, usize)) -> usize {
+    let row_diff = src.0.abs_diff(dst.0);
+    let col_diff = src.1.abs_diff(dst.1);
+
+    row_diff.min(rows - row_diff) + col_diff.min(cols - col_diff)
+}
+
+/// # Arguments
+/// * `rows`: Number of rows of the torus.
+/// * `cols`: Number of columns of the torus.
+///
+/// # Returns
+/// Diameter of a torus generated by [`torus`](crate::io::synthetic::torus).
+pub fn torus_diameter(rows: usize, cols: usize) -> usize {
+    rows / 2 + cols / 2
+}
+
+/// Adds a graph generated by the [stochastic block model](https://en.wikipedia.org/wiki/Stochastic_block_model) to the storage.
+///
+/// Vertices are partitioned into communities. Each pair of vertices in the same community is connected with probability `p_in`
+/// and each pair of vertices in different communities is connected with probability `p_out`.
+/// With `p_out` equal to zero, each community is a union of connected components, and with `p_in` equal to one, each community is a clique.
+///
+/// # Arguments
+/// * `storage`: Storage to add the graph to.
+/// * `sizes`: Size of each community.
+/// * `p_in`: Probability of an edge inside a community.
+/// * `p_out`: Probability of an edge between communities.
+/// * `rng`: Source of randomness.
+///
+/// # Returns
+/// Id of the vertices of each community.
+///
+/// # Complexity
+/// O(|V|^2)
+pub fn sbm<W, E, Dir, S>(
+    storage: &mut S,
+    sizes: &[usize],
+    p_in: f64,
+    p_out: f64,
+    rng: &mut Rng,
+) -> Vec<Vec<usize>>
+where
+    W: Copy + One + Any,
+    E: Edge<W>,
+    Dir: EdgeDir,
+    S: GraphStorage<W, E, Dir>,
+{
+    let communities: Vec<Vec<usize>> = sizes
+        .iter()
+        .map(|size| (0..*size).map(|_| storage.add_vertex()).collect())
+        .collect();
+
+    for (i, community1) in communities.iter().enumerate() {
+        for (j, community2) in communities.iter().enumerate().skip(i) {
+            let p = if i == j { p_in } else { p_out };
+            if p <= 0.0 {
+                continue;
+            }
+
+            for (index, src_id) in community1.iter().enumerate() {
+                // Inside a community, each pair is considered once.
+                let start = if i == j { index + 1 } else { 0 };
+
+                for dst_id in &community2[start..] {
+                    if rng.next_bool(p) {
+                        add_unit_edge(storage, *src_id, *dst_id);
+                    }
+                }
+            }
+        }
+    }
+
+    communities
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::{ConnectedComponents, Dijkstra};
+    use crate::graph::{ListGraph, MatGraph};
+    use crate::provide::Edges;
+    use crate::storage::{DiList, List, Mat};
+
+    #[test]
+    fn grid_distances() {
+        // Given: A 4 x 5 grid.
+        let mut storage = Mat::<usize>::init();
+        let ids = grid(&mut storage, 4, 5);
+        let graph = MatGraph::init(storage);
+
+        // When: Finding shortest paths from the corner.
+        let sp = Dijkstra::init(&graph).execute(&graph, ids[0][0]);
+
+        // Then:
+        for (row, row_ids) in ids.iter().enumerate() {
+            for (col, id) in row_ids.iter().enumerate() {
+                assert_eq!(
+                    sp.distance_to(*id).unwrap(),
+                    grid_distance((0, 0), (row, col)).into()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn torus_distances() {
+        // Given: A 5 x 6 directed torus.
+        let mut storage = DiList::<usize>::init();
+        let ids = torus(&mut storage, 5, 6);
+        let graph = ListGraph::init(storage);
+
+        // When: Finding shortest paths from a vertex.
+        let sp = Dijkstra::init(&graph).execute(&graph, ids[1][2]);
+
+        // Then:
+        assert_eq!(graph.edges_count(), 2 * 2 * 5 * 6);
+        let mut max_distance = 0;
+        for (row, row_ids) in ids.iter().enumerate() {
+            for (col, id) in row_ids.iter().enumerate() {
+                let distance = torus_distance(5, 6, (1, 2), (row, col));
+                assert_eq!(sp.distance_to(*id).unwrap(), distance.into());
+                max_distance = max_distance.max(distance);
+            }
+        }
+        assert_eq!(max_distance, torus_diameter(5, 6));
+    }
+
+    #[test]
+    #[should_panic]
+    fn small_torus() {
+        torus(&mut List::<usize>::init(), 2, 5);
+    }
+
+    #[test]
+    fn sbm_communities() {
+        // Given: Three disconnected cliques.
+        let mut storage = List::<usize>::init();
+        let communities = sbm(&mut storage, &[3, 4, 5], 1.0, 0.0, &mut Rng::seed_from(0));
+        let graph = ListGraph::init(storage);
+
+        // When: Finding connected components.
+        let mut ccs = ConnectedComponents::init(&graph).execute(&graph);
+
+        // Then: Each component is a community.
+        assert_eq!(graph.edges_count(), 3 + 6 + 10);
+        for cc in &mut ccs {
+            cc.sort_unstable();
+        }
+        ccs.sort();
+        assert_eq!(ccs, communities);
+    }
+}
+
+#[cfg(all(test, feature = "stress"))]
+mod stress_tests {
+    use super::*;
+    use crate::algo::{ConnectedComponents, Dijkstra};
+    use crate::graph::ListGraph;
+    use crate::storage::List;
+
+    #[test]
+    fn dijkstra_on_large_torus() {
+        let (rows, cols) = (60, 70);
+        let mut storage = List::<usize>::init();
+        let ids = torus(&mut storage, rows, cols);
+        let graph = ListGraph::init(storage);
+
+        let sp = Dijkstra::init(&graph).execute(&graph, ids[0][0]);
+
+        for (row, row_ids) in ids.iter().enumerate() {
+            for (col, id) in row_ids.iter().enumerate() {
+                assert_eq!(
+                    sp.distance_to(*id).unwrap(),
+                    torus_distance(rows, cols, (0, 0), (row, col)).into()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn dijkstra_on_large_grid() {
+        let (rows, cols) = (50, 80);
+        let mut storage = List::<usize>::init();
+        let ids = grid(&mut storage, rows, cols);
+        let graph = ListGraph::init(storage);
+
+        let sp = Dijkstra::init(&graph).execute(&graph, ids[rows / 2][cols / 3]);
+
+        for (row, row_ids) in ids.iter().enumerate() {
+            for (col, id) in row_ids.iter().enumerate() {
+                assert_eq!(
+                    sp.distance_to(*id).unwrap(),
+                    grid_distance((rows / 2, cols / 3), (row, col)).into()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn connected_components_of_large_sbm() {
+        let sizes = vec![1000; 20];
+        let mut storage = List::<usize>::init();
+        let communities = sbm(&mut storage, &sizes, 0.01, 0.0, &mut Rng::seed_from(7));
+        let graph = ListGraph::init(storage);
+
+        let ccs = ConnectedComponents::init(&graph).execute(&graph);
+
+        // With this density each community is connected with overwhelming probability.
+        let mut ccs: Vec<Vec<usize>> = ccs
+            .into_iter()
+            .map(|mut cc| {
+                cc.sort_unstable();
+                cc
+            })
+            .collect();
+        ccs.sort();
+        assert_eq!(ccs, communities);
+    }
+}
@@ -5,6 +5,23 @@
 /// So for one algorithm to be executable on a specific graph or subgraph, it is necessary for the graphs exposed functionalities to match the requirements of the algorithm.
 pub mod algo;
 
+/// Summaries and statistics that describe the structure of a graph.
+pub mod analysis;
+
+/// Generating and randomizing graphs.
+///
+/// Randomized functions receive a seeded [`Rng`](crate::misc::rng::Rng), so their results are reproducible.
+pub mod gen;
+
+/// Reading and writing graphs from and to text formats.
+///
+/// Readers add the vertices and edges they read to any [`GraphStorage`](crate::storage::GraphStorage).
+/// Since vertices are labeled in text formats, readers also return a map from each label to the id of its vertex in the storage.
+pub mod io;
+
+/// Utilities that are shared between other modules.
+pub mod misc;
+
 /// Re-exports traits and structs that are necessary to accomplish basic tasks with prepona.
 pub mod prelude;
 
@@ -0,0 +1,5 @@
+/// Seedable pseudo random number generation and deterministic random streams.
+pub mod rng;
+
+#[cfg(feature = "parallel")]
+pub(crate) mod par;
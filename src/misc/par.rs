@@ -0,0 +1,34 @@
+use std::thread;
+
+// Number of threads used by `par_` variants of algorithms.
+fn thread_count() -> usize {
+    thread::available_parallelism().map_or(1, |count| count.get())
+}
+
+// Applies `f` to each item using scoped threads. Items are split into contiguous chunks, one for each thread,
+// and results are returned in the same order as the items so the output does not depend on scheduling.
+pub(crate) fn map<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    if items.is_empty() {
+        return vec![];
+    }
+
+    let chunk_size = items.len().div_ceil(thread_count());
+    let f = &f;
+
+    thread::scope(|scope| {
+        let handles: Vec<_> = items
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || chunk.iter().map(f).collect::<Vec<R>>()))
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect()
+    })
+}
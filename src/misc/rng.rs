@@ -0,0 +1,224 @@
+/// A small and fast pseudo random number generator based on [SplitMix64](https://prng.di.unimi.it/splitmix64.c).
+///
+/// Generated numbers only depend on the seed, so randomized algorithms that receive the same seed produce the same results.
+/// Every randomized generator and algorithm of the crate takes an `Rng` or a seed instead of a global source of randomness, for this reason.
+/// It is not suitable for cryptographic purposes.
+///
+/// # Examples
+/// ```
+/// use prepona::misc::rng::Rng;
+///
+/// let mut rng1 = Rng::seed_from(42);
+/// let mut rng2 = Rng::seed_from(42);
+///
+/// assert_eq!(rng1.next_u64(), rng2.next_u64());
+/// assert!(rng1.next_below(10) < 10);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// Initializes the generator with the given `seed`.
+    pub fn seed_from(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    /// # Returns
+    /// Next pseudo random number.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        mix(self.state)
+    }
+
+    /// # Returns
+    /// A pseudo random number in range [0, 1).
+    pub fn next_f64(&mut self) -> f64 {
+        // Use the 53 high bits to fill the mantissa.
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// # Arguments
+    /// `bound`: Exclusive upper bound of the generated number.
+    ///
+    /// # Returns
+    /// A pseudo random number in range [0, `bound`).
+    ///
+    /// # Panics
+    /// If `bound` is zero.
+    pub fn next_below(&mut self, bound: usize) -> usize {
+        assert!(bound > 0, "Bound must be greater than zero");
+
+        // Lemire's multiply-shift reduction with rejection to remove the bias.
+        let bound = bound as u64;
+        let threshold = bound.wrapping_neg() % bound;
+        loop {
+            let product = (self.next_u64() as u128) * (bound as u128);
+            if (product as u64) >= threshold {
+                return (product >> 64) as usize;
+            }
+        }
+    }
+
+    /// # Returns
+    /// `true` with probability `p`.
+    pub fn next_bool(&mut self, p: f64) -> bool {
+        self.next_f64() < p
+    }
+
+    /// Shuffles the `slice` in place using Fisher-Yates algorithm.
+    pub fn shuffle<T>(&mut self, slice: &mut [T]) {
+        for i in (1..slice.len()).rev() {
+            let j = self.next_below(i + 1);
+            slice.swap(i, j);
+        }
+    }
+}
+
+// Finalizer of SplitMix64 which maps each input to a well mixed output.
+pub(crate) fn mix(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// Derives independent [`Rng`](crate::misc::rng::Rng) streams from a master seed.
+///
+/// Each stream is identified by an index(like id of a vertex or index of a thread) and only depends on the master seed and its index,
+/// not on the order in which streams are requested. So randomized algorithms that process vertices in parallel or in different orders still produce the same results.
+///
+/// # Examples
+/// ```
+/// use prepona::misc::rng::SplitRng;
+///
+/// let split_rng = SplitRng::seed_from(42);
+///
+/// // Streams can be requested in any order.
+/// let mut stream3 = split_rng.stream(3);
+/// let mut stream1 = split_rng.stream(1);
+///
+/// assert_eq!(stream1.next_u64(), SplitRng::seed_from(42).stream(1).next_u64());
+/// assert_ne!(stream1.next_u64(), stream3.next_u64());
+/// ```
+#[derive(Debug, Clone)]
+pub struct SplitRng {
+    seed: u64,
+}
+
+impl SplitRng {
+    /// Initializes the splitter with the given master `seed`.
+    pub fn seed_from(seed: u64) -> Self {
+        SplitRng { seed }
+    }
+
+    /// # Arguments
+    /// `index`: Index of the stream.
+    ///
+    /// # Returns
+    /// The stream with index: `index`.
+    pub fn stream(&self, index: usize) -> Rng {
+        // Mixing twice with different constants keeps streams of neighboring indices and seeds apart.
+        Rng::seed_from(mix(
+            mix(self.seed ^ 0x6a09_e667_f3bc_c909).wrapping_add(index as u64)
+        ))
+    }
+
+    /// # Arguments
+    /// `count`: Number of streams.
+    ///
+    /// # Returns
+    /// Streams with indices in range [0, `count`).
+    pub fn streams(&self, count: usize) -> Vec<Rng> {
+        (0..count).map(|index| self.stream(index)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_sequence() {
+        // Given: Two generators with the same seed.
+        let mut rng1 = Rng::seed_from(7);
+        let mut rng2 = Rng::seed_from(7);
+
+        // Then:
+        for _ in 0..100 {
+            assert_eq!(rng1.next_u64(), rng2.next_u64());
+        }
+    }
+
+    #[test]
+    fn different_seed_different_sequence() {
+        // Given: Two generators with different seeds.
+        let mut rng1 = Rng::seed_from(1);
+        let mut rng2 = Rng::seed_from(2);
+
+        // Then:
+        assert!((0..10).any(|_| rng1.next_u64() != rng2.next_u64()));
+    }
+
+    #[test]
+    fn numbers_in_range() {
+        // Given: A generator.
+        let mut rng = Rng::seed_from(0);
+
+        // Then:
+        for _ in 0..1000 {
+            assert!(rng.next_below(7) < 7);
+
+            let f = rng.next_f64();
+            assert!((0.0..1.0).contains(&f));
+        }
+    }
+
+    #[test]
+    fn shuffle_is_permutation() {
+        // Given: A generator and a vector.
+        let mut rng = Rng::seed_from(3);
+        let mut vector: Vec<usize> = (0..20).collect();
+
+        // When: Shuffling the vector.
+        rng.shuffle(&mut vector);
+
+        // Then:
+        vector.sort_unstable();
+        assert_eq!(vector, (0..20).collect::<Vec<usize>>());
+    }
+
+    #[test]
+    fn streams_are_reproducible() {
+        // Given: Two splitters with the same seed.
+        let split_rng1 = SplitRng::seed_from(11);
+        let split_rng2 = SplitRng::seed_from(11);
+
+        // When: Requesting streams in different orders.
+        let streams1 = split_rng1.streams(4);
+        let mut streams2: Vec<Rng> = (0..4).rev().map(|index| split_rng2.stream(index)).collect();
+        streams2.reverse();
+
+        // Then:
+        for (mut rng1, mut rng2) in streams1.into_iter().zip(streams2) {
+            assert_eq!(rng1.next_u64(), rng2.next_u64());
+        }
+    }
+
+    #[test]
+    fn streams_are_distinct() {
+        // Given: A splitter.
+        let split_rng = SplitRng::seed_from(0);
+
+        // When: Taking the first number of many streams.
+        let mut firsts: Vec<u64> = (0..1000)
+            .map(|index| split_rng.stream(index).next_u64())
+            .collect();
+
+        // Then: All of them are different.
+        firsts.sort_unstable();
+        firsts.dedup();
+        assert_eq!(firsts.len(), 1000);
+    }
+}
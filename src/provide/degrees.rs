@@ -0,0 +1,288 @@
+use std::collections::HashSet;
+
+use crate::graph::{Edge, EdgeDir};
+use crate::provide::{Edges, Graph};
+
+// Storages differ in how many times they return an undirected self loop, so edges are deduplicated by their ids.
+fn distinct_edges_from<W, E, G>(graph: &G, vertex_id: usize) -> Vec<(usize, &E)>
+where
+    E: Edge<W>,
+    G: Edges<W, E> + ?Sized,
+{
+    let mut seen_ids = HashSet::new();
+
+    graph
+        .edges_from_unchecked(vertex_id)
+        .into_iter()
+        .filter(|(_, edge)| seen_ids.insert(edge.get_id()))
+        .collect()
+}
+
+/// Provides degrees and weighted degrees(strengths) of vertices.
+///
+/// Self loops are counted the same way regardless of the storage:
+/// * In undirected graphs a loop is incident to its vertex twice, so it adds 2 to the degree.
+/// * In directed graphs a loop adds 1 to both in and out degrees.
+///
+/// It's implemented for every graph that provides [`Edges`](crate::provide::Edges) and [`Graph`](crate::provide::Graph).
+///
+/// ## Generic Parameters
+/// * `W`: **W**eight type associated with edges.
+/// * `E`: **E**dge type that graph uses.
+/// * `Dir`: **Dir**ection of edges: [`DirectedEdge`](crate::graph::DirectedEdge) or [`UndirectedEdge`](crate::graph::UndirectedEdge).
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph with a loop on a.
+/// //
+/// //      a  ---  b
+/// //      ⤷ ⤴
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// graph.add_edge_unchecked(a, a, 2.into());
+/// graph.add_edge_unchecked(a, b, 3.into());
+///
+/// // When:
+/// let degree = graph.degree(a);
+/// let strength = graph.weighted_degree(a, |edge| edge.get_weight().unwrap() as f64);
+///
+/// // Then:
+/// assert_eq!(degree, 3);
+/// assert_eq!(strength, 7.0);
+/// ```
+pub trait Degrees<W, E: Edge<W>, Dir: EdgeDir>: Edges<W, E> {
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// Number of edges going out of the vertex.
+    /// For undirected graphs it's the same as [`degree`](crate::provide::Degrees::degree).
+    ///
+    /// # Complexity
+    /// O(|E<sub>out</sub>|)
+    fn out_degree(&self, vertex_id: usize) -> usize {
+        self.weighted_out_degree(vertex_id, |_| 1.0) as usize
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// Number of edges coming into the vertex.
+    /// For undirected graphs it's the same as [`degree`](crate::provide::Degrees::degree).
+    ///
+    /// # Complexity
+    /// O(|E|) for directed graphs.
+    fn in_degree(&self, vertex_id: usize) -> usize {
+        self.weighted_in_degree(vertex_id, |_| 1.0) as usize
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// Number of edges incident to the vertex, counting undirected self loops twice.
+    /// For directed graphs it's the same as [`out_degree`](crate::provide::Degrees::out_degree).
+    ///
+    /// # Complexity
+    /// O(|E<sub>out</sub>|)
+    fn degree(&self, vertex_id: usize) -> usize {
+        self.weighted_degree(vertex_id, |_| 1.0) as usize
+    }
+
+    /// # Arguments
+    /// * `vertex_id`: Id of the vertex.
+    /// * `weight_fn`: Weight of each edge.
+    ///
+    /// # Returns
+    /// Sum of weights of edges going out of the vertex.
+    /// For undirected graphs it's the same as [`weighted_degree`](crate::provide::Degrees::weighted_degree).
+    ///
+    /// # Complexity
+    /// O(|E<sub>out</sub>|)
+    fn weighted_out_degree<F>(&self, vertex_id: usize, weight_fn: F) -> f64
+    where
+        F: Fn(&E) -> f64,
+    {
+        if Dir::is_undirected() {
+            return self.weighted_degree(vertex_id, weight_fn);
+        }
+
+        distinct_edges_from(self, vertex_id)
+            .into_iter()
+            .map(|(_, edge)| weight_fn(edge))
+            .sum()
+    }
+
+    /// # Arguments
+    /// * `vertex_id`: Id of the vertex.
+    /// * `weight_fn`: Weight of each edge.
+    ///
+    /// # Returns
+    /// Sum of weights of edges coming into the vertex.
+    /// For undirected graphs it's the same as [`weighted_degree`](crate::provide::Degrees::weighted_degree).
+    ///
+    /// # Complexity
+    /// O(|E|) for directed graphs.
+    fn weighted_in_degree<F>(&self, vertex_id: usize, weight_fn: F) -> f64
+    where
+        F: Fn(&E) -> f64,
+    {
+        if Dir::is_undirected() {
+            return self.weighted_degree(vertex_id, weight_fn);
+        }
+
+        self.edges()
+            .into_iter()
+            .filter(|(_, dst_id, _)| *dst_id == vertex_id)
+            .map(|(_, _, edge)| weight_fn(edge))
+            .sum()
+    }
+
+    /// Also known as the strength of the vertex.
+    ///
+    /// # Arguments
+    /// * `vertex_id`: Id of the vertex.
+    /// * `weight_fn`: Weight of each edge.
+    ///
+    /// # Returns
+    /// Sum of weights of edges incident to the vertex, counting undirected self loops twice.
+    /// For directed graphs it's the same as [`weighted_out_degree`](crate::provide::Degrees::weighted_out_degree).
+    ///
+    /// # Complexity
+    /// O(|E<sub>out</sub>|)
+    fn weighted_degree<F>(&self, vertex_id: usize, weight_fn: F) -> f64
+    where
+        F: Fn(&E) -> f64,
+    {
+        if Dir::is_directed() {
+            return self.weighted_out_degree(vertex_id, weight_fn);
+        }
+
+        distinct_edges_from(self, vertex_id)
+            .into_iter()
+            .map(|(dst_id, edge)| {
+                let weight = weight_fn(edge);
+                if dst_id == vertex_id {
+                    2.0 * weight
+                } else {
+                    weight
+                }
+            })
+            .sum()
+    }
+}
+
+impl<W, E, Dir, G> Degrees<W, E, Dir> for G
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Edges<W, E> + Graph<W, E, Dir>,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{
+        DefaultEdge, DirectedEdge, MatGraph, MultiGraph, MultiListGraph, UndirectedEdge,
+    };
+    use crate::storage::{DiList, DiMat, List, Map, Mat};
+
+    fn weight_of(edge: &DefaultEdge<usize>) -> f64 {
+        edge.get_weight().unwrap() as f64
+    }
+
+    fn undirected_degrees<G>(graph: &mut G) -> (usize, f64, usize, usize)
+    where
+        G: Degrees<usize, DefaultEdge<usize>, UndirectedEdge>
+            + Graph<usize, DefaultEdge<usize>, UndirectedEdge>,
+    {
+        // Given: Graph
+        //
+        //      a  ---  b
+        //      ⤷ ⤴
+        //
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        graph.add_edge_unchecked(a, a, DefaultEdge::init(2.into()));
+        graph.add_edge_unchecked(a, b, DefaultEdge::init(3.into()));
+
+        // When:
+        (
+            graph.degree(a),
+            graph.weighted_degree(a, weight_of),
+            graph.out_degree(a),
+            graph.in_degree(b),
+        )
+    }
+
+    #[test]
+    fn undirected_loops_are_counted_twice_in_every_storage() {
+        let expected = (3, 7.0, 3, 1);
+
+        assert_eq!(
+            undirected_degrees(&mut MatGraph::init(Mat::<usize>::init())),
+            expected
+        );
+        assert_eq!(
+            undirected_degrees(&mut MultiListGraph::init(List::<usize>::init())),
+            expected
+        );
+        assert_eq!(
+            undirected_degrees(&mut MultiGraph::init(Map::<usize>::init())),
+            expected
+        );
+    }
+
+    fn directed_degrees<G>(graph: &mut G) -> (usize, usize, usize, f64, f64)
+    where
+        G: Degrees<usize, DefaultEdge<usize>, DirectedEdge>
+            + Graph<usize, DefaultEdge<usize>, DirectedEdge>,
+    {
+        // Given: Graph
+        //
+        //      a  -->  b
+        //      ^       |
+        //      |       v
+        //      '-----  c
+        //      ⤷ ⤴
+        //
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, a, DefaultEdge::init(1.into()));
+        graph.add_edge_unchecked(a, b, DefaultEdge::init(2.into()));
+        graph.add_edge_unchecked(b, c, DefaultEdge::init(3.into()));
+        graph.add_edge_unchecked(c, a, DefaultEdge::init(4.into()));
+
+        // When:
+        (
+            graph.out_degree(a),
+            graph.in_degree(a),
+            graph.degree(a),
+            graph.weighted_out_degree(a, weight_of),
+            graph.weighted_in_degree(a, weight_of),
+        )
+    }
+
+    #[test]
+    fn directed_loops_are_counted_once_in_each_direction() {
+        let expected = (2, 2, 2, 3.0, 5.0);
+
+        assert_eq!(
+            directed_degrees(&mut MatGraph::init(DiMat::<usize>::init())),
+            expected
+        );
+        assert_eq!(
+            directed_degrees(&mut MultiListGraph::init(DiList::<usize>::init())),
+            expected
+        );
+    }
+}
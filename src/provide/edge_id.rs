@@ -0,0 +1,77 @@
+/// Identifies an edge independent of its end points.
+///
+/// Edges between the same two vertices(parallel edges) have different ids, so an `EdgeId` is enough to address a single edge even in multigraphs.
+/// An id is stable until its edge is removed. After that, the id may get reused for a new edge.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::List;
+/// use prepona::graph::MultiListGraph;
+///
+/// let mut graph = MultiListGraph::init(List::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let e1 = EdgeId::new(graph.add_edge_unchecked(a, b, 1.into()));
+/// let e2 = EdgeId::new(graph.add_edge_unchecked(a, b, 2.into()));
+///
+/// assert_eq!(graph.edge_ids().len(), 2);
+/// assert_eq!(graph.edge_endpoints(e2), Some((a, b)));
+/// assert!(graph.contains_edge_id(e1));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct EdgeId(usize);
+
+impl EdgeId {
+    /// # Arguments
+    /// `id`: Id of the edge as returned by functions like [`add_edge`](crate::provide::Graph::add_edge).
+    ///
+    /// # Returns
+    /// The edge id.
+    pub fn new(id: usize) -> Self {
+        EdgeId(id)
+    }
+
+    /// # Returns
+    /// The raw id of the edge, which can be passed to functions like [`edge`](crate::provide::Edges::edge).
+    pub fn index(&self) -> usize {
+        self.0
+    }
+}
+
+impl From<usize> for EdgeId {
+    fn from(id: usize) -> Self {
+        EdgeId(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::MatGraph;
+    use crate::provide::{Edges, Graph};
+    use crate::storage::Mat;
+
+    #[test]
+    fn removed_edge_id() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  c
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let ab_id = EdgeId::new(graph.add_edge_unchecked(a, b, 1.into()));
+        let bc_id = EdgeId::new(graph.add_edge_unchecked(b, c, 1.into()));
+
+        // When: Removing edge bc.
+        graph.remove_edge_unchecked(b, c, bc_id.index());
+
+        // Then:
+        assert_eq!(graph.edge_ids(), vec![ab_id]);
+        assert_eq!(graph.edge_endpoints(ab_id), Some((a, b)));
+        assert_eq!(graph.edge_endpoints(bc_id), None);
+        assert!(!graph.contains_edge_id(bc_id));
+    }
+}
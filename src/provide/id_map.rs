@@ -1,9 +1,91 @@
 use std::collections::HashMap;
 
+use crate::provide::Vertices;
+use crate::storage::{from_index, to_index, to_index_unchecked, Index};
+
+// Dense lookups grow up to this length regardless of the number of entries.
+const MIN_DENSE_LEN: usize = 1024;
+
+// Maps ids to ids.
+// Lookups start as a vector indexed by id which is much faster than hashing.
+// If ids turn out to be sparse(the vector would be mostly empty), lookup falls back to a hash map.
+enum Lookup {
+    Dense { ids: Vec<Option<Index>>, len: usize },
+    Sparse(HashMap<Index, Index>),
+}
+
+impl Lookup {
+    fn with_capacity(capacity: usize) -> Self {
+        Lookup::Dense {
+            ids: Vec::with_capacity(capacity),
+            len: 0,
+        }
+    }
+
+    fn insert(&mut self, key: usize, value: usize) {
+        match self {
+            Lookup::Dense { ids, len } => {
+                if key >= ids.len() {
+                    if key >= MIN_DENSE_LEN.max(4 * (*len + 1)) {
+                        // Vector would be mostly empty.
+                        let mut map: HashMap<Index, Index> = ids
+                            .iter()
+                            .enumerate()
+                            .filter_map(|(key, value)| {
+                                value.map(|value| (to_index_unchecked(key), value))
+                            })
+                            .collect();
+                        map.insert(to_index_unchecked(key), to_index_unchecked(value));
+
+                        *self = Lookup::Sparse(map);
+                        return;
+                    }
+
+                    ids.resize(key + 1, None);
+                }
+
+                if ids[key].replace(to_index_unchecked(value)).is_none() {
+                    *len += 1;
+                }
+            }
+            Lookup::Sparse(map) => {
+                map.insert(to_index_unchecked(key), to_index_unchecked(value));
+            }
+        }
+    }
+
+    fn get(&self, key: usize) -> Option<usize> {
+        match self {
+            Lookup::Dense { ids, .. } => ids.get(key).copied().flatten().map(from_index),
+            Lookup::Sparse(map) => to_index(key)
+                .ok()
+                .and_then(|key| map.get(&key))
+                .copied()
+                .map(from_index),
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Lookup::Dense { len, .. } => *len,
+            Lookup::Sparse(map) => map.len(),
+        }
+    }
+}
+
 /// Stores a two-way mapping between set of real and virtual ids.
+///
+/// Vertex ids are plain `usize` values that storages allocate close to each other(starting from zero and reusing the ids of removed vertices).
+/// So mappings are stored in vectors indexed by id and lookups need no hashing.
+/// If ids are too sparse for a vector, the map falls back to hashing.
+///
+/// When real ids are already continuos(0..n), [`identity`](crate::provide::IdMap::identity) map can be used which passes ids through without any allocation or lookup.
 pub struct IdMap {
-    real_to_virt: HashMap<usize, usize>,
-    virt_to_real: HashMap<usize, usize>,
+    // If set, ids in range 0..count are mapped to themselves and lookups are skipped.
+    identity_count: Option<usize>,
+
+    real_to_virt: Lookup,
+    virt_to_real: Lookup,
 }
 
 impl IdMap {
@@ -14,8 +96,54 @@ impl IdMap {
     /// An empty id map.
     pub fn init(entries_count: usize) -> Self {
         IdMap {
-            real_to_virt: HashMap::with_capacity(entries_count),
-            virt_to_real: HashMap::with_capacity(entries_count),
+            identity_count: None,
+
+            real_to_virt: Lookup::with_capacity(entries_count),
+            virt_to_real: Lookup::with_capacity(entries_count),
+        }
+    }
+
+    /// # Arguments
+    /// `graph`: Graph to map its vertex ids.
+    ///
+    /// # Returns
+    /// The mapping between vertex ids of the graph(real ids) and continuos ids in range 0..|V|(virtual ids).
+    /// For more info checkout [`continuos_id_map`](crate::provide::Vertices::continuos_id_map).
+    pub fn new<G: Vertices + ?Sized>(graph: &G) -> Self {
+        graph.continuos_id_map()
+    }
+
+    /// # Arguments
+    /// `count`: Number of ids.
+    ///
+    /// # Returns
+    /// An id map that maps each id in range 0..`count` to itself.
+    /// It allocates nothing until a new mapping is inserted.
+    pub fn identity(count: usize) -> Self {
+        IdMap {
+            identity_count: Some(count),
+
+            real_to_virt: Lookup::with_capacity(0),
+            virt_to_real: Lookup::with_capacity(0),
+        }
+    }
+
+    /// # Returns
+    /// `true` if map passes ids through without any lookup.
+    pub fn is_identity(&self) -> bool {
+        self.identity_count.is_some()
+    }
+
+    // Turns an identity map into a regular one so arbitrary mappings can be inserted.
+    fn materialize(&mut self) {
+        if let Some(count) = self.identity_count.take() {
+            self.real_to_virt = Lookup::with_capacity(count);
+            self.virt_to_real = Lookup::with_capacity(count);
+
+            for id in 0..count {
+                self.real_to_virt.insert(id, id);
+                self.virt_to_real.insert(id, id);
+            }
         }
     }
 
@@ -25,6 +153,7 @@ impl IdMap {
     /// * `real_id`: Real id of the mapping.
     /// * `virt_id`: Virtual id of the mapping.
     pub fn put_real_to_virt(&mut self, real_id: usize, virt_id: usize) {
+        self.materialize();
         self.real_to_virt.insert(real_id, virt_id);
     }
 
@@ -34,6 +163,7 @@ impl IdMap {
     /// * `virt_id`: Virtual id of the mapping.
     /// * `real_id`: Real id of the mapping.
     pub fn put_virt_to_real(&mut self, virt_id: usize, real_id: usize) {
+        self.materialize();
         self.virt_to_real.insert(virt_id, real_id);
     }
 
@@ -43,7 +173,25 @@ impl IdMap {
     /// # Returns
     /// Virtual id of id: `real_id`.
     pub fn virt_id_of(&self, real_id: usize) -> usize {
-        self.real_to_virt.get(&real_id).copied().unwrap()
+        if let Some(count) = self.identity_count {
+            assert!(real_id < count, "Id: {} is not mapped", real_id);
+            return real_id;
+        }
+
+        self.real_to_virt.get(real_id).unwrap()
+    }
+
+    /// # Arguments
+    /// `real_id`: Real id of the mapping.
+    ///
+    /// # Returns
+    /// * `Some`: Containing virtual id of id: `real_id`.
+    /// * `None`: If `real_id` is not mapped.
+    pub fn try_virt_id_of(&self, real_id: usize) -> Option<usize> {
+        match self.identity_count {
+            Some(count) => Some(real_id).filter(|real_id| *real_id < count),
+            None => self.real_to_virt.get(real_id),
+        }
     }
 
     /// # Arguments
@@ -52,7 +200,25 @@ impl IdMap {
     /// # Returns
     /// Real id of id: `virt_id`.
     pub fn real_id_of(&self, virt_id: usize) -> usize {
-        self.virt_to_real.get(&virt_id).copied().unwrap()
+        if let Some(count) = self.identity_count {
+            assert!(virt_id < count, "Id: {} is not mapped", virt_id);
+            return virt_id;
+        }
+
+        self.virt_to_real.get(virt_id).unwrap()
+    }
+
+    /// # Returns
+    /// Number of real ids that are mapped to a virtual id.
+    pub fn len(&self) -> usize {
+        self.identity_count
+            .unwrap_or_else(|| self.real_to_virt.len())
+    }
+
+    /// # Returns
+    /// `true` if no real id is mapped to a virtual id.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
     }
 }
 
@@ -68,8 +234,8 @@ mod tests {
         // When: Doing nothing.
 
         // Then:
-        assert!(id_map.virt_to_real.is_empty());
-        assert!(id_map.real_to_virt.is_empty());
+        assert_eq!(id_map.virt_to_real.len(), 0);
+        assert_eq!(id_map.real_to_virt.len(), 0);
     }
 
     #[test]
@@ -81,7 +247,7 @@ mod tests {
         id_map.put_real_to_virt(1, 2);
 
         // Then:
-        assert_eq!(*id_map.real_to_virt.get(&1).unwrap(), 2);
+        assert_eq!(id_map.real_to_virt.get(1).unwrap(), 2);
         assert_eq!(id_map.virt_to_real.len(), 0);
     }
 
@@ -94,8 +260,8 @@ mod tests {
         id_map.put_virt_to_real(0, 1);
 
         // Then:
-        assert_eq!(*id_map.virt_to_real.get(&0).unwrap(), 1);
-        assert!(id_map.real_to_virt.is_empty());
+        assert_eq!(id_map.virt_to_real.get(0).unwrap(), 1);
+        assert_eq!(id_map.real_to_virt.len(), 0);
     }
 
     #[test]
@@ -110,6 +276,22 @@ mod tests {
         assert_eq!(id_map.virt_id_of(0), 1);
     }
 
+    #[test]
+    fn try_get_real_to_virt() {
+        // Given: An identity map and a regular one.
+        let identity = IdMap::identity(2);
+        let mut id_map = IdMap::init(1);
+
+        // When:
+        id_map.put_real_to_virt(3, 0);
+
+        // Then: Api must return the mapping only if it exists.
+        assert_eq!(identity.try_virt_id_of(1), Some(1));
+        assert_eq!(identity.try_virt_id_of(2), None);
+        assert_eq!(id_map.try_virt_id_of(3), Some(0));
+        assert_eq!(id_map.try_virt_id_of(0), None);
+    }
+
     #[test]
     fn get_virt_to_real() {
         // Given: An empty id map.
@@ -121,4 +303,100 @@ mod tests {
         // Then: Api must return the mapping.
         assert_eq!(id_map.real_id_of(0), 1);
     }
+
+    #[test]
+    fn sparse_ids() {
+        // Given: An empty id map.
+        let mut id_map = IdMap::init(3);
+
+        // When: Mapping ids that are far apart, up to the largest id that can be stored.
+        let max_id = from_index(Index::MAX);
+        for (virt_id, real_id) in [0, 1_000_000, max_id].iter().enumerate() {
+            id_map.put_real_to_virt(*real_id, virt_id);
+            id_map.put_virt_to_real(virt_id, *real_id);
+        }
+
+        // Then:
+        assert!(matches!(id_map.real_to_virt, Lookup::Sparse(_)));
+        assert!(matches!(id_map.virt_to_real, Lookup::Dense { .. }));
+        assert_eq!(id_map.virt_id_of(1_000_000), 1);
+        assert_eq!(id_map.virt_id_of(max_id), 2);
+        assert_eq!(id_map.real_id_of(0), 0);
+        assert_eq!(id_map.real_to_virt.len(), 3);
+    }
+
+    #[test]
+    fn overwrite_mapping() {
+        // Given: An id map with one mapping.
+        let mut id_map = IdMap::init(1);
+        id_map.put_real_to_virt(3, 0);
+
+        // When: Overwriting the mapping.
+        id_map.put_real_to_virt(3, 1);
+
+        // Then:
+        assert_eq!(id_map.virt_id_of(3), 1);
+        assert_eq!(id_map.real_to_virt.len(), 1);
+    }
+
+    #[test]
+    fn identity() {
+        // Given: An identity map.
+        let id_map = IdMap::identity(3);
+
+        // Then: Ids pass through without allocation.
+        assert!(id_map.is_identity());
+        assert_eq!(id_map.real_to_virt.len(), 0);
+        assert_eq!(id_map.len(), 3);
+        assert_eq!(id_map.virt_id_of(2), 2);
+        assert_eq!(id_map.real_id_of(0), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn identity_out_of_range() {
+        IdMap::identity(3).virt_id_of(3);
+    }
+
+    #[test]
+    fn put_into_identity() {
+        // Given: An identity map.
+        let mut id_map = IdMap::identity(2);
+
+        // When: Inserting a new mapping.
+        id_map.put_real_to_virt(5, 2);
+        id_map.put_virt_to_real(2, 5);
+
+        // Then: Previous mappings are kept.
+        assert!(!id_map.is_identity());
+        assert_eq!(id_map.virt_id_of(1), 1);
+        assert_eq!(id_map.real_id_of(0), 0);
+        assert_eq!(id_map.virt_id_of(5), 2);
+        assert_eq!(id_map.len(), 3);
+    }
+
+    #[test]
+    fn continuos_id_map_of_dense_graph() {
+        use crate::graph::MatGraph;
+        use crate::provide::{Graph, Vertices};
+        use crate::storage::Mat;
+
+        // Given: Graph with three vertices.
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+
+        // Then: Ids are already continuos.
+        assert!(graph.continuos_id_map().is_identity());
+
+        // When: Removing a vertex from the middle.
+        graph.remove_vertex_unchecked(b);
+
+        // Then: Ids must be mapped.
+        let id_map = graph.continuos_id_map();
+        assert!(!id_map.is_identity());
+        assert_eq!(id_map.virt_id_of(a), 0);
+        assert_eq!(id_map.virt_id_of(c), 1);
+    }
 }
@@ -1,7 +1,15 @@
+mod degrees;
+mod edge_id;
 mod id_map;
 
+/// Differential testing of graphs and storages: running the same operations on two of them and comparing what the provider traits report.
+pub mod test_util;
+
 use anyhow::Result;
+pub use degrees::Degrees;
+pub use edge_id::EdgeId;
 pub use id_map::IdMap;
+use std::collections::HashSet;
 
 use crate::graph::{Edge, EdgeDir};
 
@@ -42,19 +50,28 @@ pub trait Vertices {
     /// For example: \
     /// if vertex ids are [1, 3, 4], they will be mapped to [0, 1, 2] so you can store information about vertices in a vector and use the new vertex ids as index.
     ///
+    /// If vertex ids are already continuos, an [`identity`](crate::provide::IdMap::identity) map is returned which costs nothing to create or query.
+    ///
     /// # Returns
     /// The two-way mapping between scattered and continuos ids.
     fn continuos_id_map(&self) -> IdMap {
-        let mut id_map = IdMap::init(self.vertex_count());
+        let vertices = self.vertices();
 
-        // Map each vertex id to its index in vector returned by vertices.
-        self.vertices()
+        if vertices
             .iter()
             .enumerate()
-            .for_each(|(virt_id, &real_id)| {
-                id_map.put_virt_to_real(virt_id, real_id);
-                id_map.put_real_to_virt(real_id, virt_id);
-            });
+            .all(|(virt_id, &real_id)| virt_id == real_id)
+        {
+            return IdMap::identity(vertices.len());
+        }
+
+        let mut id_map = IdMap::init(vertices.len());
+
+        // Map each vertex id to its index in vector returned by vertices.
+        vertices.iter().enumerate().for_each(|(virt_id, &real_id)| {
+            id_map.put_virt_to_real(virt_id, real_id);
+            id_map.put_real_to_virt(real_id, virt_id);
+        });
 
         id_map
     }
@@ -167,6 +184,28 @@ pub trait Edges<W, E: Edge<W>> {
     /// `true` if there is at least one edge from `src_id` to `dst_id` and `false` otherwise.
     fn has_any_edge_unchecked(&self, src_id: usize, dst_id: usize) -> bool;
 
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// `true` if there is an edge from the vertex to itself, `false` otherwise or if the vertex does not exist.
+    fn has_self_loop(&self, vertex_id: usize) -> bool {
+        self.has_any_edge(vertex_id, vertex_id).unwrap_or(false)
+    }
+
+    /// # Returns
+    /// All self loops in the graph in the format: (`vertex_id`, `edge`).
+    /// Each loop is reported once, regardless of how many times the underlying storage reports it.
+    fn self_loops(&self) -> Vec<(usize, &E)> {
+        let mut seen_ids = HashSet::new();
+
+        self.edges()
+            .into_iter()
+            .filter(|(src_id, dst_id, edge)| src_id == dst_id && seen_ids.insert(edge.get_id()))
+            .map(|(vertex_id, _, edge)| (vertex_id, edge))
+            .collect()
+    }
+
     /// # Returns
     /// All edges in the graph in the format: (`src_id`, `dst_id`, `edge`).
     fn edges(&self) -> Vec<(usize, usize, &E)>;
@@ -193,10 +232,53 @@ pub trait Edges<W, E: Edge<W>> {
     /// * `true`: if graph contains the edge with specified id.
     /// * `false`: otherwise.
     fn contains_edge(&self, edge_id: usize) -> bool;
+
+    /// # Returns
+    /// Ids of all edges in the graph.
+    fn edge_ids(&self) -> Vec<EdgeId> {
+        self.edges()
+            .into_iter()
+            .map(|(_, _, edge)| EdgeId::new(edge.get_id()))
+            .collect()
+    }
+
+    /// # Arguments
+    /// `edge_id`: Id of the edge.
+    ///
+    /// # Returns
+    /// * `Some`: Containing the end points of the edge in the format of (`src_id`, `dst_id`).
+    ///   For undirected edges, `src_id` is the smaller id.
+    /// * `None`: If graph does not contain the edge.
+    ///
+    /// # Complexity
+    /// O(|E|) unless the graph provides a faster implementation.
+    fn edge_endpoints(&self, edge_id: EdgeId) -> Option<(usize, usize)> {
+        self.edges()
+            .into_iter()
+            .find(|(_, _, edge)| edge.get_id() == edge_id.index())
+            .map(|(src_id, dst_id, _)| (src_id, dst_id))
+    }
+
+    /// # Arguments
+    /// `edge_id`: Id of the edge.
+    ///
+    /// # Returns
+    /// * `true`: if graph contains the edge with specified id.
+    /// * `false`: otherwise.
+    fn contains_edge_id(&self, edge_id: EdgeId) -> bool {
+        self.contains_edge(edge_id.index())
+    }
 }
 
+/// Provides the direction of edges of a graph.
+///
+/// Algorithms that only read a graph but whose result depends on the direction of its edges use this trait instead of [`Graph`],
+/// so read only views like [`WeightedView`](crate::graph::WeightedView) can be passed to them.
+/// Every [`Graph`] provides the direction of its edges.
+pub trait Direction<Ty: EdgeDir> {}
+
 /// Provides basic functionalities to store graph information.
-pub trait Graph<W, E: Edge<W>, Ty: EdgeDir> {
+pub trait Graph<W, E: Edge<W>, Ty: EdgeDir>: Direction<Ty> {
     /// Adds a vertex to the graph.
     ///
     /// # Returns
@@ -0,0 +1,570 @@
+use std::collections::HashMap;
+
+use crate::graph::{DefaultEdge, Edge, EdgeDir};
+use crate::misc::rng::Rng;
+use crate::provide::{Degrees, Edges, Graph, Neighbors, Vertices};
+
+/// A mutation applied to both graphs by [`assert_same_observations`](crate::provide::test_util::assert_same_observations).
+///
+/// Vertices and edges are not referred to by their ids, because two graphs may assign different ids to the same vertex or edge.
+/// Instead each argument picks one of the vertices(or edges) that are currently in the graphs, in the order they were added.
+/// Arguments wrap around the number of vertices(or edges), so any sequence of operations is valid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    /// Adds a vertex.
+    AddVertex,
+
+    /// Removes the picked vertex along with its edges.
+    RemoveVertex(usize),
+
+    /// Adds an edge between picked source and destination vertices, with the specified weight.
+    AddEdge(usize, usize, usize),
+
+    /// Removes the picked edge.
+    RemoveEdge(usize),
+
+    /// Changes weight of the picked edge.
+    UpdateEdge(usize, usize),
+}
+
+impl Operation {
+    // Decodes an operation from the front of `data`.
+    // Returns the operation and number of consumed bytes, or `None` if `data` is too short.
+    fn decode(data: &[u8]) -> Option<(Operation, usize)> {
+        let arg = |i: usize| data.get(i).map(|byte| *byte as usize);
+
+        match arg(0)? % 5 {
+            0 => Some((Operation::AddVertex, 1)),
+            1 => Some((Operation::RemoveVertex(arg(1)?), 2)),
+            2 => Some((Operation::AddEdge(arg(1)?, arg(2)?, arg(3)?), 4)),
+            3 => Some((Operation::RemoveEdge(arg(1)?), 2)),
+            _ => Some((Operation::UpdateEdge(arg(1)?, arg(2)?), 3)),
+        }
+    }
+}
+
+/// Generates a random sequence of operations.
+///
+/// Vertices and edges are added more often than they are removed, so graphs grow over the sequence.
+/// Self loops and parallel edges are generated as well.
+///
+/// # Arguments
+/// * `count`: Number of operations.
+/// * `rng`: Source of randomness.
+///
+/// # Returns
+/// The generated operations.
+pub fn random_operations(count: usize, rng: &mut Rng) -> Vec<Operation> {
+    (0..count)
+        .map(|_| match rng.next_below(20) {
+            0..=4 => Operation::AddVertex,
+            5..=6 => Operation::RemoveVertex(rng.next_below(256)),
+            7..=13 => Operation::AddEdge(
+                rng.next_below(256),
+                rng.next_below(256),
+                rng.next_below(100),
+            ),
+            14..=16 => Operation::RemoveEdge(rng.next_below(256)),
+            _ => Operation::UpdateEdge(rng.next_below(256), rng.next_below(100)),
+        })
+        .collect()
+}
+
+/// Decodes a sequence of operations from raw bytes, so a fuzzer can drive [`assert_same_observations`](crate::provide::test_util::assert_same_observations).
+///
+/// Every byte sequence decodes to a valid sequence of operations. Trailing bytes that do not form a whole operation are ignored.
+///
+/// # Arguments
+/// `data`: Bytes generated by the fuzzer.
+///
+/// # Returns
+/// The decoded operations.
+pub fn operations_from_bytes(mut data: &[u8]) -> Vec<Operation> {
+    let mut operations = vec![];
+
+    while let Some((operation, consumed)) = Operation::decode(data) {
+        operations.push(operation);
+        data = &data[consumed..];
+    }
+
+    operations
+}
+
+// Vertices and edges are labeled in the order they are added, so observations of two graphs can be compared.
+struct TrackedEdge {
+    label: usize,
+    left: (usize, usize, usize),
+    right: (usize, usize, usize),
+}
+
+#[derive(Default)]
+struct Tracker {
+    vertices: Vec<(usize, usize, usize)>,
+    edges: Vec<TrackedEdge>,
+    removed_vertices: Vec<(usize, usize)>,
+    removed_edges: Vec<(usize, usize)>,
+    next_label: usize,
+}
+
+impl Tracker {
+    fn new_label(&mut self) -> usize {
+        self.next_label += 1;
+
+        self.next_label - 1
+    }
+
+    // # Returns
+    // Maps from ids of vertices and edges of one side to their labels.
+    fn labels_of(&self, side: Side) -> (HashMap<usize, usize>, HashMap<usize, usize>) {
+        let vertex_labels = self
+            .vertices
+            .iter()
+            .map(|(label, left_id, right_id)| (side.pick(*left_id, *right_id), *label))
+            .collect();
+
+        let edge_labels = self
+            .edges
+            .iter()
+            .map(|edge| (side.pick(edge.left.2, edge.right.2), edge.label))
+            .collect();
+
+        (vertex_labels, edge_labels)
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Side {
+    Left,
+    Right,
+}
+
+impl Side {
+    fn pick<T>(self, left: T, right: T) -> T {
+        match self {
+            Side::Left => left,
+            Side::Right => right,
+        }
+    }
+}
+
+// Everything that can be observed about one vertex through provider traits, in terms of labels.
+#[derive(Debug, PartialEq)]
+struct VertexObservation {
+    label: usize,
+    neighbors: Vec<usize>,
+    edges_from: Vec<(usize, usize, usize)>,
+    has_self_loop: bool,
+    degree: usize,
+    in_degree: usize,
+    out_degree: usize,
+}
+
+// Everything that can be observed about an ordered pair of vertices through provider traits, in terms of labels.
+#[derive(Debug, PartialEq)]
+struct PairObservation {
+    src_label: usize,
+    dst_label: usize,
+    has_any_edge: bool,
+    edges_between: Vec<(usize, usize)>,
+}
+
+// Everything that can be observed about a graph through provider traits, in terms of labels.
+#[derive(Debug, PartialEq)]
+struct Observation {
+    vertex_count: usize,
+    edges_count: usize,
+    vertices: Vec<usize>,
+    edges: Vec<(usize, usize, usize, usize)>,
+    self_loops: Vec<(usize, usize)>,
+    per_vertex: Vec<VertexObservation>,
+    between: Vec<PairObservation>,
+    per_edge: Vec<(usize, bool, Option<usize>, Option<usize>)>,
+    removed_vertices: Vec<(bool, bool)>,
+    removed_edges: Vec<bool>,
+}
+
+fn sorted<T: Ord>(mut items: Vec<T>) -> Vec<T> {
+    items.sort_unstable();
+    items.dedup();
+
+    items
+}
+
+fn weight_of(edge: &DefaultEdge<usize>) -> usize {
+    edge.get_weight().unwrap()
+}
+
+fn observe<Dir, G>(graph: &G, tracker: &Tracker, side: Side) -> Observation
+where
+    Dir: EdgeDir,
+    G: Graph<usize, DefaultEdge<usize>, Dir>
+        + Vertices
+        + Neighbors
+        + Edges<usize, DefaultEdge<usize>>,
+{
+    // Unknown ids get a label that never matches, so they show up in the comparison instead of panicking here.
+    let (vertex_labels, edge_labels) = tracker.labels_of(side);
+    let vertex = |vertex_id: usize| *vertex_labels.get(&vertex_id).unwrap_or(&usize::MAX);
+    let edge = |edge: &DefaultEdge<usize>| {
+        (
+            *edge_labels.get(&edge.get_id()).unwrap_or(&usize::MAX),
+            weight_of(edge),
+        )
+    };
+    let ids: Vec<usize> = tracker
+        .vertices
+        .iter()
+        .map(|(_, left_id, right_id)| side.pick(*left_id, *right_id))
+        .collect();
+
+    // Storages differ in how many times they return an undirected self loop or a vertex with parallel edges to it.
+    // So these are compared as sets, which is enough because each edge has a unique label.
+    let per_vertex = ids
+        .iter()
+        .map(|vertex_id| VertexObservation {
+            label: vertex(*vertex_id),
+            neighbors: sorted(
+                graph
+                    .neighbors_unchecked(*vertex_id)
+                    .into_iter()
+                    .map(vertex)
+                    .collect(),
+            ),
+            edges_from: sorted(
+                graph
+                    .edges_from_unchecked(*vertex_id)
+                    .into_iter()
+                    .map(|(dst_id, e)| {
+                        let (label, weight) = edge(e);
+                        (vertex(dst_id), label, weight)
+                    })
+                    .collect(),
+            ),
+            has_self_loop: graph.has_self_loop(*vertex_id),
+            degree: graph.degree(*vertex_id),
+            in_degree: graph.in_degree(*vertex_id),
+            out_degree: graph.out_degree(*vertex_id),
+        })
+        .collect();
+
+    let mut between = vec![];
+    for src_id in &ids {
+        for dst_id in &ids {
+            between.push(PairObservation {
+                src_label: vertex(*src_id),
+                dst_label: vertex(*dst_id),
+                has_any_edge: graph.has_any_edge_unchecked(*src_id, *dst_id),
+                edges_between: sorted(
+                    graph
+                        .edges_between_unchecked(*src_id, *dst_id)
+                        .into_iter()
+                        .map(edge)
+                        .collect(),
+                ),
+            });
+        }
+    }
+
+    let per_edge = tracker
+        .edges
+        .iter()
+        .map(|tracked| {
+            let (src_id, dst_id, edge_id) = side.pick(tracked.left, tracked.right);
+
+            (
+                tracked.label,
+                graph.contains_edge(edge_id),
+                graph.edge(edge_id).ok().map(weight_of),
+                graph
+                    .edge_between(src_id, dst_id, edge_id)
+                    .ok()
+                    .map(weight_of),
+            )
+        })
+        .collect();
+
+    Observation {
+        vertex_count: graph.vertex_count(),
+        edges_count: graph.edges_count(),
+        vertices: sorted(graph.vertices().into_iter().map(vertex).collect()),
+        edges: sorted(
+            graph
+                .edges()
+                .into_iter()
+                .map(|(src_id, dst_id, e)| {
+                    let (src, dst) = (vertex(src_id), vertex(dst_id));
+                    let (label, weight) = edge(e);
+
+                    if Dir::is_undirected() {
+                        (src.min(dst), src.max(dst), label, weight)
+                    } else {
+                        (src, dst, label, weight)
+                    }
+                })
+                .collect(),
+        ),
+        self_loops: sorted(
+            graph
+                .self_loops()
+                .into_iter()
+                .map(|(vertex_id, e)| (vertex(vertex_id), edge(e).0))
+                .collect(),
+        ),
+        per_vertex,
+        between,
+        per_edge,
+        // Ids may be reused by one graph but not the other, so a removed id is only checked while it's not reused.
+        removed_vertices: tracker
+            .removed_vertices
+            .iter()
+            .map(|(left_id, right_id)| {
+                let vertex_id = side.pick(*left_id, *right_id);
+                let is_reused = vertex_labels.contains_key(&vertex_id);
+
+                (
+                    !is_reused && graph.contains_vertex(vertex_id),
+                    !is_reused && graph.edges_from(vertex_id).is_ok(),
+                )
+            })
+            .collect(),
+        removed_edges: tracker
+            .removed_edges
+            .iter()
+            .map(|(left_id, right_id)| {
+                let edge_id = side.pick(*left_id, *right_id);
+
+                !edge_labels.contains_key(&edge_id) && graph.contains_edge(edge_id)
+            })
+            .collect(),
+    }
+}
+
+/// Runs the same sequence of operations on two graphs and asserts that every observation made through provider traits is identical.
+///
+/// It's meant for validating a new storage against an existing one:
+/// wrap both storages in a graph that accepts loops and parallel edges, like [`MultiGraph`](crate::graph::MultiGraph),
+/// then run sequences generated by [`random_operations`](crate::provide::test_util::random_operations)
+/// or decoded by [`operations_from_bytes`](crate::provide::test_util::operations_from_bytes).
+///
+/// Ids of vertices and edges may differ between the graphs. They are matched by the order in which they are added.
+///
+/// After each operation the following are compared:
+/// * Outcome of the operation: success or failure, and weight of the removed edge.
+/// * Vertices, edges, self loops and their counts.
+/// * Neighbors, outgoing edges and degrees of each vertex.
+/// * Edges between each pair of vertices.
+/// * Lookup of each edge by its id.
+/// * Whether removed vertices and edges are still reported by `contains_vertex`, `contains_edge` and `edges_from`.
+///
+/// Vertices and edges are looked up pair by pair, so keep sequences short: a few hundred operations at most.
+///
+/// # Arguments
+/// * `left`: First graph. It must be empty.
+/// * `right`: Second graph. It must be empty.
+/// * `operations`: Operations to apply to both graphs.
+///
+/// # Panics
+/// If the graphs disagree on any observation. The message contains the index of the offending operation and both observations.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::graph::MultiGraph;
+/// use prepona::storage::{DiList, DiMap};
+/// use prepona::provide::test_util::{assert_same_observations, random_operations};
+/// use prepona::misc::rng::Rng;
+///
+/// // Given: Two different storages for directed graphs.
+/// let mut list = MultiGraph::init(DiList::<usize>::init());
+/// let mut map = MultiGraph::init(DiMap::<usize>::init());
+///
+/// // When: Applying the same random operations to both.
+/// let operations = random_operations(100, &mut Rng::seed_from(1));
+///
+/// // Then: They behave the same.
+/// assert_same_observations(&mut list, &mut map, &operations);
+/// ```
+pub fn assert_same_observations<Dir, G1, G2>(
+    left: &mut G1,
+    right: &mut G2,
+    operations: &[Operation],
+) where
+    Dir: EdgeDir,
+    G1: Graph<usize, DefaultEdge<usize>, Dir>
+        + Vertices
+        + Neighbors
+        + Edges<usize, DefaultEdge<usize>>,
+    G2: Graph<usize, DefaultEdge<usize>, Dir>
+        + Vertices
+        + Neighbors
+        + Edges<usize, DefaultEdge<usize>>,
+{
+    let mut tracker = Tracker::default();
+
+    for (step, operation) in operations.iter().enumerate() {
+        let context = || format!("after operation #{}: {:?}", step, operation);
+
+        match *operation {
+            Operation::AddVertex => {
+                let label = tracker.new_label();
+                tracker
+                    .vertices
+                    .push((label, left.add_vertex(), right.add_vertex()));
+            }
+            Operation::RemoveVertex(k) if !tracker.vertices.is_empty() => {
+                let (_, left_id, right_id) = tracker.vertices.remove(k % tracker.vertices.len());
+
+                assert_eq!(
+                    left.remove_vertex(left_id).is_ok(),
+                    right.remove_vertex(right_id).is_ok(),
+                    "Outcome of the removal differs {}",
+                    context()
+                );
+
+                let (removed_edges, edges): (Vec<TrackedEdge>, Vec<TrackedEdge>) =
+                    tracker.edges.drain(..).partition(|edge| {
+                        let (src_id, dst_id, _) = edge.left;
+                        src_id == left_id || dst_id == left_id
+                    });
+                tracker.edges = edges;
+                tracker.removed_edges.extend(
+                    removed_edges
+                        .into_iter()
+                        .map(|edge| (edge.left.2, edge.right.2)),
+                );
+                tracker.removed_vertices.push((left_id, right_id));
+            }
+            Operation::AddEdge(src, dst, weight) if !tracker.vertices.is_empty() => {
+                let (_, left_src, right_src) = tracker.vertices[src % tracker.vertices.len()];
+                let (_, left_dst, right_dst) = tracker.vertices[dst % tracker.vertices.len()];
+
+                match (
+                    left.add_edge(left_src, left_dst, weight.into()),
+                    right.add_edge(right_src, right_dst, weight.into()),
+                ) {
+                    (Ok(left_id), Ok(right_id)) => {
+                        let label = tracker.new_label();
+                        tracker.edges.push(TrackedEdge {
+                            label,
+                            left: (left_src, left_dst, left_id),
+                            right: (right_src, right_dst, right_id),
+                        });
+                    }
+                    (Err(_), Err(_)) => {}
+                    (left_result, right_result) => panic!(
+                        "Outcome of adding the edge differs {}: {:?} and {:?}",
+                        context(),
+                        left_result,
+                        right_result
+                    ),
+                }
+            }
+            Operation::RemoveEdge(k) if !tracker.edges.is_empty() => {
+                let edge = tracker.edges.remove(k % tracker.edges.len());
+                let (left_src, left_dst, left_id) = edge.left;
+                let (right_src, right_dst, right_id) = edge.right;
+
+                assert_eq!(
+                    left.remove_edge(left_src, left_dst, left_id)
+                        .map(|edge| weight_of(&edge))
+                        .ok(),
+                    right
+                        .remove_edge(right_src, right_dst, right_id)
+                        .map(|edge| weight_of(&edge))
+                        .ok(),
+                    "Removed edges differ {}",
+                    context()
+                );
+                tracker.removed_edges.push((left_id, right_id));
+            }
+            Operation::UpdateEdge(k, weight) if !tracker.edges.is_empty() => {
+                let edge = &tracker.edges[k % tracker.edges.len()];
+                let (left_src, left_dst, left_id) = edge.left;
+                let (right_src, right_dst, right_id) = edge.right;
+
+                assert_eq!(
+                    left.update_edge(left_src, left_dst, left_id, weight.into())
+                        .is_ok(),
+                    right
+                        .update_edge(right_src, right_dst, right_id, weight.into())
+                        .is_ok(),
+                    "Outcome of the update differs {}",
+                    context()
+                );
+            }
+            _ => {}
+        }
+
+        assert_eq!(
+            observe(left, &tracker, Side::Left),
+            observe(right, &tracker, Side::Right),
+            "Graphs disagree {}",
+            context()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{DirectedEdge, MultiGraph, SimpleGraph, UndirectedEdge};
+    use crate::storage::{DiList, DiMap, DiMat, GraphStorage, List, Map, Mat};
+
+    fn assert_same_storages<Dir, S1, S2>(left: fn() -> S1, right: fn() -> S2)
+    where
+        Dir: EdgeDir,
+        S1: GraphStorage<usize, DefaultEdge<usize>, Dir>,
+        S2: GraphStorage<usize, DefaultEdge<usize>, Dir>,
+    {
+        for seed in 0..10 {
+            let operations = random_operations(120, &mut Rng::seed_from(seed));
+
+            assert_same_observations(
+                &mut MultiGraph::init(left()),
+                &mut MultiGraph::init(right()),
+                &operations,
+            );
+        }
+    }
+
+    #[test]
+    fn directed_storages_agree() {
+        assert_same_storages::<DirectedEdge, _, _>(DiList::<usize>::init, DiMap::<usize>::init);
+        assert_same_storages::<DirectedEdge, _, _>(DiList::<usize>::init, DiMat::<usize>::init);
+    }
+
+    #[test]
+    fn undirected_storages_agree() {
+        assert_same_storages::<UndirectedEdge, _, _>(List::<usize>::init, Map::<usize>::init);
+        assert_same_storages::<UndirectedEdge, _, _>(List::<usize>::init, Mat::<usize>::init);
+    }
+
+    #[test]
+    #[should_panic(expected = "Outcome of adding the edge differs")]
+    fn simple_and_multi_graphs_disagree() {
+        // Given: A graph that rejects loops and one that accepts them.
+        let mut simple = SimpleGraph::init(List::<usize>::init());
+        let mut multi = MultiGraph::init(List::<usize>::init());
+
+        // When: Adding a loop.
+        let operations = [Operation::AddVertex, Operation::AddEdge(0, 0, 1)];
+
+        // Then:
+        assert_same_observations(&mut simple, &mut multi, &operations);
+    }
+
+    #[test]
+    fn bytes_decode_to_operations() {
+        // Given: Bytes of an add vertex, an add edge and a truncated remove edge.
+        let data = [0, 7, 1, 2, 3, 8];
+
+        // When:
+        let operations = operations_from_bytes(&data);
+
+        // Then:
+        assert_eq!(
+            operations,
+            vec![Operation::AddVertex, Operation::AddEdge(1, 2, 3)]
+        );
+    }
+}
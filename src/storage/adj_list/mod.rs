@@ -1,8 +1,13 @@
-use std::collections::HashSet;
+use anyhow::Result;
+use num_traits::One;
+use std::any::Any;
+use std::collections::{HashMap, HashSet};
 use std::marker::PhantomData;
+use std::str::FromStr;
 
 use crate::graph::{DefaultEdge, DirectedEdge, Edge, EdgeDir, FlowEdge, UndirectedEdge};
-use crate::storage::GraphStorage;
+use crate::io::edge_list;
+use crate::storage::{from_index, to_index, to_index_unchecked, GraphStorage, Index};
 
 /// An adjacency list that uses [`undirected`](crate::graph::UndirectedEdge) [`default edges`](crate::graph::DefaultEdge).
 pub type List<W, Dir = UndirectedEdge> = AdjList<W, DefaultEdge<W>, Dir>;
@@ -38,7 +43,7 @@ pub type DiFlowList<W> = AdjList<W, FlowEdge<W>, DirectedEdge>;
 /// * `E`: **E**dge type that graph uses.
 /// * `Dir`: **Dir**ection of edges: [`Directed`](crate::graph::DirectedEdge) or [`Undirected`](crate::graph::UndirectedEdge).
 pub struct AdjList<W, E: Edge<W>, Dir: EdgeDir = UndirectedEdge> {
-    edges_of: Vec<Vec<(usize, E)>>,
+    edges_of: Vec<Vec<(Index, E)>>,
     reusable_vertex_ids: HashSet<usize>,
 
     max_edge_id: usize,
@@ -92,6 +97,36 @@ impl<W, E: Edge<W>, Dir: EdgeDir> AdjList<W, E, Dir> {
         }
     }
 
+    // Removes vertices with ids in `removed_ids` and all of their edges in a single pass over the edges.
+    // Ids of the removed edges are freed so they can be reused.
+    //
+    // # Complexity
+    // O(|V| + |E|)
+    fn remove_vertices(&mut self, removed_ids: &HashSet<usize>) {
+        for vertex_id in removed_ids {
+            let edge_ids = self.edges_of[*vertex_id]
+                .drain(..)
+                .map(|(_, edge)| edge.get_id());
+
+            self.reusable_edge_ids.extend(edge_ids);
+        }
+
+        let reusable_edge_ids = &mut self.reusable_edge_ids;
+        for edges in self.edges_of.iter_mut() {
+            edges.retain(|(dst_id, edge)| {
+                if removed_ids.contains(&from_index(*dst_id)) {
+                    reusable_edge_ids.insert(edge.get_id());
+                    false
+                } else {
+                    true
+                }
+            });
+        }
+
+        self.vertex_count -= removed_ids.len();
+        self.reusable_vertex_ids.extend(removed_ids);
+    }
+
     // # Returns
     // * Some: Containing an id that can be reused.
     // * None: If there is no id to reuse and storage must allocate memory for a new id.
@@ -134,7 +169,52 @@ impl<W, E: Edge<W>, Dir: EdgeDir> AdjList<W, E, Dir> {
     }
 }
 
+impl<W: Copy, E: Edge<W> + Copy, Dir: EdgeDir> AdjList<W, E, Dir>
+where
+    W: FromStr + Any + One,
+{
+    /// Initializes an adjacency list from an edge list.
+    ///
+    /// For more info about the format checkout [`edge_list`](crate::io::edge_list) module.
+    ///
+    /// # Arguments
+    /// `text`: Edge list.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing the storage and a map from each label in the text to the id of its vertex in the storage.
+    /// * `Err`: If any line of the text is malformed.
+    ///
+    /// # Examples
+    /// ```
+    /// use prepona::prelude::*;
+    /// use prepona::storage::DiList;
+    ///
+    /// let (storage, labels) = DiList::<usize>::from_edge_list("1 2\n2 3 4\n").unwrap();
+    ///
+    /// assert_eq!(storage.vertex_count(), 3);
+    /// assert!(storage.has_any_edge_unchecked(labels["2"], labels["3"]));
+    /// ```
+    pub fn from_edge_list(text: &str) -> Result<(Self, HashMap<String, usize>)> {
+        let mut storage = AdjList::init();
+
+        let labels = edge_list::read(&mut storage, text)?;
+
+        Ok((storage, labels))
+    }
+}
+
 impl<W: Copy, E: Edge<W> + Copy, Dir: EdgeDir> GraphStorage<W, E, Dir> for AdjList<W, E, Dir> {
+    type EdgesFromIter<'a>
+        = std::iter::Map<std::slice::Iter<'a, (Index, E)>, fn(&'a (Index, E)) -> (usize, &'a E)>
+    where
+        Self: 'a,
+        E: 'a;
+
+    type NeighborsIter<'a>
+        = std::iter::Map<std::slice::Iter<'a, (Index, E)>, fn(&'a (Index, E)) -> usize>
+    where
+        Self: 'a;
+
     /// Adds a vertex to the graph.
     ///
     /// # Returns
@@ -142,15 +222,36 @@ impl<W: Copy, E: Edge<W> + Copy, Dir: EdgeDir> GraphStorage<W, E, Dir> for AdjLi
     ///
     /// # Complexity
     /// O(|1|)
+    ///
+    /// # Panics
+    /// If id of the new vertex does not fit in [`Index`](crate::storage::Index).
     fn add_vertex(&mut self) -> usize {
-        self.vertex_count += 1;
+        self.try_add_vertex().unwrap()
+    }
 
+    /// Adds a vertex to the graph without panicking.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing unique id of the newly added vertex.
+    /// * `Err`: [`IndexOverflow`](crate::storage::ErrorKind::IndexOverflow) if id of the new vertex does not fit in [`Index`](crate::storage::Index).
+    ///
+    /// # Complexity
+    /// O(|1|)
+    fn try_add_vertex(&mut self) -> Result<usize> {
         if let Some(reusable_id) = self.next_reusable_vertex_id() {
-            reusable_id
+            self.vertex_count += 1;
+
+            Ok(reusable_id)
         } else {
+            let vertex_id = self.edges_of.len();
+
+            // Make sure the new id can be stored as an index before allocating it.
+            to_index(vertex_id)?;
+
+            self.vertex_count += 1;
             self.edges_of.push(vec![]);
 
-            self.edges_of.len() - 1
+            Ok(vertex_id)
         }
     }
 
@@ -165,15 +266,27 @@ impl<W: Copy, E: Edge<W> + Copy, Dir: EdgeDir> GraphStorage<W, E, Dir> for AdjLi
     /// # Panics
     /// If `vertex_id` is not in range 0..|V|.
     fn remove_vertex_unchecked(&mut self, vertex_id: usize) {
-        self.edges_of[vertex_id].clear();
-
-        for src_id in self.vertices() {
-            self.edges_of[src_id].retain(|(dst_id, _)| *dst_id != vertex_id)
-        }
+        self.remove_vertices(&std::iter::once(vertex_id).collect());
+    }
 
-        self.vertex_count -= 1;
+    /// Removes every vertex for which `keep` returns `false`, along with its edges.
+    ///
+    /// # Arguments
+    /// `keep`: Receives id of each vertex and returns `true` if the vertex must be kept.
+    ///
+    /// # Complexity
+    /// O(|V| + |E|)
+    fn retain_vertices<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(usize) -> bool,
+    {
+        let removed_ids: HashSet<usize> = self
+            .vertices()
+            .into_iter()
+            .filter(|vertex_id| !keep(*vertex_id))
+            .collect();
 
-        self.reusable_vertex_ids.insert(vertex_id);
+        self.remove_vertices(&removed_ids);
     }
 
     /// Adds `edge` from vertex with id `src_id`: to vertex with id: `dst_id`.
@@ -202,10 +315,10 @@ impl<W: Copy, E: Edge<W> + Copy, Dir: EdgeDir> GraphStorage<W, E, Dir> for AdjLi
 
         edge.set_id(edge_id);
 
-        self.edges_of[src_id].push((dst_id, edge));
+        self.edges_of[src_id].push((to_index_unchecked(dst_id), edge));
 
         if self.is_undirected() {
-            self.edges_of[dst_id].push((src_id, edge));
+            self.edges_of[dst_id].push((to_index_unchecked(src_id), edge));
         }
 
         edge_id
@@ -220,17 +333,28 @@ impl<W: Copy, E: Edge<W> + Copy, Dir: EdgeDir> GraphStorage<W, E, Dir> for AdjLi
     /// * `edge`: New edge to replace the old one.
     ///
     /// # Complexity
-    /// O(|E|)
+    /// * Directed: O(E<sup>\*</sup><sub>src</sub>)
+    /// * Undirected: O(E<sup>\*</sup><sub>src</sub> + E<sup>\*</sup><sub>dst</sub>)
     ///
     /// # Panics
     /// * If `src_id` or `dst_id` is not in range 0..|V|.
     /// * If there is no edge with id: `edge_id` from `src_id` to `dst_id`.
     fn update_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge_id: usize, mut edge: E) {
-        let removed_edge = self.remove_edge_unchecked(src_id, dst_id, edge_id);
+        edge.set_id(edge_id);
 
-        edge.set_id(removed_edge.get_id());
+        // Edge is replaced in place. Removing and adding it again could give it another one of the reusable ids.
+        let index = self.edges_of[src_id]
+            .iter()
+            .position(|(_, edge)| edge.get_id() == edge_id)
+            .unwrap();
+        self.edges_of[src_id][index].1 = edge;
 
-        self.add_edge_unchecked(src_id, dst_id, edge);
+        if self.is_undirected() {
+            self.edges_of[dst_id]
+                .iter_mut()
+                .filter(|(_, stored_edge)| stored_edge.get_id() == edge_id)
+                .for_each(|(_, stored_edge)| *stored_edge = edge);
+        }
     }
 
     /// Removes the edge with id: `edge_id`.
@@ -259,11 +383,43 @@ impl<W: Copy, E: Edge<W> + Copy, Dir: EdgeDir> GraphStorage<W, E, Dir> for AdjLi
 
         self.reusable_edge_ids.insert(edge_id);
 
+        let edge = self.edges_of[src_id].remove(index).1;
+
+        // Removing from `src_id` first keeps `index` valid when the edge is a loop.
         if self.is_undirected() {
             self.edges_of[dst_id].retain(|(_, edge)| edge.get_id() != edge_id);
         }
 
-        self.edges_of[src_id].remove(index).1
+        edge
+    }
+
+    /// Removes every edge for which `keep` returns `false`.
+    ///
+    /// `keep` is called once for each edge. For undirected edges, end points are passed in the same order as [`edges`](crate::storage::GraphStorage::edges) returns them.
+    ///
+    /// # Arguments
+    /// `keep`: Receives (`src_id`, `dst_id`, `edge`) of each edge and returns `true` if the edge must be kept.
+    ///
+    /// # Complexity
+    /// O(|V| + |E|)
+    fn retain_edges<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(usize, usize, &E) -> bool,
+    {
+        let mut visited_ids = HashSet::new();
+        let mut removed_ids = HashSet::new();
+        for (src_id, dst_id, edge) in self.edges() {
+            if visited_ids.insert(edge.get_id()) && !keep(src_id, dst_id, edge) {
+                removed_ids.insert(edge.get_id());
+            }
+        }
+
+        // Each list is filtered in place, so both copies of an undirected edge are removed in a single pass.
+        for edges in self.edges_of.iter_mut() {
+            edges.retain(|(_, edge)| !removed_ids.contains(&edge.get_id()));
+        }
+
+        self.reusable_edge_ids.extend(removed_ids);
     }
 
     /// # Returns
@@ -307,10 +463,41 @@ impl<W: Copy, E: Edge<W> + Copy, Dir: EdgeDir> GraphStorage<W, E, Dir> for AdjLi
     /// # Panics
     /// If `src_id` is not in range 0..|V|.
     fn edges_from_unchecked(&self, src_id: usize) -> Vec<(usize, &E)> {
-        self.edges_of[src_id]
-            .iter()
-            .map(|(dst_id, edge)| (*dst_id, edge))
-            .collect()
+        self.edges_from_iter(src_id).collect()
+    }
+
+    /// # Arguments
+    /// `src_id`: Id of the source vertex.
+    ///
+    /// # Returns
+    /// Iterator over edges from the source vertex in the format of: (`dst_id`, `edge`).
+    ///
+    /// # Complexity
+    /// O(1) to create and O(|E<sup>out</sup>|) to consume.
+    ///
+    /// # Panics
+    /// If `src_id` is not in range 0..|V|.
+    fn edges_from_iter(&self, src_id: usize) -> Self::EdgesFromIter<'_> {
+        let edge_of: fn(&(Index, E)) -> (usize, &E) = |(dst_id, edge)| (from_index(*dst_id), edge);
+
+        self.edges_of[src_id].iter().map(edge_of)
+    }
+
+    /// # Arguments
+    /// `src_id`: Id of the source vertex.
+    ///
+    /// # Returns
+    /// Iterator over id of vertices accessible from source vertex using one edge. A vertex appears once per edge that leads to it.
+    ///
+    /// # Complexity
+    /// O(1) to create and O(|E<sup>out</sup>|) to consume.
+    ///
+    /// # Panics
+    /// If `src_id` is not in range 0..|V|.
+    fn neighbors_iter(&self, src_id: usize) -> Self::NeighborsIter<'_> {
+        let dst_of: fn(&(Index, E)) -> usize = |(dst_id, _)| from_index(*dst_id);
+
+        self.edges_of[src_id].iter().map(dst_of)
     }
 
     /// # Arguments
@@ -768,6 +955,46 @@ mod tests {
         }
     }
 
+    #[test]
+    fn update_edge_keeps_id() {
+        // Given: Undirected list with a loop and a removed edge, so there is an id to be reused.
+        //
+        //      a  ---  b  ---  c ---.
+        //                      |    |
+        //                      '----'
+        //
+        let mut list = List::<usize>::init();
+        let a = list.add_vertex();
+        let b = list.add_vertex();
+        let c = list.add_vertex();
+
+        let ab = list.add_edge_unchecked(a, b, 1.into());
+        let bc = list.add_edge_unchecked(b, c, 2.into());
+        let cc = list.add_edge_unchecked(c, c, 3.into());
+        list.remove_edge_unchecked(a, b, ab);
+
+        // When: Updating the edges.
+        list.update_edge_unchecked(c, b, bc, 4.into());
+        list.update_edge_unchecked(c, c, cc, 5.into());
+
+        // Then:
+        assert_eq!(list.edge_unchecked(bc).get_weight().unwrap(), 4);
+        assert_eq!(list.edge_unchecked(cc).get_weight().unwrap(), 5);
+        for (src_id, dst_id) in [(b, c), (c, b)] {
+            assert_eq!(list.edges_between_unchecked(src_id, dst_id).len(), 1);
+            assert_eq!(
+                list.edge_between_unchecked(src_id, dst_id, bc)
+                    .get_weight()
+                    .unwrap(),
+                4
+            );
+        }
+        assert!(list
+            .edges_between_unchecked(c, c)
+            .iter()
+            .all(|edge| edge.get_id() == cc && edge.get_weight().unwrap() == 5));
+    }
+
     #[test]
     fn directed_remove_edge() {
         // Given: Directed list
@@ -965,4 +1192,137 @@ mod tests {
         // Then: it must have zero edges again.
         assert_eq!(di_mat.edge_count(), 0);
     }
+
+    #[test]
+    fn retain() {
+        // Given: Storage
+        //
+        //      a  --1--  b  --2--  c
+        //      |         ↺5        |
+        //      '---4---  d  --3----'
+        //
+        let mut storage = List::<usize>::init();
+        let a = storage.add_vertex();
+        let b = storage.add_vertex();
+        let c = storage.add_vertex();
+        let d = storage.add_vertex();
+        let ab = storage.add_edge_unchecked(a, b, 1.into());
+        storage.add_edge_unchecked(b, c, 2.into());
+        storage.add_edge_unchecked(c, d, 3.into());
+        let da = storage.add_edge_unchecked(d, a, 4.into());
+        let bb = storage.add_edge_unchecked(b, b, 5.into());
+
+        // When: Keeping edges with even weights.
+        let mut calls = 0;
+        storage.retain_edges(|_, _, edge| {
+            calls += 1;
+            edge.get_weight().unwrap() % 2 == 0
+        });
+
+        // Then:
+        assert_eq!(calls, 5);
+        assert_eq!(storage.edge_count(), 2);
+        assert!(!storage.contains_edge(ab));
+        assert!(!storage.contains_edge(bb));
+        assert!(!storage.has_any_edge_unchecked(b, b));
+        assert!(storage.has_any_edge_unchecked(a, d));
+
+        // When: Removing b.
+        storage.retain_vertices(|vertex_id| vertex_id != b);
+
+        // Then: Its edge is removed and its id is freed.
+        assert_eq!(storage.vertex_count(), 3);
+        assert!(!storage.contains_vertex(b));
+        assert_eq!(storage.edge_count(), 1);
+        assert!(storage.contains_edge(da));
+        assert!(storage.neighbors_unchecked(c).is_empty());
+    }
+
+    #[test]
+    fn removing_vertex_frees_edge_ids() {
+        // Given: Directed storage with edges into and out of b.
+        let mut storage = DiList::<usize>::init();
+        let a = storage.add_vertex();
+        let b = storage.add_vertex();
+        let c = storage.add_vertex();
+        storage.add_edge_unchecked(a, b, 1.into());
+        storage.add_edge_unchecked(b, c, 1.into());
+        let ca = storage.add_edge_unchecked(c, a, 1.into());
+
+        // When:
+        storage.remove_vertex_unchecked(b);
+
+        // Then:
+        assert_eq!(storage.edge_count(), 1);
+        assert!(storage.contains_edge(ca));
+        assert_eq!(storage.edges().len(), 1);
+    }
+
+    #[test]
+    fn removing_loop_keeps_other_edges() {
+        // Given: Undirected storage with an edge followed by a loop in the list of b.
+        let mut storage = List::<usize>::init();
+        let a = storage.add_vertex();
+        let b = storage.add_vertex();
+        let ab = storage.add_edge_unchecked(a, b, 1.into());
+        let bb = storage.add_edge_unchecked(b, b, 1.into());
+
+        // When:
+        storage.remove_edge_unchecked(b, b, bb);
+
+        // Then:
+        assert_eq!(storage.edge_count(), 1);
+        assert_eq!(storage.edges_from_unchecked(b).len(), 1);
+        assert_eq!(storage.edges_from_unchecked(b)[0].1.get_id(), ab);
+    }
+
+    #[test]
+    fn iterators_match_vectors() {
+        // Given: Directed and undirected storages with parallel edges, a loop and a removed vertex.
+        let mut di_storage = DiList::<usize>::init();
+        let mut storage = List::<usize>::init();
+        for _ in 0..4 {
+            di_storage.add_vertex();
+            storage.add_vertex();
+        }
+        for (src_id, dst_id) in [(0, 1), (0, 1), (1, 2), (2, 2), (3, 0), (2, 0)] {
+            di_storage.add_edge_unchecked(src_id, dst_id, 1.into());
+            storage.add_edge_unchecked(src_id, dst_id, 1.into());
+        }
+        di_storage.remove_vertex_unchecked(3);
+        storage.remove_vertex_unchecked(3);
+
+        for vertex_id in 0..3 {
+            // When:
+            let di_edges: Vec<(usize, usize)> = di_storage
+                .edges_from_iter(vertex_id)
+                .map(|(dst_id, edge)| (dst_id, edge.get_id()))
+                .collect();
+            let edges: Vec<(usize, usize)> = storage
+                .edges_from_iter(vertex_id)
+                .map(|(dst_id, edge)| (dst_id, edge.get_id()))
+                .collect();
+
+            // Then:
+            let expected = |edges: Vec<(usize, &DefaultEdge<usize>)>| {
+                edges
+                    .into_iter()
+                    .map(|(dst_id, edge)| (dst_id, edge.get_id()))
+                    .collect::<Vec<(usize, usize)>>()
+            };
+            assert_eq!(
+                di_edges,
+                expected(di_storage.edges_from_unchecked(vertex_id))
+            );
+            assert_eq!(edges, expected(storage.edges_from_unchecked(vertex_id)));
+            assert_eq!(
+                di_storage.neighbors_iter(vertex_id).collect::<Vec<usize>>(),
+                di_storage.neighbors_unchecked(vertex_id)
+            );
+            assert_eq!(
+                storage.neighbors_iter(vertex_id).collect::<Vec<usize>>(),
+                storage.neighbors_unchecked(vertex_id)
+            );
+        }
+    }
 }
@@ -1,15 +1,20 @@
+use anyhow::Result;
+use num_traits::One;
 use std::{
-    collections::{HashMap, HashSet},
+    any::Any,
+    collections::{BTreeMap, HashMap, HashSet},
     marker::PhantomData,
     ops::{Index, IndexMut},
+    str::FromStr,
 };
 
 use crate::{
     graph::{Edge, EdgeDir, FlowEdge},
+    io::edge_list,
     prelude::{DefaultEdge, DirectedEdge, UndirectedEdge},
 };
 
-use super::GraphStorage;
+use super::{from_index, to_index, to_index_unchecked, GraphStorage, Index as VertexIndex};
 
 /// An adjacency map that uses [`undirected`](crate::graph::UndirectedEdge) [`default edges`](crate::graph::DefaultEdge).
 pub type Map<W, Dir = UndirectedEdge> = AdjMap<W, DefaultEdge<W>, Dir>;
@@ -36,12 +41,22 @@ pub type DiFlowMap<W> = AdjMap<W, FlowEdge<W>, DirectedEdge>;
 /// * **Directed**: For directed graphs `AdjMap` stores |V| + |E| elements.
 /// * **Undirected**: For undirected graphs `AdjMap` stores each edge twice so it stores |V| + 2*|E| elements.
 ///
+/// Each vertex keeps its own hash map keyed by destination, with one `Vec` of parallel edges per neighbor.
+/// This costs a hash table entry (key, `Vec` header and spare buckets) per distinct neighbor on top of the edges themselves,
+/// which is several times the footprint of a flat edge list like [`AdjList`](crate::storage::AdjList) for sparse graphs.
+/// In return `has_any_edge`, `edges_between`, `edge_between` and `remove_edge` find the edges between two vertices
+/// without scanning the rest of the neighbors: O(1) and O(|E<sub>src->dst</sub>|) respectively.
+///
+/// Degrees of vertices are counted as edges are added and removed, which takes two more counters per vertex
+/// and a histogram of degrees. So [`degree`](crate::storage::AdjMap::degree) and its in/out variants are O(1),
+/// and [`max_degree`](crate::storage::AdjMap::max_degree) and [`min_degree`](crate::storage::AdjMap::min_degree) are O(log(|V|)).
+///
 /// ## Generic Parameters
 /// * `W`: **W**eight type associated with edges.
 /// * `E`: **E**dge type that graph uses.
 /// * `Dir`: **Dir**ection of edges: [`Directed`](crate::graph::DirectedEdge) or [`Undirected`](crate::graph::UndirectedEdge).
 pub struct AdjMap<W: Copy, E: Edge<W> + Copy, Dir: EdgeDir = UndirectedEdge> {
-    map: HashMap<usize, HashMap<usize, Vec<E>>>,
+    map: HashMap<VertexIndex, HashMap<VertexIndex, Vec<E>>>,
 
     reusable_vertex_ids: HashSet<usize>,
     reusable_edge_ids: HashSet<usize>,
@@ -49,6 +64,16 @@ pub struct AdjMap<W: Copy, E: Edge<W> + Copy, Dir: EdgeDir = UndirectedEdge> {
     vertex_count: usize,
     max_edge_id: usize,
 
+    // Capacity of the neighbor map of each new vertex.
+    degree_hint: usize,
+
+    // Number of edges stored in the neighbor map of each vertex. Undirected loops are stored twice.
+    out_degrees: HashMap<VertexIndex, usize>,
+    // Number of edges coming into each vertex. Only kept for directed graphs.
+    in_degrees: HashMap<VertexIndex, usize>,
+    // Number of vertices with each out degree.
+    degree_counts: BTreeMap<usize, usize>,
+
     phantom_w: PhantomData<W>,
     phantom_e: PhantomData<E>,
     phantom_dir: PhantomData<Dir>,
@@ -91,12 +116,297 @@ impl<W: Copy, E: Edge<W> + Copy, Dir: EdgeDir> AdjMap<W, E, Dir> {
             vertex_count: 0,
             max_edge_id: 0,
 
+            degree_hint: 0,
+
+            out_degrees: HashMap::new(),
+            in_degrees: HashMap::new(),
+            degree_counts: BTreeMap::new(),
+
             phantom_w: PhantomData,
             phantom_e: PhantomData,
             phantom_dir: PhantomData,
         }
     }
 
+    /// Initializes an empty adjacency map with enough capacity for the specified number of vertices and edges.
+    ///
+    /// Neighbors of each vertex are stored in a separate map, so the edge capacity is spread evenly between vertices
+    /// and each added vertex gets a neighbor map with room for the average degree.
+    /// This avoids repeated reallocation when loading a graph with a known size.
+    ///
+    /// # Arguments
+    /// * `vertex_capacity`: Number of vertices the storage must hold without reallocating.
+    /// * `edge_capacity`: Expected number of edges.
+    ///
+    /// # Returns
+    /// An empty `AdjMap`.
+    ///
+    /// # Examples
+    /// ```
+    /// use prepona::prelude::*;
+    /// use prepona::storage::DiMap;
+    ///
+    /// let mut map = DiMap::<usize>::with_capacity(100, 1000);
+    /// assert!(map.vertex_capacity() >= 100);
+    ///
+    /// // Each added vertex gets room for the average degree.
+    /// for _ in 0..100 {
+    ///     map.add_vertex();
+    /// }
+    /// assert!(map.edge_capacity() >= 1000);
+    /// ```
+    pub fn with_capacity(vertex_capacity: usize, edge_capacity: usize) -> Self {
+        let mut map = AdjMap::init();
+
+        map.map.reserve(vertex_capacity);
+        map.degree_hint = Self::spread(edge_capacity, vertex_capacity);
+
+        map
+    }
+
+    /// Reserves capacity for at least `additional` more vertices.
+    ///
+    /// # Arguments
+    /// `additional`: Number of vertices to reserve capacity for.
+    pub fn reserve_vertices(&mut self, additional: usize) {
+        self.map.reserve(additional);
+        self.out_degrees.reserve(additional);
+
+        if Dir::is_directed() {
+            self.in_degrees.reserve(additional);
+        }
+    }
+
+    /// Reserves capacity for at least `additional` more edges.
+    ///
+    /// Capacity is spread evenly between neighbor maps of the current vertices.
+    ///
+    /// # Arguments
+    /// `additional`: Number of edges to reserve capacity for.
+    ///
+    /// # Complexity
+    /// O(|V|)
+    pub fn reserve_edges(&mut self, additional: usize) {
+        let per_vertex = Self::spread(additional, self.vertex_count);
+
+        for neighbors in self.map.values_mut() {
+            neighbors.reserve(per_vertex);
+        }
+    }
+
+    /// Shrinks the capacity of the storage as much as possible.
+    ///
+    /// Capacity reserved for new vertices by [`with_capacity`](crate::storage::AdjMap::with_capacity) is discarded as well.
+    ///
+    /// # Complexity
+    /// O(|V| + |E|)
+    pub fn shrink_to_fit(&mut self) {
+        self.map.shrink_to_fit();
+
+        for neighbors in self.map.values_mut() {
+            neighbors.shrink_to_fit();
+            neighbors
+                .values_mut()
+                .for_each(|edges| edges.shrink_to_fit());
+        }
+
+        self.reusable_vertex_ids.shrink_to_fit();
+        self.reusable_edge_ids.shrink_to_fit();
+        self.out_degrees.shrink_to_fit();
+        self.in_degrees.shrink_to_fit();
+        self.degree_hint = 0;
+    }
+
+    /// # Returns
+    /// Number of vertices the storage can hold without reallocating.
+    pub fn vertex_capacity(&self) -> usize {
+        self.map.capacity()
+    }
+
+    /// # Returns
+    /// Number of edges the storage can hold without reallocating any of the neighbor maps, assuming edges are spread evenly between them.
+    ///
+    /// # Complexity
+    /// O(|V|)
+    pub fn edge_capacity(&self) -> usize {
+        let neighbor_capacity: usize = self
+            .map
+            .values()
+            .map(|neighbors| neighbors.capacity())
+            .sum();
+
+        // Each undirected edge is stored in neighbor maps of both of its end points.
+        if Dir::is_undirected() {
+            neighbor_capacity / 2
+        } else {
+            neighbor_capacity
+        }
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// Number of edges going out of the vertex.
+    /// For undirected graphs it's the same as [`degree`](crate::storage::AdjMap::degree).
+    ///
+    /// # Complexity
+    /// O(1)
+    ///
+    /// # Panics
+    /// If there is no vertex with id: `vertex_id`.
+    pub fn out_degree(&self, vertex_id: usize) -> usize {
+        self.out_degrees[&to_index_unchecked(vertex_id)]
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// Number of edges coming into the vertex.
+    /// For undirected graphs it's the same as [`degree`](crate::storage::AdjMap::degree).
+    ///
+    /// # Complexity
+    /// O(1)
+    ///
+    /// # Panics
+    /// If there is no vertex with id: `vertex_id`.
+    pub fn in_degree(&self, vertex_id: usize) -> usize {
+        if Dir::is_directed() {
+            self.in_degrees[&to_index_unchecked(vertex_id)]
+        } else {
+            self.out_degree(vertex_id)
+        }
+    }
+
+    /// Counts self loops the same way as [`Degrees`](crate::provide::Degrees) does.
+    ///
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// Number of edges incident to the vertex, counting undirected self loops twice.
+    /// For directed graphs it's the same as [`out_degree`](crate::storage::AdjMap::out_degree).
+    ///
+    /// # Complexity
+    /// O(1)
+    ///
+    /// # Panics
+    /// If there is no vertex with id: `vertex_id`.
+    pub fn degree(&self, vertex_id: usize) -> usize {
+        self.out_degree(vertex_id)
+    }
+
+    /// # Returns
+    /// * `Some`: Containing the largest [`degree`](crate::storage::AdjMap::degree) of vertices.
+    /// * `None`: If the storage is empty.
+    ///
+    /// # Complexity
+    /// O(log(|V|))
+    ///
+    /// # Examples
+    /// ```
+    /// use prepona::prelude::*;
+    /// use prepona::storage::Map;
+    ///
+    /// // Given: Star with center a.
+    /// let mut map = Map::<usize>::init();
+    /// let a = map.add_vertex();
+    /// let b = map.add_vertex();
+    /// let c = map.add_vertex();
+    /// map.add_edge_unchecked(a, b, 1.into());
+    /// map.add_edge_unchecked(a, c, 1.into());
+    ///
+    /// // When:
+    /// let max_degree = map.max_degree();
+    /// let min_degree = map.min_degree();
+    ///
+    /// // Then:
+    /// assert_eq!(max_degree, Some(2));
+    /// assert_eq!(min_degree, Some(1));
+    /// ```
+    pub fn max_degree(&self) -> Option<usize> {
+        self.degree_counts.keys().next_back().copied()
+    }
+
+    /// # Returns
+    /// * `Some`: Containing the smallest [`degree`](crate::storage::AdjMap::degree) of vertices.
+    /// * `None`: If the storage is empty.
+    ///
+    /// # Complexity
+    /// O(log(|V|))
+    pub fn min_degree(&self) -> Option<usize> {
+        self.degree_counts.keys().next().copied()
+    }
+
+    // Starts counting degrees of a newly added vertex.
+    fn track_degrees(&mut self, vertex_index: VertexIndex) {
+        self.out_degrees.insert(vertex_index, 0);
+
+        if Dir::is_directed() {
+            self.in_degrees.insert(vertex_index, 0);
+        }
+
+        *self.degree_counts.entry(0).or_insert(0) += 1;
+    }
+
+    // Stops counting degrees of a removed vertex.
+    fn untrack_degrees(&mut self, vertex_index: VertexIndex) {
+        let degree = self.out_degrees.remove(&vertex_index).unwrap();
+
+        self.in_degrees.remove(&vertex_index);
+
+        self.uncount_degree(degree);
+    }
+
+    // Updates degrees after `count` edges from source to destination are stored.
+    fn increase_degrees(&mut self, src_index: VertexIndex, dst_index: VertexIndex, count: usize) {
+        let degree = self.out_degrees[&src_index];
+        self.set_out_degree(src_index, degree + count);
+
+        if Dir::is_directed() {
+            *self.in_degrees.get_mut(&dst_index).unwrap() += count;
+        }
+    }
+
+    // Updates degrees after `count` edges from source to destination are removed.
+    fn decrease_degrees(&mut self, src_index: VertexIndex, dst_index: VertexIndex, count: usize) {
+        let degree = self.out_degrees[&src_index];
+        self.set_out_degree(src_index, degree - count);
+
+        if Dir::is_directed() {
+            *self.in_degrees.get_mut(&dst_index).unwrap() -= count;
+        }
+    }
+
+    fn set_out_degree(&mut self, vertex_index: VertexIndex, degree: usize) {
+        let old_degree = self.out_degrees.insert(vertex_index, degree).unwrap();
+
+        self.uncount_degree(old_degree);
+        *self.degree_counts.entry(degree).or_insert(0) += 1;
+    }
+
+    fn uncount_degree(&mut self, degree: usize) {
+        let count = self.degree_counts.get_mut(&degree).unwrap();
+        *count -= 1;
+
+        if *count == 0 {
+            self.degree_counts.remove(&degree);
+        }
+    }
+
+    // # Returns
+    // Number of neighbor entries each of `vertex_count` vertices needs to hold `edge_count` edges.
+    fn spread(edge_count: usize, vertex_count: usize) -> usize {
+        let entry_count = if Dir::is_undirected() {
+            2 * edge_count
+        } else {
+            edge_count
+        };
+
+        entry_count.div_ceil(vertex_count.max(1))
+    }
+
     // # Returns
     // * Some: Containing an id that can be reused.
     // * None: If there is no id to reuse and storage must allocate memory for a new id.
@@ -130,7 +440,79 @@ impl<W: Copy, E: Edge<W> + Copy, Dir: EdgeDir> AdjMap<W, E, Dir> {
     }
 }
 
+impl<W: Copy, E: Edge<W> + Copy, Dir: EdgeDir> AdjMap<W, E, Dir>
+where
+    W: FromStr + Any + One,
+{
+    /// Initializes an adjacency map from an edge list.
+    ///
+    /// For more info about the format checkout [`edge_list`](crate::io::edge_list) module.
+    ///
+    /// # Arguments
+    /// `text`: Edge list.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing the storage and a map from each label in the text to the id of its vertex in the storage.
+    /// * `Err`: If any line of the text is malformed.
+    ///
+    /// # Examples
+    /// ```
+    /// use prepona::prelude::*;
+    /// use prepona::storage::DiMap;
+    ///
+    /// let (storage, labels) = DiMap::<usize>::from_edge_list("1 2\n2 3 4\n").unwrap();
+    ///
+    /// assert_eq!(storage.vertex_count(), 3);
+    /// assert!(storage.has_any_edge_unchecked(labels["2"], labels["3"]));
+    /// ```
+    pub fn from_edge_list(text: &str) -> Result<(Self, HashMap<String, usize>)> {
+        let mut storage = AdjMap::init();
+
+        let labels = edge_list::read(&mut storage, text)?;
+
+        Ok((storage, labels))
+    }
+}
+
+/// Iterator over outgoing edges of a vertex in an [`AdjMap`](crate::storage::AdjMap),
+/// returned by [`edges_from_iter`](crate::storage::GraphStorage::edges_from_iter).
+///
+/// ## Generic Parameters
+/// * `E`: **E**dge type that storage uses.
+pub struct MapEdgesFrom<'a, E> {
+    edges_to: std::collections::hash_map::Iter<'a, VertexIndex, Vec<E>>,
+    dst_id: usize,
+    edges: std::slice::Iter<'a, E>,
+}
+
+impl<'a, E> Iterator for MapEdgesFrom<'a, E> {
+    type Item = (usize, &'a E);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(edge) = self.edges.next() {
+                return Some((self.dst_id, edge));
+            }
+
+            let (dst_id, edges) = self.edges_to.next()?;
+            self.dst_id = from_index(*dst_id);
+            self.edges = edges.iter();
+        }
+    }
+}
+
 impl<W: Copy, E: Edge<W> + Copy, Dir: EdgeDir> GraphStorage<W, E, Dir> for AdjMap<W, E, Dir> {
+    type EdgesFromIter<'a>
+        = MapEdgesFrom<'a, E>
+    where
+        Self: 'a,
+        E: 'a;
+
+    type NeighborsIter<'a>
+        = std::iter::Map<MapEdgesFrom<'a, E>, fn((usize, &'a E)) -> usize>
+    where
+        Self: 'a;
+
     /// Adds a vertex to the graph.
     ///
     /// # Returns
@@ -138,21 +520,43 @@ impl<W: Copy, E: Edge<W> + Copy, Dir: EdgeDir> GraphStorage<W, E, Dir> for AdjMa
     ///
     /// # Complexity
     /// O(|1|)
+    ///
+    /// # Panics
+    /// If id of the new vertex does not fit in [`Index`](crate::storage::Index).
     fn add_vertex(&mut self) -> usize {
+        self.try_add_vertex().unwrap()
+    }
+
+    /// Adds a vertex to the graph without panicking.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing unique id of the newly added vertex.
+    /// * `Err`: [`IndexOverflow`](crate::storage::ErrorKind::IndexOverflow) if id of the new vertex does not fit in [`Index`](crate::storage::Index).
+    ///
+    /// # Complexity
+    /// O(|1|)
+    fn try_add_vertex(&mut self) -> Result<usize> {
         if let Some(reusable_id) = self.next_reusable_vertex_id() {
             self.vertex_count += 1;
 
-            self.map.insert(reusable_id, HashMap::new());
+            self.map.insert(
+                to_index_unchecked(reusable_id),
+                HashMap::with_capacity(self.degree_hint),
+            );
+            self.track_degrees(to_index_unchecked(reusable_id));
 
-            reusable_id
+            Ok(reusable_id)
         } else {
             let vertex_id = self.vertex_count;
+            let vertex_index = to_index(vertex_id)?;
 
-            self.map.insert(vertex_id, HashMap::new());
+            self.map
+                .insert(vertex_index, HashMap::with_capacity(self.degree_hint));
+            self.track_degrees(vertex_index);
 
             self.vertex_count += 1;
 
-            self.vertex_count - 1
+            Ok(vertex_id)
         }
     }
 
@@ -164,17 +568,99 @@ impl<W: Copy, E: Edge<W> + Copy, Dir: EdgeDir> GraphStorage<W, E, Dir> for AdjMa
     /// # Complexity
     /// O(|V|)
     fn remove_vertex_unchecked(&mut self, vertex_id: usize) {
-        self.map.remove(&vertex_id);
+        let vertex_index = to_index_unchecked(vertex_id);
+
+        let mut removed_entries = vec![];
+
+        // Ids of the removed edges are freed so they can be reused.
+        if let Some(neighbors) = self.map.remove(&vertex_index) {
+            self.reusable_edge_ids
+                .extend(neighbors.values().flatten().map(|edge| edge.get_id()));
+
+            removed_entries.extend(
+                neighbors
+                    .iter()
+                    .map(|(dst_index, edges)| (vertex_index, *dst_index, edges.len())),
+            );
+        }
+
+        for (src_index, neighbors) in self.map.iter_mut() {
+            if let Some(edges) = neighbors.remove(&vertex_index) {
+                self.reusable_edge_ids
+                    .extend(edges.iter().map(|edge| edge.get_id()));
 
-        for v_id in self.vertices() {
-            self[v_id].remove(&vertex_id);
+                removed_entries.push((*src_index, vertex_index, edges.len()));
+            }
+        }
+
+        for (src_index, dst_index, count) in removed_entries {
+            self.decrease_degrees(src_index, dst_index, count);
         }
+        self.untrack_degrees(vertex_index);
 
         self.reusable_vertex_ids.insert(vertex_id);
 
         self.vertex_count -= 1;
     }
 
+    /// Removes every vertex for which `keep` returns `false`, along with its edges.
+    ///
+    /// # Arguments
+    /// `keep`: Receives id of each vertex and returns `true` if the vertex must be kept.
+    ///
+    /// # Complexity
+    /// O(|V| + |E|)
+    fn retain_vertices<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(usize) -> bool,
+    {
+        let removed_indices: HashSet<VertexIndex> = self
+            .map
+            .keys()
+            .copied()
+            .filter(|vertex_index| !keep(from_index(*vertex_index)))
+            .collect();
+
+        let mut removed_entries = vec![];
+
+        let reusable_edge_ids = &mut self.reusable_edge_ids;
+        for vertex_index in &removed_indices {
+            if let Some(neighbors) = self.map.remove(vertex_index) {
+                reusable_edge_ids.extend(neighbors.values().flatten().map(|edge| edge.get_id()));
+
+                removed_entries.extend(
+                    neighbors
+                        .iter()
+                        .map(|(dst_index, edges)| (*vertex_index, *dst_index, edges.len())),
+                );
+            }
+        }
+
+        for (src_index, neighbors) in self.map.iter_mut() {
+            neighbors.retain(|dst_index, edges| {
+                if removed_indices.contains(dst_index) {
+                    reusable_edge_ids.extend(edges.iter().map(|edge| edge.get_id()));
+                    removed_entries.push((*src_index, *dst_index, edges.len()));
+                    false
+                } else {
+                    true
+                }
+            });
+        }
+
+        // Degrees of removed vertices are updated as well, so they must be untracked last.
+        for (src_index, dst_index, count) in removed_entries {
+            self.decrease_degrees(src_index, dst_index, count);
+        }
+        for vertex_index in &removed_indices {
+            self.untrack_degrees(*vertex_index);
+        }
+
+        self.vertex_count -= removed_indices.len();
+        self.reusable_vertex_ids
+            .extend(removed_indices.into_iter().map(from_index));
+    }
+
     /// Adds `edge` from vertex with id `src_id`: to vertex with id: `dst_id`.
     ///
     /// # Arguments
@@ -201,15 +687,75 @@ impl<W: Copy, E: Edge<W> + Copy, Dir: EdgeDir> GraphStorage<W, E, Dir> for AdjMa
 
         edge.set_id(edge_id);
 
-        self[src_id].entry(dst_id).or_insert(vec![]).push(edge);
+        let src_index = to_index_unchecked(src_id);
+        let dst_index = to_index_unchecked(dst_id);
+
+        self[src_id].entry(dst_index).or_insert(vec![]).push(edge);
+        self.increase_degrees(src_index, dst_index, 1);
 
         if Dir::is_undirected() {
-            self[dst_id].entry(src_id).or_insert(vec![]).push(edge);
+            self[dst_id].entry(src_index).or_insert(vec![]).push(edge);
+            self.increase_degrees(dst_index, src_index, 1);
         }
 
         edge_id
     }
 
+    /// Adds `count` vertices to the graph.
+    ///
+    /// # Arguments
+    /// `count`: Number of vertices to add.
+    ///
+    /// # Returns
+    /// Unique ids of the newly added vertices.
+    ///
+    /// # Complexity
+    /// O(`count`) with at most one reallocation.
+    fn extend_with_vertices(&mut self, count: usize) -> Vec<usize> {
+        self.reserve_vertices(count.saturating_sub(self.reusable_vertex_ids.len()));
+
+        (0..count).map(|_| self.add_vertex()).collect()
+    }
+
+    /// Adds edges to the graph in one go.
+    ///
+    /// Neighbor map of each source vertex is grown once to fit all of its new edges, instead of growing repeatedly while edges are added.
+    ///
+    /// # Arguments
+    /// `edges`: Edges to add in the format of (`src_id`, `dst_id`, `edge`).
+    ///
+    /// # Returns
+    /// Unique ids of the newly added edges, in the same order as `edges`.
+    ///
+    /// # Complexity
+    /// O(number of edges)
+    ///
+    /// # Panics
+    /// If any of the end points is not in the graph.
+    fn extend_with_edges_unchecked<I>(&mut self, edges: I) -> Vec<usize>
+    where
+        I: IntoIterator<Item = (usize, usize, E)>,
+    {
+        let edges: Vec<(usize, usize, E)> = edges.into_iter().collect();
+
+        let mut new_entry_count: HashMap<usize, usize> = HashMap::new();
+        for (src_id, dst_id, _) in &edges {
+            *new_entry_count.entry(*src_id).or_insert(0) += 1;
+
+            if Dir::is_undirected() {
+                *new_entry_count.entry(*dst_id).or_insert(0) += 1;
+            }
+        }
+        for (vertex_id, count) in new_entry_count {
+            self[vertex_id].reserve(count);
+        }
+
+        edges
+            .into_iter()
+            .map(|(src_id, dst_id, edge)| self.add_edge_unchecked(src_id, dst_id, edge))
+            .collect()
+    }
+
     /// Replaces the edge with id: `edge_id` with `edge`.
     ///
     /// # Arguments
@@ -219,17 +765,25 @@ impl<W: Copy, E: Edge<W> + Copy, Dir: EdgeDir> GraphStorage<W, E, Dir> for AdjMa
     /// * `edge`: New edge to replace the old one.
     ///
     /// # Complexity
-    /// O(|E|)
+    /// O(|E<sub>src->dst</sub>|)
     ///
     /// # Panics
     /// * If `src_id` or `dst_id` is not in range 0..|V|.
     /// * If there is no edge with id: `edge_id` from `src_id` to `dst_id`.
     fn update_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge_id: usize, mut edge: E) {
-        let removed_edge = self.remove_edge_unchecked(src_id, dst_id, edge_id);
+        edge.set_id(edge_id);
 
-        edge.set_id(removed_edge.get_id());
+        // Edge is replaced in place. Removing and adding it again could give it another one of the reusable ids.
+        let edge_vec = &mut self[(src_id, dst_id)];
+        let index = edge_vec.iter().position(|e| e.get_id() == edge_id).unwrap();
+        edge_vec[index] = edge;
 
-        self.add_edge_unchecked(src_id, dst_id, edge);
+        if Dir::is_undirected() {
+            self[(dst_id, src_id)]
+                .iter_mut()
+                .filter(|e| e.get_id() == edge_id)
+                .for_each(|e| *e = edge);
+        }
     }
 
     /// Removes the edge with id: `edge_id`.
@@ -257,23 +811,73 @@ impl<W: Copy, E: Edge<W> + Copy, Dir: EdgeDir> GraphStorage<W, E, Dir> for AdjMa
         let edge = edge_vec.swap_remove(index);
 
         if edge_vec.is_empty() {
-            self[src_id].remove(&dst_id);
+            self[src_id].remove(&to_index_unchecked(dst_id));
         }
 
         self.reusable_edge_ids.insert(edge_id);
 
+        let src_index = to_index_unchecked(src_id);
+        let dst_index = to_index_unchecked(dst_id);
+        self.decrease_degrees(src_index, dst_index, 1);
+
         if Dir::is_undirected() {
             let edge_vec = &mut self[(dst_id, src_id)];
             edge_vec.retain(|e| e.get_id() != edge_id);
 
             if edge_vec.is_empty() {
-                self[dst_id].remove(&src_id);
+                self[dst_id].remove(&src_index);
             }
+
+            self.decrease_degrees(dst_index, src_index, 1);
         }
 
         edge
     }
 
+    /// Removes every edge for which `keep` returns `false`.
+    ///
+    /// `keep` is called once for each edge. For undirected edges, end points are passed in the same order as [`edges`](crate::storage::GraphStorage::edges) returns them.
+    ///
+    /// # Arguments
+    /// `keep`: Receives (`src_id`, `dst_id`, `edge`) of each edge and returns `true` if the edge must be kept.
+    ///
+    /// # Complexity
+    /// O(|V| + |E|)
+    fn retain_edges<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(usize, usize, &E) -> bool,
+    {
+        let mut visited_ids = HashSet::new();
+        let mut removed_ids = HashSet::new();
+        for (src_id, dst_id, edge) in self.edges() {
+            if visited_ids.insert(edge.get_id()) && !keep(src_id, dst_id, edge) {
+                removed_ids.insert(edge.get_id());
+            }
+        }
+
+        let mut removed_entries = vec![];
+
+        // Neighbors left without any edge are removed, same as in remove_edge.
+        for (src_index, neighbors) in self.map.iter_mut() {
+            neighbors.retain(|dst_index, edges| {
+                let edge_count = edges.len();
+                edges.retain(|edge| !removed_ids.contains(&edge.get_id()));
+
+                if edges.len() < edge_count {
+                    removed_entries.push((*src_index, *dst_index, edge_count - edges.len()));
+                }
+
+                !edges.is_empty()
+            });
+        }
+
+        for (src_index, dst_index, count) in removed_entries {
+            self.decrease_degrees(src_index, dst_index, count);
+        }
+
+        self.reusable_edge_ids.extend(removed_ids);
+    }
+
     /// # Returns
     /// Number of vertices in the graph.
     ///
@@ -298,7 +902,7 @@ impl<W: Copy, E: Edge<W> + Copy, Dir: EdgeDir> GraphStorage<W, E, Dir> for AdjMa
     /// # Complexity
     /// O(|V|)
     fn vertices(&self) -> Vec<usize> {
-        self.map.keys().copied().collect()
+        self.map.keys().copied().map(from_index).collect()
     }
 
     /// # Arguments
@@ -313,19 +917,99 @@ impl<W: Copy, E: Edge<W> + Copy, Dir: EdgeDir> GraphStorage<W, E, Dir> for AdjMa
     /// # Panics
     /// If `src_id` is not in range 0..|V|.
     fn edges_from_unchecked(&self, src_id: usize) -> Vec<(usize, &E)> {
-        let edge_map = &self.map[&src_id];
+        self.edges_from_iter(src_id).collect()
+    }
 
-        let edges = edge_map
+    /// # Arguments
+    /// `src_id`: Id of the source vertex.
+    ///
+    /// # Returns
+    /// Iterator over edges from the source vertex in the format of: (`dst_id`, `edge`).
+    ///
+    /// # Complexity
+    /// O(1) to create and O(|E<sup>out</sup>|) to consume.
+    ///
+    /// # Panics
+    /// If there is no vertex with id: `src_id`.
+    fn edges_from_iter(&self, src_id: usize) -> Self::EdgesFromIter<'_> {
+        MapEdgesFrom {
+            edges_to: self[src_id].iter(),
+            dst_id: 0,
+            edges: [].iter(),
+        }
+    }
+
+    /// # Arguments
+    /// `src_id`: Id of the source vertex.
+    ///
+    /// # Returns
+    /// Iterator over id of vertices accessible from source vertex using one edge. A vertex appears once per edge that leads to it.
+    ///
+    /// # Complexity
+    /// O(1) to create and O(|E<sup>out</sup>|) to consume.
+    ///
+    /// # Panics
+    /// If there is no vertex with id: `src_id`.
+    fn neighbors_iter(&self, src_id: usize) -> Self::NeighborsIter<'_> {
+        let dst_of: fn((usize, &E)) -> usize = |(dst_id, _)| dst_id;
+
+        self.edges_from_iter(src_id).map(dst_of)
+    }
+
+    /// # Arguments
+    /// * `src_id`: Id of source vertex.
+    /// * `dst_id`: Id of destination vertex.
+    ///
+    /// # Returns
+    /// Edges from source vertex to destination vertex.
+    ///
+    /// # Complexity
+    /// O(|E<sub>src->dst</sub>|): Edges of the source vertex are looked up by destination instead of being scanned.
+    ///
+    /// # Panics
+    /// If there is no vertex with id: `src_id`.
+    fn edges_between_unchecked(&self, src_id: usize, dst_id: usize) -> Vec<&E> {
+        self[src_id]
+            .get(&to_index_unchecked(dst_id))
+            .map_or(vec![], |edges| edges.iter().collect())
+    }
+
+    /// # Arguments
+    /// * `src_id`: Id of source vertex.
+    /// * `dst_id`: Id of destination vertex.
+    /// * `edge_id`: Id of the edge to retrieve.
+    ///
+    /// # Returns
+    /// Edge between specified source and destination with specified id.
+    ///
+    /// # Complexity
+    /// O(|E<sub>src->dst</sub>|)
+    ///
+    /// # Panics
+    /// * If there is no vertex with id: `src_id`.
+    /// * If there is no edge with id: `edge_id` from `src_id` to `dst_id`.
+    fn edge_between_unchecked(&self, src_id: usize, dst_id: usize, edge_id: usize) -> &E {
+        self[(src_id, dst_id)]
             .iter()
-            .flat_map(|(dst_id, edge_vec)| {
-                edge_vec
-                    .iter()
-                    .map(|edge| (*dst_id, edge))
-                    .collect::<Vec<(usize, &E)>>()
-            })
-            .collect::<Vec<(usize, &E)>>();
+            .find(|edge| edge.get_id() == edge_id)
+            .unwrap()
+    }
 
-        edges
+    /// # Arguments
+    /// * `src_id`: Id of source vertex.
+    /// * `dst_id`: Id of destination vertex.
+    ///
+    /// # Returns
+    /// * `true`: If there is any edge from source vertex to destination vertex.
+    /// * `false`: Otherwise.
+    ///
+    /// # Complexity
+    /// O(1)
+    ///
+    /// # Panics
+    /// If there is no vertex with id: `src_id`.
+    fn has_any_edge_unchecked(&self, src_id: usize, dst_id: usize) -> bool {
+        self[src_id].contains_key(&to_index_unchecked(dst_id))
     }
 
     /// # Arguments
@@ -335,7 +1019,7 @@ impl<W: Copy, E: Edge<W> + Copy, Dir: EdgeDir> GraphStorage<W, E, Dir> for AdjMa
     /// * `true`: If storage contains the vertex with id: `vertex_id`.
     /// * `false`: Otherwise.
     fn contains_vertex(&self, vertex_id: usize) -> bool {
-        self.map.contains_key(&vertex_id)
+        to_index(vertex_id).is_ok_and(|vertex_index| self.map.contains_key(&vertex_index))
     }
 
     /// # Arguments
@@ -353,33 +1037,34 @@ impl<W: Copy, E: Edge<W> + Copy, Dir: EdgeDir> Index<(usize, usize)> for AdjMap<
     type Output = Vec<E>;
 
     fn index(&self, (src_id, dst_id): (usize, usize)) -> &Self::Output {
-        &self.map[&src_id][&dst_id]
+        &self[src_id][&to_index_unchecked(dst_id)]
     }
 }
 
 impl<W: Copy, E: Edge<W> + Copy, Dir: EdgeDir> IndexMut<(usize, usize)> for AdjMap<W, E, Dir> {
     fn index_mut(&mut self, (src_id, dst_id): (usize, usize)) -> &mut Self::Output {
-        self.map.get_mut(&src_id).unwrap().get_mut(&dst_id).unwrap()
+        self[src_id].get_mut(&to_index_unchecked(dst_id)).unwrap()
     }
 }
 
 impl<W: Copy, E: Edge<W> + Copy, Dir: EdgeDir> Index<usize> for AdjMap<W, E, Dir> {
-    type Output = HashMap<usize, Vec<E>>;
+    type Output = HashMap<VertexIndex, Vec<E>>;
 
     fn index(&self, src_id: usize) -> &Self::Output {
-        &self.map[&src_id]
+        &self.map[&to_index_unchecked(src_id)]
     }
 }
 
 impl<W: Copy, E: Edge<W> + Copy, Dir: EdgeDir> IndexMut<usize> for AdjMap<W, E, Dir> {
     fn index_mut(&mut self, src_id: usize) -> &mut Self::Output {
-        self.map.get_mut(&src_id).unwrap()
+        self.map.get_mut(&to_index_unchecked(src_id)).unwrap()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::misc::rng::Rng;
 
     #[test]
     fn directed_empty_map() {
@@ -794,6 +1479,46 @@ mod tests {
         }
     }
 
+    #[test]
+    fn update_edge_keeps_id() {
+        // Given: Undirected map with a loop and a removed edge, so there is an id to be reused.
+        //
+        //      a  ---  b  ---  c ---.
+        //                      |    |
+        //                      '----'
+        //
+        let mut map = Map::<usize>::init();
+        let a = map.add_vertex();
+        let b = map.add_vertex();
+        let c = map.add_vertex();
+
+        let ab = map.add_edge_unchecked(a, b, 1.into());
+        let bc = map.add_edge_unchecked(b, c, 2.into());
+        let cc = map.add_edge_unchecked(c, c, 3.into());
+        map.remove_edge_unchecked(a, b, ab);
+
+        // When: Updating the edges.
+        map.update_edge_unchecked(c, b, bc, 4.into());
+        map.update_edge_unchecked(c, c, cc, 5.into());
+
+        // Then:
+        assert_eq!(map.edge_unchecked(bc).get_weight().unwrap(), 4);
+        assert_eq!(map.edge_unchecked(cc).get_weight().unwrap(), 5);
+        for (src_id, dst_id) in [(b, c), (c, b)] {
+            assert_eq!(map.edges_between_unchecked(src_id, dst_id).len(), 1);
+            assert_eq!(
+                map.edge_between_unchecked(src_id, dst_id, bc)
+                    .get_weight()
+                    .unwrap(),
+                4
+            );
+        }
+        assert!(map
+            .edges_between_unchecked(c, c)
+            .iter()
+            .all(|edge| edge.get_id() == cc && edge.get_weight().unwrap() == 5));
+    }
+
     #[test]
     fn directed_remove_edge() {
         // Given: Directed map
@@ -987,4 +1712,390 @@ mod tests {
         // Then: it must have zero edges again.
         assert_eq!(di_mat.edge_count(), 0);
     }
+
+    #[test]
+    fn capacity() {
+        // Given: Undirected map with capacity for 10 vertices and 20 edges.
+        let mut map = Map::<usize>::with_capacity(10, 20);
+        let vertex_capacity = map.vertex_capacity();
+
+        // When: Adding as many vertices and edges.
+        let vertices: Vec<usize> = (0..10).map(|_| map.add_vertex()).collect();
+        let edge_capacity = map.edge_capacity();
+        for i in 0..20 {
+            map.add_edge_unchecked(vertices[i % 10], vertices[(i + 1 + i / 10) % 10], 1.into());
+        }
+
+        // Then: Nothing is reallocated.
+        assert!(vertex_capacity >= 10);
+        assert!(edge_capacity >= 20);
+        assert_eq!(map.vertex_capacity(), vertex_capacity);
+        assert_eq!(map.edge_capacity(), edge_capacity);
+
+        // When: Reserving more room and then shrinking.
+        map.reserve_vertices(100);
+        map.reserve_edges(1000);
+        assert!(map.vertex_capacity() >= 110);
+        assert!(map.edge_capacity() >= 1000);
+        map.shrink_to_fit();
+
+        // Then: Storage still contains every edge.
+        assert!(map.vertex_capacity() < 110);
+        assert!(map.edge_capacity() < 1000);
+        assert_eq!(map.edge_count(), 20);
+        assert!(map.has_any_edge_unchecked(vertices[0], vertices[1]));
+    }
+
+    #[test]
+    fn extend_with_edges() {
+        // Given: Directed and undirected maps with four vertices.
+        let mut map = Map::<usize>::init();
+        let mut di_map = DiMap::<usize>::init();
+        let vertices = map.extend_with_vertices(4);
+        di_map.extend_with_vertices(4);
+        let edges: Vec<(usize, usize, DefaultEdge<usize>)> = (0..4)
+            .map(|i| (vertices[i], vertices[(i + 1) % 4], (i + 1).into()))
+            .collect();
+
+        // When:
+        let ids = map.extend_with_edges(edges.clone()).unwrap();
+        let di_ids = di_map.extend_with_edges(edges).unwrap();
+
+        // Then:
+        assert_eq!(ids.len(), 4);
+        assert_eq!(map.edge_count(), 4);
+        assert_eq!(di_map.edge_count(), 4);
+        assert_eq!(map.neighbors_unchecked(vertices[0]).len(), 2);
+        assert_eq!(di_map.neighbors_unchecked(vertices[0]), vec![vertices[1]]);
+        assert_eq!(
+            *di_map
+                .edge_between_unchecked(vertices[3], vertices[0], di_ids[3])
+                .get_weight(),
+            4.into()
+        );
+
+        // When: One of the end points does not exist.
+        let result = map.extend_with_edges(vec![
+            (vertices[0], vertices[2], 1.into()),
+            (vertices[0], 10, 1.into()),
+        ]);
+
+        // Then: No edge is added.
+        assert!(result.is_err());
+        assert_eq!(map.edge_count(), 4);
+    }
+
+    #[test]
+    fn retain() {
+        // Given: Storage
+        //
+        //      a  --1--  b  --2--  c
+        //      |         ↺5        |
+        //      '---4---  d  --3----'
+        //
+        let mut storage = Map::<usize>::init();
+        let a = storage.add_vertex();
+        let b = storage.add_vertex();
+        let c = storage.add_vertex();
+        let d = storage.add_vertex();
+        let ab = storage.add_edge_unchecked(a, b, 1.into());
+        storage.add_edge_unchecked(b, c, 2.into());
+        storage.add_edge_unchecked(c, d, 3.into());
+        let da = storage.add_edge_unchecked(d, a, 4.into());
+        let bb = storage.add_edge_unchecked(b, b, 5.into());
+
+        // When: Keeping edges with even weights.
+        let mut calls = 0;
+        storage.retain_edges(|_, _, edge| {
+            calls += 1;
+            edge.get_weight().unwrap() % 2 == 0
+        });
+
+        // Then:
+        assert_eq!(calls, 5);
+        assert_eq!(storage.edge_count(), 2);
+        assert!(!storage.contains_edge(ab));
+        assert!(!storage.contains_edge(bb));
+        assert!(!storage.has_any_edge_unchecked(b, b));
+        assert!(storage.has_any_edge_unchecked(a, d));
+
+        // When: Removing b.
+        storage.retain_vertices(|vertex_id| vertex_id != b);
+
+        // Then: Its edge is removed and its id is freed.
+        assert_eq!(storage.vertex_count(), 3);
+        assert!(!storage.contains_vertex(b));
+        assert_eq!(storage.edge_count(), 1);
+        assert!(storage.contains_edge(da));
+        assert!(storage.neighbors_unchecked(c).is_empty());
+    }
+
+    #[test]
+    fn removing_vertex_frees_edge_ids() {
+        // Given: Directed storage with edges into and out of b.
+        let mut storage = DiMap::<usize>::init();
+        let a = storage.add_vertex();
+        let b = storage.add_vertex();
+        let c = storage.add_vertex();
+        storage.add_edge_unchecked(a, b, 1.into());
+        storage.add_edge_unchecked(b, c, 1.into());
+        let ca = storage.add_edge_unchecked(c, a, 1.into());
+
+        // When:
+        storage.remove_vertex_unchecked(b);
+
+        // Then:
+        assert_eq!(storage.edge_count(), 1);
+        assert!(storage.contains_edge(ca));
+        assert_eq!(storage.edges().len(), 1);
+    }
+
+    #[test]
+    fn try_add_vertex() {
+        // Given: Map with a removed vertex.
+        let mut map = DiMap::<usize>::init();
+        let a = map.try_add_vertex().unwrap();
+        let b = map.try_add_vertex().unwrap();
+        map.remove_vertex_unchecked(a);
+
+        // When:
+        let c = map.try_add_vertex().unwrap();
+
+        // Then: Id of the removed vertex is reused.
+        assert_eq!(c, a);
+        assert_ne!(c, b);
+        assert_eq!(map.vertex_count(), 2);
+        assert!(map.add_edge(c, b, 1.into()).is_ok());
+        assert!(map.add_edge(c, 5, 1.into()).is_err());
+        assert!(map.remove_edge(b, c, 0).is_err());
+    }
+
+    #[cfg(all(feature = "u32-index", target_pointer_width = "64"))]
+    #[test]
+    fn try_add_vertex_reports_overflow() {
+        // Given: Map whose next vertex id does not fit in 32 bits.
+        let mut map = DiMap::<usize>::init();
+        map.vertex_count = VertexIndex::MAX as usize + 1;
+
+        // When:
+        let result = map.try_add_vertex();
+
+        // Then:
+        assert!(result.is_err());
+        assert!(map.map.is_empty());
+    }
+
+    #[test]
+    fn iterators_match_vectors() {
+        // Given: Directed and undirected storages with parallel edges, a loop and a removed vertex.
+        let mut di_storage = DiMap::<usize>::init();
+        let mut storage = Map::<usize>::init();
+        for _ in 0..4 {
+            di_storage.add_vertex();
+            storage.add_vertex();
+        }
+        for (src_id, dst_id) in [(0, 1), (0, 1), (1, 2), (2, 2), (3, 0), (2, 0)] {
+            di_storage.add_edge_unchecked(src_id, dst_id, 1.into());
+            storage.add_edge_unchecked(src_id, dst_id, 1.into());
+        }
+        di_storage.remove_vertex_unchecked(3);
+        storage.remove_vertex_unchecked(3);
+
+        for vertex_id in 0..3 {
+            // When:
+            let di_edges: Vec<(usize, usize)> = di_storage
+                .edges_from_iter(vertex_id)
+                .map(|(dst_id, edge)| (dst_id, edge.get_id()))
+                .collect();
+            let edges: Vec<(usize, usize)> = storage
+                .edges_from_iter(vertex_id)
+                .map(|(dst_id, edge)| (dst_id, edge.get_id()))
+                .collect();
+
+            // Then:
+            let expected = |edges: Vec<(usize, &DefaultEdge<usize>)>| {
+                edges
+                    .into_iter()
+                    .map(|(dst_id, edge)| (dst_id, edge.get_id()))
+                    .collect::<Vec<(usize, usize)>>()
+            };
+            assert_eq!(
+                di_edges,
+                expected(di_storage.edges_from_unchecked(vertex_id))
+            );
+            assert_eq!(edges, expected(storage.edges_from_unchecked(vertex_id)));
+            assert_eq!(
+                di_storage.neighbors_iter(vertex_id).collect::<Vec<usize>>(),
+                di_storage.neighbors_unchecked(vertex_id)
+            );
+            assert_eq!(
+                storage.neighbors_iter(vertex_id).collect::<Vec<usize>>(),
+                storage.neighbors_unchecked(vertex_id)
+            );
+        }
+    }
+
+    #[test]
+    fn lookups_between_vertices() {
+        // Given: Directed and undirected storages with parallel edges, a loop and a removed edge.
+        let mut di_storage = DiMap::<usize>::init();
+        let mut storage = Map::<usize>::init();
+        for _ in 0..3 {
+            di_storage.add_vertex();
+            storage.add_vertex();
+        }
+        let mut di_ids = vec![];
+        let mut ids = vec![];
+        for (src_id, dst_id) in [(0, 1), (0, 1), (1, 2), (2, 2)] {
+            di_ids.push(di_storage.add_edge_unchecked(src_id, dst_id, 1.into()));
+            ids.push(storage.add_edge_unchecked(src_id, dst_id, 1.into()));
+        }
+        di_storage.remove_edge_unchecked(1, 2, di_ids[2]);
+        storage.remove_edge_unchecked(1, 2, ids[2]);
+
+        // When:
+        let mut di_between: Vec<usize> = di_storage
+            .edges_between_unchecked(0, 1)
+            .into_iter()
+            .map(|edge| edge.get_id())
+            .collect();
+        di_between.sort_unstable();
+        let loops: Vec<usize> = storage
+            .edges_between_unchecked(2, 2)
+            .into_iter()
+            .map(|edge| edge.get_id())
+            .collect();
+
+        // Then:
+        assert_eq!(di_between, vec![di_ids[0], di_ids[1]]);
+        assert!(di_storage.edges_between_unchecked(1, 0).is_empty());
+        assert!(storage.edges_between_unchecked(1, 2).is_empty());
+        assert_eq!(loops, vec![ids[3], ids[3]]);
+
+        assert_eq!(
+            di_storage.edge_between_unchecked(0, 1, di_ids[1]).get_id(),
+            di_ids[1]
+        );
+        assert_eq!(
+            storage.edge_between_unchecked(1, 0, ids[0]).get_id(),
+            ids[0]
+        );
+
+        assert!(di_storage.has_any_edge_unchecked(0, 1));
+        assert!(!di_storage.has_any_edge_unchecked(1, 0));
+        assert!(!di_storage.has_any_edge_unchecked(1, 2));
+        assert!(storage.has_any_edge_unchecked(1, 0));
+        assert!(storage.has_any_edge_unchecked(2, 2));
+        assert!(!storage.has_any_edge_unchecked(2, 1));
+    }
+
+    fn assert_cached_degrees<Dir: EdgeDir>(map: &AdjMap<usize, DefaultEdge<usize>, Dir>) {
+        let mut degrees = vec![];
+        for vertex_id in map.vertices() {
+            let out_degree = map.edges_from_unchecked(vertex_id).len();
+            let in_degree = if Dir::is_directed() {
+                map.edges()
+                    .into_iter()
+                    .filter(|(_, dst_id, _)| *dst_id == vertex_id)
+                    .count()
+            } else {
+                out_degree
+            };
+
+            assert_eq!(map.out_degree(vertex_id), out_degree);
+            assert_eq!(map.in_degree(vertex_id), in_degree);
+            assert_eq!(map.degree(vertex_id), out_degree);
+            degrees.push(out_degree);
+        }
+
+        assert_eq!(map.max_degree(), degrees.iter().max().copied());
+        assert_eq!(map.min_degree(), degrees.iter().min().copied());
+    }
+
+    fn cached_degrees_follow_mutations<Dir: EdgeDir>(seed: u64) {
+        // Given: Map and a random sequence of mutations.
+        let mut map = AdjMap::<usize, DefaultEdge<usize>, Dir>::init();
+        let mut rng = Rng::seed_from(seed);
+        assert_eq!(map.max_degree(), None);
+
+        for _ in 0..300 {
+            let vertices = map.vertices();
+            let edges: Vec<(usize, usize, usize)> = map
+                .edges()
+                .into_iter()
+                .map(|(src_id, dst_id, edge)| (src_id, dst_id, edge.get_id()))
+                .collect();
+
+            // When:
+            match rng.next_below(8) {
+                0 | 1 if vertices.len() < 12 => {
+                    map.add_vertex();
+                }
+                2 if !vertices.is_empty() => {
+                    map.remove_vertex_unchecked(vertices[rng.next_below(vertices.len())]);
+                }
+                3 if !edges.is_empty() => {
+                    let (src_id, dst_id, edge_id) = edges[rng.next_below(edges.len())];
+                    map.remove_edge_unchecked(src_id, dst_id, edge_id);
+                }
+                4 if !edges.is_empty() => {
+                    let (src_id, dst_id, edge_id) = edges[rng.next_below(edges.len())];
+                    map.update_edge_unchecked(src_id, dst_id, edge_id, 2.into());
+                }
+                5 => map.retain_edges(|_, _, _| rng.next_bool(0.8)),
+                6 if rng.next_bool(0.2) => map.retain_vertices(|_| rng.next_bool(0.8)),
+                _ if !vertices.is_empty() => {
+                    let src_id = vertices[rng.next_below(vertices.len())];
+                    let dst_id = vertices[rng.next_below(vertices.len())];
+                    map.add_edge_unchecked(src_id, dst_id, 1.into());
+                }
+                _ => {}
+            }
+
+            // Then:
+            assert_cached_degrees(&map);
+        }
+    }
+
+    #[test]
+    fn directed_cached_degrees() {
+        for seed in 0..5 {
+            cached_degrees_follow_mutations::<DirectedEdge>(seed);
+        }
+    }
+
+    #[test]
+    fn undirected_cached_degrees() {
+        for seed in 0..5 {
+            cached_degrees_follow_mutations::<UndirectedEdge>(seed);
+        }
+    }
+
+    #[test]
+    fn cached_degrees_count_loops() {
+        // Given: Directed and undirected maps with a loop on a and an edge from a to b.
+        let mut di_map = DiMap::<usize>::init();
+        let mut map = Map::<usize>::init();
+        let a = di_map.add_vertex();
+        let b = di_map.add_vertex();
+        map.extend_with_vertices(2);
+        for (src_id, dst_id) in [(a, a), (a, b)] {
+            di_map.add_edge_unchecked(src_id, dst_id, 1.into());
+            map.add_edge_unchecked(src_id, dst_id, 1.into());
+        }
+
+        // When: Doing nothing.
+
+        // Then: Loops add 1 to in and out degrees of directed graphs and 2 to degree of undirected graphs.
+        assert_eq!(di_map.out_degree(a), 2);
+        assert_eq!(di_map.in_degree(a), 1);
+        assert_eq!(di_map.in_degree(b), 1);
+        assert_eq!(di_map.max_degree(), Some(2));
+        assert_eq!(di_map.min_degree(), Some(0));
+        assert_eq!(map.degree(a), 3);
+        assert_eq!(map.in_degree(a), 3);
+        assert_eq!(map.degree(b), 1);
+        assert_eq!(map.max_degree(), Some(3));
+        assert_eq!(map.min_degree(), Some(1));
+    }
 }
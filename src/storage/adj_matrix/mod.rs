@@ -1,10 +1,14 @@
 mod utils;
 
+use anyhow::Result;
+use num_traits::One;
 use std::any::Any;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::marker::PhantomData;
+use std::str::FromStr;
 
 use crate::graph::{DefaultEdge, DirectedEdge, Edge, EdgeDir, FlowEdge, UndirectedEdge};
+use crate::io::edge_list;
 use crate::storage::GraphStorage;
 
 /// An adjacency matrix that uses [`undirected`](crate::graph::UndirectedEdge) [`default edges`](crate::graph::DefaultEdge).
@@ -138,7 +142,117 @@ impl<W, E: Edge<W>, Dir: EdgeDir> AdjMatrix<W, E, Dir> {
     }
 }
 
+impl<W, E: Edge<W>, Dir: EdgeDir> AdjMatrix<W, E, Dir>
+where
+    W: FromStr + Any + One,
+{
+    /// Initializes an adjacency matrix from an edge list.
+    ///
+    /// For more info about the format checkout [`edge_list`](crate::io::edge_list) module.
+    ///
+    /// # Arguments
+    /// `text`: Edge list.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing the storage and a map from each label in the text to the id of its vertex in the storage.
+    /// * `Err`: If any line of the text is malformed.
+    ///
+    /// # Examples
+    /// ```
+    /// use prepona::prelude::*;
+    /// use prepona::storage::DiMat;
+    ///
+    /// let (storage, labels) = DiMat::<usize>::from_edge_list("1 2\n2 3 4\n").unwrap();
+    ///
+    /// assert_eq!(storage.vertex_count(), 3);
+    /// assert!(storage.has_any_edge_unchecked(labels["2"], labels["3"]));
+    /// ```
+    pub fn from_edge_list(text: &str) -> Result<(Self, HashMap<String, usize>)> {
+        let mut storage = AdjMatrix::init();
+
+        let labels = edge_list::read(&mut storage, text)?;
+
+        Ok((storage, labels))
+    }
+}
+
+/// Iterator over outgoing edges of a vertex in an [`AdjMatrix`](crate::storage::AdjMatrix),
+/// returned by [`edges_from_iter`](crate::storage::GraphStorage::edges_from_iter).
+///
+/// ## Generic Parameters
+/// * `W`: **W**eight type associated with edges.
+/// * `E`: **E**dge type that storage uses.
+/// * `Dir`: **Dir**ection of edges: [`Directed`](crate::graph::DirectedEdge) or [`Undirected`](crate::graph::UndirectedEdge).
+pub struct MatEdgesFrom<'a, W, E: Edge<W>, Dir: EdgeDir> {
+    matrix: &'a AdjMatrix<W, E, Dir>,
+    src_id: usize,
+    next_dst_id: usize,
+    dst_id: usize,
+    edges: std::slice::Iter<'a, E>,
+}
+
+impl<'a, W: Any, E: Edge<W>, Dir: EdgeDir> Iterator for MatEdgesFrom<'a, W, E, Dir> {
+    type Item = (usize, &'a E);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(edge) = self.edges.next() {
+                return Some((self.dst_id, edge));
+            }
+
+            if self.next_dst_id >= self.matrix.total_vertex_count() {
+                return None;
+            }
+
+            self.dst_id = self.next_dst_id;
+            self.edges = self.matrix[(self.src_id, self.dst_id)].iter();
+            self.next_dst_id += 1;
+        }
+    }
+}
+
+/// Iterator over neighbors of a vertex in an [`AdjMatrix`](crate::storage::AdjMatrix),
+/// returned by [`neighbors_iter`](crate::storage::GraphStorage::neighbors_iter).
+///
+/// ## Generic Parameters
+/// * `W`: **W**eight type associated with edges.
+/// * `E`: **E**dge type that storage uses.
+/// * `Dir`: **Dir**ection of edges: [`Directed`](crate::graph::DirectedEdge) or [`Undirected`](crate::graph::UndirectedEdge).
+pub struct MatNeighbors<'a, W, E: Edge<W>, Dir: EdgeDir> {
+    matrix: &'a AdjMatrix<W, E, Dir>,
+    src_id: usize,
+    next_dst_id: usize,
+}
+
+impl<'a, W: Any, E: Edge<W>, Dir: EdgeDir> Iterator for MatNeighbors<'a, W, E, Dir> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.next_dst_id < self.matrix.total_vertex_count() {
+            let dst_id = self.next_dst_id;
+            self.next_dst_id += 1;
+
+            if !self.matrix[(self.src_id, dst_id)].is_empty() {
+                return Some(dst_id);
+            }
+        }
+
+        None
+    }
+}
+
 impl<W: Any, E: Edge<W>, Dir: EdgeDir> GraphStorage<W, E, Dir> for AdjMatrix<W, E, Dir> {
+    type EdgesFromIter<'a>
+        = MatEdgesFrom<'a, W, E, Dir>
+    where
+        Self: 'a,
+        E: 'a;
+
+    type NeighborsIter<'a>
+        = MatNeighbors<'a, W, E, Dir>
+    where
+        Self: 'a;
+
     /// Adds a vertex to the graph.
     ///
     /// # Returns
@@ -188,8 +302,14 @@ impl<W: Any, E: Edge<W>, Dir: EdgeDir> GraphStorage<W, E, Dir> for AdjMatrix<W,
     /// If `vertex_id` is not in range 0..|V|.
     fn remove_vertex_unchecked(&mut self, vertex_id: usize) {
         for other_id in 0..self.total_vertex_count() {
-            self[(vertex_id, other_id)].clear();
-            self[(other_id, vertex_id)].clear();
+            // Ids of the removed edges must be freed, so edge_count and contains_edge stay exact.
+            // In an undirected matrix both cells are the same, so the second one is already empty.
+            for cell in [(vertex_id, other_id), (other_id, vertex_id)] {
+                let removed_ids: Vec<usize> =
+                    self[cell].drain(..).map(|edge| edge.get_id()).collect();
+
+                self.reusable_edge_ids.extend(removed_ids);
+            }
         }
 
         self.reusable_vertex_ids.insert(vertex_id);
@@ -321,17 +441,47 @@ impl<W: Any, E: Edge<W>, Dir: EdgeDir> GraphStorage<W, E, Dir> for AdjMatrix<W,
     /// # Panics
     /// If `src_id` is not in range 0..|V|.
     fn edges_from_unchecked(&self, src_id: usize) -> Vec<(usize, &E)> {
-        // Map each dst_id to the list of edges that start from src_id and end in dst_id.
-        // Then flatten all the lists into a final list that contains all the outgoing edges of vertex with id: src_id.
-        (0..self.total_vertex_count())
-            .into_iter()
-            .flat_map(|dst_id| {
-                self.edges_between_unchecked(src_id, dst_id)
-                    .into_iter()
-                    .map(|edge| (dst_id, edge))
-                    .collect::<Vec<(usize, &E)>>()
-            })
-            .collect()
+        self.edges_from_iter(src_id).collect()
+    }
+
+    /// # Arguments
+    /// `src_id`: Id of the source vertex.
+    ///
+    /// # Returns
+    /// Iterator over edges from the source vertex in the format of: (`dst_id`, `edge`).
+    ///
+    /// # Complexity
+    /// O(1) to create and O(|V| + |E<sup>out</sup>|) to consume.
+    ///
+    /// # Panics
+    /// If `src_id` is not in range 0..|V|.
+    fn edges_from_iter(&self, src_id: usize) -> Self::EdgesFromIter<'_> {
+        MatEdgesFrom {
+            matrix: self,
+            src_id,
+            next_dst_id: 0,
+            dst_id: 0,
+            edges: [].iter(),
+        }
+    }
+
+    /// # Arguments
+    /// `src_id`: Id of the source vertex.
+    ///
+    /// # Returns
+    /// Iterator over id of vertices accessible from source vertex using one edge.
+    ///
+    /// # Complexity
+    /// O(1) to create and O(|V|) to consume.
+    ///
+    /// # Panics
+    /// If `src_id` is not in range 0..|V|.
+    fn neighbors_iter(&self, src_id: usize) -> Self::NeighborsIter<'_> {
+        MatNeighbors {
+            matrix: self,
+            src_id,
+            next_dst_id: 0,
+        }
     }
 
     /// # Arguments:
@@ -346,10 +496,7 @@ impl<W: Any, E: Edge<W>, Dir: EdgeDir> GraphStorage<W, E, Dir> for AdjMatrix<W,
     /// # Panics
     /// If `src_id` is not in range 0..|V|.
     fn neighbors_unchecked(&self, src_id: usize) -> Vec<usize> {
-        (0..self.total_vertex_count())
-            .into_iter()
-            .filter(|dst_id| !self[(src_id, *dst_id)].is_empty())
-            .collect()
+        self.neighbors_iter(src_id).collect()
     }
 
     /// # Arguments
@@ -368,6 +515,23 @@ impl<W: Any, E: Edge<W>, Dir: EdgeDir> GraphStorage<W, E, Dir> for AdjMatrix<W,
         self[(src_id, dst_id)].iter().collect()
     }
 
+    /// # Arguments
+    /// * `src_id`: Id of source vertex.
+    /// * `dst_id`: Id of destination vertex.
+    ///
+    /// # Returns
+    /// * `true`: If there is any edge from source vertex to destination vertex.
+    /// * `false`: Otherwise.
+    ///
+    /// # Complexity
+    /// O(1)
+    ///
+    /// # Panics
+    /// * If `src_id` or `dst_id` is not in range 0..|V|.
+    fn has_any_edge_unchecked(&self, src_id: usize, dst_id: usize) -> bool {
+        !self[(src_id, dst_id)].is_empty()
+    }
+
     /// # Arguments
     /// `vertex_id`: Id of the vertex to search for its existence in the storage.
     ///
@@ -1058,4 +1222,141 @@ mod tests {
         // Then: it must have zero edges again.
         assert_eq!(di_mat.edge_count(), 0);
     }
+
+    #[test]
+    fn removing_vertex_frees_its_edges() {
+        // Given: Matrices with edges into, out of and around vertex b.
+        let mut mat = Mat::<usize>::init();
+        let mut di_mat = DiMat::<usize>::init();
+        let [a, b, c] = [mat.add_vertex(), mat.add_vertex(), mat.add_vertex()];
+        di_mat.add_vertex();
+        di_mat.add_vertex();
+        di_mat.add_vertex();
+        let edges = [(a, b), (b, c), (b, b), (c, a)];
+        let ids: Vec<usize> = edges
+            .iter()
+            .map(|(src_id, dst_id)| mat.add_edge_unchecked(*src_id, *dst_id, 1.into()))
+            .collect();
+        let di_ids: Vec<usize> = edges
+            .iter()
+            .map(|(src_id, dst_id)| di_mat.add_edge_unchecked(*src_id, *dst_id, 1.into()))
+            .collect();
+
+        // When: Removing b.
+        mat.remove_vertex_unchecked(b);
+        di_mat.remove_vertex_unchecked(b);
+
+        // Then: Only c -> a remains.
+        assert_eq!(mat.edge_count(), 1);
+        assert_eq!(di_mat.edge_count(), 1);
+        assert!(ids[..3].iter().all(|edge_id| !mat.contains_edge(*edge_id)));
+        assert!(di_ids[..3]
+            .iter()
+            .all(|edge_id| !di_mat.contains_edge(*edge_id)));
+        assert!(mat.contains_edge(ids[3]));
+        assert!(di_mat.has_any_edge_unchecked(c, a));
+        assert!(!di_mat.has_any_edge_unchecked(a, c));
+        assert!(mat.has_any_edge_unchecked(a, c));
+
+        // And: Freed ids are reused.
+        let new_vertex = mat.add_vertex();
+        let new_id = mat.add_edge_unchecked(a, new_vertex, 1.into());
+        assert!(ids[..3].contains(&new_id));
+        assert_eq!(mat.edge_count(), 2);
+    }
+
+    #[test]
+    fn retain() {
+        // Given: Storage
+        //
+        //      a  --1--  b  --2--  c
+        //      |         ↺5        |
+        //      '---4---  d  --3----'
+        //
+        let mut storage = Mat::<usize>::init();
+        let a = storage.add_vertex();
+        let b = storage.add_vertex();
+        let c = storage.add_vertex();
+        let d = storage.add_vertex();
+        let ab = storage.add_edge_unchecked(a, b, 1.into());
+        storage.add_edge_unchecked(b, c, 2.into());
+        storage.add_edge_unchecked(c, d, 3.into());
+        let da = storage.add_edge_unchecked(d, a, 4.into());
+        let bb = storage.add_edge_unchecked(b, b, 5.into());
+
+        // When: Keeping edges with even weights.
+        let mut calls = 0;
+        storage.retain_edges(|_, _, edge| {
+            calls += 1;
+            edge.get_weight().unwrap() % 2 == 0
+        });
+
+        // Then:
+        assert_eq!(calls, 5);
+        assert_eq!(storage.edge_count(), 2);
+        assert!(!storage.contains_edge(ab));
+        assert!(!storage.contains_edge(bb));
+        assert!(!storage.has_any_edge_unchecked(b, b));
+        assert!(storage.has_any_edge_unchecked(a, d));
+
+        // When: Removing b.
+        storage.retain_vertices(|vertex_id| vertex_id != b);
+
+        // Then: Its edge is removed and its id is freed.
+        assert_eq!(storage.vertex_count(), 3);
+        assert!(!storage.contains_vertex(b));
+        assert_eq!(storage.edge_count(), 1);
+        assert!(storage.contains_edge(da));
+        assert!(storage.neighbors_unchecked(c).is_empty());
+    }
+
+    #[test]
+    fn iterators_match_vectors() {
+        // Given: Directed and undirected storages with parallel edges, a loop and a removed vertex.
+        let mut di_storage = DiMat::<usize>::init();
+        let mut storage = Mat::<usize>::init();
+        for _ in 0..4 {
+            di_storage.add_vertex();
+            storage.add_vertex();
+        }
+        for (src_id, dst_id) in [(0, 1), (0, 1), (1, 2), (2, 2), (3, 0), (2, 0)] {
+            di_storage.add_edge_unchecked(src_id, dst_id, 1.into());
+            storage.add_edge_unchecked(src_id, dst_id, 1.into());
+        }
+        di_storage.remove_vertex_unchecked(3);
+        storage.remove_vertex_unchecked(3);
+
+        for vertex_id in 0..3 {
+            // When:
+            let di_edges: Vec<(usize, usize)> = di_storage
+                .edges_from_iter(vertex_id)
+                .map(|(dst_id, edge)| (dst_id, edge.get_id()))
+                .collect();
+            let edges: Vec<(usize, usize)> = storage
+                .edges_from_iter(vertex_id)
+                .map(|(dst_id, edge)| (dst_id, edge.get_id()))
+                .collect();
+
+            // Then:
+            let expected = |edges: Vec<(usize, &DefaultEdge<usize>)>| {
+                edges
+                    .into_iter()
+                    .map(|(dst_id, edge)| (dst_id, edge.get_id()))
+                    .collect::<Vec<(usize, usize)>>()
+            };
+            assert_eq!(
+                di_edges,
+                expected(di_storage.edges_from_unchecked(vertex_id))
+            );
+            assert_eq!(edges, expected(storage.edges_from_unchecked(vertex_id)));
+            assert_eq!(
+                di_storage.neighbors_iter(vertex_id).collect::<Vec<usize>>(),
+                di_storage.neighbors_unchecked(vertex_id)
+            );
+            assert_eq!(
+                storage.neighbors_iter(vertex_id).collect::<Vec<usize>>(),
+                storage.neighbors_unchecked(vertex_id)
+            );
+        }
+    }
 }
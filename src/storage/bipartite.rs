@@ -0,0 +1,534 @@
+use std::collections::{BTreeSet, HashMap};
+use std::marker::PhantomData;
+
+use anyhow::Result;
+
+use crate::graph::{Edge, EdgeDir, ListGraph, UndirectedEdge};
+use crate::provide::Graph;
+use crate::storage::{Error, GraphStorage, List};
+
+/// Partition of a vertex in a [`Bipartite`](crate::storage::Bipartite) storage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Top,
+    Bottom,
+}
+
+impl Side {
+    /// # Returns
+    /// The other side.
+    pub fn opposite(&self) -> Side {
+        match self {
+            Side::Top => Side::Bottom,
+            Side::Bottom => Side::Top,
+        }
+    }
+}
+
+/// A storage that keeps the two partitions of a bipartite graph explicitly and rejects edges between vertices of the same partition.
+///
+/// It wraps another storage, which holds the vertices and edges. Vertices are added to a partition using
+/// [`add_top_vertex`](crate::storage::Bipartite::add_top_vertex) and [`add_bottom_vertex`](crate::storage::Bipartite::add_bottom_vertex).
+/// [`add_vertex`](crate::storage::GraphStorage::add_vertex) of [`GraphStorage`](crate::storage::GraphStorage) adds the vertex to the top partition.
+///
+/// Checked functions like [`add_edge`](crate::storage::GraphStorage::add_edge) return an [`IntraPartitionEdge`](crate::storage::ErrorKind::IntraPartitionEdge) error
+/// for edges within a partition, and unchecked functions panic.
+///
+/// ## Generic Parameters
+/// * `W`: **W**eight type associated with edges.
+/// * `E`: **E**dge type that storage uses.
+/// * `Dir`: **Dir**ection of edges: [`Directed`](crate::graph::DirectedEdge) or [`Undirected`](crate::graph::UndirectedEdge).
+/// * `S`: **S**torage that holds the vertices and edges.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::{Bipartite, List};
+/// use prepona::graph::SimpleGraph;
+///
+/// // Given: Authors a and b writing papers p and q.
+/// //
+/// //      a       b
+/// //      | \     |
+/// //      p   '-- q
+/// //
+/// let mut storage = Bipartite::init(List::<usize>::init());
+/// let a = storage.add_top_vertex();
+/// let b = storage.add_top_vertex();
+/// let p = storage.add_bottom_vertex();
+/// let q = storage.add_bottom_vertex();
+/// storage.add_edge(a, p, 1.into()).unwrap();
+/// storage.add_edge(a, q, 1.into()).unwrap();
+/// storage.add_edge(b, q, 1.into()).unwrap();
+///
+/// // When: Connecting two authors.
+/// let result = storage.add_edge(a, b, 1.into());
+///
+/// // Then:
+/// assert!(result.is_err());
+///
+/// // When: Connecting authors that share a paper.
+/// let coauthors = storage.project_top();
+///
+/// // Then:
+/// assert_eq!(coauthors.vertices(), vec![a, b]);
+/// assert!(coauthors.has_any_edge_unchecked(a, b));
+///
+/// // Bipartite is a storage, so it can back a graph.
+/// let graph = SimpleGraph::init(storage);
+/// assert_eq!(graph.edges_count(), 3);
+/// ```
+pub struct Bipartite<W, E: Edge<W>, Dir: EdgeDir, S> {
+    storage: S,
+
+    sides: HashMap<usize, Side>,
+
+    phantom_w: PhantomData<W>,
+    phantom_e: PhantomData<E>,
+    phantom_dir: PhantomData<Dir>,
+}
+
+impl<W, E: Edge<W>, Dir: EdgeDir, S: GraphStorage<W, E, Dir>> Bipartite<W, E, Dir, S> {
+    /// # Arguments
+    /// `storage`: Empty storage to hold the vertices and edges.
+    ///
+    /// # Returns
+    /// Empty bipartite storage.
+    ///
+    /// # Panics
+    /// If `storage` is not empty.
+    pub fn init(storage: S) -> Self {
+        assert_eq!(
+            storage.vertex_count(),
+            0,
+            "Partitions of existing vertices are unknown"
+        );
+
+        Bipartite {
+            storage,
+
+            sides: HashMap::new(),
+
+            phantom_w: PhantomData,
+            phantom_e: PhantomData,
+            phantom_dir: PhantomData,
+        }
+    }
+
+    /// # Arguments
+    /// `side`: Partition of the new vertex.
+    ///
+    /// # Returns
+    /// Id of the new vertex.
+    pub fn add_vertex_to(&mut self, side: Side) -> usize {
+        let vertex_id = self.storage.add_vertex();
+
+        self.sides.insert(vertex_id, side);
+
+        vertex_id
+    }
+
+    /// # Returns
+    /// Id of the new vertex in the top partition.
+    pub fn add_top_vertex(&mut self) -> usize {
+        self.add_vertex_to(Side::Top)
+    }
+
+    /// # Returns
+    /// Id of the new vertex in the bottom partition.
+    pub fn add_bottom_vertex(&mut self) -> usize {
+        self.add_vertex_to(Side::Bottom)
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// * `Some`: Containing the partition of the vertex.
+    /// * `None`: If there is no vertex with id: `vertex_id`.
+    pub fn side_of(&self, vertex_id: usize) -> Option<Side> {
+        self.sides.get(&vertex_id).copied()
+    }
+
+    /// # Arguments
+    /// `side`: Partition to list.
+    ///
+    /// # Returns
+    /// Id of vertices in the partition, sorted.
+    pub fn vertices_of(&self, side: Side) -> Vec<usize> {
+        let mut vertices: Vec<usize> = self
+            .sides
+            .iter()
+            .filter(|(_, vertex_side)| **vertex_side == side)
+            .map(|(vertex_id, _)| *vertex_id)
+            .collect();
+
+        vertices.sort_unstable();
+
+        vertices
+    }
+
+    /// # Returns
+    /// Id of vertices in the top partition, sorted.
+    pub fn top_vertices(&self) -> Vec<usize> {
+        self.vertices_of(Side::Top)
+    }
+
+    /// # Returns
+    /// Id of vertices in the bottom partition, sorted.
+    pub fn bottom_vertices(&self) -> Vec<usize> {
+        self.vertices_of(Side::Bottom)
+    }
+
+    /// # Returns
+    /// The wrapped storage.
+    pub fn storage(&self) -> &S {
+        &self.storage
+    }
+
+    /// # Returns
+    /// The wrapped storage, dropping the partitions.
+    pub fn into_storage(self) -> S {
+        self.storage
+    }
+
+    fn check_sides(&self, src_id: usize, dst_id: usize) -> Result<()> {
+        match (self.side_of(src_id), self.side_of(dst_id)) {
+            (None, _) => Err(Error::new_vnf(src_id))?,
+            (_, None) => Err(Error::new_vnf(dst_id))?,
+            (Some(src_side), Some(dst_side)) if src_side == dst_side => {
+                Err(Error::new_ipe(src_id, dst_id))?
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Computes the one mode projection of a partition:
+    /// A graph over the vertices of the partition, where two vertices are connected if they share a neighbor in the other partition.
+    ///
+    /// # Arguments
+    /// `side`: Partition to project onto.
+    ///
+    /// # Returns
+    /// Graph with the same vertex ids as the vertices of `side`.
+    /// Weight of each edge is the number of neighbors its end points share, which gives the weighted projection.
+    /// Ignore the weights to get the unweighted projection. Direction of edges is ignored.
+    ///
+    /// # Complexity
+    /// O(|V| + |E| + sum of squared degrees of the vertices in the other partition)
+    pub fn project(&self, side: Side) -> ListGraph<usize, UndirectedEdge> {
+        // Neighbors of each vertex of the other partition, sorted so each pair is counted in the same order.
+        let mut neighbors_of: HashMap<usize, BTreeSet<usize>> = HashMap::new();
+        for (src_id, dst_id, _) in self.storage.edges() {
+            let (vertex_id, other_id) = if self.sides[&src_id] == side {
+                (src_id, dst_id)
+            } else {
+                (dst_id, src_id)
+            };
+
+            neighbors_of.entry(other_id).or_default().insert(vertex_id);
+        }
+
+        let mut shared_counts = HashMap::new();
+        for neighbors in neighbors_of.values() {
+            let neighbors: Vec<usize> = neighbors.iter().copied().collect();
+
+            for (index, src_id) in neighbors.iter().enumerate() {
+                for dst_id in neighbors[index + 1..].iter() {
+                    *shared_counts.entry((*src_id, *dst_id)).or_insert(0) += 1;
+                }
+            }
+        }
+
+        // Adding every id and removing the ones outside the partition keeps the ids of the vertices.
+        let mut graph = ListGraph::init(List::init());
+        let max_id = self.sides.keys().max().map_or(0, |max_id| max_id + 1);
+        for _ in 0..max_id {
+            graph.add_vertex();
+        }
+        for vertex_id in 0..max_id {
+            if self.side_of(vertex_id) != Some(side) {
+                graph.remove_vertex_unchecked(vertex_id);
+            }
+        }
+
+        for ((src_id, dst_id), count) in shared_counts {
+            graph.add_edge_unchecked(src_id, dst_id, count.into());
+        }
+
+        graph
+    }
+
+    /// # Returns
+    /// Projection of the top partition. For more info checkout [`project`](crate::storage::Bipartite::project).
+    pub fn project_top(&self) -> ListGraph<usize, UndirectedEdge> {
+        self.project(Side::Top)
+    }
+
+    /// # Returns
+    /// Projection of the bottom partition. For more info checkout [`project`](crate::storage::Bipartite::project).
+    pub fn project_bottom(&self) -> ListGraph<usize, UndirectedEdge> {
+        self.project(Side::Bottom)
+    }
+}
+
+/// For documentation about each function checkout [`GraphStorage`](crate::storage::GraphStorage) trait and the storage you wrap.
+impl<W, E: Edge<W>, Dir: EdgeDir, S: GraphStorage<W, E, Dir>> GraphStorage<W, E, Dir>
+    for Bipartite<W, E, Dir, S>
+{
+    type EdgesFromIter<'a>
+        = S::EdgesFromIter<'a>
+    where
+        Self: 'a,
+        E: 'a;
+
+    type NeighborsIter<'a>
+        = S::NeighborsIter<'a>
+    where
+        Self: 'a;
+
+    /// Adds a vertex to the top partition.
+    fn add_vertex(&mut self) -> usize {
+        self.add_top_vertex()
+    }
+
+    /// Adds a vertex to the top partition without panicking.
+    fn try_add_vertex(&mut self) -> Result<usize> {
+        let vertex_id = self.storage.try_add_vertex()?;
+
+        self.sides.insert(vertex_id, Side::Top);
+
+        Ok(vertex_id)
+    }
+
+    fn remove_vertex_unchecked(&mut self, vertex_id: usize) {
+        self.storage.remove_vertex_unchecked(vertex_id);
+
+        self.sides.remove(&vertex_id);
+    }
+
+    fn retain_vertices<F>(&mut self, keep: F)
+    where
+        F: FnMut(usize) -> bool,
+    {
+        self.storage.retain_vertices(keep);
+
+        let storage = &self.storage;
+        self.sides
+            .retain(|vertex_id, _| storage.contains_vertex(*vertex_id));
+    }
+
+    fn contains_vertex(&self, vertex_id: usize) -> bool {
+        self.sides.contains_key(&vertex_id)
+    }
+
+    /// Adds `edge` from vertex with id `src_id`: to vertex with id: `dst_id`.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing unique id of the newly added edge.
+    /// * `Err`: [`VertexNotFound`](crate::storage::ErrorKind::VertexNotFound) if vertex with either id: `src_id` or `dst_id` does not exist.
+    /// * `Err`: [`IntraPartitionEdge`](crate::storage::ErrorKind::IntraPartitionEdge) if both vertices are in the same partition.
+    fn add_edge(&mut self, src_id: usize, dst_id: usize, edge: E) -> Result<usize> {
+        self.check_sides(src_id, dst_id)?;
+
+        Ok(self.storage.add_edge_unchecked(src_id, dst_id, edge))
+    }
+
+    /// Adds `edge` from vertex with id `src_id`: to vertex with id: `dst_id`.
+    ///
+    /// # Panics
+    /// If both vertices are in the same partition.
+    fn add_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge: E) -> usize {
+        assert_ne!(
+            self.side_of(src_id),
+            self.side_of(dst_id),
+            "Vertex: {} and vertex: {} are in the same partition",
+            src_id,
+            dst_id
+        );
+
+        self.storage.add_edge_unchecked(src_id, dst_id, edge)
+    }
+
+    /// Adds edges to the storage in one go.
+    ///
+    /// End points of all edges are checked before adding any of them, so either all edges are added or none of them.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing unique ids of the newly added edges, in the same order as `edges`.
+    /// * `Err`: [`VertexNotFound`](crate::storage::ErrorKind::VertexNotFound) if any of the end points does not exist.
+    /// * `Err`: [`IntraPartitionEdge`](crate::storage::ErrorKind::IntraPartitionEdge) if end points of any of the edges are in the same partition.
+    fn extend_with_edges<I>(&mut self, edges: I) -> Result<Vec<usize>>
+    where
+        I: IntoIterator<Item = (usize, usize, E)>,
+    {
+        let edges: Vec<(usize, usize, E)> = edges.into_iter().collect();
+
+        for (src_id, dst_id, _) in &edges {
+            self.check_sides(*src_id, *dst_id)?;
+        }
+
+        Ok(self.storage.extend_with_edges_unchecked(edges))
+    }
+
+    fn update_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge_id: usize, edge: E) {
+        self.storage
+            .update_edge_unchecked(src_id, dst_id, edge_id, edge)
+    }
+
+    fn remove_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge_id: usize) -> E {
+        self.storage.remove_edge_unchecked(src_id, dst_id, edge_id)
+    }
+
+    fn retain_edges<F>(&mut self, keep: F)
+    where
+        F: FnMut(usize, usize, &E) -> bool,
+    {
+        self.storage.retain_edges(keep)
+    }
+
+    fn contains_edge(&self, edge_id: usize) -> bool {
+        self.storage.contains_edge(edge_id)
+    }
+
+    fn vertex_count(&self) -> usize {
+        self.storage.vertex_count()
+    }
+
+    fn vertices(&self) -> Vec<usize> {
+        self.storage.vertices()
+    }
+
+    fn edges_between_unchecked(&self, src_id: usize, dst_id: usize) -> Vec<&E> {
+        self.storage.edges_between_unchecked(src_id, dst_id)
+    }
+
+    fn edge_between_unchecked(&self, src_id: usize, dst_id: usize, edge_id: usize) -> &E {
+        self.storage.edge_between_unchecked(src_id, dst_id, edge_id)
+    }
+
+    fn edge_unchecked(&self, edge_id: usize) -> &E {
+        self.storage.edge_unchecked(edge_id)
+    }
+
+    fn has_any_edge_unchecked(&self, src_id: usize, dst_id: usize) -> bool {
+        self.storage.has_any_edge_unchecked(src_id, dst_id)
+    }
+
+    fn edges(&self) -> Vec<(usize, usize, &E)> {
+        self.storage.edges()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.storage.edge_count()
+    }
+
+    fn as_directed_edges(&self) -> Vec<(usize, usize, &E)> {
+        self.storage.as_directed_edges()
+    }
+
+    fn edges_from_unchecked(&self, src_id: usize) -> Vec<(usize, &E)> {
+        self.storage.edges_from_unchecked(src_id)
+    }
+
+    fn edges_from_iter(&self, src_id: usize) -> Self::EdgesFromIter<'_> {
+        self.storage.edges_from_iter(src_id)
+    }
+
+    fn neighbors_iter(&self, src_id: usize) -> Self::NeighborsIter<'_> {
+        self.storage.neighbors_iter(src_id)
+    }
+
+    fn neighbors_unchecked(&self, src_id: usize) -> Vec<usize> {
+        self.storage.neighbors_unchecked(src_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provide::{Edges, Neighbors, Vertices};
+    use crate::storage::{DiMat, ErrorKind};
+
+    #[test]
+    fn partitions_are_kept() {
+        // Given:
+        let mut storage = Bipartite::init(DiMat::<usize>::init());
+        let a = storage.add_top_vertex();
+        let b = storage.add_vertex();
+        let c = storage.add_bottom_vertex();
+        storage.add_edge(c, a, 1.into()).unwrap();
+
+        // When:
+        let error = storage.add_edge(a, b, 1.into()).unwrap_err();
+        let batch = storage.extend_with_edges(vec![(b, c, 1.into()), (c, c, 1.into())]);
+
+        // Then:
+        assert!(matches!(
+            error.downcast_ref::<Error>().unwrap().kind(),
+            ErrorKind::IntraPartitionEdge
+        ));
+        assert!(batch.is_err());
+        assert_eq!(storage.edge_count(), 1);
+        assert_eq!(storage.top_vertices(), vec![a, b]);
+        assert_eq!(storage.bottom_vertices(), vec![c]);
+        assert_eq!(
+            storage.side_of(c).map(|side| side.opposite()),
+            Some(Side::Top)
+        );
+
+        // When: Removing a vertex.
+        storage.remove_vertex_unchecked(a);
+        storage.retain_vertices(|vertex_id| vertex_id != b);
+
+        // Then:
+        assert_eq!(storage.side_of(a), None);
+        assert!(storage.top_vertices().is_empty());
+        assert!(storage.add_edge(a, c, 1.into()).is_err());
+        assert_eq!(storage.edge_count(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn unchecked_intra_partition_edge_panics() {
+        let mut storage = Bipartite::init(DiMat::<usize>::init());
+        let a = storage.add_bottom_vertex();
+        let b = storage.add_bottom_vertex();
+
+        storage.add_edge_unchecked(a, b, 1.into());
+    }
+
+    #[test]
+    fn weighted_projection() {
+        // Given: Top vertices t0..t3, bottom vertices b0 and b1, and edges
+        // t0 - b0 (twice), t1 - b0, t1 - b1, t2 - b1, t0 - b1.
+        let mut storage = Bipartite::init(List::<usize>::init());
+        let top: Vec<_> = (0..4).map(|_| storage.add_top_vertex()).collect();
+        let bottom: Vec<_> = (0..2).map(|_| storage.add_bottom_vertex()).collect();
+        for (t, b) in [(0, 0), (0, 0), (1, 0), (1, 1), (2, 1), (0, 1)].iter() {
+            storage.add_edge_unchecked(top[*t], bottom[*b], 1.into());
+        }
+
+        // When:
+        let top_projection = storage.project_top();
+        let bottom_projection = storage.project_bottom();
+
+        // Then:
+        assert_eq!(top_projection.vertices(), top);
+        assert_eq!(top_projection.edges_count(), 3);
+        assert_eq!(
+            top_projection.edges_between_unchecked(top[0], top[1])[0].get_weight(),
+            &2.into()
+        );
+        assert_eq!(
+            top_projection.edges_between_unchecked(top[1], top[2])[0].get_weight(),
+            &1.into()
+        );
+        assert!(top_projection.neighbors_unchecked(top[3]).is_empty());
+        assert_eq!(bottom_projection.vertices(), bottom);
+        assert_eq!(
+            bottom_projection.edges_between_unchecked(bottom[0], bottom[1])[0].get_weight(),
+            &2.into()
+        );
+    }
+}
@@ -0,0 +1,210 @@
+use std::any::Any;
+use std::collections::{HashMap, HashSet};
+
+use crate::graph::{Edge, EdgeDir};
+use crate::storage::{AdjList, AdjMap, AdjMatrix, GraphStorage};
+
+/// Copies vertices and edges of `source` into `target`.
+///
+/// If `target` is empty, vertex ids of `source` are preserved, even if `source` has gaps in its ids left by removed vertices.
+/// Otherwise vertices get whatever ids `target` assigns to them and the relabeling is returned.
+/// Edges are copied as they are, but they get new ids from `target`.
+///
+/// # Arguments
+/// * `source`: Storage to copy from.
+/// * `target`: Storage to copy into.
+///
+/// # Returns
+/// * `None`: If every vertex has the same id in `target` as in `source`.
+/// * `Some`: Containing the id of each vertex of `source` in `target`, in the format of `source_id -> target_id`.
+///
+/// # Complexity
+/// O(|V| + |E|) plus the cost of adding the vertices and edges to `target`.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::{DiList, DiMat};
+/// use prepona::storage::convert::to_storage;
+///
+/// // Given: Storage with a removed vertex.
+/// let mut list = DiList::<usize>::init();
+/// let a = list.add_vertex();
+/// let b = list.add_vertex();
+/// let c = list.add_vertex();
+/// list.add_edge_unchecked(a, c, 5.into());
+/// list.remove_vertex_unchecked(b);
+///
+/// // When: Converting it to a matrix.
+/// let mut mat = DiMat::<usize>::init();
+/// let relabeling = to_storage(&list, &mut mat);
+///
+/// // Then: Ids are preserved.
+/// assert!(relabeling.is_none());
+/// assert!(!mat.contains_vertex(b));
+/// assert_eq!(mat.edges_between_unchecked(a, c)[0].get_weight(), &5.into());
+/// ```
+pub fn to_storage<W, E, Dir, S, T>(source: &S, target: &mut T) -> Option<HashMap<usize, usize>>
+where
+    E: Edge<W> + Copy,
+    Dir: EdgeDir,
+    S: GraphStorage<W, E, Dir>,
+    T: GraphStorage<W, E, Dir>,
+{
+    let vertices = source.vertices();
+
+    let target_id_of: HashMap<usize, usize> = if target.vertex_count() == 0 {
+        // Allocate every id up to the largest one and remove the ones that are not present in the source.
+        let present: HashSet<usize> = vertices.iter().copied().collect();
+        let end = vertices.iter().max().map_or(0, |max_id| max_id + 1);
+        let allocated: Vec<usize> = (0..end).map(|_| target.add_vertex()).collect();
+
+        for (source_id, target_id) in allocated.iter().enumerate() {
+            if !present.contains(&source_id) {
+                target.remove_vertex_unchecked(*target_id);
+            }
+        }
+
+        vertices.iter().map(|id| (*id, allocated[*id])).collect()
+    } else {
+        vertices
+            .iter()
+            .map(|id| (*id, target.add_vertex()))
+            .collect()
+    };
+
+    // Some storages report an undirected loop twice, so edges are deduplicated by id.
+    let mut copied = HashSet::new();
+    for (src_id, dst_id, edge) in source.edges() {
+        if copied.insert(edge.get_id()) {
+            target.add_edge_unchecked(target_id_of[&src_id], target_id_of[&dst_id], *edge);
+        }
+    }
+
+    if target_id_of
+        .iter()
+        .all(|(source_id, target_id)| source_id == target_id)
+    {
+        None
+    } else {
+        Some(target_id_of)
+    }
+}
+
+macro_rules! impl_from_storage {
+    ($source:ident => $target:ident) => {
+        /// Converts the storage while preserving its vertex ids.
+        impl<W, E, Dir> From<&$source<W, E, Dir>> for $target<W, E, Dir>
+        where
+            W: Copy + Any,
+            E: Edge<W> + Copy,
+            Dir: EdgeDir,
+        {
+            fn from(source: &$source<W, E, Dir>) -> Self {
+                let mut target = $target::init();
+                to_storage(source, &mut target);
+
+                target
+            }
+        }
+    };
+}
+
+impl_from_storage!(AdjList => AdjMap);
+impl_from_storage!(AdjList => AdjMatrix);
+impl_from_storage!(AdjMap => AdjList);
+impl_from_storage!(AdjMap => AdjMatrix);
+impl_from_storage!(AdjMatrix => AdjList);
+impl_from_storage!(AdjMatrix => AdjMap);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::DefaultEdge;
+    use crate::storage::{DiList, DiMap, DiMat, List, Map, Mat};
+
+    fn sorted_edges<W: Copy, E: Edge<W>, Dir: EdgeDir, S: GraphStorage<W, E, Dir>>(
+        storage: &S,
+    ) -> Vec<(usize, usize, W)> {
+        let mut edges: Vec<_> = storage
+            .as_directed_edges()
+            .into_iter()
+            .map(|(src_id, dst_id, edge)| (src_id, dst_id, *edge.get_weight().as_ref().unwrap()))
+            .collect();
+        edges.sort_unstable_by_key(|(src_id, dst_id, _)| (*src_id, *dst_id));
+        edges
+    }
+
+    #[test]
+    fn round_trip_between_all_storages() {
+        // Given: Directed storage with a loop and a removed vertex.
+        let mut list = DiList::<usize>::init();
+        let ids: Vec<_> = (0..5).map(|_| list.add_vertex()).collect();
+        list.add_edge_unchecked(ids[0], ids[1], 1.into());
+        list.add_edge_unchecked(ids[1], ids[3], 2.into());
+        list.add_edge_unchecked(ids[3], ids[3], 3.into());
+        list.add_edge_unchecked(ids[4], ids[0], 4.into());
+        list.remove_vertex_unchecked(ids[2]);
+
+        // When: Converting through every storage.
+        let map = DiMap::from(&list);
+        let mat = DiMat::from(&map);
+        let back = DiList::from(&mat);
+
+        // Then:
+        let expected = sorted_edges(&list);
+        assert_eq!(sorted_edges(&map), expected);
+        assert_eq!(sorted_edges(&mat), expected);
+        assert_eq!(sorted_edges(&back), expected);
+        let mut vertices = back.vertices();
+        vertices.sort_unstable();
+        assert_eq!(vertices, vec![0, 1, 3, 4]);
+    }
+
+    #[test]
+    fn undirected_loops_are_copied_once() {
+        // Given: Undirected storage with a loop.
+        let mut list = List::<usize>::init();
+        let a = list.add_vertex();
+        let b = list.add_vertex();
+        list.add_edge_unchecked(a, b, 1.into());
+        list.add_edge_unchecked(b, b, 2.into());
+
+        // When:
+        let mat = Mat::from(&list);
+        let map = Map::from(&mat);
+
+        // Then: Each storage reports the loop as a single edge id.
+        let distinct_ids = |edges: Vec<(usize, usize, &DefaultEdge<usize>)>| {
+            edges
+                .into_iter()
+                .map(|(_, _, edge)| edge.get_id())
+                .collect::<HashSet<_>>()
+                .len()
+        };
+        assert_eq!(distinct_ids(mat.edges()), 2);
+        assert_eq!(distinct_ids(map.edges()), 2);
+        assert!(mat.has_any_edge_unchecked(b, b));
+        assert!(map.has_any_edge_unchecked(a, b));
+    }
+
+    #[test]
+    fn non_empty_target_is_relabeled() {
+        // Given: Non empty target.
+        let mut source = DiMat::<usize>::init();
+        let a = source.add_vertex();
+        let b = source.add_vertex();
+        source.add_edge_unchecked(a, b, 7.into());
+        let mut target = DiMap::<usize>::init();
+        target.add_vertex();
+
+        // When:
+        let relabeling = to_storage(&source, &mut target).unwrap();
+
+        // Then:
+        assert_eq!(target.vertex_count(), 3);
+        assert_eq!(relabeling[&a], 1);
+        assert_eq!(relabeling[&b], 2);
+        assert!(target.has_any_edge_unchecked(relabeling[&a], relabeling[&b]));
+    }
+}
@@ -3,6 +3,9 @@ pub enum ErrorKind {
     VertexNotFound,
     EdgeNotFound,
     InvalidEdgeId,
+    IndexOverflow,
+    IntraPartitionEdge,
+    SelfLoop,
 }
 
 /// Error type returned by storages in `storage` module.
@@ -60,6 +63,52 @@ impl Error {
         }
     }
 
+    /// Creates an [`IndexOverflow`](crate::storage::ErrorKind::IndexOverflow) kind of error.
+    ///
+    /// # Arguments
+    /// `id`: Id that does not fit in [`Index`](crate::storage::Index).
+    ///
+    /// # Returns
+    /// `Error` with `IndexOverflow` kind and predefined msg.
+    pub fn new_io(id: usize) -> Self {
+        Error {
+            kind: ErrorKind::IndexOverflow,
+            msg: format!("Id: {} does not fit in the index type", id),
+        }
+    }
+
+    /// Creates an [`IntraPartitionEdge`](crate::storage::ErrorKind::IntraPartitionEdge) kind of error.
+    ///
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex, which is in the same partition as the source.
+    ///
+    /// # Returns
+    /// `Error` with `IntraPartitionEdge` kind and predefined msg.
+    pub fn new_ipe(src_id: usize, dst_id: usize) -> Self {
+        Error {
+            kind: ErrorKind::IntraPartitionEdge,
+            msg: format!(
+                "Vertex: {} and vertex: {} are in the same partition",
+                src_id, dst_id
+            ),
+        }
+    }
+
+    /// Creates a [`SelfLoop`](crate::storage::ErrorKind::SelfLoop) kind of error.
+    ///
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex that the rejected edge starts and ends at.
+    ///
+    /// # Returns
+    /// `Error` with `SelfLoop` kind and predefined msg.
+    pub fn new_sl(vertex_id: usize) -> Self {
+        Error {
+            kind: ErrorKind::SelfLoop,
+            msg: format!("Edge from vertex: {} to itself is a self loop", vertex_id),
+        }
+    }
+
     /// # Returns
     /// Cause of the error.
     pub fn msg(&self) -> &String {
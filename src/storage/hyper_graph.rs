@@ -0,0 +1,607 @@
+use std::any::Any;
+use std::collections::{HashMap, HashSet};
+
+use anyhow::Result;
+use magnitude::Magnitude;
+
+use crate::graph::{DefaultEdge, Edge, ListGraph, MultiListGraph, UndirectedEdge};
+use crate::provide::{Graph, Neighbors, Vertices};
+use crate::storage::{Error, List};
+
+/// An edge of a [`HyperGraph`](crate::storage::HyperGraph) that connects any number of vertices.
+///
+/// ## Generic Parameters
+/// * `W`: **W**eight type associated with the hyperedge.
+#[derive(Debug, Clone)]
+pub struct HyperEdge<W> {
+    id: usize,
+    vertices: Vec<usize>,
+    weight: Magnitude<W>,
+}
+
+impl<W> HyperEdge<W> {
+    /// # Returns
+    /// Id of the hyperedge.
+    pub fn get_id(&self) -> usize {
+        self.id
+    }
+
+    /// # Returns
+    /// Weight of the hyperedge.
+    pub fn get_weight(&self) -> &Magnitude<W> {
+        &self.weight
+    }
+
+    /// # Returns
+    /// Id of vertices that the hyperedge connects, each one only once.
+    pub fn vertices(&self) -> &[usize] {
+        &self.vertices
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// * `true`: If the hyperedge connects the vertex.
+    /// * `false`: Otherwise.
+    pub fn contains(&self, vertex_id: usize) -> bool {
+        self.vertices.contains(&vertex_id)
+    }
+}
+
+/// A storage for hypergraphs, where each edge(hyperedge) connects a set of vertices instead of a pair of them.
+///
+/// Each vertex keeps the ids of its hyperedges, so incidence queries take time proportional to the degree of the vertex.
+/// Like other storages, ids of removed vertices and hyperedges are reused.
+///
+/// Algorithms of this crate work on graphs, so a hypergraph can be converted to its [`clique expansion`](crate::storage::HyperGraph::clique_expansion)
+/// or its [`2-section`](crate::storage::HyperGraph::two_section). Both conversions keep the ids of vertices.
+/// `HyperGraph` also implements [`Vertices`](crate::provide::Vertices) and [`Neighbors`](crate::provide::Neighbors),
+/// where two vertices are neighbors if they share a hyperedge, so traversals can run on it directly.
+///
+/// ## Generic Parameters
+/// * `W`: **W**eight type associated with hyperedges.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::HyperGraph;
+///
+/// // Given: Hypergraph with hyperedges {a, b, c} and {c, d}.
+/// let mut hyper_graph = HyperGraph::<usize>::init();
+/// let a = hyper_graph.add_vertex();
+/// let b = hyper_graph.add_vertex();
+/// let c = hyper_graph.add_vertex();
+/// let d = hyper_graph.add_vertex();
+/// let abc = hyper_graph.add_hyperedge_unchecked(&[a, b, c], 1.into());
+/// hyper_graph.add_hyperedge_unchecked(&[c, d], 1.into());
+///
+/// // When:
+/// let graph = hyper_graph.two_section();
+///
+/// // Then:
+/// assert_eq!(hyper_graph.degree_unchecked(c), 2);
+/// assert_eq!(hyper_graph.hyperedge_unchecked(abc).vertices(), &[a, b, c]);
+/// assert_eq!(graph.edges_count(), 4);
+/// assert!(graph.has_any_edge_unchecked(a, c));
+/// assert!(!graph.has_any_edge_unchecked(a, d));
+/// ```
+pub struct HyperGraph<W> {
+    hyperedges_of: Vec<Vec<usize>>,
+    reusable_vertex_ids: HashSet<usize>,
+
+    hyperedges: Vec<Option<HyperEdge<W>>>,
+    reusable_hyperedge_ids: HashSet<usize>,
+
+    vertex_count: usize,
+}
+
+impl<W> HyperGraph<W> {
+    /// # Returns
+    /// An empty hypergraph.
+    pub fn init() -> Self {
+        HyperGraph {
+            hyperedges_of: vec![],
+            reusable_vertex_ids: HashSet::new(),
+
+            hyperedges: vec![],
+            reusable_hyperedge_ids: HashSet::new(),
+
+            vertex_count: 0,
+        }
+    }
+
+    fn next_reusable_id(reusable_ids: &mut HashSet<usize>) -> Option<usize> {
+        let id = reusable_ids.iter().next().copied();
+
+        if let Some(id) = id {
+            reusable_ids.remove(&id);
+        }
+
+        id
+    }
+
+    /// Adds a vertex to the hypergraph.
+    ///
+    /// # Returns
+    /// Id of the new vertex.
+    ///
+    /// # Complexity
+    /// O(1)
+    pub fn add_vertex(&mut self) -> usize {
+        self.vertex_count += 1;
+
+        if let Some(vertex_id) = Self::next_reusable_id(&mut self.reusable_vertex_ids) {
+            vertex_id
+        } else {
+            self.hyperedges_of.push(vec![]);
+
+            self.hyperedges_of.len() - 1
+        }
+    }
+
+    /// Removes a vertex from the hypergraph and from each hyperedge that connects it.
+    /// Hyperedges are kept even if they no longer connect any vertex.
+    ///
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex to be removed.
+    ///
+    /// # Returns
+    /// * `Err`: If there is no vertex with id: `vertex_id`.
+    /// * `Ok`: If the vertex is removed.
+    pub fn remove_vertex(&mut self, vertex_id: usize) -> Result<()> {
+        if !self.contains_vertex(vertex_id) {
+            Err(Error::new_vnf(vertex_id))?
+        } else {
+            self.remove_vertex_unchecked(vertex_id);
+
+            Ok(())
+        }
+    }
+
+    /// Removes a vertex from the hypergraph and from each hyperedge that connects it.
+    /// Hyperedges are kept even if they no longer connect any vertex.
+    ///
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex to be removed.
+    ///
+    /// # Complexity
+    /// O(sum of sizes of the hyperedges of the vertex)
+    ///
+    /// # Panics
+    /// If `vertex_id` is not in range 0..|V|.
+    pub fn remove_vertex_unchecked(&mut self, vertex_id: usize) {
+        for hyperedge_id in std::mem::take(&mut self.hyperedges_of[vertex_id]) {
+            if let Some(hyperedge) = &mut self.hyperedges[hyperedge_id] {
+                hyperedge.vertices.retain(|id| *id != vertex_id);
+            }
+        }
+
+        self.reusable_vertex_ids.insert(vertex_id);
+
+        self.vertex_count -= 1;
+    }
+
+    /// Adds a hyperedge that connects the given vertices.
+    ///
+    /// # Arguments
+    /// * `vertex_ids`: Id of the vertices to connect. Repeated ids are only connected once.
+    /// * `weight`: Weight of the hyperedge.
+    ///
+    /// # Returns
+    /// * `Err`: If any of the vertices does not exist.
+    /// * `Ok`: Containing id of the new hyperedge.
+    pub fn add_hyperedge(&mut self, vertex_ids: &[usize], weight: Magnitude<W>) -> Result<usize> {
+        if let Some(vertex_id) = vertex_ids.iter().find(|id| !self.contains_vertex(**id)) {
+            Err(Error::new_vnf(*vertex_id))?
+        } else {
+            Ok(self.add_hyperedge_unchecked(vertex_ids, weight))
+        }
+    }
+
+    /// Adds a hyperedge that connects the given vertices.
+    ///
+    /// # Arguments
+    /// * `vertex_ids`: Id of the vertices to connect. Repeated ids are only connected once.
+    /// * `weight`: Weight of the hyperedge.
+    ///
+    /// # Returns
+    /// Id of the new hyperedge.
+    ///
+    /// # Complexity
+    /// O(|`vertex_ids`|)
+    ///
+    /// # Panics
+    /// If any of `vertex_ids` is not in range 0..|V|.
+    pub fn add_hyperedge_unchecked(&mut self, vertex_ids: &[usize], weight: Magnitude<W>) -> usize {
+        let hyperedge_id = Self::next_reusable_id(&mut self.reusable_hyperedge_ids)
+            .unwrap_or(self.hyperedges.len());
+
+        let mut visited_ids = HashSet::new();
+        let vertices: Vec<usize> = vertex_ids
+            .iter()
+            .copied()
+            .filter(|vertex_id| visited_ids.insert(*vertex_id))
+            .collect();
+
+        for vertex_id in vertices.iter() {
+            self.hyperedges_of[*vertex_id].push(hyperedge_id);
+        }
+
+        let hyperedge = Some(HyperEdge {
+            id: hyperedge_id,
+            vertices,
+            weight,
+        });
+        if hyperedge_id == self.hyperedges.len() {
+            self.hyperedges.push(hyperedge);
+        } else {
+            self.hyperedges[hyperedge_id] = hyperedge;
+        }
+
+        hyperedge_id
+    }
+
+    /// # Arguments
+    /// `hyperedge_id`: Id of the hyperedge to be removed.
+    ///
+    /// # Returns
+    /// * `Err`: If there is no hyperedge with id: `hyperedge_id`.
+    /// * `Ok`: Containing the removed hyperedge.
+    pub fn remove_hyperedge(&mut self, hyperedge_id: usize) -> Result<HyperEdge<W>> {
+        if !self.contains_hyperedge(hyperedge_id) {
+            Err(Error::new_enf(hyperedge_id))?
+        } else {
+            Ok(self.remove_hyperedge_unchecked(hyperedge_id))
+        }
+    }
+
+    /// # Arguments
+    /// `hyperedge_id`: Id of the hyperedge to be removed.
+    ///
+    /// # Returns
+    /// The removed hyperedge.
+    ///
+    /// # Complexity
+    /// O(sum of degrees of the vertices that the hyperedge connects)
+    ///
+    /// # Panics
+    /// If there is no hyperedge with id: `hyperedge_id`.
+    pub fn remove_hyperedge_unchecked(&mut self, hyperedge_id: usize) -> HyperEdge<W> {
+        let hyperedge = self.hyperedges[hyperedge_id].take().unwrap();
+
+        for vertex_id in hyperedge.vertices.iter() {
+            self.hyperedges_of[*vertex_id].retain(|id| *id != hyperedge_id);
+        }
+
+        self.reusable_hyperedge_ids.insert(hyperedge_id);
+
+        hyperedge
+    }
+
+    /// # Arguments
+    /// `hyperedge_id`: Id of the hyperedge.
+    ///
+    /// # Returns
+    /// * `Err`: If there is no hyperedge with id: `hyperedge_id`.
+    /// * `Ok`: Containing reference to the hyperedge.
+    pub fn hyperedge(&self, hyperedge_id: usize) -> Result<&HyperEdge<W>> {
+        self.hyperedges
+            .get(hyperedge_id)
+            .and_then(|hyperedge| hyperedge.as_ref())
+            .ok_or_else(|| Error::new_enf(hyperedge_id).into())
+    }
+
+    /// # Arguments
+    /// `hyperedge_id`: Id of the hyperedge.
+    ///
+    /// # Returns
+    /// Reference to the hyperedge.
+    ///
+    /// # Panics
+    /// If there is no hyperedge with id: `hyperedge_id`.
+    pub fn hyperedge_unchecked(&self, hyperedge_id: usize) -> &HyperEdge<W> {
+        self.hyperedges[hyperedge_id].as_ref().unwrap()
+    }
+
+    /// # Returns
+    /// All hyperedges of the hypergraph.
+    pub fn hyperedges(&self) -> Vec<&HyperEdge<W>> {
+        self.hyperedges.iter().flatten().collect()
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// * `Err`: If there is no vertex with id: `vertex_id`.
+    /// * `Ok`: Containing hyperedges that connect the vertex.
+    pub fn hyperedges_of(&self, vertex_id: usize) -> Result<Vec<&HyperEdge<W>>> {
+        if !self.contains_vertex(vertex_id) {
+            Err(Error::new_vnf(vertex_id))?
+        } else {
+            Ok(self.hyperedges_of_unchecked(vertex_id))
+        }
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// Hyperedges that connect the vertex.
+    ///
+    /// # Complexity
+    /// O(degree of the vertex)
+    ///
+    /// # Panics
+    /// If `vertex_id` is not in range 0..|V|.
+    pub fn hyperedges_of_unchecked(&self, vertex_id: usize) -> Vec<&HyperEdge<W>> {
+        self.hyperedges_of[vertex_id]
+            .iter()
+            .map(|hyperedge_id| self.hyperedge_unchecked(*hyperedge_id))
+            .collect()
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// * `Err`: If there is no vertex with id: `vertex_id`.
+    /// * `Ok`: Containing number of hyperedges that connect the vertex.
+    pub fn degree(&self, vertex_id: usize) -> Result<usize> {
+        if !self.contains_vertex(vertex_id) {
+            Err(Error::new_vnf(vertex_id))?
+        } else {
+            Ok(self.degree_unchecked(vertex_id))
+        }
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// Number of hyperedges that connect the vertex.
+    ///
+    /// # Complexity
+    /// O(1)
+    ///
+    /// # Panics
+    /// If `vertex_id` is not in range 0..|V|.
+    pub fn degree_unchecked(&self, vertex_id: usize) -> usize {
+        self.hyperedges_of[vertex_id].len()
+    }
+
+    /// # Returns
+    /// Number of hyperedges in the hypergraph.
+    pub fn hyperedge_count(&self) -> usize {
+        self.hyperedges.len() - self.reusable_hyperedge_ids.len()
+    }
+
+    /// # Arguments
+    /// `hyperedge_id`: Id of the hyperedge.
+    ///
+    /// # Returns
+    /// * `true`: If hypergraph contains the hyperedge with id: `hyperedge_id`.
+    /// * `false`: Otherwise.
+    pub fn contains_hyperedge(&self, hyperedge_id: usize) -> bool {
+        hyperedge_id < self.hyperedges.len() && self.hyperedges[hyperedge_id].is_some()
+    }
+
+    // Adds the vertices of the hypergraph to `graph`, so that each vertex keeps its id.
+    fn add_vertices_to<G: Graph<V, DefaultEdge<V>, UndirectedEdge>, V>(&self, graph: &mut G) {
+        for _ in 0..self.hyperedges_of.len() {
+            graph.add_vertex();
+        }
+
+        for vertex_id in self.reusable_vertex_ids.iter() {
+            graph.remove_vertex_unchecked(*vertex_id);
+        }
+    }
+
+    /// Computes the 2-section of the hypergraph.
+    ///
+    /// # Returns
+    /// Graph with the same vertices as the hypergraph, where two vertices are connected if they share a hyperedge.
+    /// Weight of each edge is the number of hyperedges that its end points share.
+    ///
+    /// # Complexity
+    /// O(|V| + sum of squared sizes of the hyperedges)
+    pub fn two_section(&self) -> ListGraph<usize, UndirectedEdge> {
+        let mut shared_counts = HashMap::new();
+        for hyperedge in self.hyperedges() {
+            for (index, src_id) in hyperedge.vertices.iter().enumerate() {
+                for dst_id in hyperedge.vertices[index + 1..].iter() {
+                    let key = (*src_id.min(dst_id), *src_id.max(dst_id));
+
+                    *shared_counts.entry(key).or_insert(0) += 1;
+                }
+            }
+        }
+
+        let mut graph = ListGraph::init(List::init());
+        self.add_vertices_to(&mut graph);
+
+        for ((src_id, dst_id), count) in shared_counts {
+            graph.add_edge_unchecked(src_id, dst_id, count.into());
+        }
+
+        graph
+    }
+}
+
+impl<W: Any + Copy> HyperGraph<W> {
+    /// Replaces each hyperedge with a clique between the vertices that it connects.
+    ///
+    /// # Returns
+    /// Multigraph with the same vertices as the hypergraph, with one edge between each pair of vertices of each hyperedge.
+    /// Each edge has the weight of the hyperedge that it comes from, so vertices sharing multiple hyperedges are connected by parallel edges.
+    ///
+    /// # Complexity
+    /// O(|V| + sum of squared sizes of the hyperedges)
+    pub fn clique_expansion(&self) -> MultiListGraph<W, UndirectedEdge> {
+        let mut graph = MultiListGraph::init(List::init());
+        self.add_vertices_to(&mut graph);
+
+        for hyperedge in self.hyperedges() {
+            for (index, src_id) in hyperedge.vertices.iter().enumerate() {
+                for dst_id in hyperedge.vertices[index + 1..].iter() {
+                    graph.add_edge_unchecked(*src_id, *dst_id, DefaultEdge::init(hyperedge.weight));
+                }
+            }
+        }
+
+        graph
+    }
+}
+
+impl<W> Default for HyperGraph<W> {
+    fn default() -> Self {
+        HyperGraph::init()
+    }
+}
+
+/// For documentation about each function checkout [`Neighbors`](crate::provide::Neighbors) trait.
+///
+/// Two vertices are neighbors if they share a hyperedge. A vertex is never its own neighbor.
+impl<W> Neighbors for HyperGraph<W> {
+    fn neighbors(&self, src_id: usize) -> Result<Vec<usize>> {
+        if !self.contains_vertex(src_id) {
+            Err(Error::new_vnf(src_id))?
+        } else {
+            Ok(self.neighbors_unchecked(src_id))
+        }
+    }
+
+    fn neighbors_unchecked(&self, src_id: usize) -> Vec<usize> {
+        let mut visited_ids = HashSet::new();
+        visited_ids.insert(src_id);
+
+        self.hyperedges_of_unchecked(src_id)
+            .into_iter()
+            .flat_map(|hyperedge| hyperedge.vertices.iter().copied())
+            .filter(|dst_id| visited_ids.insert(*dst_id))
+            .collect()
+    }
+}
+
+/// For documentation about each function checkout [`Vertices`](crate::provide::Vertices) trait.
+impl<W> Vertices for HyperGraph<W> {
+    fn vertices(&self) -> Vec<usize> {
+        (0..self.hyperedges_of.len())
+            .filter(|vertex_id| !self.reusable_vertex_ids.contains(vertex_id))
+            .collect()
+    }
+
+    fn vertex_count(&self) -> usize {
+        self.vertex_count
+    }
+
+    fn contains_vertex(&self, vertex_id: usize) -> bool {
+        vertex_id < self.hyperedges_of.len() && !self.reusable_vertex_ids.contains(&vertex_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::{BitAdj, ConnectedComponents};
+    use crate::provide::Edges;
+
+    #[test]
+    fn add_and_remove() {
+        // Given: Hypergraph with hyperedges {a, b, c} and {b, c}.
+        let mut hyper_graph = HyperGraph::<usize>::init();
+        let a = hyper_graph.add_vertex();
+        let b = hyper_graph.add_vertex();
+        let c = hyper_graph.add_vertex();
+        let abc = hyper_graph.add_hyperedge(&[a, b, c, a], 2.into()).unwrap();
+        let bc = hyper_graph.add_hyperedge(&[b, c], 1.into()).unwrap();
+
+        // When:
+        hyper_graph.remove_vertex_unchecked(b);
+        let removed = hyper_graph.remove_hyperedge(bc).unwrap();
+
+        // Then:
+        assert_eq!(removed.vertices(), &[c]);
+        assert_eq!(hyper_graph.hyperedge_count(), 1);
+        assert_eq!(hyper_graph.hyperedge_unchecked(abc).vertices(), &[a, c]);
+        assert_eq!(hyper_graph.hyperedge_unchecked(abc).get_weight(), &2.into());
+        assert_eq!(hyper_graph.degree_unchecked(c), 1);
+        assert_eq!(hyper_graph.vertices(), vec![a, c]);
+        assert!(hyper_graph.remove_hyperedge(bc).is_err());
+        assert!(hyper_graph.add_hyperedge(&[a, b], 1.into()).is_err());
+        assert!(hyper_graph.hyperedges_of(b).is_err());
+
+        // When: Adding again.
+        let d = hyper_graph.add_vertex();
+        let cd = hyper_graph.add_hyperedge_unchecked(&[c, d], 1.into());
+
+        // Then: Ids are reused.
+        assert_eq!(d, b);
+        assert_eq!(cd, bc);
+        assert_eq!(
+            hyper_graph
+                .hyperedges_of_unchecked(c)
+                .into_iter()
+                .map(|hyperedge| hyperedge.get_id())
+                .collect::<Vec<_>>(),
+            vec![abc, cd]
+        );
+    }
+
+    #[test]
+    fn expansions_keep_vertex_ids() {
+        // Given: Hypergraph with hyperedges {a, c, d}, {c, d} and a removed vertex b.
+        let mut hyper_graph = HyperGraph::<usize>::init();
+        let a = hyper_graph.add_vertex();
+        let b = hyper_graph.add_vertex();
+        let c = hyper_graph.add_vertex();
+        let d = hyper_graph.add_vertex();
+        hyper_graph.add_hyperedge_unchecked(&[a, c, d], 3.into());
+        hyper_graph.add_hyperedge_unchecked(&[c, d], 5.into());
+        hyper_graph.remove_vertex_unchecked(b);
+
+        // When:
+        let clique_expansion = hyper_graph.clique_expansion();
+        let two_section = hyper_graph.two_section();
+
+        // Then:
+        assert_eq!(clique_expansion.vertices(), vec![a, c, d]);
+        assert_eq!(clique_expansion.edges_count(), 4);
+        assert_eq!(clique_expansion.edges_between_unchecked(c, d).len(), 2);
+        assert_eq!(two_section.vertices(), vec![a, c, d]);
+        assert_eq!(two_section.edges_count(), 3);
+        assert_eq!(
+            two_section.edges_between_unchecked(c, d)[0].get_weight(),
+            &2.into()
+        );
+        assert_eq!(
+            two_section.edges_between_unchecked(a, c)[0].get_weight(),
+            &1.into()
+        );
+    }
+
+    #[test]
+    fn neighbors_follow_shared_hyperedges() {
+        // Given: Hypergraph with hyperedges {a, b, c}, {c, d} and an isolated vertex e.
+        let mut hyper_graph = HyperGraph::<usize>::init();
+        let vertices: Vec<_> = (0..5).map(|_| hyper_graph.add_vertex()).collect();
+        hyper_graph.add_hyperedge_unchecked(&vertices[0..3], 1.into());
+        hyper_graph.add_hyperedge_unchecked(&vertices[2..4], 1.into());
+
+        // When:
+        let mut neighbors = hyper_graph.neighbors_unchecked(vertices[2]);
+        neighbors.sort_unstable();
+
+        // Then:
+        assert_eq!(neighbors, vec![vertices[0], vertices[1], vertices[3]]);
+        assert_eq!(BitAdj::init(&hyper_graph).triangle_count(), 1);
+        let two_section = hyper_graph.two_section();
+        assert_eq!(
+            ConnectedComponents::init(&two_section)
+                .execute(&two_section)
+                .len(),
+            2
+        );
+    }
+}
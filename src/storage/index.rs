@@ -0,0 +1,90 @@
+use std::convert::TryFrom;
+
+use anyhow::Result;
+
+use crate::storage::Error;
+
+/// Type used by storages and [`IdMap`](crate::provide::IdMap) to store ids of vertices internally.
+///
+/// It's `usize` by default. Enabling `u32-index` feature makes it `u32`,
+/// which halves the memory used by adjacency lists and id maps on 64-bit platforms, at the cost of limiting the number of vertices to `u32::MAX`.
+/// Public functions always receive and return ids as `usize`, so the feature does not change the api.
+#[cfg(not(feature = "u32-index"))]
+pub type Index = usize;
+
+/// Type used by storages and [`IdMap`](crate::provide::IdMap) to store ids of vertices internally.
+///
+/// It's `usize` by default. Enabling `u32-index` feature makes it `u32`,
+/// which halves the memory used by adjacency lists and id maps on 64-bit platforms, at the cost of limiting the number of vertices to `u32::MAX`.
+/// Public functions always receive and return ids as `usize`, so the feature does not change the api.
+#[cfg(feature = "u32-index")]
+pub type Index = u32;
+
+/// # Arguments
+/// `id`: Id to convert.
+///
+/// # Returns
+/// * `Err`: If `id` does not fit in [`Index`](crate::storage::Index).
+/// * `Ok`: Containing `id` as an [`Index`](crate::storage::Index).
+///
+/// # Examples
+/// ```
+/// use prepona::storage::{to_index, from_index};
+///
+/// assert_eq!(from_index(to_index(42).unwrap()), 42);
+/// ```
+#[allow(clippy::useless_conversion)]
+pub fn to_index(id: usize) -> Result<Index> {
+    Ok(Index::try_from(id).map_err(|_| Error::new_io(id))?)
+}
+
+/// Converts `id` to [`Index`](crate::storage::Index).
+///
+/// # Arguments
+/// `id`: Id to convert.
+///
+/// # Returns
+/// `id` as an [`Index`](crate::storage::Index).
+///
+/// # Panics
+/// If `id` does not fit in [`Index`](crate::storage::Index).
+pub fn to_index_unchecked(id: usize) -> Index {
+    to_index(id).unwrap()
+}
+
+/// # Arguments
+/// `index`: Index to convert.
+///
+/// # Returns
+/// `index` as a `usize` id.
+#[allow(clippy::useless_conversion)]
+pub fn from_index(index: Index) -> usize {
+    // Index is never wider than usize on supported platforms.
+    usize::try_from(index).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        // Given:
+        let ids = [0, 1, 1024, u32::MAX as usize];
+
+        // Then:
+        assert!(ids
+            .iter()
+            .all(|id| from_index(to_index_unchecked(*id)) == *id));
+    }
+
+    #[cfg(all(feature = "u32-index", target_pointer_width = "64"))]
+    #[test]
+    fn overflow_is_reported() {
+        // When: Converting an id that does not fit in 32 bits.
+        let result = to_index(u32::MAX as usize + 1);
+
+        // Then:
+        assert!(result.is_err());
+    }
+}
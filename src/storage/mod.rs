@@ -1,16 +1,30 @@
 mod adj_list;
 mod adj_map;
 mod adj_matrix;
+mod bipartite;
 mod error;
+mod hyper_graph;
+mod index;
+mod observed;
+mod self_loop_guard;
+
+/// Converting between storages.
+pub mod convert;
 
 pub use adj_list::{AdjList, DiFlowList, DiList, FlowList, List};
-pub use adj_map::{AdjMap, DiFlowMap, DiMap, FlowMap, Map};
-pub use adj_matrix::{AdjMatrix, DiFlowMat, DiMat, FlowMat, Mat};
+pub use adj_map::{AdjMap, DiFlowMap, DiMap, FlowMap, Map, MapEdgesFrom};
+pub use adj_matrix::{AdjMatrix, DiFlowMat, DiMat, FlowMat, Mat, MatEdgesFrom, MatNeighbors};
+pub use bipartite::{Bipartite, Side};
 pub use error::{Error, ErrorKind};
+pub use hyper_graph::{HyperEdge, HyperGraph};
+pub use index::{from_index, to_index, to_index_unchecked, Index};
+pub use observed::{GraphObserver, ObservedStorage, StorageEvent};
+pub use self_loop_guard::{SelfLoopGuard, SelfLoopPolicy};
 
 use crate::graph::{Edge, EdgeDir};
 
 use anyhow::Result;
+use std::collections::HashSet;
 
 /// Defines the api that a storage must provide in order to be usable for storing graph data.
 ///
@@ -38,12 +52,32 @@ use anyhow::Result;
 /// * `E`: **E**dge type that graph uses.
 /// * `Dir`: **Dir**ection of edges: [`Directed`](crate::graph::DirectedEdge) or [`Undirected`](crate::graph::UndirectedEdge).
 pub trait GraphStorage<W, E: Edge<W>, Dir: EdgeDir> {
+    /// Iterator over outgoing edges of a vertex, returned by [`edges_from_iter`](crate::storage::GraphStorage::edges_from_iter).
+    type EdgesFromIter<'a>: Iterator<Item = (usize, &'a E)>
+    where
+        Self: 'a,
+        E: 'a;
+
+    /// Iterator over neighbors of a vertex, returned by [`neighbors_iter`](crate::storage::GraphStorage::neighbors_iter).
+    type NeighborsIter<'a>: Iterator<Item = usize>
+    where
+        Self: 'a;
+
     /// Adds a vertex to the storage.
     ///
     /// # Returns
     /// Unique id of the newly added vertex.
     fn add_vertex(&mut self) -> usize;
 
+    /// Adds a vertex to the storage without panicking.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing unique id of the newly added vertex.
+    /// * `Err`: [`IndexOverflow`](crate::storage::ErrorKind::IndexOverflow) if id of the new vertex does not fit in [`Index`](crate::storage::Index).
+    fn try_add_vertex(&mut self) -> Result<usize> {
+        Ok(self.add_vertex())
+    }
+
     /// Removes the vertex with id: `vertex_id` from storage.
     ///
     /// # Arguments
@@ -66,6 +100,25 @@ pub trait GraphStorage<W, E: Edge<W>, Dir: EdgeDir> {
     /// `vertex_id`: Id of the vertex to be removed.
     fn remove_vertex_unchecked(&mut self, vertex_id: usize);
 
+    /// Removes every vertex for which `keep` returns `false`, along with its edges.
+    ///
+    /// # Arguments
+    /// `keep`: Receives id of each vertex and returns `true` if the vertex must be kept.
+    fn retain_vertices<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(usize) -> bool,
+    {
+        let removed_ids: Vec<usize> = self
+            .vertices()
+            .into_iter()
+            .filter(|vertex_id| !keep(*vertex_id))
+            .collect();
+
+        for vertex_id in removed_ids {
+            self.remove_vertex_unchecked(vertex_id);
+        }
+    }
+
     /// # Arguments
     /// `vertex_id`: Id of the vertex.
     ///
@@ -105,6 +158,61 @@ pub trait GraphStorage<W, E: Edge<W>, Dir: EdgeDir> {
     /// Unique id of the newly added edge.
     fn add_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge: E) -> usize;
 
+    /// Adds `count` vertices to the storage.
+    ///
+    /// # Arguments
+    /// `count`: Number of vertices to add.
+    ///
+    /// # Returns
+    /// Unique ids of the newly added vertices.
+    fn extend_with_vertices(&mut self, count: usize) -> Vec<usize> {
+        (0..count).map(|_| self.add_vertex()).collect()
+    }
+
+    /// Adds edges to the storage in one go.
+    ///
+    /// End points of all edges are checked before adding any of them, so either all edges are added or none of them.
+    ///
+    /// # Arguments
+    /// `edges`: Edges to add in the format of (`src_id`, `dst_id`, `edge`).
+    ///
+    /// # Returns
+    /// * `Ok`: Containing unique ids of the newly added edges, in the same order as `edges`.
+    /// * `Err`: [`VertexNotFound`](crate::storage::ErrorKind::VertexNotFound) if any of the end points does not exist.
+    fn extend_with_edges<I>(&mut self, edges: I) -> Result<Vec<usize>>
+    where
+        I: IntoIterator<Item = (usize, usize, E)>,
+    {
+        let edges: Vec<(usize, usize, E)> = edges.into_iter().collect();
+
+        for (src_id, dst_id, _) in &edges {
+            if !self.contains_vertex(*src_id) {
+                Err(Error::new_vnf(*src_id))?
+            } else if !self.contains_vertex(*dst_id) {
+                Err(Error::new_vnf(*dst_id))?
+            }
+        }
+
+        Ok(self.extend_with_edges_unchecked(edges))
+    }
+
+    /// Adds edges to the storage in one go.
+    ///
+    /// # Arguments
+    /// `edges`: Edges to add in the format of (`src_id`, `dst_id`, `edge`).
+    ///
+    /// # Returns
+    /// Unique ids of the newly added edges, in the same order as `edges`.
+    fn extend_with_edges_unchecked<I>(&mut self, edges: I) -> Vec<usize>
+    where
+        I: IntoIterator<Item = (usize, usize, E)>,
+    {
+        edges
+            .into_iter()
+            .map(|(src_id, dst_id, edge)| self.add_edge_unchecked(src_id, dst_id, edge))
+            .collect()
+    }
+
     /// Replaces the edge with id: `edge_id` with `edge`.
     ///
     /// # Arguments
@@ -117,16 +225,14 @@ pub trait GraphStorage<W, E: Edge<W>, Dir: EdgeDir> {
     /// * `Ok`: If edge updated successfully.
     /// * `Err`: [`VertexNotFound`](crate::storage::ErrorKind::VertexNotFound) if vertex with either id: `src_id` or `dst_id` does not exist.
     /// * `Err`: [`EdgeNotFound`](crate::storage::ErrorKind::EdgeNotFound) if edge with specified id does not exist.
+    /// * `Err`: [`InvalidEdgeId`](crate::storage::ErrorKind::InvalidEdgeId) if edge with specified id does exist but it's not from source to destination.
     fn update_edge(&mut self, src_id: usize, dst_id: usize, edge_id: usize, edge: E) -> Result<()> {
-        if !self.contains_vertex(src_id) {
-            Err(Error::new_vnf(src_id))?
-        } else if !self.contains_vertex(dst_id) {
-            Err(Error::new_vnf(dst_id))?
-        } else if !self.contains_edge(edge_id) {
-            Err(Error::new_enf(edge_id))?
-        } else {
-            Ok(self.update_edge_unchecked(src_id, dst_id, edge_id, edge))
-        }
+        // Makes sure the edge exists and goes from source to destination.
+        self.edge_between(src_id, dst_id, edge_id)?;
+
+        self.update_edge_unchecked(src_id, dst_id, edge_id, edge);
+
+        Ok(())
     }
 
     /// Replaces the edge with id: `edge_id` with `edge`.
@@ -155,16 +261,12 @@ pub trait GraphStorage<W, E: Edge<W>, Dir: EdgeDir> {
     /// * `Ok`: Containing the removed edge.
     /// * `Err`: [`VertexNotFound`](crate::storage::ErrorKind::VertexNotFound) if vertex with either id: `src_id` or `dst_id` does not exist.
     /// * `Err`: [`EdgeNotFound`](crate::storage::ErrorKind::EdgeNotFound) if edge with specified id does not exist.
+    /// * `Err`: [`InvalidEdgeId`](crate::storage::ErrorKind::InvalidEdgeId) if edge with specified id does exist but it's not from source to destination.
     fn remove_edge(&mut self, src_id: usize, dst_id: usize, edge_id: usize) -> Result<E> {
-        if !self.contains_vertex(src_id) {
-            Err(Error::new_vnf(src_id))?
-        } else if !self.contains_vertex(dst_id) {
-            Err(Error::new_vnf(dst_id))?
-        } else if !self.contains_edge(edge_id) {
-            Err(Error::new_enf(edge_id))?
-        } else {
-            Ok(self.remove_edge_unchecked(src_id, dst_id, edge_id))
-        }
+        // Makes sure the edge exists and goes from source to destination.
+        self.edge_between(src_id, dst_id, edge_id)?;
+
+        Ok(self.remove_edge_unchecked(src_id, dst_id, edge_id))
     }
 
     /// Removes the edge with id: `edge_id`.
@@ -178,6 +280,30 @@ pub trait GraphStorage<W, E: Edge<W>, Dir: EdgeDir> {
     /// The removed edge.
     fn remove_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge_id: usize) -> E;
 
+    /// Removes every edge for which `keep` returns `false`.
+    ///
+    /// `keep` is called once for each edge. For undirected edges, end points are passed in the same order as [`edges`](crate::storage::GraphStorage::edges) returns them.
+    ///
+    /// # Arguments
+    /// `keep`: Receives (`src_id`, `dst_id`, `edge`) of each edge and returns `true` if the edge must be kept.
+    fn retain_edges<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(usize, usize, &E) -> bool,
+    {
+        let mut visited_ids = HashSet::new();
+        let removed_edges: Vec<(usize, usize, usize)> = self
+            .edges()
+            .into_iter()
+            .filter(|(_, _, edge)| visited_ids.insert(edge.get_id()))
+            .filter(|(src_id, dst_id, edge)| !keep(*src_id, *dst_id, edge))
+            .map(|(src_id, dst_id, edge)| (src_id, dst_id, edge.get_id()))
+            .collect();
+
+        for (src_id, dst_id, edge_id) in removed_edges {
+            self.remove_edge_unchecked(src_id, dst_id, edge_id);
+        }
+    }
+
     /// # Arguments
     /// `edge_id`: Id of the edge.
     ///
@@ -339,6 +465,32 @@ pub trait GraphStorage<W, E: Edge<W>, Dir: EdgeDir> {
         !self.edges_between_unchecked(src_id, dst_id).is_empty()
     }
 
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// `true` if there is an edge from the vertex to itself, `false` otherwise or if the vertex does not exist.
+    fn has_self_loop(&self, vertex_id: usize) -> bool {
+        self.has_any_edge(vertex_id, vertex_id).unwrap_or(false)
+    }
+
+    /// # Returns
+    /// All self loops in the storage in the format: (`vertex_id`, `edge`).
+    /// Each loop is reported once, even in undirected storages that keep a loop in the edge list of its vertex twice.
+    fn self_loops(&self) -> Vec<(usize, &E)> {
+        let mut seen_ids = HashSet::new();
+
+        self.vertices()
+            .into_iter()
+            .flat_map(|vertex_id| {
+                self.edges_between_unchecked(vertex_id, vertex_id)
+                    .into_iter()
+                    .map(move |edge| (vertex_id, edge))
+            })
+            .filter(|(_, edge)| seen_ids.insert(edge.get_id()))
+            .collect()
+    }
+
     /// # Returns
     /// All edges in the storage in the format: (`src_id`, `dst_id`, `edge`).
     fn edges(&self) -> Vec<(usize, usize, &E)> {
@@ -435,6 +587,26 @@ pub trait GraphStorage<W, E: Edge<W>, Dir: EdgeDir> {
             .collect()
     }
 
+    /// Same as [`edges_from_unchecked`](crate::storage::GraphStorage::edges_from_unchecked),
+    /// but edges are visited one by one instead of being collected into a vector.
+    ///
+    /// # Arguments
+    /// `src_id`: Id of the source vertex.
+    ///
+    /// # Returns
+    /// Iterator over edges from the source vertex in the format of: (`dst_id`, `edge`).
+    fn edges_from_iter(&self, src_id: usize) -> Self::EdgesFromIter<'_>;
+
+    /// Same as [`neighbors_unchecked`](crate::storage::GraphStorage::neighbors_unchecked),
+    /// but neighbors are visited one by one instead of being collected into a vector.
+    ///
+    /// # Arguments
+    /// `src_id`: Id of the source vertex.
+    ///
+    /// # Returns
+    /// Iterator over id of vertices accessible from source vertex using one edge.
+    fn neighbors_iter(&self, src_id: usize) -> Self::NeighborsIter<'_>;
+
     /// # Returns
     /// * `true`: If storage is being used to store directed edges.
     /// * 'false`: Otherwise.
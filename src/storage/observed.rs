@@ -0,0 +1,477 @@
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+use anyhow::Result;
+
+use crate::graph::{Edge, EdgeDir};
+use crate::storage::GraphStorage;
+
+/// A change made to an [`ObservedStorage`](crate::storage::ObservedStorage).
+///
+/// ## Generic Parameters
+/// * `E`: **E**dge type that storage uses.
+pub enum StorageEvent<'a, E> {
+    /// A vertex with the given id is added.
+    VertexAdded(usize),
+
+    /// A vertex with the given id is removed. Removal of each of its edges is reported before this event.
+    VertexRemoved(usize),
+
+    /// `edge` is added from `src_id` to `dst_id`.
+    EdgeAdded {
+        src_id: usize,
+        dst_id: usize,
+        edge: &'a E,
+    },
+
+    /// `edge` is removed from between `src_id` and `dst_id`.
+    EdgeRemoved {
+        src_id: usize,
+        dst_id: usize,
+        edge: &'a E,
+    },
+}
+
+/// Receives changes made to an [`ObservedStorage`](crate::storage::ObservedStorage).
+///
+/// It's implemented for closures that receive a `&StorageEvent`, and for [`DynamicSSSP`](crate::algo::DynamicSSSP).
+///
+/// ## Generic Parameters
+/// * `W`: **W**eight type associated with edges.
+/// * `E`: **E**dge type that storage uses.
+pub trait GraphObserver<W, E: Edge<W>> {
+    /// Is called after each change to the storage.
+    ///
+    /// # Arguments
+    /// `event`: The change.
+    fn notify(&mut self, event: &StorageEvent<E>);
+}
+
+impl<W, E: Edge<W>, F: FnMut(&StorageEvent<E>)> GraphObserver<W, E> for F {
+    fn notify(&mut self, event: &StorageEvent<E>) {
+        self(event)
+    }
+}
+
+/// A storage that wraps another storage and reports every change made to it to its observers.
+///
+/// It's useful for keeping caches like degree tables, connectivity information or shortest paths up to date
+/// without rebuilding them after each change. Observers are shared using `Rc<RefCell<_>>`, so they can still be queried after subscribing.
+/// Bulk operations like [`retain_vertices`](crate::storage::GraphStorage::retain_vertices) and [`extend_with_edges`](crate::storage::GraphStorage::extend_with_edges)
+/// are carried out one change at a time, so each change is reported.
+///
+/// ## Generic Parameters
+/// * `W`: **W**eight type associated with edges.
+/// * `E`: **E**dge type that storage uses.
+/// * `Dir`: **Dir**ection of edges: [`Directed`](crate::graph::DirectedEdge) or [`Undirected`](crate::graph::UndirectedEdge).
+/// * `S`: **S**torage to observe.
+///
+/// # Examples
+/// ```
+/// use std::rc::Rc;
+/// use std::cell::RefCell;
+/// use prepona::prelude::*;
+/// use prepona::storage::{DiList, ObservedStorage, StorageEvent};
+/// use prepona::graph::SimpleGraph;
+///
+/// // Given: Graph with an observer that counts the edges of each vertex.
+/// let degrees = Rc::new(RefCell::new(std::collections::HashMap::new()));
+/// let observer = {
+///     let degrees = degrees.clone();
+///     Rc::new(RefCell::new(move |event: &StorageEvent<DefaultEdge<usize>>| {
+///         let mut degrees = degrees.borrow_mut();
+///         match event {
+///             StorageEvent::EdgeAdded { src_id, .. } => *degrees.entry(*src_id).or_insert(0) += 1,
+///             StorageEvent::EdgeRemoved { src_id, .. } => *degrees.get_mut(src_id).unwrap() -= 1,
+///             _ => {}
+///         }
+///     }))
+/// };
+/// let mut graph = SimpleGraph::init(ObservedStorage::init(DiList::<usize>::init()));
+/// graph.subscribe(observer);
+///
+/// // When:
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(a, c, 1.into());
+/// graph.remove_vertex_unchecked(b);
+///
+/// // Then:
+/// assert_eq!(degrees.borrow()[&a], 1);
+/// ```
+pub struct ObservedStorage<W, E: Edge<W>, Dir: EdgeDir, S> {
+    storage: S,
+
+    observers: Vec<Rc<RefCell<dyn GraphObserver<W, E>>>>,
+
+    phantom_dir: PhantomData<Dir>,
+}
+
+impl<W, E: Edge<W>, Dir: EdgeDir, S: GraphStorage<W, E, Dir>> ObservedStorage<W, E, Dir, S> {
+    /// # Arguments
+    /// `storage`: Storage to observe.
+    ///
+    /// # Returns
+    /// Observed storage with no observers.
+    pub fn init(storage: S) -> Self {
+        ObservedStorage {
+            storage,
+
+            observers: vec![],
+
+            phantom_dir: PhantomData,
+        }
+    }
+
+    /// Adds an observer that gets notified of all subsequent changes.
+    ///
+    /// # Arguments
+    /// `observer`: Observer to add.
+    pub fn subscribe<O>(&mut self, observer: Rc<RefCell<O>>)
+    where
+        O: GraphObserver<W, E> + 'static,
+    {
+        self.observers.push(observer);
+    }
+
+    /// # Returns
+    /// Number of observers.
+    pub fn observer_count(&self) -> usize {
+        self.observers.len()
+    }
+
+    /// # Returns
+    /// The observed storage.
+    pub fn storage(&self) -> &S {
+        &self.storage
+    }
+
+    /// # Returns
+    /// The observed storage, dropping the observers.
+    pub fn into_storage(self) -> S {
+        self.storage
+    }
+
+    fn emit(&self, event: StorageEvent<E>) {
+        for observer in &self.observers {
+            observer.borrow_mut().notify(&event);
+        }
+    }
+
+    // # Returns
+    // Edges that start or end at the vertex, each one only once.
+    //
+    // # Complexity
+    // * Undirected: O(|E<sub>vertex</sub>|)
+    // * Directed: O(|V| + |E|) since incoming edges must be searched for.
+    fn incident_edges(&self, vertex_id: usize) -> Vec<(usize, usize, &E)> {
+        let mut incident: Vec<(usize, usize, &E)> = self
+            .storage
+            .edges_from_unchecked(vertex_id)
+            .into_iter()
+            .map(|(dst_id, edge)| (vertex_id, dst_id, edge))
+            .collect();
+
+        if Dir::is_directed() {
+            for src_id in self.storage.vertices() {
+                if src_id != vertex_id {
+                    incident.extend(
+                        self.storage
+                            .edges_between_unchecked(src_id, vertex_id)
+                            .into_iter()
+                            .map(|edge| (src_id, vertex_id, edge)),
+                    );
+                }
+            }
+        }
+
+        // Some storages report undirected loops twice.
+        let mut visited_ids = HashSet::new();
+        incident.retain(|(_, _, edge)| visited_ids.insert(edge.get_id()));
+
+        incident
+    }
+}
+
+/// For documentation about each function checkout [`GraphStorage`](crate::storage::GraphStorage) trait and the storage you observe.
+impl<W, E: Edge<W>, Dir: EdgeDir, S: GraphStorage<W, E, Dir>> GraphStorage<W, E, Dir>
+    for ObservedStorage<W, E, Dir, S>
+{
+    type EdgesFromIter<'a>
+        = S::EdgesFromIter<'a>
+    where
+        Self: 'a,
+        E: 'a;
+
+    type NeighborsIter<'a>
+        = S::NeighborsIter<'a>
+    where
+        Self: 'a;
+
+    fn add_vertex(&mut self) -> usize {
+        let vertex_id = self.storage.add_vertex();
+
+        self.emit(StorageEvent::VertexAdded(vertex_id));
+
+        vertex_id
+    }
+
+    fn try_add_vertex(&mut self) -> Result<usize> {
+        let vertex_id = self.storage.try_add_vertex()?;
+
+        self.emit(StorageEvent::VertexAdded(vertex_id));
+
+        Ok(vertex_id)
+    }
+
+    fn remove_vertex_unchecked(&mut self, vertex_id: usize) {
+        for (src_id, dst_id, edge) in self.incident_edges(vertex_id) {
+            self.emit(StorageEvent::EdgeRemoved {
+                src_id,
+                dst_id,
+                edge,
+            });
+        }
+
+        self.storage.remove_vertex_unchecked(vertex_id);
+
+        self.emit(StorageEvent::VertexRemoved(vertex_id));
+    }
+
+    fn contains_vertex(&self, vertex_id: usize) -> bool {
+        self.storage.contains_vertex(vertex_id)
+    }
+
+    fn add_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge: E) -> usize {
+        let edge_id = self.storage.add_edge_unchecked(src_id, dst_id, edge);
+
+        self.emit(StorageEvent::EdgeAdded {
+            src_id,
+            dst_id,
+            edge: self.storage.edge_between_unchecked(src_id, dst_id, edge_id),
+        });
+
+        edge_id
+    }
+
+    fn update_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge_id: usize, edge: E) {
+        self.emit(StorageEvent::EdgeRemoved {
+            src_id,
+            dst_id,
+            edge: self.storage.edge_between_unchecked(src_id, dst_id, edge_id),
+        });
+
+        self.storage
+            .update_edge_unchecked(src_id, dst_id, edge_id, edge);
+
+        self.emit(StorageEvent::EdgeAdded {
+            src_id,
+            dst_id,
+            edge: self.storage.edge_between_unchecked(src_id, dst_id, edge_id),
+        });
+    }
+
+    fn remove_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge_id: usize) -> E {
+        let edge = self.storage.remove_edge_unchecked(src_id, dst_id, edge_id);
+
+        self.emit(StorageEvent::EdgeRemoved {
+            src_id,
+            dst_id,
+            edge: &edge,
+        });
+
+        edge
+    }
+
+    fn contains_edge(&self, edge_id: usize) -> bool {
+        self.storage.contains_edge(edge_id)
+    }
+
+    fn vertex_count(&self) -> usize {
+        self.storage.vertex_count()
+    }
+
+    fn vertices(&self) -> Vec<usize> {
+        self.storage.vertices()
+    }
+
+    fn edges_between_unchecked(&self, src_id: usize, dst_id: usize) -> Vec<&E> {
+        self.storage.edges_between_unchecked(src_id, dst_id)
+    }
+
+    fn edge_between_unchecked(&self, src_id: usize, dst_id: usize, edge_id: usize) -> &E {
+        self.storage.edge_between_unchecked(src_id, dst_id, edge_id)
+    }
+
+    fn edge_unchecked(&self, edge_id: usize) -> &E {
+        self.storage.edge_unchecked(edge_id)
+    }
+
+    fn has_any_edge_unchecked(&self, src_id: usize, dst_id: usize) -> bool {
+        self.storage.has_any_edge_unchecked(src_id, dst_id)
+    }
+
+    fn edges(&self) -> Vec<(usize, usize, &E)> {
+        self.storage.edges()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.storage.edge_count()
+    }
+
+    fn as_directed_edges(&self) -> Vec<(usize, usize, &E)> {
+        self.storage.as_directed_edges()
+    }
+
+    fn edges_from_unchecked(&self, src_id: usize) -> Vec<(usize, &E)> {
+        self.storage.edges_from_unchecked(src_id)
+    }
+
+    fn edges_from_iter(&self, src_id: usize) -> Self::EdgesFromIter<'_> {
+        self.storage.edges_from_iter(src_id)
+    }
+
+    fn neighbors_iter(&self, src_id: usize) -> Self::NeighborsIter<'_> {
+        self.storage.neighbors_iter(src_id)
+    }
+
+    fn neighbors_unchecked(&self, src_id: usize) -> Vec<usize> {
+        self.storage.neighbors_unchecked(src_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::DynamicSSSP;
+    use crate::graph::{DefaultEdge, SimpleGraph};
+    use crate::misc::rng::Rng;
+    use crate::provide::{Edges, Graph, Vertices};
+    use crate::storage::{DiMat, List};
+
+    // Records events as (kind, src_id, dst_id), where vertex events use the vertex id as both end points.
+    #[derive(Default)]
+    struct Recorder {
+        events: Vec<(&'static str, usize, usize)>,
+    }
+
+    impl GraphObserver<usize, DefaultEdge<usize>> for Recorder {
+        fn notify(&mut self, event: &StorageEvent<DefaultEdge<usize>>) {
+            self.events.push(match event {
+                StorageEvent::VertexAdded(id) => ("+v", *id, *id),
+                StorageEvent::VertexRemoved(id) => ("-v", *id, *id),
+                StorageEvent::EdgeAdded { src_id, dst_id, .. } => ("+e", *src_id, *dst_id),
+                StorageEvent::EdgeRemoved { src_id, dst_id, .. } => ("-e", *src_id, *dst_id),
+            })
+        }
+    }
+
+    #[test]
+    fn vertex_removal_reports_incident_edges() {
+        // Given: Directed storage
+        //
+        //      a  -->  b  -->  c
+        //      ^       ↺       |
+        //      '---------------'
+        //
+        let recorder = Rc::new(RefCell::new(Recorder::default()));
+        let mut storage = ObservedStorage::init(DiMat::<usize>::init());
+        storage.subscribe(recorder.clone());
+        let a = storage.add_vertex();
+        let b = storage.add_vertex();
+        let c = storage.add_vertex();
+        storage.add_edge_unchecked(a, b, 1.into());
+        storage.add_edge_unchecked(b, c, 1.into());
+        storage.add_edge_unchecked(b, b, 1.into());
+        storage.add_edge_unchecked(c, a, 1.into());
+        recorder.borrow_mut().events.clear();
+
+        // When:
+        storage.remove_vertex_unchecked(b);
+
+        // Then: Each edge of b is reported once, before b itself.
+        let mut events = recorder.borrow().events.clone();
+        assert_eq!(events.pop(), Some(("-v", b, b)));
+        events.sort_unstable();
+        assert_eq!(events, vec![("-e", a, b), ("-e", b, b), ("-e", b, c)]);
+    }
+
+    #[test]
+    fn updates_and_bulk_operations_are_reported() {
+        // Given: Undirected storage with a loop.
+        let recorder = Rc::new(RefCell::new(Recorder::default()));
+        let mut storage = ObservedStorage::init(List::<usize>::init());
+        storage.subscribe(recorder.clone());
+        let vertices = storage.extend_with_vertices(3);
+        let ids = storage.extend_with_edges_unchecked(vec![
+            (vertices[0], vertices[1], 1.into()),
+            (vertices[1], vertices[1], 2.into()),
+        ]);
+
+        // When:
+        storage.update_edge_unchecked(vertices[0], vertices[1], ids[0], 5.into());
+        storage.retain_edges(|src_id, dst_id, _| src_id != dst_id);
+
+        // Then:
+        assert_eq!(
+            recorder.borrow().events[3..],
+            [
+                ("+e", vertices[0], vertices[1]),
+                ("+e", vertices[1], vertices[1]),
+                ("-e", vertices[0], vertices[1]),
+                ("+e", vertices[0], vertices[1]),
+                ("-e", vertices[1], vertices[1]),
+            ]
+        );
+        assert_eq!(storage.edge_count(), 1);
+        assert_eq!(
+            *storage.storage().edges_from_unchecked(vertices[1])[0]
+                .1
+                .get_weight(),
+            5.into()
+        );
+    }
+
+    #[test]
+    fn dynamic_sssp_follows_the_graph() {
+        let mut rng = Rng::seed_from(3102);
+
+        // Given: Graph with a subscribed DynamicSSSP.
+        let mut graph = SimpleGraph::init(ObservedStorage::init(DiMat::<usize>::init()));
+        let vertices: Vec<usize> = (0..15).map(|_| graph.add_vertex()).collect();
+        let sssp = Rc::new(RefCell::new(DynamicSSSP::init(&graph, vertices[0])));
+        graph.subscribe(sssp.clone());
+
+        for _ in 0..200 {
+            // When: Randomly adding edges and removing vertices.
+            let src_id = vertices[rng.next_below(vertices.len())];
+            let dst_id = vertices[rng.next_below(vertices.len())];
+            if !graph.contains_vertex(src_id) || !graph.contains_vertex(dst_id) {
+                continue;
+            }
+            if src_id != dst_id && !graph.has_any_edge_unchecked(src_id, dst_id) {
+                graph.add_edge_unchecked(src_id, dst_id, (rng.next_below(10) + 1).into());
+            }
+            if rng.next_bool(0.05) && src_id != vertices[0] {
+                graph.remove_vertex_unchecked(src_id);
+            }
+
+            // Then: Distances match a fresh computation.
+            let fresh = DynamicSSSP::init(&graph, vertices[0]);
+            for vertex_id in graph.vertices() {
+                let (distance, expected) = (
+                    sssp.borrow().distance_to(vertex_id),
+                    fresh.distance_to(vertex_id),
+                );
+                assert_eq!(distance.is_finite(), expected.is_finite());
+                if expected.is_finite() {
+                    assert_eq!(distance, expected);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,375 @@
+use std::marker::PhantomData;
+
+use anyhow::Result;
+
+use crate::graph::{Edge, EdgeDir};
+use crate::storage::{Error, GraphStorage};
+
+/// Whether a [`SelfLoopGuard`](crate::storage::SelfLoopGuard) accepts edges from a vertex to itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelfLoopPolicy {
+    Allow,
+    Reject,
+}
+
+/// A storage that decides whether self loops can be added to the storage it wraps.
+///
+/// Algorithms like bipartite checks and matrix exports treat loops differently, so rejecting them when the storage is built
+/// is simpler than handling them later.
+///
+/// With [`Reject`](crate::storage::SelfLoopPolicy::Reject) policy, checked functions like [`add_edge`](crate::storage::GraphStorage::add_edge)
+/// return a [`SelfLoop`](crate::storage::ErrorKind::SelfLoop) error for loops, and unchecked functions panic.
+///
+/// ## Generic Parameters
+/// * `W`: **W**eight type associated with edges.
+/// * `E`: **E**dge type that storage uses.
+/// * `Dir`: **Dir**ection of edges: [`Directed`](crate::graph::DirectedEdge) or [`Undirected`](crate::graph::UndirectedEdge).
+/// * `S`: **S**torage that holds the vertices and edges.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::{List, SelfLoopGuard, SelfLoopPolicy};
+///
+/// // Given: Storage that rejects self loops.
+/// let mut storage = SelfLoopGuard::init(List::<usize>::init(), SelfLoopPolicy::Reject);
+/// let a = storage.add_vertex();
+/// let b = storage.add_vertex();
+///
+/// // When:
+/// let loop_result = storage.add_edge(a, a, 1.into());
+/// let edge_result = storage.add_edge(a, b, 1.into());
+///
+/// // Then:
+/// assert!(loop_result.is_err());
+/// assert!(edge_result.is_ok());
+///
+/// // When: Allowing loops.
+/// storage.set_policy(SelfLoopPolicy::Allow).unwrap();
+/// storage.add_edge(b, b, 1.into()).unwrap();
+///
+/// // Then:
+/// assert!(storage.has_self_loop(b));
+/// assert!(storage.set_policy(SelfLoopPolicy::Reject).is_err());
+/// ```
+pub struct SelfLoopGuard<W, E: Edge<W>, Dir: EdgeDir, S> {
+    storage: S,
+
+    policy: SelfLoopPolicy,
+
+    phantom_w: PhantomData<W>,
+    phantom_e: PhantomData<E>,
+    phantom_dir: PhantomData<Dir>,
+}
+
+impl<W, E: Edge<W>, Dir: EdgeDir, S: GraphStorage<W, E, Dir>> SelfLoopGuard<W, E, Dir, S> {
+    /// # Arguments
+    /// * `storage`: Storage to hold the vertices and edges.
+    /// * `policy`: Whether self loops can be added.
+    ///
+    /// # Returns
+    /// Storage that wraps `storage`.
+    ///
+    /// # Panics
+    /// If `policy` is [`Reject`](crate::storage::SelfLoopPolicy::Reject) and `storage` already contains a self loop.
+    pub fn init(storage: S, policy: SelfLoopPolicy) -> Self {
+        let mut guard = SelfLoopGuard {
+            storage,
+
+            policy: SelfLoopPolicy::Allow,
+
+            phantom_w: PhantomData,
+            phantom_e: PhantomData,
+            phantom_dir: PhantomData,
+        };
+
+        guard
+            .set_policy(policy)
+            .expect("Storage already contains a self loop");
+
+        guard
+    }
+
+    /// # Returns
+    /// Whether self loops can be added.
+    pub fn policy(&self) -> SelfLoopPolicy {
+        self.policy
+    }
+
+    /// # Arguments
+    /// `policy`: Whether self loops can be added from now on.
+    ///
+    /// # Returns
+    /// * `Ok`: If the policy is changed.
+    /// * `Err`: [`SelfLoop`](crate::storage::ErrorKind::SelfLoop) if `policy` is [`Reject`](crate::storage::SelfLoopPolicy::Reject) and the storage already contains a self loop.
+    pub fn set_policy(&mut self, policy: SelfLoopPolicy) -> Result<()> {
+        if policy == SelfLoopPolicy::Reject {
+            if let Some((vertex_id, _)) = self.storage.self_loops().first() {
+                Err(Error::new_sl(*vertex_id))?
+            }
+        }
+
+        self.policy = policy;
+
+        Ok(())
+    }
+
+    /// # Returns
+    /// The wrapped storage.
+    pub fn storage(&self) -> &S {
+        &self.storage
+    }
+
+    /// # Returns
+    /// The wrapped storage.
+    pub fn into_storage(self) -> S {
+        self.storage
+    }
+
+    fn check_edge(&self, src_id: usize, dst_id: usize) -> Result<()> {
+        if !self.storage.contains_vertex(src_id) {
+            Err(Error::new_vnf(src_id))?
+        } else if !self.storage.contains_vertex(dst_id) {
+            Err(Error::new_vnf(dst_id))?
+        } else if src_id == dst_id && self.policy == SelfLoopPolicy::Reject {
+            Err(Error::new_sl(src_id))?
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// For documentation about each function checkout [`GraphStorage`](crate::storage::GraphStorage) trait and the storage you wrap.
+impl<W, E: Edge<W>, Dir: EdgeDir, S: GraphStorage<W, E, Dir>> GraphStorage<W, E, Dir>
+    for SelfLoopGuard<W, E, Dir, S>
+{
+    type EdgesFromIter<'a>
+        = S::EdgesFromIter<'a>
+    where
+        Self: 'a,
+        E: 'a;
+
+    type NeighborsIter<'a>
+        = S::NeighborsIter<'a>
+    where
+        Self: 'a;
+
+    fn add_vertex(&mut self) -> usize {
+        self.storage.add_vertex()
+    }
+
+    fn try_add_vertex(&mut self) -> Result<usize> {
+        self.storage.try_add_vertex()
+    }
+
+    fn remove_vertex_unchecked(&mut self, vertex_id: usize) {
+        self.storage.remove_vertex_unchecked(vertex_id)
+    }
+
+    fn retain_vertices<F>(&mut self, keep: F)
+    where
+        F: FnMut(usize) -> bool,
+    {
+        self.storage.retain_vertices(keep)
+    }
+
+    fn contains_vertex(&self, vertex_id: usize) -> bool {
+        self.storage.contains_vertex(vertex_id)
+    }
+
+    /// Adds `edge` from vertex with id `src_id`: to vertex with id: `dst_id`.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing unique id of the newly added edge.
+    /// * `Err`: [`VertexNotFound`](crate::storage::ErrorKind::VertexNotFound) if vertex with either id: `src_id` or `dst_id` does not exist.
+    /// * `Err`: [`SelfLoop`](crate::storage::ErrorKind::SelfLoop) if the edge is a self loop and the policy rejects them.
+    fn add_edge(&mut self, src_id: usize, dst_id: usize, edge: E) -> Result<usize> {
+        self.check_edge(src_id, dst_id)?;
+
+        Ok(self.storage.add_edge_unchecked(src_id, dst_id, edge))
+    }
+
+    /// Adds `edge` from vertex with id `src_id`: to vertex with id: `dst_id`.
+    ///
+    /// # Panics
+    /// If the edge is a self loop and the policy rejects them.
+    fn add_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge: E) -> usize {
+        assert!(
+            src_id != dst_id || self.policy == SelfLoopPolicy::Allow,
+            "Edge from vertex: {} to itself is a self loop",
+            src_id
+        );
+
+        self.storage.add_edge_unchecked(src_id, dst_id, edge)
+    }
+
+    /// Adds edges to the storage in one go.
+    ///
+    /// All edges are checked before adding any of them, so either all edges are added or none of them.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing unique ids of the newly added edges, in the same order as `edges`.
+    /// * `Err`: [`VertexNotFound`](crate::storage::ErrorKind::VertexNotFound) if any of the end points does not exist.
+    /// * `Err`: [`SelfLoop`](crate::storage::ErrorKind::SelfLoop) if any of the edges is a self loop and the policy rejects them.
+    fn extend_with_edges<I>(&mut self, edges: I) -> Result<Vec<usize>>
+    where
+        I: IntoIterator<Item = (usize, usize, E)>,
+    {
+        let edges: Vec<(usize, usize, E)> = edges.into_iter().collect();
+
+        for (src_id, dst_id, _) in &edges {
+            self.check_edge(*src_id, *dst_id)?;
+        }
+
+        Ok(self.storage.extend_with_edges_unchecked(edges))
+    }
+
+    fn update_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge_id: usize, edge: E) {
+        self.storage
+            .update_edge_unchecked(src_id, dst_id, edge_id, edge)
+    }
+
+    fn remove_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge_id: usize) -> E {
+        self.storage.remove_edge_unchecked(src_id, dst_id, edge_id)
+    }
+
+    fn retain_edges<F>(&mut self, keep: F)
+    where
+        F: FnMut(usize, usize, &E) -> bool,
+    {
+        self.storage.retain_edges(keep)
+    }
+
+    fn contains_edge(&self, edge_id: usize) -> bool {
+        self.storage.contains_edge(edge_id)
+    }
+
+    fn vertex_count(&self) -> usize {
+        self.storage.vertex_count()
+    }
+
+    fn vertices(&self) -> Vec<usize> {
+        self.storage.vertices()
+    }
+
+    fn edges_between_unchecked(&self, src_id: usize, dst_id: usize) -> Vec<&E> {
+        self.storage.edges_between_unchecked(src_id, dst_id)
+    }
+
+    fn edge_between_unchecked(&self, src_id: usize, dst_id: usize, edge_id: usize) -> &E {
+        self.storage.edge_between_unchecked(src_id, dst_id, edge_id)
+    }
+
+    fn edge_unchecked(&self, edge_id: usize) -> &E {
+        self.storage.edge_unchecked(edge_id)
+    }
+
+    fn has_any_edge_unchecked(&self, src_id: usize, dst_id: usize) -> bool {
+        self.storage.has_any_edge_unchecked(src_id, dst_id)
+    }
+
+    fn edges(&self) -> Vec<(usize, usize, &E)> {
+        self.storage.edges()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.storage.edge_count()
+    }
+
+    fn as_directed_edges(&self) -> Vec<(usize, usize, &E)> {
+        self.storage.as_directed_edges()
+    }
+
+    fn edges_from_unchecked(&self, src_id: usize) -> Vec<(usize, &E)> {
+        self.storage.edges_from_unchecked(src_id)
+    }
+
+    fn edges_from_iter(&self, src_id: usize) -> Self::EdgesFromIter<'_> {
+        self.storage.edges_from_iter(src_id)
+    }
+
+    fn neighbors_iter(&self, src_id: usize) -> Self::NeighborsIter<'_> {
+        self.storage.neighbors_iter(src_id)
+    }
+
+    fn neighbors_unchecked(&self, src_id: usize) -> Vec<usize> {
+        self.storage.neighbors_unchecked(src_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{DefaultEdge, MultiGraph};
+    use crate::provide::Edges;
+    use crate::storage::{DiMat, ErrorKind, List, Map};
+
+    #[test]
+    fn rejected_loops_are_not_added() {
+        // Given:
+        let mut storage = SelfLoopGuard::init(DiMat::<usize>::init(), SelfLoopPolicy::Reject);
+        let a = storage.add_vertex();
+        let b = storage.add_vertex();
+
+        // When:
+        let error = storage.add_edge(b, b, 1.into()).unwrap_err();
+        let batch = storage.extend_with_edges(vec![(a, b, 1.into()), (a, a, 1.into())]);
+
+        // Then:
+        assert!(matches!(
+            error.downcast_ref::<Error>().unwrap().kind(),
+            ErrorKind::SelfLoop
+        ));
+        assert!(batch.is_err());
+        assert_eq!(storage.edge_count(), 0);
+        assert_eq!(storage.policy(), SelfLoopPolicy::Reject);
+    }
+
+    #[test]
+    #[should_panic(expected = "Storage already contains a self loop")]
+    fn reject_existing_loops() {
+        // Given: Storage with a loop.
+        let mut storage = List::<usize>::init();
+        let a = storage.add_vertex();
+        storage.add_edge_unchecked(a, a, DefaultEdge::init(1.into()));
+
+        // When:
+        SelfLoopGuard::init(storage, SelfLoopPolicy::Reject);
+    }
+
+    #[test]
+    fn loops_are_reported_once() {
+        // Given: Undirected storage that keeps a loop twice in the edge list of its vertex.
+        //
+        //      a  ---  b
+        //      ⤷ ⤴
+        //
+        let mut storage = Map::<usize>::init();
+        let a = storage.add_vertex();
+        let b = storage.add_vertex();
+        let loop_id = storage.add_edge_unchecked(a, a, DefaultEdge::init(1.into()));
+        storage.add_edge_unchecked(a, b, DefaultEdge::init(1.into()));
+
+        // When:
+        let loop_ids: Vec<(usize, usize)> = storage
+            .self_loops()
+            .into_iter()
+            .map(|(vertex_id, edge)| (vertex_id, edge.get_id()))
+            .collect();
+
+        // Then:
+        assert_eq!(loop_ids, vec![(a, loop_id)]);
+        assert!(storage.has_self_loop(a));
+        assert!(!storage.has_self_loop(b));
+        assert!(!storage.has_self_loop(5));
+
+        // When: Asking the graph instead of the storage.
+        let graph = MultiGraph::init(storage);
+
+        // Then:
+        assert_eq!(graph.self_loops().len(), 1);
+        assert!(graph.has_self_loop(a));
+        assert!(!graph.has_self_loop(b));
+        assert!(!graph.has_self_loop(5));
+    }
+}